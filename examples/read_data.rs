@@ -67,12 +67,22 @@ fn read_only() -> OdsResult<()> {
                 spreadsheet_ods::Value::Currency(v, cur) => {
                     println!("({},{}) = currency {} {}", r, c, v, cur)
                 }
+                spreadsheet_ods::Value::NumberDecimal(v) => {
+                    println!("({},{}) = number {}", r, c, v)
+                }
+                spreadsheet_ods::Value::PercentageDecimal(v) => {
+                    println!("({},{}) = percent {}", r, c, v)
+                }
+                spreadsheet_ods::Value::CurrencyDecimal(v, cur) => {
+                    println!("({},{}) = currency {} {}", r, c, v, cur)
+                }
                 spreadsheet_ods::Value::Text(v) => println!("({},{}) = text {}", r, c, v),
                 spreadsheet_ods::Value::TextXml(v) => println!("({},{}) = xml {:?}", r, c, v),
                 spreadsheet_ods::Value::DateTime(v) => println!("({},{}) = date {}", r, c, v),
                 spreadsheet_ods::Value::TimeDuration(v) => {
                     println!("({},{}) = duration {}", r, c, v)
                 }
+                spreadsheet_ods::Value::Error(v) => println!("({},{}) = error {}", r, c, v),
             }
         }
     }
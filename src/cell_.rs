@@ -1,7 +1,7 @@
-use crate::draw::{Annotation, DrawFrame};
+use crate::draw::{Annotation, DrawFrame, DrawShape};
 use crate::validation::ValidationRef;
 use crate::value_::Value;
-use crate::CellStyleRef;
+use crate::{CellRange, CellStyleRef};
 use get_size::GetSize;
 use get_size_derive::GetSize;
 use std::fmt::{Display, Formatter};
@@ -108,6 +108,8 @@ pub(crate) struct CellDataExt {
     pub(crate) annotation: Option<Box<Annotation>>,
     // Draw
     pub(crate) draw_frames: Vec<DrawFrame>,
+    // Shapes
+    pub(crate) draw_shapes: Vec<DrawShape>,
 }
 
 impl Default for CellData {
@@ -164,6 +166,9 @@ impl CellData {
             if !extra.draw_frames.is_empty() {
                 return false;
             }
+            if !extra.draw_shapes.is_empty() {
+                return false;
+            }
             if !extra.matrix_span.is_empty() {
                 return false;
             }
@@ -187,6 +192,14 @@ impl CellData {
         }
     }
 
+    pub(crate) fn has_draw_shapes(&self) -> bool {
+        if let Some(extra) = &self.extra {
+            !extra.draw_shapes.is_empty()
+        } else {
+            false
+        }
+    }
+
     pub(crate) fn extra_mut(&mut self) -> &mut CellDataExt {
         if self.extra.is_none() {
             self.extra = Some(Box::default());
@@ -195,7 +208,7 @@ impl CellData {
     }
 
     pub(crate) fn cloned_cell_content(&self) -> CellContent {
-        let (validation_name, span, matrix_span, annotation, draw_frames) =
+        let (validation_name, span, matrix_span, annotation, draw_frames, draw_shapes) =
             if let Some(extra) = &self.extra {
                 (
                     extra.validation_name.clone(),
@@ -203,6 +216,7 @@ impl CellData {
                     extra.matrix_span,
                     extra.annotation.clone(),
                     extra.draw_frames.clone(),
+                    extra.draw_shapes.clone(),
                 )
             } else {
                 (
@@ -211,6 +225,7 @@ impl CellData {
                     Default::default(),
                     None,
                     Vec::new(),
+                    Vec::new(),
                 )
             };
 
@@ -224,11 +239,12 @@ impl CellData {
             matrix_span,
             annotation,
             draw_frames,
+            draw_shapes,
         }
     }
 
     pub(crate) fn into_cell_content(self) -> CellContent {
-        let (validation_name, span, matrix_span, annotation, draw_frames) =
+        let (validation_name, span, matrix_span, annotation, draw_frames, draw_shapes) =
             if let Some(extra) = self.extra {
                 (
                     extra.validation_name,
@@ -236,6 +252,7 @@ impl CellData {
                     extra.matrix_span,
                     extra.annotation,
                     extra.draw_frames,
+                    extra.draw_shapes,
                 )
             } else {
                 (
@@ -244,6 +261,7 @@ impl CellData {
                     Default::default(),
                     None,
                     Vec::new(),
+                    Vec::new(),
                 )
             };
 
@@ -257,11 +275,12 @@ impl CellData {
             matrix_span,
             annotation,
             draw_frames,
+            draw_shapes,
         }
     }
 
     pub(crate) fn cell_content_ref(&self) -> CellContentRef<'_> {
-        let (validation_name, span, matrix_span, annotation, draw_frames) =
+        let (validation_name, span, matrix_span, annotation, draw_frames, draw_shapes) =
             if let Some(extra) = &self.extra {
                 (
                     extra.validation_name.as_ref(),
@@ -269,9 +288,17 @@ impl CellData {
                     extra.matrix_span,
                     extra.annotation.as_ref(),
                     Some(&extra.draw_frames),
+                    Some(&extra.draw_shapes),
                 )
             } else {
-                (None, CellSpan::default(), CellSpan::default(), None, None)
+                (
+                    None,
+                    CellSpan::default(),
+                    CellSpan::default(),
+                    None,
+                    None,
+                    None,
+                )
             };
 
         CellContentRef {
@@ -284,10 +311,36 @@ impl CellData {
             matrix_span,
             annotation: annotation.map(|v| v.as_ref()),
             draw_frames,
+            draw_shapes,
         }
     }
 }
 
+/// An array formula and the range of cells that hold its results.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArrayFormula<'a> {
+    /// The cells with the results. The formula is stored with the first
+    /// cell of the range.
+    pub range: CellRange,
+    /// The formula.
+    pub formula: &'a str,
+}
+
+/// How the content of the covered cells is handled when cells are
+/// merged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeMode {
+    /// Keep the content of the first cell, the covered cells are
+    /// cleared.
+    KeepFirst,
+    /// The text of all cells is joined with a space and moved to the
+    /// first cell. The covered cells are cleared.
+    Concatenate,
+    /// The covered cells keep their content. It's hidden as long as the
+    /// cells are merged.
+    KeepCovered,
+}
+
 /// Holds references to the combined content of a cell.
 /// A temporary to hold the data when iterating over a sheet.
 #[derive(Debug, Clone, Copy)]
@@ -310,6 +363,8 @@ pub struct CellContentRef<'a> {
     pub annotation: Option<&'a Annotation>,
     /// Reference to draw-frames.
     pub draw_frames: Option<&'a Vec<DrawFrame>>,
+    /// Reference to draw-shapes.
+    pub draw_shapes: Option<&'a Vec<DrawShape>>,
 }
 
 impl<'a> CellContentRef<'a> {
@@ -379,6 +434,12 @@ impl<'a> CellContentRef<'a> {
         self.draw_frames
     }
 
+    /// Returns draw shapes.
+    #[inline]
+    pub fn draw_shapes(&self) -> Option<&'a Vec<DrawShape>> {
+        self.draw_shapes
+    }
+
     /// Creates a owned CellContent.
     pub fn to_owned(&self) -> CellContent {
         CellContent {
@@ -391,6 +452,7 @@ impl<'a> CellContentRef<'a> {
             matrix_span: self.matrix_span,
             annotation: self.annotation.map(|v| Box::new(v.clone())),
             draw_frames: self.draw_frames.cloned().unwrap_or_default(),
+            draw_shapes: self.draw_shapes.cloned().unwrap_or_default(),
         }
     }
 }
@@ -416,6 +478,8 @@ pub struct CellContent {
     pub annotation: Option<Box<Annotation>>,
     /// DrawFrames
     pub draw_frames: Vec<DrawFrame>,
+    /// DrawShapes
+    pub draw_shapes: Vec<DrawShape>,
 }
 
 impl CellContent {
@@ -445,6 +509,7 @@ impl CellContent {
             || !self.matrix_span.is_empty()
             || self.annotation.is_some()
             || !self.draw_frames.is_empty()
+            || !self.draw_shapes.is_empty()
         {
             Some(Box::new(CellDataExt {
                 validation_name: self.validation_name.take(),
@@ -452,6 +517,7 @@ impl CellContent {
                 matrix_span: self.matrix_span,
                 annotation: self.annotation.take(),
                 draw_frames: std::mem::take(&mut self.draw_frames),
+                draw_shapes: std::mem::take(&mut self.draw_shapes),
             }))
         } else {
             None
@@ -623,4 +689,16 @@ impl CellContent {
     pub fn draw_frames(&self) -> &Vec<DrawFrame> {
         &self.draw_frames
     }
+
+    /// Draw Shapes
+    #[inline]
+    pub fn set_draw_shapes(&mut self, draw_shapes: Vec<DrawShape>) {
+        self.draw_shapes = draw_shapes;
+    }
+
+    /// Draw Shapes
+    #[inline]
+    pub fn draw_shapes(&self) -> &Vec<DrawShape> {
+        &self.draw_shapes
+    }
 }
@@ -1,10 +1,15 @@
 //! Defines conditional expressions that are used for cell-validation and
 //! conditional styles via style-maps.
+use chrono::NaiveDate;
 use get_size::GetSize;
 use get_size_derive::GetSize;
+use rust_decimal::prelude::ToPrimitive;
+use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
 
-use crate::CellRange;
+use crate::refs::{parse_cellrange, parse_cellref};
+use crate::value_::Value as CellValue;
+use crate::{CellRange, OdsError};
 
 /// A value that is used in a comparison.
 #[derive(Clone, Debug)]
@@ -107,6 +112,28 @@ impl ValueCondition {
         Self { cond: str.into() }
     }
 
+    /// Evaluates the condition against a cell-value.
+    ///
+    /// Only value() compared with a literal can be evaluated.
+    pub(crate) fn eval_cell_content(&self, value: &CellValue) -> Option<bool> {
+        let (op, v) = parse_value_condition(self.cond.as_str())?;
+        ConditionExpr::Content(op, v).eval(value, &|_| None)
+    }
+
+    /// Typed form of the condition. A value-condition is always a
+    /// comparison of value() with an operand.
+    pub fn compare(&self) -> Result<(CompareOp, Operand), OdsError> {
+        parse_value_condition(self.cond.as_str())
+            .ok_or_else(|| OdsError::Parse("invalid value condition", Some(self.cond.clone())))
+    }
+
+    /// Compares the cell-content with an operand.
+    pub fn value_cmp(op: CompareOp, value: Operand) -> ValueCondition {
+        ValueCondition {
+            cond: format!("value(){}{}", op, value),
+        }
+    }
+
     /// Compares the cell-content with a value.
     pub fn value_eq<V: Into<Value>>(value: V) -> ValueCondition {
         let mut buf = String::new();
@@ -383,11 +410,647 @@ impl Condition {
         buf.push(')');
         Condition { cond: buf }
     }
+
+    /// Evaluates the condition against a cell-value.
+    ///
+    /// Only conditions that depend on the cell-content alone can be
+    /// evaluated. Returns None for anything else, e.g. is-true-formula().
+    pub(crate) fn eval_cell_content(&self, value: &CellValue) -> Option<bool> {
+        let expr = parse_condition(self.cond.as_str())?;
+        expr.eval(value, &|_| None)
+    }
+
+    /// Evaluates the condition of a validation against a cell-value.
+    ///
+    /// The values for a cell-content-is-in-list() with a cell-range are
+    /// found with lookup. Returns None if the condition can't be evaluated.
+    pub(crate) fn eval_validation(
+        &self,
+        value: &CellValue,
+        lookup: &dyn Fn(&CellRange) -> Option<Vec<CellValue>>,
+    ) -> Option<bool> {
+        let expr = parse_condition(self.cond.as_str())?;
+        expr.eval(value, lookup)
+    }
+
+    /// Typed form of the condition.
+    pub fn expr(&self) -> Result<ConditionExpr, OdsError> {
+        ConditionExpr::parse(self.cond.as_str())
+    }
+
+    /// Replaces the condition. A namespace prefix "of:" of the current
+    /// condition is kept.
+    pub fn set_expr(&mut self, expr: &ConditionExpr) {
+        if self.cond.trim_start().starts_with("of:") {
+            self.cond = format!("of:{}", expr);
+        } else {
+            self.cond = expr.to_string();
+        }
+    }
+}
+
+impl From<ConditionExpr> for Condition {
+    fn from(expr: ConditionExpr) -> Self {
+        Condition {
+            cond: expr.to_string(),
+        }
+    }
+}
+
+impl From<&ConditionExpr> for Condition {
+    fn from(expr: &ConditionExpr) -> Self {
+        Condition {
+            cond: expr.to_string(),
+        }
+    }
+}
+
+/// Typed form of a [Condition].
+///
+/// ```
+/// use spreadsheet_ods::condition::{CompareOp, Condition, ConditionExpr, ContentType, Operand};
+///
+/// let c = ConditionExpr::parse("of:cell-content-is-whole-number() and cell-content-is-between(1;10)").unwrap();
+/// assert_eq!(
+///     c,
+///     ConditionExpr::And(
+///         ContentType::WholeNumber,
+///         Box::new(ConditionExpr::ContentBetween(Operand::Number(1.0), Operand::Number(10.0)))
+///     )
+/// );
+///
+/// let c = Condition::from(ConditionExpr::Content(CompareOp::Ge, Operand::Number(5.0)));
+/// assert_eq!(c.to_string(), "cell-content()>=5");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConditionExpr {
+    /// cell-content() compared with a value.
+    Content(CompareOp, Operand),
+    /// cell-content-is-between(a, b)
+    ContentBetween(Operand, Operand),
+    /// cell-content-is-not-between(a, b)
+    ContentNotBetween(Operand, Operand),
+    /// cell-content-text-length() compared with a value.
+    TextLength(CompareOp, Operand),
+    /// cell-content-text-length-is-between(a, b)
+    TextLengthBetween(Operand, Operand),
+    /// cell-content-text-length-is-not-between(a, b)
+    TextLengthNotBetween(Operand, Operand),
+    /// cell-content-is-in-list() with a list of values.
+    InList(Vec<Operand>),
+    /// cell-content-is-in-list() with a cell-range.
+    InRange(CellRange),
+    /// A type check combined with a second condition.
+    /// For example cell-content-is-date() and cell-content()>0
+    And(ContentType, Box<ConditionExpr>),
+    /// is-true-formula(formula)
+    IsTrueFormula(String),
+}
+
+/// Type checks that are combined with another condition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentType {
+    /// cell-content-is-date()
+    Date,
+    /// cell-content-is-time()
+    Time,
+    /// cell-content-is-decimal-number()
+    DecimalNumber,
+    /// cell-content-is-whole-number()
+    WholeNumber,
+}
+
+/// Comparison operators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    /// =
+    Eq,
+    /// !=
+    Ne,
+    /// <
+    Lt,
+    /// >
+    Gt,
+    /// <=
+    Le,
+    /// >=
+    Ge,
+}
+
+/// Operand of a condition.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operand {
+    /// Numeric literal.
+    Number(f64),
+    /// String literal.
+    Text(String),
+    /// true or false.
+    Bool(bool),
+    /// Anything else is kept as formula. This can be a cell-reference,
+    /// a named range or a complete expression.
+    Formula(String),
+}
+
+impl Display for ConditionExpr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConditionExpr::Content(op, v) => write!(f, "cell-content(){}{}", op, v),
+            ConditionExpr::ContentBetween(a, b) => {
+                write!(f, "cell-content-is-between({}, {})", a, b)
+            }
+            ConditionExpr::ContentNotBetween(a, b) => {
+                write!(f, "cell-content-is-not-between({}, {})", a, b)
+            }
+            ConditionExpr::TextLength(op, v) => {
+                write!(f, "cell-content-text-length(){}{}", op, v)
+            }
+            ConditionExpr::TextLengthBetween(a, b) => {
+                write!(f, "cell-content-text-length-is-between({}, {})", a, b)
+            }
+            ConditionExpr::TextLengthNotBetween(a, b) => {
+                write!(f, "cell-content-text-length-is-not-between({}, {})", a, b)
+            }
+            ConditionExpr::InList(list) => {
+                write!(f, "cell-content-is-in-list(")?;
+                for (idx, v) in list.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ";")?;
+                    }
+                    write!(f, "{}", v)?;
+                }
+                write!(f, ")")
+            }
+            ConditionExpr::InRange(range) => {
+                write!(f, "cell-content-is-in-list({})", range.to_formula())
+            }
+            ConditionExpr::And(ctype, expr) => write!(f, "{} and {}", ctype, expr),
+            ConditionExpr::IsTrueFormula(formula) => write!(f, "is-true-formula({})", formula),
+        }
+    }
+}
+
+impl Display for ContentType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContentType::Date => write!(f, "cell-content-is-date()"),
+            ContentType::Time => write!(f, "cell-content-is-time()"),
+            ContentType::DecimalNumber => write!(f, "cell-content-is-decimal-number()"),
+            ContentType::WholeNumber => write!(f, "cell-content-is-whole-number()"),
+        }
+    }
+}
+
+impl Display for CompareOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompareOp::Eq => write!(f, "="),
+            CompareOp::Ne => write!(f, "!="),
+            CompareOp::Lt => write!(f, "<"),
+            CompareOp::Gt => write!(f, ">"),
+            CompareOp::Le => write!(f, "<="),
+            CompareOp::Ge => write!(f, ">="),
+        }
+    }
+}
+
+impl Display for Operand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operand::Number(v) => write!(f, "{}", v),
+            Operand::Text(v) => write!(f, "{}", quote(v)),
+            Operand::Bool(v) => write!(f, "{}", v),
+            Operand::Formula(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+impl From<f64> for Operand {
+    fn from(v: f64) -> Self {
+        Operand::Number(v)
+    }
+}
+
+impl From<i32> for Operand {
+    fn from(v: i32) -> Self {
+        Operand::Number(v as f64)
+    }
+}
+
+impl From<bool> for Operand {
+    fn from(v: bool) -> Self {
+        Operand::Bool(v)
+    }
+}
+
+impl From<&str> for Operand {
+    fn from(v: &str) -> Self {
+        Operand::Text(v.to_string())
+    }
+}
+
+impl From<String> for Operand {
+    fn from(v: String) -> Self {
+        Operand::Text(v)
+    }
+}
+
+impl CompareOp {
+    fn eval(&self, ord: Ordering) -> bool {
+        match self {
+            CompareOp::Eq => ord == Ordering::Equal,
+            CompareOp::Ne => ord != Ordering::Equal,
+            CompareOp::Lt => ord == Ordering::Less,
+            CompareOp::Gt => ord == Ordering::Greater,
+            CompareOp::Le => ord != Ordering::Greater,
+            CompareOp::Ge => ord != Ordering::Less,
+        }
+    }
+}
+
+impl ConditionExpr {
+    /// Parses a condition as it is used for table:condition and
+    /// style:condition. A leading namespace prefix "of:" is ignored.
+    pub fn parse(cond: &str) -> Result<ConditionExpr, OdsError> {
+        parse_condition(cond)
+            .ok_or_else(|| OdsError::Parse("invalid condition", Some(cond.to_string())))
+    }
+
+    /// Evaluates the condition against a cell-value.
+    ///
+    /// The values for a cell-range are found with lookup. Returns None
+    /// if any part of the condition needs a formula engine.
+    pub(crate) fn eval(
+        &self,
+        value: &CellValue,
+        lookup: &dyn Fn(&CellRange) -> Option<Vec<CellValue>>,
+    ) -> Option<bool> {
+        Some(match self {
+            ConditionExpr::Content(op, v) => op.eval(compare_value(value, v)?),
+            ConditionExpr::ContentBetween(a, b) => {
+                compare_value(value, a)? != Ordering::Less
+                    && compare_value(value, b)? != Ordering::Greater
+            }
+            ConditionExpr::ContentNotBetween(a, b) => {
+                compare_value(value, a)? == Ordering::Less
+                    || compare_value(value, b)? == Ordering::Greater
+            }
+            ConditionExpr::TextLength(op, v) => {
+                let len = CellValue::Number(text_length(value) as f64);
+                op.eval(compare_value(&len, v)?)
+            }
+            ConditionExpr::TextLengthBetween(a, b) => {
+                let len = CellValue::Number(text_length(value) as f64);
+                compare_value(&len, a)? != Ordering::Less
+                    && compare_value(&len, b)? != Ordering::Greater
+            }
+            ConditionExpr::TextLengthNotBetween(a, b) => {
+                let len = CellValue::Number(text_length(value) as f64);
+                compare_value(&len, a)? == Ordering::Less
+                    || compare_value(&len, b)? == Ordering::Greater
+            }
+            ConditionExpr::InList(list) => {
+                let mut found = false;
+                for v in list {
+                    found |= list_match(value, v)?;
+                }
+                found
+            }
+            ConditionExpr::InRange(range) => lookup(range)?.iter().any(|v| {
+                let op = match value_as_number(v) {
+                    _ if matches!(v, CellValue::Empty) => return false,
+                    Some(n) => Operand::Number(n),
+                    None => Operand::Text(v.as_cow_str_or("").to_string()),
+                };
+                list_match(value, &op) == Some(true)
+            }),
+            ConditionExpr::And(ctype, expr) => {
+                let number = match value {
+                    CellValue::Text(_) | CellValue::TextXml(_) | CellValue::Error(_) => None,
+                    _ => value_as_number(value),
+                };
+                let is_type = match (ctype, number) {
+                    (_, None) => false,
+                    (ContentType::WholeNumber, Some(n)) => n.fract() == 0.0,
+                    (ContentType::Date | ContentType::Time | ContentType::DecimalNumber, _) => true,
+                };
+                is_type && expr.eval(value, lookup)?
+            }
+            ConditionExpr::IsTrueFormula(_) => return None,
+        })
+    }
+}
+
+/// Length of the text of a value. Numbers count with their
+/// plain representation.
+fn text_length(value: &CellValue) -> usize {
+    match value {
+        CellValue::Empty => 0,
+        CellValue::Text(_) | CellValue::TextXml(_) | CellValue::Error(_) => {
+            value.as_cow_str_or("").chars().count()
+        }
+        CellValue::Boolean(v) => {
+            if *v {
+                4
+            } else {
+                5
+            }
+        }
+        _ => value_as_number(value)
+            .map(|v| v.to_string().chars().count())
+            .unwrap_or_default(),
+    }
+}
+
+/// Equality for list entries. Text that looks like a number matches the
+/// number, otherwise text is compared case-insensitive.
+fn list_match(value: &CellValue, op: &Operand) -> Option<bool> {
+    match (value_as_number(value), op) {
+        (Some(v), Operand::Text(s)) => Some(s.trim().parse::<f64>().ok() == Some(v)),
+        _ => Some(compare_value(value, op)? == Ordering::Equal),
+    }
+}
+
+/// Numeric view of a cell-value as used in formulas.
+/// Dates are counted as days since 30.12.1899.
+pub(crate) fn value_as_number(value: &CellValue) -> Option<f64> {
+    match value {
+        CellValue::Empty => Some(0f64),
+        CellValue::Boolean(v) => Some(if *v { 1f64 } else { 0f64 }),
+        CellValue::Number(v) => Some(*v),
+        CellValue::Percentage(v) => Some(*v),
+        CellValue::Currency(v, _) => Some(*v),
+        CellValue::NumberDecimal(v)
+        | CellValue::PercentageDecimal(v)
+        | CellValue::CurrencyDecimal(v, _) => v.to_f64(),
+        CellValue::DateTime(v) => {
+            let base = NaiveDate::from_ymd_opt(1899, 12, 30)
+                .expect("valid date")
+                .and_hms_opt(0, 0, 0)
+                .expect("valid time");
+            Some((*v - base).num_milliseconds() as f64 / 86_400_000f64)
+        }
+        CellValue::TimeDuration(v) => Some(v.num_milliseconds() as f64 / 86_400_000f64),
+        CellValue::Text(_) | CellValue::TextXml(_) | CellValue::Error(_) => None,
+    }
+}
+
+/// Compares like LibreOffice does: numbers are always less than text,
+/// text is compared case-insensitive. Formulas can't be compared.
+fn compare_value(value: &CellValue, op: &Operand) -> Option<Ordering> {
+    let n = match op {
+        Operand::Number(n) => Some(*n),
+        Operand::Bool(v) => Some(if *v { 1f64 } else { 0f64 }),
+        Operand::Text(_) => None,
+        Operand::Formula(_) => return None,
+    };
+    Some(match (value_as_number(value), n, op) {
+        (Some(v), Some(n), _) => v.partial_cmp(&n).unwrap_or(Ordering::Equal),
+        (Some(_), None, _) => Ordering::Less,
+        (None, Some(_), _) => Ordering::Greater,
+        (None, None, Operand::Text(s)) => {
+            let v = value.as_cow_str_or("");
+            v.to_lowercase().cmp(&s.to_lowercase())
+        }
+        (None, None, _) => return None,
+    })
+}
+
+// value() op operand
+fn parse_value_condition(cond: &str) -> Option<(CompareOp, Operand)> {
+    let rest = cond.trim().strip_prefix("value()")?;
+    let (op, rest) = parse_compare_op(rest.trim_start())?;
+    Some((op, parse_last_operand(rest)?))
+}
+
+fn parse_condition(cond: &str) -> Option<ConditionExpr> {
+    let cond = cond.trim();
+    let cond = cond.strip_prefix("of:").unwrap_or(cond);
+
+    for ctype in [
+        ContentType::Date,
+        ContentType::Time,
+        ContentType::DecimalNumber,
+        ContentType::WholeNumber,
+    ] {
+        if let Some(rest) = cond.strip_prefix(ctype.to_string().as_str()) {
+            let rest = rest.trim_start().strip_prefix("and")?;
+            if !rest.starts_with(char::is_whitespace) {
+                return None;
+            }
+            let expr = parse_condition(rest)?;
+            return Some(ConditionExpr::And(ctype, Box::new(expr)));
+        }
+    }
+
+    if let Some(rest) = cond.strip_prefix("cell-content()") {
+        let (op, rest) = parse_compare_op(rest.trim_start())?;
+        Some(ConditionExpr::Content(op, parse_last_operand(rest)?))
+    } else if let Some(rest) = cond.strip_prefix("cell-content-is-between") {
+        let (a, b) = parse_operand_pair(rest)?;
+        Some(ConditionExpr::ContentBetween(a, b))
+    } else if let Some(rest) = cond.strip_prefix("cell-content-is-not-between") {
+        let (a, b) = parse_operand_pair(rest)?;
+        Some(ConditionExpr::ContentNotBetween(a, b))
+    } else if let Some(rest) = cond.strip_prefix("cell-content-text-length()") {
+        let (op, rest) = parse_compare_op(rest.trim_start())?;
+        Some(ConditionExpr::TextLength(op, parse_last_operand(rest)?))
+    } else if let Some(rest) = cond.strip_prefix("cell-content-text-length-is-between") {
+        let (a, b) = parse_operand_pair(rest)?;
+        Some(ConditionExpr::TextLengthBetween(a, b))
+    } else if let Some(rest) = cond.strip_prefix("cell-content-text-length-is-not-between") {
+        let (a, b) = parse_operand_pair(rest)?;
+        Some(ConditionExpr::TextLengthNotBetween(a, b))
+    } else if let Some(rest) = cond.strip_prefix("cell-content-is-in-list") {
+        parse_list(rest)
+    } else if let Some(rest) = cond.strip_prefix("is-true-formula") {
+        let formula = rest.trim_start().strip_prefix('(')?;
+        let formula = formula.trim_end().strip_suffix(')')?;
+        Some(ConditionExpr::IsTrueFormula(formula.trim().to_string()))
+    } else {
+        None
+    }
+}
+
+// ("a";"b") or ([.A1:.A5])
+fn parse_list(s: &str) -> Option<ConditionExpr> {
+    let s = s.trim_start().strip_prefix('(')?.trim_start();
+    if let Some(range) = s
+        .strip_prefix('[')
+        .and_then(|v| v.trim_end().strip_suffix(')'))
+        .and_then(|v| v.trim_end().strip_suffix(']'))
+    {
+        if !range.contains(']') {
+            if let Some(range) = parse_list_range(range) {
+                return Some(ConditionExpr::InRange(range));
+            }
+        }
+    }
+
+    let mut list = Vec::new();
+    let mut s = s;
+    loop {
+        let (op, rest) = parse_operand(s)?;
+        list.push(op);
+        let rest = rest.trim_start();
+        if let Some(rest) = rest.strip_prefix(';').or_else(|| rest.strip_prefix(',')) {
+            s = rest;
+        } else {
+            let rest = rest.strip_prefix(')')?;
+            if !rest.trim().is_empty() {
+                return None;
+            }
+            break;
+        }
+    }
+    Some(ConditionExpr::InList(list))
+}
+
+// A single cell is a range too.
+fn parse_list_range(range: &str) -> Option<CellRange> {
+    match parse_cellrange(range) {
+        Ok(range) => Some(range),
+        Err(_) => {
+            let cell = parse_cellref(range).ok()?;
+            let mut range = CellRange::local(cell.row(), cell.col(), cell.row(), cell.col());
+            range.set_row_abs(cell.row_abs());
+            range.set_col_abs(cell.col_abs());
+            range.set_to_row_abs(cell.row_abs());
+            range.set_to_col_abs(cell.col_abs());
+            if let Some(table) = cell.table() {
+                range.set_table(table);
+            }
+            Some(range)
+        }
+    }
+}
+
+fn parse_compare_op(s: &str) -> Option<(CompareOp, &str)> {
+    if let Some(rest) = s.strip_prefix("!=") {
+        Some((CompareOp::Ne, rest))
+    } else if let Some(rest) = s.strip_prefix("<>") {
+        Some((CompareOp::Ne, rest))
+    } else if let Some(rest) = s.strip_prefix("<=") {
+        Some((CompareOp::Le, rest))
+    } else if let Some(rest) = s.strip_prefix(">=") {
+        Some((CompareOp::Ge, rest))
+    } else if let Some(rest) = s.strip_prefix('=') {
+        Some((CompareOp::Eq, rest))
+    } else if let Some(rest) = s.strip_prefix('<') {
+        Some((CompareOp::Lt, rest))
+    } else if let Some(rest) = s.strip_prefix('>') {
+        Some((CompareOp::Gt, rest))
+    } else {
+        None
+    }
+}
+
+// (a, b) or (a; b)
+fn parse_operand_pair(s: &str) -> Option<(Operand, Operand)> {
+    let s = s.trim_start().strip_prefix('(')?;
+    let (a, s) = parse_operand(s)?;
+    let s = s.trim_start();
+    let s = s.strip_prefix(',').or_else(|| s.strip_prefix(';'))?;
+    let (b, s) = parse_operand(s)?;
+    let s = s.trim_start().strip_prefix(')')?;
+    if !s.trim().is_empty() {
+        return None;
+    }
+    Some((a, b))
+}
+
+// Operand that ends the condition.
+fn parse_last_operand(s: &str) -> Option<Operand> {
+    let s = s.trim();
+    if s.is_empty() {
+        return None;
+    }
+    match parse_literal(s) {
+        Some((op, rest)) if rest.trim().is_empty() => Some(op),
+        _ => Some(Operand::Formula(s.to_string())),
+    }
+}
+
+// Operand in a parameter list. Anything that is not a plain literal
+// is taken as formula up to the next separator.
+fn parse_operand(s: &str) -> Option<(Operand, &str)> {
+    let s = s.trim_start();
+    if let Some((op, rest)) = parse_literal(s) {
+        if rest.trim_start().starts_with([',', ';', ')']) {
+            return Some((op, rest));
+        }
+    }
+    let end = find_separator(s)?;
+    let formula = s[..end].trim();
+    if formula.is_empty() {
+        return None;
+    }
+    Some((Operand::Formula(formula.to_string()), &s[end..]))
+}
+
+// Finds the first ',', ';' or ')' outside of parentheses, cell-references
+// and string literals.
+fn find_separator(s: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut in_ref = false;
+    for (idx, c) in s.char_indices() {
+        match c {
+            '"' if !in_ref => in_string = !in_string,
+            _ if in_string => {}
+            '[' => in_ref = true,
+            ']' => in_ref = false,
+            _ if in_ref => {}
+            '(' => depth += 1,
+            ')' if depth == 0 => return Some(idx),
+            ')' => depth -= 1,
+            ',' | ';' if depth == 0 => return Some(idx),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn parse_literal(s: &str) -> Option<(Operand, &str)> {
+    let s = s.trim_start();
+    if let Some(mut rest) = s.strip_prefix('"') {
+        let mut buf = String::new();
+        loop {
+            let idx = rest.find('"')?;
+            buf.push_str(&rest[..idx]);
+            rest = &rest[idx + 1..];
+            if let Some(r) = rest.strip_prefix('"') {
+                buf.push('"');
+                rest = r;
+            } else {
+                break;
+            }
+        }
+        Some((Operand::Text(buf), rest))
+    } else if s.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        let end = s
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(s.len());
+        let (word, rest) = s.split_at(end);
+        if word.eq_ignore_ascii_case("true") {
+            Some((Operand::Bool(true), rest))
+        } else if word.eq_ignore_ascii_case("false") {
+            Some((Operand::Bool(false), rest))
+        } else {
+            None
+        }
+    } else {
+        let end = s
+            .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e' | 'E')))
+            .unwrap_or(s.len());
+        let (num, rest) = s.split_at(end);
+        Some((Operand::Number(num.parse().ok()?), rest))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::condition::{Condition, ValueCondition};
+    use crate::value_::Value as CellValue;
     use crate::CellRange;
 
     #[test]
@@ -445,4 +1108,46 @@ mod tests {
         let c = Condition::is_true_formula("formula");
         assert_eq!(c.to_string(), "is-true-formula(formula)");
     }
+
+    #[test]
+    fn test_eval_cell_content() {
+        let c = Condition::content_eq(5);
+        assert_eq!(c.eval_cell_content(&CellValue::Number(5.0)), Some(true));
+        assert_eq!(c.eval_cell_content(&CellValue::Number(4.0)), Some(false));
+        assert_eq!(c.eval_cell_content(&"5".into()), Some(false));
+
+        let c = Condition::content_eq("BB");
+        assert_eq!(c.eval_cell_content(&"bb".into()), Some(true));
+        assert_eq!(c.eval_cell_content(&CellValue::Number(1.0)), Some(false));
+
+        let c = Condition::content_ge(2.5);
+        assert_eq!(c.eval_cell_content(&CellValue::Percentage(2.5)), Some(true));
+        assert_eq!(c.eval_cell_content(&CellValue::Empty), Some(false));
+
+        let c = Condition::new("of:cell-content-is-between(1;10)");
+        assert_eq!(c.eval_cell_content(&CellValue::Number(10.0)), Some(true));
+        assert_eq!(c.eval_cell_content(&CellValue::Number(11.0)), Some(false));
+
+        let c = Condition::new("cell-content-is-not-between(1, 10)");
+        assert_eq!(c.eval_cell_content(&CellValue::Number(0.0)), Some(true));
+
+        let c = Condition::new(r#"cell-content()="a""b""#);
+        assert_eq!(c.eval_cell_content(&r#"A"B"#.into()), Some(true));
+
+        let c = Condition::is_true_formula("formula");
+        assert_eq!(c.eval_cell_content(&CellValue::Empty), None);
+    }
+
+    #[test]
+    fn test_eval_value_condition() {
+        let c = ValueCondition::value_ge(0);
+        assert_eq!(c.eval_cell_content(&CellValue::Number(0.0)), Some(true));
+        assert_eq!(c.eval_cell_content(&CellValue::Number(-0.5)), Some(false));
+
+        let c = ValueCondition::new("value() < 10");
+        assert_eq!(c.eval_cell_content(&CellValue::Number(9.0)), Some(true));
+
+        let c = ValueCondition::new("something()");
+        assert_eq!(c.eval_cell_content(&CellValue::Number(9.0)), None);
+    }
 }
@@ -173,6 +173,52 @@ impl ConfigMap {
 
         &mut self.values.get_mut(idx).unwrap().1
     }
+
+    /// Returns a ConfigItem
+    pub(crate) fn get_mut<S>(&mut self, name: S) -> Option<&mut ConfigItem>
+    where
+        S: AsRef<str>,
+    {
+        let idx = self.key_index.get(name.as_ref());
+
+        if let Some(idx) = idx {
+            self.values.get_mut(*idx).map(|v| &mut v.1)
+        } else {
+            None
+        }
+    }
+
+    /// Removes a ConfigItem.
+    pub(crate) fn remove<S>(&mut self, name: S) -> Option<ConfigItem>
+    where
+        S: AsRef<str>,
+    {
+        let idx = self.key_index.remove(name.as_ref())?;
+        let (_, item) = self.values.remove(idx);
+        for v in self.key_index.values_mut() {
+            if *v > idx {
+                *v -= 1;
+            }
+        }
+        Some(item)
+    }
+
+    /// Changes the name of a ConfigItem. Keeps the position.
+    /// Replaces an existing item with the new name.
+    pub(crate) fn rename<S, T>(&mut self, name: S, new_name: T)
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        if name.as_ref() == new_name.as_ref() || !self.key_index.contains_key(name.as_ref()) {
+            return;
+        }
+        self.remove(new_name.as_ref());
+        if let Some(idx) = self.key_index.remove(name.as_ref()) {
+            self.values[idx].0 = new_name.as_ref().to_string();
+            self.key_index.insert(new_name.as_ref().to_string(), idx);
+        }
+    }
 }
 
 pub(crate) struct ConfigIter<'a> {
@@ -455,6 +501,49 @@ impl ConfigItem {
         }
     }
 
+    /// Recursive get for any ConfigItem.
+    pub(crate) fn get_rec_mut<S>(&mut self, names: &[S]) -> Option<&mut ConfigItem>
+    where
+        S: AsRef<str>,
+    {
+        if let Some((name, rest)) = names.split_first() {
+            self.as_map_mut()?.get_mut(name.as_ref())?.get_rec_mut(rest)
+        } else {
+            Some(self)
+        }
+    }
+
+    /// Removes an item from this map.
+    ///
+    /// Panics
+    /// If this is not a map-like ConfigItem.
+    pub(crate) fn remove<S>(&mut self, name: S) -> Option<ConfigItem>
+    where
+        S: AsRef<str>,
+    {
+        if let Some(m) = self.as_map_mut() {
+            m.remove(name)
+        } else {
+            panic!()
+        }
+    }
+
+    /// Renames an item in this map.
+    ///
+    /// Panics
+    /// If this is not a map-like ConfigItem.
+    pub(crate) fn rename<S, T>(&mut self, name: S, new_name: T)
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        if let Some(m) = self.as_map_mut() {
+            m.rename(name, new_name);
+        } else {
+            panic!()
+        }
+    }
+
     /// Recursive get for only the ConfigValue leaves.
     pub(crate) fn get_value_rec<S>(&self, names: &[S]) -> Option<&ConfigValue>
     where
@@ -530,6 +619,14 @@ impl Config {
         self.config.get_value_rec(names)
     }
 
+    /// Recursive get.
+    pub(crate) fn get_mut<S>(&mut self, names: &[S]) -> Option<&mut ConfigItem>
+    where
+        S: AsRef<str>,
+    {
+        self.config.get_rec_mut(names)
+    }
+
     pub(crate) fn create_path<S>(&mut self, names: &[(S, ConfigItemType)]) -> &mut ConfigItem
     where
         S: AsRef<str>,
@@ -628,4 +725,23 @@ mod tests {
             ("CodeName", ConfigItemType::Value), // here
         ]);
     }
+
+    #[test]
+    fn test_rename_remove() {
+        let mut map = ConfigMap::new();
+        map.insert("a", 1);
+        map.insert("b", 2);
+        map.insert("c", 3);
+
+        map.rename("b", "x");
+        assert_eq!(map.get("b"), None);
+        assert_eq!(map.get("x"), Some(&ConfigItem::from(2)));
+        let keys: Vec<_> = map.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(keys, vec!["a", "x", "c"]);
+
+        assert_eq!(map.remove("a"), Some(ConfigItem::from(1)));
+        assert_eq!(map.get("x"), Some(&ConfigItem::from(2)));
+        assert_eq!(map.get("c"), Some(&ConfigItem::from(3)));
+        assert_eq!(map.remove("a"), None);
+    }
 }
@@ -7,11 +7,15 @@ use crate::style::units::RelativeScale;
 use crate::style::{GraphicStyleRef, ParagraphStyleRef};
 use crate::text::{TextP, TextTag};
 use crate::xlink::{XLinkActuate, XLinkShow, XLinkType};
-use crate::{CellRef, Length, OdsError};
+use crate::xmltree::XmlTag;
+use crate::{CellRange, CellRef, Length, OdsError, WorkBook};
 use base64::Engine;
 use chrono::NaiveDateTime;
 use get_size::GetSize;
 use get_size_derive::GetSize;
+use std::borrow::Cow;
+use std::fmt;
+use std::fmt::{Display, Formatter};
 
 /// The <office:annotation> element specifies an OpenDocument annotation. The annotation's
 /// text is contained in <text:p> and <text:list> elements.
@@ -120,6 +124,11 @@ impl Annotation {
         &self.text
     }
 
+    /// Text.
+    pub fn text_mut(&mut self) -> &mut Vec<TextTag> {
+        &mut self.text
+    }
+
     /// Text.
     pub fn push_text(&mut self, text: TextTag) {
         self.text.push(text);
@@ -156,72 +165,6 @@ impl Annotation {
     xml_id!(attr);
 }
 
-// /// The <draw:rect> element represents a rectangular drawing shape.
-// #[derive(Debug, Clone)]
-// pub struct DrawRect {
-//     ///
-//     name: String,
-//     ///
-//     attr: AttrMap2,
-// }
-//
-// impl DrawRect {
-//     pub fn new_empty() -> Self {
-//         Self {
-//             name: "".to_string(),
-//             attr: Default::default(),
-//         }
-//     }
-//
-//     pub fn new<S: Into<String>>(name: S) -> Self {
-//         Self {
-//             name: name.into(),
-//             attr: Default::default(),
-//         }
-//     }
-//
-//     /// Allows access to all attributes of the style itself.
-//     pub fn attrmap(&self) -> &AttrMap2 {
-//         &self.attr
-//     }
-//
-//     /// Allows access to all attributes of the style itself.
-//     pub fn attrmap_mut(&mut self) -> &mut AttrMap2 {
-//         &mut self.attr
-//     }
-//
-//     /// Name
-//     pub fn name(&self) -> &str {
-//         &self.name
-//     }
-//
-//     /// Name
-//     pub fn set_name<S: Into<String>>(&mut self, name: S) {
-//         self.name = name.into();
-//     }
-//
-//     draw_caption_id!(attr);
-//     draw_class_names!(attr);
-//     draw_corner_radius!(attr);
-//     draw_id!(attr);
-//     draw_layer!(attr);
-//     draw_style_name!(attr);
-//     draw_text_style_name!(attr);
-//     draw_transform!(attr);
-//     draw_z_index!(attr);
-//     svg_height!(attr);
-//     svg_width!(attr);
-//     svg_rx!(attr);
-//     svg_ry!(attr);
-//     svg_x!(attr);
-//     svg_y!(attr);
-//     table_end_cell_address!(attr);
-//     table_end_x!(attr);
-//     table_end_y!(attr);
-//     table_table_background!(attr);
-//     xml_id!(attr);
-// }
-
 /// The <draw:frame> element represents a frame and serves as the container for elements that
 /// may occur in a frame.
 /// Frame formatting properties are stored in styles belonging to the graphic family.
@@ -243,6 +186,8 @@ pub struct DrawFrame {
 pub enum DrawFrameContent {
     /// Image
     Image(DrawImage),
+    /// Text box
+    TextBox(DrawTextBox),
 }
 
 impl DrawFrame {
@@ -251,6 +196,33 @@ impl DrawFrame {
         Default::default()
     }
 
+    /// Frame with a text box.
+    pub fn new_text_box(x: Length, y: Length, width: Length, height: Length) -> Self {
+        let mut frame = Self::new();
+        frame.svg_x(x);
+        frame.svg_y(y);
+        frame.set_width(width);
+        frame.set_height(height);
+        frame.push_content(DrawFrameContent::TextBox(DrawTextBox::new()));
+        frame
+    }
+
+    /// Returns the first text box of the frame.
+    pub fn text_box(&self) -> Option<&DrawTextBox> {
+        self.content.iter().find_map(|v| match v {
+            DrawFrameContent::TextBox(v) => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Returns the first text box of the frame.
+    pub fn text_box_mut(&mut self) -> Option<&mut DrawTextBox> {
+        self.content.iter_mut().find_map(|v| match v {
+            DrawFrameContent::TextBox(v) => Some(v),
+            _ => None,
+        })
+    }
+
     /// Allows access to all attributes of the style itself.
     pub fn attrmap(&self) -> &AttrMap2 {
         &self.attr
@@ -340,6 +312,390 @@ impl DrawFrame {
     xml_id!(attr);
 }
 
+/// The <draw:text-box> element represents a text box. It contains paragraphs,
+/// lists etc. and is the content of a <draw:frame>.
+#[derive(Debug, Clone, Default, GetSize)]
+pub struct DrawTextBox {
+    attr: AttrMap2,
+    text: Vec<TextTag>,
+}
+
+impl DrawTextBox {
+    /// New.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Allows access to all attributes.
+    pub fn attrmap(&self) -> &AttrMap2 {
+        &self.attr
+    }
+
+    /// Allows access to all attributes.
+    pub fn attrmap_mut(&mut self) -> &mut AttrMap2 {
+        &mut self.attr
+    }
+
+    /// Text
+    pub fn text(&self) -> &Vec<TextTag> {
+        &self.text
+    }
+
+    /// Text
+    pub fn push_text(&mut self, text: TextTag) {
+        self.text.push(text);
+    }
+
+    /// Text
+    pub fn push_text_str<S: Into<String>>(&mut self, text: S) {
+        self.text.push(TextP::new().text(text).into_xmltag());
+    }
+
+    /// Text
+    pub fn set_text(&mut self, text: Vec<TextTag>) {
+        self.text = text;
+    }
+
+    xml_id!(attr);
+}
+
+/// Kind of drawing shape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GetSize)]
+pub enum DrawShapeKind {
+    /// <draw:rect>
+    Rect,
+    /// <draw:ellipse>
+    Ellipse,
+    /// <draw:line>
+    Line,
+    /// <draw:connector>
+    Connector,
+    /// <draw:polygon>
+    Polygon,
+    /// <draw:custom-shape>
+    CustomShape,
+    /// <draw:control> places a form control.
+    Control,
+}
+
+impl DrawShapeKind {
+    /// Element name.
+    pub fn tag(&self) -> &'static str {
+        match self {
+            DrawShapeKind::Rect => "draw:rect",
+            DrawShapeKind::Ellipse => "draw:ellipse",
+            DrawShapeKind::Line => "draw:line",
+            DrawShapeKind::Connector => "draw:connector",
+            DrawShapeKind::Polygon => "draw:polygon",
+            DrawShapeKind::CustomShape => "draw:custom-shape",
+            DrawShapeKind::Control => "draw:control",
+        }
+    }
+
+    /// Kind from the element name.
+    pub fn from_tag(tag: &[u8]) -> Option<Self> {
+        match tag {
+            b"draw:rect" => Some(DrawShapeKind::Rect),
+            b"draw:ellipse" => Some(DrawShapeKind::Ellipse),
+            b"draw:line" => Some(DrawShapeKind::Line),
+            b"draw:connector" => Some(DrawShapeKind::Connector),
+            b"draw:polygon" => Some(DrawShapeKind::Polygon),
+            b"draw:custom-shape" => Some(DrawShapeKind::CustomShape),
+            b"draw:control" => Some(DrawShapeKind::Control),
+            _ => None,
+        }
+    }
+}
+
+/// Routing of a connector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GetSize)]
+#[allow(missing_docs)]
+pub enum ConnectorType {
+    Standard,
+    Lines,
+    Line,
+    Curve,
+}
+
+impl Display for ConnectorType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ConnectorType::Standard => write!(f, "standard"),
+            ConnectorType::Lines => write!(f, "lines"),
+            ConnectorType::Line => write!(f, "line"),
+            ConnectorType::Curve => write!(f, "curve"),
+        }
+    }
+}
+
+/// A drawing shape: <draw:rect>, <draw:ellipse>, <draw:line>, <draw:connector>,
+/// <draw:polygon>, <draw:custom-shape> or <draw:control>.
+///
+/// The shape is formatted with a graphic style set via set_draw_style_name().
+/// It can be anchored to a cell with Sheet::add_draw_shape() or to the
+/// page with Sheet::add_page_shape().
+///
+/// Shapes can contain text. The geometry of a custom-shape is given by a
+/// <draw:enhanced-geometry> element, which is kept as XmlTag.
+#[derive(Debug, Clone, GetSize)]
+pub struct DrawShape {
+    kind: DrawShapeKind,
+    /// The <svg:title> element specifies a name for a graphic object.
+    title: Option<String>,
+    /// The <svg:desc> element specifies a prose description of a graphic object.
+    desc: Option<String>,
+    attr: AttrMap2,
+    text: Vec<TextTag>,
+    /// draw:enhanced-geometry and other child elements.
+    extra: Vec<XmlTag>,
+}
+
+impl DrawShape {
+    /// New shape without any geometry.
+    pub fn new(kind: DrawShapeKind) -> Self {
+        Self {
+            kind,
+            title: None,
+            desc: None,
+            attr: Default::default(),
+            text: Default::default(),
+            extra: Default::default(),
+        }
+    }
+
+    /// Rectangle.
+    pub fn new_rect(x: Length, y: Length, width: Length, height: Length) -> Self {
+        let mut shape = Self::new(DrawShapeKind::Rect);
+        shape.set_bounds(x, y, width, height);
+        shape
+    }
+
+    /// Ellipse.
+    pub fn new_ellipse(x: Length, y: Length, width: Length, height: Length) -> Self {
+        let mut shape = Self::new(DrawShapeKind::Ellipse);
+        shape.set_bounds(x, y, width, height);
+        shape
+    }
+
+    /// Line from (x1,y1) to (x2,y2).
+    pub fn new_line(x1: Length, y1: Length, x2: Length, y2: Length) -> Self {
+        let mut shape = Self::new(DrawShapeKind::Line);
+        shape.set_x1(x1);
+        shape.set_y1(y1);
+        shape.set_x2(x2);
+        shape.set_y2(y2);
+        shape
+    }
+
+    /// Connector from (x1,y1) to (x2,y2). Use set_draw_start_shape() and
+    /// set_draw_end_shape() to attach it to other shapes.
+    pub fn new_connector(x1: Length, y1: Length, x2: Length, y2: Length) -> Self {
+        let mut shape = Self::new(DrawShapeKind::Connector);
+        shape.set_x1(x1);
+        shape.set_y1(y1);
+        shape.set_x2(x2);
+        shape.set_y2(y2);
+        shape
+    }
+
+    /// Polygon. The points are given in a coordinate system that is mapped
+    /// to the bounding box. The viewBox spans the range of all points.
+    pub fn new_polygon(
+        x: Length,
+        y: Length,
+        width: Length,
+        height: Length,
+        points: &[(i64, i64)],
+    ) -> Self {
+        let mut shape = Self::new(DrawShapeKind::Polygon);
+        shape.set_bounds(x, y, width, height);
+        let min_x = points.iter().map(|v| v.0).min().unwrap_or_default();
+        let min_y = points.iter().map(|v| v.1).min().unwrap_or_default();
+        let max_x = points.iter().map(|v| v.0).max().unwrap_or_default();
+        let max_y = points.iter().map(|v| v.1).max().unwrap_or_default();
+        shape.set_view_box(
+            min_x,
+            min_y,
+            max_x.saturating_sub(min_x).max(1),
+            max_y.saturating_sub(min_y).max(1),
+        );
+        shape.set_draw_points(points);
+        shape
+    }
+
+    /// Custom shape with one of the predefined geometries, eg "rectangle",
+    /// "ellipse", "right-arrow", "left-arrow", "up-arrow", "down-arrow",
+    /// "round-rectangular-callout", "rectangular-callout", "cloud-callout",
+    /// "star5".
+    pub fn new_custom_shape<S: Into<String>>(
+        x: Length,
+        y: Length,
+        width: Length,
+        height: Length,
+        shape_type: S,
+    ) -> Self {
+        let mut shape = Self::new(DrawShapeKind::CustomShape);
+        shape.set_bounds(x, y, width, height);
+        shape.set_enhanced_geometry(
+            XmlTag::new("draw:enhanced-geometry")
+                .attr("svg:viewBox", "0 0 21600 21600")
+                .attr("draw:type", shape_type),
+        );
+        shape
+    }
+
+    /// Places the form control with the given id. See crate::form.
+    pub fn new_control<S: Into<String>>(
+        x: Length,
+        y: Length,
+        width: Length,
+        height: Length,
+        control_id: S,
+    ) -> Self {
+        let mut shape = Self::new(DrawShapeKind::Control);
+        shape.set_bounds(x, y, width, height);
+        shape.set_draw_control(control_id);
+        shape
+    }
+
+    fn set_bounds(&mut self, x: Length, y: Length, width: Length, height: Length) {
+        self.svg_x(x);
+        self.svg_y(y);
+        self.set_width(width);
+        self.set_height(height);
+    }
+
+    /// Kind of shape.
+    pub fn kind(&self) -> DrawShapeKind {
+        self.kind
+    }
+
+    /// Allows access to all attributes.
+    pub fn attrmap(&self) -> &AttrMap2 {
+        &self.attr
+    }
+
+    /// Allows access to all attributes.
+    pub fn attrmap_mut(&mut self) -> &mut AttrMap2 {
+        &mut self.attr
+    }
+
+    /// Desc
+    pub fn desc(&self) -> Option<&String> {
+        self.desc.as_ref()
+    }
+
+    /// Desc
+    pub fn set_desc<S: Into<String>>(&mut self, desc: S) {
+        self.desc = Some(desc.into())
+    }
+
+    /// Desc
+    pub fn clear_desc(&mut self) {
+        self.desc = None;
+    }
+
+    /// Title
+    pub fn title(&self) -> Option<&String> {
+        self.title.as_ref()
+    }
+
+    /// Title
+    pub fn set_title<S: Into<String>>(&mut self, title: S) {
+        self.title = Some(title.into());
+    }
+
+    /// Title
+    pub fn clear_title(&mut self) {
+        self.title = None;
+    }
+
+    /// Text
+    pub fn text(&self) -> &Vec<TextTag> {
+        &self.text
+    }
+
+    /// Text
+    pub fn push_text(&mut self, text: TextTag) {
+        self.text.push(text);
+    }
+
+    /// Text
+    pub fn push_text_str<S: Into<String>>(&mut self, text: S) {
+        self.text.push(TextP::new().text(text).into_xmltag());
+    }
+
+    /// Text
+    pub fn set_text(&mut self, text: Vec<TextTag>) {
+        self.text = text;
+    }
+
+    /// The <draw:enhanced-geometry> of a custom-shape.
+    pub fn enhanced_geometry(&self) -> Option<&XmlTag> {
+        self.extra
+            .iter()
+            .find(|v| v.name() == "draw:enhanced-geometry")
+    }
+
+    /// The <draw:enhanced-geometry> of a custom-shape.
+    pub fn set_enhanced_geometry(&mut self, geometry: XmlTag) {
+        self.extra.retain(|v| v.name() != "draw:enhanced-geometry");
+        self.extra.push(geometry);
+    }
+
+    /// Other child elements, eg draw:enhanced-geometry or draw:glue-point.
+    pub fn extra(&self) -> &Vec<XmlTag> {
+        &self.extra
+    }
+
+    /// Other child elements, eg draw:enhanced-geometry or draw:glue-point.
+    pub fn push_extra(&mut self, tag: XmlTag) {
+        self.extra.push(tag);
+    }
+
+    draw_name!(attr);
+    draw_class_names!(attr);
+    draw_connector_type!(attr);
+    draw_control!(attr);
+    draw_end_shape!(attr);
+    draw_id!(attr);
+    draw_layer!(attr);
+    draw_points!(attr);
+    draw_start_shape!(attr);
+    draw_style_name!(attr);
+    draw_text_style_name!(attr);
+    draw_transform!(attr);
+    draw_z_index!(attr);
+    svg_height!(attr);
+    svg_rx!(attr);
+    svg_ry!(attr);
+    svg_view_box!(attr);
+    svg_width!(attr);
+    svg_x!(attr);
+    svg_x1!(attr);
+    svg_x2!(attr);
+    svg_y!(attr);
+    svg_y1!(attr);
+    svg_y2!(attr);
+    table_end_cell_address!(attr);
+    table_end_x!(attr);
+    table_end_y!(attr);
+    table_table_background!(attr);
+    xml_id!(attr);
+}
+
+/// Drawing anchored to the page. The order of the drawings is kept, it is
+/// the z-order when no draw:z-index is given.
+#[derive(Debug, Clone, GetSize)]
+pub enum PageDrawing {
+    /// Frame
+    Frame(DrawFrame),
+    /// Shape
+    Shape(DrawShape),
+    /// Any other element, kept as xml.
+    Xml(XmlTag),
+}
+
 /// The <draw:image> element represents an image. An image can be either:
 /// • A link to an external resource
 /// or
@@ -440,3 +796,491 @@ impl DrawImage {
     xlink_type!(attr);
     xml_id!(attr);
 }
+
+/// Options for [Sheet::insert_image](crate::Sheet::insert_image).
+///
+/// By default the image is inserted with its intrinsic size, as given by
+/// the pixel size and resolution of the image.
+#[derive(Debug, Clone, Default)]
+pub struct ImageOptions {
+    // fit the image into this range.
+    pub(crate) fit: Option<CellRange>,
+    // don't keep the aspect ratio when fitting.
+    pub(crate) stretch: bool,
+    // explicit size.
+    pub(crate) size: Option<(Length, Length)>,
+    // scale factor.
+    pub(crate) scale: Option<f64>,
+    // draw:name
+    name: Option<String>,
+    // svg:title
+    title: Option<String>,
+    // svg:desc
+    desc: Option<String>,
+    // draw:style-name
+    style: Option<GraphicStyleRef>,
+}
+
+impl ImageOptions {
+    /// Default options.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Fits the image into the given cell range. The size of the range
+    /// is calculated from the column widths and row heights of the sheet.
+    /// The aspect ratio of the image is kept, unless stretch() is set too.
+    pub fn fit(mut self, range: CellRange) -> Self {
+        self.fit = Some(range);
+        self
+    }
+
+    /// When fitting to a cell range, fill the complete range and
+    /// don't keep the aspect ratio.
+    pub fn stretch(mut self) -> Self {
+        self.stretch = true;
+        self
+    }
+
+    /// Explicit size of the frame. Overrides fit().
+    pub fn size(mut self, width: Length, height: Length) -> Self {
+        self.size = Some((width, height));
+        self
+    }
+
+    /// Scales the calculated size.
+    pub fn scale(mut self, scale: f64) -> Self {
+        self.scale = Some(scale);
+        self
+    }
+
+    /// Name of the frame.
+    pub fn name<S: Into<String>>(mut self, name: S) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Title of the frame.
+    pub fn title<S: Into<String>>(mut self, title: S) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Description of the frame. Used for accessibility.
+    pub fn desc<S: Into<String>>(mut self, desc: S) -> Self {
+        self.desc = Some(desc.into());
+        self
+    }
+
+    /// Graphic style for the frame.
+    pub fn style(mut self, style: &GraphicStyleRef) -> Self {
+        self.style = Some(style.clone());
+        self
+    }
+
+    /// Creates the frame for the image. The size must already be calculated.
+    pub(crate) fn create_frame(&self, image: DrawImage, width: f64, height: f64) -> DrawFrame {
+        let mut frame = DrawFrame::new();
+        if let Some(name) = &self.name {
+            frame.set_draw_name(name);
+        }
+        if let Some(title) = &self.title {
+            frame.set_title(title);
+        }
+        if let Some(desc) = &self.desc {
+            frame.set_desc(desc);
+        }
+        if let Some(style) = &self.style {
+            frame.set_draw_style_name(style.clone());
+        }
+        frame.set_width(Length::Pt(width));
+        frame.set_height(Length::Pt(height));
+        frame.svg_x(Length::Pt(0.0));
+        frame.svg_y(Length::Pt(0.0));
+        frame.push_content(DrawFrameContent::Image(image));
+        frame
+    }
+}
+
+/// An image embedded in a sheet, as returned by [WorkBook::images](crate::WorkBook::images).
+#[derive(Debug, Clone)]
+pub struct SheetImage<'a> {
+    book: &'a WorkBook,
+    sheet: usize,
+    anchor: CellRef,
+    frame: &'a DrawFrame,
+    image: &'a DrawImage,
+}
+
+impl<'a> SheetImage<'a> {
+    pub(crate) fn new(
+        book: &'a WorkBook,
+        sheet: usize,
+        anchor: CellRef,
+        frame: &'a DrawFrame,
+        image: &'a DrawImage,
+    ) -> Self {
+        Self {
+            book,
+            sheet,
+            anchor,
+            frame,
+            image,
+        }
+    }
+
+    /// Index of the sheet.
+    pub fn sheet(&self) -> usize {
+        self.sheet
+    }
+
+    /// Anchor cell, including the sheet name.
+    pub fn anchor(&self) -> &CellRef {
+        &self.anchor
+    }
+
+    /// The frame containing the image.
+    pub fn frame(&self) -> &'a DrawFrame {
+        self.frame
+    }
+
+    /// The image itself.
+    pub fn image(&self) -> &'a DrawImage {
+        self.image
+    }
+
+    /// Link to the image data, if the data is stored in the package or
+    /// is external.
+    pub fn href(&self) -> Option<&'a str> {
+        self.image.attrmap().attr("xlink:href")
+    }
+
+    /// Mime type of the image. Uses draw:mime-type or the media type of the
+    /// manifest entry.
+    pub fn mime_type(&self) -> Option<&'a str> {
+        if let Some(mime_type) = self.image.attrmap().attr("draw:mime-type") {
+            Some(mime_type)
+        } else if let Some(href) = self.href() {
+            self.book.manifest(href).map(|v| v.media_type.as_str())
+        } else {
+            None
+        }
+    }
+
+    /// Image data. Either embedded in the image or from the package.
+    pub fn bytes(&self) -> Result<Cow<'a, [u8]>, OdsError> {
+        if self.image.get_binary_base64().is_some() {
+            Ok(Cow::Owned(self.image.get_binary()?))
+        } else if let Some(href) = self.href() {
+            if let Some(buf) = self.book.manifest(href).and_then(|v| v.buffer.as_ref()) {
+                Ok(Cow::Borrowed(buf.as_slice()))
+            } else {
+                Err(OdsError::Ods(format!("No image data for {}", href)))
+            }
+        } else {
+            Err(OdsError::Ods("No image data".to_string()))
+        }
+    }
+}
+
+/// Intrinsic properties of image data.
+///
+/// Recognizes PNG, JPEG, GIF, SVG and WebP.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageInfo {
+    ext: &'static str,
+    mime_type: &'static str,
+    width: u32,
+    height: u32,
+    dpi: (f64, f64),
+}
+
+/// Resolution if the image has none.
+const DEFAULT_DPI: f64 = 96.0;
+
+impl ImageInfo {
+    /// Detects the image format and reads size and resolution.
+    /// Returns None if the format is not recognized.
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+            png_info(data)
+        } else if data.starts_with(b"\xFF\xD8") {
+            jpeg_info(data)
+        } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+            gif_info(data)
+        } else if data.len() >= 12 && &data[0..4] == b"RIFF" && &data[8..12] == b"WEBP" {
+            webp_info(data)
+        } else {
+            svg_info(data)
+        }
+    }
+
+    /// File extension.
+    pub fn ext(&self) -> &'static str {
+        self.ext
+    }
+
+    /// Mime type.
+    pub fn mime_type(&self) -> &'static str {
+        self.mime_type
+    }
+
+    /// Width in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Height in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Horizontal and vertical resolution in dots per inch.
+    /// Defaults to 96 dpi if the image contains no resolution.
+    pub fn dpi(&self) -> (f64, f64) {
+        self.dpi
+    }
+
+    /// Intrinsic size in pt.
+    pub fn size_pt(&self) -> (f64, f64) {
+        (
+            self.width as f64 * 72.0 / self.dpi.0,
+            self.height as f64 * 72.0 / self.dpi.1,
+        )
+    }
+
+    /// Default path of the image inside the package.
+    /// The name is derived from the image data.
+    pub(crate) fn package_path(&self, data: &[u8]) -> String {
+        // FNV-1a, stable across runs and platforms.
+        let mut hash = 0xcbf29ce484222325u64;
+        for b in data {
+            hash ^= *b as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+        format!("Pictures/{:016x}.{}", hash, self.ext)
+    }
+}
+
+fn be16(data: &[u8], pos: usize) -> Option<u32> {
+    let v = data.get(pos..pos + 2)?;
+    Some(u16::from_be_bytes([v[0], v[1]]) as u32)
+}
+
+fn be32(data: &[u8], pos: usize) -> Option<u32> {
+    let v = data.get(pos..pos + 4)?;
+    Some(u32::from_be_bytes([v[0], v[1], v[2], v[3]]))
+}
+
+fn le16(data: &[u8], pos: usize) -> Option<u32> {
+    let v = data.get(pos..pos + 2)?;
+    Some(u16::from_le_bytes([v[0], v[1]]) as u32)
+}
+
+fn le24(data: &[u8], pos: usize) -> Option<u32> {
+    let v = data.get(pos..pos + 3)?;
+    Some(u32::from_le_bytes([v[0], v[1], v[2], 0]))
+}
+
+fn png_info(data: &[u8]) -> Option<ImageInfo> {
+    if data.get(12..16)? != b"IHDR" {
+        return None;
+    }
+    let width = be32(data, 16)?;
+    let height = be32(data, 20)?;
+
+    // pHYs comes before the first IDAT.
+    let mut dpi = (DEFAULT_DPI, DEFAULT_DPI);
+    let mut pos = 8;
+    while let Some(len) = be32(data, pos) {
+        let ty = data.get(pos + 4..pos + 8)?;
+        if ty == b"pHYs" {
+            let x = be32(data, pos + 8)?;
+            let y = be32(data, pos + 12)?;
+            let unit = *data.get(pos + 16)?;
+            // pixel per meter
+            if unit == 1 && x > 0 && y > 0 {
+                dpi = (x as f64 * 0.0254, y as f64 * 0.0254);
+            }
+            break;
+        } else if ty == b"IDAT" {
+            break;
+        }
+        pos += 12 + len as usize;
+    }
+
+    Some(ImageInfo {
+        ext: "png",
+        mime_type: "image/png",
+        width,
+        height,
+        dpi,
+    })
+}
+
+fn jpeg_info(data: &[u8]) -> Option<ImageInfo> {
+    let mut dpi = (DEFAULT_DPI, DEFAULT_DPI);
+    let mut pos = 2;
+    loop {
+        if *data.get(pos)? != 0xFF {
+            return None;
+        }
+        // fill bytes
+        while *data.get(pos + 1)? == 0xFF {
+            pos += 1;
+        }
+        let marker = *data.get(pos + 1)?;
+        pos += 2;
+        // markers without length
+        if marker == 0x01 || (0xD0..=0xD8).contains(&marker) {
+            continue;
+        }
+        let len = be16(data, pos)? as usize;
+        let segment = data.get(pos + 2..pos + len)?;
+        match marker {
+            0xE0 if segment.starts_with(b"JFIF\0") => {
+                let unit = *segment.get(7)?;
+                let x = be16(segment, 8)? as f64;
+                let y = be16(segment, 10)? as f64;
+                if x > 0.0 && y > 0.0 {
+                    if unit == 1 {
+                        dpi = (x, y);
+                    } else if unit == 2 {
+                        dpi = (x * 2.54, y * 2.54);
+                    }
+                }
+            }
+            0xC0..=0xCF if marker != 0xC4 && marker != 0xC8 && marker != 0xCC => {
+                let height = be16(segment, 1)?;
+                let width = be16(segment, 3)?;
+                return Some(ImageInfo {
+                    ext: "jpg",
+                    mime_type: "image/jpeg",
+                    width,
+                    height,
+                    dpi,
+                });
+            }
+            // start of scan without frame header.
+            0xDA => return None,
+            _ => {}
+        }
+        pos += len;
+    }
+}
+
+fn gif_info(data: &[u8]) -> Option<ImageInfo> {
+    Some(ImageInfo {
+        ext: "gif",
+        mime_type: "image/gif",
+        width: le16(data, 6)?,
+        height: le16(data, 8)?,
+        dpi: (DEFAULT_DPI, DEFAULT_DPI),
+    })
+}
+
+fn webp_info(data: &[u8]) -> Option<ImageInfo> {
+    let (width, height) = match data.get(12..16)? {
+        b"VP8X" => (le24(data, 24)? + 1, le24(data, 27)? + 1),
+        b"VP8L" => {
+            if *data.get(20)? != 0x2F {
+                return None;
+            }
+            let v = data.get(21..25)?;
+            let bits = u32::from_le_bytes([v[0], v[1], v[2], v[3]]);
+            ((bits & 0x3FFF) + 1, ((bits >> 14) & 0x3FFF) + 1)
+        }
+        b"VP8 " => {
+            if data.get(23..26)? != b"\x9d\x01\x2a" {
+                return None;
+            }
+            (le16(data, 26)? & 0x3FFF, le16(data, 28)? & 0x3FFF)
+        }
+        _ => return None,
+    };
+    Some(ImageInfo {
+        ext: "webp",
+        mime_type: "image/webp",
+        width,
+        height,
+        dpi: (DEFAULT_DPI, DEFAULT_DPI),
+    })
+}
+
+fn svg_info(data: &[u8]) -> Option<ImageInfo> {
+    // the cut may split a char.
+    let text = String::from_utf8_lossy(&data[..data.len().min(4096)]);
+    let start = text.find("<svg")?;
+    let end = start + text[start..].find('>')?;
+    let tag = &text[start + 4..end];
+
+    let width = svg_attr(tag, "width").and_then(svg_length_px);
+    let height = svg_attr(tag, "height").and_then(svg_length_px);
+    let view_box = svg_attr(tag, "viewBox").and_then(|v| {
+        let v = v
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|v| !v.is_empty())
+            .map(|v| v.parse::<f64>().ok())
+            .collect::<Option<Vec<_>>>()?;
+        if v.len() == 4 {
+            Some((v[2], v[3]))
+        } else {
+            None
+        }
+    });
+
+    let (width, height) = match (width, height, view_box) {
+        (Some(w), Some(h), _) => (w, h),
+        (Some(w), None, Some((vw, vh))) if vw > 0.0 => (w, w * vh / vw),
+        (None, Some(h), Some((vw, vh))) if vh > 0.0 => (h * vw / vh, h),
+        (_, _, Some((vw, vh))) => (vw, vh),
+        _ => return None,
+    };
+
+    Some(ImageInfo {
+        ext: "svg",
+        mime_type: "image/svg+xml",
+        width: width.round() as u32,
+        height: height.round() as u32,
+        dpi: (DEFAULT_DPI, DEFAULT_DPI),
+    })
+}
+
+// Finds an attribute value in the attribute part of a tag.
+fn svg_attr<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = tag;
+    while let Some(idx) = rest.find(name) {
+        let before = rest[..idx].chars().next_back();
+        let after = rest[idx + name.len()..].trim_start();
+        if before.map(|c| c.is_whitespace()).unwrap_or(false) && after.starts_with('=') {
+            let value = after[1..].trim_start();
+            let quote = value.chars().next()?;
+            if quote == '"' || quote == '\'' {
+                let value = &value[1..];
+                return Some(&value[..value.find(quote)?]);
+            }
+        }
+        rest = &rest[idx + name.len()..];
+    }
+    None
+}
+
+// SVG lengths in px. Percentages can't be resolved.
+fn svg_length_px(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-' || c == '+' || c == 'e'))
+        .unwrap_or(value.len());
+    let (num, unit) = value.split_at(split);
+    let num = num.parse::<f64>().ok()?;
+    let factor = match unit.trim() {
+        "" | "px" => 1.0,
+        "pt" => 96.0 / 72.0,
+        "pc" => 16.0,
+        "in" => 96.0,
+        "cm" => 96.0 / 2.54,
+        "mm" => 96.0 / 25.4,
+        _ => return None,
+    };
+    Some(num * factor).filter(|v| v.is_finite())
+}
@@ -0,0 +1,345 @@
+//!
+//! Find and replace in cell values, formulas and annotations.
+//!
+//! ```
+//! use spreadsheet_ods::find::FindQuery;
+//! use spreadsheet_ods::{CellRef, Sheet, WorkBook};
+//!
+//! let mut wb = WorkBook::new_empty();
+//! let mut sheet = Sheet::new("one");
+//! sheet.set_value(0, 0, "Frobnicator 3000");
+//! sheet.set_value(1, 0, "the frobnicator");
+//! wb.push_sheet(sheet);
+//!
+//! let query = FindQuery::new("frobnicator");
+//! assert_eq!(
+//!     wb.find(&query).unwrap(),
+//!     vec![CellRef::remote("one", 0, 0), CellRef::remote("one", 1, 0)]
+//! );
+//! assert_eq!(wb.replace_all(&query, "Gizmo").unwrap(), 2);
+//! ```
+//!
+
+use crate::cell_::CellData;
+use crate::text::TextTag;
+use crate::xmltree::XmlContent;
+use crate::{CellRange, CellRef, OdsError, Sheet, Value};
+use regex::{NoExpand, Regex, RegexBuilder};
+use std::borrow::Cow;
+
+/// Defines what to search for and where.
+///
+/// By default only the cell values are searched, case-insensitive, and
+/// the text may occur anywhere in the cell.
+///
+/// Values of any type are matched by their plain representation:
+/// numbers as "1234.5", booleans as "TRUE"/"FALSE" and dates as
+/// "2024-12-31" or "2024-12-31 13:45:00". Only text values are
+/// changed by a replace.
+#[derive(Debug, Clone)]
+pub struct FindQuery {
+    pattern: String,
+    regex: bool,
+    match_case: bool,
+    whole_cell: bool,
+    values: bool,
+    formulas: bool,
+    annotations: bool,
+    range: Option<CellRange>,
+}
+
+impl FindQuery {
+    /// Searches for the text.
+    pub fn new<S: Into<String>>(text: S) -> Self {
+        Self {
+            pattern: text.into(),
+            regex: false,
+            match_case: false,
+            whole_cell: false,
+            values: true,
+            formulas: false,
+            annotations: false,
+            range: None,
+        }
+    }
+
+    /// Searches with a regular expression. In the replacement text
+    /// $1 or ${name} can be used to refer to capture groups.
+    pub fn new_regex<S: Into<String>>(pattern: S) -> Result<Self, OdsError> {
+        let pattern = pattern.into();
+        if let Err(e) = Regex::new(&pattern) {
+            return Err(OdsError::Parse("invalid regex", Some(e.to_string())));
+        }
+        Ok(Self {
+            regex: true,
+            ..Self::new(pattern)
+        })
+    }
+
+    /// Upper and lower case must match.
+    pub fn match_case(mut self, match_case: bool) -> Self {
+        self.match_case = match_case;
+        self
+    }
+
+    /// The text must match the whole cell content.
+    pub fn whole_cell(mut self, whole_cell: bool) -> Self {
+        self.whole_cell = whole_cell;
+        self
+    }
+
+    /// Search the cell values. This is the default.
+    pub fn in_values(mut self, values: bool) -> Self {
+        self.values = values;
+        self
+    }
+
+    /// Search the formulas.
+    pub fn in_formulas(mut self, formulas: bool) -> Self {
+        self.formulas = formulas;
+        self
+    }
+
+    /// Search the annotations.
+    pub fn in_annotations(mut self, annotations: bool) -> Self {
+        self.annotations = annotations;
+        self
+    }
+
+    /// Only search within this range. If the range has a table name
+    /// only this sheet is searched.
+    pub fn in_range(mut self, range: CellRange) -> Self {
+        self.range = Some(range);
+        self
+    }
+
+    pub(crate) fn matcher(&self) -> Result<Matcher<'_>, OdsError> {
+        let pattern = if self.regex {
+            Cow::Borrowed(self.pattern.as_str())
+        } else {
+            Cow::Owned(regex::escape(&self.pattern))
+        };
+        let pattern = if self.whole_cell {
+            Cow::Owned(format!("^(?:{})$", pattern))
+        } else {
+            pattern
+        };
+        match RegexBuilder::new(&pattern)
+            .case_insensitive(!self.match_case)
+            .build()
+        {
+            Ok(re) => Ok(Matcher { query: self, re }),
+            Err(e) => Err(OdsError::Parse("invalid regex", Some(e.to_string()))),
+        }
+    }
+}
+
+/// Compiled query.
+pub(crate) struct Matcher<'a> {
+    query: &'a FindQuery,
+    re: Regex,
+}
+
+impl Matcher<'_> {
+    fn in_range(&self, sheet: &Sheet, row: u32, col: u32) -> bool {
+        match &self.query.range {
+            Some(range) => {
+                range.table().map(|v| v == sheet.name()).unwrap_or(true) && range.contains(row, col)
+            }
+            None => true,
+        }
+    }
+
+    fn is_match(&self, cell: &CellData) -> bool {
+        if self.query.values {
+            if let Some(text) = value_text(&cell.value) {
+                if self.re.is_match(&text) {
+                    return true;
+                }
+            }
+        }
+        if self.query.formulas {
+            if let Some(formula) = &cell.formula {
+                if self.re.is_match(formula) {
+                    return true;
+                }
+            }
+        }
+        if self.query.annotations {
+            if let Some(annotation) = cell.extra.as_ref().and_then(|v| v.annotation.as_ref()) {
+                if self.re.is_match(&tags_text(annotation.text())) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    fn replace<'t>(&self, text: &'t str, replacement: &str) -> Cow<'t, str> {
+        if self.query.regex {
+            self.re.replace_all(text, replacement)
+        } else {
+            self.re.replace_all(text, NoExpand(replacement))
+        }
+    }
+
+    /// Replaces in a cell. Returns true if anything changed.
+    fn replace_cell(&self, cell: &mut CellData, replacement: &str) -> bool {
+        let mut changed = false;
+        if self.query.values {
+            match &mut cell.value {
+                Value::Text(text) => {
+                    if let Cow::Owned(v) = self.replace(text, replacement) {
+                        *text = v;
+                        changed = true;
+                    }
+                }
+                Value::TextXml(tags) => {
+                    changed |= self.replace_tags(tags, replacement);
+                }
+                _ => {}
+            }
+        }
+        if self.query.formulas {
+            if let Some(formula) = &mut cell.formula {
+                if let Cow::Owned(v) = self.replace(formula, replacement) {
+                    *formula = v;
+                    changed = true;
+                }
+            }
+        }
+        if self.query.annotations {
+            if let Some(annotation) = cell.extra.as_mut().and_then(|v| v.annotation.as_mut()) {
+                changed |= self.replace_tags(annotation.text_mut(), replacement);
+            }
+        }
+        changed
+    }
+
+    /// Replaces within the text of the tags, which keeps the formatting.
+    /// With whole_cell the result goes to the first text and the other
+    /// texts are removed.
+    fn replace_tags(&self, tags: &mut [TextTag], replacement: &str) -> bool {
+        if self.query.whole_cell {
+            let text = tags_text(tags);
+            if !self.re.is_match(&text) {
+                return false;
+            }
+            let mut new_text = Some(self.replace(&text, replacement).into_owned());
+            for tag in tags.iter_mut() {
+                set_texts(tag, &mut new_text);
+            }
+            true
+        } else {
+            let mut changed = false;
+            for tag in tags.iter_mut() {
+                changed |= self.replace_texts(tag, replacement);
+            }
+            changed
+        }
+    }
+
+    fn replace_texts(&self, tag: &mut TextTag, replacement: &str) -> bool {
+        let mut changed = false;
+        for content in tag.content_mut() {
+            match content {
+                XmlContent::Text(text) => {
+                    if let Cow::Owned(v) = self.replace(text, replacement) {
+                        *text = v;
+                        changed = true;
+                    }
+                }
+                XmlContent::Tag(tag) => {
+                    changed |= self.replace_texts(tag, replacement);
+                }
+            }
+        }
+        changed
+    }
+}
+
+/// Sets the first text to the new text and clears all others.
+fn set_texts(tag: &mut TextTag, new_text: &mut Option<String>) {
+    for content in tag.content_mut() {
+        match content {
+            XmlContent::Text(text) => {
+                *text = new_text.take().unwrap_or_default();
+            }
+            XmlContent::Tag(tag) => {
+                set_texts(tag, new_text);
+            }
+        }
+    }
+}
+
+/// Plain text of the tags. Paragraphs are separated by a newline.
+fn tags_text(tags: &[TextTag]) -> String {
+    let mut buf = String::new();
+    for (idx, tag) in tags.iter().enumerate() {
+        if idx > 0 {
+            buf.push('\n');
+        }
+        tag.extract_text(&mut buf);
+    }
+    buf
+}
+
+/// Text of a value as used for matching.
+pub(crate) fn value_text(value: &Value) -> Option<Cow<'_, str>> {
+    match value {
+        Value::Empty => None,
+        Value::Boolean(v) => Some(Cow::Borrowed(if *v { "TRUE" } else { "FALSE" })),
+        Value::Number(v) | Value::Percentage(v) | Value::Currency(v, _) => {
+            Some(Cow::Owned(v.to_string()))
+        }
+        Value::NumberDecimal(v) | Value::PercentageDecimal(v) | Value::CurrencyDecimal(v, _) => {
+            Some(Cow::Owned(v.to_string()))
+        }
+        Value::Text(v) => Some(Cow::Borrowed(v.as_str())),
+        Value::TextXml(v) => Some(Cow::Owned(tags_text(v))),
+        Value::DateTime(v) => {
+            if v.time() == Default::default() {
+                Some(Cow::Owned(v.format("%Y-%m-%d").to_string()))
+            } else {
+                Some(Cow::Owned(v.format("%Y-%m-%d %H:%M:%S").to_string()))
+            }
+        }
+        Value::Error(v) => Some(Cow::Borrowed(v.as_str())),
+        Value::TimeDuration(_) => None,
+    }
+}
+
+/// Finds all matching cells of the sheet.
+pub(crate) fn find_in_sheet(sheet: &Sheet, matcher: &Matcher<'_>) -> Vec<CellRef> {
+    sheet
+        .data
+        .iter()
+        .filter(|((row, col), cell)| matcher.in_range(sheet, *row, *col) && matcher.is_match(cell))
+        .map(|((row, col), _)| CellRef::remote(sheet.name(), *row, *col))
+        .collect()
+}
+
+/// Replaces in all matching cells of the sheet. Returns the number of
+/// changed cells.
+pub(crate) fn replace_in_sheet(
+    sheet: &mut Sheet,
+    matcher: &Matcher<'_>,
+    replacement: &str,
+) -> usize {
+    let cells = sheet
+        .data
+        .keys()
+        .filter(|(row, col)| matcher.in_range(sheet, *row, *col))
+        .copied()
+        .collect::<Vec<_>>();
+
+    let mut count = 0;
+    for key in cells {
+        if let Some(cell) = sheet.data.get_mut(&key) {
+            if matcher.replace_cell(cell, replacement) {
+                count += 1;
+            }
+        }
+    }
+    count
+}
@@ -0,0 +1,572 @@
+//!
+//! Form controls.
+//!
+//! The controls are defined in a Form that belongs to a sheet. They are
+//! placed on the sheet with a draw:control shape, that references the
+//! control by its id.
+//!
+//! ```
+//! use spreadsheet_ods::form::{FormControl};
+//! use spreadsheet_ods::{CellRange, CellRef, Length, Sheet};
+//!
+//! let mut sheet = Sheet::new("questions");
+//!
+//! let mut check = FormControl::new_checkbox("agree", "I agree");
+//! check.set_linked_cell(CellRef::remote("questions", 0, 1));
+//! sheet
+//!     .add_form_control(0, 0, check, Length::Cm(3.0), Length::Cm(0.6))
+//!     .unwrap();
+//!
+//! let mut list = FormControl::new_listbox("choice");
+//! list.set_linked_cell(CellRef::remote("questions", 1, 1));
+//! list.set_source_cell_range(CellRange::remote("questions", 10, 0, 14, 0));
+//! list.set_dropdown(true);
+//! sheet
+//!     .add_form_control(1, 0, list, Length::Cm(3.0), Length::Cm(0.6))
+//!     .unwrap();
+//! ```
+//!
+
+use crate::attrmap2::AttrMap2;
+use crate::refs::{parse_cellrange, parse_cellref};
+use crate::xmltree::XmlTag;
+use crate::{CellRange, CellRef, OdsError};
+use get_size::GetSize;
+use get_size_derive::GetSize;
+use std::fmt::{Display, Formatter};
+
+/// Kind of form control.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GetSize)]
+pub enum FormControlKind {
+    /// <form:button>
+    Button,
+    /// <form:checkbox>
+    Checkbox,
+    /// <form:radio>
+    Radio,
+    /// <form:listbox>
+    ListBox,
+    /// <form:combobox>
+    ComboBox,
+    /// <form:value-range> with a spin button implementation.
+    SpinButton,
+    /// <form:fixed-text>
+    Label,
+}
+
+impl FormControlKind {
+    /// Element name.
+    pub fn tag(&self) -> &'static str {
+        match self {
+            FormControlKind::Button => "form:button",
+            FormControlKind::Checkbox => "form:checkbox",
+            FormControlKind::Radio => "form:radio",
+            FormControlKind::ListBox => "form:listbox",
+            FormControlKind::ComboBox => "form:combobox",
+            FormControlKind::SpinButton => "form:value-range",
+            FormControlKind::Label => "form:fixed-text",
+        }
+    }
+
+    /// Value for form:control-implementation.
+    pub fn implementation(&self) -> &'static str {
+        match self {
+            FormControlKind::Button => "ooo:com.sun.star.form.component.CommandButton",
+            FormControlKind::Checkbox => "ooo:com.sun.star.form.component.CheckBox",
+            FormControlKind::Radio => "ooo:com.sun.star.form.component.RadioButton",
+            FormControlKind::ListBox => "ooo:com.sun.star.form.component.ListBox",
+            FormControlKind::ComboBox => "ooo:com.sun.star.form.component.ComboBox",
+            FormControlKind::SpinButton => "ooo:com.sun.star.form.component.SpinButton",
+            FormControlKind::Label => "ooo:com.sun.star.form.component.FixedText",
+        }
+    }
+
+    /// Kind from the element name. form:value-range is used for spin buttons
+    /// and scrollbars, the control-implementation decides.
+    pub fn from_tag(tag: &[u8], implementation: Option<&str>) -> Option<Self> {
+        match tag {
+            b"form:button" => Some(FormControlKind::Button),
+            b"form:checkbox" => Some(FormControlKind::Checkbox),
+            b"form:radio" => Some(FormControlKind::Radio),
+            b"form:listbox" => Some(FormControlKind::ListBox),
+            b"form:combobox" => Some(FormControlKind::ComboBox),
+            b"form:value-range"
+                if implementation
+                    .map(|v| v.ends_with("SpinButton"))
+                    .unwrap_or(false) =>
+            {
+                Some(FormControlKind::SpinButton)
+            }
+            b"form:fixed-text" => Some(FormControlKind::Label),
+            _ => None,
+        }
+    }
+}
+
+/// How the selection of a list box is written to the linked cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GetSize)]
+pub enum ListLinkageType {
+    /// The selected entry.
+    Selection,
+    /// The index of the selected entry.
+    SelectionIndices,
+}
+
+impl Display for ListLinkageType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ListLinkageType::Selection => write!(f, "selection"),
+            ListLinkageType::SelectionIndices => write!(f, "selection-indices"),
+        }
+    }
+}
+
+/// A form control.
+///
+/// The control is bound to a cell via set_linked_cell(). List boxes and
+/// combo boxes take their entries from a cell range via set_source_cell_range()
+/// or from a fixed list of items.
+#[derive(Debug, Clone, GetSize)]
+pub struct FormControl {
+    kind: FormControlKind,
+    attr: AttrMap2,
+    /// form:option or form:item
+    items: Vec<FormItem>,
+    /// form:properties, office:event-listeners
+    extra: Vec<XmlTag>,
+}
+
+impl FormControl {
+    /// New control. The id is used to reference the control from a
+    /// draw:control shape and is also used as name.
+    pub fn new<S: Into<String>>(kind: FormControlKind, id: S) -> Self {
+        let id = id.into();
+        let mut attr = AttrMap2::new();
+        attr.set_attr("form:name", id.clone());
+        attr.set_attr("form:control-implementation", kind.implementation());
+        attr.set_attr("xml:id", id.clone());
+        attr.set_attr("form:id", id);
+        Self {
+            kind,
+            attr,
+            items: Default::default(),
+            extra: Default::default(),
+        }
+    }
+
+    /// Empty control, used when reading.
+    pub(crate) fn new_empty(kind: FormControlKind) -> Self {
+        Self {
+            kind,
+            attr: Default::default(),
+            items: Default::default(),
+            extra: Default::default(),
+        }
+    }
+
+    /// Push button.
+    pub fn new_button<S: Into<String>, T: Into<String>>(id: S, label: T) -> Self {
+        let mut ctrl = Self::new(FormControlKind::Button, id);
+        ctrl.set_label(label);
+        ctrl
+    }
+
+    /// Checkbox.
+    pub fn new_checkbox<S: Into<String>, T: Into<String>>(id: S, label: T) -> Self {
+        let mut ctrl = Self::new(FormControlKind::Checkbox, id);
+        ctrl.set_label(label);
+        ctrl
+    }
+
+    /// Radio button. Radio buttons with the same group name form a group.
+    pub fn new_radio<S: Into<String>, T: Into<String>, U: Into<String>>(
+        id: S,
+        group: T,
+        label: U,
+    ) -> Self {
+        let mut ctrl = Self::new(FormControlKind::Radio, id);
+        ctrl.set_name(group);
+        ctrl.set_label(label);
+        ctrl
+    }
+
+    /// List box.
+    pub fn new_listbox<S: Into<String>>(id: S) -> Self {
+        Self::new(FormControlKind::ListBox, id)
+    }
+
+    /// Combo box.
+    pub fn new_combobox<S: Into<String>>(id: S) -> Self {
+        let mut ctrl = Self::new(FormControlKind::ComboBox, id);
+        ctrl.set_dropdown(true);
+        ctrl
+    }
+
+    /// Spin button.
+    pub fn new_spin_button<S: Into<String>>(id: S, min: i64, max: i64) -> Self {
+        let mut ctrl = Self::new(FormControlKind::SpinButton, id);
+        ctrl.set_min_value(min);
+        ctrl.set_max_value(max);
+        ctrl
+    }
+
+    /// Label.
+    pub fn new_label<S: Into<String>, T: Into<String>>(id: S, label: T) -> Self {
+        let mut ctrl = Self::new(FormControlKind::Label, id);
+        ctrl.set_label(label);
+        ctrl
+    }
+
+    /// Kind of control.
+    pub fn kind(&self) -> FormControlKind {
+        self.kind
+    }
+
+    /// Allows access to all attributes.
+    pub fn attrmap(&self) -> &AttrMap2 {
+        &self.attr
+    }
+
+    /// Allows access to all attributes.
+    pub fn attrmap_mut(&mut self) -> &mut AttrMap2 {
+        &mut self.attr
+    }
+
+    /// Id of the control. Uses form:id or xml:id.
+    pub fn id(&self) -> Option<&str> {
+        self.attr
+            .attr("form:id")
+            .or_else(|| self.attr.attr("xml:id"))
+    }
+
+    /// Name. Radio buttons with the same name form a group.
+    pub fn set_name<S: Into<String>>(&mut self, name: S) {
+        self.attr.set_attr("form:name", name.into());
+    }
+
+    /// Name.
+    pub fn name(&self) -> Option<&str> {
+        self.attr.attr("form:name")
+    }
+
+    /// Label of buttons, checkboxes, radio buttons and labels.
+    pub fn set_label<S: Into<String>>(&mut self, label: S) {
+        self.attr.set_attr("form:label", label.into());
+    }
+
+    /// Label.
+    pub fn label(&self) -> Option<&str> {
+        self.attr.attr("form:label")
+    }
+
+    /// The cell that receives the value of the control.
+    pub fn set_linked_cell(&mut self, cell: CellRef) {
+        self.attr.set_attr("form:linked-cell", cell.to_string());
+    }
+
+    /// The cell that receives the value of the control.
+    pub fn linked_cell(&self) -> Result<Option<CellRef>, OdsError> {
+        self.attr
+            .attr("form:linked-cell")
+            .map(parse_cellref)
+            .transpose()
+    }
+
+    /// The entries for a list box or combo box.
+    pub fn set_source_cell_range(&mut self, range: CellRange) {
+        self.attr
+            .set_attr("form:source-cell-range", range.to_string());
+    }
+
+    /// The entries for a list box or combo box.
+    pub fn source_cell_range(&self) -> Result<Option<CellRange>, OdsError> {
+        self.attr
+            .attr("form:source-cell-range")
+            .map(parse_cellrange)
+            .transpose()
+    }
+
+    /// What a list box writes to the linked cell.
+    pub fn set_list_linkage_type(&mut self, linkage: ListLinkageType) {
+        self.attr
+            .set_attr("form:list-linkage-type", linkage.to_string());
+    }
+
+    /// What a list box writes to the linked cell.
+    pub fn list_linkage_type(&self) -> Result<Option<ListLinkageType>, OdsError> {
+        match self.attr.attr("form:list-linkage-type") {
+            None => Ok(None),
+            Some("selection") => Ok(Some(ListLinkageType::Selection)),
+            Some("selection-indices") => Ok(Some(ListLinkageType::SelectionIndices)),
+            Some(v) => Err(OdsError::Parse(
+                "invalid form:list-linkage-type",
+                Some(v.to_string()),
+            )),
+        }
+    }
+
+    /// Show a list box or combo box as dropdown.
+    pub fn set_dropdown(&mut self, dropdown: bool) {
+        self.attr.set_attr("form:dropdown", dropdown.to_string());
+    }
+
+    /// Show a list box or combo box as dropdown.
+    pub fn dropdown(&self) -> bool {
+        self.attr.attr("form:dropdown") == Some("true")
+    }
+
+    /// Current state of a checkbox or radio button.
+    pub fn set_checked(&mut self, checked: bool) {
+        if self.kind == FormControlKind::Radio {
+            self.attr
+                .set_attr("form:current-selected", checked.to_string());
+        } else {
+            self.attr.set_attr(
+                "form:current-state",
+                if checked { "checked" } else { "unchecked" },
+            );
+        }
+    }
+
+    /// Current state of a checkbox or radio button.
+    pub fn checked(&self) -> bool {
+        if self.kind == FormControlKind::Radio {
+            self.attr.attr("form:current-selected") == Some("true")
+        } else {
+            self.attr.attr("form:current-state") == Some("checked")
+        }
+    }
+
+    /// Value of the control. For checkboxes and radio buttons this is the
+    /// value written to the linked cell.
+    pub fn set_value<S: Into<String>>(&mut self, value: S) {
+        self.attr.set_attr("form:value", value.into());
+    }
+
+    /// Value of the control.
+    pub fn value(&self) -> Option<&str> {
+        self.attr.attr("form:value")
+    }
+
+    /// Minimum value of a spin button.
+    pub fn set_min_value(&mut self, min: i64) {
+        self.attr.set_attr("form:min-value", min.to_string());
+    }
+
+    /// Minimum value of a spin button.
+    pub fn min_value(&self) -> Result<Option<i64>, OdsError> {
+        Ok(self
+            .attr
+            .attr("form:min-value")
+            .map(|v| v.parse())
+            .transpose()?)
+    }
+
+    /// Maximum value of a spin button.
+    pub fn set_max_value(&mut self, max: i64) {
+        self.attr.set_attr("form:max-value", max.to_string());
+    }
+
+    /// Maximum value of a spin button.
+    pub fn max_value(&self) -> Result<Option<i64>, OdsError> {
+        Ok(self
+            .attr
+            .attr("form:max-value")
+            .map(|v| v.parse())
+            .transpose()?)
+    }
+
+    /// Step size of a spin button.
+    pub fn set_step_size(&mut self, step: i64) {
+        self.attr.set_attr("form:step-size", step.to_string());
+    }
+
+    /// Step size of a spin button.
+    pub fn step_size(&self) -> Result<Option<i64>, OdsError> {
+        Ok(self
+            .attr
+            .attr("form:step-size")
+            .map(|v| v.parse())
+            .transpose()?)
+    }
+
+    /// Tab order.
+    pub fn set_tab_index(&mut self, idx: u32) {
+        self.attr.set_attr("form:tab-index", idx.to_string());
+    }
+
+    /// Disables the control.
+    pub fn set_disabled(&mut self, disabled: bool) {
+        self.attr.set_attr("form:disabled", disabled.to_string());
+    }
+
+    /// Fixed entries of a list box or combo box.
+    pub fn items(&self) -> &Vec<FormItem> {
+        &self.items
+    }
+
+    /// Fixed entries of a list box or combo box.
+    pub fn items_mut(&mut self) -> &mut Vec<FormItem> {
+        &mut self.items
+    }
+
+    /// Fixed entries of a list box or combo box.
+    pub fn set_items(&mut self, items: Vec<FormItem>) {
+        self.items = items;
+    }
+
+    /// Adds an entry with the given label.
+    pub fn push_item<S: Into<String>>(&mut self, label: S) {
+        self.items.push(FormItem::new(label));
+    }
+
+    /// Adds an entry.
+    pub fn push_form_item(&mut self, item: FormItem) {
+        self.items.push(item);
+    }
+
+    /// Other content, eg form:properties or office:event-listeners.
+    pub fn extra(&self) -> &Vec<XmlTag> {
+        &self.extra
+    }
+
+    /// Other content, eg form:properties or office:event-listeners.
+    pub fn push_extra(&mut self, tag: XmlTag) {
+        self.extra.push(tag);
+    }
+}
+
+/// An entry of a list box (form:option) or a combo box (form:item).
+#[derive(Debug, Clone, Default, PartialEq, GetSize)]
+pub struct FormItem {
+    attr: AttrMap2,
+}
+
+impl FormItem {
+    /// New entry.
+    pub fn new<S: Into<String>>(label: S) -> Self {
+        let mut attr = AttrMap2::new();
+        attr.set_attr("form:label", label.into());
+        Self { attr }
+    }
+
+    /// Empty entry, used when reading.
+    pub(crate) fn new_empty() -> Self {
+        Self {
+            attr: Default::default(),
+        }
+    }
+
+    /// Attributes.
+    pub fn attrmap(&self) -> &AttrMap2 {
+        &self.attr
+    }
+
+    /// Attributes.
+    pub fn attrmap_mut(&mut self) -> &mut AttrMap2 {
+        &mut self.attr
+    }
+
+    /// Label.
+    pub fn label(&self) -> Option<&str> {
+        self.attr.attr("form:label")
+    }
+
+    /// Value. Only used by list boxes.
+    pub fn set_value<S: Into<String>>(&mut self, value: S) {
+        self.attr.set_attr("form:value", value.into());
+    }
+
+    /// Value. Only used by list boxes.
+    pub fn value(&self) -> Option<&str> {
+        self.attr.attr("form:value")
+    }
+
+    /// Initially selected. Only used by list boxes.
+    pub fn set_selected(&mut self, selected: bool) {
+        self.attr.set_attr("form:selected", selected.to_string());
+    }
+
+    /// Initially selected. Only used by list boxes.
+    pub fn selected(&self) -> bool {
+        self.attr.attr("form:selected") == Some("true")
+    }
+}
+
+/// A form contains the controls of a sheet.
+#[derive(Debug, Clone, GetSize)]
+pub struct Form {
+    attr: AttrMap2,
+    controls: Vec<FormControl>,
+    /// form:properties, nested forms, other controls.
+    extra: Vec<XmlTag>,
+}
+
+impl Form {
+    /// New form.
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        let mut attr = AttrMap2::new();
+        attr.set_attr("form:name", name.into());
+        attr.set_attr("form:apply-filter", "true");
+        attr.set_attr("form:command-type", "table");
+        Self {
+            attr,
+            controls: Default::default(),
+            extra: Default::default(),
+        }
+    }
+
+    /// Empty form, used when reading.
+    pub(crate) fn new_empty() -> Self {
+        Self {
+            attr: Default::default(),
+            controls: Default::default(),
+            extra: Default::default(),
+        }
+    }
+
+    /// Allows access to all attributes.
+    pub fn attrmap(&self) -> &AttrMap2 {
+        &self.attr
+    }
+
+    /// Allows access to all attributes.
+    pub fn attrmap_mut(&mut self) -> &mut AttrMap2 {
+        &mut self.attr
+    }
+
+    /// Name.
+    pub fn name(&self) -> Option<&str> {
+        self.attr.attr("form:name")
+    }
+
+    /// Controls.
+    pub fn controls(&self) -> &Vec<FormControl> {
+        &self.controls
+    }
+
+    /// Controls.
+    pub fn controls_mut(&mut self) -> &mut Vec<FormControl> {
+        &mut self.controls
+    }
+
+    /// Add a control.
+    pub fn push_control(&mut self, control: FormControl) {
+        self.controls.push(control);
+    }
+
+    /// Finds a control by id.
+    pub fn control(&self, id: &str) -> Option<&FormControl> {
+        self.controls.iter().find(|v| v.id() == Some(id))
+    }
+
+    /// Other content, eg form:properties or controls not modeled here.
+    pub fn extra(&self) -> &Vec<XmlTag> {
+        &self.extra
+    }
+
+    /// Other content, eg form:properties or controls not modeled here.
+    pub fn push_extra(&mut self, tag: XmlTag) {
+        self.extra.push(tag);
+    }
+}
@@ -64,6 +64,9 @@ use icu_locid::{LanguageIdentifier, Locale};
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
+/// Upper limit for the digit counts of a format part.
+const MAX_DIGITS: u32 = 30;
+
 style_ref2!(ValueFormatRef);
 
 /// Trait used by the builder types.
@@ -384,6 +387,15 @@ impl FormatPart {
         self.attr.attr_def(name, default)
     }
 
+    /// Returns a number of digits, e.g. number:decimal-places.
+    /// Anything beyond MAX_DIGITS is cut.
+    pub(crate) fn attr_digits(&self, name: &str) -> Option<u32> {
+        self.attr
+            .attr(name)
+            .and_then(|v| v.parse::<u32>().ok())
+            .map(|v| v.min(MAX_DIGITS))
+    }
+
     /// Sets the position for embedded text in a number format part.
     pub fn set_position(&mut self, pos: i32) {
         self.position = Some(pos);
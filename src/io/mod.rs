@@ -1,11 +1,14 @@
 use crate::HashMap;
 use get_size::GetSize;
-use std::borrow::Cow;
+use std::borrow::{Borrow, Cow};
+use std::cmp::Ordering;
 
 pub(crate) mod format;
 pub(crate) mod parse;
+pub(crate) mod pdf;
 pub(crate) mod read;
 pub(crate) mod write;
+pub(crate) mod xlsx;
 
 mod xmlwriter;
 
@@ -31,7 +34,35 @@ impl NamespaceMap {
         self.map.insert(Cow::Borrowed(k), Cow::Borrowed(v));
     }
 
+    /// Entries ordered by name.
     pub(crate) fn entries(&self) -> impl Iterator<Item = (&Cow<'static, str>, &Cow<'static, str>)> {
-        self.map.iter()
+        sorted_entries(&self.map).into_iter()
     }
 }
+
+/// Entries of the map ordered by key. Gives the same output for the
+/// same content, regardless of the hashing.
+pub(crate) fn sorted_entries<K: Borrow<str>, V>(map: &HashMap<K, V>) -> Vec<(&K, &V)> {
+    let mut entries = map.iter().collect::<Vec<_>>();
+    entries.sort_by(|(a, _), (b, _)| cmp_name((*a).borrow(), (*b).borrow()));
+    entries
+}
+
+/// Values of the map ordered by key.
+pub(crate) fn sorted_values<K: Borrow<str>, V>(map: &HashMap<K, V>) -> impl Iterator<Item = &V> {
+    sorted_entries(map).into_iter().map(|(_, v)| v)
+}
+
+/// Compares names with a numeric suffix by the value of the number,
+/// so "ce2" comes before "ce10".
+fn cmp_name(a: &str, b: &str) -> Ordering {
+    let a_prefix = a.trim_end_matches(|c: char| c.is_ascii_digit());
+    let b_prefix = b.trim_end_matches(|c: char| c.is_ascii_digit());
+    let a_num = a[a_prefix.len()..].trim_start_matches('0');
+    let b_num = b[b_prefix.len()..].trim_start_matches('0');
+    a_prefix
+        .cmp(b_prefix)
+        .then_with(|| a_num.len().cmp(&b_num.len()))
+        .then_with(|| a_num.cmp(b_num))
+        .then_with(|| a.cmp(b))
+}
@@ -16,6 +16,7 @@ use nom::combinator::{all_consuming, eof, opt};
 use nom::number::complete::double;
 use nom::sequence::{pair, preceded, terminated, tuple};
 use nom::AsChar;
+use rust_decimal::Decimal;
 use std::fmt::{Display, Formatter};
 use std::str::{from_utf8, from_utf8_unchecked};
 
@@ -96,6 +97,7 @@ pub(crate) fn parse_xlink_show(input: KSpan<'_>) -> Result<XLinkShow, OdsError>
     match input {
         b"new" => Ok(XLinkShow::New),
         b"replace" => Ok(XLinkShow::Replace),
+        b"embed" => Ok(XLinkShow::Embed),
         _ => Err(OdsError::Parse(
             "invalid xlink:show",
             Some(from_utf8(input)?.to_string()),
@@ -169,6 +171,15 @@ pub(crate) fn parse_f64(input: KSpan<'_>) -> Result<f64, OdsError> {
     Ok(token_float(input)?)
 }
 
+/// Parse a decimal. Gives None if the value has no exact decimal
+/// representation, e.g. if it uses an exponent or has too many digits.
+#[inline]
+pub(crate) fn parse_decimal(input: KSpan<'_>) -> Option<Decimal> {
+    from_utf8(input)
+        .ok()
+        .and_then(|v| Decimal::from_str_exact(v).ok())
+}
+
 /// Parse a XML Schema datetime.
 #[inline]
 pub(crate) fn parse_datetime(input: KSpan<'_>) -> Result<NaiveDateTime, OdsError> {
@@ -0,0 +1,624 @@
+//!
+//! Converts cell values to the text that is displayed, using the value
+//! formats of the workbook.
+//!
+
+use crate::color::Rgb;
+use crate::condition::value_as_number;
+use crate::find::value_text;
+use crate::format::{FormatPart, FormatPartType, ValueFormatTrait};
+use crate::locale::{format_locale, localized_format};
+use crate::style::ParseStyleAttr;
+use crate::text::TextTag;
+use crate::xmltree::XmlContent;
+use crate::{Value, ValueType, WorkBook};
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Timelike};
+use rust_decimal::Decimal;
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const WEEKDAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+/// Nesting depth for style-maps of value formats.
+const MAX_MAP_DEPTH: u32 = 4;
+
+/// The displayed text of a cell.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DisplayText {
+    pub(crate) text: String,
+    /// Numbers are right aligned by default, and replaced with ### if
+    /// they don't fit.
+    pub(crate) numeric: bool,
+    /// Color from the value format.
+    pub(crate) color: Option<Rgb<u8>>,
+}
+
+/// Formats the value with the named value format. Uses a default
+/// format if there is none.
+pub(crate) fn display_value(
+    book: &WorkBook,
+    value: &Value,
+    format: Option<&str>,
+    zero_values: bool,
+) -> DisplayText {
+    let numeric = matches!(
+        value,
+        Value::Number(_)
+            | Value::Percentage(_)
+            | Value::Currency(_, _)
+            | Value::NumberDecimal(_)
+            | Value::PercentageDecimal(_)
+            | Value::CurrencyDecimal(_, _)
+            | Value::DateTime(_)
+            | Value::TimeDuration(_)
+    );
+    if numeric && !zero_values && value_as_number(value) == Some(0.0) {
+        return DisplayText::default();
+    }
+
+    if let Some(mut display) = format.and_then(|v| format_named(book, v, value, 0)) {
+        display.numeric = numeric;
+        return display;
+    }
+
+    let text = match value {
+        Value::Number(v) => general(*v),
+        Value::Percentage(v) => format!("{}%", general(*v * 100.0)),
+        Value::Currency(v, c) => format!("{:.2} {}", v, c).trim_end().to_string(),
+        Value::NumberDecimal(v) => v.normalize().to_string(),
+        Value::PercentageDecimal(v) => format!("{}%", (*v * Decimal::ONE_HUNDRED).normalize()),
+        Value::CurrencyDecimal(v, c) => format!("{:.2} {}", v, c).trim_end().to_string(),
+        Value::TextXml(v) => plain_text(v, &|_| None).join("\n"),
+        Value::TimeDuration(v) => {
+            let secs = v.num_seconds();
+            format!(
+                "{}{}:{:02}:{:02}",
+                if secs < 0 { "-" } else { "" },
+                secs.abs() / 3600,
+                secs.abs() / 60 % 60,
+                secs.abs() % 60
+            )
+        }
+        _ => value_text(value).unwrap_or_default().into_owned(),
+    };
+    DisplayText {
+        text,
+        numeric,
+        color: None,
+    }
+}
+
+fn format_named(book: &WorkBook, name: &str, value: &Value, depth: u32) -> Option<DisplayText> {
+    if let Some(format) = book.formats_number.get(name) {
+        format_value(book, format, value, depth)
+    } else if let Some(format) = book.formats_percentage.get(name) {
+        format_value(book, format, value, depth)
+    } else if let Some(format) = book.formats_currency.get(name) {
+        format_value(book, format, value, depth)
+    } else if let Some(format) = book.formats_datetime.get(name) {
+        format_value(book, format, value, depth)
+    } else if let Some(format) = book.formats_timeduration.get(name) {
+        format_value(book, format, value, depth)
+    } else if let Some(format) = book.formats_boolean.get(name) {
+        format_value(book, format, value, depth)
+    } else if let Some(format) = book.formats_text.get(name) {
+        format_value(book, format, value, depth)
+    } else {
+        None
+    }
+}
+
+fn format_value<T: ValueFormatTrait>(
+    book: &WorkBook,
+    format: &T,
+    value: &Value,
+    depth: u32,
+) -> Option<DisplayText> {
+    let number = match value {
+        Value::Text(_) | Value::TextXml(_) | Value::Empty => None,
+        _ => value_as_number(value),
+    };
+
+    // Text is only formatted by formats with a text-content part.
+    if number.is_none()
+        && !format
+            .parts()
+            .iter()
+            .any(|v| v.part_type() == FormatPartType::TextContent)
+    {
+        return None;
+    }
+
+    if let (Some(_), Some(stylemaps)) = (number, format.stylemaps()) {
+        if depth < MAX_MAP_DEPTH {
+            for sm in stylemaps {
+                if sm.condition().eval_cell_content(value) == Some(true) {
+                    if let Some(v) = format_named(book, sm.applied_style(), value, depth + 1) {
+                        return Some(v);
+                    }
+                }
+            }
+        }
+    }
+    // The format that remains after the conditions brings its own sign.
+    let signed = !format.stylemaps().map(|v| !v.is_empty()).unwrap_or(false);
+
+    Some(DisplayText {
+        text: format_parts(format, value, number, signed),
+        numeric: false,
+        color: Rgb::parse_attr(format.textstyle().attr("fo:color"))
+            .ok()
+            .flatten(),
+    })
+}
+
+fn format_parts<T: ValueFormatTrait>(
+    format: &T,
+    value: &Value,
+    number: Option<f64>,
+    signed: bool,
+) -> String {
+    let separators = separators(format);
+    let value_type = format.value_type();
+    let elapsed = value_type == ValueType::TimeDuration
+        || format.attrmap().attr("number:truncate-on-overflow") == Some("false");
+    let ampm = format
+        .parts()
+        .iter()
+        .any(|v| v.part_type() == FormatPartType::AmPm);
+
+    let number = number.unwrap_or_default();
+    let datetime = match value {
+        Value::DateTime(v) => Some(*v),
+        _ => datetime_from_number(number),
+    };
+    let duration = match value {
+        Value::TimeDuration(v) => *v,
+        _ => Duration::milliseconds((number * 86_400_000.0).round() as i64),
+    };
+    // seconds are rounded to the shown decimals beforehand.
+    let seconds_decimals = format
+        .parts()
+        .iter()
+        .find(|v| v.part_type() == FormatPartType::Seconds)
+        .and_then(|v| v.attr_digits("number:decimal-places"))
+        .unwrap_or(0);
+
+    let mut buf = String::new();
+    let mut first_hours = true;
+    let mut sign_done = false;
+    for part in format.parts() {
+        let long = part.attr_def("number:style", "short") == "long";
+        match part.part_type() {
+            FormatPartType::Number => {
+                let mut v = number;
+                if value_type == ValueType::Percentage {
+                    v *= 100.0;
+                }
+                push_sign(&mut buf, v, signed, &mut sign_done);
+                format_number(&mut buf, v.abs(), part, separators);
+            }
+            FormatPartType::ScientificNumber => {
+                push_sign(&mut buf, number, signed, &mut sign_done);
+                format_scientific(&mut buf, number.abs(), part, separators.0);
+            }
+            FormatPartType::Fraction => {
+                push_sign(&mut buf, number, signed, &mut sign_done);
+                format_fraction(&mut buf, number.abs(), part);
+            }
+            FormatPartType::CurrencySymbol => match part.content().filter(|v| !v.is_empty()) {
+                Some(symbol) => buf.push_str(symbol),
+                None => {
+                    if let Value::Currency(_, c) | Value::CurrencyDecimal(_, c) = value {
+                        buf.push_str(c);
+                    }
+                }
+            },
+            FormatPartType::Day => {
+                if let Some(dt) = datetime {
+                    push_padded(&mut buf, dt.day() as i64, long);
+                }
+            }
+            FormatPartType::Month => {
+                if let Some(dt) = datetime {
+                    if part.attr_def("number:textual", "false") == "true" {
+                        let name = MONTHS[dt.month0() as usize];
+                        buf.push_str(if long { name } else { &name[..3] });
+                    } else {
+                        push_padded(&mut buf, dt.month() as i64, long);
+                    }
+                }
+            }
+            FormatPartType::Year => {
+                if let Some(dt) = datetime {
+                    if long {
+                        buf.push_str(&format!("{:04}", dt.year()));
+                    } else {
+                        buf.push_str(&format!("{:02}", dt.year().rem_euclid(100)));
+                    }
+                }
+            }
+            FormatPartType::DayOfWeek => {
+                if let Some(dt) = datetime {
+                    let name = WEEKDAYS[dt.weekday().num_days_from_monday() as usize];
+                    buf.push_str(if long { name } else { &name[..3] });
+                }
+            }
+            FormatPartType::WeekOfYear => {
+                if let Some(dt) = datetime {
+                    buf.push_str(&dt.iso_week().week().to_string());
+                }
+            }
+            FormatPartType::Quarter => {
+                if let Some(dt) = datetime {
+                    let q = dt.month0() / 3 + 1;
+                    if long {
+                        buf.push_str(&format!("{}. quarter", q));
+                    } else {
+                        buf.push_str(&format!("Q{}", q));
+                    }
+                }
+            }
+            FormatPartType::Era => {
+                if let Some(dt) = datetime {
+                    buf.push_str(if dt.year() > 0 { "AD" } else { "BC" });
+                }
+            }
+            FormatPartType::Hours => {
+                if elapsed && first_hours {
+                    let secs = round_seconds(duration, seconds_decimals);
+                    if secs < 0.0 {
+                        buf.push('-');
+                    }
+                    push_padded(&mut buf, (secs.abs() / 3600.0).floor() as i64, long);
+                } else if let Some(dt) = datetime {
+                    let mut h = dt.hour() as i64;
+                    if ampm {
+                        h %= 12;
+                        if h == 0 {
+                            h = 12;
+                        }
+                    }
+                    push_padded(&mut buf, h, long);
+                }
+                first_hours = false;
+            }
+            FormatPartType::Minutes => {
+                if elapsed {
+                    let secs = round_seconds(duration, seconds_decimals).abs();
+                    let minutes = (secs / 60.0).floor() as i64;
+                    // without hours the minutes are elapsed too.
+                    push_padded(
+                        &mut buf,
+                        if first_hours { minutes } else { minutes % 60 },
+                        long,
+                    );
+                } else if let Some(dt) = datetime {
+                    push_padded(&mut buf, dt.minute() as i64, long);
+                }
+            }
+            FormatPartType::Seconds => {
+                let secs = if elapsed {
+                    round_seconds(duration, seconds_decimals).abs() % 60.0
+                } else if let Some(dt) = datetime {
+                    dt.second() as f64 + dt.nanosecond() as f64 / 1e9
+                } else {
+                    0.0
+                };
+                let s = format!("{:.*}", seconds_decimals as usize, secs);
+                let s = s.replace('.', &separators.0.to_string());
+                if long && secs < 10.0 {
+                    buf.push('0');
+                }
+                buf.push_str(&s);
+            }
+            FormatPartType::AmPm => {
+                if let Some(dt) = datetime {
+                    buf.push_str(if dt.hour() < 12 { "AM" } else { "PM" });
+                }
+            }
+            FormatPartType::Boolean => {
+                buf.push_str(if number != 0.0 { "TRUE" } else { "FALSE" });
+            }
+            FormatPartType::Text => {
+                if let Some(text) = part.content() {
+                    if text.contains('-') || text.contains('(') {
+                        sign_done = true;
+                    }
+                    buf.push_str(text);
+                }
+            }
+            FormatPartType::TextContent => match value {
+                Value::Text(v) => buf.push_str(v),
+                Value::TextXml(v) => buf.push_str(&plain_text(v, &|_| None).join("\n")),
+                _ => {}
+            },
+            FormatPartType::FillCharacter => {}
+        }
+    }
+
+    buf
+}
+
+fn push_sign(buf: &mut String, v: f64, signed: bool, sign_done: &mut bool) {
+    if signed && !*sign_done && v < 0.0 {
+        buf.push('-');
+    }
+    *sign_done = true;
+}
+
+fn push_padded(buf: &mut String, v: i64, long: bool) {
+    if long {
+        buf.push_str(&format!("{:02}", v));
+    } else {
+        buf.push_str(&v.to_string());
+    }
+}
+
+fn round_seconds(duration: Duration, decimals: u32) -> f64 {
+    let factor = 10f64.powi(decimals as i32);
+    (duration.num_milliseconds() as f64 / 1000.0 * factor).round() / factor
+}
+
+fn datetime_from_number(number: f64) -> Option<NaiveDateTime> {
+    let base = NaiveDate::from_ymd_opt(1899, 12, 30)?.and_hms_opt(0, 0, 0)?;
+    let ms = (number * 86_400_000.0).round();
+    if ms.abs() > 1e15 {
+        return None;
+    }
+    base.checked_add_signed(Duration::milliseconds(ms as i64))
+}
+
+/// Decimal separator and grouping character for the language of the
+/// format.
+fn separators<T: ValueFormatTrait>(format: &T) -> (char, char) {
+    if let Some(lf) = format_locale(format).and_then(localized_format) {
+        return (lf.decimal_separator(), lf.grouping_separator());
+    }
+    let language = format.attrmap().attr("number:language").unwrap_or("");
+    let country = format.attrmap().attr("number:country").unwrap_or("");
+    match (language, country) {
+        ("de" | "fr" | "it", "CH") => ('.', '\''),
+        ("de" | "es" | "it" | "nl" | "pt" | "da" | "id" | "tr" | "el" | "ro", _) => (',', '.'),
+        (
+            "fr" | "pl" | "sv" | "cs" | "sk" | "fi" | "nb" | "nn" | "no" | "ru" | "uk" | "hu"
+            | "bg",
+            _,
+        ) => (',', '\u{A0}'),
+        _ => ('.', ','),
+    }
+}
+
+/// Formats the number with up to 10 significant digits.
+pub(crate) fn general(v: f64) -> String {
+    if !v.is_finite() {
+        return v.to_string();
+    }
+    let abs = v.abs();
+    if abs == 0.0 {
+        "0".to_string()
+    } else if !(1e-5..1e11).contains(&abs) {
+        let s = format!("{:.5E}", v);
+        let (mantissa, exp) = s.split_once('E').unwrap_or((s.as_str(), "0"));
+        let mantissa = mantissa.trim_end_matches('0').trim_end_matches('.');
+        let exp: i32 = exp.parse().unwrap_or(0);
+        format!(
+            "{}E{}{:02}",
+            mantissa,
+            if exp < 0 { '-' } else { '+' },
+            exp.abs()
+        )
+    } else {
+        let int_digits = abs.log10().floor() as i32 + 1;
+        let decimals = (10 - int_digits).clamp(0, 10) as usize;
+        let s = format!("{:.*}", decimals, v);
+        if s.contains('.') {
+            s.trim_end_matches('0').trim_end_matches('.').to_string()
+        } else {
+            s
+        }
+    }
+}
+
+/// number:number without the sign.
+fn format_number(buf: &mut String, v: f64, part: &FormatPart, separators: (char, char)) {
+    let mut v = v;
+    if let Some(factor) = part
+        .attrmap()
+        .attr("number:display-factor")
+        .and_then(|v| v.parse::<f64>().ok())
+        .filter(|v| *v != 0.0)
+    {
+        v /= factor;
+    }
+
+    let Some(decimals) = part.attr_digits("number:decimal-places") else {
+        buf.push_str(&general(v).replace('.', &separators.0.to_string()));
+        return;
+    };
+    let min_decimals = part
+        .attr_digits("number:min-decimal-places")
+        .unwrap_or(decimals)
+        .min(decimals);
+    let min_integer = part.attr_digits("number:min-integer-digits").unwrap_or(1);
+    let grouping = part.attr_def("number:grouping", "false") == "true";
+
+    let s = format!("{:.*}", decimals as usize, v);
+    let (int, frac) = s.split_once('.').unwrap_or((s.as_str(), ""));
+    let mut frac = frac.to_string();
+    while frac.len() > min_decimals as usize && frac.ends_with('0') {
+        frac.pop();
+    }
+    let int = int.trim_start_matches('0');
+
+    let mut digits = String::new();
+    for _ in int.len()..min_integer as usize {
+        digits.push('0');
+    }
+    digits.push_str(int);
+
+    if grouping {
+        let len = digits.chars().count();
+        for (i, c) in digits.chars().enumerate() {
+            if i > 0 && (len - i).is_multiple_of(3) {
+                buf.push(separators.1);
+            }
+            buf.push(c);
+        }
+    } else {
+        buf.push_str(&digits);
+    }
+    if !frac.is_empty() {
+        buf.push(separators.0);
+        buf.push_str(&frac);
+    }
+}
+
+/// number:scientific-number without the sign.
+fn format_scientific(buf: &mut String, v: f64, part: &FormatPart, decimal: char) {
+    let decimals = part.attr_digits("number:decimal-places").unwrap_or(2) as usize;
+    let min_integer = part
+        .attr_digits("number:min-integer-digits")
+        .unwrap_or(1)
+        .max(1) as i32;
+    let exp_digits = part.attr_digits("number:min-exponent-digits").unwrap_or(2) as usize;
+
+    let mut exp = if v == 0.0 {
+        0
+    } else {
+        v.log10().floor() as i32 - (min_integer - 1)
+    };
+    let mut mantissa = format!("{:.*}", decimals, v / 10f64.powi(exp));
+    // rounding can add a digit.
+    if mantissa.split('.').next().map(|v| v.len()).unwrap_or(0) > min_integer as usize {
+        exp += 1;
+        mantissa = format!("{:.*}", decimals, v / 10f64.powi(exp));
+    }
+    buf.push_str(&mantissa.replace('.', &decimal.to_string()));
+    buf.push('E');
+    buf.push(if exp < 0 { '-' } else { '+' });
+    buf.push_str(&format!("{:0width$}", exp.abs(), width = exp_digits));
+}
+
+/// number:fraction without the sign.
+fn format_fraction(buf: &mut String, v: f64, part: &FormatPart) {
+    let with_integer = part.attrmap().attr("number:min-integer-digits").is_some();
+    let (int, frac) = if with_integer {
+        (v.trunc(), v.fract())
+    } else {
+        (0.0, v)
+    };
+
+    let denominator_value = part
+        .attrmap()
+        .attr("number:denominator-value")
+        .and_then(|v| v.parse::<u32>().ok());
+    let (mut numerator, denominator) = match denominator_value {
+        Some(d) if d > 0 => ((frac * d as f64).round() as u64, d as u64),
+        _ => {
+            let digits = part
+                .attr_digits("number:min-denominator-digits")
+                .unwrap_or(1)
+                .clamp(1, 4);
+            let max = 10u64.pow(digits) - 1;
+            let mut best = (frac.round() as u64, 1u64);
+            let mut best_err = (frac - frac.round()).abs();
+            for d in 2..=max {
+                let n = (frac * d as f64).round();
+                let err = (frac - n / d as f64).abs();
+                if err < best_err - 1e-12 {
+                    best = (n as u64, d);
+                    best_err = err;
+                }
+            }
+            best
+        }
+    };
+
+    let mut int = int as u64;
+    if with_integer && numerator >= denominator {
+        int += numerator / denominator;
+        numerator %= denominator;
+    }
+
+    if with_integer {
+        if int > 0 || numerator == 0 {
+            buf.push_str(&int.to_string());
+        }
+        if numerator > 0 {
+            if int > 0 {
+                buf.push(' ');
+            }
+            buf.push_str(&format!("{}/{}", numerator, denominator));
+        }
+    } else {
+        buf.push_str(&format!("{}/{}", numerator, denominator));
+    }
+}
+
+/// Extracts the text of paragraphs, one string per line. The callback
+/// can replace fields with their current value.
+pub(crate) fn plain_text(
+    tags: &[TextTag],
+    field: &dyn Fn(&TextTag) -> Option<String>,
+) -> Vec<String> {
+    let mut lines = Vec::new();
+    for tag in tags {
+        let mut line = String::new();
+        push_text(tag, field, &mut line, &mut lines);
+        lines.push(line);
+    }
+    lines
+}
+
+fn push_text(
+    tag: &TextTag,
+    field: &dyn Fn(&TextTag) -> Option<String>,
+    line: &mut String,
+    lines: &mut Vec<String>,
+) {
+    if let Some(v) = field(tag) {
+        line.push_str(&v);
+        return;
+    }
+    match tag.name() {
+        "text:s" => {
+            let count = tag
+                .get_attr("text:c")
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(1);
+            line.push_str(&" ".repeat(count));
+        }
+        "text:tab" => line.push_str("    "),
+        "text:line-break" => lines.push(std::mem::take(line)),
+        _ => {
+            for c in tag.content() {
+                match c {
+                    XmlContent::Text(t) => line.push_str(t),
+                    XmlContent::Tag(t) => push_text(t, field, line, lines),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,276 @@
+//!
+//! Low level PDF output. Collects the objects of the document and
+//! builds page content streams.
+//!
+
+use crate::color::Rgb;
+use crate::io::pdf::font::{win_ansi, PdfFont};
+use crate::style::units::Border;
+use crate::OdsError;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use std::fmt::Write as _;
+use std::io::Write;
+
+/// Objects of a PDF file. The object number is the index + 1.
+pub(crate) struct PdfDocument {
+    objects: Vec<Vec<u8>>,
+}
+
+impl PdfDocument {
+    pub(crate) fn new() -> Self {
+        Self {
+            objects: Vec::new(),
+        }
+    }
+
+    /// Reserves an object number. The object is set later.
+    pub(crate) fn reserve(&mut self) -> u32 {
+        self.objects.push(Vec::new());
+        self.objects.len() as u32
+    }
+
+    /// Sets the content of a reserved object.
+    pub(crate) fn set<S: Into<Vec<u8>>>(&mut self, id: u32, obj: S) {
+        self.objects[id as usize - 1] = obj.into();
+    }
+
+    /// Adds an object and returns its number.
+    pub(crate) fn add<S: Into<Vec<u8>>>(&mut self, obj: S) -> u32 {
+        self.objects.push(obj.into());
+        self.objects.len() as u32
+    }
+
+    /// Adds a deflated stream.
+    pub(crate) fn add_stream(&mut self, data: &[u8]) -> Result<u32, OdsError> {
+        let mut enc = ZlibEncoder::new(Vec::new(), Compression::default());
+        enc.write_all(data)?;
+        let data = enc.finish()?;
+
+        let mut obj = format!(
+            "<< /Length {} /Filter /FlateDecode >>\nstream\n",
+            data.len()
+        )
+        .into_bytes();
+        obj.extend_from_slice(&data);
+        obj.extend_from_slice(b"\nendstream");
+        Ok(self.add(obj))
+    }
+
+    /// Writes the file with the cross-reference table.
+    pub(crate) fn write<W: Write>(&self, root: u32, info: u32, mut w: W) -> Result<(), OdsError> {
+        let mut out = Vec::new();
+        out.extend_from_slice(b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n");
+
+        let mut offsets = Vec::with_capacity(self.objects.len());
+        for (i, obj) in self.objects.iter().enumerate() {
+            offsets.push(out.len());
+            out.extend_from_slice(format!("{} 0 obj\n", i + 1).as_bytes());
+            out.extend_from_slice(obj);
+            out.extend_from_slice(b"\nendobj\n");
+        }
+
+        let xref = out.len();
+        let mut buf = String::new();
+        let _ = write!(
+            buf,
+            "xref\n0 {}\n0000000000 65535 f \n",
+            self.objects.len() + 1
+        );
+        for offset in offsets {
+            let _ = writeln!(buf, "{:010} 00000 n ", offset);
+        }
+        let _ = write!(
+            buf,
+            "trailer\n<< /Size {} /Root {} 0 R /Info {} 0 R >>\nstartxref\n{}\n%%EOF\n",
+            self.objects.len() + 1,
+            root,
+            info,
+            xref
+        );
+        out.extend_from_slice(buf.as_bytes());
+
+        w.write_all(&out)?;
+        Ok(())
+    }
+}
+
+/// Appends a PDF string literal. The text is encoded with WinAnsiEncoding.
+pub(crate) fn push_string(buf: &mut String, text: &str) {
+    buf.push('(');
+    for c in text.chars() {
+        match win_ansi(c) {
+            b @ (b'(' | b')' | b'\\') => {
+                buf.push('\\');
+                buf.push(b as char);
+            }
+            b @ 32..=126 => buf.push(b as char),
+            b => {
+                let _ = write!(buf, "\\{:03o}", b);
+            }
+        }
+    }
+    buf.push(')');
+}
+
+/// Appends a number with at most 3 decimals.
+fn push_num(buf: &mut String, v: f64) {
+    let v = (v * 1000.0).round() / 1000.0;
+    let _ = write!(buf, "{} ", if v == 0.0 { 0.0 } else { v });
+}
+
+/// Content stream of one page.
+pub(crate) struct Canvas {
+    buf: String,
+    fonts: [bool; PdfFont::COUNT],
+}
+
+impl Canvas {
+    pub(crate) fn new() -> Self {
+        Self {
+            buf: String::new(),
+            fonts: [false; PdfFont::COUNT],
+        }
+    }
+
+    /// Fonts used on this page.
+    pub(crate) fn fonts(&self) -> &[bool; PdfFont::COUNT] {
+        &self.fonts
+    }
+
+    /// Content stream.
+    pub(crate) fn content(&self) -> &[u8] {
+        self.buf.as_bytes()
+    }
+
+    /// Saves the graphics state.
+    pub(crate) fn save(&mut self) {
+        self.buf.push_str("q\n");
+    }
+
+    /// Restores the graphics state.
+    pub(crate) fn restore(&mut self) {
+        self.buf.push_str("Q\n");
+    }
+
+    /// Restricts drawing to the rectangle until the next restore().
+    pub(crate) fn clip(&mut self, x: f64, y: f64, w: f64, h: f64) {
+        self.rect(x, y, w, h);
+        self.buf.push_str("re W n\n");
+    }
+
+    pub(crate) fn fill_rect(&mut self, x: f64, y: f64, w: f64, h: f64, color: Rgb<u8>) {
+        self.fill_color(color);
+        self.rect(x, y, w, h);
+        self.buf.push_str("re f\n");
+    }
+
+    /// Draws a line. Double borders are drawn as two thin lines.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn line(
+        &mut self,
+        x0: f64,
+        y0: f64,
+        x1: f64,
+        y1: f64,
+        width: f64,
+        color: Rgb<u8>,
+        style: Border,
+    ) {
+        if style == Border::Double && width >= 1.0 {
+            // offset perpendicular to the line.
+            let len = ((x1 - x0).powi(2) + (y1 - y0).powi(2)).sqrt().max(0.001);
+            let dx = (y1 - y0) / len * width / 3.0;
+            let dy = -(x1 - x0) / len * width / 3.0;
+            self.line(
+                x0 + dx,
+                y0 + dy,
+                x1 + dx,
+                y1 + dy,
+                width / 3.0,
+                color,
+                Border::Solid,
+            );
+            self.line(
+                x0 - dx,
+                y0 - dy,
+                x1 - dx,
+                y1 - dy,
+                width / 3.0,
+                color,
+                Border::Solid,
+            );
+            return;
+        }
+
+        self.stroke_color(color);
+        push_num(&mut self.buf, width);
+        self.buf.push_str("w ");
+        match style {
+            Border::Dotted => {
+                self.buf.push('[');
+                push_num(&mut self.buf, width);
+                push_num(&mut self.buf, width * 2.0);
+                self.buf.push_str("] 0 d ");
+            }
+            Border::Dashed => {
+                self.buf.push('[');
+                push_num(&mut self.buf, width * 4.0 + 1.0);
+                push_num(&mut self.buf, width * 2.0 + 1.0);
+                self.buf.push_str("] 0 d ");
+            }
+            _ => {
+                self.buf.push_str("[] 0 d ");
+            }
+        }
+        push_num(&mut self.buf, x0);
+        push_num(&mut self.buf, y0);
+        self.buf.push_str("m ");
+        push_num(&mut self.buf, x1);
+        push_num(&mut self.buf, y1);
+        self.buf.push_str("l S\n");
+    }
+
+    /// Draws text starting at the baseline position.
+    pub(crate) fn text(
+        &mut self,
+        x: f64,
+        y: f64,
+        font: PdfFont,
+        size: f64,
+        color: Rgb<u8>,
+        text: &str,
+    ) {
+        self.fonts[font.index()] = true;
+        self.fill_color(color);
+        let _ = write!(self.buf, "BT /F{} ", font.index());
+        push_num(&mut self.buf, size);
+        self.buf.push_str("Tf ");
+        push_num(&mut self.buf, x);
+        push_num(&mut self.buf, y);
+        self.buf.push_str("Td ");
+        push_string(&mut self.buf, text);
+        self.buf.push_str(" Tj ET\n");
+    }
+
+    fn rect(&mut self, x: f64, y: f64, w: f64, h: f64) {
+        push_num(&mut self.buf, x);
+        push_num(&mut self.buf, y);
+        push_num(&mut self.buf, w);
+        push_num(&mut self.buf, h);
+    }
+
+    fn fill_color(&mut self, color: Rgb<u8>) {
+        push_num(&mut self.buf, color.r as f64 / 255.0);
+        push_num(&mut self.buf, color.g as f64 / 255.0);
+        push_num(&mut self.buf, color.b as f64 / 255.0);
+        self.buf.push_str("rg ");
+    }
+
+    fn stroke_color(&mut self, color: Rgb<u8>) {
+        push_num(&mut self.buf, color.r as f64 / 255.0);
+        push_num(&mut self.buf, color.g as f64 / 255.0);
+        push_num(&mut self.buf, color.b as f64 / 255.0);
+        self.buf.push_str("RG ");
+    }
+}
@@ -0,0 +1,225 @@
+//!
+//! Metrics and encoding for the standard 14 fonts of PDF.
+//!
+//! Only the Latin fonts are used. They need not be embedded, every PDF
+//! reader has them. Text is encoded with WinAnsiEncoding, characters
+//! outside of it are replaced with '?'.
+//!
+
+/// Widths of the characters 32..=126 of Helvetica.
+const HELVETICA: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, //
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, //
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778, //
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556, //
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556, //
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+/// Widths of the characters 32..=126 of Helvetica-Bold.
+const HELVETICA_BOLD: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278, //
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611, //
+    975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778, //
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556, //
+    333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611, //
+    611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
+
+/// Widths of the characters 32..=126 of Times-Roman.
+const TIMES: [u16; 95] = [
+    250, 333, 408, 500, 500, 833, 778, 180, 333, 333, 500, 564, 250, 333, 250, 278, //
+    500, 500, 500, 500, 500, 500, 500, 500, 500, 500, 278, 278, 564, 564, 564, 444, //
+    921, 722, 667, 667, 722, 611, 556, 722, 722, 333, 389, 722, 611, 889, 722, 722, //
+    556, 722, 667, 556, 611, 722, 722, 944, 722, 722, 611, 333, 278, 333, 469, 500, //
+    333, 444, 500, 444, 500, 444, 333, 500, 500, 278, 278, 500, 278, 778, 500, 500, //
+    500, 500, 333, 389, 278, 500, 500, 722, 500, 500, 444, 480, 200, 480, 541,
+];
+
+/// Widths of the characters 32..=126 of Times-Bold.
+const TIMES_BOLD: [u16; 95] = [
+    250, 333, 555, 500, 500, 1000, 833, 278, 333, 333, 500, 570, 250, 333, 250, 278, //
+    500, 500, 500, 500, 500, 500, 500, 500, 500, 500, 333, 333, 570, 570, 570, 500, //
+    930, 722, 667, 722, 722, 667, 611, 778, 778, 389, 500, 778, 667, 944, 722, 778, //
+    611, 778, 722, 556, 667, 722, 722, 1000, 722, 722, 667, 333, 278, 333, 581, 500, //
+    333, 500, 556, 444, 556, 444, 333, 500, 556, 278, 333, 556, 278, 833, 556, 500, //
+    556, 556, 444, 389, 333, 556, 500, 722, 500, 500, 444, 394, 220, 394, 520,
+];
+
+/// Base letters for U+00C0..=U+00FF. Used to approximate the width of
+/// accented characters.
+const LATIN1_BASE: &[u8; 64] = b"AAAAAAACEEEEIIIIDNOOOOO+OUUUUYPsaaaaaaaceeeeiiiidnooooo+ouuuuypy";
+
+/// Characters of WinAnsiEncoding in the range 0x80..=0x9F.
+const WIN_ANSI_80: [char; 32] = [
+    '€', '\0', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\0', 'Ž', '\0', //
+    '\0', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\0', 'ž', 'Ÿ',
+];
+
+/// Font family of the standard fonts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum PdfFontFamily {
+    Helvetica,
+    Times,
+    Courier,
+}
+
+/// One of the standard fonts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct PdfFont {
+    pub(crate) family: PdfFontFamily,
+    pub(crate) bold: bool,
+    pub(crate) italic: bool,
+}
+
+impl Default for PdfFont {
+    fn default() -> Self {
+        Self {
+            family: PdfFontFamily::Helvetica,
+            bold: false,
+            italic: false,
+        }
+    }
+}
+
+impl PdfFont {
+    /// Number of distinct fonts.
+    pub(crate) const COUNT: usize = 12;
+
+    /// Chooses a standard font for a font-family name. Serif fonts map to
+    /// Times, monospaced fonts to Courier and everything else to Helvetica.
+    pub(crate) fn for_family(family: Option<&str>, bold: bool, italic: bool) -> Self {
+        let family = match family.map(|v| v.to_ascii_lowercase()) {
+            Some(v)
+                if v.contains("mono")
+                    || v.contains("courier")
+                    || v.contains("consolas")
+                    || v.contains("fixed") =>
+            {
+                PdfFontFamily::Courier
+            }
+            Some(v)
+                if v.contains("times")
+                    || v.contains("georgia")
+                    || v.contains("cambria")
+                    || v.contains("garamond")
+                    || v.contains("roman")
+                    || (v.contains("serif") && !v.contains("sans")) =>
+            {
+                PdfFontFamily::Times
+            }
+            _ => PdfFontFamily::Helvetica,
+        };
+        Self {
+            family,
+            bold,
+            italic,
+        }
+    }
+
+    /// Index of the font in 0..COUNT.
+    pub(crate) fn index(&self) -> usize {
+        let family = match self.family {
+            PdfFontFamily::Helvetica => 0,
+            PdfFontFamily::Times => 4,
+            PdfFontFamily::Courier => 8,
+        };
+        family + if self.bold { 1 } else { 0 } + if self.italic { 2 } else { 0 }
+    }
+
+    /// Inverse of index().
+    pub(crate) fn from_index(idx: usize) -> Self {
+        let family = match idx / 4 {
+            0 => PdfFontFamily::Helvetica,
+            1 => PdfFontFamily::Times,
+            _ => PdfFontFamily::Courier,
+        };
+        Self {
+            family,
+            bold: idx % 2 == 1,
+            italic: idx % 4 >= 2,
+        }
+    }
+
+    /// PostScript name of the font.
+    pub(crate) fn base_font(&self) -> &'static str {
+        match (self.family, self.bold, self.italic) {
+            (PdfFontFamily::Helvetica, false, false) => "Helvetica",
+            (PdfFontFamily::Helvetica, true, false) => "Helvetica-Bold",
+            (PdfFontFamily::Helvetica, false, true) => "Helvetica-Oblique",
+            (PdfFontFamily::Helvetica, true, true) => "Helvetica-BoldOblique",
+            (PdfFontFamily::Times, false, false) => "Times-Roman",
+            (PdfFontFamily::Times, true, false) => "Times-Bold",
+            (PdfFontFamily::Times, false, true) => "Times-Italic",
+            (PdfFontFamily::Times, true, true) => "Times-BoldItalic",
+            (PdfFontFamily::Courier, false, false) => "Courier",
+            (PdfFontFamily::Courier, true, false) => "Courier-Bold",
+            (PdfFontFamily::Courier, false, true) => "Courier-Oblique",
+            (PdfFontFamily::Courier, true, true) => "Courier-BoldOblique",
+        }
+    }
+
+    /// Ascender in 1/1000 of the font size.
+    pub(crate) fn ascent(&self) -> f64 {
+        match self.family {
+            PdfFontFamily::Helvetica => 718.0,
+            PdfFontFamily::Times => 683.0,
+            PdfFontFamily::Courier => 629.0,
+        }
+    }
+
+    /// Descender in 1/1000 of the font size. This is a positive value.
+    pub(crate) fn descent(&self) -> f64 {
+        match self.family {
+            PdfFontFamily::Helvetica => 207.0,
+            PdfFontFamily::Times => 217.0,
+            PdfFontFamily::Courier => 157.0,
+        }
+    }
+
+    /// Width of a character in 1/1000 of the font size. Italic variants
+    /// use the widths of the upright font.
+    pub(crate) fn char_width(&self, c: char) -> u16 {
+        if self.family == PdfFontFamily::Courier {
+            return 600;
+        }
+        let table = match (self.family, self.bold) {
+            (PdfFontFamily::Times, false) => &TIMES,
+            (PdfFontFamily::Times, true) => &TIMES_BOLD,
+            (_, false) => &HELVETICA,
+            (_, true) => &HELVETICA_BOLD,
+        };
+        let ascii = |c: u8| table[(c - 32) as usize];
+
+        if win_ansi(c) == b'?' {
+            return ascii(b'?');
+        }
+        match c {
+            ' '..='~' => ascii(c as u8),
+            '\u{A0}' => ascii(b' '),
+            '\u{C0}'..='\u{FF}' => ascii(LATIN1_BASE[c as usize - 0xC0]),
+            '…' | '—' | '‰' | '™' => 1000,
+            '–' | '€' => ascii(b'0'),
+            '‘' | '’' | '‚' | '‹' | '›' => ascii(b'\''),
+            '“' | '”' | '„' => ascii(b'"'),
+            _ => ascii(b'n'),
+        }
+    }
+
+    /// Width of the text in pt.
+    pub(crate) fn text_width(&self, text: &str, size: f64) -> f64 {
+        text.chars().map(|c| self.char_width(c) as f64).sum::<f64>() * size / 1000.0
+    }
+}
+
+/// Encodes a character with WinAnsiEncoding.
+pub(crate) fn win_ansi(c: char) -> u8 {
+    match c {
+        ' '..='~' | '\u{A0}'..='\u{FF}' => c as u8,
+        '\u{202F}' | '\u{2007}' => 0xA0,
+        _ => match WIN_ANSI_80.iter().position(|v| *v == c && c != '\0') {
+            Some(idx) => 0x80 + idx as u8,
+            None => b'?',
+        },
+    }
+}
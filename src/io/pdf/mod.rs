@@ -0,0 +1,11 @@
+//!
+//! PDF export.
+//!
+
+mod display;
+mod document;
+mod font;
+pub(crate) mod write;
+
+#[cfg(test)]
+mod tests;
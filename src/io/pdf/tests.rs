@@ -0,0 +1,181 @@
+use crate::condition::ValueCondition;
+use crate::format::{
+    FormatPart, FormatPartType, ValueFormatDateTime, ValueFormatNumber, ValueFormatTimeDuration,
+    ValueFormatTrait, ValueStyleMap,
+};
+use crate::io::pdf::display::{display_value, general};
+use crate::io::pdf::font::{win_ansi, PdfFont, PdfFontFamily};
+use crate::io::pdf::write::{split_groups, wrap_lines};
+use crate::{Value, WorkBook};
+use chrono::{Duration, NaiveDate};
+use std::collections::BTreeSet;
+
+#[test]
+fn test_general() {
+    assert_eq!(general(0.0), "0");
+    assert_eq!(general(1.5), "1.5");
+    assert_eq!(general(-42.0), "-42");
+    assert_eq!(general(1.0 / 3.0), "0.3333333333");
+    assert_eq!(general(1e12), "1E+12");
+    assert_eq!(general(0.000001), "1E-06");
+}
+
+#[test]
+fn test_display_value() {
+    let mut book = WorkBook::new_empty();
+
+    let mut f1 = ValueFormatNumber::new_named("f1");
+    f1.part_number().fixed_decimal_places(2).grouping().build();
+    book.add_number_format(f1);
+
+    let mut f2 = ValueFormatDateTime::new_named("f2");
+    f2.part_day().long_style().build();
+    f2.part_text(".").build();
+    f2.part_month().long_style().build();
+    f2.part_text(".").build();
+    f2.part_year().long_style().build();
+    book.add_datetime_format(f2);
+
+    let d = display_value(&book, &Value::Number(1234.5), Some("f1"), true);
+    assert_eq!(d.text, "1,234.50");
+    assert!(d.numeric);
+
+    let date = NaiveDate::from_ymd_opt(2024, 3, 7)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+    let d = display_value(&book, &Value::DateTime(date), Some("f2"), true);
+    assert_eq!(d.text, "07.03.2024");
+
+    let d = display_value(&book, &Value::Boolean(true), None, true);
+    assert_eq!(d.text, "TRUE");
+    let d = display_value(&book, &Value::Text("abc".into()), Some("f1"), true);
+    assert_eq!(d.text, "abc");
+    assert!(!d.numeric);
+
+    let d = display_value(&book, &Value::Number(0.0), None, false);
+    assert_eq!(d.text, "");
+}
+
+#[test]
+fn test_display_digits() {
+    let mut book = WorkBook::new_empty();
+
+    let mut f1 = ValueFormatNumber::new_named("f1");
+    let mut part = FormatPart::new(FormatPartType::Number);
+    part.set_attr("number:decimal-places", "100000".into());
+    part.set_attr("number:min-integer-digits", "4294967295".into());
+    f1.push_part(part);
+    book.add_number_format(f1);
+
+    let mut f2 = ValueFormatNumber::new_named("f2");
+    let mut part = FormatPart::new(FormatPartType::ScientificNumber);
+    part.set_attr("number:decimal-places", "70000".into());
+    part.set_attr("number:min-exponent-digits", "70000".into());
+    f2.push_part(part);
+    book.add_number_format(f2);
+
+    let mut f3 = ValueFormatTimeDuration::new_named("f3");
+    let mut part = FormatPart::new(FormatPartType::Seconds);
+    part.set_attr("number:decimal-places", "70000".into());
+    f3.push_part(part);
+    book.add_timeduration_format(f3);
+
+    let d = display_value(&book, &Value::Number(1.5), Some("f1"), true);
+    assert_eq!(d.text, format!("{}1.5{}", "0".repeat(29), "0".repeat(29)));
+    let d = display_value(&book, &Value::Number(1.5), Some("f2"), true);
+    assert_eq!(d.text, format!("1.5{}E+{}", "0".repeat(29), "0".repeat(30)));
+    let d = display_value(
+        &book,
+        &Value::TimeDuration(Duration::seconds(5)),
+        Some("f3"),
+        true,
+    );
+    assert_eq!(d.text, format!("5.{}", "0".repeat(30)));
+}
+
+#[test]
+fn test_display_stylemap() {
+    let mut book = WorkBook::new_empty();
+
+    let mut pos = ValueFormatNumber::new_named("pos");
+    pos.part_number().fixed_decimal_places(2).build();
+    book.add_number_format(pos);
+
+    let mut f1 = ValueFormatNumber::new_named("f1");
+    f1.part_text("minus ").build();
+    f1.part_number().fixed_decimal_places(1).build();
+    f1.push_stylemap(ValueStyleMap::new(ValueCondition::value_ge(0), "pos"));
+    book.add_number_format(f1);
+
+    let d = display_value(&book, &Value::Number(-1.25), Some("f1"), true);
+    assert_eq!(d.text, "minus 1.2");
+    let d = display_value(&book, &Value::Number(1.25), Some("f1"), true);
+    assert_eq!(d.text, "1.25");
+}
+
+#[test]
+fn test_font() {
+    let font = PdfFont::for_family(Some("Liberation Serif"), true, false);
+    assert_eq!(font.family, PdfFontFamily::Times);
+    assert_eq!(font.base_font(), "Times-Bold");
+    assert_eq!(PdfFont::from_index(font.index()), font);
+
+    let font = PdfFont::for_family(Some("Liberation Sans"), false, false);
+    assert_eq!(font.family, PdfFontFamily::Helvetica);
+    assert_eq!(font.text_width("AAA", 10.0), 20.01);
+
+    let font = PdfFont::for_family(Some("DejaVu Sans Mono"), false, true);
+    assert_eq!(font.base_font(), "Courier-Oblique");
+    assert_eq!(font.text_width("äx€", 10.0), 18.0);
+
+    assert_eq!(win_ansi('€'), 0x80);
+    assert_eq!(win_ansi('ü'), 0xFC);
+    assert_eq!(win_ansi('漢'), b'?');
+}
+
+#[test]
+fn test_split_groups() {
+    let no_breaks = BTreeSet::new();
+    assert_eq!(
+        split_groups(0, 9, |_| 10.0, 35.0, None, &no_breaks),
+        vec![(0, 2), (3, 5), (6, 8), (9, 9)]
+    );
+    // hidden rows need no space.
+    assert_eq!(
+        split_groups(
+            0,
+            5,
+            |i| if i % 2 == 0 { 0.0 } else { 10.0 },
+            20.0,
+            None,
+            &no_breaks
+        ),
+        vec![(0, 4), (5, 5)]
+    );
+    // repeated rows use up space after the first page.
+    assert_eq!(
+        split_groups(0, 9, |_| 10.0, 40.0, Some((0, 0)), &no_breaks),
+        vec![(0, 3), (4, 6), (7, 9)]
+    );
+    let breaks = BTreeSet::from([2, 3]);
+    assert_eq!(
+        split_groups(0, 4, |_| 10.0, 100.0, None, &breaks),
+        vec![(0, 1), (2, 2), (3, 4)]
+    );
+}
+
+#[test]
+fn test_wrap_lines() {
+    let font = PdfFont::default();
+    // "aaaa" is 22.24pt at size 10.
+    assert_eq!(
+        wrap_lines("aaaa aaaa aaaa", font, 10.0, 50.0),
+        vec!["aaaa aaaa", "aaaa"]
+    );
+    assert_eq!(
+        wrap_lines("aaaaaaaaaaaa", font, 10.0, 30.0),
+        vec!["aaaaa", "aaaaa", "aa"]
+    );
+    assert_eq!(wrap_lines("a\n\nb", font, 10.0, 30.0), vec!["a", "", "b"]);
+}
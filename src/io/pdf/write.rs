@@ -0,0 +1,1193 @@
+//!
+//! Writes a WorkBook as PDF.
+//!
+
+use crate::color::Rgb;
+use crate::io::pdf::display::{display_value, plain_text, DisplayText};
+use crate::io::pdf::document::{push_string, Canvas, PdfDocument};
+use crate::io::pdf::font::PdfFont;
+use crate::sheet::Visibility;
+use crate::style::units::{
+    Border, CellAlignVertical, FontSize, FontStyle, FontWeight, LengthPercent, LineStyle, Margin,
+    Percent, PrintCentering, PrintOrder, PrintOrientation, TextAlign, TextAlignSource, WrapOption,
+};
+use crate::style::{CellStyle, CellStyleRef, HeaderFooter, PageStyle, PrintScale};
+use crate::text::TextTag;
+use crate::HashMap;
+use crate::{Length, OdsError, Sheet, Value, WorkBook};
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::rc::Rc;
+
+/// A4 in pt.
+const DEFAULT_PAGE_SIZE: (f64, f64) = (595.28, 841.89);
+/// Page margins if the page-style has none. 2cm.
+const DEFAULT_MARGIN: f64 = 56.69;
+/// Space between header/footer and the table. 0.25cm.
+const DEFAULT_HF_SPACING: f64 = 7.09;
+/// Font size if the cell-style has none.
+const DEFAULT_FONT_SIZE: f64 = 10.0;
+/// Font size for headers and footers.
+const HF_FONT_SIZE: f64 = 10.0;
+/// Cell padding if the cell-style has none.
+const DEFAULT_PADDING: f64 = 2.0;
+/// Line height relative to the font size.
+const LINE_HEIGHT: f64 = 1.2;
+/// Scaling for fit-to-pages doesn't go below this.
+const MIN_SCALE: f64 = 0.1;
+
+/// Writes the PDF into a supplied buffer.
+pub fn write_pdf_buf(book: &WorkBook, mut buf: Vec<u8>) -> Result<Vec<u8>, OdsError> {
+    write_pdf_impl(&mut buf, book)?;
+
+    Ok(buf)
+}
+
+/// Writes the PDF to the given Write.
+pub fn write_pdf_to<T: Write>(book: &WorkBook, mut write: T) -> Result<(), OdsError> {
+    write_pdf_impl(&mut write, book)?;
+
+    Ok(())
+}
+
+/// Writes all printable sheets of the workbook as PDF.
+///
+/// Each sheet is paginated according to the page-style of its master-page:
+/// paper size, margins, print-scale and print order. Print ranges, repeated
+/// header rows/columns and manual page breaks are respected, and the
+/// header and footer of the master-page are printed on every page.
+///
+/// Cells are rendered with their displayed value, font, alignment,
+/// borders and background. Merged cells span their area.
+///
+/// Only the standard PDF fonts are used, which are mapped from the
+/// font-family: serif fonts to Times, monospaced fonts to Courier and
+/// everything else to Helvetica. Characters outside of WinAnsiEncoding
+/// are replaced with '?'. Images, drawings and charts are not rendered.
+pub fn write_pdf<P: AsRef<Path>>(book: &WorkBook, pdf_path: P) -> Result<(), OdsError> {
+    let mut write = BufWriter::new(File::create(pdf_path)?);
+
+    write_pdf_impl(&mut write, book)?;
+
+    write.flush()?;
+
+    Ok(())
+}
+
+fn write_pdf_impl<W: Write>(write: W, book: &WorkBook) -> Result<(), OdsError> {
+    let mut sheets = Vec::new();
+    for (idx, sheet) in book.iter_sheets().enumerate() {
+        if !sheet.print() {
+            continue;
+        }
+        let mut render = SheetRender::new(book, idx);
+        let pages = render.paginate();
+        sheets.push((render, pages));
+    }
+    let total = sheets.iter().map(|(_, p)| p.len()).sum::<usize>();
+
+    let now = chrono::Local::now();
+    let fields = FieldValues {
+        pages: total.max(1) as u32,
+        title: book.metadata().title.clone(),
+        date: now.format("%Y-%m-%d").to_string(),
+        time: now.format("%H:%M:%S").to_string(),
+    };
+
+    let mut doc = PdfDocument::new();
+    let catalog = doc.reserve();
+    let pages_id = doc.reserve();
+    let resources = doc.reserve();
+
+    let mut fonts = [false; PdfFont::COUNT];
+    let mut kids = Vec::new();
+    let mut page_no = 0u32;
+    for (render, pages) in sheets.iter_mut() {
+        for (i, page) in pages.iter().enumerate() {
+            page_no = match render.setup.first_page_number {
+                Some(n) if i == 0 => n,
+                _ => page_no + 1,
+            };
+
+            let mut canvas = Canvas::new();
+            render.render_page(&mut canvas, page, page_no, &fields);
+
+            let content = doc.add_stream(canvas.content())?;
+            for (used, page_used) in fonts.iter_mut().zip(canvas.fonts()) {
+                *used |= page_used;
+            }
+            kids.push(doc.add(format!(
+                "<< /Type /Page /Parent {} 0 R /MediaBox [0 0 {} {}] /Resources {} 0 R /Contents {} 0 R >>",
+                pages_id,
+                pt(render.setup.width),
+                pt(render.setup.height),
+                resources,
+                content
+            )));
+        }
+    }
+    if kids.is_empty() {
+        // A PDF needs at least one page.
+        let content = doc.add_stream(&[])?;
+        kids.push(doc.add(format!(
+            "<< /Type /Page /Parent {} 0 R /MediaBox [0 0 {} {}] /Resources {} 0 R /Contents {} 0 R >>",
+            pages_id,
+            pt(DEFAULT_PAGE_SIZE.0),
+            pt(DEFAULT_PAGE_SIZE.1),
+            resources,
+            content
+        )));
+    }
+
+    let mut font_dict = String::new();
+    for (idx, used) in fonts.iter().enumerate() {
+        if *used {
+            let font = doc.add(format!(
+                "<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>",
+                PdfFont::from_index(idx).base_font()
+            ));
+            font_dict.push_str(&format!("/F{} {} 0 R ", idx, font));
+        }
+    }
+    doc.set(resources, format!("<< /Font << {}>> >>", font_dict));
+
+    let kids = kids
+        .iter()
+        .map(|v| format!("{} 0 R", v))
+        .collect::<Vec<_>>()
+        .join(" ");
+    doc.set(
+        pages_id,
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            kids,
+            total.max(1)
+        ),
+    );
+    doc.set(
+        catalog,
+        format!("<< /Type /Catalog /Pages {} 0 R >>", pages_id),
+    );
+
+    let mut info = String::from("<< /Producer ");
+    push_string(&mut info, "spreadsheet-ods");
+    if !fields.title.is_empty() {
+        info.push_str(" /Title ");
+        push_string(&mut info, &fields.title);
+    }
+    info.push_str(" >>");
+    let info = doc.add(info);
+
+    doc.write(catalog, info, write)
+}
+
+/// Formats a length for the PDF.
+fn pt(v: f64) -> f64 {
+    (v * 100.0).round() / 100.0
+}
+
+fn length_pt(v: Length) -> Option<f64> {
+    match v {
+        Length::Default => None,
+        Length::Cm(v) => Some(v * 72.0 / 2.54),
+        Length::Mm(v) => Some(v * 72.0 / 25.4),
+        Length::In(v) => Some(v * 72.0),
+        Length::Pt(v) => Some(v),
+        Length::Pc(v) => Some(v * 12.0),
+        Length::Em(v) => Some(v * 12.0),
+    }
+}
+
+/// Values for the fields in headers and footers.
+struct FieldValues {
+    pages: u32,
+    title: String,
+    date: String,
+    time: String,
+}
+
+/// Header or footer of the page.
+struct HfSetup<'a> {
+    hf: &'a HeaderFooter,
+    /// Used for even pages.
+    hf_left: Option<&'a HeaderFooter>,
+    height: f64,
+    spacing: f64,
+}
+
+/// Page geometry and print options of a sheet. All lengths in pt.
+struct PageSetup<'a> {
+    width: f64,
+    height: f64,
+    margin_left: f64,
+    margin_right: f64,
+    margin_top: f64,
+    margin_bottom: f64,
+    header: Option<HfSetup<'a>>,
+    footer: Option<HfSetup<'a>>,
+    print_scale: Option<PrintScale>,
+    scale: f64,
+    ttb: bool,
+    grid: bool,
+    zero_values: bool,
+    centering: PrintCentering,
+    first_page_number: Option<u32>,
+}
+
+impl<'a> PageSetup<'a> {
+    fn new(book: &'a WorkBook, sheet: &Sheet) -> Self {
+        let masterpage = sheet
+            .style()
+            .and_then(|v| book.tablestyle(v))
+            .and_then(|v| v.attrmap().attr("style:master-page-name"))
+            .and_then(|v| book.masterpage(v))
+            .or_else(|| book.masterpage("Default"));
+        let pagestyle = masterpage
+            .and_then(|v| v.pagestyle())
+            .and_then(|v| book.pagestyle(v));
+
+        let mut setup = PageSetup {
+            width: DEFAULT_PAGE_SIZE.0,
+            height: DEFAULT_PAGE_SIZE.1,
+            margin_left: DEFAULT_MARGIN,
+            margin_right: DEFAULT_MARGIN,
+            margin_top: DEFAULT_MARGIN,
+            margin_bottom: DEFAULT_MARGIN,
+            header: None,
+            footer: None,
+            print_scale: None,
+            scale: 1.0,
+            ttb: true,
+            grid: false,
+            zero_values: true,
+            centering: PrintCentering::None,
+            first_page_number: None,
+        };
+
+        if let Some(ps) = pagestyle {
+            setup.read_pagestyle(ps);
+        }
+        if let Some(mp) = masterpage {
+            setup.header = hf_setup(
+                mp.header(),
+                mp.header_left(),
+                pagestyle.and_then(|v| v.headerstyle().min_height().ok().flatten()),
+                pagestyle.and_then(|v| v.headerstyle().margin_bottom().ok().flatten()),
+            );
+            setup.footer = hf_setup(
+                mp.footer(),
+                mp.footer_left(),
+                pagestyle.and_then(|v| v.footerstyle().min_height().ok().flatten()),
+                pagestyle.and_then(|v| v.footerstyle().margin_top().ok().flatten()),
+            );
+        }
+
+        setup
+    }
+
+    fn read_pagestyle(&mut self, ps: &PageStyle) {
+        let width = ps.page_width().ok().flatten().and_then(length_pt);
+        let height = ps.page_height().ok().flatten().and_then(length_pt);
+        if let (Some(width), Some(height)) = (width, height) {
+            self.width = width;
+            self.height = height;
+        } else if let Ok(Some(PrintOrientation::Landscape)) = ps.print_orientation() {
+            self.width = DEFAULT_PAGE_SIZE.1;
+            self.height = DEFAULT_PAGE_SIZE.0;
+        }
+
+        let margin = |v: Result<Option<Margin>, OdsError>| match v {
+            Ok(Some(Margin::Length(v))) => length_pt(v),
+            _ => None,
+        };
+        let all = margin(ps.margin());
+        self.margin_left = margin(ps.margin_left()).or(all).unwrap_or(DEFAULT_MARGIN);
+        self.margin_right = margin(ps.margin_right()).or(all).unwrap_or(DEFAULT_MARGIN);
+        self.margin_top = margin(ps.margin_top()).or(all).unwrap_or(DEFAULT_MARGIN);
+        self.margin_bottom = margin(ps.margin_bottom()).or(all).unwrap_or(DEFAULT_MARGIN);
+
+        self.print_scale = ps.print_scale().ok().flatten();
+        self.ttb = !matches!(ps.print_page_order(), Ok(Some(PrintOrder::Ltr)));
+        if let Some(print) = ps.style().attr("style:print") {
+            self.grid = print.split_whitespace().any(|v| v == "grid");
+            self.zero_values = print.split_whitespace().any(|v| v == "zero-values");
+        }
+        self.centering = ps
+            .table_centering()
+            .ok()
+            .flatten()
+            .unwrap_or(PrintCentering::None);
+        self.first_page_number = ps.first_page_number().ok().flatten();
+    }
+
+    fn header_space(&self) -> f64 {
+        self.header
+            .as_ref()
+            .map(|v| v.height + v.spacing)
+            .unwrap_or_default()
+    }
+
+    fn footer_space(&self) -> f64 {
+        self.footer
+            .as_ref()
+            .map(|v| v.height + v.spacing)
+            .unwrap_or_default()
+    }
+
+    fn content_left(&self) -> f64 {
+        self.margin_left
+    }
+
+    fn content_top(&self) -> f64 {
+        self.height - self.margin_top - self.header_space()
+    }
+
+    fn content_width(&self) -> f64 {
+        (self.width - self.margin_left - self.margin_right).max(1.0)
+    }
+
+    fn content_height(&self) -> f64 {
+        (self.height
+            - self.margin_top
+            - self.margin_bottom
+            - self.header_space()
+            - self.footer_space())
+        .max(1.0)
+    }
+}
+
+fn hf_setup<'a>(
+    hf: &'a HeaderFooter,
+    hf_left: &'a HeaderFooter,
+    min_height: Option<LengthPercent>,
+    spacing: Option<Margin>,
+) -> Option<HfSetup<'a>> {
+    // display only deactivates the left side.
+    if hf.is_empty() {
+        return None;
+    }
+    let hf_left = Some(hf_left).filter(|v| v.display() && !v.is_empty());
+
+    let lines = [Some(hf), hf_left]
+        .into_iter()
+        .flatten()
+        .flat_map(|v| [v.left(), v.center(), v.right(), v.content()])
+        .map(|v| v.len())
+        .max()
+        .unwrap_or(1)
+        .max(1);
+    let min_height = match min_height {
+        Some(LengthPercent::Length(v)) => length_pt(v).unwrap_or_default(),
+        _ => 0.0,
+    };
+    let spacing = match spacing {
+        Some(Margin::Length(v)) => length_pt(v).unwrap_or(DEFAULT_HF_SPACING),
+        _ => DEFAULT_HF_SPACING,
+    };
+
+    Some(HfSetup {
+        hf,
+        hf_left,
+        height: min_height.max(lines as f64 * HF_FONT_SIZE * LINE_HEIGHT),
+        spacing,
+    })
+}
+
+/// The rows and columns printed on one page. Repeated rows and columns
+/// come first.
+struct Page {
+    rows: Vec<u32>,
+    cols: Vec<u32>,
+}
+
+/// Result of the pagination.
+struct Pagination {
+    pages: Vec<Page>,
+    /// Maximum number of pages in width.
+    width_pages: usize,
+    /// Maximum number of pages in height.
+    height_pages: usize,
+}
+
+/// Layout of a cell derived from the effective cell-style.
+struct CellLook {
+    font: PdfFont,
+    size: f64,
+    color: Rgb<u8>,
+    underline: bool,
+    /// None aligns by value type.
+    align: Option<TextAlign>,
+    valign: CellAlignVertical,
+    wrap: bool,
+    background: Option<Rgb<u8>>,
+    /// left, top, right, bottom
+    borders: [Option<BorderLine>; 4],
+    padding: f64,
+    value_format: Option<String>,
+}
+
+#[derive(Clone, Copy)]
+struct BorderLine {
+    width: f64,
+    style: Border,
+    color: Rgb<u8>,
+}
+
+impl CellLook {
+    fn new(book: &WorkBook, style: &CellStyle) -> Self {
+        let family = style.font_name().map(|v| {
+            book.font(v)
+                .and_then(|v| v.attrmap().attr("svg:font-family"))
+                .map(|v| v.trim_matches(|c| c == '\'' || c == '"'))
+                .unwrap_or(v)
+        });
+        let bold = matches!(
+            style.font_weight(),
+            Ok(Some(
+                FontWeight::Bold
+                    | FontWeight::W600
+                    | FontWeight::W700
+                    | FontWeight::W800
+                    | FontWeight::W900
+            ))
+        );
+        let italic = matches!(
+            style.font_style(),
+            Ok(Some(FontStyle::Italic | FontStyle::Oblique))
+        );
+        let size = match style.font_size() {
+            Ok(Some(FontSize::Length(v))) => length_pt(v),
+            Ok(Some(FontSize::Percent(Percent::Percent(v)))) => Some(DEFAULT_FONT_SIZE * v / 100.0),
+            _ => None,
+        }
+        .filter(|v| *v > 0.0)
+        .unwrap_or(DEFAULT_FONT_SIZE);
+
+        let align = if matches!(
+            style.text_align_source(),
+            Ok(Some(TextAlignSource::ValueType))
+        ) {
+            None
+        } else {
+            style.text_align().ok().flatten()
+        };
+
+        let border =
+            |v: Result<Option<(Length, Border, Rgb<u8>)>, OdsError>| -> Option<BorderLine> {
+                match v {
+                    Ok(Some((width, style, color)))
+                        if !matches!(style, Border::None | Border::Hidden) =>
+                    {
+                        let width = length_pt(width).unwrap_or(0.75);
+                        (width > 0.0).then_some(BorderLine {
+                            width,
+                            style,
+                            color,
+                        })
+                    }
+                    _ => None,
+                }
+            };
+        let all = border(style.border());
+
+        Self {
+            font: PdfFont::for_family(family, bold, italic),
+            size,
+            color: style.color().ok().flatten().unwrap_or(Rgb::new(0, 0, 0)),
+            underline: matches!(style.text_underline_style(), Ok(Some(v)) if v != LineStyle::None),
+            align,
+            valign: style
+                .vertical_align()
+                .ok()
+                .flatten()
+                .unwrap_or(CellAlignVertical::Automatic),
+            wrap: matches!(style.wrap_option(), Ok(Some(WrapOption::Wrap))),
+            background: style.background_color().ok().flatten(),
+            borders: [
+                border(style.border_left()).or(all),
+                border(style.border_top()).or(all),
+                border(style.border_right()).or(all),
+                border(style.border_bottom()).or(all),
+            ],
+            padding: style
+                .padding()
+                .ok()
+                .flatten()
+                .and_then(length_pt)
+                .unwrap_or(DEFAULT_PADDING),
+            value_format: style.value_format().map(|v| v.to_string()),
+        }
+    }
+
+    fn line_height(&self) -> f64 {
+        self.size * LINE_HEIGHT
+    }
+}
+
+type LookKey<'a> = (
+    Option<&'a CellStyleRef>,
+    Option<&'a CellStyleRef>,
+    Option<&'a CellStyleRef>,
+);
+
+/// Renders the pages of one sheet.
+struct SheetRender<'a> {
+    book: &'a WorkBook,
+    idx: usize,
+    sheet: &'a Sheet,
+    setup: PageSetup<'a>,
+    /// Layout for cell-styles without style-maps.
+    looks: HashMap<LookKey<'a>, Rc<CellLook>>,
+    /// Merged cells. Maps every covered cell to the merged range.
+    merged: HashMap<(u32, u32), (u32, u32, u32, u32)>,
+    /// Rows with the default height that need more space.
+    row_heights: HashMap<u32, f64>,
+    row_breaks: BTreeSet<u32>,
+    col_breaks: BTreeSet<u32>,
+}
+
+impl<'a> SheetRender<'a> {
+    fn new(book: &'a WorkBook, idx: usize) -> Self {
+        let sheet = book.sheet(idx);
+        let mut render = Self {
+            book,
+            idx,
+            sheet,
+            setup: PageSetup::new(book, sheet),
+            looks: Default::default(),
+            merged: Default::default(),
+            row_heights: Default::default(),
+            row_breaks: sheet.row_page_breaks().collect(),
+            col_breaks: sheet.col_page_breaks().collect(),
+        };
+
+        let mut merged = Vec::new();
+        let mut cells = Vec::new();
+        for ((row, col), cell) in sheet.iter() {
+            let (row_span, col_span) = (cell.span.row_span(), cell.span.col_span());
+            if row_span > 1 || col_span > 1 {
+                merged.push((row, col, row + row_span - 1, col + col_span - 1));
+            } else if !matches!(cell.value, Value::Empty)
+                && matches!(sheet.row_height(row), Length::Default)
+            {
+                cells.push((row, col));
+            }
+        }
+        for (row, col, to_row, to_col) in merged {
+            for r in row..=to_row {
+                for c in col..=to_col {
+                    render.merged.insert((r, c), (row, col, to_row, to_col));
+                }
+            }
+        }
+        // Rows with the default height grow with their content.
+        for (row, col) in cells {
+            let look = render.look(row, col);
+            let display = render.display(row, col, &look);
+            let lines = if look.wrap {
+                let width = sheet.col_width_pt(col) - 2.0 * look.padding;
+                wrap_lines(&display.text, look.font, look.size, width).len()
+            } else {
+                display.text.lines().count()
+            };
+            let height = lines as f64 * look.line_height() + 2.0 * look.padding;
+            if height > sheet.row_height_pt(row) {
+                let h = render.row_heights.entry(row).or_default();
+                *h = h.max(height);
+            }
+        }
+
+        render
+    }
+
+    fn col_size(&self, col: u32) -> f64 {
+        if self.sheet.col_visible(col) != Visibility::Visible {
+            0.0
+        } else {
+            self.sheet.col_width_pt(col)
+        }
+    }
+
+    fn row_size(&self, row: u32) -> f64 {
+        if self.sheet.row_visible(row) != Visibility::Visible {
+            0.0
+        } else if let Some(height) = self.row_heights.get(&row) {
+            *height
+        } else {
+            self.sheet.row_height_pt(row)
+        }
+    }
+
+    fn look(&mut self, row: u32, col: u32) -> Rc<CellLook> {
+        let book = self.book;
+        let sheet = self.sheet;
+        let style = if let Some(style) = sheet.cellstyle(row, col) {
+            Some(style)
+        } else if !sheet.is_empty(row, col) {
+            book.def_style(sheet.value(row, col).value_type())
+        } else {
+            None
+        };
+        // style-maps depend on the value.
+        let mapped = style
+            .and_then(|v| book.cellstyle(v))
+            .and_then(|v| v.stylemaps())
+            .map(|v| !v.is_empty())
+            .unwrap_or(false);
+        let key = (style, sheet.row_cellstyle(row), sheet.col_cellstyle(col));
+        if !mapped {
+            if let Some(look) = self.looks.get(&key) {
+                return Rc::clone(look);
+            }
+        }
+
+        let look = Rc::new(CellLook::new(
+            book,
+            &book.effective_cell_style(self.idx, row, col),
+        ));
+        if !mapped {
+            self.looks.insert(key, Rc::clone(&look));
+        }
+        look
+    }
+
+    fn display(&self, row: u32, col: u32, look: &CellLook) -> DisplayText {
+        display_value(
+            self.book,
+            self.sheet.value(row, col),
+            look.value_format.as_deref(),
+            self.setup.zero_values,
+        )
+    }
+
+    /// The areas to print. Either the print ranges or the used area.
+    fn print_areas(&self) -> Vec<(u32, u32, u32, u32)> {
+        let (rows, cols) = self.sheet.used_grid_size();
+        // merged cells may reach beyond the used area.
+        let (rows, cols) = self
+            .merged
+            .values()
+            .fold((rows, cols), |(rows, cols), (_, _, to_row, to_col)| {
+                (rows.max(to_row + 1), cols.max(to_col + 1))
+            });
+
+        match self.sheet.print_ranges().filter(|v| !v.is_empty()) {
+            Some(ranges) => ranges
+                .iter()
+                .filter(|v| {
+                    v.table()
+                        .map(|t| t.as_str() == self.sheet.name())
+                        .unwrap_or(true)
+                })
+                .map(|v| {
+                    (
+                        v.row(),
+                        v.col(),
+                        v.to_row().min(rows.saturating_sub(1)).max(v.row()),
+                        v.to_col().min(cols.saturating_sub(1)).max(v.col()),
+                    )
+                })
+                .collect(),
+            None if rows > 0 && cols > 0 => vec![(0, 0, rows - 1, cols - 1)],
+            None => Vec::new(),
+        }
+    }
+
+    /// Splits the print areas into pages. Applies the print-scale.
+    fn paginate(&mut self) -> Vec<Page> {
+        let mut scale = match self.setup.print_scale {
+            Some(PrintScale::Percent(v)) if v > 0.0 => v / 100.0,
+            _ => 1.0,
+        };
+        let mut pagination = self.paginate_scaled(scale);
+        loop {
+            let fits = match self.setup.print_scale {
+                Some(PrintScale::Pages(n)) if n > 0 => pagination.pages.len() <= n as usize,
+                Some(PrintScale::FitTo { width, height }) => {
+                    (width == 0 || pagination.width_pages <= width as usize)
+                        && (height == 0 || pagination.height_pages <= height as usize)
+                }
+                _ => true,
+            };
+            if fits || scale <= MIN_SCALE {
+                break;
+            }
+            scale = (scale * 0.95).max(MIN_SCALE);
+            pagination = self.paginate_scaled(scale);
+        }
+        self.setup.scale = scale;
+        pagination.pages
+    }
+
+    fn paginate_scaled(&self, scale: f64) -> Pagination {
+        let width = self.setup.content_width() / scale;
+        let height = self.setup.content_height() / scale;
+        let header_rows = self.sheet.header_rows().map(|v| (v.from, v.to));
+        let header_cols = self.sheet.header_cols().map(|v| (v.from, v.to));
+
+        let mut pagination = Pagination {
+            pages: Vec::new(),
+            width_pages: 0,
+            height_pages: 0,
+        };
+        for (row, col, to_row, to_col) in self.print_areas() {
+            let col_groups = split_groups(
+                col,
+                to_col,
+                |c| self.col_size(c),
+                width,
+                header_cols,
+                &self.col_breaks,
+            );
+            let row_groups = split_groups(
+                row,
+                to_row,
+                |r| self.row_size(r),
+                height,
+                header_rows,
+                &self.row_breaks,
+            );
+            pagination.width_pages = pagination.width_pages.max(col_groups.len());
+            pagination.height_pages = pagination.height_pages.max(row_groups.len());
+
+            let mut groups = Vec::new();
+            if self.setup.ttb {
+                for c in &col_groups {
+                    for r in &row_groups {
+                        groups.push((*r, *c));
+                    }
+                }
+            } else {
+                for r in &row_groups {
+                    for c in &col_groups {
+                        groups.push((*r, *c));
+                    }
+                }
+            }
+
+            for ((row, to_row), (col, to_col)) in groups {
+                if !self.has_content(row, col, to_row, to_col) {
+                    continue;
+                }
+                let rows = with_repeated(row, to_row, header_rows)
+                    .filter(|r| self.row_size(*r) > 0.0)
+                    .collect();
+                let cols = with_repeated(col, to_col, header_cols)
+                    .filter(|c| self.col_size(*c) > 0.0)
+                    .collect();
+                pagination.pages.push(Page { rows, cols });
+            }
+        }
+        pagination
+    }
+
+    /// Empty pages are not printed.
+    fn has_content(&self, row: u32, col: u32, to_row: u32, to_col: u32) -> bool {
+        for r in row..=to_row {
+            for c in col..=to_col {
+                if !self.sheet.is_empty(r, c) || self.merged.contains_key(&(r, c)) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    fn render_page(
+        &mut self,
+        canvas: &mut Canvas,
+        page: &Page,
+        page_no: u32,
+        fields: &FieldValues,
+    ) {
+        let scale = self.setup.scale;
+
+        // positions of rows and columns.
+        let table_width = page.cols.iter().map(|c| self.col_size(*c)).sum::<f64>() * scale;
+        let table_height = page.rows.iter().map(|r| self.row_size(*r)).sum::<f64>() * scale;
+        let mut x = self.setup.content_left();
+        if matches!(
+            self.setup.centering,
+            PrintCentering::Horizontal | PrintCentering::Both
+        ) {
+            x += ((self.setup.content_width() - table_width) / 2.0).max(0.0);
+        }
+        let mut y = self.setup.content_top();
+        if matches!(
+            self.setup.centering,
+            PrintCentering::Vertical | PrintCentering::Both
+        ) {
+            y -= ((self.setup.content_height() - table_height) / 2.0).max(0.0);
+        }
+        let mut col_pos = HashMap::new();
+        for (i, c) in page.cols.iter().enumerate() {
+            let w = self.col_size(*c) * scale;
+            col_pos.insert(*c, (i, x, w));
+            x += w;
+        }
+        let mut row_pos = HashMap::new();
+        for r in &page.rows {
+            let h = self.row_size(*r) * scale;
+            y -= h;
+            row_pos.insert(*r, (y, h));
+        }
+
+        // cells and merged cells on this page.
+        let mut regions = Vec::new();
+        let mut seen = BTreeSet::new();
+        for r in &page.rows {
+            for c in &page.cols {
+                let region = if let Some((row, col, to_row, to_col)) = self.merged.get(&(*r, *c)) {
+                    if !seen.insert((*row, *col)) {
+                        continue;
+                    }
+                    let cols = (*col..=*to_col).filter_map(|v| col_pos.get(&v));
+                    let rows = (*row..=*to_row).filter_map(|v| row_pos.get(&v));
+                    let x0 = cols.clone().map(|v| v.1).fold(f64::MAX, f64::min);
+                    let x1 = cols.map(|v| v.1 + v.2).fold(f64::MIN, f64::max);
+                    let y0 = rows.clone().map(|v| v.0).fold(f64::MAX, f64::min);
+                    let y1 = rows.map(|v| v.0 + v.1).fold(f64::MIN, f64::max);
+                    Region {
+                        row: *row,
+                        col: *col,
+                        merged: true,
+                        x: x0,
+                        y: y0,
+                        w: x1 - x0,
+                        h: y1 - y0,
+                    }
+                } else {
+                    let (_, x, w) = col_pos[c];
+                    let (y, h) = row_pos[r];
+                    Region {
+                        row: *r,
+                        col: *c,
+                        merged: false,
+                        x,
+                        y,
+                        w,
+                        h,
+                    }
+                };
+                regions.push(region);
+            }
+        }
+        let looks = regions
+            .iter()
+            .map(|v| self.look(v.row, v.col))
+            .collect::<Vec<_>>();
+
+        for (region, look) in regions.iter().zip(&looks) {
+            if let Some(color) = look.background {
+                canvas.fill_rect(region.x, region.y, region.w, region.h, color);
+            }
+        }
+        if self.setup.grid {
+            for region in &regions {
+                let (x0, y0) = (region.x, region.y);
+                let (x1, y1) = (region.x + region.w, region.y + region.h);
+                let color = Rgb::new(192, 192, 192);
+                canvas.line(x0, y0, x1, y0, 0.25, color, Border::Solid);
+                canvas.line(x0, y1, x1, y1, 0.25, color, Border::Solid);
+                canvas.line(x0, y0, x0, y1, 0.25, color, Border::Solid);
+                canvas.line(x1, y0, x1, y1, 0.25, color, Border::Solid);
+            }
+        }
+        for (region, look) in regions.iter().zip(&looks) {
+            self.render_text(canvas, page, &col_pos, region, look);
+        }
+        for (region, look) in regions.iter().zip(&looks) {
+            let (x0, y0) = (region.x, region.y);
+            let (x1, y1) = (region.x + region.w, region.y + region.h);
+            let [left, top, right, bottom] = look.borders;
+            if let Some(b) = left {
+                canvas.line(x0, y0, x0, y1, b.width * scale, b.color, b.style);
+            }
+            if let Some(b) = top {
+                canvas.line(x0, y1, x1, y1, b.width * scale, b.color, b.style);
+            }
+            if let Some(b) = right {
+                canvas.line(x1, y0, x1, y1, b.width * scale, b.color, b.style);
+            }
+            if let Some(b) = bottom {
+                canvas.line(x0, y0, x1, y0, b.width * scale, b.color, b.style);
+            }
+        }
+
+        let even = page_no.is_multiple_of(2);
+        if let Some(header) = &self.setup.header {
+            let top = self.setup.height - self.setup.margin_top;
+            self.render_hf(canvas, header, top, even, page_no, fields);
+        }
+        if let Some(footer) = &self.setup.footer {
+            let top = self.setup.margin_bottom + footer.height;
+            self.render_hf(canvas, footer, top, even, page_no, fields);
+        }
+    }
+
+    fn render_text(
+        &self,
+        canvas: &mut Canvas,
+        page: &Page,
+        col_pos: &HashMap<u32, (usize, f64, f64)>,
+        region: &Region,
+        look: &CellLook,
+    ) {
+        let display = self.display(region.row, region.col, look);
+        if display.text.is_empty() {
+            return;
+        }
+        let scale = self.setup.scale;
+        let size = look.size * scale;
+        let padding = look.padding * scale;
+        let avail = region.w - 2.0 * padding;
+
+        let align = match look.align {
+            Some(TextAlign::End | TextAlign::Right) => TextAlign::Right,
+            Some(TextAlign::Center) => TextAlign::Center,
+            Some(_) => TextAlign::Left,
+            None if display.numeric => TextAlign::Right,
+            None => TextAlign::Left,
+        };
+
+        let mut lines = if look.wrap {
+            wrap_lines(&display.text, look.font, size, avail)
+        } else {
+            display.text.lines().map(|v| v.to_string()).collect()
+        };
+
+        // Clipping area. Text that doesn't fit can flow into empty cells.
+        let (mut x0, mut x1) = (region.x, region.x + region.w);
+        let widest = lines
+            .iter()
+            .map(|v| look.font.text_width(v, size))
+            .fold(0.0, f64::max);
+        if widest > avail && !look.wrap {
+            if display.numeric {
+                let hash = look.font.text_width("#", size);
+                let n = (avail / hash).floor().max(1.0) as usize;
+                lines = vec!["#".repeat(n)];
+            } else if !region.merged {
+                let (idx, _, _) = col_pos[&region.col];
+                let mut need = widest - avail;
+                let grow_right = align != TextAlign::Right;
+                let grow_left = align != TextAlign::Left;
+                let mut right = idx + 1;
+                let mut left = idx;
+                while need > 0.0 {
+                    let mut grown = false;
+                    if grow_right
+                        && right < page.cols.len()
+                        && self.is_free(region.row, page.cols[right])
+                    {
+                        let (_, _, w) = col_pos[&page.cols[right]];
+                        x1 += w;
+                        need -= w;
+                        right += 1;
+                        grown = true;
+                    }
+                    if grow_left
+                        && need > 0.0
+                        && left > 0
+                        && self.is_free(region.row, page.cols[left - 1])
+                    {
+                        let (_, _, w) = col_pos[&page.cols[left - 1]];
+                        x0 -= w;
+                        need -= w;
+                        left -= 1;
+                        grown = true;
+                    }
+                    if !grown {
+                        break;
+                    }
+                }
+            }
+        }
+
+        let font = look.font;
+        let ascent = font.ascent() * size / 1000.0;
+        let descent = font.descent() * size / 1000.0;
+        let line_height = size * LINE_HEIGHT;
+        let block = lines.len() as f64 * line_height;
+        let block_top = match look.valign {
+            CellAlignVertical::Top => region.y + region.h - padding,
+            CellAlignVertical::Middle => region.y + (region.h + block) / 2.0,
+            CellAlignVertical::Bottom | CellAlignVertical::Automatic => region.y + padding + block,
+        };
+        let first_baseline = block_top - (line_height - ascent - descent) / 2.0 - ascent;
+        let color = display.color.unwrap_or(look.color);
+
+        canvas.save();
+        canvas.clip(x0, region.y, x1 - x0, region.h);
+        for (i, line) in lines.iter().enumerate() {
+            let width = font.text_width(line, size);
+            let x = match align {
+                TextAlign::Right => region.x + region.w - padding - width,
+                TextAlign::Center => region.x + (region.w - width) / 2.0,
+                _ => region.x + padding,
+            };
+            let y = first_baseline - i as f64 * line_height;
+            canvas.text(x, y, font, size, color, line);
+            if look.underline {
+                let uy = y - descent / 2.0;
+                canvas.line(x, uy, x + width, uy, size / 15.0, color, Border::Solid);
+            }
+        }
+        canvas.restore();
+    }
+
+    /// Text can flow into this cell.
+    fn is_free(&self, row: u32, col: u32) -> bool {
+        self.sheet.is_empty(row, col) && !self.merged.contains_key(&(row, col))
+    }
+
+    fn render_hf(
+        &self,
+        canvas: &mut Canvas,
+        hf: &HfSetup<'_>,
+        top: f64,
+        even: bool,
+        page_no: u32,
+        fields: &FieldValues,
+    ) {
+        let content = match hf.hf_left {
+            Some(left) if even => left,
+            _ => hf.hf,
+        };
+        let field = |tag: &TextTag| -> Option<String> {
+            match tag.name() {
+                "text:page-number" => Some(page_no.to_string()),
+                "text:page-count" => Some(fields.pages.to_string()),
+                "text:sheet-name" => Some(self.sheet.name().to_string()),
+                "text:title" if !fields.title.is_empty() => Some(fields.title.clone()),
+                "text:date" => Some(fields.date.clone()),
+                "text:time" => Some(fields.time.clone()),
+                _ => None,
+            }
+        };
+
+        let regions = if content.left().is_empty()
+            && content.center().is_empty()
+            && content.right().is_empty()
+        {
+            vec![(content.content(), TextAlign::Left)]
+        } else {
+            vec![
+                (content.left(), TextAlign::Left),
+                (content.center(), TextAlign::Center),
+                (content.right(), TextAlign::Right),
+            ]
+        };
+
+        let font = PdfFont::default();
+        let size = HF_FONT_SIZE;
+        let left = self.setup.margin_left;
+        let right = self.setup.width - self.setup.margin_right;
+        for (tags, align) in regions {
+            for (i, line) in plain_text(tags, &field).iter().enumerate() {
+                let width = font.text_width(line, size);
+                let x = match align {
+                    TextAlign::Right => right - width,
+                    TextAlign::Center => (left + right - width) / 2.0,
+                    _ => left,
+                };
+                let y = top - font.ascent() * size / 1000.0 - i as f64 * size * LINE_HEIGHT;
+                canvas.text(x, y, font, size, Rgb::new(0, 0, 0), line);
+            }
+        }
+    }
+}
+
+/// A cell or a merged range on the page.
+struct Region {
+    row: u32,
+    col: u32,
+    merged: bool,
+    x: f64,
+    y: f64,
+    w: f64,
+    h: f64,
+}
+
+/// Prepends the repeated rows/columns if the group starts after them.
+fn with_repeated(start: u32, end: u32, repeat: Option<(u32, u32)>) -> impl Iterator<Item = u32> {
+    let repeat = match repeat {
+        Some((from, to)) if start > to => Some(from..=to),
+        _ => None,
+    };
+    repeat.into_iter().flatten().chain(start..=end)
+}
+
+/// Splits start..=end into groups that fit into the available size.
+/// Repeated rows/columns use up space on every page after them, and
+/// a manual break always starts a new group.
+pub(crate) fn split_groups(
+    start: u32,
+    end: u32,
+    size: impl Fn(u32) -> f64,
+    avail: f64,
+    repeat: Option<(u32, u32)>,
+    breaks: &BTreeSet<u32>,
+) -> Vec<(u32, u32)> {
+    let repeat_size = repeat
+        .map(|(from, to)| (from..=to).map(&size).sum::<f64>())
+        .unwrap_or_default();
+    let avail_for = |group_start: u32| match repeat {
+        // keep some space if the repeated part is too big.
+        Some((_, to)) if group_start > to => (avail - repeat_size).max(avail / 4.0),
+        _ => avail,
+    };
+
+    let mut groups = Vec::new();
+    let mut group_start = start;
+    let mut used = 0.0;
+    for i in start..=end {
+        let s = size(i);
+        if i > group_start
+            && (breaks.contains(&i) || (s > 0.0 && used + s > avail_for(group_start)))
+        {
+            groups.push((group_start, i - 1));
+            group_start = i;
+            used = 0.0;
+        }
+        used += s;
+    }
+    groups.push((group_start, end));
+    groups
+}
+
+/// Breaks the text into lines that fit the width. Words that are too
+/// long are broken anywhere.
+pub(crate) fn wrap_lines(text: &str, font: PdfFont, size: f64, width: f64) -> Vec<String> {
+    let mut lines = Vec::new();
+    for para in text.split('\n') {
+        let mut line = String::new();
+        for word in para.split(' ') {
+            let candidate = if line.is_empty() {
+                word.to_string()
+            } else {
+                format!("{} {}", line, word)
+            };
+            if font.text_width(&candidate, size) <= width || line.is_empty() && word.is_empty() {
+                line = candidate;
+                continue;
+            }
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            // break long words.
+            for c in word.chars() {
+                line.push(c);
+                if font.text_width(&line, size) > width && line.chars().count() > 1 {
+                    line.pop();
+                    lines.push(std::mem::take(&mut line));
+                    line.push(c);
+                }
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
@@ -10,20 +10,25 @@ use std::str::from_utf8;
 use chrono::{Duration, NaiveDateTime};
 use quick_xml::events::attributes::Attribute;
 use quick_xml::events::{BytesStart, Event};
+use rust_decimal::Decimal;
 use zip::ZipArchive;
 
 use crate::attrmap2::AttrMap2;
 use crate::cell_::CellData;
 use crate::condition::{Condition, ValueCondition};
 use crate::config::{Config, ConfigItem, ConfigItemType, ConfigValue};
-use crate::draw::{Annotation, DrawFrame, DrawFrameContent, DrawImage};
+use crate::draw::{
+    Annotation, DrawFrame, DrawFrameContent, DrawImage, DrawShape, DrawShapeKind, DrawTextBox,
+    PageDrawing,
+};
 use crate::ds::detach::Detach;
 use crate::error::OdsError;
+use crate::form::{Form, FormControl, FormControlKind, FormItem};
 use crate::format::{FormatPart, FormatPartType, ValueFormatTrait, ValueStyleMap};
 use crate::io::parse::{
-    parse_bool, parse_currency, parse_datetime, parse_duration, parse_f64, parse_i16, parse_i32,
-    parse_i64, parse_string, parse_u32, parse_visibility, parse_xlink_actuate, parse_xlink_show,
-    parse_xlink_type,
+    parse_bool, parse_currency, parse_datetime, parse_decimal, parse_duration, parse_f64,
+    parse_i16, parse_i32, parse_i64, parse_string, parse_u32, parse_visibility,
+    parse_xlink_actuate, parse_xlink_show, parse_xlink_type,
 };
 use crate::io::NamespaceMap;
 use crate::manifest::Manifest;
@@ -36,19 +41,21 @@ use crate::sheet::{Grouped, SplitMode};
 use crate::sheet_::{dedup_colheader, CellDataIter, CellDataIterMut, ColHeader, RowHeader};
 use crate::style::stylemap::StyleMap;
 use crate::style::tabstop::TabStop;
+use crate::style::units::PageBreak;
 use crate::style::{
     AnyStyleRef, ColStyle, ColStyleRef, FontFaceDecl, GraphicStyle, HeaderFooter, MasterPage,
     MasterPageRef, PageStyle, ParagraphStyle, RowStyle, RowStyleRef, RubyStyle, StyleOrigin,
     StyleUse, TableStyle, TableStyleRef, TextStyle,
 };
 use crate::text::{TextP, TextTag};
+use crate::validate::{validate, Issue, IssueKind};
 use crate::validation::{MessageType, Validation, ValidationError, ValidationHelp, ValidationRef};
 use crate::workbook::{EventListener, Script};
 use crate::xmltree::XmlTag;
 use crate::{
-    CellStyle, CellStyleRef, Length, Sheet, Value, ValueFormatBoolean, ValueFormatCurrency,
-    ValueFormatDateTime, ValueFormatNumber, ValueFormatPercentage, ValueFormatText,
-    ValueFormatTimeDuration, ValueType, WorkBook,
+    CellStyle, CellStyleRef, FormulaError, Length, Sheet, Value, ValueFormatBoolean,
+    ValueFormatCurrency, ValueFormatDateTime, ValueFormatNumber, ValueFormatPercentage,
+    ValueFormatText, ValueFormatTimeDuration, ValueType, WorkBook,
 };
 
 type OdsXmlReader<'a> = quick_xml::Reader<&'a mut dyn BufRead>;
@@ -62,6 +69,10 @@ pub struct OdsOptions {
     use_repeat_for_cells: bool,
     // ignore empty cells.
     ignore_empty_cells: bool,
+    // collect issues.
+    collect_issues: bool,
+    // numbers as decimal.
+    use_decimal: bool,
 }
 
 impl OdsOptions {
@@ -130,6 +141,27 @@ impl OdsOptions {
         self
     }
 
+    /// Collects the problems found while reading.
+    ///
+    /// Elements and attributes the reader doesn't understand are reported
+    /// with their position in the file. After reading validate() is run
+    /// on the result. All issues are available via WorkBook::issues().
+    pub fn collect_issues(mut self) -> Self {
+        self.collect_issues = true;
+        self
+    }
+
+    /// Numbers, percentages and currencies are read as decimals.
+    ///
+    /// The values are stored as Value::NumberDecimal, Value::PercentageDecimal
+    /// and Value::CurrencyDecimal and are written back exactly as they were
+    /// read. Values that have no exact decimal representation are read as
+    /// f64 as before.
+    pub fn use_decimal(mut self) -> Self {
+        self.use_decimal = true;
+        self
+    }
+
     /// Reads a .ods file.
     pub fn read_ods<T: Read + Seek>(&self, read: T) -> Result<WorkBook, OdsError> {
         let zip = ZipArchive::new(read)?;
@@ -193,6 +225,12 @@ struct OdsContext {
     content_only: bool,
     use_repeat_for_cells: bool,
     ignore_empty_cells: bool,
+    collect_issues: bool,
+    use_decimal: bool,
+
+    // current file for the issue location.
+    part: &'static str,
+    issues: Vec<Issue>,
 
     buffers: Vec<Vec<u8>>,
     xml_buffer: Vec<XmlTag>,
@@ -206,6 +244,8 @@ impl OdsContext {
             content_only: options.content_only,
             use_repeat_for_cells: options.use_repeat_for_cells,
             ignore_empty_cells: options.ignore_empty_cells,
+            collect_issues: options.collect_issues,
+            use_decimal: options.use_decimal,
             ..Default::default()
         }
     }
@@ -247,10 +287,20 @@ impl OdsContext {
         buf.clear();
         self.buffers.push(buf);
     }
+
+    // Validates the result and moves all issues to the workbook.
+    fn finish_issues(&mut self) {
+        if self.collect_issues {
+            let mut issues = mem::take(&mut self.issues);
+            issues.extend(validate(&self.book));
+            self.book.issues = issues;
+        }
+    }
 }
 
 fn read_fods_impl(read: &mut dyn BufRead, options: &OdsOptions) -> Result<WorkBook, OdsError> {
     let mut ctx = OdsContext::new(options);
+    ctx.part = "fods";
     let mut xml = quick_xml::Reader::from_reader(read);
 
     let mut buf = ctx.pop_buf();
@@ -262,7 +312,7 @@ fn read_fods_impl(read: &mut dyn BufRead, options: &OdsOptions) -> Result<WorkBo
 
         match &evt {
             Event::Start(xml_tag) if xml_tag.name().as_ref() == b"office:document" => {
-                let (version, xmlns) = read_namespaces_and_version(&mut xml, xml_tag)?;
+                let (version, xmlns) = read_namespaces_and_version(&mut ctx, &mut xml, xml_tag)?;
                 ctx.book.xmlns.insert("fods.xml".to_string(), xmlns);
                 if let Some(version) = version {
                     ctx.book.set_version(version);
@@ -279,6 +329,7 @@ fn read_fods_impl(read: &mut dyn BufRead, options: &OdsOptions) -> Result<WorkBo
             Event::Start(xml_tag) if xml_tag.name().as_ref() == b"office:scripts" => {
                 read_scripts(&mut ctx, &mut xml)?
             }
+            Event::Empty(xml_tag) if xml_tag.name().as_ref() == b"office:font-face-decls" => {}
             Event::Start(xml_tag) if xml_tag.name().as_ref() == b"office:font-face-decls" => {
                 read_office_font_face_decls(&mut ctx, &mut xml, StyleOrigin::Content)?
             }
@@ -300,7 +351,7 @@ fn read_fods_impl(read: &mut dyn BufRead, options: &OdsOptions) -> Result<WorkBo
                 break;
             }
             _ => {
-                unused_event("read_fods_content", &evt)?;
+                unused_event(&mut ctx, &xml, "read_fods_content", &evt)?;
             }
         }
     }
@@ -311,6 +362,8 @@ fn read_fods_impl(read: &mut dyn BufRead, options: &OdsOptions) -> Result<WorkBo
     // We do some data duplication here, to make everything easier to use.
     calc_derived(&mut ctx.book)?;
 
+    ctx.finish_issues();
+
     Ok(ctx.book)
 }
 
@@ -319,6 +372,7 @@ fn read_fods_impl_content_only(
     options: &OdsOptions,
 ) -> Result<WorkBook, OdsError> {
     let mut ctx = OdsContext::new(options);
+    ctx.part = "fods";
     let mut xml: quick_xml::Reader<&mut dyn BufRead> = quick_xml::Reader::from_reader(read);
 
     let mut buf = ctx.pop_buf();
@@ -344,6 +398,8 @@ fn read_fods_impl_content_only(
 
     calculations(&mut ctx)?;
 
+    ctx.finish_issues();
+
     Ok(ctx.book)
 }
 
@@ -359,6 +415,7 @@ fn read_ods_impl<R: Read + Seek>(
         let read: &mut dyn BufRead = &mut read;
         let mut xml = quick_xml::Reader::from_reader(read);
 
+        ctx.part = "META-INF/manifest.xml";
         read_ods_manifest(&mut ctx, &mut xml)?;
     }
 
@@ -369,6 +426,7 @@ fn read_ods_impl<R: Read + Seek>(
         let read: &mut dyn BufRead = &mut read;
         let mut xml = quick_xml::Reader::from_reader(read);
 
+        ctx.part = "meta.xml";
         read_ods_metadata(&mut ctx, &mut xml)?;
     }
 
@@ -376,6 +434,7 @@ fn read_ods_impl<R: Read + Seek>(
         let mut read = BufReader::new(z);
         let read: &mut dyn BufRead = &mut read;
         let mut xml = quick_xml::Reader::from_reader(read);
+        ctx.part = "settings.xml";
         read_ods_settings(&mut ctx, &mut xml)?;
     }
 
@@ -383,6 +442,7 @@ fn read_ods_impl<R: Read + Seek>(
         let mut read = BufReader::new(z);
         let read: &mut dyn BufRead = &mut read;
         let mut xml = quick_xml::Reader::from_reader(read);
+        ctx.part = "styles.xml";
         read_ods_styles(&mut ctx, &mut xml)?;
     }
 
@@ -390,6 +450,7 @@ fn read_ods_impl<R: Read + Seek>(
         let mut read = BufReader::new(zip.by_name("content.xml")?);
         let read: &mut dyn BufRead = &mut read;
         let mut xml = quick_xml::Reader::from_reader(read);
+        ctx.part = "content.xml";
         read_ods_content(&mut ctx, &mut xml)?;
     }
 
@@ -398,6 +459,8 @@ fn read_ods_impl<R: Read + Seek>(
     // We do some data duplication here, to make everything easier to use.
     calc_derived(&mut ctx.book)?;
 
+    ctx.finish_issues();
+
     Ok(ctx.book)
 }
 
@@ -413,10 +476,13 @@ fn read_ods_impl_content_only<R: Read + Seek>(
     let mut xml = quick_xml::Reader::from_reader(read);
 
     // todo: this still reads styles etc from content.xml
+    ctx.part = "content.xml";
     read_ods_content(&mut ctx, &mut xml)?;
 
     calculations(&mut ctx)?;
 
+    ctx.finish_issues();
+
     Ok(ctx.book)
 }
 
@@ -475,7 +541,7 @@ fn read_ods_manifest(ctx: &mut OdsContext, xml: &mut OdsXmlReader<'_>) -> Result
                 break;
             }
             _ => {
-                unused_event("read_manifest", &evt)?;
+                unused_event(ctx, xml, "read_manifest", &evt)?;
             }
         }
         buf.clear();
@@ -645,6 +711,7 @@ fn calc_derived(book: &mut WorkBook) -> Result<(), OdsError> {
                     } else {
                         ch.width = style.col_width()?;
                     }
+                    ch.page_break = style.break_before()? == Some(PageBreak::Page);
                 }
             }
         }
@@ -658,6 +725,7 @@ fn calc_derived(book: &mut WorkBook) -> Result<(), OdsError> {
                     } else {
                         rh.height = style.row_height()?;
                     }
+                    rh.page_break = style.break_before()? == Some(PageBreak::Page);
                 }
             }
         }
@@ -733,7 +801,7 @@ fn read_ods_content(ctx: &mut OdsContext, xml: &mut OdsXmlReader<'_>) -> Result<
             Event::Decl(_) => {}
 
             Event::Start(xml_tag) if xml_tag.name().as_ref() == b"office:document-content" => {
-                let (version, xmlns) = read_namespaces_and_version(xml, xml_tag)?;
+                let (version, xmlns) = read_namespaces_and_version(ctx, xml, xml_tag)?;
                 if let Some(version) = version {
                     ctx.book.set_version(version);
                 }
@@ -745,6 +813,7 @@ fn read_ods_content(ctx: &mut OdsContext, xml: &mut OdsXmlReader<'_>) -> Result<
             Event::Start(xml_tag) if xml_tag.name().as_ref() == b"office:scripts" => {
                 read_scripts(ctx, xml)?
             }
+            Event::Empty(xml_tag) if xml_tag.name().as_ref() == b"office:font-face-decls" => {}
             Event::Start(xml_tag) if xml_tag.name().as_ref() == b"office:font-face-decls" => {
                 read_office_font_face_decls(ctx, xml, StyleOrigin::Content)?
             }
@@ -765,7 +834,7 @@ fn read_ods_content(ctx: &mut OdsContext, xml: &mut OdsXmlReader<'_>) -> Result<
                 break;
             }
             _ => {
-                unused_event("read_ods_content", &evt)?;
+                unused_event(ctx, xml, "read_ods_content", &evt)?;
             }
         }
 
@@ -849,7 +918,7 @@ fn read_office_body(ctx: &mut OdsContext, xml: &mut OdsXmlReader<'_>) -> Result<
                 break;
             }
             _ => {
-                unused_event("read_office_body", &evt)?;
+                unused_event(ctx, xml, "read_office_body", &evt)?;
             }
         }
 
@@ -861,6 +930,7 @@ fn read_office_body(ctx: &mut OdsContext, xml: &mut OdsXmlReader<'_>) -> Result<
 }
 
 fn read_namespaces_and_version(
+    ctx: &mut OdsContext,
     xml: &mut OdsXmlReader<'_>,
     super_tag: &BytesStart<'_>,
 ) -> Result<(Option<String>, NamespaceMap), OdsError> {
@@ -889,6 +959,8 @@ fn read_namespaces_and_version(
             }
             attr => {
                 unused_attr(
+                    ctx,
+                    xml,
                     "read_namespaces_and_version",
                     super_tag.name().as_ref(),
                     &attr,
@@ -907,7 +979,7 @@ fn read_table(
 ) -> Result<(), OdsError> {
     let mut sheet = Sheet::new("");
 
-    read_table_attr(xml, &mut sheet, super_tag)?;
+    read_table_attr(ctx, xml, &mut sheet, super_tag)?;
 
     // Cell
     let mut row: u32 = 0;
@@ -941,12 +1013,19 @@ fn read_table(
                     || xml_tag.name().as_ref() == b"table:desc"
                     || xml_tag.name().as_ref() == b"table:table-source"
                     || xml_tag.name().as_ref() == b"office:dde-source"
-                    || xml_tag.name().as_ref() == b"table:scenario"
-                    || xml_tag.name().as_ref() == b"office:forms"
-                    || xml_tag.name().as_ref() == b"table:shapes" =>
+                    || xml_tag.name().as_ref() == b"table:scenario" =>
             {
                 sheet.extra.push(read_xml(ctx, xml, xml_tag, empty_tag)?);
             }
+            Event::Start(xml_tag) | Event::Empty(xml_tag)
+                if xml_tag.name().as_ref() == b"office:forms" =>
+            {
+                read_office_forms(ctx, xml, xml_tag, empty_tag, &mut sheet)?;
+            }
+            Event::Start(xml_tag) if xml_tag.name().as_ref() == b"table:shapes" => {
+                read_table_shapes(ctx, xml, &mut sheet)?;
+            }
+            Event::Empty(xml_tag) if xml_tag.name().as_ref() == b"table:shapes" => {}
             Event::End(xml_tag)
                 if xml_tag.name().as_ref() == b"table:title"
                     || xml_tag.name().as_ref() == b"table:desc"
@@ -971,7 +1050,7 @@ fn read_table(
             // table columns
             //
             Event::Start(xml_tag) if xml_tag.name().as_ref() == b"table:table-column-group" => {
-                let v = read_table_column_group_attr(col, xml_tag)?;
+                let v = read_table_column_group_attr(ctx, xml, col, xml_tag)?;
                 col_group.push(v);
             }
             Event::End(xml_tag) if xml_tag.name().as_ref() == b"table:table-column-group" => {
@@ -999,7 +1078,7 @@ fn read_table(
             Event::End(xml_tag) if xml_tag.name().as_ref() == b"table:table-columns" => {}
 
             Event::Empty(xml_tag) if xml_tag.name().as_ref() == b"table:table-column" => {
-                let col_repeat = read_table_col_attr(xml, &mut sheet, xml_tag, col)?;
+                let col_repeat = read_table_col_attr(ctx, xml, &mut sheet, xml_tag, col)?;
                 col += col_repeat;
             }
 
@@ -1007,7 +1086,7 @@ fn read_table(
             // table rows
             //
             Event::Start(xml_tag) if xml_tag.name().as_ref() == b"table:table-row-group" => {
-                let v = read_table_row_group_attr(row, xml_tag)?;
+                let v = read_table_row_group_attr(ctx, xml, row, xml_tag)?;
                 row_group.push(v);
             }
             Event::End(xml_tag) if xml_tag.name().as_ref() == b"table:table-row-group" => {
@@ -1041,7 +1120,7 @@ fn read_table(
             }
             Event::Start(xml_tag) if xml_tag.name().as_ref() == b"table:table-row" => {
                 col = 0;
-                row_repeat = read_table_row_attr(xml, &mut sheet, row, xml_tag)?;
+                row_repeat = read_table_row_attr(ctx, xml, &mut sheet, row, xml_tag)?;
             }
             Event::End(xml_tag) if xml_tag.name().as_ref() == b"table:table-row" => {
                 if col_data {
@@ -1067,7 +1146,7 @@ fn read_table(
             }
 
             _ => {
-                unused_event("read_table", &evt)?;
+                unused_event(ctx, xml, "read_table", &evt)?;
             }
         }
         buf.clear();
@@ -1084,6 +1163,7 @@ fn read_table(
 
 // Reads the table attributes.
 fn read_table_attr(
+    ctx: &mut OdsContext,
     xml: &mut OdsXmlReader<'_>,
     sheet: &mut Sheet,
     super_tag: &BytesStart<'_>,
@@ -1108,7 +1188,13 @@ fn read_table_attr(
                 sheet.print_ranges = parse_cellranges(v.as_ref())?;
             }
             attr => {
-                unused_attr("read_table_attr", super_tag.name().as_ref(), &attr)?;
+                unused_attr(
+                    ctx,
+                    xml,
+                    "read_table_attr",
+                    super_tag.name().as_ref(),
+                    &attr,
+                )?;
             }
         }
     }
@@ -1118,6 +1204,7 @@ fn read_table_attr(
 
 // Reads table-row attributes. Returns the repeat-count.
 fn read_table_row_attr(
+    ctx: &mut OdsContext,
     xml: &mut OdsXmlReader<'_>,
     sheet: &mut Sheet,
     row: u32,
@@ -1147,7 +1234,13 @@ fn read_table_row_attr(
                 row_header.get_or_insert_with(RowHeader::default).visible = visible;
             }
             attr => {
-                unused_attr("read_table_row_attr", super_tag.name().as_ref(), &attr)?;
+                unused_attr(
+                    ctx,
+                    xml,
+                    "read_table_row_attr",
+                    super_tag.name().as_ref(),
+                    &attr,
+                )?;
             }
         }
     }
@@ -1162,6 +1255,8 @@ fn read_table_row_attr(
 
 // Reads the table:table-column-group attributes.
 fn read_table_column_group_attr(
+    ctx: &mut OdsContext,
+    xml: &mut OdsXmlReader<'_>,
     table_col: u32,
     super_tag: &BytesStart<'_>,
 ) -> Result<Grouped, OdsError> {
@@ -1174,6 +1269,8 @@ fn read_table_column_group_attr(
             }
             attr => {
                 unused_attr(
+                    ctx,
+                    xml,
                     "read_table_column_group_attr",
                     super_tag.name().as_ref(),
                     &attr,
@@ -1190,7 +1287,12 @@ fn read_table_column_group_attr(
 }
 
 // Reads the table:table-row-group attributes.
-fn read_table_row_group_attr(row: u32, super_tag: &BytesStart<'_>) -> Result<Grouped, OdsError> {
+fn read_table_row_group_attr(
+    ctx: &mut OdsContext,
+    xml: &mut OdsXmlReader<'_>,
+    row: u32,
+    super_tag: &BytesStart<'_>,
+) -> Result<Grouped, OdsError> {
     let mut display = true;
 
     for attr in super_tag.attributes().with_checks(false) {
@@ -1200,6 +1302,8 @@ fn read_table_row_group_attr(row: u32, super_tag: &BytesStart<'_>) -> Result<Gro
             }
             attr => {
                 unused_attr(
+                    ctx,
+                    xml,
                     "read_table_row_group_attr",
                     super_tag.name().as_ref(),
                     &attr,
@@ -1217,6 +1321,7 @@ fn read_table_row_group_attr(row: u32, super_tag: &BytesStart<'_>) -> Result<Gro
 
 // Reads the table-column attributes. Creates as many copies as indicated.
 fn read_table_col_attr(
+    ctx: &mut OdsContext,
     xml: &mut OdsXmlReader<'_>,
     sheet: &mut Sheet,
     super_tag: &BytesStart<'_>,
@@ -1245,7 +1350,13 @@ fn read_table_col_attr(
                 col_header.get_or_insert_with(ColHeader::default).visible = visible;
             }
             attr => {
-                unused_attr("read_table_col_attr", super_tag.name().as_ref(), &attr)?;
+                unused_attr(
+                    ctx,
+                    xml,
+                    "read_table_col_attr",
+                    super_tag.name().as_ref(),
+                    &attr,
+                )?;
             }
         }
     }
@@ -1273,9 +1384,11 @@ struct ReadTableCell {
     val_datetime: Option<NaiveDateTime>,
     val_duration: Option<Duration>,
     val_float: Option<f64>,
+    val_decimal: Option<Decimal>,
     val_bool: Option<bool>,
     val_string: Option<String>,
     val_currency: Option<String>,
+    val_error: bool,
 
     content: TextContent,
 }
@@ -1304,9 +1417,11 @@ fn read_table_cell(
         val_datetime: None,
         val_duration: None,
         val_float: None,
+        val_decimal: None,
         val_bool: None,
         val_string: None,
         val_currency: None,
+        val_error: false,
         content: TextContent::Empty,
     };
 
@@ -1358,7 +1473,12 @@ fn read_table_cell(
                     .validation_name = Some(ValidationRef::from(name.as_ref()));
             }
             attr if attr.key.as_ref() == b"calcext:value-type" => {
-                // not used. office:value-type seems to be good enough.
+                // only needed for errors, otherwise office:value-type
+                // is good enough.
+                if attr.value.as_ref() == b"error" {
+                    cell.get_or_insert_with(CellData::default);
+                    tc.val_error = true;
+                }
             }
             attr if attr.key.as_ref() == b"office:value-type" => {
                 cell.get_or_insert_with(CellData::default);
@@ -1389,6 +1509,9 @@ fn read_table_cell(
             attr if attr.key.as_ref() == b"office:value" => {
                 cell.get_or_insert_with(CellData::default);
                 tc.val_float = Some(parse_f64(&attr.value)?);
+                if ctx.use_decimal {
+                    tc.val_decimal = parse_decimal(&attr.value);
+                }
             }
             attr if attr.key.as_ref() == b"office:boolean-value" => {
                 cell.get_or_insert_with(CellData::default);
@@ -1412,7 +1535,13 @@ fn read_table_cell(
                     Some(CellStyleRef::from(name.as_ref()));
             }
             attr => {
-                unused_attr("read_table_cell2", super_tag.name().as_ref(), &attr)?;
+                unused_attr(
+                    ctx,
+                    xml,
+                    "read_table_cell2",
+                    super_tag.name().as_ref(),
+                    &attr,
+                )?;
             }
         }
     }
@@ -1444,6 +1573,16 @@ fn read_table_cell(
                         .draw_frames
                         .push(draw_frame);
                 }
+                Event::Start(xml_tag) | Event::Empty(xml_tag)
+                    if DrawShapeKind::from_tag(xml_tag.name().as_ref()).is_some() =>
+                {
+                    let empty_tag = matches!(evt, Event::Empty(_));
+                    let draw_shape = read_draw_shape(ctx, xml, xml_tag, empty_tag)?;
+                    cell.get_or_insert_with(CellData::default)
+                        .extra_mut()
+                        .draw_shapes
+                        .push(draw_shape);
+                }
 
                 Event::End(xml_tag) if xml_tag.name() == super_tag.name() => {
                     break;
@@ -1452,7 +1591,7 @@ fn read_table_cell(
                     break;
                 }
                 _ => {
-                    unused_event("read_table_cell", &evt)?;
+                    unused_event(ctx, xml, "read_table_cell", &evt)?;
                 }
             }
 
@@ -1561,6 +1700,28 @@ fn append_text(new_txt: TextContent, mut content: TextContent) -> TextContent {
 
 #[inline(always)]
 fn set_value(tc: ReadTableCell, cell: &mut CellData) -> Result<(), OdsError> {
+    if tc.val_error {
+        // The error text is the displayed content.
+        let text = match tc.content {
+            TextContent::Empty => tc.val_string.unwrap_or_default(),
+            TextContent::Text(txt) => txt,
+            TextContent::Xml(xml) => {
+                let mut buf = String::new();
+                xml.extract_text(&mut buf);
+                buf
+            }
+            TextContent::XmlVec(vec) => {
+                let mut buf = String::new();
+                for xml in vec {
+                    xml.extract_text(&mut buf);
+                }
+                buf
+            }
+        };
+        cell.value = Value::Error(FormulaError::from(text.as_str()));
+        return Ok(());
+    }
+
     match tc.val_type {
         ValueType::Empty => {
             // noop
@@ -1573,21 +1734,28 @@ fn set_value(tc: ReadTableCell, cell: &mut CellData) -> Result<(), OdsError> {
             }
         }
         ValueType::Number => {
-            if let Some(v) = tc.val_float {
+            if let Some(v) = tc.val_decimal {
+                cell.value = Value::NumberDecimal(v);
+            } else if let Some(v) = tc.val_float {
                 cell.value = Value::Number(v);
             } else {
                 return Err(OdsError::Parse("no float value", None));
             }
         }
         ValueType::Percentage => {
-            if let Some(v) = tc.val_float {
+            if let Some(v) = tc.val_decimal {
+                cell.value = Value::PercentageDecimal(v);
+            } else if let Some(v) = tc.val_float {
                 cell.value = Value::Percentage(v);
             } else {
                 return Err(OdsError::Parse("no float value", None));
             }
         }
         ValueType::Currency => {
-            if let Some(v) = tc.val_float {
+            if let Some(v) = tc.val_decimal {
+                let c = tc.val_currency.unwrap_or_default();
+                cell.value = Value::CurrencyDecimal(v, c.into_boxed_str());
+            } else if let Some(v) = tc.val_float {
                 if let Some(c) = tc.val_currency {
                     cell.value = Value::Currency(v, c.into_boxed_str());
                 } else {
@@ -1617,7 +1785,7 @@ fn set_value(tc: ReadTableCell, cell: &mut CellData) -> Result<(), OdsError> {
                 }
             }
         }
-        ValueType::TextXml => {
+        ValueType::TextXml | ValueType::Error => {
             unreachable!();
         }
         ValueType::DateTime => {
@@ -1695,7 +1863,7 @@ fn read_annotation(
                 break;
             }
             _ => {
-                unused_event("read_annotation", &evt)?;
+                unused_event(ctx, xml, "read_annotation", &evt)?;
             }
         }
 
@@ -1733,6 +1901,13 @@ fn read_draw_frame(
                     ctx, xml, xml_tag, empty_tag,
                 )?));
             }
+            Event::Empty(xml_tag) | Event::Start(xml_tag)
+                if xml_tag.name().as_ref() == b"draw:text-box" =>
+            {
+                draw_frame.push_content(DrawFrameContent::TextBox(read_text_box(
+                    ctx, xml, xml_tag, empty_tag,
+                )?));
+            }
             Event::Empty(xml_tag) if xml_tag.name().as_ref() == b"svg:desc" => {}
             Event::Start(xml_tag) if xml_tag.name().as_ref() == b"svg:desc" => {
                 if let Some(v) = read_text(ctx, xml, xml_tag, empty_tag, parse_string)? {
@@ -1749,7 +1924,7 @@ fn read_draw_frame(
                 break;
             }
             _ => {
-                unused_event("read_draw_frame", &evt)?;
+                unused_event(ctx, xml, "read_draw_frame", &evt)?;
             }
         }
 
@@ -1760,6 +1935,314 @@ fn read_draw_frame(
     Ok(draw_frame)
 }
 
+fn read_text_box(
+    ctx: &mut OdsContext,
+    xml: &mut OdsXmlReader<'_>,
+    super_tag: &BytesStart<'_>,
+    empty_tag: bool,
+) -> Result<DrawTextBox, OdsError> {
+    let mut text_box = DrawTextBox::new();
+
+    copy_attr2(xml, text_box.attrmap_mut(), super_tag)?;
+
+    if !empty_tag {
+        let mut buf = ctx.pop_buf();
+        loop {
+            let evt = xml.read_event_into(&mut buf)?;
+            let empty_tag = matches!(evt, Event::Empty(_));
+            if cfg!(feature = "dump_xml") {
+                println!("read_text_box {:?}", evt);
+            }
+            match &evt {
+                Event::End(xml_tag) if xml_tag.name().as_ref() == b"draw:text-box" => {
+                    break;
+                }
+                Event::Start(xml_tag) | Event::Empty(xml_tag) => {
+                    text_box.push_text(read_xml(ctx, xml, xml_tag, empty_tag)?);
+                }
+                Event::Eof => {
+                    break;
+                }
+                _ => {
+                    unused_event(ctx, xml, "read_text_box", &evt)?;
+                }
+            }
+
+            buf.clear();
+        }
+        ctx.push_buf(buf);
+    }
+
+    Ok(text_box)
+}
+
+fn read_draw_shape(
+    ctx: &mut OdsContext,
+    xml: &mut OdsXmlReader<'_>,
+    super_tag: &BytesStart<'_>,
+    empty_tag: bool,
+) -> Result<DrawShape, OdsError> {
+    let Some(kind) = DrawShapeKind::from_tag(super_tag.name().as_ref()) else {
+        return Err(OdsError::Ods(format!(
+            "unknown shape {}",
+            from_utf8(super_tag.name().as_ref())?
+        )));
+    };
+    let mut draw_shape = DrawShape::new(kind);
+
+    copy_attr2(xml, draw_shape.attrmap_mut(), super_tag)?;
+
+    if !empty_tag {
+        let mut buf = ctx.pop_buf();
+        loop {
+            let evt = xml.read_event_into(&mut buf)?;
+            let empty_tag = matches!(evt, Event::Empty(_));
+            if cfg!(feature = "dump_xml") {
+                println!("read_draw_shape {:?}", evt);
+            }
+            match &evt {
+                Event::End(xml_tag) if xml_tag.name() == super_tag.name() => {
+                    break;
+                }
+                Event::Empty(xml_tag) if xml_tag.name().as_ref() == b"svg:desc" => {}
+                Event::Start(xml_tag) if xml_tag.name().as_ref() == b"svg:desc" => {
+                    if let Some(v) = read_text(ctx, xml, xml_tag, empty_tag, parse_string)? {
+                        draw_shape.set_desc(v);
+                    }
+                }
+                Event::Empty(xml_tag) if xml_tag.name().as_ref() == b"svg:title" => {}
+                Event::Start(xml_tag) if xml_tag.name().as_ref() == b"svg:title" => {
+                    if let Some(v) = read_text(ctx, xml, xml_tag, empty_tag, parse_string)? {
+                        draw_shape.set_title(v);
+                    }
+                }
+                Event::Start(xml_tag) | Event::Empty(xml_tag)
+                    if xml_tag.name().as_ref() == b"text:p"
+                        || xml_tag.name().as_ref() == b"text:h"
+                        || xml_tag.name().as_ref() == b"text:list" =>
+                {
+                    draw_shape.push_text(read_xml(ctx, xml, xml_tag, empty_tag)?);
+                }
+                Event::Start(xml_tag) | Event::Empty(xml_tag) => {
+                    draw_shape.push_extra(read_xml(ctx, xml, xml_tag, empty_tag)?);
+                }
+                Event::Eof => {
+                    break;
+                }
+                _ => {
+                    unused_event(ctx, xml, "read_draw_shape", &evt)?;
+                }
+            }
+
+            buf.clear();
+        }
+        ctx.push_buf(buf);
+    }
+
+    Ok(draw_shape)
+}
+
+/// Drawings anchored to the page in document order. Everything that is
+/// not a frame or a known shape is kept as xml.
+fn read_table_shapes(
+    ctx: &mut OdsContext,
+    xml: &mut OdsXmlReader<'_>,
+    sheet: &mut Sheet,
+) -> Result<(), OdsError> {
+    let mut buf = ctx.pop_buf();
+    loop {
+        let evt = xml.read_event_into(&mut buf)?;
+        let empty_tag = matches!(evt, Event::Empty(_));
+        if cfg!(feature = "dump_xml") {
+            println!("read_table_shapes {:?}", evt);
+        }
+        match &evt {
+            Event::End(xml_tag) if xml_tag.name().as_ref() == b"table:shapes" => {
+                break;
+            }
+            Event::Start(xml_tag) if xml_tag.name().as_ref() == b"draw:frame" => {
+                sheet.add_page_frame(read_draw_frame(ctx, xml, xml_tag)?);
+            }
+            Event::Start(xml_tag) | Event::Empty(xml_tag)
+                if DrawShapeKind::from_tag(xml_tag.name().as_ref()).is_some() =>
+            {
+                sheet.add_page_shape(read_draw_shape(ctx, xml, xml_tag, empty_tag)?);
+            }
+            Event::Start(xml_tag) | Event::Empty(xml_tag) => {
+                let tag = read_xml(ctx, xml, xml_tag, empty_tag)?;
+                sheet.page_drawings.push(PageDrawing::Xml(tag));
+            }
+            Event::Eof => {
+                break;
+            }
+            _ => {
+                unused_event(ctx, xml, "read_table_shapes", &evt)?;
+            }
+        }
+
+        buf.clear();
+    }
+    ctx.push_buf(buf);
+
+    Ok(())
+}
+
+/// Forms of a sheet. The attributes of office:forms and everything that is
+/// not a form are kept as xml.
+fn read_office_forms(
+    ctx: &mut OdsContext,
+    xml: &mut OdsXmlReader<'_>,
+    super_tag: &BytesStart<'_>,
+    empty_tag: bool,
+    sheet: &mut Sheet,
+) -> Result<(), OdsError> {
+    let mut unknown = XmlTag::new("office:forms");
+    copy_attr2(xml, unknown.attrmap_mut(), super_tag)?;
+
+    if !empty_tag {
+        let mut buf = ctx.pop_buf();
+        loop {
+            let evt = xml.read_event_into(&mut buf)?;
+            let empty_tag = matches!(evt, Event::Empty(_));
+            if cfg!(feature = "dump_xml") {
+                println!("read_office_forms {:?}", evt);
+            }
+            match &evt {
+                Event::End(xml_tag) if xml_tag.name().as_ref() == b"office:forms" => {
+                    break;
+                }
+                Event::Start(xml_tag) | Event::Empty(xml_tag)
+                    if xml_tag.name().as_ref() == b"form:form" =>
+                {
+                    sheet.add_form(read_form(ctx, xml, xml_tag, empty_tag)?);
+                }
+                Event::Start(xml_tag) | Event::Empty(xml_tag) => {
+                    unknown.add_tag(read_xml(ctx, xml, xml_tag, empty_tag)?);
+                }
+                Event::Eof => {
+                    break;
+                }
+                _ => {
+                    unused_event(ctx, xml, "read_office_forms", &evt)?;
+                }
+            }
+
+            buf.clear();
+        }
+        ctx.push_buf(buf);
+    }
+
+    if !unknown.is_empty() || !unknown.attrmap().is_empty() {
+        sheet.extra.push(unknown);
+    }
+
+    Ok(())
+}
+
+fn read_form(
+    ctx: &mut OdsContext,
+    xml: &mut OdsXmlReader<'_>,
+    super_tag: &BytesStart<'_>,
+    empty_tag: bool,
+) -> Result<Form, OdsError> {
+    let mut form = Form::new_empty();
+    copy_attr2(xml, form.attrmap_mut(), super_tag)?;
+
+    if !empty_tag {
+        let mut buf = ctx.pop_buf();
+        loop {
+            let evt = xml.read_event_into(&mut buf)?;
+            let empty_tag = matches!(evt, Event::Empty(_));
+            if cfg!(feature = "dump_xml") {
+                println!("read_form {:?}", evt);
+            }
+            match &evt {
+                Event::End(xml_tag) if xml_tag.name().as_ref() == b"form:form" => {
+                    break;
+                }
+                Event::Start(xml_tag) | Event::Empty(xml_tag) => {
+                    let mut attr = AttrMap2::new();
+                    copy_attr2(xml, &mut attr, xml_tag)?;
+                    let kind = FormControlKind::from_tag(
+                        xml_tag.name().as_ref(),
+                        attr.attr("form:control-implementation"),
+                    );
+                    if let Some(kind) = kind {
+                        let mut control = FormControl::new_empty(kind);
+                        *control.attrmap_mut() = attr;
+                        read_form_control(ctx, xml, xml_tag, empty_tag, &mut control)?;
+                        form.push_control(control);
+                    } else {
+                        form.push_extra(read_xml(ctx, xml, xml_tag, empty_tag)?);
+                    }
+                }
+                Event::Eof => {
+                    break;
+                }
+                _ => {
+                    unused_event(ctx, xml, "read_form", &evt)?;
+                }
+            }
+
+            buf.clear();
+        }
+        ctx.push_buf(buf);
+    }
+
+    Ok(form)
+}
+
+/// Reads the content of a control. The attributes are already set.
+fn read_form_control(
+    ctx: &mut OdsContext,
+    xml: &mut OdsXmlReader<'_>,
+    super_tag: &BytesStart<'_>,
+    empty_tag: bool,
+    control: &mut FormControl,
+) -> Result<(), OdsError> {
+    if !empty_tag {
+        let mut buf = ctx.pop_buf();
+        loop {
+            let evt = xml.read_event_into(&mut buf)?;
+            let empty_tag = matches!(evt, Event::Empty(_));
+            if cfg!(feature = "dump_xml") {
+                println!("read_form_control {:?}", evt);
+            }
+            match &evt {
+                Event::End(xml_tag) if xml_tag.name() == super_tag.name() => {
+                    break;
+                }
+                Event::Start(xml_tag) | Event::Empty(xml_tag)
+                    if xml_tag.name().as_ref() == b"form:option"
+                        || xml_tag.name().as_ref() == b"form:item" =>
+                {
+                    let mut item = FormItem::new_empty();
+                    copy_attr2(xml, item.attrmap_mut(), xml_tag)?;
+                    control.push_form_item(item);
+                    if !empty_tag {
+                        // no content allowed.
+                        read_xml(ctx, xml, xml_tag, empty_tag)?;
+                    }
+                }
+                Event::Start(xml_tag) | Event::Empty(xml_tag) => {
+                    control.push_extra(read_xml(ctx, xml, xml_tag, empty_tag)?);
+                }
+                Event::Eof => {
+                    break;
+                }
+                _ => {
+                    unused_event(ctx, xml, "read_form_control", &evt)?;
+                }
+            }
+
+            buf.clear();
+        }
+        ctx.push_buf(buf);
+    }
+
+    Ok(())
+}
+
 fn read_image(
     ctx: &mut OdsContext,
     xml: &mut OdsXmlReader<'_>,
@@ -1799,7 +2282,7 @@ fn read_image(
                     break;
                 }
                 _ => {
-                    unused_event("read_image", &evt)?;
+                    unused_event(ctx, xml, "read_image", &evt)?;
                 }
             }
 
@@ -1836,8 +2319,8 @@ fn read_scripts(ctx: &mut OdsContext, xml: &mut OdsXmlReader<'_>) -> Result<(),
             Event::Start(xml_tag) | Event::Empty(xml_tag)
                 if xml_tag.name().as_ref() == b"script:event-listener" =>
             {
-                ctx.book
-                    .add_event_listener(read_event_listener(xml, xml_tag)?);
+                let evt_listener = read_event_listener(ctx, xml, xml_tag)?;
+                ctx.book.add_event_listener(evt_listener);
             }
             Event::End(xml_tag) if xml_tag.name().as_ref() == b"script:event-listener" => {}
 
@@ -1845,7 +2328,7 @@ fn read_scripts(ctx: &mut OdsContext, xml: &mut OdsXmlReader<'_>) -> Result<(),
                 break;
             }
             _ => {
-                unused_event("read_scripts", &evt)?;
+                unused_event(ctx, xml, "read_scripts", &evt)?;
             }
         }
 
@@ -1874,6 +2357,7 @@ fn read_script(
 
 // reads the page-layout tag
 fn read_event_listener(
+    ctx: &mut OdsContext,
     xml: &mut OdsXmlReader<'_>,
     super_tag: &BytesStart<'_>,
 ) -> Result<EventListener, OdsError> {
@@ -1899,7 +2383,13 @@ fn read_event_listener(
                 evt.link_type = parse_xlink_type(attr.decode_and_unescape_value(xml)?.as_bytes())?;
             }
             attr => {
-                unused_attr("read_event_listener", super_tag.name().as_ref(), &attr)?;
+                unused_attr(
+                    ctx,
+                    xml,
+                    "read_event_listener",
+                    super_tag.name().as_ref(),
+                    &attr,
+                )?;
             }
         }
     }
@@ -1939,7 +2429,7 @@ fn read_office_font_face_decls(
                 break;
             }
             _ => {
-                unused_event("read_fonts", &evt)?;
+                unused_event(ctx, xml, "read_fonts", &evt)?;
             }
         }
 
@@ -1968,7 +2458,13 @@ fn read_page_style(
                 pl.master_page_usage = Some(value.to_string());
             }
             attr => {
-                unused_attr("read_page_style", super_tag.name().as_ref(), &attr)?;
+                unused_attr(
+                    ctx,
+                    xml,
+                    "read_page_style",
+                    super_tag.name().as_ref(),
+                    &attr,
+                )?;
             }
         }
     }
@@ -2033,7 +2529,7 @@ fn read_page_style(
             Event::Text(_) => (),
             Event::Eof => break,
             _ => {
-                unused_event("read_page_layout", &evt)?;
+                unused_event(ctx, xml, "read_page_layout", &evt)?;
             }
         }
 
@@ -2057,13 +2553,13 @@ fn read_validations(ctx: &mut OdsContext, xml: &mut OdsXmlReader<'_>) -> Result<
             println!(" read_validations {:?}", evt);
         }
         match &evt {
-            Event::Start(xml_tag) if xml_tag.name().as_ref() == b"table:content-validation" => {
-                read_validation(xml, &mut valid, xml_tag)?;
+            Event::Empty(xml_tag) if xml_tag.name().as_ref() == b"table:content-validation" => {
+                read_validation(ctx, xml, &mut valid, xml_tag)?;
                 ctx.book.add_validation(valid);
                 valid = Validation::new();
             }
             Event::Start(xml_tag) if xml_tag.name().as_ref() == b"table:content-validation" => {
-                read_validation(xml, &mut valid, xml_tag)?;
+                read_validation(ctx, xml, &mut valid, xml_tag)?;
             }
             Event::End(xml_tag) if xml_tag.name().as_ref() == b"table:content-validation" => {
                 ctx.book.add_validation(valid);
@@ -2089,7 +2585,7 @@ fn read_validations(ctx: &mut OdsContext, xml: &mut OdsXmlReader<'_>) -> Result<
             Event::Text(_) => (),
             Event::Eof => break,
             _ => {
-                unused_event("read_validations", &evt)?;
+                unused_event(ctx, xml, "read_validations", &evt)?;
             }
         }
     }
@@ -2116,7 +2612,13 @@ fn read_validation_help(
                 vh.set_title(Some(attr.decode_and_unescape_value(xml)?.to_string()));
             }
             attr => {
-                unused_attr("read_validations", super_tag.name().as_ref(), &attr)?;
+                unused_attr(
+                    ctx,
+                    xml,
+                    "read_validations",
+                    super_tag.name().as_ref(),
+                    &attr,
+                )?;
             }
         }
     }
@@ -2170,7 +2672,13 @@ fn read_validation_error(
                 ve.set_title(Some(attr.decode_and_unescape_value(xml)?.to_string()));
             }
             attr => {
-                unused_attr("read_validations", super_tag.name().as_ref(), &attr)?;
+                unused_attr(
+                    ctx,
+                    xml,
+                    "read_validations",
+                    super_tag.name().as_ref(),
+                    &attr,
+                )?;
             }
         }
     }
@@ -2194,6 +2702,7 @@ fn read_validation_error(
 }
 
 fn read_validation(
+    ctx: &mut OdsContext,
     xml: &mut OdsXmlReader<'_>,
     valid: &mut Validation,
     super_tag: &BytesStart<'_>,
@@ -2219,7 +2728,13 @@ fn read_validation(
                 valid.set_display(attr.value.as_ref().try_into()?);
             }
             attr => {
-                unused_attr("read_validation", super_tag.name().as_ref(), &attr)?;
+                unused_attr(
+                    ctx,
+                    xml,
+                    "read_validation",
+                    super_tag.name().as_ref(),
+                    &attr,
+                )?;
             }
         }
     }
@@ -2250,7 +2765,7 @@ fn read_office_master_styles(
             Event::Text(_) => (),
             Event::Eof => break,
             _ => {
-                unused_event("read_master_styles", &evt)?;
+                unused_event(ctx, xml, "read_master_styles", &evt)?;
             }
         }
 
@@ -2286,7 +2801,13 @@ fn read_master_page(
                 masterpage.set_next_masterpage(&MasterPageRef::from(v));
             }
             attr => {
-                unused_attr("read_master_page", super_tag.name().as_ref(), &attr)?;
+                unused_attr(
+                    ctx,
+                    xml,
+                    "read_master_page",
+                    super_tag.name().as_ref(),
+                    &attr,
+                )?;
             }
         }
     }
@@ -2327,7 +2848,7 @@ fn read_master_page(
             }
             Event::Eof => break,
             _ => {
-                unused_event("read_master_page", &evt)?;
+                unused_event(ctx, xml, "read_master_page", &evt)?;
             }
         }
 
@@ -2355,7 +2876,13 @@ fn read_headerfooter(
                 hf.set_display(parse_bool(&attr.value)?);
             }
             attr => {
-                unused_attr("read_headerfooter", super_tag.name().as_ref(), &attr)?;
+                unused_attr(
+                    ctx,
+                    xml,
+                    "read_headerfooter",
+                    super_tag.name().as_ref(),
+                    &attr,
+                )?;
             }
         }
     }
@@ -2414,7 +2941,7 @@ fn read_headerfooter(
             }
             Event::Eof => break,
             _ => {
-                unused_event("read_headerfooter", &evt)?;
+                unused_event(ctx, xml, "read_headerfooter", &evt)?;
             }
         }
 
@@ -2466,7 +2993,7 @@ fn read_office_styles(
             Event::Text(_) => (),
             Event::Eof => break,
             _ => {
-                unused_event("read_styles_tag", &evt)?;
+                unused_event(ctx, xml, "read_styles_tag", &evt)?;
             }
         }
 
@@ -2520,7 +3047,7 @@ fn read_office_automatic_styles(
             Event::Text(_) => (),
             Event::Eof => break,
             _ => {
-                unused_event("read_auto_styles", &evt)?;
+                unused_event(ctx, xml, "read_auto_styles", &evt)?;
             }
         }
 
@@ -2828,7 +3355,7 @@ fn read_value_format_parts<T: ValueFormatTrait>(
             Event::Start(xml_tag) | Event::Empty(xml_tag)
                 if xml_tag.name().as_ref() == b"style:map" =>
             {
-                valuestyle.push_stylemap(read_value_stylemap(xml, xml_tag)?);
+                valuestyle.push_stylemap(read_value_stylemap(ctx, xml, xml_tag)?);
             }
             Event::Start(xml_tag) | Event::Empty(xml_tag)
                 if xml_tag.name().as_ref() == b"style:text-properties" =>
@@ -2840,7 +3367,7 @@ fn read_value_format_parts<T: ValueFormatTrait>(
             }
             Event::Eof => break,
             _ => {
-                unused_event("read_value_format_parts", &evt)?;
+                unused_event(ctx, xml, "read_value_format_parts", &evt)?;
             }
         }
 
@@ -2876,7 +3403,7 @@ fn read_part(
                     break;
                 }
                 _ => {
-                    unused_event("read_part", &evt)?;
+                    unused_event(ctx, xml, "read_part", &evt)?;
                 }
             }
         }
@@ -2915,7 +3442,7 @@ fn read_part_text(
                     break;
                 }
                 _ => {
-                    unused_event("read_part_text", &evt)?;
+                    unused_event(ctx, xml, "read_part_text", &evt)?;
                 }
             }
         }
@@ -2954,6 +3481,8 @@ fn read_part_number(
                             }
                             _ => {
                                 unused_attr(
+                                    ctx,
+                                    xml,
                                     "read_part_embedded_text",
                                     xml_tag.name().as_ref(),
                                     &attr,
@@ -2973,7 +3502,7 @@ fn read_part_number(
                     break;
                 }
                 _ => {
-                    unused_event("read_part_embedded_text", &evt)?;
+                    unused_event(ctx, xml, "read_part_embedded_text", &evt)?;
                 }
             }
         }
@@ -3063,7 +3592,7 @@ fn read_tablestyle(
                 Event::Start(xml_tag) | Event::Empty(xml_tag) => match xml_tag.name().as_ref() {
                     b"style:table-properties" => copy_attr2(xml, style.tablestyle_mut(), xml_tag)?,
                     _ => {
-                        unused_event("read_table_style", &evt)?;
+                        unused_event(ctx, xml, "read_table_style", &evt)?;
                     }
                 },
                 Event::Text(_) => (),
@@ -3072,12 +3601,12 @@ fn read_tablestyle(
                         ctx.book.add_tablestyle(style);
                         break;
                     } else {
-                        unused_event("read_table_style", &evt)?;
+                        unused_event(ctx, xml, "read_table_style", &evt)?;
                     }
                 }
                 Event::Eof => break,
                 _ => {
-                    unused_event("read_table_style", &evt)?;
+                    unused_event(ctx, xml, "read_table_style", &evt)?;
                 }
             }
         }
@@ -3121,7 +3650,7 @@ fn read_rowstyle(
                         copy_attr2(xml, style.rowstyle_mut(), xml_tag)?
                     }
                     _ => {
-                        unused_event("read_rowstyle", &evt)?;
+                        unused_event(ctx, xml, "read_rowstyle", &evt)?;
                     }
                 },
                 Event::Text(_) => (),
@@ -3130,12 +3659,12 @@ fn read_rowstyle(
                         ctx.book.add_rowstyle(style);
                         break;
                     } else {
-                        unused_event("read_rowstyle", &evt)?;
+                        unused_event(ctx, xml, "read_rowstyle", &evt)?;
                     }
                 }
                 Event::Eof => break,
                 _ => {
-                    unused_event("read_rowstyle", &evt)?;
+                    unused_event(ctx, xml, "read_rowstyle", &evt)?;
                 }
             }
         }
@@ -3178,7 +3707,7 @@ fn read_colstyle(
                         copy_attr2(xml, style.colstyle_mut(), xml_tag)?
                     }
                     _ => {
-                        unused_event("read_colstyle", &evt)?;
+                        unused_event(ctx, xml, "read_colstyle", &evt)?;
                     }
                 },
                 Event::Text(_) => (),
@@ -3187,12 +3716,12 @@ fn read_colstyle(
                         ctx.book.add_colstyle(style);
                         break;
                     } else {
-                        unused_event("read_colstyle", &evt)?;
+                        unused_event(ctx, xml, "read_colstyle", &evt)?;
                     }
                 }
                 Event::Eof => break,
                 _ => {
-                    unused_event("read_colstyle", &evt)?;
+                    unused_event(ctx, xml, "read_colstyle", &evt)?;
                 }
             }
         }
@@ -3255,7 +3784,7 @@ fn read_cellstyle(
                 Event::Start(xml_tag) | Event::Empty(xml_tag)
                     if xml_tag.name().as_ref() == b"style:map" =>
                 {
-                    style.push_stylemap(read_stylemap(xml, xml_tag)?);
+                    style.push_stylemap(read_stylemap(ctx, xml, xml_tag)?);
                 }
                 // todo: tab-stops
                 // b"style:tab-stops" => (),
@@ -3271,7 +3800,7 @@ fn read_cellstyle(
                 }
                 Event::Eof => break,
                 _ => {
-                    unused_event("read_cellstyle", &evt)?;
+                    unused_event(ctx, xml, "read_cellstyle", &evt)?;
                 }
             }
         }
@@ -3322,7 +3851,7 @@ fn read_paragraphstyle(
                 Event::End(xml_tag) if xml_tag.name().as_ref() == b"style:paragraph-properties" => {
                 }
                 // b"style:graphic-properties" => copy_attr(style.graphic_mut(), xml, xml_tag)?,
-                // b"style:map" => style.push_stylemap(read_stylemap(xml, xml_tag)?),
+                // b"style:map" => style.push_stylemap(read_stylemap(ctx, xml, xml_tag)?),
                 Event::Start(xml_tag) | Event::Empty(xml_tag)
                     if xml_tag.name().as_ref() == b"style:tab-stops" => {}
                 Event::End(xml_tag) if xml_tag.name().as_ref() == b"style:tab-stops" => {}
@@ -3342,7 +3871,7 @@ fn read_paragraphstyle(
                 Event::Text(_) => (),
                 Event::Eof => break,
                 _ => {
-                    unused_event("read_paragraphstyle", &evt)?;
+                    unused_event(ctx, xml, "read_paragraphstyle", &evt)?;
                 }
             }
         }
@@ -3392,7 +3921,7 @@ fn read_textstyle(
                 Event::Text(_) => (),
                 Event::Eof => break,
                 _ => {
-                    unused_event("read_textstyle", &evt)?;
+                    unused_event(ctx, xml, "read_textstyle", &evt)?;
                 }
             }
         }
@@ -3442,7 +3971,7 @@ fn read_rubystyle(
                 Event::Text(_) => (),
                 Event::Eof => break,
                 _ => {
-                    unused_event("read_rubystyle", &evt)?;
+                    unused_event(ctx, xml, "read_rubystyle", &evt)?;
                 }
             }
         }
@@ -3502,7 +4031,7 @@ fn read_graphicstyle(
                 Event::Text(_) => (),
                 Event::Eof => break,
                 _ => {
-                    unused_event("read_graphicstyle", &evt)?;
+                    unused_event(ctx, xml, "read_graphicstyle", &evt)?;
                 }
             }
         }
@@ -3514,6 +4043,7 @@ fn read_graphicstyle(
 
 // style:map inside a number style.
 fn read_value_stylemap(
+    ctx: &mut OdsContext,
     xml: &mut OdsXmlReader<'_>,
     super_tag: &BytesStart<'_>,
 ) -> Result<ValueStyleMap, OdsError> {
@@ -3529,7 +4059,13 @@ fn read_value_stylemap(
                 sm.set_applied_style(attr.decode_and_unescape_value(xml)?);
             }
             attr => {
-                unused_attr("read_value_stylemap", super_tag.name().as_ref(), &attr)?;
+                unused_attr(
+                    ctx,
+                    xml,
+                    "read_value_stylemap",
+                    super_tag.name().as_ref(),
+                    &attr,
+                )?;
             }
         }
     }
@@ -3538,6 +4074,7 @@ fn read_value_stylemap(
 }
 
 fn read_stylemap(
+    ctx: &mut OdsContext,
     xml: &mut OdsXmlReader<'_>,
     super_tag: &BytesStart<'_>,
 ) -> Result<StyleMap, OdsError> {
@@ -3558,7 +4095,7 @@ fn read_stylemap(
                 sm.set_base_cell(Some(parse_cellref(v.as_ref())?));
             }
             attr => {
-                unused_attr("read_stylemap", super_tag.name().as_ref(), &attr)?;
+                unused_attr(ctx, xml, "read_stylemap", super_tag.name().as_ref(), &attr)?;
             }
         }
     }
@@ -3617,12 +4154,13 @@ fn read_ods_styles(ctx: &mut OdsContext, xml: &mut OdsXmlReader<'_>) -> Result<(
         match &evt {
             Event::Decl(_) => {}
             Event::Start(xml_tag) if xml_tag.name().as_ref() == b"office:document-styles" => {
-                let (_, xmlns) = read_namespaces_and_version(xml, xml_tag)?;
+                let (_, xmlns) = read_namespaces_and_version(ctx, xml, xml_tag)?;
                 ctx.book.xmlns.insert("styles.xml".to_string(), xmlns);
             }
             Event::End(xml_tag) if xml_tag.name().as_ref() == b"office:document-styles" => {
                 // noop
             }
+            Event::Empty(xml_tag) if xml_tag.name().as_ref() == b"office:font-face-decls" => {}
             Event::Start(xml_tag) if xml_tag.name().as_ref() == b"office:font-face-decls" => {
                 read_office_font_face_decls(ctx, xml, StyleOrigin::Styles)?
             }
@@ -3639,7 +4177,7 @@ fn read_ods_styles(ctx: &mut OdsContext, xml: &mut OdsXmlReader<'_>) -> Result<(
                 break;
             }
             _ => {
-                unused_event("read_styles", &evt)?;
+                unused_event(ctx, xml, "read_styles", &evt)?;
             }
         }
 
@@ -3752,7 +4290,7 @@ fn read_ods_metadata(ctx: &mut OdsContext, xml: &mut OdsXmlReader<'_>) -> Result
 
         match &evt {
             Event::Start(xml_tag) if xml_tag.name().as_ref() == b"office:document-meta" => {
-                let (_, xmlns) = read_namespaces_and_version(xml, xml_tag)?;
+                let (_, xmlns) = read_namespaces_and_version(ctx, xml, xml_tag)?;
                 ctx.book.xmlns.insert("meta.xml".to_string(), xmlns);
             }
             Event::End(xml_tag) if xml_tag.name().as_ref() == b"office:document-meta" => {}
@@ -3766,7 +4304,7 @@ fn read_ods_metadata(ctx: &mut OdsContext, xml: &mut OdsXmlReader<'_>) -> Result
                 break;
             }
             _ => {
-                unused_event("read_ods_metadata", &evt)?;
+                unused_event(ctx, xml, "read_ods_metadata", &evt)?;
             }
         }
 
@@ -3911,24 +4449,24 @@ fn read_office_meta(ctx: &mut OdsContext, xml: &mut OdsXmlReader<'_>) -> Result<
             }
 
             Event::Start(xml_tag) if xml_tag.name().as_ref() == b"meta:template" => {
-                ctx.book.metadata.template = read_metadata_template(xml, xml_tag)?;
+                ctx.book.metadata.template = read_metadata_template(ctx, xml, xml_tag)?;
             }
             Event::End(xml_tag) if xml_tag.name().as_ref() == b"meta:template" => {}
 
             Event::Start(xml_tag) if xml_tag.name().as_ref() == b"meta:auto-reload" => {
-                ctx.book.metadata.auto_reload = read_metadata_auto_reload(xml, xml_tag)?;
+                ctx.book.metadata.auto_reload = read_metadata_auto_reload(ctx, xml, xml_tag)?;
             }
             Event::End(xml_tag) if xml_tag.name().as_ref() == b"meta:auto-reload" => {}
 
             Event::Start(xml_tag) if xml_tag.name().as_ref() == b"meta:hyperlink-behaviour" => {
                 ctx.book.metadata.hyperlink_behaviour =
-                    read_metadata_hyperlink_behaviour(xml, xml_tag)?;
+                    read_metadata_hyperlink_behaviour(ctx, xml, xml_tag)?;
             }
             Event::End(xml_tag) if xml_tag.name().as_ref() == b"meta:hyperlink-behaviour" => {}
 
             Event::Start(xml_tag) if xml_tag.name().as_ref() == b"meta:document-statistic" => {
                 ctx.book.metadata.document_statistics =
-                    read_metadata_document_statistics(xml, xml_tag)?;
+                    read_metadata_document_statistics(ctx, xml, xml_tag)?;
             }
             Event::End(xml_tag) if xml_tag.name().as_ref() == b"meta:document-statistic" => {}
 
@@ -3943,7 +4481,7 @@ fn read_office_meta(ctx: &mut OdsContext, xml: &mut OdsXmlReader<'_>) -> Result<
                 break;
             }
             _ => {
-                unused_event("read_metadata", &evt)?;
+                unused_event(ctx, xml, "read_metadata", &evt)?;
             }
         }
 
@@ -3955,6 +4493,7 @@ fn read_office_meta(ctx: &mut OdsContext, xml: &mut OdsXmlReader<'_>) -> Result<
 }
 
 fn read_metadata_template(
+    ctx: &mut OdsContext,
     xml: &mut OdsXmlReader<'_>,
     tag: &BytesStart<'_>,
 ) -> Result<MetaTemplate, OdsError> {
@@ -3984,7 +4523,13 @@ fn read_metadata_template(
                 )?);
             }
             attr => {
-                unused_attr("read_metadata_template", tag.name().as_ref(), &attr)?;
+                unused_attr(
+                    ctx,
+                    xml,
+                    "read_metadata_template",
+                    tag.name().as_ref(),
+                    &attr,
+                )?;
             }
         }
     }
@@ -3993,6 +4538,7 @@ fn read_metadata_template(
 }
 
 fn read_metadata_auto_reload(
+    ctx: &mut OdsContext,
     xml: &mut OdsXmlReader<'_>,
     tag: &BytesStart<'_>,
 ) -> Result<MetaAutoReload, OdsError> {
@@ -4024,7 +4570,13 @@ fn read_metadata_auto_reload(
                 )?);
             }
             attr => {
-                unused_attr("read_metadata_auto_reload", tag.name().as_ref(), &attr)?;
+                unused_attr(
+                    ctx,
+                    xml,
+                    "read_metadata_auto_reload",
+                    tag.name().as_ref(),
+                    &attr,
+                )?;
             }
         }
     }
@@ -4033,6 +4585,7 @@ fn read_metadata_auto_reload(
 }
 
 fn read_metadata_hyperlink_behaviour(
+    ctx: &mut OdsContext,
     xml: &mut OdsXmlReader<'_>,
     tag: &BytesStart<'_>,
 ) -> Result<MetaHyperlinkBehaviour, OdsError> {
@@ -4051,6 +4604,8 @@ fn read_metadata_hyperlink_behaviour(
             }
             attr => {
                 unused_attr(
+                    ctx,
+                    xml,
                     "read_metadata_hyperlink_behaviour",
                     tag.name().as_ref(),
                     &attr,
@@ -4063,6 +4618,7 @@ fn read_metadata_hyperlink_behaviour(
 }
 
 fn read_metadata_document_statistics(
+    ctx: &mut OdsContext,
     xml: &mut OdsXmlReader<'_>,
     tag: &BytesStart<'_>,
 ) -> Result<MetaDocumentStatistics, OdsError> {
@@ -4088,6 +4644,8 @@ fn read_metadata_document_statistics(
             }
             attr => {
                 unused_attr(
+                    ctx,
+                    xml,
                     "read_metadata_document_statistics",
                     tag.name().as_ref(),
                     &attr,
@@ -4121,7 +4679,13 @@ fn read_metadata_user_defined(
                 });
             }
             attr => {
-                unused_attr("read_meta_user_defined", tag.name().as_ref(), &attr)?;
+                unused_attr(
+                    ctx,
+                    xml,
+                    "read_meta_user_defined",
+                    tag.name().as_ref(),
+                    &attr,
+                )?;
             }
         }
     }
@@ -4156,7 +4720,7 @@ fn read_metadata_user_defined(
                 break;
             }
             _ => {
-                unused_event("read_meta_user_defined", &evt)?;
+                unused_event(ctx, xml, "read_meta_user_defined", &evt)?;
             }
         }
 
@@ -4194,7 +4758,7 @@ fn read_metadata_value<T>(
                 break;
             }
             _ => {
-                unused_event("read_metadata_value", &evt)?;
+                unused_event(ctx, xml, "read_metadata_value", &evt)?;
             }
         }
 
@@ -4217,7 +4781,7 @@ fn read_ods_settings(ctx: &mut OdsContext, xml: &mut OdsXmlReader<'_>) -> Result
             Event::Decl(_) => {}
 
             Event::Start(xml_tag) if xml_tag.name().as_ref() == b"office:document-settings" => {
-                let (_, xmlns) = read_namespaces_and_version(xml, xml_tag)?;
+                let (_, xmlns) = read_namespaces_and_version(ctx, xml, xml_tag)?;
                 ctx.book.xmlns.insert("settings.xml".to_string(), xmlns);
             }
             Event::End(xml_tag) if xml_tag.name().as_ref() == b"office:document-settings" => {}
@@ -4230,7 +4794,7 @@ fn read_ods_settings(ctx: &mut OdsContext, xml: &mut OdsXmlReader<'_>) -> Result
                 break;
             }
             _ => {
-                unused_event("read_settings", &evt)?;
+                unused_event(ctx, xml, "read_settings", &evt)?;
             }
         }
 
@@ -4262,7 +4826,7 @@ fn read_office_settings(ctx: &mut OdsContext, xml: &mut OdsXmlReader<'_>) -> Res
             }
             Event::Eof => break,
             _ => {
-                unused_event("read_office_settings", &evt)?;
+                unused_event(ctx, xml, "read_office_settings", &evt)?;
             }
         }
 
@@ -4290,7 +4854,13 @@ fn read_config_item_set(
                 name = Some(attr.decode_and_unescape_value(xml)?.to_string());
             }
             attr => {
-                unused_attr("read_config_item_set", super_tag.name().as_ref(), &attr)?;
+                unused_attr(
+                    ctx,
+                    xml,
+                    "read_config_item_set",
+                    super_tag.name().as_ref(),
+                    &attr,
+                )?;
             }
         }
     }
@@ -4332,7 +4902,7 @@ fn read_config_item_set(
             }
             Event::Eof => break,
             _ => {
-                unused_event("read_config_item_set", &evt)?;
+                unused_event(ctx, xml, "read_config_item_set", &evt)?;
             }
         }
 
@@ -4359,6 +4929,8 @@ fn read_config_item_map_indexed(
             }
             attr => {
                 unused_attr(
+                    ctx,
+                    xml,
                     "read_config_item_map_indexed",
                     super_tag.name().as_ref(),
                     &attr,
@@ -4394,7 +4966,7 @@ fn read_config_item_map_indexed(
             }
             Event::Eof => break,
             _ => {
-                unused_event("read_config_item_map_indexed", &evt)?;
+                unused_event(ctx, xml, "read_config_item_map_indexed", &evt)?;
             }
         }
 
@@ -4421,6 +4993,8 @@ fn read_config_item_map_named(
             }
             attr => {
                 unused_attr(
+                    ctx,
+                    xml,
                     "read_config_item_map_named",
                     super_tag.name().as_ref(),
                     &attr,
@@ -4462,7 +5036,7 @@ fn read_config_item_map_named(
             }
             Event::Eof => break,
             _ => {
-                unused_event("read_config_item_map_named", &evt)?;
+                unused_event(ctx, xml, "read_config_item_map_named", &evt)?;
             }
         }
 
@@ -4489,6 +5063,8 @@ fn read_config_item_map_entry(
             }
             attr => {
                 unused_attr(
+                    ctx,
+                    xml,
                     "read_config_item_map_entry",
                     super_tag.name().as_ref(),
                     &attr,
@@ -4532,7 +5108,7 @@ fn read_config_item_map_entry(
 
             Event::Eof => break,
             _ => {
-                unused_event("read_config_item_map_entry", &evt)?;
+                unused_event(ctx, xml, "read_config_item_map_entry", &evt)?;
             }
         }
 
@@ -4590,7 +5166,13 @@ fn read_config_item(
                 };
             }
             attr => {
-                unused_attr("read_config_item", super_tag.name().as_ref(), &attr)?;
+                unused_attr(
+                    ctx,
+                    xml,
+                    "read_config_item",
+                    super_tag.name().as_ref(),
+                    &attr,
+                )?;
             }
         }
     }
@@ -4654,7 +5236,7 @@ fn read_config_item(
                 break;
             }
             _ => {
-                unused_event("read_config_item", &evt)?;
+                unused_event(ctx, xml, "read_config_item", &evt)?;
             }
         }
 
@@ -4734,7 +5316,7 @@ fn read_xml(
                     break;
                 }
                 _ => {
-                    unused_event("read_xml", &evt)?;
+                    unused_event(ctx, xml, "read_xml", &evt)?;
                 }
             }
             buf.clear();
@@ -4891,7 +5473,7 @@ fn read_text_or_tag(
                 }
 
                 _ => {
-                    unused_event("read_text_or_tag", &evt)?;
+                    unused_event(ctx, xml, "read_text_or_tag", &evt)?;
                 }
             }
         }
@@ -4946,7 +5528,7 @@ where
                     break;
                 }
                 _ => {
-                    unused_event("read_text", &evt)?;
+                    unused_event(ctx, xml, "read_text", &evt)?;
                 }
             }
         }
@@ -4960,18 +5542,39 @@ where
 }
 
 #[inline(always)]
-fn unused_attr(func: &str, tag: &[u8], attr: &Attribute<'_>) -> Result<(), OdsError> {
+fn unused_attr(
+    ctx: &mut OdsContext,
+    xml: &OdsXmlReader<'_>,
+    func: &str,
+    tag: &[u8],
+    attr: &Attribute<'_>,
+) -> Result<(), OdsError> {
     if cfg!(feature = "dump_unused") {
         let tag = from_utf8(tag)?;
         let key = from_utf8(attr.key.as_ref())?;
         let value = from_utf8(attr.value.as_ref())?;
         println!("unused attr: {} '{}' ({}:{})", func, tag, key, value);
     }
+    if ctx.collect_issues {
+        let tag = from_utf8(tag)?;
+        let key = from_utf8(attr.key.as_ref())?;
+        let value = from_utf8(attr.value.as_ref())?;
+        ctx.issues.push(Issue::new(
+            IssueKind::UnknownAttribute,
+            format!("{}:{}", ctx.part, xml.buffer_position()),
+            format!("{}=\"{}\" in <{}>", key, value, tag),
+        ));
+    }
     Ok(())
 }
 
 #[inline(always)]
-fn unused_event(func: &str, evt: &Event<'_>) -> Result<(), OdsError> {
+fn unused_event(
+    ctx: &mut OdsContext,
+    xml: &OdsXmlReader<'_>,
+    func: &str,
+    evt: &Event<'_>,
+) -> Result<(), OdsError> {
     if cfg!(feature = "dump_unused") {
         match &evt {
             Event::Text(xml_text) => {
@@ -4984,5 +5587,28 @@ fn unused_event(func: &str, evt: &Event<'_>) -> Result<(), OdsError> {
             }
         }
     }
+    if ctx.collect_issues {
+        let message = match &evt {
+            Event::Start(xml_tag) | Event::Empty(xml_tag) => {
+                Some(format!("<{}>", from_utf8(xml_tag.name().as_ref())?))
+            }
+            Event::Text(xml_text) => {
+                let text = xml_text.unescape()?;
+                if !text.trim().is_empty() {
+                    Some(format!("text '{}'", text.trim()))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+        if let Some(message) = message {
+            ctx.issues.push(Issue::new(
+                IssueKind::UnknownElement,
+                format!("{}:{}", ctx.part, xml.buffer_position()),
+                message,
+            ));
+        }
+    }
     Ok(())
 }
@@ -1,23 +1,29 @@
 use crate::cell_::CellData;
 use crate::config::{ConfigItem, ConfigItemType, ConfigValue};
-use crate::draw::{Annotation, DrawFrame, DrawFrameContent, DrawImage};
+use crate::draw::{
+    Annotation, DrawFrame, DrawFrameContent, DrawImage, DrawShape, DrawTextBox, ImageInfo,
+    PageDrawing,
+};
 use crate::error::OdsError;
+use crate::form::{Form, FormControl, FormControlKind};
 use crate::format::{FormatPartType, ValueFormatTrait};
 use crate::io::format::{format_duration2, format_validation_condition};
 use crate::io::xmlwriter::XmlWriter;
-use crate::io::NamespaceMap;
+use crate::io::{sorted_values, NamespaceMap};
 use crate::manifest::Manifest;
 use crate::metadata::MetaValue;
 use crate::refs::{format_cellranges, CellRange};
 use crate::sheet::Visibility;
 use crate::sheet_::{dedup_colheader, CellDataIter};
+use crate::style::units::PageBreak;
 use crate::style::{
-    CellStyle, ColStyle, FontFaceDecl, GraphicStyle, HeaderFooter, MasterPage, MasterPageRef,
-    PageStyle, PageStyleRef, ParagraphStyle, RowStyle, RubyStyle, StyleOrigin, StyleUse,
-    TableStyle, TextStyle,
+    CellStyle, ColStyle, ColStyleRef, FontFaceDecl, GraphicStyle, HeaderFooter, MasterPage,
+    MasterPageRef, PageStyle, PageStyleRef, ParagraphStyle, RowStyle, RowStyleRef, RubyStyle,
+    StyleOrigin, StyleUse, TableStyle, TextStyle,
 };
 use crate::validation::ValidationDisplay;
 use crate::workbook::{EventListener, Script};
+use crate::xlink::{XLinkActuate, XLinkShow, XLinkType};
 use crate::xmltree::{XmlContent, XmlTag};
 use crate::HashMap;
 use crate::{Length, Sheet, Value, ValueType, WorkBook};
@@ -48,6 +54,8 @@ impl<T> SeekWrite for T where T: Seek + Write {}
 pub struct OdsWriteOptions {
     method: CompressionMethod,
     level: Option<i64>,
+    deterministic: bool,
+    pretty_print: bool,
 }
 
 impl OdsWriteOptions {
@@ -63,6 +71,45 @@ impl OdsWriteOptions {
         self
     }
 
+    /// Writes the same bytes for the same workbook. All zip entries get
+    /// a fixed timestamp of 1980-01-01 instead of the current time.
+    ///
+    /// Styles, value formats, fonts and the manifest are always written
+    /// ordered by name.
+    pub fn deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+        self
+    }
+
+    /// Adds line breaks to the xml, which makes diffs more readable.
+    /// Paragraphs and other elements with text content are kept on
+    /// one line, so the text doesn't change.
+    pub fn pretty_print(mut self, pretty_print: bool) -> Self {
+        self.pretty_print = pretty_print;
+        self
+    }
+
+    /// Default options for a zip entry.
+    fn entry_options(&self) -> FileOptions<'static, ()> {
+        if self.deterministic {
+            FileOptions::default().last_modified_time(zip::DateTime::default())
+        } else {
+            FileOptions::default()
+        }
+    }
+
+    /// Options for a compressed zip entry.
+    fn file_options(&self) -> FileOptions<'static, ()> {
+        self.entry_options()
+            .compression_method(self.method)
+            .compression_level(self.level)
+    }
+
+    /// Xml writer for one zip entry.
+    fn xml_writer<W: Write>(&self, writer: W) -> XmlWriter<W> {
+        XmlWriter::new(writer).line_break(self.pretty_print)
+    }
+
     /// Write the ods to the given writer.
     pub fn write_ods<T: Write + Seek>(
         self,
@@ -350,54 +397,30 @@ fn write_ods_impl<W: Write + Seek>(
     sanity_checks(book)?;
     calculations(book)?;
 
+    calc_images(book)?;
     create_manifest(book)?;
 
     zip_writer.start_file(
         "mimetype",
-        FileOptions::<()>::default().compression_method(CompressionMethod::Stored),
+        cfg.entry_options().compression_method(CompressionMethod::Stored),
     )?;
     write_ods_mimetype(&mut zip_writer)?;
 
-    zip_writer.add_directory("META-INF", FileOptions::<()>::default())?;
-    zip_writer.start_file(
-        "META-INF/manifest.xml",
-        FileOptions::<()>::default()
-            .compression_method(cfg.method)
-            .compression_level(cfg.level),
-    )?;
-    write_ods_manifest(book, &mut XmlWriter::new(&mut zip_writer))?;
+    zip_writer.add_directory("META-INF", cfg.entry_options())?;
+    zip_writer.start_file("META-INF/manifest.xml", cfg.file_options())?;
+    write_ods_manifest(book, &mut cfg.xml_writer(&mut zip_writer))?;
 
-    zip_writer.start_file(
-        "meta.xml",
-        FileOptions::<()>::default()
-            .compression_method(cfg.method)
-            .compression_level(cfg.level),
-    )?;
-    write_ods_metadata(book, &mut XmlWriter::new(&mut zip_writer))?;
+    zip_writer.start_file("meta.xml", cfg.file_options())?;
+    write_ods_metadata(book, &mut cfg.xml_writer(&mut zip_writer))?;
 
-    zip_writer.start_file(
-        "settings.xml",
-        FileOptions::<()>::default()
-            .compression_method(cfg.method)
-            .compression_level(cfg.level),
-    )?;
-    write_ods_settings(book, &mut XmlWriter::new(&mut zip_writer))?;
+    zip_writer.start_file("settings.xml", cfg.file_options())?;
+    write_ods_settings(book, &mut cfg.xml_writer(&mut zip_writer))?;
 
-    zip_writer.start_file(
-        "styles.xml",
-        FileOptions::<()>::default()
-            .compression_method(cfg.method)
-            .compression_level(cfg.level),
-    )?;
-    write_ods_styles(book, &mut XmlWriter::new(&mut zip_writer))?;
+    zip_writer.start_file("styles.xml", cfg.file_options())?;
+    write_ods_styles(book, &mut cfg.xml_writer(&mut zip_writer))?;
 
-    zip_writer.start_file(
-        "content.xml",
-        FileOptions::<()>::default()
-            .compression_method(cfg.method)
-            .compression_level(cfg.level),
-    )?;
-    write_ods_content(book, &mut XmlWriter::new(&mut zip_writer))?;
+    zip_writer.start_file("content.xml", cfg.file_options())?;
+    write_ods_content(book, &mut cfg.xml_writer(&mut zip_writer))?;
 
     write_ods_extra(&cfg, &mut zip_writer, book)?;
 
@@ -485,6 +508,34 @@ fn calc_col_header_styles(book: &mut WorkBook) -> Result<(), OdsError> {
     for i in 0..book.num_sheets() {
         let mut sheet = book.detach_sheet(i);
 
+        // Manual page breaks need a column style with fo:break-before.
+        let mut variants: HashMap<(Option<ColStyleRef>, bool), ColStyleRef> = HashMap::new();
+        for ch in sheet.col_header.values_mut() {
+            let style = ch.style.as_ref().and_then(|v| book.colstyle(v));
+            let has_break = match style {
+                Some(style) => style.break_before()? == Some(PageBreak::Page),
+                None => false,
+            };
+            if ch.page_break != has_break {
+                let key = (ch.style.clone(), ch.page_break);
+                let variant = if let Some(variant) = variants.get(&key) {
+                    variant.clone()
+                } else {
+                    let mut style = style.cloned().unwrap_or_else(ColStyle::new_empty);
+                    style.set_name("");
+                    if ch.page_break {
+                        style.set_break_before(PageBreak::Page);
+                    } else {
+                        style.colstyle_mut().clear_attr("fo:break-before");
+                    }
+                    let variant = book.add_colstyle(style);
+                    variants.insert(key, variant.clone());
+                    variant
+                };
+                ch.style = Some(variant);
+            }
+        }
+
         // Set the column widths.
         for ch in sheet.col_header.values_mut() {
             // Any non default values?
@@ -517,6 +568,34 @@ fn calc_row_header_styles(book: &mut WorkBook) -> Result<(), OdsError> {
     for i in 0..book.num_sheets() {
         let mut sheet = book.detach_sheet(i);
 
+        // Manual page breaks need a row style with fo:break-before.
+        let mut variants: HashMap<(Option<RowStyleRef>, bool), RowStyleRef> = HashMap::new();
+        for rh in sheet.row_header.values_mut() {
+            let style = rh.style.as_ref().and_then(|v| book.rowstyle(v));
+            let has_break = match style {
+                Some(style) => style.break_before()? == Some(PageBreak::Page),
+                None => false,
+            };
+            if rh.page_break != has_break {
+                let key = (rh.style.clone(), rh.page_break);
+                let variant = if let Some(variant) = variants.get(&key) {
+                    variant.clone()
+                } else {
+                    let mut style = style.cloned().unwrap_or_else(RowStyle::new_empty);
+                    style.set_name("");
+                    if rh.page_break {
+                        style.set_break_before(PageBreak::Page);
+                    } else {
+                        style.rowstyle_mut().clear_attr("fo:break-before");
+                    }
+                    let variant = book.add_rowstyle(style);
+                    variants.insert(key, variant.clone());
+                    variant
+                };
+                rh.style = Some(variant);
+            }
+        }
+
         for rh in sheet.row_header.values_mut() {
             if rh.height != Length::Default && rh.style.is_none() {
                 let rowstyle = book.add_rowstyle(RowStyle::new_empty());
@@ -621,6 +700,62 @@ fn calc_config(book: &mut WorkBook) -> Result<(), OdsError> {
     Ok(())
 }
 
+/// Moves embedded image data to separate files in the package.
+fn calc_images(book: &mut WorkBook) -> Result<(), OdsError> {
+    for i in 0..book.num_sheets() {
+        let mut sheet = book.detach_sheet(i);
+
+        for cell in sheet.data.values_mut() {
+            if let Some(extra) = cell.extra.as_mut() {
+                for frame in extra.draw_frames.iter_mut() {
+                    package_images(book, frame);
+                }
+            }
+        }
+        for frame in sheet.page_frames_mut() {
+            package_images(book, frame);
+        }
+
+        book.attach_sheet(sheet);
+    }
+
+    Ok(())
+}
+
+fn package_images(book: &mut WorkBook, frame: &mut DrawFrame) {
+    for content in frame.content_mut() {
+        let DrawFrameContent::Image(image) = content else {
+            continue;
+        };
+        if image.attrmap().attr("xlink:href").is_some() || image.get_binary_base64().is_none() {
+            continue;
+        }
+        // leave it embedded if it can't be decoded.
+        let Ok(bytes) = image.get_binary() else {
+            continue;
+        };
+        let Some(info) = ImageInfo::detect(&bytes) else {
+            continue;
+        };
+
+        let href = info.package_path(&bytes);
+        let mime_type = image
+            .attrmap()
+            .attr("draw:mime-type")
+            .unwrap_or(info.mime_type())
+            .to_string();
+        image.set_xlink_href(href.as_str());
+        image.set_xlink_type(XLinkType::Simple);
+        image.set_xlink_show(XLinkShow::Embed);
+        image.set_xlink_actuate(XLinkActuate::OnLoad);
+        image.clear_binary();
+
+        if book.manifest(&href).is_none() {
+            book.add_manifest(Manifest::with_buf(href, mime_type, bytes));
+        }
+    }
+}
+
 // Create the standard manifest entries.
 fn create_manifest(book: &mut WorkBook) -> Result<(), OdsError> {
     if !book.manifest.contains_key("/") {
@@ -707,7 +842,7 @@ fn write_ods_manifest(book: &WorkBook, xml_out: &mut OdsXmlWriter<'_>) -> Result
     )?;
     xml_out.attr_esc("manifest:version", &book.version())?;
 
-    for manifest in book.manifest.values() {
+    for manifest in sorted_values(&book.manifest) {
         xml_out.empty("manifest:file-entry")?;
         xml_out.attr_esc("manifest:full-path", &manifest.full_path)?;
         if let Some(version) = &manifest.version {
@@ -1418,7 +1553,7 @@ fn write_content_validations(
     if !book.validations.is_empty() {
         xml_out.elem("table:content-validations")?;
 
-        for valid in book.validations.values() {
+        for valid in sorted_values(&book.validations) {
             xml_out.elem("table:content-validation")?;
             xml_out.attr_esc("table:name", valid.name())?;
             xml_out.attr_esc("table:condition", &format_validation_condition(valid))?;
@@ -1599,13 +1734,14 @@ fn write_sheet(
             || tag.name() == "table:table-source"
             || tag.name() == "office:dde-source"
             || tag.name() == "table:scenario"
-            || tag.name() == "office:forms"
-            || tag.name() == "table:shapes"
         {
             write_xmltag(tag, xml_out)?;
         }
     }
 
+    write_office_forms(sheet, xml_out)?;
+    write_table_shapes(sheet, xml_out)?;
+
     let max_cell = sheet.used_grid_size();
 
     write_table_columns(sheet, max_cell, xml_out)?;
@@ -2128,7 +2264,10 @@ fn write_cell(
         "table:table-cell"
     };
 
-    let has_subs = cell.value != Value::Empty || cell.has_annotation() || cell.has_draw_frames();
+    let has_subs = cell.value != Value::Empty
+        || cell.has_annotation()
+        || cell.has_draw_frames()
+        || cell.has_draw_shapes();
     xml_out.elem_if(has_subs, tag)?;
 
     if let Some(formula) = &cell.formula {
@@ -2218,11 +2357,16 @@ fn write_cell(
         }
         Value::Currency(v, c) => {
             xml_out.attr_str("office:value-type", "currency")?;
-            xml_out.attr_esc("office:currency", c)?;
+            // office:currency is optional, an empty one is not valid.
+            if !c.is_empty() {
+                xml_out.attr_esc("office:currency", c)?;
+            }
             xml_out.attr("office:value", v)?;
             xml_out.elem("text:p")?;
-            xml_out.text_esc(c)?;
-            xml_out.text_str(" ")?;
+            if !c.is_empty() {
+                xml_out.text_esc(c)?;
+                xml_out.text_str(" ")?;
+            }
             xml_out.text(v)?;
             xml_out.end_elem("text:p")?;
         }
@@ -2240,6 +2384,42 @@ fn write_cell(
             xml_out.text(v)?;
             xml_out.end_elem("text:p")?;
         }
+        Value::CurrencyDecimal(v, c) => {
+            xml_out.attr_str("office:value-type", "currency")?;
+            // office:currency is optional, an empty one is not valid.
+            if !c.is_empty() {
+                xml_out.attr_esc("office:currency", c)?;
+            }
+            xml_out.attr("office:value", v)?;
+            xml_out.elem("text:p")?;
+            if !c.is_empty() {
+                xml_out.text_esc(c)?;
+                xml_out.text_str(" ")?;
+            }
+            xml_out.text(v)?;
+            xml_out.end_elem("text:p")?;
+        }
+        Value::NumberDecimal(v) => {
+            xml_out.attr_str("office:value-type", "float")?;
+            xml_out.attr("office:value", v)?;
+            xml_out.elem("text:p")?;
+            xml_out.text(v)?;
+            xml_out.end_elem("text:p")?;
+        }
+        Value::PercentageDecimal(v) => {
+            xml_out.attr_str("office:value-type", "percentage")?;
+            xml_out.attr("office:value", v)?;
+            xml_out.elem("text:p")?;
+            xml_out.text(v)?;
+            xml_out.end_elem("text:p")?;
+        }
+        Value::Error(e) => {
+            // like LibreOffice: a string with the extended value-type.
+            xml_out.attr_str("office:value-type", "string")?;
+            xml_out.attr_esc("office:string-value", e.as_str())?;
+            xml_out.attr_str("calcext:value-type", "error")?;
+            xml_out.elem_text_esc("text:p", e.as_str())?;
+        }
     }
 
     if let Some(annotation) = cell.extra.as_ref().and_then(|v| v.annotation.as_ref()) {
@@ -2252,11 +2432,125 @@ fn write_cell(
         }
     }
 
+    if let Some(draw_shapes) = cell.extra.as_ref().map(|v| &v.draw_shapes) {
+        for draw_shape in draw_shapes {
+            write_draw_shape(draw_shape, xml_out)?;
+        }
+    }
+
+    xml_out.end_elem_if(has_subs, tag)?;
+
+    Ok(())
+}
+
+/// Forms of the sheet. Unknown elements and the attributes from reading are
+/// kept as an office:forms tag in extra.
+fn write_office_forms(sheet: &Sheet, xml_out: &mut OdsXmlWriter<'_>) -> Result<(), OdsError> {
+    let unknown = sheet.extra.iter().find(|v| v.name() == "office:forms");
+
+    if sheet.forms.is_empty() && unknown.is_none() {
+        return Ok(());
+    }
+
+    let has_subs = !sheet.forms.is_empty() || unknown.map(|v| !v.is_empty()).unwrap_or(false);
+    xml_out.elem_if(has_subs, "office:forms")?;
+    if let Some(unknown) = unknown {
+        for (k, v) in unknown.attrmap().iter() {
+            xml_out.attr_esc(k.as_ref(), v)?;
+        }
+    } else {
+        xml_out.attr_str("form:automatic-focus", "false")?;
+        xml_out.attr_str("form:apply-design-mode", "false")?;
+    }
+    for form in &sheet.forms {
+        write_form(form, xml_out)?;
+    }
+    if let Some(unknown) = unknown {
+        for content in unknown.content() {
+            if let XmlContent::Tag(tag) = content {
+                write_xmltag(tag, xml_out)?;
+            }
+        }
+    }
+    xml_out.end_elem_if(has_subs, "office:forms")?;
+
+    Ok(())
+}
+
+fn write_form(form: &Form, xml_out: &mut OdsXmlWriter<'_>) -> Result<(), OdsError> {
+    let has_subs = !form.controls().is_empty() || !form.extra().is_empty();
+    xml_out.elem_if(has_subs, "form:form")?;
+    for (k, v) in form.attrmap().iter() {
+        xml_out.attr_esc(k.as_ref(), v)?;
+    }
+    // form:properties and office:event-listeners come first.
+    for tag in form.extra() {
+        if tag.name() == "form:properties" || tag.name() == "office:event-listeners" {
+            write_xmltag(tag, xml_out)?;
+        }
+    }
+    for control in form.controls() {
+        write_form_control(control, xml_out)?;
+    }
+    for tag in form.extra() {
+        if tag.name() != "form:properties" && tag.name() != "office:event-listeners" {
+            write_xmltag(tag, xml_out)?;
+        }
+    }
+    xml_out.end_elem_if(has_subs, "form:form")?;
+
+    Ok(())
+}
+
+fn write_form_control(
+    control: &FormControl,
+    xml_out: &mut OdsXmlWriter<'_>,
+) -> Result<(), OdsError> {
+    let tag = control.kind().tag();
+    let item_tag = if control.kind() == FormControlKind::ComboBox {
+        "form:item"
+    } else {
+        "form:option"
+    };
+
+    let has_subs = !control.extra().is_empty() || !control.items().is_empty();
+    xml_out.elem_if(has_subs, tag)?;
+    for (k, v) in control.attrmap().iter() {
+        xml_out.attr_esc(k.as_ref(), v)?;
+    }
+    for content in control.extra() {
+        write_xmltag(content, xml_out)?;
+    }
+    for item in control.items() {
+        xml_out.empty(item_tag)?;
+        for (k, v) in item.attrmap().iter() {
+            xml_out.attr_esc(k.as_ref(), v)?;
+        }
+    }
     xml_out.end_elem_if(has_subs, tag)?;
 
     Ok(())
 }
 
+/// Drawings anchored to the page in document order.
+fn write_table_shapes(sheet: &Sheet, xml_out: &mut OdsXmlWriter<'_>) -> Result<(), OdsError> {
+    if sheet.page_drawings.is_empty() {
+        return Ok(());
+    }
+
+    xml_out.elem("table:shapes")?;
+    for drawing in &sheet.page_drawings {
+        match drawing {
+            PageDrawing::Frame(draw_frame) => write_draw_frame(draw_frame, xml_out)?,
+            PageDrawing::Shape(draw_shape) => write_draw_shape(draw_shape, xml_out)?,
+            PageDrawing::Xml(tag) => write_xmltag(tag, xml_out)?,
+        }
+    }
+    xml_out.end_elem("table:shapes")?;
+
+    Ok(())
+}
+
 fn write_draw_frame(
     draw_frame: &DrawFrame,
     xml_out: &mut OdsXmlWriter<'_>,
@@ -2271,6 +2565,9 @@ fn write_draw_frame(
             DrawFrameContent::Image(img) => {
                 write_draw_image(img, xml_out)?;
             }
+            DrawFrameContent::TextBox(text_box) => {
+                write_draw_text_box(text_box, xml_out)?;
+            }
         }
     }
 
@@ -2290,6 +2587,60 @@ fn write_draw_frame(
     Ok(())
 }
 
+fn write_draw_text_box(
+    text_box: &DrawTextBox,
+    xml_out: &mut OdsXmlWriter<'_>,
+) -> Result<(), OdsError> {
+    xml_out.elem_if(!text_box.text().is_empty(), "draw:text-box")?;
+    for (k, v) in text_box.attrmap().iter() {
+        xml_out.attr_esc(k.as_ref(), v)?;
+    }
+    for content in text_box.text() {
+        write_xmltag(content, xml_out)?;
+    }
+    xml_out.end_elem_if(!text_box.text().is_empty(), "draw:text-box")?;
+
+    Ok(())
+}
+
+fn write_draw_shape(
+    draw_shape: &DrawShape,
+    xml_out: &mut OdsXmlWriter<'_>,
+) -> Result<(), OdsError> {
+    let tag = draw_shape.kind().tag();
+    xml_out.elem(tag)?;
+    for (k, v) in draw_shape.attrmap().iter() {
+        xml_out.attr_esc(k.as_ref(), v)?;
+    }
+
+    if let Some(title) = draw_shape.title() {
+        xml_out.elem("svg:title")?;
+        xml_out.text_esc(title)?;
+        xml_out.end_elem("svg:title")?;
+    }
+    if let Some(desc) = draw_shape.desc() {
+        xml_out.elem("svg:desc")?;
+        xml_out.text_esc(desc)?;
+        xml_out.end_elem("svg:desc")?;
+    }
+    for content in draw_shape.extra() {
+        if content.name() != "draw:enhanced-geometry" {
+            write_xmltag(content, xml_out)?;
+        }
+    }
+    for content in draw_shape.text() {
+        write_xmltag(content, xml_out)?;
+    }
+    // the schema wants the geometry last.
+    if let Some(geometry) = draw_shape.enhanced_geometry() {
+        write_xmltag(geometry, xml_out)?;
+    }
+
+    xml_out.end_elem(tag)?;
+
+    Ok(())
+}
+
 fn write_draw_image(
     draw_image: &DrawImage,
     xml_out: &mut OdsXmlWriter<'_>,
@@ -2361,7 +2712,7 @@ fn write_event_listeners(
     events: &HashMap<String, EventListener>,
     xml_out: &mut OdsXmlWriter<'_>,
 ) -> Result<(), OdsError> {
-    for event in events.values() {
+    for event in sorted_values(events) {
         xml_out.empty("script:event-listener")?;
         xml_out.attr_esc("script:event-name", &event.event_name)?;
         xml_out.attr_esc("script:language", &event.script_lang)?;
@@ -2390,7 +2741,7 @@ fn write_style_font_face(
     origin: StyleOrigin,
     xml_out: &mut OdsXmlWriter<'_>,
 ) -> Result<(), OdsError> {
-    for font in fonts.values().filter(|s| s.origin() == origin) {
+    for font in sorted_values(fonts).filter(|s| s.origin() == origin) {
         xml_out.empty("style:font-face")?;
         xml_out.attr_esc("style:name", font.name())?;
         for (a, v) in font.attrmap().iter() {
@@ -2443,42 +2794,42 @@ fn write_styles(
     styleuse: StyleUse,
     xml_out: &mut OdsXmlWriter<'_>,
 ) -> Result<(), OdsError> {
-    for style in book.colstyles.values() {
+    for style in sorted_values(&book.colstyles) {
         if style.origin() == origin && style.styleuse() == styleuse {
             write_colstyle(style, xml_out)?;
         }
     }
-    for style in book.rowstyles.values() {
+    for style in sorted_values(&book.rowstyles) {
         if style.origin() == origin && style.styleuse() == styleuse {
             write_rowstyle(style, xml_out)?;
         }
     }
-    for style in book.tablestyles.values() {
+    for style in sorted_values(&book.tablestyles) {
         if style.origin() == origin && style.styleuse() == styleuse {
             write_tablestyle(style, xml_out)?;
         }
     }
-    for style in book.cellstyles.values() {
+    for style in sorted_values(&book.cellstyles) {
         if style.origin() == origin && style.styleuse() == styleuse {
             write_cellstyle(style, xml_out)?;
         }
     }
-    for style in book.paragraphstyles.values() {
+    for style in sorted_values(&book.paragraphstyles) {
         if style.origin() == origin && style.styleuse() == styleuse {
             write_paragraphstyle(style, xml_out)?;
         }
     }
-    for style in book.textstyles.values() {
+    for style in sorted_values(&book.textstyles) {
         if style.origin() == origin && style.styleuse() == styleuse {
             write_textstyle(style, xml_out)?;
         }
     }
-    for style in book.rubystyles.values() {
+    for style in sorted_values(&book.rubystyles) {
         if style.origin() == origin && style.styleuse() == styleuse {
             write_rubystyle(style, xml_out)?;
         }
     }
-    for style in book.graphicstyles.values() {
+    for style in sorted_values(&book.graphicstyles) {
         if style.origin() == origin && style.styleuse() == styleuse {
             write_graphicstyle(style, xml_out)?;
         }
@@ -2868,12 +3219,11 @@ fn write_valuestyle<T: ValueFormatTrait>(
     styleuse: StyleUse,
     xml_out: &mut OdsXmlWriter<'_>,
 ) -> Result<(), OdsError> {
-    for value_format in value_formats
-        .values()
-        .filter(|s| s.origin() == origin && s.styleuse() == styleuse)
+    for value_format in
+        sorted_values(value_formats).filter(|s| s.origin() == origin && s.styleuse() == styleuse)
     {
         let tag = match value_format.value_type() {
-            ValueType::Empty => unreachable!(),
+            ValueType::Empty | ValueType::Error => unreachable!(),
             ValueType::Boolean => "number:boolean-style",
             ValueType::Number => "number:number-style",
             ValueType::Text => "number:text-style",
@@ -2984,7 +3334,7 @@ fn write_pagestyles(
     styles: &HashMap<PageStyleRef, PageStyle>,
     xml_out: &mut OdsXmlWriter<'_>,
 ) -> Result<(), OdsError> {
-    for style in styles.values() {
+    for style in sorted_values(styles) {
         xml_out.elem("style:page-layout")?;
         xml_out.attr_esc("style:name", style.name())?;
         if let Some(master_page_usage) = &style.master_page_usage {
@@ -3026,7 +3376,7 @@ fn write_masterpage(
     masterpages: &HashMap<MasterPageRef, MasterPage>,
     xml_out: &mut OdsXmlWriter<'_>,
 ) -> Result<(), OdsError> {
-    for masterpage in masterpages.values() {
+    for masterpage in sorted_values(masterpages) {
         xml_out.elem("style:master-page")?;
         xml_out.attr_esc("style:name", masterpage.name())?;
         if !masterpage.display_name().is_empty() {
@@ -3136,6 +3486,9 @@ fn write_xmltag(x: &XmlTag, xml_out: &mut OdsXmlWriter<'_>) -> Result<(), OdsErr
     for (k, v) in x.attrmap().iter() {
         xml_out.attr_esc(k.as_ref(), v)?;
     }
+    if x.content().iter().any(|v| matches!(v, XmlContent::Text(_))) {
+        xml_out.mixed_content();
+    }
 
     for c in x.content() {
         match c {
@@ -3159,20 +3512,15 @@ fn write_ods_extra<W: Write + Seek>(
     zip_writer: &mut ZipWriter<W>,
     book: &WorkBook,
 ) -> Result<(), OdsError> {
-    for manifest in book.manifest.values() {
+    for manifest in sorted_values(&book.manifest) {
         if !matches!(
             manifest.full_path.as_str(),
             "/" | "settings.xml" | "styles.xml" | "content.xml" | "meta.xml"
         ) {
             if manifest.is_dir() {
-                zip_writer.add_directory(&manifest.full_path, FileOptions::<()>::default())?;
+                zip_writer.add_directory(&manifest.full_path, cfg.entry_options())?;
             } else {
-                zip_writer.start_file(
-                    manifest.full_path.as_str(),
-                    FileOptions::<()>::default()
-                        .compression_method(cfg.method)
-                        .compression_level(cfg.level),
-                )?;
+                zip_writer.start_file(manifest.full_path.as_str(), cfg.file_options())?;
                 if let Some(buf) = &manifest.buffer {
                     zip_writer.write_all(buf.as_slice())?;
                 }
@@ -0,0 +1,547 @@
+//!
+//! Office Open XML (xlsx) support.
+//!
+//! Contains the parts that are shared between reading and writing,
+//! mainly the conversion between OpenFormula and the A1 syntax used by
+//! Excel.
+//!
+
+use crate::refs::format::{fmt_col_name, fmt_row_name};
+use crate::refs::format_refs::{fmt_cell_range_a1, fmt_cell_ref_a1, fmt_table_name_a1};
+use crate::refs::{
+    parse_cellrange, parse_cellrange_a1, parse_cellref, parse_cellref_a1, parse_colrange,
+    parse_rowrange,
+};
+use crate::{CellRange, CellRef};
+
+pub(crate) mod read;
+pub(crate) mod write;
+
+#[cfg(test)]
+mod tests;
+
+pub(crate) const NS_MAIN: &str = "http://schemas.openxmlformats.org/spreadsheetml/2006/main";
+pub(crate) const NS_REL: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+pub(crate) const NS_PKG_REL: &str = "http://schemas.openxmlformats.org/package/2006/relationships";
+pub(crate) const NS_CONTENT_TYPES: &str =
+    "http://schemas.openxmlformats.org/package/2006/content-types";
+pub(crate) const NS_DRAWING: &str =
+    "http://schemas.openxmlformats.org/drawingml/2006/spreadsheetDrawing";
+pub(crate) const NS_DRAWING_MAIN: &str = "http://schemas.openxmlformats.org/drawingml/2006/main";
+
+pub(crate) const REL_OFFICE_DOCUMENT: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument";
+pub(crate) const REL_WORKSHEET: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet";
+pub(crate) const REL_STYLES: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles";
+pub(crate) const REL_SHARED_STRINGS: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/sharedStrings";
+pub(crate) const REL_COMMENTS: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/comments";
+pub(crate) const REL_VML_DRAWING: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/vmlDrawing";
+pub(crate) const REL_DRAWING: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/drawing";
+pub(crate) const REL_IMAGE: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/image";
+
+/// Maximum number of rows in a worksheet.
+pub(crate) const MAX_ROWS: u32 = 1_048_576;
+/// Maximum number of columns in a worksheet.
+pub(crate) const MAX_COLS: u32 = 16_384;
+
+/// Converts an OpenFormula expression to the A1 syntax used by Excel.
+///
+/// The "of:" namespace and the leading "=" are removed, references
+/// like [$Sheet1.A1:.B2] are converted to Sheet1!A1:B2 and the
+/// parameter separator ';' is replaced with ','. In inline arrays the
+/// row separator '|' becomes ';'. String literals are copied unchanged.
+pub(crate) fn excel_formula(formula: &str) -> String {
+    let f = formula.strip_prefix("of:").unwrap_or(formula);
+    let f = f.strip_prefix('=').unwrap_or(f);
+
+    let mut buf = String::with_capacity(f.len());
+    let mut array = false;
+    let mut rest = f;
+    while let Some(c) = rest.chars().next() {
+        match c {
+            '"' => {
+                let end = string_end(rest);
+                buf.push_str(&rest[..end]);
+                rest = &rest[end..];
+            }
+            '[' => {
+                if let Some(end) = ref_end(rest) {
+                    excel_ref(&mut buf, &rest[1..end]);
+                    rest = &rest[end + 1..];
+                } else {
+                    buf.push_str(rest);
+                    rest = "";
+                }
+            }
+            '{' | '}' => {
+                array = c == '{';
+                buf.push(c);
+                rest = &rest[1..];
+            }
+            ';' | '~' => {
+                buf.push(',');
+                rest = &rest[1..];
+            }
+            '|' if array => {
+                buf.push(';');
+                rest = &rest[1..];
+            }
+            '!' => {
+                // intersection
+                buf.push(' ');
+                rest = &rest[1..];
+            }
+            _ => {
+                if let Some(r) = rest.strip_prefix("COM.MICROSOFT.") {
+                    buf.push_str("_xlfn.");
+                    rest = r;
+                } else {
+                    buf.push(c);
+                    rest = &rest[c.len_utf8()..];
+                }
+            }
+        }
+    }
+
+    buf
+}
+
+// Byte index after the closing quote of the string literal at the
+// start of s. Doubled quotes are part of the literal.
+fn string_end(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    let mut i = 1;
+    while i < bytes.len() {
+        if bytes[i] == b'"' {
+            if bytes.get(i + 1) == Some(&b'"') {
+                i += 2;
+                continue;
+            }
+            return i + 1;
+        }
+        i += 1;
+    }
+    s.len()
+}
+
+// Byte index of the ']' that closes the reference at the start of s.
+// Quoted sheet names may contain a ']'.
+fn ref_end(s: &str) -> Option<usize> {
+    let mut quoted = false;
+    for (i, c) in s.char_indices() {
+        match c {
+            '\'' => quoted = !quoted,
+            ']' if !quoted => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Appends the A1 notation for the content of a [...] reference.
+fn excel_ref(buf: &mut String, reference: &str) {
+    if let Ok(r) = parse_cellrange(reference) {
+        push_sheet_prefix(buf, r.table(), r.to_table());
+        push_col(buf, r.col_abs(), r.col());
+        push_row(buf, r.row_abs(), r.row());
+        buf.push(':');
+        push_col(buf, r.to_col_abs(), r.to_col());
+        push_row(buf, r.to_row_abs(), r.to_row());
+    } else if let Ok(r) = parse_cellref(reference) {
+        push_sheet_prefix(buf, r.table(), None);
+        push_col(buf, r.col_abs(), r.col());
+        push_row(buf, r.row_abs(), r.row());
+    } else if let Ok(r) = parse_colrange(reference) {
+        push_sheet_prefix(buf, r.table(), r.to_table());
+        push_col(buf, r.col_abs(), r.col());
+        buf.push(':');
+        push_col(buf, r.to_col_abs(), r.to_col());
+    } else if let Ok(r) = parse_rowrange(reference) {
+        push_sheet_prefix(buf, r.table(), r.to_table());
+        push_row(buf, r.row_abs(), r.row());
+        buf.push(':');
+        push_row(buf, r.to_row_abs(), r.to_row());
+    } else {
+        buf.push_str("#REF!");
+    }
+}
+
+fn push_sheet_prefix(buf: &mut String, table: Option<&String>, to_table: Option<&String>) {
+    if let Some(table) = table {
+        match to_table {
+            Some(to_table) if to_table != table => {
+                let _ = fmt_table_name_a1(buf, format!("{}:{}", table, to_table).as_str());
+            }
+            _ => {
+                let _ = fmt_table_name_a1(buf, table);
+            }
+        }
+        buf.push('!');
+    }
+}
+
+fn push_col(buf: &mut String, abs: bool, col: u32) {
+    if abs {
+        buf.push('$');
+    }
+    let _ = fmt_col_name(buf, col);
+}
+
+fn push_row(buf: &mut String, abs: bool, row: u32) {
+    if abs {
+        buf.push('$');
+    }
+    let _ = fmt_row_name(buf, row);
+}
+
+/// Cell reference in A1 notation, without any $.
+pub(crate) fn a1_cell(row: u32, col: u32) -> String {
+    let mut buf = String::new();
+    let _ = fmt_cell_ref_a1(&mut buf, &CellRef::local(row, col));
+    buf
+}
+
+/// Cell range in A1 notation, without any $. Collapses to a single
+/// cell reference if the range covers only one cell.
+pub(crate) fn a1_range(row: u32, col: u32, to_row: u32, to_col: u32) -> String {
+    let mut buf = String::new();
+    let _ = fmt_cell_range_a1(&mut buf, &CellRange::local(row, col, to_row, to_col));
+    buf
+}
+
+/// Converts an Excel formula in A1 syntax to OpenFormula.
+///
+/// This is the reverse of excel_formula(). Relative references are
+/// moved by the given offset, which is needed for shared formulas.
+/// Anything that can't be recognized as a reference is copied unchanged.
+pub(crate) fn open_formula(formula: &str, row_offset: u32, col_offset: u32) -> String {
+    let f = formula.strip_prefix('=').unwrap_or(formula);
+
+    let mut buf = String::with_capacity(f.len() + 16);
+    buf.push_str("of:=");
+    let mut array = false;
+    let mut rest = f;
+    while let Some(c) = rest.chars().next() {
+        match c {
+            '"' => {
+                let end = string_end(rest);
+                buf.push_str(&rest[..end]);
+                rest = &rest[end..];
+            }
+            '\'' => {
+                let end = quoted_end(rest);
+                let sheet = rest[1..end.max(2) - 1].replace("''", "'");
+                // 3D references are quoted as a whole: 'Sheet 1:Sheet 3'!A1
+                let (sheet, to_sheet) = match sheet.split_once(':') {
+                    Some((sheet, to_sheet)) => (sheet, Some(to_sheet)),
+                    None => (sheet.as_str(), None),
+                };
+                match rest[end..]
+                    .strip_prefix('!')
+                    .and_then(|v| a1_ref(v).map(|r| (r, v)))
+                {
+                    Some(((r, len), v)) => {
+                        r.push_open(&mut buf, Some(sheet), to_sheet, row_offset, col_offset);
+                        rest = &v[len..];
+                    }
+                    None => {
+                        buf.push_str(&rest[..end]);
+                        rest = &rest[end..];
+                    }
+                }
+            }
+            '#' => {
+                // error literals
+                let end = rest[1..]
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '/'))
+                    .map(|v| v + 1)
+                    .unwrap_or(rest.len());
+                let end = if matches!(rest[end..].chars().next(), Some('!' | '?')) {
+                    end + 1
+                } else {
+                    end
+                };
+                buf.push_str(&rest[..end]);
+                rest = &rest[end..];
+            }
+            '{' | '}' => {
+                array = c == '{';
+                buf.push(c);
+                rest = &rest[1..];
+            }
+            ',' => {
+                buf.push(';');
+                rest = &rest[1..];
+            }
+            ';' => {
+                buf.push(if array { '|' } else { ';' });
+                rest = &rest[1..];
+            }
+            c if is_word_char(c) => {
+                let end = rest.find(|c: char| !is_word_char(c)).unwrap_or(rest.len());
+                let word = &rest[..end];
+                let after = &rest[end..];
+                if let Some(v) = after.strip_prefix('!') {
+                    if let Some((r, len)) = a1_ref(v) {
+                        r.push_open(&mut buf, Some(word), None, row_offset, col_offset);
+                        rest = &v[len..];
+                    } else {
+                        buf.push_str(word);
+                        rest = after;
+                    }
+                } else if let Some((to_sheet, r, len)) = a1_ref_3d(after) {
+                    r.push_open(&mut buf, Some(word), Some(to_sheet), row_offset, col_offset);
+                    rest = &after[len..];
+                } else if after.starts_with('(') {
+                    if let Some(name) = word.strip_prefix("_xlfn.") {
+                        buf.push_str("COM.MICROSOFT.");
+                        buf.push_str(name.strip_prefix("_xlws.").unwrap_or(name));
+                    } else {
+                        buf.push_str(word);
+                    }
+                    rest = after;
+                } else if let Some((r, len)) = a1_ref(rest) {
+                    r.push_open(&mut buf, None, None, row_offset, col_offset);
+                    rest = &rest[len..];
+                } else {
+                    buf.push_str(word);
+                    rest = after;
+                }
+            }
+            _ => {
+                buf.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    buf
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '.' || c == '$'
+}
+
+// Byte index after the closing quote of the quoted name at the start
+// of s. Doubled quotes are part of the name.
+fn quoted_end(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    let mut i = 1;
+    while i < bytes.len() {
+        if bytes[i] == b'\'' {
+            if bytes.get(i + 1) == Some(&b'\'') {
+                i += 2;
+                continue;
+            }
+            return i + 1;
+        }
+        i += 1;
+    }
+    s.len()
+}
+
+/// A reference in A1 syntax.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum A1Ref {
+    Cell(A1Pos),
+    Range(A1Pos, A1Pos),
+    Cols((bool, u32), (bool, u32)),
+    Rows((bool, u32), (bool, u32)),
+}
+
+/// Absolute flag and value for row and column.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct A1Pos {
+    row: (bool, u32),
+    col: (bool, u32),
+}
+
+impl A1Ref {
+    fn push_open(
+        &self,
+        buf: &mut String,
+        sheet: Option<&str>,
+        to_sheet: Option<&str>,
+        row_offset: u32,
+        col_offset: u32,
+    ) {
+        let shift_row = |(abs, row): (bool, u32)| {
+            if abs {
+                (abs, row)
+            } else {
+                (abs, row.saturating_add(row_offset))
+            }
+        };
+        let shift_col = |(abs, col): (bool, u32)| {
+            if abs {
+                (abs, col)
+            } else {
+                (abs, col.saturating_add(col_offset))
+            }
+        };
+        let push_pos = |buf: &mut String, pos: &A1Pos| {
+            let (abs, col) = shift_col(pos.col);
+            push_col(buf, abs, col);
+            let (abs, row) = shift_row(pos.row);
+            push_row(buf, abs, row);
+        };
+
+        let push_to = |buf: &mut String| {
+            buf.push(':');
+            if let Some(to_sheet) = to_sheet {
+                buf.push('$');
+                let _ = fmt_table_name_a1(buf, to_sheet);
+            }
+            buf.push('.');
+        };
+
+        buf.push('[');
+        if let Some(sheet) = sheet {
+            buf.push('$');
+            let _ = fmt_table_name_a1(buf, sheet);
+        }
+        buf.push('.');
+        match self {
+            A1Ref::Cell(pos) => {
+                push_pos(buf, pos);
+                // 3D references are always ranges.
+                if to_sheet.is_some() {
+                    push_to(buf);
+                    push_pos(buf, pos);
+                }
+            }
+            A1Ref::Range(pos, to_pos) => {
+                push_pos(buf, pos);
+                push_to(buf);
+                push_pos(buf, to_pos);
+            }
+            A1Ref::Cols(col, to_col) => {
+                let (abs, col) = shift_col(*col);
+                push_col(buf, abs, col);
+                push_to(buf);
+                let (abs, col) = shift_col(*to_col);
+                push_col(buf, abs, col);
+            }
+            A1Ref::Rows(row, to_row) => {
+                let (abs, row) = shift_row(*row);
+                push_row(buf, abs, row);
+                push_to(buf);
+                let (abs, row) = shift_row(*to_row);
+                push_row(buf, abs, row);
+            }
+        }
+        buf.push(']');
+    }
+}
+
+/// Parses the rest of a 3D reference after the first sheet name, e.g.
+/// ":Sheet3!A1". Returns the second sheet name, the reference and the
+/// number of bytes used.
+fn a1_ref_3d(s: &str) -> Option<(&str, A1Ref, usize)> {
+    let v = s.strip_prefix(':')?;
+    let end = v.find(|c: char| !is_word_char(c)).unwrap_or(v.len());
+    if end == 0 {
+        return None;
+    }
+    let (r, len) = v[end..].strip_prefix('!').and_then(a1_ref)?;
+    Some((&v[..end], r, 1 + end + 1 + len))
+}
+
+/// Parses a reference at the start of s. Returns the reference and the
+/// number of bytes used. The reference must not be followed by any
+/// character that would continue a name.
+fn a1_ref(s: &str) -> Option<(A1Ref, usize)> {
+    let r = if let Some((pos, len)) = a1_pos(s) {
+        if let Some((to_pos, to_len)) = s[len..].strip_prefix(':').and_then(a1_pos) {
+            (A1Ref::Range(pos, to_pos), len + 1 + to_len)
+        } else {
+            (A1Ref::Cell(pos), len)
+        }
+    } else if let Some((col, len)) = a1_col(s) {
+        let (to_col, to_len) = s[len..].strip_prefix(':').and_then(a1_col)?;
+        (A1Ref::Cols(col, to_col), len + 1 + to_len)
+    } else if let Some((row, len)) = a1_row(s) {
+        let (to_row, to_len) = s[len..].strip_prefix(':').and_then(a1_row)?;
+        (A1Ref::Rows(row, to_row), len + 1 + to_len)
+    } else {
+        return None;
+    };
+
+    match s[r.1..].chars().next() {
+        Some(c) if is_word_char(c) || c == '(' || c == '!' => None,
+        _ => Some(r),
+    }
+}
+
+fn a1_pos(s: &str) -> Option<(A1Pos, usize)> {
+    let (col, len) = a1_col(s)?;
+    let (row, row_len) = a1_row(&s[len..])?;
+    Some((A1Pos { row, col }, len + row_len))
+}
+
+// Column with optional $. Returns the 0-based column.
+fn a1_col(s: &str) -> Option<((bool, u32), usize)> {
+    let (abs, s, start) = match s.strip_prefix('$') {
+        Some(s) => (true, s, 1),
+        None => (false, s, 0),
+    };
+    let len = s
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(s.len());
+    if len == 0 || len > 3 {
+        return None;
+    }
+    let mut col = 0u32;
+    for c in s[..len].bytes() {
+        col = col * 26 + (c.to_ascii_uppercase() - b'A' + 1) as u32;
+    }
+    if col > MAX_COLS {
+        return None;
+    }
+    Some(((abs, col - 1), start + len))
+}
+
+// Row with optional $. Returns the 0-based row.
+fn a1_row(s: &str) -> Option<((bool, u32), usize)> {
+    let (abs, s, start) = match s.strip_prefix('$') {
+        Some(s) => (true, s, 1),
+        None => (false, s, 0),
+    };
+    let len = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    if len == 0 {
+        return None;
+    }
+    let row: u32 = s[..len].parse().ok()?;
+    if row == 0 || row > MAX_ROWS {
+        return None;
+    }
+    Some(((abs, row - 1), start + len))
+}
+
+/// Is the 0-based cell inside the limits of a worksheet.
+fn in_sheet(row: u32, col: u32) -> bool {
+    row < MAX_ROWS && col < MAX_COLS
+}
+
+/// Parses a cell reference without sheet, e.g. "B3". Returns
+/// the 0-based row and column.
+pub(crate) fn parse_a1_cell(s: &str) -> Option<(u32, u32)> {
+    let r = parse_cellref_a1(s).ok()?;
+    (r.table().is_none() && in_sheet(r.row(), r.col())).then(|| (r.row(), r.col()))
+}
+
+/// Parses a range without sheet, e.g. "B3:C4" or "B3". Returns the
+/// 0-based start and end row and column.
+pub(crate) fn parse_a1_range(s: &str) -> Option<(u32, u32, u32, u32)> {
+    let r = parse_cellrange_a1(s).ok()?;
+    (r.table().is_none() && in_sheet(r.row(), r.col()) && in_sheet(r.to_row(), r.to_col()))
+        .then(|| (r.row(), r.col(), r.to_row(), r.to_col()))
+}
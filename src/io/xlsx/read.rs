@@ -0,0 +1,1673 @@
+//!
+//! Reads an Office Open XML spreadsheet (xlsx) into a WorkBook.
+//!
+
+use crate::color::Rgb;
+use crate::condition::ValueCondition;
+use crate::error::OdsError;
+use crate::format::{FormatPart, FormatPartType, ValueFormatRef, ValueFormatTrait, ValueStyleMap};
+use crate::io::xlsx::{open_formula, parse_a1_cell, parse_a1_range};
+use crate::refs::parse_cellrange;
+use crate::sheet::Visibility;
+use crate::style::units::{
+    Angle, Border, CellAlignVertical, CellProtect, FontSize, LineStyle, TextAlign, TextAlignSource,
+    WrapOption,
+};
+use crate::style::{color_string, CellStyle, CellStyleRef, FontFaceDecl, TextStyle, TextStyleRef};
+use crate::text::{TextP, TextSpan};
+use crate::xmltree::{XmlContent, XmlTag};
+use crate::HashMap;
+use crate::{
+    FormulaError, Length, Sheet, Value, ValueFormatCurrency, ValueFormatDateTime,
+    ValueFormatNumber, ValueFormatPercentage, ValueFormatText, ValueFormatTimeDuration, WorkBook,
+};
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use quick_xml::events::{BytesStart, Event};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Cursor, Read, Seek};
+use std::path::Path;
+use std::str::from_utf8;
+use zip::ZipArchive;
+
+type XlsxXmlReader<'a> = quick_xml::Reader<&'a mut dyn BufRead>;
+
+/// Reads an XLSX-file from a buffer.
+pub fn read_xlsx_buf(buf: &[u8]) -> Result<WorkBook, OdsError> {
+    let read = Cursor::new(buf);
+    read_xlsx_from(read)
+}
+
+/// Reads an XLSX-file from a reader.
+pub fn read_xlsx_from<T: Read + Seek>(read: T) -> Result<WorkBook, OdsError> {
+    let zip = ZipArchive::new(read)?;
+    read_xlsx_impl(zip)
+}
+
+/// Reads an XLSX-file.
+///
+/// Values, shared strings, rich text, cell styles, number formats,
+/// merged cells, column widths, row heights and formulas are converted.
+/// Formulas are translated to OpenFormula, references that can't be
+/// recognized are copied unchanged.
+pub fn read_xlsx<P: AsRef<Path>>(path: P) -> Result<WorkBook, OdsError> {
+    let read = BufReader::new(File::open(path.as_ref())?);
+    read_xlsx_from(read)
+}
+
+/// How a numeric cell value is interpreted.
+#[derive(Debug, Clone, PartialEq)]
+enum FormatKind {
+    General,
+    Number,
+    Percentage,
+    Currency(Box<str>),
+    DateTime,
+    TimeDuration,
+    Text,
+}
+
+/// Font as defined in styles.xml or in a rich text run.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct XlsxFont {
+    name: Option<String>,
+    size: Option<String>,
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    strike: bool,
+    color: Option<Rgb<u8>>,
+}
+
+/// One line of a border.
+#[derive(Debug, Clone, Copy)]
+struct XlsxLine {
+    width: Length,
+    border: Border,
+    color: Rgb<u8>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct XlsxBorder {
+    left: Option<XlsxLine>,
+    right: Option<XlsxLine>,
+    top: Option<XlsxLine>,
+    bottom: Option<XlsxLine>,
+    diagonal_up: Option<XlsxLine>,
+    diagonal_down: Option<XlsxLine>,
+}
+
+/// Cell format from cellXfs.
+#[derive(Debug, Clone, Default)]
+struct XlsxXf {
+    num_fmt: u32,
+    font: usize,
+    fill: usize,
+    border: usize,
+    horizontal: Option<String>,
+    vertical: Option<String>,
+    wrap: bool,
+    shrink: bool,
+    rotation: Option<u32>,
+    locked: bool,
+    hidden: bool,
+}
+
+struct XlsxContext {
+    book: WorkBook,
+    date1904: bool,
+
+    num_fmts: HashMap<u32, String>,
+    fonts: Vec<XlsxFont>,
+    fills: Vec<Option<Rgb<u8>>>,
+    borders: Vec<XlsxBorder>,
+    xfs: Vec<XlsxXf>,
+
+    // converted on first use
+    formats: HashMap<u32, Option<(ValueFormatRef, FormatKind)>>,
+    cellstyles: HashMap<usize, Option<(CellStyleRef, FormatKind)>>,
+    textstyles: Vec<(XlsxFont, TextStyleRef)>,
+
+    strings: Vec<Value>,
+}
+
+impl XlsxContext {
+    fn new() -> Self {
+        Self {
+            book: WorkBook::new_empty(),
+            date1904: false,
+            num_fmts: Default::default(),
+            fonts: Default::default(),
+            fills: Default::default(),
+            borders: Default::default(),
+            xfs: Default::default(),
+            formats: Default::default(),
+            cellstyles: Default::default(),
+            textstyles: Default::default(),
+            strings: Default::default(),
+        }
+    }
+}
+
+fn read_xlsx_impl<R: Read + Seek>(mut zip: ZipArchive<R>) -> Result<WorkBook, OdsError> {
+    let mut ctx = XlsxContext::new();
+
+    let workbook_path = read_rels(&mut zip, "_rels/.rels", "")?
+        .into_iter()
+        .find(|(_, rel_type, _)| rel_type.ends_with("/officeDocument"))
+        .map(|(_, _, target)| target)
+        .unwrap_or_else(|| "xl/workbook.xml".to_string());
+    let (base, file) = match workbook_path.rsplit_once('/') {
+        Some((base, file)) => (base.to_string(), file.to_string()),
+        None => (String::new(), workbook_path.clone()),
+    };
+    let rels = read_rels(&mut zip, &format!("{}/_rels/{}.rels", base, file), &base)?;
+
+    if let Some((_, _, path)) = rels.iter().find(|(_, t, _)| t.ends_with("/styles")) {
+        if let Some(styles) = read_part_tree(&mut zip, path)? {
+            read_styles(&mut ctx, &styles)?;
+        }
+    }
+    if let Some((_, _, path)) = rels.iter().find(|(_, t, _)| t.ends_with("/sharedStrings")) {
+        if let Ok(z) = zip.by_name(path) {
+            let mut read = BufReader::new(z);
+            let read: &mut dyn BufRead = &mut read;
+            let mut xml = quick_xml::Reader::from_reader(read);
+            xml.expand_empty_elements(true);
+            read_shared_strings(&mut ctx, &mut xml)?;
+        }
+    }
+
+    let Some(workbook) = read_part_tree(&mut zip, &workbook_path)? else {
+        return Err(OdsError::Xml(quick_xml::Error::UnexpectedEof(
+            workbook_path.clone(),
+        )));
+    };
+
+    for tag in children(&workbook) {
+        if tag.name() == "workbookPr" {
+            ctx.date1904 = matches!(tag.get_attr("date1904"), Some("1" | "true"));
+        }
+    }
+
+    let mut sheet_names = Vec::new();
+    for tag in children(&workbook).filter(|v| v.name() == "sheets") {
+        for sheet_tag in children(tag).filter(|v| v.name() == "sheet") {
+            let name = sheet_tag.get_attr("name").unwrap_or_default().to_string();
+            let Some((_, _, path)) = sheet_tag
+                .get_attr("id")
+                .and_then(|id| rels.iter().find(|(rid, _, _)| rid == id))
+                .filter(|(_, t, _)| t.ends_with("/worksheet"))
+            else {
+                continue;
+            };
+
+            let mut sheet = Sheet::new(name.as_str());
+            if matches!(sheet_tag.get_attr("state"), Some("hidden" | "veryHidden")) {
+                sheet.set_display(false);
+            }
+
+            let mut read = BufReader::new(zip.by_name(path)?);
+            let read: &mut dyn BufRead = &mut read;
+            let mut xml = quick_xml::Reader::from_reader(read);
+            xml.expand_empty_elements(true);
+            read_sheet(&mut ctx, &mut xml, &mut sheet)?;
+
+            sheet_names.push(name);
+            ctx.book.push_sheet(sheet);
+        }
+    }
+
+    for tag in children(&workbook).filter(|v| v.name() == "definedNames") {
+        for name_tag in children(tag).filter(|v| v.name() == "definedName") {
+            if name_tag.get_attr("name") != Some("_xlnm.Print_Area") {
+                continue;
+            }
+            let Some(idx) = name_tag
+                .get_attr("localSheetId")
+                .and_then(|v| v.parse::<usize>().ok())
+                .filter(|v| *v < ctx.book.num_sheets())
+            else {
+                continue;
+            };
+            let formula = open_formula(text_content(name_tag).as_str(), 0, 0);
+            for range in formula
+                .trim_start_matches("of:=")
+                .split(';')
+                .filter_map(|v| v.strip_prefix('[').and_then(|v| v.strip_suffix(']')))
+            {
+                if let Ok(range) = parse_cellrange(range) {
+                    ctx.book.sheet_mut(idx).add_print_range(range);
+                }
+            }
+        }
+    }
+
+    Ok(ctx.book)
+}
+
+/// Reads the relationships of a part. Targets are resolved relative
+/// to base.
+fn read_rels<R: Read + Seek>(
+    zip: &mut ZipArchive<R>,
+    path: &str,
+    base: &str,
+) -> Result<Vec<(String, String, String)>, OdsError> {
+    let mut rels = Vec::new();
+    if let Some(tree) = read_part_tree(zip, path)? {
+        for tag in children(&tree).filter(|v| v.name() == "Relationship") {
+            if tag.get_attr("TargetMode") == Some("External") {
+                continue;
+            }
+            let target = tag.get_attr("Target").unwrap_or_default();
+            let target = if let Some(target) = target.strip_prefix('/') {
+                target.to_string()
+            } else if base.is_empty() {
+                target.to_string()
+            } else {
+                format!("{}/{}", base, target)
+            };
+            rels.push((
+                tag.get_attr("Id").unwrap_or_default().to_string(),
+                tag.get_attr("Type").unwrap_or_default().to_string(),
+                normalize_path(&target),
+            ));
+        }
+    }
+    Ok(rels)
+}
+
+// Removes ".." from the path.
+fn normalize_path(path: &str) -> String {
+    let mut parts: Vec<&str> = Vec::new();
+    for p in path.split('/') {
+        match p {
+            ".." => {
+                parts.pop();
+            }
+            "." | "" => {}
+            p => parts.push(p),
+        }
+    }
+    parts.join("/")
+}
+
+/// Reads a complete part as a tree of XmlTags. Element and attribute
+/// names are stripped of their namespace prefix.
+fn read_part_tree<R: Read + Seek>(
+    zip: &mut ZipArchive<R>,
+    path: &str,
+) -> Result<Option<XmlTag>, OdsError> {
+    let Ok(z) = zip.by_name(path) else {
+        return Ok(None);
+    };
+    let mut read = BufReader::new(z);
+    let read: &mut dyn BufRead = &mut read;
+    let mut xml = quick_xml::Reader::from_reader(read);
+    xml.expand_empty_elements(true);
+
+    let mut buf = Vec::new();
+    loop {
+        let evt = xml.read_event_into(&mut buf)?;
+        match &evt {
+            Event::Start(xml_tag) => {
+                let xml_tag = xml_tag.to_owned();
+                return Ok(Some(read_tree(&mut xml, &xml_tag)?));
+            }
+            Event::Eof => {
+                return Ok(None);
+            }
+            _ => {}
+        }
+        buf.clear();
+    }
+}
+
+/// Reads the element as a tree of XmlTags.
+fn read_tree(xml: &mut XlsxXmlReader<'_>, start: &BytesStart<'_>) -> Result<XmlTag, OdsError> {
+    let mut stack = vec![tree_tag(xml, start)?];
+
+    let mut buf = Vec::new();
+    loop {
+        let evt = xml.read_event_into(&mut buf)?;
+        match &evt {
+            Event::Start(xml_tag) => {
+                stack.push(tree_tag(xml, xml_tag)?);
+            }
+            Event::End(_) => {
+                let tag = stack.pop().expect("tag");
+                if let Some(parent) = stack.last_mut() {
+                    parent.add_tag(tag);
+                } else {
+                    return Ok(tag);
+                }
+            }
+            Event::Text(xml_text) => {
+                if let Some(tag) = stack.last_mut() {
+                    tag.add_text(xml_text.unescape()?.as_ref());
+                }
+            }
+            Event::CData(xml_text) => {
+                if let Some(tag) = stack.last_mut() {
+                    tag.add_text(from_utf8(xml_text.as_ref())?);
+                }
+            }
+            Event::Eof => {
+                return Err(OdsError::Xml(quick_xml::Error::UnexpectedEof(
+                    from_utf8(start.name().as_ref())?.to_string(),
+                )));
+            }
+            _ => {}
+        }
+        buf.clear();
+    }
+}
+
+fn tree_tag(xml: &XlsxXmlReader<'_>, xml_tag: &BytesStart<'_>) -> Result<XmlTag, OdsError> {
+    let mut tag = XmlTag::new(from_utf8(xml_tag.local_name().as_ref())?);
+    for attr in xml_tag.attributes().with_checks(false) {
+        let attr = attr?;
+        let key = attr.key.local_name();
+        tag.set_attr(
+            from_utf8(key.as_ref())?,
+            attr.decode_and_unescape_value(xml)?.as_ref(),
+        );
+    }
+    Ok(tag)
+}
+
+/// Child elements.
+fn children(tag: &XmlTag) -> impl Iterator<Item = &XmlTag> {
+    tag.content().iter().filter_map(|v| match v {
+        XmlContent::Tag(t) => Some(t),
+        XmlContent::Text(_) => None,
+    })
+}
+
+fn child<'a>(tag: &'a XmlTag, name: &str) -> Option<&'a XmlTag> {
+    children(tag).find(|v| v.name() == name)
+}
+
+/// Direct text content.
+fn text_content(tag: &XmlTag) -> String {
+    let mut buf = String::new();
+    for c in tag.content() {
+        if let XmlContent::Text(t) = c {
+            buf.push_str(t);
+        }
+    }
+    buf
+}
+
+fn attr_bool(tag: &XmlTag, name: &str, default: bool) -> bool {
+    match tag.get_attr(name) {
+        Some("1" | "true") => true,
+        Some("0" | "false") => false,
+        _ => default,
+    }
+}
+
+fn attr_num<T: std::str::FromStr>(tag: &XmlTag, name: &str) -> Option<T> {
+    tag.get_attr(name).and_then(|v| v.parse().ok())
+}
+
+fn read_styles(ctx: &mut XlsxContext, styles: &XmlTag) -> Result<(), OdsError> {
+    for section in children(styles) {
+        match section.name() {
+            "numFmts" => {
+                for tag in children(section).filter(|v| v.name() == "numFmt") {
+                    if let (Some(id), Some(code)) =
+                        (attr_num::<u32>(tag, "numFmtId"), tag.get_attr("formatCode"))
+                    {
+                        ctx.num_fmts.insert(id, code.to_string());
+                    }
+                }
+            }
+            "fonts" => {
+                for tag in children(section).filter(|v| v.name() == "font") {
+                    ctx.fonts.push(read_font(tag));
+                }
+            }
+            "fills" => {
+                for tag in children(section).filter(|v| v.name() == "fill") {
+                    let color = child(tag, "patternFill")
+                        .filter(|v| v.get_attr("patternType").unwrap_or("none") != "none")
+                        .and_then(|v| child(v, "fgColor"))
+                        .and_then(read_color);
+                    ctx.fills.push(color);
+                }
+            }
+            "borders" => {
+                for tag in children(section).filter(|v| v.name() == "border") {
+                    let line = |name: &str| child(tag, name).and_then(read_line);
+                    let diagonal = line("diagonal");
+                    ctx.borders.push(XlsxBorder {
+                        left: line("left").or_else(|| line("start")),
+                        right: line("right").or_else(|| line("end")),
+                        top: line("top"),
+                        bottom: line("bottom"),
+                        diagonal_up: diagonal.filter(|_| attr_bool(tag, "diagonalUp", false)),
+                        diagonal_down: diagonal.filter(|_| attr_bool(tag, "diagonalDown", false)),
+                    });
+                }
+            }
+            "cellXfs" => {
+                for tag in children(section).filter(|v| v.name() == "xf") {
+                    let mut xf = XlsxXf {
+                        num_fmt: attr_num(tag, "numFmtId").unwrap_or(0),
+                        font: attr_num(tag, "fontId").unwrap_or(0),
+                        fill: attr_num(tag, "fillId").unwrap_or(0),
+                        border: attr_num(tag, "borderId").unwrap_or(0),
+                        locked: true,
+                        ..Default::default()
+                    };
+                    if let Some(alignment) = child(tag, "alignment") {
+                        xf.horizontal = alignment.get_attr("horizontal").map(|v| v.to_string());
+                        xf.vertical = alignment.get_attr("vertical").map(|v| v.to_string());
+                        xf.wrap = attr_bool(alignment, "wrapText", false);
+                        xf.shrink = attr_bool(alignment, "shrinkToFit", false);
+                        xf.rotation = attr_num(alignment, "textRotation");
+                    }
+                    if let Some(protection) = child(tag, "protection") {
+                        xf.locked = attr_bool(protection, "locked", true);
+                        xf.hidden = attr_bool(protection, "hidden", false);
+                    }
+                    ctx.xfs.push(xf);
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Font from a font or rPr element.
+fn read_font(tag: &XmlTag) -> XlsxFont {
+    let mut font = XlsxFont::default();
+    for prop in children(tag) {
+        match prop.name() {
+            "name" | "rFont" => font.name = prop.get_attr("val").map(|v| v.to_string()),
+            "sz" => font.size = prop.get_attr("val").map(|v| v.to_string()),
+            "b" => font.bold = attr_bool(prop, "val", true),
+            "i" => font.italic = attr_bool(prop, "val", true),
+            "strike" => font.strike = attr_bool(prop, "val", true),
+            "u" => font.underline = prop.get_attr("val") != Some("none"),
+            "color" => font.color = read_color(prop),
+            _ => {}
+        }
+    }
+    font
+}
+
+fn read_line(tag: &XmlTag) -> Option<XlsxLine> {
+    let (width, border) = match tag.get_attr("style")? {
+        "hair" => (Length::Pt(0.5), Border::Solid),
+        "thin" => (Length::Pt(0.75), Border::Solid),
+        "medium" => (Length::Pt(1.75), Border::Solid),
+        "thick" => (Length::Pt(3.0), Border::Solid),
+        "dotted" => (Length::Pt(0.75), Border::Dotted),
+        "dashed" | "dashDot" | "dashDotDot" => (Length::Pt(0.75), Border::Dashed),
+        "mediumDashed" | "mediumDashDot" | "mediumDashDotDot" | "slantDashDot" => {
+            (Length::Pt(1.75), Border::Dashed)
+        }
+        "double" => (Length::Pt(2.25), Border::Double),
+        _ => return None,
+    };
+    let color = child(tag, "color")
+        .and_then(read_color)
+        .unwrap_or(Rgb::new(0, 0, 0));
+    Some(XlsxLine {
+        width,
+        border,
+        color,
+    })
+}
+
+/// Color from the rgb or indexed attribute. Theme colors are not
+/// supported.
+fn read_color(tag: &XmlTag) -> Option<Rgb<u8>> {
+    if let Some(rgb) = tag.get_attr("rgb") {
+        let rgb = u32::from_str_radix(rgb, 16).ok()?;
+        Some(Rgb::new(
+            ((rgb >> 16) & 0xff) as u8,
+            ((rgb >> 8) & 0xff) as u8,
+            (rgb & 0xff) as u8,
+        ))
+    } else if let Some(idx) = attr_num::<usize>(tag, "indexed") {
+        let rgb = *INDEXED_COLORS.get(idx)?;
+        Some(Rgb::new(
+            ((rgb >> 16) & 0xff) as u8,
+            ((rgb >> 8) & 0xff) as u8,
+            (rgb & 0xff) as u8,
+        ))
+    } else {
+        None
+    }
+}
+
+/// The legacy color palette.
+const INDEXED_COLORS: [u32; 66] = [
+    0x000000, 0xFFFFFF, 0xFF0000, 0x00FF00, 0x0000FF, 0xFFFF00, 0xFF00FF, 0x00FFFF, //
+    0x000000, 0xFFFFFF, 0xFF0000, 0x00FF00, 0x0000FF, 0xFFFF00, 0xFF00FF, 0x00FFFF, //
+    0x800000, 0x008000, 0x000080, 0x808000, 0x800080, 0x008080, 0xC0C0C0, 0x808080, //
+    0x9999FF, 0x993366, 0xFFFFCC, 0xCCFFFF, 0x660066, 0xFF8080, 0x0066CC, 0xCCCCFF, //
+    0x000080, 0xFF00FF, 0xFFFF00, 0x00FFFF, 0x800080, 0x800000, 0x008080, 0x0000FF, //
+    0x00CCFF, 0xCCFFFF, 0xCCFFCC, 0xFFFF99, 0x99CCFF, 0xFF99CC, 0xCC99FF, 0xFFCC99, //
+    0x3366FF, 0x33CCCC, 0x99CC00, 0xFFCC00, 0xFF9900, 0xFF6600, 0x666699, 0x969696, //
+    0x003366, 0x339966, 0x003300, 0x333300, 0x993300, 0x993366, 0x333399, 0x333333, //
+    0x000000, 0xFFFFFF,
+];
+
+fn read_shared_strings(ctx: &mut XlsxContext, xml: &mut XlsxXmlReader<'_>) -> Result<(), OdsError> {
+    let mut buf = Vec::new();
+    loop {
+        let evt = xml.read_event_into(&mut buf)?;
+        match &evt {
+            Event::Start(xml_tag) if xml_tag.local_name().as_ref() == b"si" => {
+                let xml_tag = xml_tag.to_owned();
+                let si = read_tree(xml, &xml_tag)?;
+                let value = string_item(ctx, &si);
+                ctx.strings.push(value);
+            }
+            Event::Eof => {
+                break;
+            }
+            _ => {}
+        }
+        buf.clear();
+    }
+    Ok(())
+}
+
+/// Sets the font attributes of a CellStyle or TextStyle.
+macro_rules! apply_font {
+    ($style:expr, $font:expr) => {
+        if let Some(name) = &$font.name {
+            $style.set_font_name(name.as_str());
+        }
+        if let Some(size) = $font.size.as_ref().and_then(|v| v.parse::<f64>().ok()) {
+            $style.set_font_size(FontSize::Length(Length::Pt(size)));
+        }
+        if $font.bold {
+            $style.set_font_bold();
+        }
+        if $font.italic {
+            $style.set_font_italic();
+        }
+        if $font.underline {
+            $style.set_text_underline_style(LineStyle::Solid);
+        }
+        if $font.strike {
+            $style.set_text_line_through_style(LineStyle::Solid);
+        }
+        if let Some(color) = $font.color {
+            $style.set_color(color);
+        }
+    };
+}
+
+/// Value of a si or is element. Runs with formatting are converted to
+/// text:span elements.
+fn string_item(ctx: &mut XlsxContext, tag: &XmlTag) -> Value {
+    let mut runs = Vec::new();
+    for item in children(tag) {
+        match item.name() {
+            "t" => runs.push((None, text_content(item))),
+            "r" => {
+                let font = child(item, "rPr").map(read_font);
+                let text = child(item, "t").map(text_content).unwrap_or_default();
+                runs.push((font, text));
+            }
+            _ => {
+                // phonetic runs etc.
+            }
+        }
+    }
+
+    if runs.iter().all(|(font, _)| font.is_none()) {
+        let mut text = String::new();
+        for (_, t) in runs {
+            text.push_str(t.as_str());
+        }
+        return Value::Text(text);
+    }
+
+    // One paragraph per line.
+    let mut paragraphs = Vec::new();
+    let mut p = TextP::new();
+    for (font, text) in runs {
+        let style = font.map(|v| textstyle(ctx, v));
+        for (i, line) in text.split('\n').enumerate() {
+            if i > 0 {
+                paragraphs.push(p.into_xmltag());
+                p = TextP::new();
+            }
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(style) = &style {
+                p = p.tag(TextSpan::new().style_name(style).text(line));
+            } else {
+                p = p.text(line);
+            }
+        }
+    }
+    paragraphs.push(p.into_xmltag());
+
+    Value::TextXml(paragraphs)
+}
+
+fn textstyle(ctx: &mut XlsxContext, font: XlsxFont) -> TextStyleRef {
+    if let Some((_, style)) = ctx.textstyles.iter().find(|(v, _)| *v == font) {
+        return style.clone();
+    }
+
+    let mut style = TextStyle::new_empty();
+    if let Some(name) = &font.name {
+        font_decl(&mut ctx.book, name);
+    }
+    apply_font!(style, font);
+    let style = ctx.book.add_textstyle(style);
+    ctx.textstyles.push((font, style.clone()));
+    style
+}
+
+/// Registers a font-face declaration.
+fn font_decl(book: &mut WorkBook, name: &str) {
+    if book.font(name).is_none() {
+        let mut font = FontFaceDecl::new(name);
+        font.set_font_family(name);
+        book.add_font(font);
+    }
+}
+
+fn read_sheet(
+    ctx: &mut XlsxContext,
+    xml: &mut XlsxXmlReader<'_>,
+    sheet: &mut Sheet,
+) -> Result<(), OdsError> {
+    let mut shared_formulas: HashMap<String, (u32, u32, String)> = HashMap::new();
+    let mut row = 0u32;
+    let mut col = 0u32;
+    // inside rowBreaks or colBreaks
+    let mut row_breaks = None;
+
+    let mut buf = Vec::new();
+    loop {
+        let evt = xml.read_event_into(&mut buf)?;
+        match &evt {
+            Event::Start(xml_tag) if xml_tag.local_name().as_ref() == b"rowBreaks" => {
+                row_breaks = Some(true);
+            }
+            Event::Start(xml_tag) if xml_tag.local_name().as_ref() == b"colBreaks" => {
+                row_breaks = Some(false);
+            }
+            Event::End(xml_tag)
+                if xml_tag.local_name().as_ref() == b"rowBreaks"
+                    || xml_tag.local_name().as_ref() == b"colBreaks" =>
+            {
+                row_breaks = None;
+            }
+            Event::Start(xml_tag) if xml_tag.local_name().as_ref() == b"brk" => {
+                let tag = tree_tag(xml, xml_tag)?;
+                // the break is after the given 1-based row/column.
+                if let (Some(row_breaks), Some(id)) = (row_breaks, attr_num::<u32>(&tag, "id")) {
+                    if id > 0 && attr_bool(&tag, "man", false) {
+                        if row_breaks {
+                            sheet.insert_page_break_before_row(id);
+                        } else {
+                            sheet.insert_page_break_before_col(id);
+                        }
+                    }
+                }
+            }
+            Event::Start(xml_tag) if xml_tag.local_name().as_ref() == b"col" => {
+                let tag = tree_tag(xml, xml_tag)?;
+                read_col(sheet, &tag);
+            }
+            Event::Start(xml_tag) if xml_tag.local_name().as_ref() == b"row" => {
+                let tag = tree_tag(xml, xml_tag)?;
+                row = match attr_num::<u32>(&tag, "r") {
+                    Some(r) if r > 0 => r - 1,
+                    _ => row,
+                };
+                col = 0;
+                if attr_bool(&tag, "customHeight", false) {
+                    if let Some(height) = attr_num::<f64>(&tag, "ht") {
+                        sheet.set_row_height(row, Length::Pt(height));
+                    }
+                }
+                if attr_bool(&tag, "hidden", false) {
+                    sheet.set_row_visible(row, Visibility::Collapsed);
+                }
+            }
+            Event::End(xml_tag) if xml_tag.local_name().as_ref() == b"row" => {
+                row += 1;
+            }
+            Event::Start(xml_tag) if xml_tag.local_name().as_ref() == b"c" => {
+                let xml_tag = xml_tag.to_owned();
+                let cell = read_tree(xml, &xml_tag)?;
+                if let Some((r, c)) = cell.get_attr("r").and_then(parse_a1_cell) {
+                    row = r;
+                    col = c;
+                }
+                read_cell(ctx, sheet, &mut shared_formulas, row, col, &cell)?;
+                col += 1;
+            }
+            Event::Start(xml_tag) if xml_tag.local_name().as_ref() == b"mergeCell" => {
+                let tag = tree_tag(xml, xml_tag)?;
+                if let Some((row, col, to_row, to_col)) =
+                    tag.get_attr("ref").and_then(parse_a1_range)
+                {
+                    if to_row > row {
+                        sheet.set_row_span(row, col, to_row - row + 1);
+                    }
+                    if to_col > col {
+                        sheet.set_col_span(row, col, to_col - col + 1);
+                    }
+                }
+            }
+            Event::Eof => {
+                break;
+            }
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(())
+}
+
+fn read_col(sheet: &mut Sheet, tag: &XmlTag) {
+    let (Some(min), Some(max)) = (attr_num::<u32>(tag, "min"), attr_num::<u32>(tag, "max")) else {
+        return;
+    };
+    if min == 0 || max < min {
+        return;
+    }
+    let col = min - 1;
+
+    let width = if attr_bool(tag, "customWidth", false) {
+        attr_num::<f64>(tag, "width")
+    } else {
+        None
+    };
+    let hidden = attr_bool(tag, "hidden", false);
+    if width.is_none() && !hidden {
+        return;
+    }
+
+    if let Some(width) = width {
+        // digit widths -> pixels -> points
+        let pt = width * 7.0 * 72.0 / 96.0;
+        sheet.set_col_width(col, Length::Pt((pt * 100.0).round() / 100.0));
+    }
+    if hidden {
+        sheet.set_col_visible(col, Visibility::Collapsed);
+    }
+    sheet._set_col_header_span(col, max - min + 1);
+}
+
+fn read_cell(
+    ctx: &mut XlsxContext,
+    sheet: &mut Sheet,
+    shared_formulas: &mut HashMap<String, (u32, u32, String)>,
+    row: u32,
+    col: u32,
+    cell: &XmlTag,
+) -> Result<(), OdsError> {
+    let style = match attr_num::<usize>(cell, "s") {
+        Some(xf) if xf > 0 => cellstyle(ctx, xf),
+        _ => None,
+    };
+
+    let v = child(cell, "v").map(text_content);
+    let value = match (cell.get_attr("t").unwrap_or("n"), v) {
+        ("inlineStr", _) => match child(cell, "is") {
+            Some(is) => string_item(ctx, is),
+            None => Value::Empty,
+        },
+        (_, None) => Value::Empty,
+        ("s", Some(v)) => v
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .and_then(|idx| ctx.strings.get(idx))
+            .cloned()
+            .unwrap_or_default(),
+        ("str", Some(v)) => Value::Text(v),
+        ("e", Some(v)) => Value::Error(FormulaError::from(v.as_str())),
+        ("b", Some(v)) => Value::Boolean(matches!(v.trim(), "1" | "true")),
+        ("d", Some(v)) => match parse_iso_datetime(v.trim()) {
+            Some(v) => Value::DateTime(v),
+            None => Value::Text(v),
+        },
+        (_, Some(v)) => {
+            let v = v.trim().parse::<f64>()?;
+            let kind = style
+                .as_ref()
+                .map(|(_, kind)| kind.clone())
+                .unwrap_or(FormatKind::General);
+            numeric_value(v, kind, ctx.date1904)
+        }
+    };
+
+    if let Some(f) = child(cell, "f") {
+        let text = text_content(f);
+        let formula = match f.get_attr("t") {
+            Some("shared") => {
+                let si = f.get_attr("si").unwrap_or_default().to_string();
+                if !text.is_empty() {
+                    shared_formulas.insert(si, (row, col, text.clone()));
+                    Some(open_formula(&text, 0, 0))
+                } else if let Some((base_row, base_col, text)) = shared_formulas.get(&si) {
+                    Some(open_formula(
+                        text,
+                        row.saturating_sub(*base_row),
+                        col.saturating_sub(*base_col),
+                    ))
+                } else {
+                    None
+                }
+            }
+            Some("array") => {
+                // a reversed range is no valid ref.
+                if let Some((row, col, to_row, to_col)) = f.get_attr("ref").and_then(parse_a1_range)
+                {
+                    if let (Some(row_span), Some(col_span)) =
+                        (to_row.checked_sub(row), to_col.checked_sub(col))
+                    {
+                        sheet.set_matrix_row_span(row, col, row_span + 1);
+                        sheet.set_matrix_col_span(row, col, col_span + 1);
+                    }
+                }
+                Some(open_formula(&text, 0, 0))
+            }
+            _ if !text.is_empty() => Some(open_formula(&text, 0, 0)),
+            _ => None,
+        };
+        if let Some(formula) = formula {
+            sheet.set_formula(row, col, formula);
+        }
+    }
+
+    if value != Value::Empty {
+        sheet.set_value(row, col, value);
+    }
+    if let Some((style, _)) = style {
+        sheet.set_cellstyle(row, col, &style);
+    }
+
+    Ok(())
+}
+
+/// Converts a number according to the number format.
+fn numeric_value(v: f64, kind: FormatKind, date1904: bool) -> Value {
+    match kind {
+        FormatKind::General | FormatKind::Number | FormatKind::Text => Value::Number(v),
+        FormatKind::Percentage => Value::Percentage(v),
+        FormatKind::Currency(c) => Value::Currency(v, c),
+        FormatKind::DateTime => match serial_datetime(v, date1904) {
+            Some(d) => Value::DateTime(d),
+            None => Value::Number(v),
+        },
+        FormatKind::TimeDuration => {
+            Value::TimeDuration(Duration::milliseconds((v * 86_400_000.0).round() as i64))
+        }
+    }
+}
+
+/// Date serials count the days since 1899-12-30 or 1904-01-01.
+fn serial_datetime(v: f64, date1904: bool) -> Option<NaiveDateTime> {
+    let base = if date1904 {
+        NaiveDate::from_ymd_opt(1904, 1, 1)?
+    } else {
+        NaiveDate::from_ymd_opt(1899, 12, 30)?
+    };
+    if !v.is_finite() || v.abs() > 3_000_000.0 {
+        return None;
+    }
+    base.and_hms_opt(0, 0, 0)?
+        .checked_add_signed(Duration::milliseconds((v * 86_400_000.0).round() as i64))
+}
+
+fn parse_iso_datetime(v: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(v, "%Y-%m-%dT%H:%M:%S%.f")
+        .ok()
+        .or_else(|| NaiveDateTime::parse_from_str(v, "%Y-%m-%dT%H:%M:%S%.fZ").ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(v, "%Y-%m-%d")
+                .ok()
+                .and_then(|v| v.and_hms_opt(0, 0, 0))
+        })
+}
+
+/// CellStyle for the cell format. Created on first use.
+fn cellstyle(ctx: &mut XlsxContext, xf_idx: usize) -> Option<(CellStyleRef, FormatKind)> {
+    if let Some(style) = ctx.cellstyles.get(&xf_idx) {
+        return style.clone();
+    }
+
+    let style = ctx
+        .xfs
+        .get(xf_idx)
+        .cloned()
+        .map(|xf| create_cellstyle(ctx, &xf));
+    ctx.cellstyles.insert(xf_idx, style.clone());
+    style
+}
+
+fn create_cellstyle(ctx: &mut XlsxContext, xf: &XlsxXf) -> (CellStyleRef, FormatKind) {
+    let (mut style, kind) = match value_format(ctx, xf.num_fmt) {
+        Some((format, kind)) => (CellStyle::new("", &format), kind),
+        None => (CellStyle::new_empty(), FormatKind::General),
+    };
+
+    // font 0 is the default font.
+    if xf.font > 0 {
+        if let Some(font) = ctx.fonts.get(xf.font).cloned() {
+            if let Some(name) = &font.name {
+                font_decl(&mut ctx.book, name);
+            }
+            apply_font!(style, font);
+        }
+    }
+
+    if let Some(Some(color)) = ctx.fills.get(xf.fill) {
+        style.set_background_color(*color);
+    }
+
+    if let Some(border) = ctx.borders.get(xf.border) {
+        if let Some(l) = border.left {
+            style.set_border_left(l.width, l.border, l.color);
+        }
+        if let Some(l) = border.right {
+            style.set_border_right(l.width, l.border, l.color);
+        }
+        if let Some(l) = border.top {
+            style.set_border_top(l.width, l.border, l.color);
+        }
+        if let Some(l) = border.bottom {
+            style.set_border_bottom(l.width, l.border, l.color);
+        }
+        if let Some(l) = border.diagonal_up {
+            style.set_diagonal_bl_tr(l.width, l.border, l.color);
+        }
+        if let Some(l) = border.diagonal_down {
+            style.set_diagonal_tl_br(l.width, l.border, l.color);
+        }
+    }
+
+    let align = match xf.horizontal.as_deref() {
+        Some("left") => Some(TextAlign::Start),
+        Some("center" | "centerContinuous") => Some(TextAlign::Center),
+        Some("right") => Some(TextAlign::End),
+        Some("justify" | "distributed") => Some(TextAlign::Justify),
+        _ => None,
+    };
+    if let Some(align) = align {
+        style.set_text_align_source(TextAlignSource::Fix);
+        style.set_text_align(align);
+    }
+    match xf.vertical.as_deref() {
+        Some("top") => style.set_vertical_align(CellAlignVertical::Top),
+        Some("center") => style.set_vertical_align(CellAlignVertical::Middle),
+        Some("bottom") => style.set_vertical_align(CellAlignVertical::Bottom),
+        _ => {}
+    }
+    if xf.wrap {
+        style.set_wrap_option(WrapOption::Wrap);
+    }
+    if xf.shrink {
+        style.set_shrink_to_fit(true);
+    }
+    match xf.rotation {
+        Some(v @ 1..=90) => style.set_rotation_angle(Angle::Deg(v as f64)),
+        Some(v @ 91..=180) => style.set_rotation_angle(Angle::Deg((450 - v) as f64)),
+        _ => {}
+    }
+    match (xf.locked, xf.hidden) {
+        (false, false) => style.set_cell_protect(CellProtect::None),
+        (false, true) => style.set_cell_protect(CellProtect::FormulaHidden),
+        (true, true) => style.set_cell_protect(CellProtect::ProtectedFormulaHidden),
+        (true, false) => {}
+    }
+
+    (ctx.book.add_cellstyle(style), kind)
+}
+
+/// Value format for a numFmtId. Created on first use.
+fn value_format(ctx: &mut XlsxContext, num_fmt: u32) -> Option<(ValueFormatRef, FormatKind)> {
+    if let Some(format) = ctx.formats.get(&num_fmt) {
+        return format.clone();
+    }
+
+    let code = ctx
+        .num_fmts
+        .get(&num_fmt)
+        .map(|v| v.as_str())
+        .or_else(|| builtin_format(num_fmt));
+    let format = code.and_then(|code| add_format(&mut ctx.book, code));
+    ctx.formats.insert(num_fmt, format.clone());
+    format
+}
+
+/// Predefined formats.
+fn builtin_format(num_fmt: u32) -> Option<&'static str> {
+    match num_fmt {
+        1 => Some("0"),
+        2 => Some("0.00"),
+        3 => Some("#,##0"),
+        4 => Some("#,##0.00"),
+        9 => Some("0%"),
+        10 => Some("0.00%"),
+        11 => Some("0.00E+00"),
+        12 => Some("# ?/?"),
+        13 => Some("# ??/??"),
+        14 => Some("mm-dd-yy"),
+        15 => Some("d-mmm-yy"),
+        16 => Some("d-mmm"),
+        17 => Some("mmm-yy"),
+        18 => Some("h:mm AM/PM"),
+        19 => Some("h:mm:ss AM/PM"),
+        20 => Some("h:mm"),
+        21 => Some("h:mm:ss"),
+        22 => Some("m/d/yy h:mm"),
+        37 => Some("#,##0 ;(#,##0)"),
+        38 => Some("#,##0 ;[Red](#,##0)"),
+        39 => Some("#,##0.00;(#,##0.00)"),
+        40 => Some("#,##0.00;[Red](#,##0.00)"),
+        45 => Some("mm:ss"),
+        46 => Some("[h]:mm:ss"),
+        47 => Some("mmss.0"),
+        48 => Some("##0.0E+0"),
+        49 => Some("@"),
+        _ => None,
+    }
+}
+
+/// One section of a format code.
+#[derive(Debug, Default)]
+struct XlsxFormatSection {
+    condition: Option<String>,
+    color: Option<Rgb<u8>>,
+    currency: Option<String>,
+    elapsed: bool,
+    percent: bool,
+    parts: Vec<FormatPart>,
+}
+
+impl XlsxFormatSection {
+    fn kind(&self) -> FormatKind {
+        let has =
+            |types: &[FormatPartType]| self.parts.iter().any(|v| types.contains(&v.part_type()));
+        if has(&[
+            FormatPartType::Year,
+            FormatPartType::Month,
+            FormatPartType::Day,
+            FormatPartType::DayOfWeek,
+        ]) {
+            FormatKind::DateTime
+        } else if has(&[
+            FormatPartType::Hours,
+            FormatPartType::Minutes,
+            FormatPartType::Seconds,
+        ]) {
+            FormatKind::TimeDuration
+        } else if self.percent {
+            FormatKind::Percentage
+        } else if let Some(currency) = &self.currency {
+            FormatKind::Currency(currency.as_str().into())
+        } else if has(&[FormatPartType::TextContent]) {
+            FormatKind::Text
+        } else if self.parts.iter().any(|v| {
+            v.part_type() != FormatPartType::Number
+                || v.attrmap().attr("number:decimal-places").is_some()
+        }) {
+            FormatKind::Number
+        } else {
+            FormatKind::General
+        }
+    }
+}
+
+/// Creates the value format for a format code.
+fn add_format(book: &mut WorkBook, code: &str) -> Option<(ValueFormatRef, FormatKind)> {
+    let mut sections = split_sections(code)
+        .into_iter()
+        .map(parse_format_section)
+        .collect::<Vec<_>>();
+
+    // The main format and the conditional ones.
+    let mut maps = Vec::new();
+    let main;
+    if sections.iter().any(|v| v.condition.is_some()) {
+        let idx = sections
+            .iter()
+            .position(|v| v.condition.is_none())
+            .unwrap_or(sections.len() - 1);
+        main = sections.remove(idx);
+        for section in sections.into_iter().take(3) {
+            if let Some(cond) = section.condition.clone() {
+                maps.push((cond, section));
+            }
+        }
+    } else {
+        sections.truncate(3);
+        main = match sections.len() {
+            1 => sections.remove(0),
+            2 => {
+                let main = sections.remove(1);
+                maps.push((">=0".to_string(), sections.remove(0)));
+                main
+            }
+            _ => {
+                let main = sections.remove(2);
+                let negative = sections.remove(1);
+                maps.push((">0".to_string(), sections.remove(0)));
+                maps.push(("<0".to_string(), negative));
+                main
+            }
+        };
+    }
+
+    let kind = Some(main.kind())
+        .into_iter()
+        .chain(maps.iter().map(|(_, v)| v.kind()))
+        .find(|v| *v != FormatKind::General)
+        .unwrap_or(FormatKind::General);
+
+    let format = match &kind {
+        FormatKind::General if maps.is_empty() => return None,
+        FormatKind::General | FormatKind::Number => add_sections(
+            book,
+            ValueFormatNumber::new_empty,
+            WorkBook::add_number_format,
+            &main,
+            &maps,
+        ),
+        FormatKind::Percentage => add_sections(
+            book,
+            ValueFormatPercentage::new_empty,
+            WorkBook::add_percentage_format,
+            &main,
+            &maps,
+        ),
+        FormatKind::Currency(_) => add_sections(
+            book,
+            ValueFormatCurrency::new_empty,
+            WorkBook::add_currency_format,
+            &main,
+            &maps,
+        ),
+        FormatKind::DateTime => add_sections(
+            book,
+            ValueFormatDateTime::new_empty,
+            WorkBook::add_datetime_format,
+            &main,
+            &maps,
+        ),
+        FormatKind::TimeDuration => add_sections(
+            book,
+            ValueFormatTimeDuration::new_empty,
+            WorkBook::add_timeduration_format,
+            &main,
+            &maps,
+        ),
+        FormatKind::Text => add_sections(
+            book,
+            ValueFormatText::new_empty,
+            WorkBook::add_text_format,
+            &main,
+            &maps,
+        ),
+    };
+
+    Some((format, kind))
+}
+
+fn add_sections<T: ValueFormatTrait>(
+    book: &mut WorkBook,
+    new_format: fn() -> T,
+    add_format: fn(&mut WorkBook, T) -> ValueFormatRef,
+    main: &XlsxFormatSection,
+    maps: &[(String, XlsxFormatSection)],
+) -> ValueFormatRef {
+    let mut format = new_format();
+    fill_format(&mut format, main);
+    for (cond, section) in maps {
+        let mut map_format = new_format();
+        fill_format(&mut map_format, section);
+        let map_format = add_format(book, map_format);
+        format.push_stylemap(ValueStyleMap::new(
+            ValueCondition::new(format!("value(){}", cond.replace("<>", "!="))),
+            map_format.as_str(),
+        ));
+    }
+    add_format(book, format)
+}
+
+fn fill_format<T: ValueFormatTrait>(format: &mut T, section: &XlsxFormatSection) {
+    if let Some(color) = section.color {
+        format
+            .textstyle_mut()
+            .set_attr("fo:color", color_string(color));
+    }
+    if section.elapsed {
+        format
+            .attrmap_mut()
+            .set_attr("number:truncate-on-overflow", "false");
+    }
+    for part in &section.parts {
+        format.push_part(part.clone());
+    }
+}
+
+/// Splits the format code at ';'.
+fn split_sections(code: &str) -> Vec<&str> {
+    let mut sections = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    let mut bracket = false;
+    let mut escaped = false;
+    for (i, c) in code.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' if !quoted => escaped = true,
+            '"' => quoted = !quoted,
+            '[' if !quoted => bracket = true,
+            ']' if !quoted => bracket = false,
+            ';' if !quoted && !bracket => {
+                sections.push(&code[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    sections.push(&code[start..]);
+    sections
+}
+
+fn part(part_type: FormatPartType, attr: &[(&str, String)]) -> FormatPart {
+    let mut part = FormatPart::new(part_type);
+    for (k, v) in attr {
+        part.set_attr(k, v.clone());
+    }
+    part
+}
+
+fn style_attr(long: bool) -> Vec<(&'static str, String)> {
+    if long {
+        vec![("number:style", "long".to_string())]
+    } else {
+        Vec::new()
+    }
+}
+
+fn parse_format_section(code: &str) -> XlsxFormatSection {
+    let mut section = XlsxFormatSection::default();
+    let chars = code.chars().collect::<Vec<_>>();
+
+    let mut text = String::new();
+    let flush = |text: &mut String, parts: &mut Vec<FormatPart>| {
+        if !text.is_empty() {
+            let mut part = FormatPart::new(FormatPartType::Text);
+            part.set_content(std::mem::take(text));
+            parts.push(part);
+        }
+    };
+
+    // last date/time part for the ambiguous m.
+    let mut after_hours = false;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '"' => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|v| *v == '"')
+                    .map(|v| i + 1 + v)
+                    .unwrap_or(chars.len());
+                text.extend(&chars[i + 1..end]);
+                i = end + 1;
+            }
+            '\\' => {
+                if let Some(c) = chars.get(i + 1) {
+                    text.push(*c);
+                }
+                i += 2;
+            }
+            '_' => {
+                text.push(' ');
+                i += 2;
+            }
+            '*' => {
+                flush(&mut text, &mut section.parts);
+                if let Some(c) = chars.get(i + 1) {
+                    let mut part = FormatPart::new(FormatPartType::FillCharacter);
+                    part.set_content(c.to_string());
+                    section.parts.push(part);
+                }
+                i += 2;
+            }
+            '[' => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|v| *v == ']')
+                    .map(|v| i + 1 + v)
+                    .unwrap_or(chars.len());
+                let inner = chars[i + 1..end].iter().collect::<String>();
+                i = end + 1;
+
+                let lower = inner.to_ascii_lowercase();
+                if let Some(color) = named_color(&lower) {
+                    section.color = Some(color);
+                } else if let Some(currency) = inner.strip_prefix('$') {
+                    let symbol = currency.split('-').next().unwrap_or_default();
+                    if !symbol.is_empty() {
+                        flush(&mut text, &mut section.parts);
+                        push_currency(&mut section, symbol);
+                    }
+                } else if inner.starts_with(['<', '>', '=']) {
+                    section.condition = Some(inner);
+                } else if !lower.is_empty()
+                    && lower
+                        .chars()
+                        .all(|v| v == lower.chars().next().unwrap_or('x'))
+                    && matches!(lower.chars().next(), Some('h' | 'm' | 's'))
+                {
+                    flush(&mut text, &mut section.parts);
+                    section.elapsed = true;
+                    let part_type = match lower.chars().next() {
+                        Some('h') => FormatPartType::Hours,
+                        Some('m') => FormatPartType::Minutes,
+                        _ => FormatPartType::Seconds,
+                    };
+                    after_hours = part_type == FormatPartType::Hours;
+                    section
+                        .parts
+                        .push(part(part_type, &style_attr(lower.len() > 1)));
+                }
+            }
+            '0' | '#' | '?' | '.' | ',' if is_number_start(&chars, i) => {
+                flush(&mut text, &mut section.parts);
+                i = parse_number(&chars, i, &mut section.parts);
+            }
+            '%' => {
+                section.percent = true;
+                text.push('%');
+                i += 1;
+            }
+            '@' => {
+                flush(&mut text, &mut section.parts);
+                section
+                    .parts
+                    .push(FormatPart::new(FormatPartType::TextContent));
+                i += 1;
+            }
+            '$' | '€' | '£' | '¥' => {
+                flush(&mut text, &mut section.parts);
+                push_currency(&mut section, c.to_string().as_str());
+                i += 1;
+            }
+            _ if starts_with_ci(&chars[i..], "general") => {
+                flush(&mut text, &mut section.parts);
+                section.parts.push(FormatPart::new(FormatPartType::Number));
+                i += 7;
+            }
+            _ if starts_with_ci(&chars[i..], "am/pm") => {
+                flush(&mut text, &mut section.parts);
+                section.parts.push(FormatPart::new(FormatPartType::AmPm));
+                i += 5;
+            }
+            _ if starts_with_ci(&chars[i..], "a/p") => {
+                flush(&mut text, &mut section.parts);
+                section.parts.push(FormatPart::new(FormatPartType::AmPm));
+                i += 3;
+            }
+            'y' | 'Y' | 'm' | 'M' | 'd' | 'D' | 'h' | 'H' | 's' | 'S' | 'e' | 'E' => {
+                flush(&mut text, &mut section.parts);
+                let lc = c.to_ascii_lowercase();
+                let len = chars[i..]
+                    .iter()
+                    .take_while(|v| v.to_ascii_lowercase() == lc)
+                    .count();
+                i += len;
+                let long = len > 1;
+                match lc {
+                    'y' | 'e' => {
+                        section.parts.push(part(
+                            FormatPartType::Year,
+                            &style_attr(len > 2 || lc == 'e'),
+                        ));
+                    }
+                    'd' if len <= 2 => {
+                        section
+                            .parts
+                            .push(part(FormatPartType::Day, &style_attr(long)));
+                    }
+                    'd' => {
+                        section
+                            .parts
+                            .push(part(FormatPartType::DayOfWeek, &style_attr(len > 3)));
+                    }
+                    'h' => {
+                        after_hours = true;
+                        section
+                            .parts
+                            .push(part(FormatPartType::Hours, &style_attr(long)));
+                        continue;
+                    }
+                    'm' if len <= 2 && (after_hours || seconds_follow(&chars, i)) => {
+                        section
+                            .parts
+                            .push(part(FormatPartType::Minutes, &style_attr(long)));
+                    }
+                    'm' => {
+                        let mut attr = style_attr(len == 2 || len == 4);
+                        if len >= 3 {
+                            attr.push(("number:textual", "true".to_string()));
+                        }
+                        section.parts.push(part(FormatPartType::Month, &attr));
+                    }
+                    _ => {
+                        // seconds with decimals
+                        let mut attr = style_attr(long);
+                        if chars.get(i) == Some(&'.') && chars.get(i + 1) == Some(&'0') {
+                            let decimals = chars[i + 1..].iter().take_while(|v| **v == '0').count();
+                            attr.push(("number:decimal-places", decimals.to_string()));
+                            i += 1 + decimals;
+                        }
+                        section.parts.push(part(FormatPartType::Seconds, &attr));
+                    }
+                }
+                after_hours = false;
+            }
+            _ => {
+                text.push(c);
+                i += 1;
+            }
+        }
+    }
+    flush(&mut text, &mut section.parts);
+
+    section
+}
+
+fn starts_with_ci(chars: &[char], s: &str) -> bool {
+    let mut it = chars.iter();
+    s.chars()
+        .all(|c| it.next().map(|v| v.to_ascii_lowercase()) == Some(c))
+}
+
+/// Is the m followed by seconds?
+fn seconds_follow(chars: &[char], i: usize) -> bool {
+    for c in &chars[i..] {
+        match c.to_ascii_lowercase() {
+            's' => return true,
+            'y' | 'm' | 'd' | 'h' => return false,
+            _ => {}
+        }
+    }
+    false
+}
+
+fn is_number_start(chars: &[char], i: usize) -> bool {
+    match chars[i] {
+        '0' | '#' | '?' => true,
+        // .00 or ,## etc
+        '.' | ',' => matches!(chars.get(i + 1), Some('0' | '#' | '?')),
+        _ => false,
+    }
+}
+
+/// Parses a run of digit placeholders. Returns the index after the
+/// number.
+fn parse_number(chars: &[char], start: usize, parts: &mut Vec<FormatPart>) -> usize {
+    let is_digit = |c: &char| matches!(c, '0' | '#' | '?');
+    let run_end = |from: usize| {
+        from + chars[from..]
+            .iter()
+            .take_while(|c| is_digit(c) || **c == ',' || **c == '.')
+            .count()
+    };
+
+    let end = run_end(start);
+    let run = &chars[start..end];
+
+    // fraction
+    let mut i = end;
+    let fraction = if chars.get(i) == Some(&'/') {
+        Some((None, run))
+    } else {
+        let space_end = i + chars[i..].iter().take_while(|c| **c == ' ').count();
+        let num_end = space_end
+            + chars[space_end..]
+                .iter()
+                .take_while(|c| is_digit(c))
+                .count();
+        if space_end > i && num_end > space_end && chars.get(num_end) == Some(&'/') {
+            i = num_end;
+            Some((Some(run), &chars[space_end..num_end]))
+        } else {
+            None
+        }
+    };
+    if let Some((integer, numerator)) = fraction {
+        // skip the '/'
+        i += 1;
+        let mut attr = Vec::new();
+        if let Some(integer) = integer {
+            let min = integer.iter().filter(|c| **c == '0').count();
+            attr.push(("number:min-integer-digits", min.to_string()));
+        }
+        attr.push((
+            "number:min-numerator-digits",
+            numerator.iter().filter(|c| is_digit(c)).count().to_string(),
+        ));
+        let den_len = chars[i..]
+            .iter()
+            .take_while(|c| is_digit(c) || c.is_ascii_digit())
+            .count();
+        let denominator = chars[i..i + den_len].iter().collect::<String>();
+        if !denominator.is_empty() && denominator.chars().all(|c| c.is_ascii_digit()) {
+            attr.push(("number:denominator-value", denominator));
+        } else {
+            attr.push(("number:min-denominator-digits", den_len.max(1).to_string()));
+        }
+        parts.push(part(FormatPartType::Fraction, &attr));
+        return i + den_len;
+    }
+
+    let (integer, decimals) = match run.iter().position(|c| *c == '.') {
+        Some(p) => (&run[..p], &run[p + 1..]),
+        None => (run, &run[run.len()..]),
+    };
+
+    // trailing commas scale by 1000.
+    let trailing = integer.iter().rev().take_while(|c| **c == ',').count()
+        + decimals.iter().rev().take_while(|c| **c == ',').count();
+    let integer_digits =
+        &integer[..integer.len() - integer.iter().rev().take_while(|c| **c == ',').count()];
+    let grouping = integer_digits.contains(&',');
+
+    let mut attr = vec![
+        (
+            "number:decimal-places",
+            decimals.iter().filter(|c| is_digit(c)).count().to_string(),
+        ),
+        (
+            "number:min-decimal-places",
+            decimals.iter().filter(|c| **c == '0').count().to_string(),
+        ),
+        (
+            "number:min-integer-digits",
+            integer_digits
+                .iter()
+                .filter(|c| **c == '0')
+                .count()
+                .to_string(),
+        ),
+    ];
+    if grouping {
+        attr.push(("number:grouping", "true".to_string()));
+    }
+    if trailing > 0 {
+        attr.push((
+            "number:display-factor",
+            1000u64.pow(trailing as u32).to_string(),
+        ));
+    }
+
+    // scientific
+    if matches!(chars.get(end), Some('E' | 'e')) && matches!(chars.get(end + 1), Some('+' | '-')) {
+        let exp_len = chars[end + 2..].iter().take_while(|c| is_digit(c)).count();
+        if exp_len > 0 {
+            attr.push(("number:min-exponent-digits", exp_len.to_string()));
+            parts.push(part(FormatPartType::ScientificNumber, &attr));
+            return end + 2 + exp_len;
+        }
+    }
+
+    parts.push(part(FormatPartType::Number, &attr));
+    end
+}
+
+fn push_currency(section: &mut XlsxFormatSection, symbol: &str) {
+    let code = match symbol {
+        "$" => Some("USD"),
+        "€" => Some("EUR"),
+        "£" => Some("GBP"),
+        "¥" => Some("JPY"),
+        "CHF" => Some("CHF"),
+        v if v.len() == 3 && v.chars().all(|c| c.is_ascii_uppercase()) => Some(v),
+        _ => None,
+    };
+    if let Some(code) = code {
+        section.currency = Some(code.to_string());
+        let mut part = FormatPart::new(FormatPartType::CurrencySymbol);
+        part.set_content(symbol);
+        section.parts.push(part);
+    } else {
+        let mut part = FormatPart::new(FormatPartType::Text);
+        part.set_content(symbol);
+        section.parts.push(part);
+    }
+}
+
+fn named_color(name: &str) -> Option<Rgb<u8>> {
+    match name {
+        "black" => Some(Rgb::new(0, 0, 0)),
+        "red" => Some(Rgb::new(255, 0, 0)),
+        "green" => Some(Rgb::new(0, 255, 0)),
+        "blue" => Some(Rgb::new(0, 0, 255)),
+        "yellow" => Some(Rgb::new(255, 255, 0)),
+        "magenta" => Some(Rgb::new(255, 0, 255)),
+        "cyan" => Some(Rgb::new(0, 255, 255)),
+        "white" => Some(Rgb::new(255, 255, 255)),
+        _ => None,
+    }
+}
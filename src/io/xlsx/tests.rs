@@ -0,0 +1,80 @@
+use crate::io::xlsx::{a1_range, excel_formula, open_formula, parse_a1_cell, parse_a1_range};
+
+#[test]
+fn test_excel_formula() {
+    assert_eq!(excel_formula("of:=SUM([.A1:.B3])"), "SUM(A1:B3)");
+    assert_eq!(excel_formula("of:=[.$A$1]+[.B$2]"), "$A$1+B$2");
+    assert_eq!(
+        excel_formula("of:=IF([.A1]>0;\"a;b\";\"\"\"\")"),
+        "IF(A1>0,\"a;b\",\"\"\"\")"
+    );
+    assert_eq!(excel_formula("of:=[$Sheet2.C4]*2"), "Sheet2!C4*2");
+    assert_eq!(
+        excel_formula("of:=[$'My Sheet'.A1:.A5]"),
+        "'My Sheet'!A1:A5"
+    );
+    assert_eq!(excel_formula("of:=[$'It''s'.A1]"), "'It''s'!A1");
+    assert_eq!(excel_formula("of:=[$R1C1.A1]"), "'R1C1'!A1");
+    assert_eq!(excel_formula("of:=[$AB12.A1:.B2]"), "'AB12'!A1:B2");
+    assert_eq!(
+        excel_formula("of:=COM.MICROSOFT.IFS([.A1]>1;1)"),
+        "_xlfn.IFS(A1>1,1)"
+    );
+    assert_eq!(excel_formula("=1+2"), "1+2");
+    assert_eq!(excel_formula("of:=SUM({1;2|3;4})"), "SUM({1,2;3,4})");
+}
+
+#[test]
+fn test_a1_range() {
+    assert_eq!(a1_range(0, 0, 0, 0), "A1");
+    assert_eq!(a1_range(1, 2, 3, 27), "C2:AB4");
+}
+
+#[test]
+fn test_open_formula() {
+    assert_eq!(open_formula("SUM(A1:B3)", 0, 0), "of:=SUM([.A1:.B3])");
+    assert_eq!(open_formula("=$A$1+B$2", 0, 0), "of:=[.$A$1]+[.B$2]");
+    assert_eq!(
+        open_formula("IF(A1>0,\"a,b\",\"\")", 0, 0),
+        "of:=IF([.A1]>0;\"a,b\";\"\")"
+    );
+    assert_eq!(open_formula("Sheet2!C4*2", 0, 0), "of:=[$Sheet2.C4]*2");
+    assert_eq!(
+        open_formula("'My Sheet'!A1:A5", 0, 0),
+        "of:=[$'My Sheet'.A1:.A5]"
+    );
+    assert_eq!(
+        open_formula("_xlfn.IFS(A1>1,1)", 0, 0),
+        "of:=COM.MICROSOFT.IFS([.A1]>1;1)"
+    );
+    assert_eq!(open_formula("SUM({1,2;3,4})", 0, 0), "of:=SUM({1;2|3;4})");
+    assert_eq!(open_formula("LOG10(A1)", 0, 0), "of:=LOG10([.A1])");
+    assert_eq!(
+        open_formula("SUM(Sheet1:Sheet3!A1)", 0, 0),
+        "of:=SUM([$Sheet1.A1:$Sheet3.A1])"
+    );
+    assert_eq!(
+        open_formula("SUM('My Sheet:Other'!A1:B2)", 0, 0),
+        "of:=SUM([$'My Sheet'.A1:$Other.B2])"
+    );
+    // shared formula
+    assert_eq!(open_formula("A1+$B1+C$1", 2, 1), "of:=[.B3]+[.$B3]+[.D$1]");
+}
+
+#[test]
+fn test_parse_a1_range() {
+    assert_eq!(parse_a1_range("C2:AB4"), Some((1, 2, 3, 27)));
+    assert_eq!(parse_a1_range("A1"), Some((0, 0, 0, 0)));
+    assert_eq!(parse_a1_range("A"), None);
+    assert_eq!(parse_a1_range("$B$2:C3"), Some((1, 1, 2, 2)));
+    assert_eq!(
+        parse_a1_range("A1:XFD1048576"),
+        Some((0, 0, 1048575, 16383))
+    );
+    assert_eq!(parse_a1_range("XFE1"), None);
+    assert_eq!(parse_a1_range("A1:A1048577"), None);
+    assert_eq!(parse_a1_range("Sheet1!A1"), None);
+    assert_eq!(parse_a1_cell("B3"), Some((2, 1)));
+    assert_eq!(parse_a1_cell("B3:C4"), None);
+    assert_eq!(parse_a1_cell("ZZZZZZZZ1"), None);
+}
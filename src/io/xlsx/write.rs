@@ -0,0 +1,2302 @@
+//!
+//! Writes a WorkBook as Office Open XML spreadsheet (xlsx).
+//!
+
+use crate::cell_::CellData;
+use crate::color::Rgb;
+use crate::condition::value_as_number;
+use crate::draw::{Annotation, DrawFrame, DrawFrameContent};
+use crate::format::{FormatPart, FormatPartType, ValueFormatTrait};
+use crate::io::xlsx::{
+    a1_cell, a1_range, excel_formula, MAX_COLS, MAX_ROWS, NS_CONTENT_TYPES, NS_DRAWING,
+    NS_DRAWING_MAIN, NS_MAIN, NS_PKG_REL, NS_REL, REL_COMMENTS, REL_DRAWING, REL_IMAGE,
+    REL_OFFICE_DOCUMENT, REL_SHARED_STRINGS, REL_STYLES, REL_VML_DRAWING, REL_WORKSHEET,
+};
+use crate::io::xmlwriter::XmlWriter;
+use crate::sheet::Visibility;
+use crate::style::units::{
+    Angle, Border, CellAlignVertical, CellProtect, FontSize, FontStyle, FontWeight, LineStyle,
+    TextAlign, TextAlignSource, WrapOption,
+};
+use crate::style::{CellStyle, CellStyleRef, ParseStyleAttr};
+use crate::text::TextTag;
+use crate::validation::{Validation, ValidationDisplay};
+use crate::HashMap;
+use crate::{Length, OdsError, Sheet, Value, ValueType, WorkBook};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::fs::File;
+use std::hash::Hash;
+use std::io::{BufWriter, Cursor, Seek, Write};
+use std::path::Path;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+type XlsxXmlWriter<'a> = XmlWriter<&'a mut dyn Write>;
+
+/// Custom number formats start with this id.
+const FIRST_NUM_FMT: u32 = 164;
+/// Font used if the default cell-style doesn't define one.
+const DEFAULT_FONT: &str = "Calibri";
+/// Font size used if the default cell-style doesn't define one.
+const DEFAULT_FONT_SIZE: &str = "11";
+/// Width of a digit of the default font in pixels. Column widths are
+/// measured in multiples of this.
+const DIGIT_WIDTH_PX: f64 = 7.0;
+/// English Metric Units per point.
+const EMU_PER_PT: f64 = 12700.0;
+/// Row headers without cell data are only written if they span less
+/// rows. Avoids writing a million rows for a trailing row-header.
+const MAX_HEADER_SPAN: u32 = 1024;
+
+/// Writes the XLSX file into a supplied buffer.
+pub fn write_xlsx_buf(book: &WorkBook, buf: Vec<u8>) -> Result<Vec<u8>, OdsError> {
+    let mut cursor = Cursor::new(buf);
+
+    write_xlsx_impl(ZipWriter::new(&mut cursor), book)?;
+
+    Ok(cursor.into_inner())
+}
+
+/// Writes the XLSX file to the given Write.
+pub fn write_xlsx_to<T: Write + Seek>(book: &WorkBook, mut write: T) -> Result<(), OdsError> {
+    write_xlsx_impl(ZipWriter::new(&mut write), book)?;
+
+    Ok(())
+}
+
+/// Writes the XLSX file.
+///
+/// The conversion is lossy. Sheets, values, formulas, merged cells,
+/// column widths and row heights, cell styles, number formats,
+/// validations, annotations and images are converted. Everything else
+/// is dropped.
+pub fn write_xlsx<P: AsRef<Path>>(book: &WorkBook, xlsx_path: P) -> Result<(), OdsError> {
+    let mut write = BufWriter::new(File::create(xlsx_path)?);
+
+    write_xlsx_impl(ZipWriter::new(&mut write), book)?;
+
+    write.flush()?;
+
+    Ok(())
+}
+
+/// Deduplicated list of entries.
+#[derive(Debug)]
+struct Registry<T> {
+    items: Vec<T>,
+    index: HashMap<T, usize>,
+}
+
+impl<T: Clone + Eq + Hash> Registry<T> {
+    fn new() -> Self {
+        Self {
+            items: Default::default(),
+            index: Default::default(),
+        }
+    }
+
+    fn add(&mut self, item: T) -> usize {
+        if let Some(idx) = self.index.get(&item) {
+            *idx
+        } else {
+            let idx = self.items.len();
+            self.index.insert(item.clone(), idx);
+            self.items.push(item);
+            idx
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct XlsxFont {
+    name: String,
+    size: String,
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    strike: bool,
+    color: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct XlsxLine {
+    style: &'static str,
+    color: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+struct XlsxBorder {
+    left: Option<XlsxLine>,
+    right: Option<XlsxLine>,
+    top: Option<XlsxLine>,
+    bottom: Option<XlsxLine>,
+    diagonal: Option<XlsxLine>,
+    diagonal_up: bool,
+    diagonal_down: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct XlsxXf {
+    num_fmt: u32,
+    font: usize,
+    fill: usize,
+    border: usize,
+    horizontal: Option<&'static str>,
+    vertical: Option<&'static str>,
+    wrap: bool,
+    shrink: bool,
+    rotation: Option<u32>,
+    locked: bool,
+    hidden: bool,
+}
+
+type XfKey = (
+    Option<CellStyleRef>,
+    Option<CellStyleRef>,
+    Option<CellStyleRef>,
+    ValueType,
+);
+
+/// Everything that is collected while writing the sheets and
+/// written afterwards.
+struct XlsxContext<'a> {
+    book: &'a WorkBook,
+    strings: Registry<String>,
+    string_refs: usize,
+    num_fmts: Registry<String>,
+    fonts: Registry<XlsxFont>,
+    // Solid fill colors. The first two fills are fixed.
+    fills: Registry<String>,
+    borders: Registry<XlsxBorder>,
+    xfs: Registry<XlsxXf>,
+    xf_cache: HashMap<XfKey, usize>,
+    style_xf_cache: HashMap<CellStyleRef, usize>,
+    media_count: usize,
+    image_ext: BTreeSet<&'static str>,
+    has_vml: bool,
+}
+
+struct XlsxComment<'a> {
+    row: u32,
+    col: u32,
+    annotation: &'a Annotation,
+}
+
+struct XlsxImage {
+    row: u32,
+    col: u32,
+    x: i64,
+    y: i64,
+    cx: i64,
+    cy: i64,
+    name: String,
+    descr: String,
+    media: String,
+    data: Vec<u8>,
+}
+
+/// Additional parts of a sheet.
+struct XlsxSheetParts<'a> {
+    comments: Vec<XlsxComment<'a>>,
+    images: Vec<XlsxImage>,
+    comments_rel: Option<String>,
+    vml_rel: Option<String>,
+    drawing_rel: Option<String>,
+}
+
+fn write_xlsx_impl<W: Write + Seek>(
+    mut zip_writer: ZipWriter<W>,
+    book: &WorkBook,
+) -> Result<(), OdsError> {
+    let opts = FileOptions::<()>::default().compression_method(CompressionMethod::Deflated);
+
+    let mut ctx = XlsxContext::new(book);
+
+    let mut sheet_parts = Vec::new();
+    for (idx, sheet) in book.iter_sheets().enumerate() {
+        let mut parts = collect_sheet_parts(&mut ctx, sheet)?;
+
+        let mut rels = Vec::new();
+        if !parts.images.is_empty() {
+            for image in &parts.images {
+                zip_writer.start_file(format!("xl/media/{}", image.media), opts)?;
+                zip_writer.write_all(&image.data)?;
+            }
+
+            zip_writer.start_file(format!("xl/drawings/drawing{}.xml", idx + 1), opts)?;
+            write_drawing(&parts.images, &mut XmlWriter::new(&mut zip_writer))?;
+
+            zip_writer.start_file(
+                format!("xl/drawings/_rels/drawing{}.xml.rels", idx + 1),
+                opts,
+            )?;
+            let image_rels = parts
+                .images
+                .iter()
+                .enumerate()
+                .map(|(i, v)| {
+                    (
+                        format!("rId{}", i + 1),
+                        REL_IMAGE,
+                        format!("../media/{}", v.media),
+                    )
+                })
+                .collect::<Vec<_>>();
+            write_rels(&image_rels, &mut XmlWriter::new(&mut zip_writer))?;
+
+            let rid = format!("rId{}", rels.len() + 1);
+            rels.push((
+                rid.clone(),
+                REL_DRAWING,
+                format!("../drawings/drawing{}.xml", idx + 1),
+            ));
+            parts.drawing_rel = Some(rid);
+        }
+        if !parts.comments.is_empty() {
+            ctx.has_vml = true;
+
+            zip_writer.start_file(format!("xl/comments{}.xml", idx + 1), opts)?;
+            write_comments(&parts.comments, &mut XmlWriter::new(&mut zip_writer))?;
+
+            zip_writer.start_file(format!("xl/drawings/vmlDrawing{}.vml", idx + 1), opts)?;
+            write_vml(idx, &parts.comments, &mut XmlWriter::new(&mut zip_writer))?;
+
+            let rid = format!("rId{}", rels.len() + 1);
+            rels.push((
+                rid.clone(),
+                REL_COMMENTS,
+                format!("../comments{}.xml", idx + 1),
+            ));
+            parts.comments_rel = Some(rid);
+
+            let rid = format!("rId{}", rels.len() + 1);
+            rels.push((
+                rid.clone(),
+                REL_VML_DRAWING,
+                format!("../drawings/vmlDrawing{}.vml", idx + 1),
+            ));
+            parts.vml_rel = Some(rid);
+        }
+        if !rels.is_empty() {
+            zip_writer.start_file(
+                format!("xl/worksheets/_rels/sheet{}.xml.rels", idx + 1),
+                opts,
+            )?;
+            write_rels(&rels, &mut XmlWriter::new(&mut zip_writer))?;
+        }
+
+        zip_writer.start_file(format!("xl/worksheets/sheet{}.xml", idx + 1), opts)?;
+        write_sheet(
+            &mut ctx,
+            idx,
+            sheet,
+            &parts,
+            &mut XmlWriter::new(&mut zip_writer),
+        )?;
+
+        sheet_parts.push((parts.comments.is_empty(), parts.images.is_empty()));
+    }
+
+    zip_writer.start_file("xl/sharedStrings.xml", opts)?;
+    write_shared_strings(&ctx, &mut XmlWriter::new(&mut zip_writer))?;
+
+    zip_writer.start_file("xl/styles.xml", opts)?;
+    write_styles(&ctx, &mut XmlWriter::new(&mut zip_writer))?;
+
+    zip_writer.start_file("xl/workbook.xml", opts)?;
+    write_workbook(book, &mut XmlWriter::new(&mut zip_writer))?;
+
+    zip_writer.start_file("xl/_rels/workbook.xml.rels", opts)?;
+    let mut rels = Vec::new();
+    for idx in 0..book.num_sheets() {
+        rels.push((
+            format!("rId{}", idx + 1),
+            REL_WORKSHEET,
+            format!("worksheets/sheet{}.xml", idx + 1),
+        ));
+    }
+    rels.push((
+        format!("rId{}", book.num_sheets() + 1),
+        REL_STYLES,
+        "styles.xml".to_string(),
+    ));
+    rels.push((
+        format!("rId{}", book.num_sheets() + 2),
+        REL_SHARED_STRINGS,
+        "sharedStrings.xml".to_string(),
+    ));
+    write_rels(&rels, &mut XmlWriter::new(&mut zip_writer))?;
+
+    zip_writer.start_file("_rels/.rels", opts)?;
+    write_rels(
+        &[(
+            "rId1".to_string(),
+            REL_OFFICE_DOCUMENT,
+            "xl/workbook.xml".to_string(),
+        )],
+        &mut XmlWriter::new(&mut zip_writer),
+    )?;
+
+    zip_writer.start_file("[Content_Types].xml", opts)?;
+    write_content_types(&ctx, &sheet_parts, &mut XmlWriter::new(&mut zip_writer))?;
+
+    zip_writer.finish()?;
+
+    Ok(())
+}
+
+impl<'a> XlsxContext<'a> {
+    fn new(book: &'a WorkBook) -> Self {
+        let mut ctx = Self {
+            book,
+            strings: Registry::new(),
+            string_refs: 0,
+            num_fmts: Registry::new(),
+            fonts: Registry::new(),
+            fills: Registry::new(),
+            borders: Registry::new(),
+            xfs: Registry::new(),
+            xf_cache: Default::default(),
+            style_xf_cache: Default::default(),
+            media_count: 0,
+            image_ext: Default::default(),
+            has_vml: false,
+        };
+
+        // The default cell-style defines xf 0 and font 0.
+        let default_style = book
+            .cellstyles
+            .values()
+            .find(|v| v.styleuse() == crate::style::StyleUse::Default)
+            .cloned()
+            .unwrap_or_else(CellStyle::new_empty);
+        ctx.borders.add(XlsxBorder::default());
+        let xf = ctx.xf_record(&default_style, ValueType::Empty);
+        ctx.xfs.add(xf);
+
+        ctx
+    }
+
+    fn shared_string(&mut self, text: String) -> usize {
+        self.string_refs += 1;
+        self.strings.add(text)
+    }
+
+    /// Index of the cell format for the given cell.
+    fn xf_index(
+        &mut self,
+        sheet_idx: usize,
+        sheet: &Sheet,
+        row: u32,
+        col: u32,
+        cell: &CellData,
+    ) -> usize {
+        let key = (
+            cell.style.clone(),
+            sheet.row_cellstyle(row).cloned(),
+            sheet.col_cellstyle(col).cloned(),
+            cell.value.value_type(),
+        );
+        // conditional styles depend on the value.
+        let cacheable = cell
+            .style
+            .as_ref()
+            .and_then(|v| self.book.cellstyle(v))
+            .and_then(|v| v.stylemaps())
+            .map(|v| v.is_empty())
+            .unwrap_or(true);
+
+        if cacheable {
+            if let Some(idx) = self.xf_cache.get(&key) {
+                return *idx;
+            }
+        }
+
+        let style = self.book.effective_cell_style(sheet_idx, row, col);
+        let xf = self.xf_record(&style, cell.value.value_type());
+        let idx = self.xfs.add(xf);
+
+        if cacheable {
+            self.xf_cache.insert(key, idx);
+        }
+        idx
+    }
+
+    /// Index of the cell format for a default cell-style of a row or
+    /// column.
+    fn style_xf_index(&mut self, style: &CellStyleRef) -> usize {
+        if let Some(idx) = self.style_xf_cache.get(style) {
+            return *idx;
+        }
+        let resolved = self.book.resolved_cellstyle(style);
+        let xf = self.xf_record(&resolved, ValueType::Empty);
+        let idx = self.xfs.add(xf);
+        self.style_xf_cache.insert(style.clone(), idx);
+        idx
+    }
+
+    fn xf_record(&mut self, style: &CellStyle, value_type: ValueType) -> XlsxXf {
+        let num_fmt = match style.value_format().and_then(|v| format_code(self.book, v)) {
+            Some(code) if code == "General" => 0,
+            Some(code) => self.num_fmts.add(code) as u32 + FIRST_NUM_FMT,
+            None => match value_type {
+                ValueType::Percentage => 10,
+                ValueType::DateTime => 22,
+                ValueType::TimeDuration => 46,
+                _ => 0,
+            },
+        };
+
+        let font = XlsxFont {
+            name: style
+                .font_name()
+                .map(|v| self.font_family(v))
+                .unwrap_or_else(|| DEFAULT_FONT.to_string()),
+            size: match style.font_size() {
+                Ok(Some(FontSize::Length(v))) => length_pt(v).map(fmt_pt),
+                _ => None,
+            }
+            .unwrap_or_else(|| DEFAULT_FONT_SIZE.to_string()),
+            bold: matches!(
+                style.font_weight(),
+                Ok(Some(
+                    FontWeight::Bold
+                        | FontWeight::W600
+                        | FontWeight::W700
+                        | FontWeight::W800
+                        | FontWeight::W900
+                ))
+            ),
+            italic: matches!(
+                style.font_style(),
+                Ok(Some(FontStyle::Italic | FontStyle::Oblique))
+            ),
+            underline: matches!(style.text_underline_style(), Ok(Some(v)) if v != LineStyle::None),
+            strike: matches!(style.text_line_through_style(), Ok(Some(v)) if v != LineStyle::None),
+            color: style.color().ok().flatten().map(argb),
+        };
+        let font = self.fonts.add(font);
+
+        let fill = match style.background_color() {
+            Ok(Some(color)) => self.fills.add(argb(color)) + 2,
+            _ => 0,
+        };
+
+        let border = XlsxBorder {
+            left: border_line(style.border_left().ok().flatten())
+                .or_else(|| border_line(style.border().ok().flatten())),
+            right: border_line(style.border_right().ok().flatten())
+                .or_else(|| border_line(style.border().ok().flatten())),
+            top: border_line(style.border_top().ok().flatten())
+                .or_else(|| border_line(style.border().ok().flatten())),
+            bottom: border_line(style.border_bottom().ok().flatten())
+                .or_else(|| border_line(style.border().ok().flatten())),
+            diagonal: border_line(style.diagonal_bl_tr().ok().flatten())
+                .or_else(|| border_line(style.diagonal_tl_br().ok().flatten())),
+            diagonal_up: border_line(style.diagonal_bl_tr().ok().flatten()).is_some(),
+            diagonal_down: border_line(style.diagonal_tl_br().ok().flatten()).is_some(),
+        };
+        let border = self.borders.add(border);
+
+        let horizontal = if matches!(
+            style.text_align_source(),
+            Ok(Some(TextAlignSource::ValueType))
+        ) {
+            None
+        } else {
+            match style.text_align() {
+                Ok(Some(TextAlign::Start | TextAlign::Left)) => Some("left"),
+                Ok(Some(TextAlign::End | TextAlign::Right)) => Some("right"),
+                Ok(Some(TextAlign::Center)) => Some("center"),
+                Ok(Some(TextAlign::Justify)) => Some("justify"),
+                _ => None,
+            }
+        };
+        let vertical = match style.vertical_align() {
+            Ok(Some(CellAlignVertical::Top)) => Some("top"),
+            Ok(Some(CellAlignVertical::Middle)) => Some("center"),
+            Ok(Some(CellAlignVertical::Bottom)) => Some("bottom"),
+            _ => None,
+        };
+        let rotation = match style.rotation_angle() {
+            Ok(Some(angle)) => {
+                let deg = match angle {
+                    Angle::Deg(v) => v,
+                    Angle::Grad(v) => v * 0.9,
+                    Angle::Rad(v) => v.to_degrees(),
+                }
+                .rem_euclid(360.0)
+                .round() as u32;
+                match deg {
+                    1..=90 => Some(deg),
+                    270..=359 => Some(90 + 360 - deg),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        let (locked, hidden) = match style.cell_protect() {
+            Ok(Some(CellProtect::None)) => (false, false),
+            Ok(Some(CellProtect::FormulaHidden)) => (false, true),
+            Ok(Some(CellProtect::HiddenAndProtected | CellProtect::ProtectedFormulaHidden)) => {
+                (true, true)
+            }
+            _ => (true, false),
+        };
+
+        XlsxXf {
+            num_fmt,
+            font,
+            fill,
+            border,
+            horizontal,
+            vertical,
+            wrap: matches!(style.wrap_option(), Ok(Some(WrapOption::Wrap))),
+            shrink: matches!(style.shrink_to_fit(), Ok(Some(true))),
+            rotation,
+            locked,
+            hidden,
+        }
+    }
+
+    /// Font family of a font-face declaration.
+    fn font_family(&self, name: &str) -> String {
+        self.book
+            .font(name)
+            .and_then(|v| v.attrmap().attr("svg:font-family"))
+            .map(|v| v.trim_matches(|c| c == '\'' || c == '"').to_string())
+            .unwrap_or_else(|| name.to_string())
+    }
+}
+
+fn collect_sheet_parts<'s>(
+    ctx: &mut XlsxContext<'_>,
+    sheet: &'s Sheet,
+) -> Result<XlsxSheetParts<'s>, OdsError> {
+    let mut comments = Vec::new();
+    let mut images = Vec::new();
+
+    for ((row, col), cell) in sheet.data.iter() {
+        let Some(extra) = &cell.extra else {
+            continue;
+        };
+        if let Some(annotation) = &extra.annotation {
+            comments.push(XlsxComment {
+                row: *row,
+                col: *col,
+                annotation: annotation.as_ref(),
+            });
+        }
+        for frame in &extra.draw_frames {
+            for content in frame.content_ref() {
+                match content {
+                    DrawFrameContent::Image(image) => {
+                        let data = if image.get_binary_base64().is_some() {
+                            image.get_binary()?
+                        } else if let Some(href) = image.attrmap().attr("xlink:href") {
+                            ctx.book
+                                .manifest(href)
+                                .and_then(|v| v.buffer.clone())
+                                .unwrap_or_default()
+                        } else {
+                            Vec::new()
+                        };
+                        if data.is_empty() {
+                            continue;
+                        }
+
+                        let ext = image_ext(&data);
+                        ctx.image_ext.insert(ext);
+                        ctx.media_count += 1;
+
+                        images.push(XlsxImage {
+                            row: *row,
+                            col: *col,
+                            x: frame_emu(frame, "svg:x").unwrap_or(0),
+                            y: frame_emu(frame, "svg:y").unwrap_or(0),
+                            cx: frame_emu(frame, "svg:width").unwrap_or(72 * EMU_PER_PT as i64),
+                            cy: frame_emu(frame, "svg:height").unwrap_or(72 * EMU_PER_PT as i64),
+                            name: frame
+                                .attrmap()
+                                .attr("draw:name")
+                                .map(|v| v.to_string())
+                                .unwrap_or_else(|| format!("Image {}", ctx.media_count)),
+                            descr: frame.desc().or(frame.title()).cloned().unwrap_or_default(),
+                            media: format!("image{}.{}", ctx.media_count, ext),
+                            data,
+                        });
+                    }
+                    DrawFrameContent::TextBox(_) => {}
+                }
+            }
+        }
+    }
+
+    Ok(XlsxSheetParts {
+        comments,
+        images,
+        comments_rel: None,
+        vml_rel: None,
+        drawing_rel: None,
+    })
+}
+
+fn write_sheet(
+    ctx: &mut XlsxContext<'_>,
+    sheet_idx: usize,
+    sheet: &Sheet,
+    parts: &XlsxSheetParts<'_>,
+    xml_out: &mut XlsxXmlWriter<'_>,
+) -> Result<(), OdsError> {
+    // Formats of the default cell-styles of the columns.
+    let col_xfs = sheet
+        .col_header
+        .iter()
+        .filter_map(|(col, header)| {
+            header.cellstyle.as_ref().map(|style| {
+                (
+                    *col,
+                    col.saturating_add(header.span.max(1)),
+                    ctx.style_xf_index(style),
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+
+    // The used area is defined by the cells with some content. Blank
+    // cells only carry a format.
+    let mut used_rows = 0;
+    let mut used_cols = 0;
+    for ((row, col), cell) in sheet.data.iter() {
+        if !is_blank(cell) {
+            used_rows = used_rows.max(
+                row.saturating_add(sheet.row_repeat(*row).max(1))
+                    .min(MAX_ROWS),
+            );
+            used_cols = used_cols.max(col.saturating_add(cell.repeat.max(1)).min(MAX_COLS));
+        }
+    }
+
+    // (col, base row, base col, cell) for each row. Repeated cells are
+    // expanded, except blank cells that have the format of the row or
+    // column anyway.
+    let mut rows: BTreeMap<u32, Vec<(u32, u32, u32, &CellData)>> = BTreeMap::new();
+    let mut merged = Vec::new();
+    let mut validations: Vec<(&str, Vec<(u32, u32)>)> = Vec::new();
+    for ((row, col), cell) in sheet.data.iter() {
+        let row_span = sheet.row_repeat(*row).max(1);
+        let col_span = cell.repeat.max(1);
+        let mut row_end = row.saturating_add(row_span).min(MAX_ROWS);
+        let mut col_end = col.saturating_add(col_span).min(MAX_COLS);
+
+        if is_blank(cell) {
+            // Long runs of blank cells usually fill up the rest of the
+            // sheet. Like the row headers they end with the used area.
+            if row_span > MAX_HEADER_SPAN {
+                row_end = row_end.min(used_rows.max(row + 1));
+            }
+            if col_span > MAX_HEADER_SPAN {
+                col_end = col_end.min(used_cols.max(col + 1));
+            }
+
+            let xf = ctx.xf_index(sheet_idx, sheet, *row, *col, cell);
+            let spans = match sheet.row_cellstyle(*row) {
+                Some(style) => {
+                    if ctx.style_xf_index(style) == xf {
+                        Vec::new()
+                    } else {
+                        vec![(*col, col_end)]
+                    }
+                }
+                None => blank_spans(&col_xfs, *col, col_end, xf),
+            };
+            for r in *row..row_end {
+                for (c, c_end) in spans.iter() {
+                    for c in *c..*c_end {
+                        rows.entry(r).or_default().push((c, *row, *col, cell));
+                    }
+                }
+            }
+            continue;
+        }
+
+        for r in *row..row_end {
+            for c in *col..col_end {
+                rows.entry(r).or_default().push((c, *row, *col, cell));
+            }
+        }
+
+        if let Some(extra) = &cell.extra {
+            if extra.span.row_span > 1 || extra.span.col_span > 1 {
+                merged.push(a1_range(
+                    *row,
+                    *col,
+                    row + extra.span.row_span.max(1) - 1,
+                    col + extra.span.col_span.max(1) - 1,
+                ));
+            }
+            if let Some(name) = &extra.validation_name {
+                let cells =
+                    if let Some(idx) = validations.iter().position(|(v, _)| *v == name.as_str()) {
+                        &mut validations[idx].1
+                    } else {
+                        validations.push((name.as_str(), Vec::new()));
+                        &mut validations.last_mut().expect("validation").1
+                    };
+                for r in *row..row_end {
+                    for c in *col..col_end {
+                        cells.push((r, c));
+                    }
+                }
+            }
+        }
+    }
+    let used_rows = rows.keys().next_back().map(|v| v + 1).unwrap_or(0);
+    let used_cols = rows
+        .values()
+        .flat_map(|v| v.iter().map(|(c, _, _, _)| *c + 1))
+        .max()
+        .unwrap_or(0);
+    for (row, header) in sheet.row_header.iter() {
+        if header.visible == Visibility::Visible
+            && header.cellstyle.is_none()
+            && row_height(ctx.book, sheet, *row).is_none()
+        {
+            continue;
+        }
+        let row_end = if header.span <= MAX_HEADER_SPAN {
+            row.saturating_add(header.span)
+        } else {
+            row.saturating_add(header.span).min(used_rows)
+        }
+        .min(MAX_ROWS);
+        for r in *row..row_end {
+            rows.entry(r).or_default();
+        }
+    }
+
+    xml_out.dtd("UTF-8")?;
+    xml_out.elem("worksheet")?;
+    xml_out.attr_str("xmlns", NS_MAIN)?;
+    xml_out.attr_str("xmlns:r", NS_REL)?;
+
+    xml_out.empty("dimension")?;
+    if used_rows > 0 && used_cols > 0 {
+        xml_out.attr("ref", &a1_range(0, 0, used_rows - 1, used_cols - 1))?;
+    } else {
+        xml_out.attr_str("ref", "A1")?;
+    }
+
+    xml_out.elem("sheetViews")?;
+    xml_out.empty("sheetView")?;
+    if sheet_idx == 0 {
+        xml_out.attr_str("tabSelected", "1")?;
+    }
+    xml_out.attr_str("workbookViewId", "0")?;
+    xml_out.end_elem("sheetViews")?;
+
+    xml_out.empty("sheetFormatPr")?;
+    xml_out.attr_str("defaultRowHeight", "15")?;
+
+    write_cols(ctx.book, sheet, &col_xfs, xml_out)?;
+
+    xml_out.elem("sheetData")?;
+    for (row, mut cells) in rows {
+        cells.sort_by_key(|(c, _, _, _)| *c);
+        cells.dedup_by_key(|(c, _, _, _)| *c);
+
+        xml_out.elem("row")?;
+        xml_out.attr("r", &(row + 1))?;
+        if let Some(height) = row_height(ctx.book, sheet, row) {
+            xml_out.attr("ht", &fmt_pt(height))?;
+            xml_out.attr_str("customHeight", "1")?;
+        }
+        if sheet.row_visible(row) != Visibility::Visible {
+            xml_out.attr_str("hidden", "1")?;
+        }
+        let row_xf = sheet.row_cellstyle(row).map(|v| ctx.style_xf_index(v));
+        if let Some(row_xf) = row_xf {
+            xml_out.attr("s", &row_xf)?;
+            xml_out.attr_str("customFormat", "1")?;
+        }
+        for (col, base_row, base_col, cell) in cells {
+            let default_xf = row_xf.unwrap_or_else(|| col_xf(&col_xfs, col));
+            write_cell(
+                ctx, sheet_idx, sheet, row, col, base_row, base_col, cell, default_xf, xml_out,
+            )?;
+        }
+        xml_out.end_elem("row")?;
+    }
+    xml_out.end_elem("sheetData")?;
+
+    if !merged.is_empty() {
+        xml_out.elem("mergeCells")?;
+        xml_out.attr("count", &merged.len())?;
+        for range in merged {
+            xml_out.empty("mergeCell")?;
+            xml_out.attr("ref", &range)?;
+        }
+        xml_out.end_elem("mergeCells")?;
+    }
+
+    let validations = validations
+        .into_iter()
+        .filter_map(|(name, cells)| {
+            ctx.book
+                .validation(name)
+                .map(|validation| (validation, cells))
+        })
+        .filter_map(|(validation, cells)| {
+            let (row, col) = cells[0];
+            excel_validation(validation.condition().to_string().as_str(), row, col)
+                .map(|v| (validation, v, cells))
+        })
+        .collect::<Vec<_>>();
+    if !validations.is_empty() {
+        xml_out.elem("dataValidations")?;
+        xml_out.attr("count", &validations.len())?;
+        for (validation, xv, cells) in validations {
+            write_validation(validation, &xv, &cells, xml_out)?;
+        }
+        xml_out.end_elem("dataValidations")?;
+    }
+
+    // The break is after the given 1-based row/column.
+    let row_breaks = sheet
+        .row_page_breaks()
+        .filter(|v| *v > 0)
+        .collect::<Vec<_>>();
+    if !row_breaks.is_empty() {
+        xml_out.elem("rowBreaks")?;
+        xml_out.attr("count", &row_breaks.len())?;
+        xml_out.attr("manualBreakCount", &row_breaks.len())?;
+        for row in row_breaks {
+            xml_out.empty("brk")?;
+            xml_out.attr("id", &row)?;
+            xml_out.attr("max", &16383)?;
+            xml_out.attr("man", "1")?;
+        }
+        xml_out.end_elem("rowBreaks")?;
+    }
+    let col_breaks = sheet
+        .col_page_breaks()
+        .filter(|v| *v > 0)
+        .collect::<Vec<_>>();
+    if !col_breaks.is_empty() {
+        xml_out.elem("colBreaks")?;
+        xml_out.attr("count", &col_breaks.len())?;
+        xml_out.attr("manualBreakCount", &col_breaks.len())?;
+        for col in col_breaks {
+            xml_out.empty("brk")?;
+            xml_out.attr("id", &col)?;
+            xml_out.attr("max", &1048575)?;
+            xml_out.attr("man", "1")?;
+        }
+        xml_out.end_elem("colBreaks")?;
+    }
+
+    if let Some(rid) = &parts.drawing_rel {
+        xml_out.empty("drawing")?;
+        xml_out.attr("r:id", rid)?;
+    }
+    if let Some(rid) = &parts.vml_rel {
+        xml_out.empty("legacyDrawing")?;
+        xml_out.attr("r:id", rid)?;
+    }
+
+    xml_out.end_elem("worksheet")?;
+    xml_out.close()?;
+
+    Ok(())
+}
+
+/// Cells without content and extras.
+fn is_blank(cell: &CellData) -> bool {
+    cell.value == Value::Empty && cell.formula.is_none() && cell.extra.is_none()
+}
+
+/// Format of the column, 0 if it has no default cell-style.
+fn col_xf(col_xfs: &[(u32, u32, usize)], col: u32) -> usize {
+    col_xfs
+        .iter()
+        .find(|(from, to, _)| *from <= col && col < *to)
+        .map(|(_, _, xf)| *xf)
+        .unwrap_or(0)
+}
+
+/// Parts of the columns col..col_end where the column format differs
+/// from the given format. Columns without a default cell-style have
+/// the format 0.
+fn blank_spans(
+    col_xfs: &[(u32, u32, usize)],
+    col: u32,
+    col_end: u32,
+    xf: usize,
+) -> Vec<(u32, u32)> {
+    let mut spans: Vec<(u32, u32)> = Vec::new();
+    let mut push = |c: u32, c_end: u32, col_xf: usize| {
+        if c < c_end && col_xf != xf {
+            match spans.last_mut() {
+                Some(last) if last.1 == c => last.1 = c_end,
+                _ => spans.push((c, c_end)),
+            }
+        }
+    };
+
+    let mut c = col;
+    for (from, to, col_xf) in col_xfs.iter().copied() {
+        if to <= c {
+            continue;
+        }
+        if from >= col_end {
+            break;
+        }
+        push(c, from.max(c), 0);
+        push(from.max(c), to.min(col_end), col_xf);
+        c = to.min(col_end);
+    }
+    push(c, col_end, 0);
+
+    spans
+}
+
+fn write_cols(
+    book: &WorkBook,
+    sheet: &Sheet,
+    col_xfs: &[(u32, u32, usize)],
+    xml_out: &mut XlsxXmlWriter<'_>,
+) -> Result<(), OdsError> {
+    let mut cols = Vec::new();
+    for (col, header) in sheet.col_header.iter() {
+        if *col >= MAX_COLS {
+            break;
+        }
+        let width = length_pt(header.width).or_else(|| {
+            header
+                .style
+                .as_ref()
+                .and_then(|v| book.colstyle(v))
+                .and_then(|v| v.col_width().ok())
+                .and_then(length_pt)
+        });
+        let hidden = header.visible != Visibility::Visible;
+        let xf = col_xfs
+            .iter()
+            .find(|(v, _, _)| v == col)
+            .map(|(_, _, xf)| *xf);
+        if width.is_none() && !hidden && xf.is_none() {
+            continue;
+        }
+        let to_col = col.saturating_add(header.span.max(1)).min(MAX_COLS);
+        cols.push((*col, to_col, width, hidden, xf));
+    }
+
+    if !cols.is_empty() {
+        xml_out.elem("cols")?;
+        for (col, to_col, width, hidden, xf) in cols {
+            xml_out.empty("col")?;
+            xml_out.attr("min", &(col + 1))?;
+            xml_out.attr("max", &to_col)?;
+            if let Some(width) = width {
+                // points -> pixels -> digit widths
+                let width = (width * 96.0 / 72.0 / DIGIT_WIDTH_PX * 256.0).round() / 256.0;
+                xml_out.attr("width", &width)?;
+                xml_out.attr_str("customWidth", "1")?;
+            } else {
+                xml_out.attr_str("width", "9.140625")?;
+            }
+            if let Some(xf) = xf {
+                xml_out.attr("style", &xf)?;
+            }
+            if hidden {
+                xml_out.attr_str("hidden", "1")?;
+            }
+        }
+        xml_out.end_elem("cols")?;
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn write_cell(
+    ctx: &mut XlsxContext<'_>,
+    sheet_idx: usize,
+    sheet: &Sheet,
+    row: u32,
+    col: u32,
+    base_row: u32,
+    base_col: u32,
+    cell: &CellData,
+    default_xf: usize,
+    xml_out: &mut XlsxXmlWriter<'_>,
+) -> Result<(), OdsError> {
+    let xf = ctx.xf_index(sheet_idx, sheet, base_row, base_col, cell);
+    let formula = cell.formula.as_ref().map(|v| excel_formula(v));
+
+    let number = |v: f64| {
+        if v.is_finite() {
+            (None, Some(v.to_string()))
+        } else {
+            (Some("e"), Some("#NUM!".to_string()))
+        }
+    };
+    let (cell_type, value) = match &cell.value {
+        Value::Empty => (None, None),
+        Value::Boolean(v) => (Some("b"), Some(if *v { "1" } else { "0" }.to_string())),
+        Value::Number(v) | Value::Percentage(v) | Value::Currency(v, _) => number(*v),
+        Value::NumberDecimal(v) | Value::PercentageDecimal(v) | Value::CurrencyDecimal(v, _) => {
+            (None, Some(v.to_string()))
+        }
+        Value::DateTime(_) | Value::TimeDuration(_) => {
+            number(value_as_number(&cell.value).unwrap_or_default())
+        }
+        Value::Error(v) => (Some("e"), Some(v.as_str().to_string())),
+        Value::Text(_) | Value::TextXml(_) => {
+            let text = match &cell.value {
+                Value::TextXml(v) => text_of(v),
+                v => v.as_cow_str_or("").to_string(),
+            };
+            if formula.is_some() {
+                (Some("str"), Some(text))
+            } else {
+                (Some("s"), Some(ctx.shared_string(text).to_string()))
+            }
+        }
+    };
+
+    // The format of the row or column applies to missing cells.
+    if formula.is_none() && value.is_none() && xf == default_xf {
+        return Ok(());
+    }
+
+    let has_content = formula.is_some() || value.is_some();
+    xml_out.elem_if(has_content, "c")?;
+    xml_out.attr("r", &a1_cell(row, col))?;
+    if xf != 0 || default_xf != 0 {
+        xml_out.attr("s", &xf)?;
+    }
+    if let Some(cell_type) = cell_type {
+        xml_out.attr("t", cell_type)?;
+    }
+    if let Some(formula) = &formula {
+        xml_out.elem_text_esc("f", formula)?;
+    }
+    if let Some(value) = &value {
+        xml_out.elem_text_esc("v", xml_text(value).as_ref())?;
+    }
+    xml_out.end_elem_if(has_content, "c")?;
+
+    Ok(())
+}
+
+/// Data validation converted to xlsx.
+struct XlsxValidation {
+    validation_type: &'static str,
+    operator: &'static str,
+    formula1: String,
+    formula2: Option<String>,
+}
+
+fn write_validation(
+    validation: &Validation,
+    xv: &XlsxValidation,
+    cells: &[(u32, u32)],
+    xml_out: &mut XlsxXmlWriter<'_>,
+) -> Result<(), OdsError> {
+    xml_out.elem("dataValidation")?;
+    xml_out.attr("type", xv.validation_type)?;
+    if xv.operator != "between" {
+        xml_out.attr("operator", xv.operator)?;
+    }
+    if validation.allow_empty() {
+        xml_out.attr_str("allowBlank", "1")?;
+    }
+    if xv.validation_type == "list" && matches!(validation.display(), ValidationDisplay::NoDisplay)
+    {
+        // sic! this hides the dropdown.
+        xml_out.attr_str("showDropDown", "1")?;
+    }
+    if let Some(help) = validation.help() {
+        if help.display() {
+            xml_out.attr_str("showInputMessage", "1")?;
+        }
+        if let Some(title) = help.title() {
+            xml_out.attr_esc("promptTitle", title)?;
+        }
+        if let Some(text) = help.text() {
+            xml_out.attr_esc("prompt", &text_of(std::slice::from_ref(text)))?;
+        }
+    }
+    if let Some(err) = validation.err() {
+        if err.display() {
+            xml_out.attr_str("showErrorMessage", "1")?;
+        }
+        xml_out.attr("errorStyle", err.msg_type())?;
+        if let Some(title) = err.title() {
+            xml_out.attr_esc("errorTitle", title)?;
+        }
+        if let Some(text) = err.text() {
+            xml_out.attr_esc("error", &text_of(std::slice::from_ref(text)))?;
+        }
+    }
+    xml_out.attr("sqref", &sqref(cells))?;
+    xml_out.elem_text_esc("formula1", &xv.formula1)?;
+    if let Some(formula2) = &xv.formula2 {
+        xml_out.elem_text_esc("formula2", formula2)?;
+    }
+    xml_out.end_elem("dataValidation")?;
+    Ok(())
+}
+
+/// Joins horizontally adjacent cells to ranges.
+fn sqref(cells: &[(u32, u32)]) -> String {
+    let mut cells = cells.to_vec();
+    cells.sort();
+    cells.dedup();
+
+    let mut buf = String::new();
+    let mut it = cells.into_iter().peekable();
+    while let Some((row, col)) = it.next() {
+        let mut to_col = col;
+        while let Some((r, c)) = it.peek() {
+            if *r == row && *c == to_col + 1 {
+                to_col = *c;
+                it.next();
+            } else {
+                break;
+            }
+        }
+        if !buf.is_empty() {
+            buf.push(' ');
+        }
+        buf.push_str(a1_range(row, col, row, to_col).as_str());
+    }
+    buf
+}
+
+/// Converts a validation condition. The cell is used as base for
+/// conditions that must be expressed as a custom formula.
+fn excel_validation(cond: &str, row: u32, col: u32) -> Option<XlsxValidation> {
+    let cond = cond.trim();
+    let cond = cond.strip_prefix("of:").unwrap_or(cond);
+
+    let (validation_type, cond) =
+        if let Some(rest) = cond.strip_prefix("cell-content-is-whole-number() and ") {
+            (Some("whole"), rest)
+        } else if let Some(rest) = cond.strip_prefix("cell-content-is-decimal-number() and ") {
+            (Some("decimal"), rest)
+        } else if let Some(rest) = cond.strip_prefix("cell-content-is-date() and ") {
+            (Some("date"), rest)
+        } else if let Some(rest) = cond.strip_prefix("cell-content-is-time() and ") {
+            (Some("time"), rest)
+        } else {
+            (None, cond)
+        };
+
+    let validation = |validation_type, operator, formula1: &str, formula2: Option<&str>| {
+        Some(XlsxValidation {
+            validation_type,
+            operator,
+            formula1: excel_formula(formula1.trim()),
+            formula2: formula2.map(|v| excel_formula(v.trim())),
+        })
+    };
+
+    if let Some(rest) = cond.strip_prefix("cell-content-text-length()") {
+        let (operator, _, value) = split_compare_op(rest)?;
+        validation("textLength", operator, value, None)
+    } else if let Some(rest) = cond.strip_prefix("cell-content-text-length-is-between") {
+        let (a, b) = split_pair(rest)?;
+        validation("textLength", "between", a, Some(b))
+    } else if let Some(rest) = cond.strip_prefix("cell-content-text-length-is-not-between") {
+        let (a, b) = split_pair(rest)?;
+        validation("textLength", "notBetween", a, Some(b))
+    } else if let Some(rest) = cond.strip_prefix("cell-content()") {
+        let (operator, symbol, value) = split_compare_op(rest)?;
+        let validation_type =
+            validation_type.or_else(|| value.trim().parse::<f64>().ok().map(|_| "decimal"));
+        if let Some(validation_type) = validation_type {
+            validation(validation_type, operator, value, None)
+        } else {
+            Some(XlsxValidation {
+                validation_type: "custom",
+                operator: "between",
+                formula1: format!(
+                    "{}{}{}",
+                    a1_cell(row, col),
+                    symbol,
+                    excel_formula(value.trim())
+                ),
+                formula2: None,
+            })
+        }
+    } else if let Some(rest) = cond.strip_prefix("cell-content-is-between") {
+        let (a, b) = split_pair(rest)?;
+        validation(validation_type.unwrap_or("decimal"), "between", a, Some(b))
+    } else if let Some(rest) = cond.strip_prefix("cell-content-is-not-between") {
+        let (a, b) = split_pair(rest)?;
+        validation(
+            validation_type.unwrap_or("decimal"),
+            "notBetween",
+            a,
+            Some(b),
+        )
+    } else if let Some(rest) = cond
+        .strip_prefix("cell-content-is-in-list(")
+        .and_then(|v| v.strip_suffix(')'))
+    {
+        if rest.trim_start().starts_with('"') {
+            let mut buf = String::new();
+            buf.push('"');
+            for (i, v) in split_top_level(rest, &[';']).iter().enumerate() {
+                if i > 0 {
+                    buf.push(',');
+                }
+                let v = v.trim();
+                let v = v.strip_prefix('"').unwrap_or(v);
+                let v = v.strip_suffix('"').unwrap_or(v);
+                buf.push_str(v.replace("\"\"", "\"").as_str());
+            }
+            buf.push('"');
+            Some(XlsxValidation {
+                validation_type: "list",
+                operator: "between",
+                formula1: buf,
+                formula2: None,
+            })
+        } else {
+            validation("list", "between", rest, None)
+        }
+    } else if let Some(rest) = cond
+        .strip_prefix("is-true-formula(")
+        .and_then(|v| v.strip_suffix(')'))
+    {
+        validation("custom", "between", rest, None)
+    } else {
+        None
+    }
+}
+
+/// Operator name, operator symbol and the rest.
+fn split_compare_op(s: &str) -> Option<(&'static str, &'static str, &str)> {
+    let s = s.trim_start();
+    if let Some(rest) = s.strip_prefix("!=") {
+        Some(("notEqual", "<>", rest))
+    } else if let Some(rest) = s.strip_prefix("<=") {
+        Some(("lessThanOrEqual", "<=", rest))
+    } else if let Some(rest) = s.strip_prefix(">=") {
+        Some(("greaterThanOrEqual", ">=", rest))
+    } else if let Some(rest) = s.strip_prefix('=') {
+        Some(("equal", "=", rest))
+    } else if let Some(rest) = s.strip_prefix('<') {
+        Some(("lessThan", "<", rest))
+    } else if let Some(rest) = s.strip_prefix('>') {
+        Some(("greaterThan", ">", rest))
+    } else {
+        None
+    }
+}
+
+// (a, b) or (a; b)
+fn split_pair(s: &str) -> Option<(&str, &str)> {
+    let s = s.trim().strip_prefix('(')?.strip_suffix(')')?;
+    let parts = split_top_level(s, &[',', ';']);
+    match parts.as_slice() {
+        [a, b] => Some((a, b)),
+        _ => None,
+    }
+}
+
+// Splits at the separators outside of strings and parentheses.
+fn split_top_level<'s>(s: &'s str, sep: &[char]) -> Vec<&'s str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut quoted = false;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '(' | '[' if !quoted => depth += 1,
+            ')' | ']' if !quoted => depth -= 1,
+            c if !quoted && depth == 0 && sep.contains(&c) => {
+                parts.push(&s[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+}
+
+fn write_comments(
+    comments: &[XlsxComment<'_>],
+    xml_out: &mut XlsxXmlWriter<'_>,
+) -> Result<(), OdsError> {
+    let mut authors = Registry::new();
+    let comments = comments
+        .iter()
+        .map(|v| {
+            let author = authors.add(v.annotation.creator().cloned().unwrap_or_default());
+            (v, author)
+        })
+        .collect::<Vec<_>>();
+
+    xml_out.dtd("UTF-8")?;
+    xml_out.elem("comments")?;
+    xml_out.attr_str("xmlns", NS_MAIN)?;
+
+    xml_out.elem("authors")?;
+    for author in &authors.items {
+        xml_out.elem_text_esc("author", xml_text(author).as_ref())?;
+    }
+    xml_out.end_elem("authors")?;
+
+    xml_out.elem("commentList")?;
+    for (comment, author) in comments {
+        xml_out.elem("comment")?;
+        xml_out.attr("ref", &a1_cell(comment.row, comment.col))?;
+        xml_out.attr("authorId", &author)?;
+        xml_out.elem("text")?;
+        xml_out.elem("t")?;
+        xml_out.attr_str("xml:space", "preserve")?;
+        xml_out.text_esc(xml_text(&text_of(comment.annotation.text())).as_ref())?;
+        xml_out.end_elem("t")?;
+        xml_out.end_elem("text")?;
+        xml_out.end_elem("comment")?;
+    }
+    xml_out.end_elem("commentList")?;
+
+    xml_out.end_elem("comments")?;
+    xml_out.close()?;
+
+    Ok(())
+}
+
+/// Excel needs a vml shape for each comment.
+fn write_vml(
+    sheet_idx: usize,
+    comments: &[XlsxComment<'_>],
+    xml_out: &mut XlsxXmlWriter<'_>,
+) -> Result<(), OdsError> {
+    xml_out.elem("xml")?;
+    xml_out.attr_str("xmlns:v", "urn:schemas-microsoft-com:vml")?;
+    xml_out.attr_str("xmlns:o", "urn:schemas-microsoft-com:office:office")?;
+    xml_out.attr_str("xmlns:x", "urn:schemas-microsoft-com:office:excel")?;
+
+    xml_out.elem("o:shapelayout")?;
+    xml_out.attr_str("v:ext", "edit")?;
+    xml_out.empty("o:idmap")?;
+    xml_out.attr_str("v:ext", "edit")?;
+    xml_out.attr("data", &(sheet_idx + 1))?;
+    xml_out.end_elem("o:shapelayout")?;
+
+    xml_out.elem("v:shapetype")?;
+    xml_out.attr_str("id", "_x0000_t202")?;
+    xml_out.attr_str("coordsize", "21600,21600")?;
+    xml_out.attr_str("o:spt", "202")?;
+    xml_out.attr_str("path", "m,l,21600r21600,l21600,xe")?;
+    xml_out.empty("v:stroke")?;
+    xml_out.attr_str("joinstyle", "miter")?;
+    xml_out.empty("v:path")?;
+    xml_out.attr_str("gradientshapeok", "t")?;
+    xml_out.attr_str("o:connecttype", "rect")?;
+    xml_out.end_elem("v:shapetype")?;
+
+    for (i, comment) in comments.iter().enumerate() {
+        xml_out.elem("v:shape")?;
+        xml_out.attr("id", &format!("_x0000_s{}", 1024 * (sheet_idx + 1) + i + 1))?;
+        xml_out.attr_str("type", "#_x0000_t202")?;
+        if comment.annotation.display() {
+            xml_out.attr_str(
+                "style",
+                "position:absolute;margin-left:60pt;margin-top:2pt;width:108pt;height:60pt;z-index:1;visibility:visible",
+            )?;
+        } else {
+            xml_out.attr_str(
+                "style",
+                "position:absolute;margin-left:60pt;margin-top:2pt;width:108pt;height:60pt;z-index:1;visibility:hidden",
+            )?;
+        }
+        xml_out.attr_str("fillcolor", "#ffffe1")?;
+        xml_out.attr_str("o:insetmode", "auto")?;
+        xml_out.empty("v:fill")?;
+        xml_out.attr_str("color2", "#ffffe1")?;
+        xml_out.empty("v:shadow")?;
+        xml_out.attr_str("on", "t")?;
+        xml_out.attr_str("color", "black")?;
+        xml_out.attr_str("obscured", "t")?;
+        xml_out.empty("v:path")?;
+        xml_out.attr_str("o:connecttype", "none")?;
+        xml_out.elem("v:textbox")?;
+        xml_out.attr_str("style", "mso-direction-alt:auto")?;
+        xml_out.empty("div")?;
+        xml_out.attr_str("style", "text-align:left")?;
+        xml_out.end_elem("v:textbox")?;
+        xml_out.elem("x:ClientData")?;
+        xml_out.attr_str("ObjectType", "Note")?;
+        xml_out.empty("x:MoveWithCells")?;
+        xml_out.empty("x:SizeWithCells")?;
+        xml_out.elem_text(
+            "x:Anchor",
+            &format!(
+                "{}, 15, {}, 2, {}, 15, {}, 16",
+                comment.col + 1,
+                comment.row,
+                comment.col + 3,
+                comment.row + 4
+            ),
+        )?;
+        xml_out.elem_text("x:AutoFill", "False")?;
+        xml_out.elem_text("x:Row", &comment.row)?;
+        xml_out.elem_text("x:Column", &comment.col)?;
+        if comment.annotation.display() {
+            xml_out.empty("x:Visible")?;
+        }
+        xml_out.end_elem("x:ClientData")?;
+        xml_out.end_elem("v:shape")?;
+    }
+
+    xml_out.end_elem("xml")?;
+    xml_out.close()?;
+
+    Ok(())
+}
+
+fn write_drawing(images: &[XlsxImage], xml_out: &mut XlsxXmlWriter<'_>) -> Result<(), OdsError> {
+    xml_out.dtd("UTF-8")?;
+    xml_out.elem("xdr:wsDr")?;
+    xml_out.attr_str("xmlns:xdr", NS_DRAWING)?;
+    xml_out.attr_str("xmlns:a", NS_DRAWING_MAIN)?;
+    xml_out.attr_str("xmlns:r", NS_REL)?;
+
+    for (i, image) in images.iter().enumerate() {
+        xml_out.elem("xdr:oneCellAnchor")?;
+        xml_out.elem("xdr:from")?;
+        xml_out.elem_text("xdr:col", &image.col)?;
+        xml_out.elem_text("xdr:colOff", &image.x)?;
+        xml_out.elem_text("xdr:row", &image.row)?;
+        xml_out.elem_text("xdr:rowOff", &image.y)?;
+        xml_out.end_elem("xdr:from")?;
+        xml_out.empty("xdr:ext")?;
+        xml_out.attr("cx", &image.cx)?;
+        xml_out.attr("cy", &image.cy)?;
+
+        xml_out.elem("xdr:pic")?;
+        xml_out.elem("xdr:nvPicPr")?;
+        xml_out.empty("xdr:cNvPr")?;
+        xml_out.attr("id", &(i + 1))?;
+        xml_out.attr_esc("name", &image.name)?;
+        if !image.descr.is_empty() {
+            xml_out.attr_esc("descr", &image.descr)?;
+        }
+        xml_out.elem("xdr:cNvPicPr")?;
+        xml_out.empty("a:picLocks")?;
+        xml_out.attr_str("noChangeAspect", "1")?;
+        xml_out.end_elem("xdr:cNvPicPr")?;
+        xml_out.end_elem("xdr:nvPicPr")?;
+
+        xml_out.elem("xdr:blipFill")?;
+        xml_out.empty("a:blip")?;
+        xml_out.attr("r:embed", &format!("rId{}", i + 1))?;
+        xml_out.elem("a:stretch")?;
+        xml_out.empty("a:fillRect")?;
+        xml_out.end_elem("a:stretch")?;
+        xml_out.end_elem("xdr:blipFill")?;
+
+        xml_out.elem("xdr:spPr")?;
+        xml_out.elem("a:xfrm")?;
+        xml_out.empty("a:off")?;
+        xml_out.attr_str("x", "0")?;
+        xml_out.attr_str("y", "0")?;
+        xml_out.empty("a:ext")?;
+        xml_out.attr("cx", &image.cx)?;
+        xml_out.attr("cy", &image.cy)?;
+        xml_out.end_elem("a:xfrm")?;
+        xml_out.elem("a:prstGeom")?;
+        xml_out.attr_str("prst", "rect")?;
+        xml_out.empty("a:avLst")?;
+        xml_out.end_elem("a:prstGeom")?;
+        xml_out.end_elem("xdr:spPr")?;
+        xml_out.end_elem("xdr:pic")?;
+
+        xml_out.empty("xdr:clientData")?;
+        xml_out.end_elem("xdr:oneCellAnchor")?;
+    }
+
+    xml_out.end_elem("xdr:wsDr")?;
+    xml_out.close()?;
+
+    Ok(())
+}
+
+fn write_rels(
+    rels: &[(String, &'static str, String)],
+    xml_out: &mut XlsxXmlWriter<'_>,
+) -> Result<(), OdsError> {
+    xml_out.dtd("UTF-8")?;
+    xml_out.elem("Relationships")?;
+    xml_out.attr_str("xmlns", NS_PKG_REL)?;
+    for (id, rel_type, target) in rels {
+        xml_out.empty("Relationship")?;
+        xml_out.attr_esc("Id", id)?;
+        xml_out.attr_str("Type", rel_type)?;
+        xml_out.attr_esc("Target", target)?;
+    }
+    xml_out.end_elem("Relationships")?;
+    xml_out.close()?;
+    Ok(())
+}
+
+fn write_shared_strings(
+    ctx: &XlsxContext<'_>,
+    xml_out: &mut XlsxXmlWriter<'_>,
+) -> Result<(), OdsError> {
+    xml_out.dtd("UTF-8")?;
+    xml_out.elem("sst")?;
+    xml_out.attr_str("xmlns", NS_MAIN)?;
+    xml_out.attr("count", &ctx.string_refs)?;
+    xml_out.attr("uniqueCount", &ctx.strings.items.len())?;
+    for s in &ctx.strings.items {
+        xml_out.elem("si")?;
+        xml_out.elem("t")?;
+        xml_out.attr_str("xml:space", "preserve")?;
+        xml_out.text_esc(xml_text(s).as_ref())?;
+        xml_out.end_elem("t")?;
+        xml_out.end_elem("si")?;
+    }
+    xml_out.end_elem("sst")?;
+    xml_out.close()?;
+    Ok(())
+}
+
+fn write_styles(ctx: &XlsxContext<'_>, xml_out: &mut XlsxXmlWriter<'_>) -> Result<(), OdsError> {
+    xml_out.dtd("UTF-8")?;
+    xml_out.elem("styleSheet")?;
+    xml_out.attr_str("xmlns", NS_MAIN)?;
+
+    if !ctx.num_fmts.items.is_empty() {
+        xml_out.elem("numFmts")?;
+        xml_out.attr("count", &ctx.num_fmts.items.len())?;
+        for (i, code) in ctx.num_fmts.items.iter().enumerate() {
+            xml_out.empty("numFmt")?;
+            xml_out.attr("numFmtId", &(i as u32 + FIRST_NUM_FMT))?;
+            xml_out.attr_esc("formatCode", code)?;
+        }
+        xml_out.end_elem("numFmts")?;
+    }
+
+    xml_out.elem("fonts")?;
+    xml_out.attr("count", &ctx.fonts.items.len())?;
+    for font in &ctx.fonts.items {
+        xml_out.elem("font")?;
+        if font.bold {
+            xml_out.empty("b")?;
+        }
+        if font.italic {
+            xml_out.empty("i")?;
+        }
+        if font.strike {
+            xml_out.empty("strike")?;
+        }
+        if font.underline {
+            xml_out.empty("u")?;
+        }
+        xml_out.empty("sz")?;
+        xml_out.attr("val", &font.size)?;
+        if let Some(color) = &font.color {
+            xml_out.empty("color")?;
+            xml_out.attr("rgb", color)?;
+        }
+        xml_out.empty("name")?;
+        xml_out.attr_esc("val", &font.name)?;
+        xml_out.end_elem("font")?;
+    }
+    xml_out.end_elem("fonts")?;
+
+    xml_out.elem("fills")?;
+    xml_out.attr("count", &(ctx.fills.items.len() + 2))?;
+    xml_out.elem("fill")?;
+    xml_out.empty("patternFill")?;
+    xml_out.attr_str("patternType", "none")?;
+    xml_out.end_elem("fill")?;
+    xml_out.elem("fill")?;
+    xml_out.empty("patternFill")?;
+    xml_out.attr_str("patternType", "gray125")?;
+    xml_out.end_elem("fill")?;
+    for color in &ctx.fills.items {
+        xml_out.elem("fill")?;
+        xml_out.elem("patternFill")?;
+        xml_out.attr_str("patternType", "solid")?;
+        xml_out.empty("fgColor")?;
+        xml_out.attr("rgb", color)?;
+        xml_out.empty("bgColor")?;
+        xml_out.attr_str("indexed", "64")?;
+        xml_out.end_elem("patternFill")?;
+        xml_out.end_elem("fill")?;
+    }
+    xml_out.end_elem("fills")?;
+
+    xml_out.elem("borders")?;
+    xml_out.attr("count", &ctx.borders.items.len())?;
+    for border in &ctx.borders.items {
+        xml_out.elem("border")?;
+        if border.diagonal_up {
+            xml_out.attr_str("diagonalUp", "1")?;
+        }
+        if border.diagonal_down {
+            xml_out.attr_str("diagonalDown", "1")?;
+        }
+        for (tag, line) in [
+            ("left", &border.left),
+            ("right", &border.right),
+            ("top", &border.top),
+            ("bottom", &border.bottom),
+            ("diagonal", &border.diagonal),
+        ] {
+            if let Some(line) = line {
+                xml_out.elem(tag)?;
+                xml_out.attr("style", line.style)?;
+                xml_out.empty("color")?;
+                xml_out.attr("rgb", &line.color)?;
+                xml_out.end_elem(tag)?;
+            } else {
+                xml_out.empty(tag)?;
+            }
+        }
+        xml_out.end_elem("border")?;
+    }
+    xml_out.end_elem("borders")?;
+
+    xml_out.elem("cellStyleXfs")?;
+    xml_out.attr_str("count", "1")?;
+    xml_out.empty("xf")?;
+    xml_out.attr_str("numFmtId", "0")?;
+    xml_out.attr_str("fontId", "0")?;
+    xml_out.attr_str("fillId", "0")?;
+    xml_out.attr_str("borderId", "0")?;
+    xml_out.end_elem("cellStyleXfs")?;
+
+    xml_out.elem("cellXfs")?;
+    xml_out.attr("count", &ctx.xfs.items.len())?;
+    for xf in &ctx.xfs.items {
+        let has_alignment = xf.horizontal.is_some()
+            || xf.vertical.is_some()
+            || xf.wrap
+            || xf.shrink
+            || xf.rotation.is_some();
+        let has_protection = !xf.locked || xf.hidden;
+
+        xml_out.elem_if(has_alignment || has_protection, "xf")?;
+        xml_out.attr("numFmtId", &xf.num_fmt)?;
+        xml_out.attr("fontId", &xf.font)?;
+        xml_out.attr("fillId", &xf.fill)?;
+        xml_out.attr("borderId", &xf.border)?;
+        xml_out.attr_str("xfId", "0")?;
+        if xf.num_fmt != 0 {
+            xml_out.attr_str("applyNumberFormat", "1")?;
+        }
+        if xf.font != 0 {
+            xml_out.attr_str("applyFont", "1")?;
+        }
+        if xf.fill != 0 {
+            xml_out.attr_str("applyFill", "1")?;
+        }
+        if xf.border != 0 {
+            xml_out.attr_str("applyBorder", "1")?;
+        }
+        if has_alignment {
+            xml_out.attr_str("applyAlignment", "1")?;
+        }
+        if has_protection {
+            xml_out.attr_str("applyProtection", "1")?;
+        }
+        if has_alignment {
+            xml_out.empty("alignment")?;
+            if let Some(horizontal) = xf.horizontal {
+                xml_out.attr("horizontal", horizontal)?;
+            }
+            if let Some(vertical) = xf.vertical {
+                xml_out.attr("vertical", vertical)?;
+            }
+            if let Some(rotation) = xf.rotation {
+                xml_out.attr("textRotation", &rotation)?;
+            }
+            if xf.wrap {
+                xml_out.attr_str("wrapText", "1")?;
+            }
+            if xf.shrink {
+                xml_out.attr_str("shrinkToFit", "1")?;
+            }
+        }
+        if has_protection {
+            xml_out.empty("protection")?;
+            if !xf.locked {
+                xml_out.attr_str("locked", "0")?;
+            }
+            if xf.hidden {
+                xml_out.attr_str("hidden", "1")?;
+            }
+        }
+        xml_out.end_elem_if(has_alignment || has_protection, "xf")?;
+    }
+    xml_out.end_elem("cellXfs")?;
+
+    xml_out.elem("cellStyles")?;
+    xml_out.attr_str("count", "1")?;
+    xml_out.empty("cellStyle")?;
+    xml_out.attr_str("name", "Normal")?;
+    xml_out.attr_str("xfId", "0")?;
+    xml_out.attr_str("builtinId", "0")?;
+    xml_out.end_elem("cellStyles")?;
+
+    xml_out.end_elem("styleSheet")?;
+    xml_out.close()?;
+
+    Ok(())
+}
+
+fn write_workbook(book: &WorkBook, xml_out: &mut XlsxXmlWriter<'_>) -> Result<(), OdsError> {
+    xml_out.dtd("UTF-8")?;
+    xml_out.elem("workbook")?;
+    xml_out.attr_str("xmlns", NS_MAIN)?;
+    xml_out.attr_str("xmlns:r", NS_REL)?;
+
+    xml_out.elem("bookViews")?;
+    xml_out.empty("workbookView")?;
+    xml_out.end_elem("bookViews")?;
+
+    xml_out.elem("sheets")?;
+    for (idx, sheet) in book.iter_sheets().enumerate() {
+        xml_out.empty("sheet")?;
+        xml_out.attr_esc("name", sheet.name())?;
+        xml_out.attr("sheetId", &(idx + 1))?;
+        if !sheet.display() {
+            xml_out.attr_str("state", "hidden")?;
+        }
+        xml_out.attr("r:id", &format!("rId{}", idx + 1))?;
+    }
+    xml_out.end_elem("sheets")?;
+
+    let print_ranges = book
+        .iter_sheets()
+        .enumerate()
+        .filter_map(|(idx, sheet)| {
+            sheet
+                .print_ranges()
+                .filter(|v| !v.is_empty())
+                .map(|ranges| {
+                    let ranges = ranges
+                        .iter()
+                        .map(|v| {
+                            let mut range = v.clone().absolute();
+                            if range.table().is_none() {
+                                range.set_table(sheet.name());
+                            }
+                            excel_formula(range.to_formula().as_str())
+                        })
+                        .collect::<Vec<_>>();
+                    (idx, ranges.join(","))
+                })
+        })
+        .collect::<Vec<_>>();
+    if !print_ranges.is_empty() {
+        xml_out.elem("definedNames")?;
+        for (idx, ranges) in print_ranges {
+            xml_out.elem("definedName")?;
+            xml_out.attr_str("name", "_xlnm.Print_Area")?;
+            xml_out.attr("localSheetId", &idx)?;
+            xml_out.text_esc(&ranges)?;
+            xml_out.end_elem("definedName")?;
+        }
+        xml_out.end_elem("definedNames")?;
+    }
+
+    xml_out.end_elem("workbook")?;
+    xml_out.close()?;
+
+    Ok(())
+}
+
+fn write_content_types(
+    ctx: &XlsxContext<'_>,
+    sheet_parts: &[(bool, bool)],
+    xml_out: &mut XlsxXmlWriter<'_>,
+) -> Result<(), OdsError> {
+    xml_out.dtd("UTF-8")?;
+    xml_out.elem("Types")?;
+    xml_out.attr_str("xmlns", NS_CONTENT_TYPES)?;
+
+    let mut defaults = vec![
+        (
+            "rels",
+            "application/vnd.openxmlformats-package.relationships+xml",
+        ),
+        ("xml", "application/xml"),
+    ];
+    if ctx.has_vml {
+        defaults.push((
+            "vml",
+            "application/vnd.openxmlformats-officedocument.vmlDrawing",
+        ));
+    }
+    for ext in &ctx.image_ext {
+        defaults.push((ext, image_content_type(ext)));
+    }
+    for (ext, content_type) in defaults {
+        xml_out.empty("Default")?;
+        xml_out.attr("Extension", ext)?;
+        xml_out.attr("ContentType", content_type)?;
+    }
+
+    let mut overrides = vec![
+        (
+            "/xl/workbook.xml".to_string(),
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml",
+        ),
+        (
+            "/xl/styles.xml".to_string(),
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.styles+xml",
+        ),
+        (
+            "/xl/sharedStrings.xml".to_string(),
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sharedStrings+xml",
+        ),
+    ];
+    for (idx, (no_comments, no_images)) in sheet_parts.iter().enumerate() {
+        overrides.push((
+            format!("/xl/worksheets/sheet{}.xml", idx + 1),
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml",
+        ));
+        if !no_comments {
+            overrides.push((
+                format!("/xl/comments{}.xml", idx + 1),
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.comments+xml",
+            ));
+        }
+        if !no_images {
+            overrides.push((
+                format!("/xl/drawings/drawing{}.xml", idx + 1),
+                "application/vnd.openxmlformats-officedocument.drawing+xml",
+            ));
+        }
+    }
+    for (part, content_type) in overrides {
+        xml_out.empty("Override")?;
+        xml_out.attr_esc("PartName", &part)?;
+        xml_out.attr("ContentType", content_type)?;
+    }
+
+    xml_out.end_elem("Types")?;
+    xml_out.close()?;
+
+    Ok(())
+}
+
+/// Excel number format code for the value format.
+fn format_code(book: &WorkBook, name: &str) -> Option<String> {
+    if let Some(format) = book.formats_number.get(name) {
+        sections_code(book, format)
+    } else if let Some(format) = book.formats_percentage.get(name) {
+        sections_code(book, format)
+    } else if let Some(format) = book.formats_currency.get(name) {
+        sections_code(book, format)
+    } else if let Some(format) = book.formats_datetime.get(name) {
+        sections_code(book, format)
+    } else if let Some(format) = book.formats_timeduration.get(name) {
+        sections_code(book, format)
+    } else if let Some(format) = book.formats_text.get(name) {
+        sections_code(book, format)
+    } else {
+        None
+    }
+}
+
+/// Format code without any conditional sections.
+fn simple_format_code(book: &WorkBook, name: &str) -> Option<String> {
+    if let Some(format) = book.formats_number.get(name) {
+        part_code(format)
+    } else if let Some(format) = book.formats_percentage.get(name) {
+        part_code(format)
+    } else if let Some(format) = book.formats_currency.get(name) {
+        part_code(format)
+    } else if let Some(format) = book.formats_datetime.get(name) {
+        part_code(format)
+    } else if let Some(format) = book.formats_timeduration.get(name) {
+        part_code(format)
+    } else if let Some(format) = book.formats_text.get(name) {
+        part_code(format)
+    } else {
+        None
+    }
+}
+
+fn sections_code<T: ValueFormatTrait>(book: &WorkBook, format: &T) -> Option<String> {
+    let main = part_code(format)?;
+
+    let Some(stylemaps) = format.stylemaps().filter(|v| !v.is_empty()) else {
+        return Some(main);
+    };
+
+    let mut sections = Vec::new();
+    for stylemap in stylemaps {
+        let Some(code) = simple_format_code(book, stylemap.applied_style()) else {
+            continue;
+        };
+        let cond = stylemap.condition().to_string();
+        let cond = cond.trim().strip_prefix("value()").unwrap_or(cond.as_str());
+        sections.push((cond.trim().to_string(), code));
+    }
+
+    // The usual positive/negative split maps to the standard sections.
+    // Otherwise the conditions are written as such.
+    if let [(cond, code)] = sections.as_slice() {
+        if cond == ">=0" {
+            return Some(format!("{};{}", code, main));
+        }
+    }
+
+    let mut buf = String::new();
+    for (cond, code) in sections {
+        let _ = write!(buf, "[{}]{};", cond.replace("!=", "<>"), code);
+    }
+    buf.push_str(main.as_str());
+    Some(buf)
+}
+
+fn part_code<T: ValueFormatTrait>(format: &T) -> Option<String> {
+    let value_type = format.value_type();
+    let elapsed = value_type == ValueType::TimeDuration
+        || format.attrmap().attr("number:truncate-on-overflow") == Some("false");
+
+    let mut buf = String::new();
+
+    if let Ok(Some(color)) = Rgb::parse_attr(format.textstyle().attr("fo:color")) {
+        let name = match (color.r, color.g, color.b) {
+            (0, 0, 0) => Some("[Black]"),
+            (255, 0, 0) => Some("[Red]"),
+            (0, 255, 0) => Some("[Green]"),
+            (0, 0, 255) => Some("[Blue]"),
+            (255, 255, 0) => Some("[Yellow]"),
+            (255, 0, 255) => Some("[Magenta]"),
+            (0, 255, 255) => Some("[Cyan]"),
+            (255, 255, 255) => Some("[White]"),
+            _ => None,
+        };
+        if let Some(name) = name {
+            buf.push_str(name);
+        }
+    }
+
+    let mut first_hours = true;
+    for part in format.parts() {
+        let long = part.attr_def("number:style", "short") == "long";
+        match part.part_type() {
+            FormatPartType::Number => {
+                number_code(&mut buf, part);
+            }
+            FormatPartType::ScientificNumber => {
+                number_code(&mut buf, part);
+                let digits = part.attr_digits("number:min-exponent-digits").unwrap_or(2);
+                buf.push_str("E+");
+                push_n(&mut buf, '0', digits.max(1));
+            }
+            FormatPartType::Fraction => {
+                if let Some(digits) = part.attr_digits("number:min-integer-digits") {
+                    if digits == 0 {
+                        buf.push('#');
+                    } else {
+                        push_n(&mut buf, '0', digits);
+                    }
+                    buf.push(' ');
+                }
+                let numerator = part.attr_digits("number:min-numerator-digits").unwrap_or(1);
+                push_n(&mut buf, '?', numerator.max(1));
+                buf.push('/');
+                if let Some(denominator) = part.attrmap().attr("number:denominator-value") {
+                    buf.push_str(denominator);
+                } else {
+                    let denominator = part
+                        .attr_digits("number:min-denominator-digits")
+                        .unwrap_or(1);
+                    push_n(&mut buf, '?', denominator.max(1));
+                }
+            }
+            FormatPartType::CurrencySymbol => {
+                if let Some(symbol) = part.content().filter(|v| !v.is_empty()) {
+                    let _ = write!(buf, "[${}]", symbol.replace(']', ""));
+                }
+            }
+            FormatPartType::Day => {
+                buf.push_str(if long { "dd" } else { "d" });
+            }
+            FormatPartType::Month => {
+                let textual = part.attr_def("number:textual", "false") == "true";
+                buf.push_str(match (textual, long) {
+                    (true, true) => "mmmm",
+                    (true, false) => "mmm",
+                    (false, true) => "mm",
+                    (false, false) => "m",
+                });
+            }
+            FormatPartType::Year => {
+                buf.push_str(if long { "yyyy" } else { "yy" });
+            }
+            FormatPartType::DayOfWeek => {
+                buf.push_str(if long { "dddd" } else { "ddd" });
+            }
+            FormatPartType::Hours => {
+                let hours = if long { "hh" } else { "h" };
+                if elapsed && first_hours {
+                    let _ = write!(buf, "[{}]", hours);
+                } else {
+                    buf.push_str(hours);
+                }
+                first_hours = false;
+            }
+            FormatPartType::Minutes => {
+                buf.push_str(if long { "mm" } else { "m" });
+            }
+            FormatPartType::Seconds => {
+                buf.push_str(if long { "ss" } else { "s" });
+                let decimals = part.attr_digits("number:decimal-places").unwrap_or(0);
+                if decimals > 0 {
+                    buf.push('.');
+                    push_n(&mut buf, '0', decimals);
+                }
+            }
+            FormatPartType::AmPm => {
+                buf.push_str("AM/PM");
+            }
+            FormatPartType::Text => {
+                if let Some(text) = part.content() {
+                    if value_type == ValueType::Percentage && text.contains('%') {
+                        // an unquoted % scales the value.
+                        for (i, t) in text.split('%').enumerate() {
+                            if i > 0 {
+                                buf.push('%');
+                            }
+                            push_quoted(&mut buf, t);
+                        }
+                    } else {
+                        push_quoted(&mut buf, text);
+                    }
+                }
+            }
+            FormatPartType::TextContent => {
+                buf.push('@');
+            }
+            FormatPartType::FillCharacter => {
+                if let Some(c) = part.content().and_then(|v| v.chars().next()) {
+                    buf.push('*');
+                    buf.push(c);
+                }
+            }
+            FormatPartType::Boolean => {
+                // booleans are written as such.
+                return None;
+            }
+            FormatPartType::Era | FormatPartType::WeekOfYear | FormatPartType::Quarter => {}
+        }
+    }
+
+    if buf.is_empty() {
+        None
+    } else {
+        Some(buf)
+    }
+}
+
+/// Digits for number:number and number:scientific-number.
+fn number_code(buf: &mut String, part: &FormatPart) {
+    let Some(decimals) = part.attr_digits("number:decimal-places") else {
+        buf.push_str("General");
+        return;
+    };
+    let min_decimals = part
+        .attr_digits("number:min-decimal-places")
+        .unwrap_or(decimals)
+        .min(decimals);
+    let min_integer = part.attr_digits("number:min-integer-digits").unwrap_or(1);
+    let grouping = part.attr_def("number:grouping", "false") == "true";
+
+    // integer digits are built right to left.
+    let positions = if grouping {
+        min_integer.max(4)
+    } else {
+        min_integer.max(1)
+    };
+    let mut integer = Vec::new();
+    for i in 0..positions {
+        if grouping && i > 0 && i % 3 == 0 {
+            integer.push(',');
+        }
+        integer.push(if i < min_integer { '0' } else { '#' });
+    }
+    buf.extend(integer.iter().rev());
+
+    if decimals > 0 {
+        buf.push('.');
+        push_n(buf, '0', min_decimals);
+        push_n(buf, '#', decimals - min_decimals);
+    }
+
+    if let Some(factor) = part
+        .attrmap()
+        .attr("number:display-factor")
+        .and_then(|v| v.parse::<f64>().ok())
+    {
+        let mut factor = factor;
+        while factor >= 1000.0 {
+            buf.push(',');
+            factor /= 1000.0;
+        }
+    }
+
+    if let Some(text) = part.content() {
+        push_quoted(buf, text);
+    }
+}
+
+fn push_n(buf: &mut String, c: char, n: u32) {
+    for _ in 0..n {
+        buf.push(c);
+    }
+}
+
+fn push_quoted(buf: &mut String, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    if text.contains('"') {
+        for c in text.chars() {
+            buf.push('\\');
+            buf.push(c);
+        }
+    } else {
+        buf.push('"');
+        buf.push_str(text);
+        buf.push('"');
+    }
+}
+
+/// Row height in pt if one is set.
+fn row_height(book: &WorkBook, sheet: &Sheet, row: u32) -> Option<f64> {
+    length_pt(sheet.row_height(row)).or_else(|| {
+        sheet
+            .rowstyle(row)
+            .and_then(|v| book.rowstyle(v))
+            .and_then(|v| v.row_height().ok())
+            .and_then(length_pt)
+    })
+}
+
+/// Converts to pt. Em is calculated with a 12pt font.
+fn length_pt(length: Length) -> Option<f64> {
+    match length {
+        Length::Default => None,
+        Length::Cm(v) => Some(v * 72.0 / 2.54),
+        Length::Mm(v) => Some(v * 72.0 / 25.4),
+        Length::In(v) => Some(v * 72.0),
+        Length::Pt(v) => Some(v),
+        Length::Pc(v) => Some(v * 12.0),
+        Length::Em(v) => Some(v * 12.0),
+    }
+}
+
+fn fmt_pt(v: f64) -> String {
+    ((v * 100.0).round() / 100.0).to_string()
+}
+
+fn frame_emu(frame: &DrawFrame, attr: &str) -> Option<i64> {
+    Length::parse_attr(frame.attrmap().attr(attr))
+        .ok()
+        .flatten()
+        .and_then(length_pt)
+        .map(|v| (v * EMU_PER_PT).round() as i64)
+}
+
+fn argb(color: Rgb<u8>) -> String {
+    format!("FF{:02X}{:02X}{:02X}", color.r, color.g, color.b)
+}
+
+fn border_line(border: Option<(Length, Border, Rgb<u8>)>) -> Option<XlsxLine> {
+    let (width, border, color) = border?;
+    let width = length_pt(width).unwrap_or(0.75);
+    let style = match border {
+        Border::None | Border::Hidden => return None,
+        _ if width <= 0.0 => return None,
+        Border::Dotted => "dotted",
+        Border::Dashed if width > 1.0 => "mediumDashed",
+        Border::Dashed => "dashed",
+        Border::Double => "double",
+        _ if width <= 1.0 => "thin",
+        _ if width <= 2.5 => "medium",
+        _ => "thick",
+    };
+    Some(XlsxLine {
+        style,
+        color: argb(color),
+    })
+}
+
+/// Plain text of some paragraphs.
+fn text_of(text: &[TextTag]) -> String {
+    let mut buf = String::new();
+    for (i, t) in text.iter().enumerate() {
+        if i > 0 {
+            buf.push('\n');
+        }
+        t.extract_text(&mut buf);
+    }
+    buf
+}
+
+/// Removes characters that are not allowed in XML.
+fn xml_text(text: &str) -> Cow<'_, str> {
+    if text
+        .chars()
+        .any(|c| c < ' ' && !matches!(c, '\t' | '\n' | '\r'))
+    {
+        Cow::Owned(
+            text.chars()
+                .filter(|c| *c >= ' ' || matches!(c, '\t' | '\n' | '\r'))
+                .collect(),
+        )
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// File extension from the image data.
+fn image_ext(data: &[u8]) -> &'static str {
+    if data.starts_with(b"\x89PNG") {
+        "png"
+    } else if data.starts_with(b"\xFF\xD8") {
+        "jpeg"
+    } else if data.starts_with(b"GIF8") {
+        "gif"
+    } else if data.starts_with(b"BM") {
+        "bmp"
+    } else if data.starts_with(b"II*\0") || data.starts_with(b"MM\0*") {
+        "tiff"
+    } else if data.starts_with(b"<?xml") || data.starts_with(b"<svg") {
+        "svg"
+    } else {
+        "png"
+    }
+}
+
+fn image_content_type(ext: &str) -> &'static str {
+    match ext {
+        "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "bmp" => "image/bmp",
+        "tiff" => "image/tiff",
+        "svg" => "image/svg+xml",
+        _ => "image/png",
+    }
+}
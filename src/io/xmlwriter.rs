@@ -74,6 +74,9 @@ pub(crate) struct XmlWriter<W: Write> {
     stack: Stack,
     open: Open,
     line_break: bool,
+    /// Open elements with mixed content, counted from the outermost.
+    /// Line breaks in there would change the text.
+    mixed: u32,
 
     // short time temp space
     tmp: Vec<u8>,
@@ -99,6 +102,7 @@ impl<W: Write> XmlWriter<W> {
             writer: Box::new(writer),
             open: Open::None,
             line_break: false,
+            mixed: 0,
             tmp: Default::default(),
             tmp2: Default::default(),
         }
@@ -109,6 +113,29 @@ impl<W: Write> XmlWriter<W> {
         self
     }
 
+    /// Line break after a tag, if line breaks are enabled and this
+    /// is not inside mixed content.
+    fn push_line_break(&mut self) {
+        if self.line_break && self.mixed == 0 {
+            self.buf.push('\n');
+        }
+    }
+
+    /// Marks the current element as mixed content. Text and tags
+    /// are written without any line breaks until the element ends.
+    pub(crate) fn mixed_content(&mut self) {
+        if self.mixed == 0 {
+            self.mixed = 1;
+        }
+    }
+
+    /// Paragraphs and spans always have mixed content.
+    fn start_mixed(&mut self, name: &str) {
+        if self.mixed > 0 || matches!(name, "text:p" | "text:h" | "text:span") {
+            self.mixed += 1;
+        }
+    }
+
     /// Write the DTD. You have to take care of the encoding
     /// on the underlying Write yourself.
     pub(crate) fn dtd(&mut self, encoding: &str) -> io::Result<()> {
@@ -140,9 +167,7 @@ impl<W: Write> XmlWriter<W> {
         self.buf.push('/');
         self.buf.push_str(name);
         self.buf.push('>');
-        if self.line_break {
-            self.buf.push('\n');
-        }
+        self.push_line_break();
 
         Ok(())
     }
@@ -166,9 +191,7 @@ impl<W: Write> XmlWriter<W> {
         self.buf.push('/');
         self.buf.push_str(name);
         self.buf.push('>');
-        if self.line_break {
-            self.buf.push('\n');
-        }
+        self.push_line_break();
 
         Ok(())
     }
@@ -180,9 +203,7 @@ impl<W: Write> XmlWriter<W> {
         self.buf.push_str("<!--");
         self.buf.push_str(comment);
         self.buf.push_str("-->");
-        if self.line_break {
-            self.buf.push('\n');
-        }
+        self.push_line_break();
 
         Ok(())
     }
@@ -192,6 +213,7 @@ impl<W: Write> XmlWriter<W> {
         self.close_elem()?;
 
         self.stack.push(name);
+        self.start_mixed(name);
 
         self.buf.push('<');
         self.open = Open::Elem;
@@ -205,6 +227,7 @@ impl<W: Write> XmlWriter<W> {
 
         if has_content {
             self.stack.push(name);
+            self.start_mixed(name);
         }
 
         self.buf.push('<');
@@ -233,9 +256,7 @@ impl<W: Write> XmlWriter<W> {
             Open::Empty => {
                 self.buf.push('/');
                 self.buf.push('>');
-                if self.line_break {
-                    self.buf.push('\n');
-                }
+                self.push_line_break();
             }
         }
         self.open = Open::None;
@@ -381,6 +402,7 @@ impl<W: Write> XmlWriter<W> {
     /// Write a text, doesn't escape the text.
     pub(crate) fn text_str(&mut self, text: &'static str) -> io::Result<()> {
         self.close_elem()?;
+        self.mixed_content();
         self.buf.push_str(text);
         Ok(())
     }
@@ -388,6 +410,7 @@ impl<W: Write> XmlWriter<W> {
     /// Write a text, doesn't escape the text.
     pub(crate) fn text<T: Display + ?Sized>(&mut self, text: &T) -> io::Result<()> {
         self.close_elem()?;
+        self.mixed_content();
         let _ = write!(self.buf, "{}", text);
         Ok(())
     }
@@ -395,6 +418,7 @@ impl<W: Write> XmlWriter<W> {
     /// Write a text, escapes the text automatically
     pub(crate) fn text_esc<T: Display + ?Sized>(&mut self, text: &T) -> io::Result<()> {
         self.close_elem()?;
+        self.mixed_content();
         self.escape(text)?;
         Ok(())
     }
@@ -428,9 +452,8 @@ impl<W: Write> XmlWriter<W> {
         self.buf.push('/');
         self.buf.push_str(name);
         self.buf.push('>');
-        if self.line_break {
-            self.buf.push('\n');
-        }
+        self.mixed = self.mixed.saturating_sub(1);
+        self.push_line_break();
 
         Ok(())
     }
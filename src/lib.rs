@@ -8,6 +8,7 @@ pub use crate::format::{
     ValueFormatBoolean, ValueFormatCurrency, ValueFormatDateTime, ValueFormatNumber,
     ValueFormatPercentage, ValueFormatRef, ValueFormatText, ValueFormatTimeDuration,
 };
+pub use crate::io::pdf::write::{write_pdf, write_pdf_buf, write_pdf_to};
 pub use crate::io::read::{
     read_fods, read_fods_buf, read_fods_from, read_ods, read_ods_buf, read_ods_from, OdsOptions,
 };
@@ -15,11 +16,13 @@ pub use crate::io::write::{
     write_fods, write_fods_buf, write_fods_to, write_ods, write_ods_buf,
     write_ods_buf_uncompressed, write_ods_to, OdsWriteOptions,
 };
-pub use crate::refs::{CCol, CRow, CellRange, CellRef, ColRange, RowRange};
+pub use crate::io::xlsx::read::{read_xlsx, read_xlsx_buf, read_xlsx_from};
+pub use crate::io::xlsx::write::{write_xlsx, write_xlsx_buf, write_xlsx_to};
+pub use crate::refs::{CCol, CRow, CellRange, CellRangeList, CellRef, ColRange, RowRange};
 pub use crate::sheet_::Sheet;
 pub use crate::style::units::{Angle, Length};
 pub use crate::style::{CellStyle, CellStyleRef};
-pub use crate::value_::{Value, ValueType};
+pub use crate::value_::{FormulaError, Value, ValueType};
 // pub mod value {
 // }
 pub use crate::workbook_::WorkBook;
@@ -55,22 +58,25 @@ mod config;
 mod ds;
 mod error;
 mod io;
-mod locale;
 mod sheet_;
+mod sheetrefs;
 #[macro_use]
 mod value_;
 mod workbook_;
 
 pub mod cell {
     //! Detail structs for a Cell.
-    pub use crate::cell_::CellSpan;
+    pub use crate::cell_::{ArrayFormula, CellSpan, MergeMode};
 }
 pub mod condition;
 pub mod defaultstyles;
 pub mod draw;
+pub mod find;
+pub mod form;
 pub mod format;
 #[macro_use]
 pub mod formula;
+pub mod locale;
 pub mod manifest;
 pub mod metadata;
 pub mod refs;
@@ -78,11 +84,14 @@ pub mod sheet {
     //! Detail structs for a Sheet.
     pub use crate::sheet_::{CellIter, Grouped, Range, SheetConfig, SplitMode, Visibility};
 }
+pub mod sort;
 pub mod style;
 pub mod text;
+pub mod validate;
 pub mod validation;
 pub mod workbook {
     //! Detail structs for the WorkBook.
+    pub use crate::sheetrefs::RefChanges;
     pub use crate::workbook_::{EventListener, Script, WorkBookConfig};
 }
 pub mod xlink;
@@ -20,6 +20,14 @@ impl LocalizedValueFormat for LocaleDeAt {
         LocaleDeAt::LOCALE
     }
 
+    fn decimal_separator(&self) -> char {
+        ','
+    }
+
+    fn grouping_separator(&self) -> char {
+        '.'
+    }
+
     fn boolean_format(&self) -> ValueFormatBoolean {
         let mut v = ValueFormatBoolean::new_localized(DefaultFormat::bool(), Self::LOCALE);
         v.part_boolean().build();
@@ -85,7 +93,7 @@ impl LocalizedValueFormat for LocaleDeAt {
     }
 
     fn time_of_day_format(&self) -> ValueFormatDateTime {
-        let mut v = ValueFormatDateTime::new_localized(DefaultFormat::datetime(), Self::LOCALE);
+        let mut v = ValueFormatDateTime::new_localized(DefaultFormat::time_of_day(), Self::LOCALE);
         v.part_hours().style(FormatNumberStyle::Long).build();
         v.part_text(":").build();
         v.part_minutes().style(FormatNumberStyle::Long).build();
@@ -95,7 +103,8 @@ impl LocalizedValueFormat for LocaleDeAt {
     }
 
     fn time_interval_format(&self) -> ValueFormatTimeDuration {
-        let mut v = ValueFormatTimeDuration::new_localized(DefaultFormat::datetime(), Self::LOCALE);
+        let mut v =
+            ValueFormatTimeDuration::new_localized(DefaultFormat::time_interval(), Self::LOCALE);
         v.set_truncate_on_overflow(false);
 
         v.part_hours().style(FormatNumberStyle::Long).build();
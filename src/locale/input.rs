@@ -0,0 +1,536 @@
+use crate::defaultstyles::DefaultFormat;
+use crate::format::{FormatPart, FormatPartType, ValueFormatRef, ValueFormatTrait};
+use crate::locale::localized_format;
+use crate::{Value, ValueType};
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime};
+use icu_locid::{locale, Locale};
+
+/// Result of parsing user input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedValue {
+    /// The typed value.
+    pub value: Value,
+    /// Suggested value format. This is either the target format given
+    /// with [ValueParser::set_format] or one of the [DefaultFormat]s as
+    /// created by [WorkBook::new](crate::WorkBook::new).
+    /// None for plain numbers and text.
+    pub format: Option<ValueFormatRef>,
+}
+
+/// Order of day, month and year in a date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DateOrder {
+    Dmy,
+    Mdy,
+    Ymd,
+}
+
+/// Parses text as entered by a user into a typed Value, similar to the
+/// input line of a spreadsheet application.
+///
+/// Recognizes
+/// * booleans: TRUE/FALSE and the localized words.
+/// * numbers with the decimal and grouping separators of the locale,
+///   and in scientific notation. Parentheses denote a negative number.
+/// * percentages: "12,5 %".
+/// * currencies: the symbol of the locale, common currency symbols and
+///   ISO codes before or after the number: "1.234,56 €", "$-5", "-5 USD".
+/// * dates in the order of the locale and ISO 8601. A missing year
+///   is the current year, two digit years are 1930..2029.
+/// * times: "3:45 PM", "13:45:10.5". Hours beyond 24 or negative times
+///   are durations.
+/// * date and time: "31.12.2024 13:45", "2024-12-31T13:45:00".
+///
+/// Everything else is text. A leading apostrophe forces text.
+///
+/// ```
+/// # #[cfg(feature = "locale_de_DE")] {
+/// use icu_locid::locale;
+/// use spreadsheet_ods::locale::ValueParser;
+/// use spreadsheet_ods::Value;
+///
+/// let parser = ValueParser::new(locale!("de_DE"));
+/// assert_eq!(
+///     parser.parse("1.234,56 €").value,
+///     Value::new_currency("EUR", 1234.56)
+/// );
+/// assert_eq!(parser.parse("12,5 %").value, Value::Percentage(0.125));
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ValueParser {
+    decimal: char,
+    grouping: char,
+    date_order: DateOrder,
+    /// Currency symbol of the locale and the ISO code.
+    currency: Option<(String, String)>,
+    /// Localized words for TRUE and FALSE.
+    booleans: Option<(&'static str, &'static str)>,
+    /// Value type and name of the target format.
+    target: Option<(ValueType, ValueFormatRef)>,
+}
+
+/// Well known currency symbols. Longer symbols first.
+const CURRENCY_SYMBOLS: [(&str, &str); 10] = [
+    ("R$", "BRL"),
+    ("US$", "USD"),
+    ("CHF", "CHF"),
+    ("zł", "PLN"),
+    ("kr", "SEK"),
+    ("€", "EUR"),
+    ("$", "USD"),
+    ("£", "GBP"),
+    ("￥", "JPY"),
+    ("¥", "JPY"),
+];
+
+impl ValueParser {
+    /// Parser for the given locale. Uses the separators, date order and
+    /// currency of the registered [LocalizedValueFormat](crate::locale::LocalizedValueFormat).
+    /// Without one the defaults for "en" are used.
+    pub fn new(locale: Locale) -> Self {
+        let mut parser = Self {
+            decimal: '.',
+            grouping: ',',
+            date_order: DateOrder::Ymd,
+            currency: None,
+            booleans: boolean_words(locale.id.language.as_str()),
+            target: None,
+        };
+
+        if let Some(lf) =
+            localized_format(locale.clone()).or_else(|| localized_format(locale!("en")))
+        {
+            parser.decimal = lf.decimal_separator();
+            parser.grouping = lf.grouping_separator();
+            if let Some(order) = date_order(lf.date_format().parts()) {
+                parser.date_order = order;
+            }
+            parser.set_currency(lf.currency_format().parts(), &locale);
+        }
+
+        parser
+    }
+
+    /// Parses with a target format. The date order and currency symbol
+    /// are taken from the format, and the format is suggested if the
+    /// value type fits.
+    pub fn set_format<T: ValueFormatTrait>(&mut self, format: &T) {
+        let locale = format_locale(format);
+        if let Some(lf) = locale.clone().and_then(localized_format) {
+            self.decimal = lf.decimal_separator();
+            self.grouping = lf.grouping_separator();
+        }
+        if let Some(order) = date_order(format.parts()) {
+            self.date_order = order;
+        }
+        self.set_currency(format.parts(), &locale.unwrap_or(Locale::UND));
+        self.target = Some((format.value_type(), format.format_ref()));
+    }
+
+    fn set_currency(&mut self, parts: &[FormatPart], locale: &Locale) {
+        let Some(part) = parts
+            .iter()
+            .find(|v| v.part_type() == FormatPartType::CurrencySymbol)
+        else {
+            return;
+        };
+        let symbol = part.content().cloned().unwrap_or_default();
+        let code = part
+            .attrmap()
+            .attr("number:country")
+            .and_then(currency_code)
+            .or_else(|| locale.id.region.and_then(|v| currency_code(v.as_str())))
+            .or_else(|| {
+                CURRENCY_SYMBOLS
+                    .iter()
+                    .find(|(s, _)| *s == symbol)
+                    .map(|(_, c)| *c)
+            });
+        if let Some(code) = code {
+            let symbol = if symbol.is_empty() {
+                code.to_string()
+            } else {
+                symbol
+            };
+            self.currency = Some((symbol, code.to_string()));
+        }
+    }
+
+    /// Parses the text.
+    pub fn parse(&self, text: &str) -> ParsedValue {
+        let text = text.trim();
+        let (value, default) = if text.is_empty() {
+            (Value::Empty, None)
+        } else if let Some(text) = text.strip_prefix('\'') {
+            (Value::Text(text.to_string()), None)
+        } else if let Some(v) = self.parse_boolean(text) {
+            (Value::Boolean(v), Some(DefaultFormat::bool()))
+        } else if let Some(v) = self.parse_number(text) {
+            (Value::Number(v), None)
+        } else if let Some(v) = self.parse_percentage(text) {
+            (Value::Percentage(v), Some(DefaultFormat::percent()))
+        } else if let Some((v, code)) = self.parse_currency(text) {
+            (
+                Value::Currency(v, code.into()),
+                Some(DefaultFormat::currency()),
+            )
+        } else if let Some(v) = self.parse_date(text) {
+            (
+                Value::DateTime(v.and_time(NaiveTime::MIN)),
+                Some(DefaultFormat::date()),
+            )
+        } else if let Some((v, clock)) = self.parse_time(text) {
+            let format = if clock {
+                DefaultFormat::time_of_day()
+            } else {
+                DefaultFormat::time_interval()
+            };
+            (Value::TimeDuration(v), Some(format))
+        } else if let Some(v) = self.parse_datetime(text) {
+            (Value::DateTime(v), Some(DefaultFormat::datetime()))
+        } else {
+            (Value::Text(text.to_string()), None)
+        };
+
+        let format = match &self.target {
+            Some((value_type, format)) if *value_type == value.value_type() => Some(format.clone()),
+            _ => default,
+        };
+        ParsedValue { value, format }
+    }
+
+    fn parse_boolean(&self, text: &str) -> Option<bool> {
+        let text = text.to_uppercase();
+        if text == "TRUE" {
+            Some(true)
+        } else if text == "FALSE" {
+            Some(false)
+        } else if let Some((t, f)) = self.booleans {
+            if text == t {
+                Some(true)
+            } else if text == f {
+                Some(false)
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Parses a number with an optional sign.
+    fn parse_number(&self, text: &str) -> Option<f64> {
+        if let Some(text) = text.strip_prefix('(').and_then(|v| v.strip_suffix(')')) {
+            return self.parse_unsigned(text.trim()).map(|v| -v);
+        }
+        if let Some(text) = text.strip_prefix('-') {
+            self.parse_unsigned(text).map(|v| -v)
+        } else if let Some(text) = text.strip_prefix('+') {
+            self.parse_unsigned(text)
+        } else {
+            self.parse_unsigned(text)
+        }
+    }
+
+    /// Parses digits with grouping, decimals and exponent.
+    fn parse_unsigned(&self, text: &str) -> Option<f64> {
+        let (mantissa, exponent) = match text.find(['e', 'E']) {
+            Some(idx) => {
+                let exp = &text[idx + 1..];
+                let digits = exp.strip_prefix(['+', '-']).unwrap_or(exp);
+                if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+                    return None;
+                }
+                (&text[..idx], Some(exp))
+            }
+            None => (text, None),
+        };
+
+        let (int, frac) = match mantissa.split_once(self.decimal) {
+            Some((int, frac)) => (int, Some(frac)),
+            None => (mantissa, None),
+        };
+        if let Some(frac) = frac {
+            if !frac.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+        }
+        let int = self.ungroup(int)?;
+        if int.is_empty() && frac.map(|v| v.is_empty()).unwrap_or(true) {
+            return None;
+        }
+
+        let mut buf = if int.is_empty() { "0".to_string() } else { int };
+        if let Some(frac) = frac.filter(|v| !v.is_empty()) {
+            buf.push('.');
+            buf.push_str(frac);
+        }
+        if let Some(exp) = exponent {
+            buf.push('e');
+            buf.push_str(exp);
+        }
+        buf.parse().ok().filter(|v: &f64| v.is_finite())
+    }
+
+    /// Removes grouping separators. All groups but the first must have
+    /// three digits.
+    fn ungroup(&self, text: &str) -> Option<String> {
+        let is_grouping = |c: char| {
+            c == self.grouping
+                || (self.grouping.is_whitespace() && (c == ' ' || c == '\u{202F}'))
+                || (self.grouping == '\'' && c == '’')
+        };
+        let mut buf = String::new();
+        if text.is_empty() {
+            return Some(buf);
+        }
+        for (idx, group) in text.split(is_grouping).enumerate() {
+            if group.is_empty() || !group.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            if idx > 0 && group.len() != 3 {
+                return None;
+            }
+            buf.push_str(group);
+        }
+        Some(buf)
+    }
+
+    fn parse_percentage(&self, text: &str) -> Option<f64> {
+        let number = text
+            .strip_suffix('%')
+            .or_else(|| text.strip_prefix('%'))?
+            .trim();
+        self.parse_number(number).map(|v| v / 100.0)
+    }
+
+    /// Parses a number with a currency symbol. The sign can be before
+    /// or after the symbol.
+    fn parse_currency(&self, text: &str) -> Option<(f64, String)> {
+        let (negative, text) = match text.strip_prefix('-') {
+            Some(text) => (true, text.trim_start()),
+            None => (false, text),
+        };
+
+        let own = self.currency.iter().map(|(s, c)| (s.as_str(), c.as_str()));
+        let known = CURRENCY_SYMBOLS.iter().map(|(s, c)| {
+            // the local meaning of ¥ and $.
+            match &self.currency {
+                Some((symbol, code)) if symbol == s => (*s, code.as_str()),
+                _ => (*s, *c),
+            }
+        });
+        for (symbol, code) in own.chain(known) {
+            let number = text
+                .strip_prefix(symbol)
+                .or_else(|| text.strip_suffix(symbol));
+            if let Some(v) = number.and_then(|v| self.parse_number(v.trim())) {
+                return Some((if negative { -v } else { v }, code.to_string()));
+            }
+        }
+
+        // ISO code
+        let is_code = |v: &str| v.len() == 3 && v.chars().all(|c| c.is_ascii_uppercase());
+        let prefix = text.get(..3).filter(|v| is_code(v));
+        let suffix = text
+            .get(text.len().saturating_sub(3)..)
+            .filter(|v| is_code(v));
+        let (number, code) = if let Some(code) = prefix {
+            (&text[3..], code)
+        } else if let Some(code) = suffix {
+            (&text[..text.len() - 3], code)
+        } else {
+            return None;
+        };
+        let v = self.parse_number(number.trim())?;
+        Some((if negative { -v } else { v }, code.to_string()))
+    }
+
+    /// Parses a date in the order of the locale or as ISO 8601.
+    fn parse_date(&self, text: &str) -> Option<NaiveDate> {
+        let sep = text.chars().find(|c| matches!(c, '.' | '/' | '-'))?;
+        let text = text.strip_suffix(sep).unwrap_or(text);
+        let parts = text.split(sep).map(|v| v.trim()).collect::<Vec<_>>();
+        if parts
+            .iter()
+            .any(|v| v.is_empty() || v.len() > 4 || !v.chars().all(|c| c.is_ascii_digit()))
+        {
+            return None;
+        }
+        let num = |v: &str| v.parse::<u32>().ok();
+
+        let (year, month, day) = match parts.as_slice() {
+            [y, m, d] if y.len() == 4 => (expand_year(y)?, num(m)?, num(d)?),
+            [a, b, c] => match self.date_order {
+                DateOrder::Dmy => (expand_year(c)?, num(b)?, num(a)?),
+                DateOrder::Mdy => (expand_year(c)?, num(a)?, num(b)?),
+                DateOrder::Ymd => (expand_year(a)?, num(b)?, num(c)?),
+            },
+            [a, b] if a.len() <= 2 && b.len() <= 2 => {
+                let year = chrono::Local::now().year();
+                match self.date_order {
+                    DateOrder::Dmy => (year, num(b)?, num(a)?),
+                    DateOrder::Mdy | DateOrder::Ymd => (year, num(a)?, num(b)?),
+                }
+            }
+            _ => return None,
+        };
+        NaiveDate::from_ymd_opt(year, month, day)
+    }
+
+    /// Parses a time of day or a duration. Returns true for a
+    /// time of day.
+    fn parse_time(&self, text: &str) -> Option<(Duration, bool)> {
+        let lower = text.to_lowercase();
+        let (text, pm) = if let Some(v) = lower
+            .strip_suffix("am")
+            .or_else(|| lower.strip_suffix("a.m."))
+        {
+            (v.trim_end(), Some(false))
+        } else if let Some(v) = lower
+            .strip_suffix("pm")
+            .or_else(|| lower.strip_suffix("p.m."))
+        {
+            (v.trim_end(), Some(true))
+        } else {
+            (lower.as_str(), None)
+        };
+        let (negative, text) = match text.strip_prefix('-') {
+            Some(text) => (true, text),
+            None => (false, text),
+        };
+
+        let parts = text.split(':').collect::<Vec<_>>();
+        let digits = |v: &str| !v.is_empty() && v.chars().all(|c| c.is_ascii_digit());
+        let (hours, minutes, seconds) = match parts.as_slice() {
+            [h, m] if digits(h) && digits(m) && m.len() <= 2 => (*h, *m, None),
+            [h, m, s] if digits(h) && digits(m) && m.len() <= 2 => (*h, *m, Some(*s)),
+            _ => return None,
+        };
+        let mut hours = hours.parse::<i64>().ok()?;
+        let minutes = minutes.parse::<i64>().ok()?;
+        let millis = match seconds {
+            Some(s) => {
+                let (secs, frac) = match s.split_once([self.decimal, '.']) {
+                    Some((secs, frac)) => (secs, frac),
+                    None => (s, ""),
+                };
+                if !digits(secs) || secs.len() > 2 || !frac.chars().all(|c| c.is_ascii_digit()) {
+                    return None;
+                }
+                let frac = format!("0.{}0", frac).parse::<f64>().ok()?;
+                secs.parse::<i64>().ok()? * 1000 + (frac * 1000.0).round() as i64
+            }
+            None => 0,
+        };
+        if minutes >= 60 || millis >= 60_000 {
+            return None;
+        }
+        if let Some(pm) = pm {
+            if negative || !(1..=12).contains(&hours) {
+                return None;
+            }
+            hours = hours % 12 + if pm { 12 } else { 0 };
+        }
+
+        let millis = hours
+            .checked_mul(3600)
+            .and_then(|v| v.checked_add(minutes * 60))
+            .and_then(|v| v.checked_mul(1000))
+            .and_then(|v| v.checked_add(millis))?;
+        let duration = Duration::milliseconds(millis);
+        let clock = !negative && hours < 24;
+        Some((if negative { -duration } else { duration }, clock))
+    }
+
+    /// Parses a date followed by a time of day.
+    fn parse_datetime(&self, text: &str) -> Option<NaiveDateTime> {
+        let (date, time) = match text.split_once('T') {
+            Some((date, time)) if date.len() == 10 => (date, time),
+            _ => text.split_once(char::is_whitespace)?,
+        };
+        let date = self.parse_date(date.trim())?;
+        match self.parse_time(time.trim())? {
+            (time, true) => Some(date.and_time(NaiveTime::MIN) + time),
+            (_, false) => None,
+        }
+    }
+}
+
+/// Parses the text with a [ValueParser] for the locale.
+pub fn parse_value(text: &str, locale: Locale) -> ParsedValue {
+    ValueParser::new(locale).parse(text)
+}
+
+/// Two digit years are in 1930..2029.
+fn expand_year(v: &str) -> Option<i32> {
+    let year = v.parse::<i32>().ok()?;
+    match v.len() {
+        1 | 2 if year < 30 => Some(2000 + year),
+        1 | 2 => Some(1900 + year),
+        4 => Some(year),
+        _ => None,
+    }
+}
+
+/// Order of the date parts of a format.
+fn date_order(parts: &[FormatPart]) -> Option<DateOrder> {
+    let order = parts
+        .iter()
+        .filter_map(|v| match v.part_type() {
+            FormatPartType::Day => Some('D'),
+            FormatPartType::Month => Some('M'),
+            FormatPartType::Year => Some('Y'),
+            _ => None,
+        })
+        .collect::<String>();
+    match order.as_str() {
+        "DMY" | "DM" => Some(DateOrder::Dmy),
+        "MDY" | "MD" => Some(DateOrder::Mdy),
+        "YMD" => Some(DateOrder::Ymd),
+        _ => None,
+    }
+}
+
+/// Locale of a value format.
+pub(crate) fn format_locale<T: ValueFormatTrait>(format: &T) -> Option<Locale> {
+    let language = format.attrmap().attr("number:language")?;
+    match format.attrmap().attr("number:country") {
+        Some(country) => format!("{}-{}", language, country).parse().ok(),
+        None => language.parse().ok(),
+    }
+}
+
+/// ISO code of the currency of a country.
+fn currency_code(country: &str) -> Option<&'static str> {
+    match country {
+        "AT" | "BE" | "DE" | "ES" | "FI" | "FR" | "GR" | "IE" | "IT" | "LU" | "NL" | "PT" => {
+            Some("EUR")
+        }
+        "BR" => Some("BRL"),
+        "CH" => Some("CHF"),
+        "CN" => Some("CNY"),
+        "GB" => Some("GBP"),
+        "JP" => Some("JPY"),
+        "PL" => Some("PLN"),
+        "SE" => Some("SEK"),
+        "US" => Some("USD"),
+        _ => None,
+    }
+}
+
+/// Localized words for TRUE and FALSE.
+fn boolean_words(language: &str) -> Option<(&'static str, &'static str)> {
+    match language {
+        "de" => Some(("WAHR", "FALSCH")),
+        "es" => Some(("VERDADERO", "FALSO")),
+        "fr" => Some(("VRAI", "FAUX")),
+        "it" => Some(("VERO", "FALSO")),
+        "nl" => Some(("WAAR", "ONWAAR")),
+        "pl" => Some(("PRAWDA", "FAŁSZ")),
+        "pt" => Some(("VERDADEIRO", "FALSO")),
+        "sv" => Some(("SANT", "FALSKT")),
+        _ => None,
+    }
+}
@@ -1,13 +1,100 @@
 //!
 //! Defines localized versions for all default formats.
 //!
+//! The built-in locales can be activated via feature-flags. Additional
+//! locales can be added with [register_locale].
+//!
+//! ```
+//! use icu_locid::{locale, Locale};
+//! use spreadsheet_ods::defaultstyles::DefaultFormat;
+//! use spreadsheet_ods::locale::{register_locale, LocalizedValueFormat};
+//! use spreadsheet_ods::{
+//!     ValueFormatBoolean, ValueFormatCurrency, ValueFormatDateTime, ValueFormatNumber,
+//!     ValueFormatPercentage, ValueFormatTimeDuration, WorkBook,
+//! };
+//!
+//! struct LocaleDeLu;
+//!
+//! impl LocalizedValueFormat for LocaleDeLu {
+//!     fn locale(&self) -> Locale {
+//!         locale!("de_LU")
+//!     }
+//!     fn decimal_separator(&self) -> char {
+//!         ','
+//!     }
+//!     fn grouping_separator(&self) -> char {
+//!         '.'
+//!     }
+//!     fn boolean_format(&self) -> ValueFormatBoolean {
+//!         let mut v = ValueFormatBoolean::new_localized(DefaultFormat::bool(), self.locale());
+//!         v.part_boolean().build();
+//!         v
+//!     }
+//!     // ...
+//! #     fn number_format(&self) -> ValueFormatNumber {
+//! #         ValueFormatNumber::new_localized(DefaultFormat::number(), self.locale())
+//! #     }
+//! #     fn percentage_format(&self) -> ValueFormatPercentage {
+//! #         ValueFormatPercentage::new_localized(DefaultFormat::percent(), self.locale())
+//! #     }
+//! #     fn currency_format(&self) -> ValueFormatCurrency {
+//! #         ValueFormatCurrency::new_localized(DefaultFormat::currency(), self.locale())
+//! #     }
+//! #     fn date_format(&self) -> ValueFormatDateTime {
+//! #         ValueFormatDateTime::new_localized(DefaultFormat::date(), self.locale())
+//! #     }
+//! #     fn datetime_format(&self) -> ValueFormatDateTime {
+//! #         ValueFormatDateTime::new_localized(DefaultFormat::datetime(), self.locale())
+//! #     }
+//! #     fn time_of_day_format(&self) -> ValueFormatDateTime {
+//! #         ValueFormatDateTime::new_localized(DefaultFormat::time_of_day(), self.locale())
+//! #     }
+//! #     fn time_interval_format(&self) -> ValueFormatTimeDuration {
+//! #         ValueFormatTimeDuration::new_localized(DefaultFormat::time_interval(), self.locale())
+//! #     }
+//! }
+//!
+//! static LOCALE_DE_LU: LocaleDeLu = LocaleDeLu;
+//!
+//! register_locale(&LOCALE_DE_LU);
+//! let wb = WorkBook::new(locale!("de_LU"));
+//! ```
+//!
+
+pub(crate) use input::format_locale;
+pub use input::{parse_value, ParsedValue, ValueParser};
 
 mod default;
+mod input;
 
 #[cfg(feature = "locale_de_AT")]
 mod de_at;
+#[cfg(feature = "locale_de_CH")]
+mod de_ch;
+#[cfg(feature = "locale_de_DE")]
+mod de_de;
+#[cfg(feature = "locale_en_GB")]
+mod en_gb;
 #[cfg(feature = "locale_en_US")]
 mod en_us;
+#[cfg(feature = "locale_es_ES")]
+mod es_es;
+#[cfg(feature = "locale_fr_FR")]
+mod fr_fr;
+#[cfg(feature = "locale_it_IT")]
+mod it_it;
+#[cfg(feature = "locale_ja_JP")]
+mod ja_jp;
+#[cfg(feature = "locale_nl_NL")]
+mod nl_nl;
+#[cfg(feature = "locale_pl_PL")]
+mod pl_pl;
+#[cfg(feature = "locale_pt_BR")]
+mod pt_br;
+#[cfg(feature = "locale_sv_SE")]
+mod sv_se;
+#[cfg(feature = "locale_zh_CN")]
+mod zh_cn;
 
 use crate::HashMap;
 use crate::{
@@ -16,12 +103,24 @@ use crate::{
 };
 use icu_locid::Locale;
 use lazy_static::lazy_static;
+use std::sync::RwLock;
 
 /// Defines functions that generate the standard formats for various
 /// value types.
-#[allow(dead_code)]
-pub(crate) trait LocalizedValueFormat: Sync {
+///
+/// The formats must use the names from
+/// [DefaultFormat](crate::defaultstyles::DefaultFormat).
+pub trait LocalizedValueFormat: Sync {
+    /// Locale of the formats.
     fn locale(&self) -> Locale;
+    /// Decimal separator. Defaults to '.'.
+    fn decimal_separator(&self) -> char {
+        '.'
+    }
+    /// Grouping separator. Defaults to ','.
+    fn grouping_separator(&self) -> char {
+        ','
+    }
     /// Default boolean format.
     fn boolean_format(&self) -> ValueFormatBoolean;
     /// Default number format.
@@ -41,7 +140,7 @@ pub(crate) trait LocalizedValueFormat: Sync {
 }
 
 lazy_static! {
-    static ref LOCALE_DATA: HashMap<Locale, &'static dyn LocalizedValueFormat> = {
+    static ref LOCALE_DATA: RwLock<HashMap<Locale, &'static dyn LocalizedValueFormat>> = {
         #[allow(unused_mut)]
         let mut lm: HashMap<Locale, &'static dyn LocalizedValueFormat> = HashMap::new();
 
@@ -50,15 +149,92 @@ lazy_static! {
         {
             lm.insert(icu_locid::locale!("de_AT"), &de_at::LOCALE_DE_AT);
         }
+        #[cfg(feature = "locale_de_CH")]
+        {
+            lm.insert(icu_locid::locale!("de_CH"), &de_ch::LOCALE_DE_CH);
+        }
+        #[cfg(feature = "locale_de_DE")]
+        {
+            lm.insert(icu_locid::locale!("de_DE"), &de_de::LOCALE_DE_DE);
+            lm.insert(icu_locid::locale!("de"), &de_de::LOCALE_DE_DE);
+        }
+        #[cfg(feature = "locale_en_GB")]
+        {
+            lm.insert(icu_locid::locale!("en_GB"), &en_gb::LOCALE_EN_GB);
+        }
         #[cfg(feature = "locale_en_US")]
         {
             lm.insert(icu_locid::locale!("en_US"), &en_us::LOCALE_EN_US);
         }
-        lm
+        #[cfg(feature = "locale_es_ES")]
+        {
+            lm.insert(icu_locid::locale!("es_ES"), &es_es::LOCALE_ES_ES);
+            lm.insert(icu_locid::locale!("es"), &es_es::LOCALE_ES_ES);
+        }
+        #[cfg(feature = "locale_fr_FR")]
+        {
+            lm.insert(icu_locid::locale!("fr_FR"), &fr_fr::LOCALE_FR_FR);
+            lm.insert(icu_locid::locale!("fr"), &fr_fr::LOCALE_FR_FR);
+        }
+        #[cfg(feature = "locale_it_IT")]
+        {
+            lm.insert(icu_locid::locale!("it_IT"), &it_it::LOCALE_IT_IT);
+            lm.insert(icu_locid::locale!("it"), &it_it::LOCALE_IT_IT);
+        }
+        #[cfg(feature = "locale_ja_JP")]
+        {
+            lm.insert(icu_locid::locale!("ja_JP"), &ja_jp::LOCALE_JA_JP);
+            lm.insert(icu_locid::locale!("ja"), &ja_jp::LOCALE_JA_JP);
+        }
+        #[cfg(feature = "locale_nl_NL")]
+        {
+            lm.insert(icu_locid::locale!("nl_NL"), &nl_nl::LOCALE_NL_NL);
+            lm.insert(icu_locid::locale!("nl"), &nl_nl::LOCALE_NL_NL);
+        }
+        #[cfg(feature = "locale_pl_PL")]
+        {
+            lm.insert(icu_locid::locale!("pl_PL"), &pl_pl::LOCALE_PL_PL);
+            lm.insert(icu_locid::locale!("pl"), &pl_pl::LOCALE_PL_PL);
+        }
+        #[cfg(feature = "locale_pt_BR")]
+        {
+            lm.insert(icu_locid::locale!("pt_BR"), &pt_br::LOCALE_PT_BR);
+            lm.insert(icu_locid::locale!("pt"), &pt_br::LOCALE_PT_BR);
+        }
+        #[cfg(feature = "locale_sv_SE")]
+        {
+            lm.insert(icu_locid::locale!("sv_SE"), &sv_se::LOCALE_SV_SE);
+            lm.insert(icu_locid::locale!("sv"), &sv_se::LOCALE_SV_SE);
+        }
+        #[cfg(feature = "locale_zh_CN")]
+        {
+            lm.insert(icu_locid::locale!("zh_CN"), &zh_cn::LOCALE_ZH_CN);
+            lm.insert(icu_locid::locale!("zh"), &zh_cn::LOCALE_ZH_CN);
+        }
+        RwLock::new(lm)
     };
 }
 
-/// Returns the localized format or a fallback.
-pub(crate) fn localized_format(locale: Locale) -> Option<&'static dyn LocalizedValueFormat> {
-    LOCALE_DATA.get(&locale).copied()
+/// Registers the formats for a locale. Replaces any existing
+/// formats for the same locale, including the built-in ones.
+///
+/// The first locale registered for a language is used as fallback
+/// for this language too.
+pub fn register_locale(formats: &'static dyn LocalizedValueFormat) {
+    let locale = formats.locale();
+    let mut data = LOCALE_DATA.write().expect("locale data");
+    data.entry(Locale::from(locale.id.language))
+        .or_insert(formats);
+    data.insert(locale, formats);
+}
+
+/// Returns the localized formats for the locale.
+///
+/// If there are none for the exact locale, the formats for the
+/// language alone are used. This maps e.g. fr_CA to fr_FR.
+pub fn localized_format(locale: Locale) -> Option<&'static dyn LocalizedValueFormat> {
+    let data = LOCALE_DATA.read().expect("locale data");
+    data.get(&locale)
+        .or_else(|| data.get(&Locale::from(locale.id.language)))
+        .copied()
 }
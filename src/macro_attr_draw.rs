@@ -218,3 +218,143 @@ macro_rules! draw_mime_type {
         }
     };
 }
+
+macro_rules! draw_fill {
+    ($acc:ident) => {
+        /// The draw:fill attribute specifies the fill style for a graphic object. Graphic objects that are
+        /// not closed, such as a path without a closepath at the end, will not be filled.
+        pub fn set_fill(&mut self, fill: DrawFill) {
+            self.$acc.set_attr("draw:fill", fill.to_string());
+        }
+
+        /// Parses the draw:fill attribute.
+        pub fn fill(&self) -> Result<Option<DrawFill>, OdsError> {
+            DrawFill::parse_attr(self.$acc.attr("draw:fill"))
+        }
+    };
+}
+
+macro_rules! draw_fill_color {
+    ($acc:ident) => {
+        /// The draw:fill-color attribute specifies the color of the fill for a graphic object. It is used
+        /// only if the draw:fill attribute has the value solid.
+        pub fn set_fill_color(&mut self, color: Rgb<u8>) {
+            self.$acc.set_attr("draw:fill-color", color_string(color));
+        }
+
+        /// Parses the draw:fill-color attribute.
+        pub fn fill_color(&self) -> Result<Option<Rgb<u8>>, OdsError> {
+            Rgb::parse_attr(self.$acc.attr("draw:fill-color"))
+        }
+    };
+}
+
+macro_rules! draw_stroke {
+    ($acc:ident) => {
+        /// The draw:stroke attribute specifies the style of the stroke on the current object.
+        pub fn set_stroke(&mut self, stroke: DrawStroke) {
+            self.$acc.set_attr("draw:stroke", stroke.to_string());
+        }
+
+        /// Parses the draw:stroke attribute.
+        pub fn stroke(&self) -> Result<Option<DrawStroke>, OdsError> {
+            DrawStroke::parse_attr(self.$acc.attr("draw:stroke"))
+        }
+    };
+}
+
+macro_rules! draw_textarea_horizontal_align {
+    ($acc:ident) => {
+        /// The draw:textarea-horizontal-align attribute specifies the horizontal text anchor of
+        /// the text of a drawing shape.
+        pub fn set_textarea_horizontal_align(&mut self, align: TextAreaHorizontalAlign) {
+            self.$acc
+                .set_attr("draw:textarea-horizontal-align", align.to_string());
+        }
+
+        /// Parses the draw:textarea-horizontal-align attribute.
+        pub fn textarea_horizontal_align(
+            &self,
+        ) -> Result<Option<TextAreaHorizontalAlign>, OdsError> {
+            TextAreaHorizontalAlign::parse_attr(self.$acc.attr("draw:textarea-horizontal-align"))
+        }
+    };
+}
+
+macro_rules! draw_textarea_vertical_align {
+    ($acc:ident) => {
+        /// The draw:textarea-vertical-align attribute specifies the vertical text anchor of
+        /// the text of a drawing shape.
+        pub fn set_textarea_vertical_align(&mut self, align: TextAreaVerticalAlign) {
+            self.$acc
+                .set_attr("draw:textarea-vertical-align", align.to_string());
+        }
+
+        /// Parses the draw:textarea-vertical-align attribute.
+        pub fn textarea_vertical_align(&self) -> Result<Option<TextAreaVerticalAlign>, OdsError> {
+            TextAreaVerticalAlign::parse_attr(self.$acc.attr("draw:textarea-vertical-align"))
+        }
+    };
+}
+
+macro_rules! draw_points {
+    ($acc:ident) => {
+        /// The draw:points attribute specifies a sequence of points in the coordinate system
+        /// given by the svg:viewBox attribute.
+        pub fn set_draw_points(&mut self, points: &[(i64, i64)]) {
+            let mut buf = String::new();
+            for (x, y) in points {
+                if !buf.is_empty() {
+                    buf.push(' ');
+                }
+                buf.push_str(&format!("{},{}", x, y));
+            }
+            self.$acc.set_attr("draw:points", buf);
+        }
+    };
+}
+
+macro_rules! draw_start_shape {
+    ($acc:ident) => {
+        /// The draw:start-shape attribute specifies the shape to which a connector is connected at
+        /// its start. The value is the draw:id of the shape.
+        pub fn set_draw_start_shape<S: Into<String>>(&mut self, id: S) {
+            self.$acc.set_attr("draw:start-shape", id.into());
+        }
+    };
+}
+
+macro_rules! draw_end_shape {
+    ($acc:ident) => {
+        /// The draw:end-shape attribute specifies the shape to which a connector is connected at
+        /// its end. The value is the draw:id of the shape.
+        pub fn set_draw_end_shape<S: Into<String>>(&mut self, id: S) {
+            self.$acc.set_attr("draw:end-shape", id.into());
+        }
+    };
+}
+
+macro_rules! draw_control {
+    ($acc:ident) => {
+        /// The draw:control attribute specifies the form control that is
+        /// displayed by a <draw:control>. The value is the id of the control.
+        pub fn set_draw_control<S: Into<String>>(&mut self, id: S) {
+            self.$acc.set_attr("draw:control", id.into());
+        }
+
+        /// The draw:control attribute specifies the form control that is
+        /// displayed by a <draw:control>.
+        pub fn draw_control(&self) -> Option<&str> {
+            self.$acc.attr("draw:control")
+        }
+    };
+}
+
+macro_rules! draw_connector_type {
+    ($acc:ident) => {
+        /// The draw:type attribute specifies the routing of a connector.
+        pub fn set_connector_type(&mut self, connector_type: ConnectorType) {
+            self.$acc.set_attr("draw:type", connector_type.to_string());
+        }
+    };
+}
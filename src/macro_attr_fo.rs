@@ -12,6 +12,15 @@ macro_rules! fo_background_color {
             self.$acc
                 .set_attr("fo:background-color", color_string(color));
         }
+
+        /// Parses the fo:background-color attribute.
+        /// The value transparent is returned as None.
+        pub fn background_color(&self) -> Result<Option<Rgb<u8>>, OdsError> {
+            match self.$acc.attr("fo:background-color") {
+                Some("transparent") => Ok(None),
+                v => Rgb::parse_attr(v),
+            }
+        }
     };
 }
 
@@ -23,29 +32,54 @@ macro_rules! fo_border {
                 .set_attr("fo:border", border_string(width, border, color));
         }
 
+        /// Parses the fo:border attribute.
+        pub fn border(&self) -> Result<Option<(Length, Border, Rgb<u8>)>, OdsError> {
+            parse_border(self.$acc.attr("fo:border"))
+        }
+
         /// Border style. See §7.29.4 of XSL
         pub fn set_border_bottom(&mut self, width: Length, border: Border, color: Rgb<u8>) {
             self.$acc
                 .set_attr("fo:border-bottom", border_string(width, border, color));
         }
 
+        /// Parses the fo:border-bottom attribute.
+        pub fn border_bottom(&self) -> Result<Option<(Length, Border, Rgb<u8>)>, OdsError> {
+            parse_border(self.$acc.attr("fo:border-bottom"))
+        }
+
         /// Border style. See §7.29.6 of XSL.
         pub fn set_border_left(&mut self, width: Length, border: Border, color: Rgb<u8>) {
             self.$acc
                 .set_attr("fo:border-left", border_string(width, border, color));
         }
 
+        /// Parses the fo:border-left attribute.
+        pub fn border_left(&self) -> Result<Option<(Length, Border, Rgb<u8>)>, OdsError> {
+            parse_border(self.$acc.attr("fo:border-left"))
+        }
+
         /// Border style. See §7.29.7 of XSL.
         pub fn set_border_right(&mut self, width: Length, border: Border, color: Rgb<u8>) {
             self.$acc
                 .set_attr("fo:border-right", border_string(width, border, color));
         }
 
+        /// Parses the fo:border-right attribute.
+        pub fn border_right(&self) -> Result<Option<(Length, Border, Rgb<u8>)>, OdsError> {
+            parse_border(self.$acc.attr("fo:border-right"))
+        }
+
         /// Border style. See §7.29.10 of XSL.
         pub fn set_border_top(&mut self, width: Length, border: Border, color: Rgb<u8>) {
             self.$acc
                 .set_attr("fo:border-top", border_string(width, border, color));
         }
+
+        /// Parses the fo:border-top attribute.
+        pub fn border_top(&self) -> Result<Option<(Length, Border, Rgb<u8>)>, OdsError> {
+            parse_border(self.$acc.attr("fo:border-top"))
+        }
     };
 }
 
@@ -64,6 +98,11 @@ macro_rules! fo_padding {
             self.$acc.set_attr("fo:padding", padding.to_string());
         }
 
+        /// Parses the fo:padding attribute.
+        pub fn padding(&self) -> Result<Option<Length>, OdsError> {
+            Length::parse_attr(self.$acc.attr("fo:padding"))
+        }
+
         /// Padding. See §7.7.36 of XSL.
         ///
         /// The fo:padding-bottom attribute is usable with the following elements:
@@ -77,6 +116,11 @@ macro_rules! fo_padding {
             self.$acc.set_attr("fo:padding-bottom", padding.to_string());
         }
 
+        /// Parses the fo:padding-bottom attribute.
+        pub fn padding_bottom(&self) -> Result<Option<Length>, OdsError> {
+            Length::parse_attr(self.$acc.attr("fo:padding-bottom"))
+        }
+
         /// Padding. See §7.7.37 of XSL.
         ///
         /// The fo:padding-left attribute is usable with the following elements:
@@ -90,6 +134,11 @@ macro_rules! fo_padding {
             self.$acc.set_attr("fo:padding-left", padding.to_string());
         }
 
+        /// Parses the fo:padding-left attribute.
+        pub fn padding_left(&self) -> Result<Option<Length>, OdsError> {
+            Length::parse_attr(self.$acc.attr("fo:padding-left"))
+        }
+
         /// Padding. See §7.7.38 of XSL.
         ///
         /// The fo:padding-right attribute is usable with the following elements:
@@ -103,6 +152,11 @@ macro_rules! fo_padding {
             self.$acc.set_attr("fo:padding-right", padding.to_string());
         }
 
+        /// Parses the fo:padding-right attribute.
+        pub fn padding_right(&self) -> Result<Option<Length>, OdsError> {
+            Length::parse_attr(self.$acc.attr("fo:padding-right"))
+        }
+
         /// Padding. See §7.7.35 of XSL.
         ///
         /// The fo:padding-top attribute is usable with the following elements:
@@ -115,6 +169,11 @@ macro_rules! fo_padding {
             assert!(padding.is_positive());
             self.$acc.set_attr("fo:padding-top", padding.to_string());
         }
+
+        /// Parses the fo:padding-top attribute.
+        pub fn padding_top(&self) -> Result<Option<Length>, OdsError> {
+            Length::parse_attr(self.$acc.attr("fo:padding-top"))
+        }
     };
 }
 
@@ -127,6 +186,11 @@ macro_rules! fo_wrap_option {
         pub fn set_wrap_option(&mut self, wrap: WrapOption) {
             self.$acc.set_attr("fo:wrap-option", wrap.to_string());
         }
+
+        /// Parses the fo:wrap-option attribute.
+        pub fn wrap_option(&self) -> Result<Option<WrapOption>, OdsError> {
+            WrapOption::parse_attr(self.$acc.attr("fo:wrap-option"))
+        }
     };
 }
 
@@ -153,6 +217,11 @@ macro_rules! fo_border_line_width {
             );
         }
 
+        /// Parses the style:border-line-width attribute.
+        pub fn border_line_width(&self) -> Result<Option<(Length, Length, Length)>, OdsError> {
+            parse_border_line_width(self.$acc.attr("style:border-line-width"))
+        }
+
         /// The style:border-line-width-bottom attribute specifies the widths of the bottom border
         /// for borders defined by the FO border properties (see 20.183) if the property for the bottom border
         /// has the value double.
@@ -179,6 +248,13 @@ macro_rules! fo_border_line_width {
             );
         }
 
+        /// Parses the style:border-line-width-bottom attribute.
+        pub fn border_line_width_bottom(
+            &self,
+        ) -> Result<Option<(Length, Length, Length)>, OdsError> {
+            parse_border_line_width(self.$acc.attr("style:border-line-width-bottom"))
+        }
+
         /// The style:border-line-width-left attribute specifies the widths of the left border for
         /// borders defined by the FO border properties (see 20.183) if the property for the left border has the
         /// value double.
@@ -205,6 +281,11 @@ macro_rules! fo_border_line_width {
             );
         }
 
+        /// Parses the style:border-line-width-left attribute.
+        pub fn border_line_width_left(&self) -> Result<Option<(Length, Length, Length)>, OdsError> {
+            parse_border_line_width(self.$acc.attr("style:border-line-width-left"))
+        }
+
         /// The style:border-line-width-right attribute specifies the widths of the right border for
         /// borders defined by the FO border properties (see 20.183) if the property for the right border has
         /// the value double.
@@ -231,6 +312,13 @@ macro_rules! fo_border_line_width {
             );
         }
 
+        /// Parses the style:border-line-width-right attribute.
+        pub fn border_line_width_right(
+            &self,
+        ) -> Result<Option<(Length, Length, Length)>, OdsError> {
+            parse_border_line_width(self.$acc.attr("style:border-line-width-right"))
+        }
+
         /// The style:border-line-width-top attribute specifies the widths of the top border for
         /// borders defined by the FO border properties (see 20.183) if the property for the top border has the
         /// value double.
@@ -251,6 +339,11 @@ macro_rules! fo_border_line_width {
                 border_line_width_string(inner, spacing, outer),
             );
         }
+
+        /// Parses the style:border-line-width-top attribute.
+        pub fn border_line_width_top(&self) -> Result<Option<(Length, Length, Length)>, OdsError> {
+            parse_border_line_width(self.$acc.attr("style:border-line-width-top"))
+        }
     };
 }
 
@@ -264,6 +357,11 @@ macro_rules! fo_break {
             self.$acc.set_attr("fo:break-before", pagebreak.to_string());
         }
 
+        /// Parses the fo:break-before attribute.
+        pub fn break_before(&self) -> Result<Option<PageBreak>, OdsError> {
+            PageBreak::parse_attr(self.$acc.attr("fo:break-before"))
+        }
+
         /// See §7.19.1 of XSL. The values odd-page and even-page are not supported.
         /// This attribute shall not be used at the same time as fo:break-before.
         /// In the OpenDocument XSL-compatible namespace, the fo:break-after attribute does not
@@ -271,6 +369,11 @@ macro_rules! fo_break {
         pub fn set_break_after(&mut self, pagebreak: PageBreak) {
             self.$acc.set_attr("fo:break-after", pagebreak.to_string());
         }
+
+        /// Parses the fo:break-after attribute.
+        pub fn break_after(&self) -> Result<Option<PageBreak>, OdsError> {
+            PageBreak::parse_attr(self.$acc.attr("fo:break-after"))
+        }
     };
 }
 
@@ -282,6 +385,11 @@ macro_rules! fo_hyphenation {
                 .set_attr("fo:hyphenation-keep", hyphenation.to_string());
         }
 
+        /// Parses the fo:hyphenation-keep attribute.
+        pub fn hyphenation_keep(&self) -> Result<Option<Hyphenation>, OdsError> {
+            Hyphenation::parse_attr(self.$acc.attr("fo:hyphenation-keep"))
+        }
+
         /// See §7.15.2 of XSL.
         /// The defined values for the fo:hyphenation-ladder-count attribute are:
         /// * no-limit:
@@ -290,6 +398,11 @@ macro_rules! fo_hyphenation {
             self.$acc
                 .set_attr("fo:hyphenation-ladder-count", hyphenation.to_string());
         }
+
+        /// Parses the fo:hyphenation-ladder-count attribute.
+        pub fn hyphenation_ladder_count(&self) -> Result<Option<HyphenationLadderCount>, OdsError> {
+            HyphenationLadderCount::parse_attr(self.$acc.attr("fo:hyphenation-ladder-count"))
+        }
     };
 }
 
@@ -306,6 +419,11 @@ macro_rules! fo_keep_together {
             self.$acc
                 .set_attr("fo:keep-together", keep_together.to_string());
         }
+
+        /// Parses the fo:keep-together attribute.
+        pub fn keep_together(&self) -> Result<Option<TextKeep>, OdsError> {
+            TextKeep::parse_attr(self.$acc.attr("fo:keep-together"))
+        }
     };
 }
 
@@ -318,6 +436,11 @@ macro_rules! fo_keep_with_next {
             self.$acc
                 .set_attr("fo:keep-with-next", keep_with_next.to_string());
         }
+
+        /// Parses the fo:keep-with-next attribute.
+        pub fn keep_with_next(&self) -> Result<Option<TextKeep>, OdsError> {
+            TextKeep::parse_attr(self.$acc.attr("fo:keep-with-next"))
+        }
     };
 }
 
@@ -342,6 +465,11 @@ macro_rules! fo_line_height {
             self.$acc
                 .set_attr("fo:line-height", line_height.to_string());
         }
+
+        /// Parses the fo:line-height attribute.
+        pub fn line_height(&self) -> Result<Option<LineHeight>, OdsError> {
+            LineHeight::parse_attr(self.$acc.attr("fo:line-height"))
+        }
     };
 }
 
@@ -362,6 +490,11 @@ macro_rules! fo_margin {
             self.$acc.set_attr("fo:margin", margin.to_string());
         }
 
+        /// Parses the fo:margin attribute.
+        pub fn margin(&self) -> Result<Option<Margin>, OdsError> {
+            Margin::parse_attr(self.$acc.attr("fo:margin"))
+        }
+
         /// See §7.10.2 of XSL.
         /// If this attribute is contained in a style:paragraph-properties 17.6 element, its value may
         /// be a percentage that refers to the corresponding margin of a parent style.
@@ -379,6 +512,11 @@ macro_rules! fo_margin {
             self.$acc.set_attr("fo:margin-bottom", margin.to_string());
         }
 
+        /// Parses the fo:margin-bottom attribute.
+        pub fn margin_bottom(&self) -> Result<Option<Margin>, OdsError> {
+            Margin::parse_attr(self.$acc.attr("fo:margin-bottom"))
+        }
+
         /// See §7.10.3 of XSL.
         /// If this attribute is contained in a style:paragraph-properties 17.6 element, its value may
         /// be a percentage that refers to the corresponding margin of a parent style.
@@ -397,6 +535,11 @@ macro_rules! fo_margin {
             self.$acc.set_attr("fo:margin-left", margin.to_string());
         }
 
+        /// Parses the fo:margin-left attribute.
+        pub fn margin_left(&self) -> Result<Option<Margin>, OdsError> {
+            Margin::parse_attr(self.$acc.attr("fo:margin-left"))
+        }
+
         /// See §7.10.4 of XSL.
         /// If this attribute is contained in a style:paragraph-properties 17.6 element, its value may
         /// be a percentage that refers to the corresponding margin of a parent style.
@@ -415,6 +558,11 @@ macro_rules! fo_margin {
             self.$acc.set_attr("fo:margin-right", margin.to_string());
         }
 
+        /// Parses the fo:margin-right attribute.
+        pub fn margin_right(&self) -> Result<Option<Margin>, OdsError> {
+            Margin::parse_attr(self.$acc.attr("fo:margin-right"))
+        }
+
         /// See §7.10.1 of XSL.
         /// If this attribute is contained in a style:paragraph-properties 17.6 element, its value may
         /// be a percentage that refers to the corresponding margin of a parent style.
@@ -431,6 +579,11 @@ macro_rules! fo_margin {
             assert!(margin.is_positive());
             self.$acc.set_attr("fo:margin-top", margin.to_string());
         }
+
+        /// Parses the fo:margin-top attribute.
+        pub fn margin_top(&self) -> Result<Option<Margin>, OdsError> {
+            Margin::parse_attr(self.$acc.attr("fo:margin-top"))
+        }
     };
 }
 
@@ -443,6 +596,11 @@ macro_rules! fo_orphans {
         pub fn set_orphans(&mut self, orphans: u32) {
             self.$acc.set_attr("fo:orphans", orphans.to_string());
         }
+
+        /// Parses the fo:orphans attribute.
+        pub fn orphans(&self) -> Result<Option<u32>, OdsError> {
+            u32::parse_attr(self.$acc.attr("fo:orphans"))
+        }
     };
 }
 
@@ -462,6 +620,11 @@ macro_rules! fo_text_align {
         pub fn set_text_align(&mut self, align: TextAlign) {
             self.$acc.set_attr("fo:text-align", align.to_string());
         }
+
+        /// Parses the fo:text-align attribute.
+        pub fn text_align(&self) -> Result<Option<TextAlign>, OdsError> {
+            TextAlign::parse_attr(self.$acc.attr("fo:text-align"))
+        }
     };
 }
 
@@ -476,6 +639,11 @@ macro_rules! fo_text_align_last {
         pub fn set_text_align_last(&mut self, align: TextAlignLast) {
             self.$acc.set_attr("fo:text-align-last", align.to_string());
         }
+
+        /// Parses the fo:text-align-last attribute.
+        pub fn text_align_last(&self) -> Result<Option<TextAlignLast>, OdsError> {
+            TextAlignLast::parse_attr(self.$acc.attr("fo:text-align-last"))
+        }
     };
 }
 
@@ -494,6 +662,11 @@ macro_rules! fo_text_indent {
         pub fn set_text_indent(&mut self, indent: Indent) {
             self.$acc.set_attr("fo:text-indent", indent.to_string());
         }
+
+        /// Parses the fo:text-indent attribute.
+        pub fn text_indent(&self) -> Result<Option<Indent>, OdsError> {
+            Indent::parse_attr(self.$acc.attr("fo:text-indent"))
+        }
     };
 }
 
@@ -512,6 +685,11 @@ macro_rules! fo_widows {
         pub fn set_widows(&mut self, num: u32) {
             self.$acc.set_attr("fo:widows", num.to_string());
         }
+
+        /// Parses the fo:widows attribute.
+        pub fn widows(&self) -> Result<Option<u32>, OdsError> {
+            u32::parse_attr(self.$acc.attr("fo:widows"))
+        }
     };
 }
 
@@ -523,6 +701,11 @@ macro_rules! fo_color {
         pub fn set_color(&mut self, color: Rgb<u8>) {
             self.$acc.set_attr("fo:color", color_string(color));
         }
+
+        /// Parses the fo:color attribute.
+        pub fn color(&self) -> Result<Option<Rgb<u8>>, OdsError> {
+            Rgb::parse_attr(self.$acc.attr("fo:color"))
+        }
     };
 }
 
@@ -571,6 +754,11 @@ macro_rules! fo_font_size {
             assert!(size.is_positive());
             self.$acc.set_attr("fo:font-size", size.to_string());
         }
+
+        /// Parses the fo:font-size attribute.
+        pub fn font_size(&self) -> Result<Option<FontSize>, OdsError> {
+            FontSize::parse_attr(self.$acc.attr("fo:font-size"))
+        }
     };
 }
 macro_rules! fo_font_size_rel {
@@ -582,6 +770,11 @@ macro_rules! fo_font_size_rel {
         pub fn set_font_size_rel(&mut self, size: FontSize) {
             self.$acc.set_attr("fo:font-size-rel", size.to_string());
         }
+
+        /// Parses the fo:font-size-rel attribute.
+        pub fn font_size_rel(&self) -> Result<Option<FontSize>, OdsError> {
+            FontSize::parse_attr(self.$acc.attr("fo:font-size-rel"))
+        }
     };
 }
 
@@ -593,6 +786,11 @@ macro_rules! fo_font_style {
             self.$acc.set_attr("fo:font-style", style.to_string());
         }
 
+        /// Parses the fo:font-style attribute.
+        pub fn font_style(&self) -> Result<Option<FontStyle>, OdsError> {
+            FontStyle::parse_attr(self.$acc.attr("fo:font-style"))
+        }
+
         /// Set the font-style to italic.
         pub fn set_font_italic(&mut self) {
             self.$acc.set_attr("fo:font-style", "italic".to_string());
@@ -608,6 +806,11 @@ macro_rules! fo_font_weight {
             self.$acc.set_attr("fo:font-weight", weight.to_string());
         }
 
+        /// Parses the fo:font-weight attribute.
+        pub fn font_weight(&self) -> Result<Option<FontWeight>, OdsError> {
+            FontWeight::parse_attr(self.$acc.attr("fo:font-weight"))
+        }
+
         /// Sets the font-weight to bold. See set_font_weight.
         pub fn set_font_bold(&mut self) {
             self.$acc
@@ -622,6 +825,11 @@ macro_rules! fo_font_variant {
         pub fn set_font_variant(&mut self, var: FontVariant) {
             self.$acc.set_attr("fo:font-variant", var.to_string());
         }
+
+        /// Parses the fo:font-variant attribute.
+        pub fn font_variant(&self) -> Result<Option<FontVariant>, OdsError> {
+            FontVariant::parse_attr(self.$acc.attr("fo:font-variant"))
+        }
     };
 }
 
@@ -646,6 +854,11 @@ macro_rules! fo_hyphenate {
         pub fn set_hyphenate(&mut self, hyphenate: bool) {
             self.$acc.set_attr("fo:hyphenate", hyphenate.to_string());
         }
+
+        /// Parses the fo:hyphenate attribute.
+        pub fn hyphenate(&self) -> Result<Option<bool>, OdsError> {
+            bool::parse_attr(self.$acc.attr("fo:hyphenate"))
+        }
     };
 }
 
@@ -656,6 +869,11 @@ macro_rules! fo_hyphenation_push_char_count {
             self.$acc
                 .set_attr("fo:hyphenation-push-char-count", count.to_string());
         }
+
+        /// Parses the fo:hyphenation-push-char-count attribute.
+        pub fn hyphenation_push_char_count(&self) -> Result<Option<u32>, OdsError> {
+            u32::parse_attr(self.$acc.attr("fo:hyphenation-push-char-count"))
+        }
     };
 }
 
@@ -666,6 +884,11 @@ macro_rules! fo_hyphenation_remain_char_count {
             self.$acc
                 .set_attr("fo:hyphenation-remain-char-count", count.to_string());
         }
+
+        /// Parses the fo:hyphenation-remain-char-count attribute.
+        pub fn hyphenation_remain_char_count(&self) -> Result<Option<u32>, OdsError> {
+            u32::parse_attr(self.$acc.attr("fo:hyphenation-remain-char-count"))
+        }
     };
 }
 
@@ -676,6 +899,11 @@ macro_rules! fo_letter_spacing {
         pub fn set_letter_spacing(&mut self, spacing: LetterSpacing) {
             self.$acc.set_attr("fo:letter-spacing", spacing.to_string());
         }
+
+        /// Parses the fo:letter-spacing attribute.
+        pub fn letter_spacing(&self) -> Result<Option<LetterSpacing>, OdsError> {
+            LetterSpacing::parse_attr(self.$acc.attr("fo:letter-spacing"))
+        }
     };
 }
 
@@ -707,6 +935,11 @@ macro_rules! fo_text_transform {
         pub fn set_text_transform(&mut self, trans: TextTransform) {
             self.$acc.set_attr("fo:text-transform", trans.to_string());
         }
+
+        /// Parses the fo:text-transform attribute.
+        pub fn text_transform(&self) -> Result<Option<TextTransform>, OdsError> {
+            TextTransform::parse_attr(self.$acc.attr("fo:text-transform"))
+        }
     };
 }
 
@@ -716,6 +949,11 @@ macro_rules! fo_min_height {
         pub fn set_min_height(&mut self, height: LengthPercent) {
             self.$acc.set_attr("fo:min-height", height.to_string());
         }
+
+        /// Parses the fo:min-height attribute.
+        pub fn min_height(&self) -> Result<Option<LengthPercent>, OdsError> {
+            LengthPercent::parse_attr(self.$acc.attr("fo:min-height"))
+        }
     };
 }
 
@@ -726,6 +964,11 @@ macro_rules! fo_page_height {
             self.style_mut()
                 .set_attr("fo:page-height", height.to_string());
         }
+
+        /// Parses the fo:page-height attribute.
+        pub fn page_height(&self) -> Result<Option<Length>, OdsError> {
+            Length::parse_attr(self.$acc.attr("fo:page-height"))
+        }
     };
 }
 
@@ -736,5 +979,10 @@ macro_rules! fo_page_width {
             self.style_mut()
                 .set_attr("fo:page-width", width.to_string());
         }
+
+        /// Parses the fo:page-width attribute.
+        pub fn page_width(&self) -> Result<Option<Length>, OdsError> {
+            Length::parse_attr(self.$acc.attr("fo:page-width"))
+        }
     };
 }
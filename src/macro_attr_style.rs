@@ -72,6 +72,11 @@ macro_rules! style_cell_protect {
             self.$acc
                 .set_attr("style:cell-protect", protect.to_string());
         }
+
+        /// Parses the style:cell-protect attribute.
+        pub fn cell_protect(&self) -> Result<Option<CellProtect>, OdsError> {
+            CellProtect::parse_attr(self.$acc.attr("style:cell-protect"))
+        }
     };
 }
 
@@ -98,6 +103,11 @@ macro_rules! style_diagonal {
                 .set_attr("style:diagonal-bl-tr", border_string(width, border, color));
         }
 
+        /// Parses the style:diagonal-bl-tr attribute.
+        pub fn diagonal_bl_tr(&self) -> Result<Option<(Length, Border, Rgb<u8>)>, OdsError> {
+            parse_border(self.$acc.attr("style:diagonal-bl-tr"))
+        }
+
         // style:diagonal-bl-tr-widths 20.260,
         /// The style:diagonal-bl-tr-widths attribute specifies the width between a double line
         /// border to use for a bottom-left to top-right diagonal in a spreadsheet cell.
@@ -108,6 +118,11 @@ macro_rules! style_diagonal {
             );
         }
 
+        /// Parses the style:diagonal-bl-tr-widths attribute.
+        pub fn diagonal_bl_tr_widths(&self) -> Result<Option<(Length, Length, Length)>, OdsError> {
+            parse_border_line_width(self.$acc.attr("style:diagonal-bl-tr-widths"))
+        }
+
         // style:diagonal-tl-br 20.261,
         /// The style:diagonal-tl-br attribute specifies the style of border to use for a left-top to
         /// bottom-right diagonal in a spreadsheet cell.
@@ -116,6 +131,11 @@ macro_rules! style_diagonal {
                 .set_attr("style:diagonal-tl-br", border_string(width, border, color));
         }
 
+        /// Parses the style:diagonal-tl-br attribute.
+        pub fn diagonal_tl_br(&self) -> Result<Option<(Length, Border, Rgb<u8>)>, OdsError> {
+            parse_border(self.$acc.attr("style:diagonal-tl-br"))
+        }
+
         // style:diagonal-tl-br-widths 20.262,
         /// The style:diagonal-tl-br-widths attribute specifies the width between a double line
         /// border to use for a top-left to bottom-right diagonal in a spreadsheet cell.
@@ -125,6 +145,11 @@ macro_rules! style_diagonal {
                 border_line_width_string(inner, spacing, outer),
             );
         }
+
+        /// Parses the style:diagonal-tl-br-widths attribute.
+        pub fn diagonal_tl_br_widths(&self) -> Result<Option<(Length, Length, Length)>, OdsError> {
+            parse_border_line_width(self.$acc.attr("style:diagonal-tl-br-widths"))
+        }
     };
 }
 
@@ -141,6 +166,11 @@ macro_rules! style_direction {
         pub fn set_direction(&mut self, direction: WritingDirection) {
             self.$acc.set_attr("style:direction", direction.to_string());
         }
+
+        /// Parses the style:direction attribute.
+        pub fn direction(&self) -> Result<Option<WritingDirection>, OdsError> {
+            WritingDirection::parse_attr(self.$acc.attr("style:direction"))
+        }
     };
 }
 
@@ -159,6 +189,11 @@ macro_rules! style_glyph_orientation_vertical {
                 glyph_orientation.to_string(),
             );
         }
+
+        /// Parses the style:glyph-orientation-vertical attribute.
+        pub fn glyph_orientation_vertical(&self) -> Result<Option<GlyphOrientation>, OdsError> {
+            GlyphOrientation::parse_attr(self.$acc.attr("style:glyph-orientation-vertical"))
+        }
     };
 }
 
@@ -172,6 +207,11 @@ macro_rules! style_print_content {
         pub fn set_print_content(&mut self, print: bool) {
             self.$acc.set_attr("style:print-content", print.to_string());
         }
+
+        /// Parses the style:print-content attribute.
+        pub fn print_content(&self) -> Result<Option<bool>, OdsError> {
+            bool::parse_attr(self.$acc.attr("style:print-content"))
+        }
     };
 }
 
@@ -190,6 +230,11 @@ macro_rules! style_repeat_content {
             self.$acc
                 .set_attr("style:repeat-content", print.to_string());
         }
+
+        /// Parses the style:repeat-content attribute.
+        pub fn repeat_content(&self) -> Result<Option<bool>, OdsError> {
+            bool::parse_attr(self.$acc.attr("style:repeat-content"))
+        }
     };
 }
 
@@ -203,6 +248,11 @@ macro_rules! style_rotation_align {
             self.$acc
                 .set_attr("style:rotation-align", align.to_string());
         }
+
+        /// Parses the style:rotation-align attribute.
+        pub fn rotation_align(&self) -> Result<Option<RotationAlign>, OdsError> {
+            RotationAlign::parse_attr(self.$acc.attr("style:rotation-align"))
+        }
     };
 }
 
@@ -219,6 +269,11 @@ macro_rules! style_rotation_scale {
             self.$acc
                 .set_attr("style:text-rotation-scale", scale.to_string());
         }
+
+        /// Parses the style:text-rotation-scale attribute.
+        pub fn rotation_scale(&self) -> Result<Option<RotationScale>, OdsError> {
+            RotationScale::parse_attr(self.$acc.attr("style:text-rotation-scale"))
+        }
     };
 }
 
@@ -230,6 +285,11 @@ macro_rules! style_rotation_angle {
             self.$acc
                 .set_attr("style:rotation-angle", angle.to_string());
         }
+
+        /// Parses the style:rotation-angle attribute.
+        pub fn rotation_angle(&self) -> Result<Option<Angle>, OdsError> {
+            Angle::parse_attr(self.$acc.attr("style:rotation-angle"))
+        }
     };
 }
 
@@ -280,6 +340,11 @@ macro_rules! style_shrink_to_fit {
             self.$acc
                 .set_attr("style:shrink-to-fit", shrink.to_string());
         }
+
+        /// Parses the style:shrink-to-fit attribute.
+        pub fn shrink_to_fit(&self) -> Result<Option<bool>, OdsError> {
+            bool::parse_attr(self.$acc.attr("style:shrink-to-fit"))
+        }
     };
 }
 
@@ -296,6 +361,11 @@ macro_rules! style_text_align_source {
             self.cellstyle
                 .set_attr("style:text-align-source", align.to_string());
         }
+
+        /// Parses the style:text-align-source attribute.
+        pub fn text_align_source(&self) -> Result<Option<TextAlignSource>, OdsError> {
+            TextAlignSource::parse_attr(self.$acc.attr("style:text-align-source"))
+        }
     };
 }
 
@@ -317,6 +387,11 @@ macro_rules! style_vertical_align {
             self.cellstyle
                 .set_attr("style:vertical-align", align.to_string());
         }
+
+        /// Parses the style:vertical-align attribute.
+        pub fn vertical_align(&self) -> Result<Option<CellAlignVertical>, OdsError> {
+            CellAlignVertical::parse_attr(self.$acc.attr("style:vertical-align"))
+        }
     };
 }
 
@@ -335,6 +410,11 @@ macro_rules! style_auto_text_indent {
             self.$acc
                 .set_attr("style:auto-text-indent", indent.to_string());
         }
+
+        /// Parses the style:auto-text-indent attribute.
+        pub fn auto_text_indent(&self) -> Result<Option<bool>, OdsError> {
+            bool::parse_attr(self.$acc.attr("style:auto-text-indent"))
+        }
     };
 }
 
@@ -348,6 +428,11 @@ macro_rules! style_background_transparency {
             self.$acc
                 .set_attr("style:background-transparency", percent.to_string());
         }
+
+        /// Parses the style:background-transparency attribute.
+        pub fn background_transparency(&self) -> Result<Option<Percent>, OdsError> {
+            Percent::parse_attr(self.$acc.attr("style:background-transparency"))
+        }
     };
 }
 
@@ -370,6 +455,11 @@ macro_rules! style_contextual_spacing {
             self.$acc
                 .set_attr("style:contextual-spacing", spacing.to_string());
         }
+
+        /// Parses the style:contextual-spacing attribute.
+        pub fn contextual_spacing(&self) -> Result<Option<bool>, OdsError> {
+            bool::parse_attr(self.$acc.attr("style:contextual-spacing"))
+        }
     };
 }
 
@@ -389,6 +479,11 @@ macro_rules! style_font_independent_line_spacing {
             self.$acc
                 .set_attr("style:font-independent-line-spacing", spacing.to_string());
         }
+
+        /// Parses the style:font-independent-line-spacing attribute.
+        pub fn font_independent_line_spacing(&self) -> Result<Option<bool>, OdsError> {
+            bool::parse_attr(self.$acc.attr("style:font-independent-line-spacing"))
+        }
     };
 }
 
@@ -428,6 +523,11 @@ macro_rules! style_join_border {
         pub fn set_join_border(&mut self, join: bool) {
             self.$acc.set_attr("style:join-border", join.to_string());
         }
+
+        /// Parses the style:join-border attribute.
+        pub fn join_border(&self) -> Result<Option<bool>, OdsError> {
+            bool::parse_attr(self.$acc.attr("style:join-border"))
+        }
     };
 }
 
@@ -449,6 +549,11 @@ macro_rules! style_justify_single_word {
             self.$acc
                 .set_attr("style:justify-single-word", justify.to_string());
         }
+
+        /// Parses the style:justify-single-word attribute.
+        pub fn justify_single_word(&self) -> Result<Option<bool>, OdsError> {
+            bool::parse_attr(self.$acc.attr("style:justify-single-word"))
+        }
     };
 }
 
@@ -466,6 +571,11 @@ macro_rules! style_line_break {
             self.$acc
                 .set_attr("style:line-break", linebreak.to_string());
         }
+
+        /// Parses the style:line-break attribute.
+        pub fn line_break(&self) -> Result<Option<LineBreak>, OdsError> {
+            LineBreak::parse_attr(self.$acc.attr("style:line-break"))
+        }
     };
 }
 
@@ -482,6 +592,11 @@ macro_rules! style_line_height_at_least {
             self.$acc
                 .set_attr("style:line-height-at-least", height.to_string());
         }
+
+        /// Parses the style:line-height-at-least attribute.
+        pub fn line_height_at_least(&self) -> Result<Option<Length>, OdsError> {
+            Length::parse_attr(self.$acc.attr("style:line-height-at-least"))
+        }
     };
 }
 
@@ -496,6 +611,11 @@ macro_rules! style_line_spacing {
             self.$acc
                 .set_attr("style:line-spacing", spacing.to_string());
         }
+
+        /// Parses the style:line-spacing attribute.
+        pub fn line_spacing(&self) -> Result<Option<Length>, OdsError> {
+            Length::parse_attr(self.$acc.attr("style:line-spacing"))
+        }
     };
 }
 
@@ -519,6 +639,11 @@ macro_rules! style_page_number {
             self.$acc
                 .set_attr("style:page-number", page_number.to_string());
         }
+
+        /// Parses the style:page-number attribute.
+        pub fn page_number(&self) -> Result<Option<PageNumber>, OdsError> {
+            PageNumber::parse_attr(self.$acc.attr("style:page-number"))
+        }
     };
 }
 
@@ -539,6 +664,11 @@ macro_rules! style_punctuation_wrap {
             self.$acc
                 .set_attr("style:punctuation-wrap", wrap.to_string());
         }
+
+        /// Parses the style:punctuation-wrap attribute.
+        pub fn punctuation_wrap(&self) -> Result<Option<PunctuationWrap>, OdsError> {
+            PunctuationWrap::parse_attr(self.$acc.attr("style:punctuation-wrap"))
+        }
     };
 }
 
@@ -558,6 +688,11 @@ macro_rules! style_register_true {
             self.$acc
                 .set_attr("style:register-true", register.to_string());
         }
+
+        /// Parses the style:register-true attribute.
+        pub fn register_true(&self) -> Result<Option<bool>, OdsError> {
+            bool::parse_attr(self.$acc.attr("style:register-true"))
+        }
     };
 }
 
@@ -578,6 +713,11 @@ macro_rules! style_snap_to_layout_grid {
             self.$acc
                 .set_attr("style:snap-to-layout-grid", snap.to_string());
         }
+
+        /// Parses the style:snap-to-layout-grid attribute.
+        pub fn snap_to_layout_grid(&self) -> Result<Option<bool>, OdsError> {
+            bool::parse_attr(self.$acc.attr("style:snap-to-layout-grid"))
+        }
     };
 }
 
@@ -594,6 +734,11 @@ macro_rules! style_tab_stop_distance {
             self.$acc
                 .set_attr("style:tab-stop-distance", tab.to_string());
         }
+
+        /// Parses the style:tab-stop-distance attribute.
+        pub fn tab_stop_distance(&self) -> Result<Option<Length>, OdsError> {
+            Length::parse_attr(self.$acc.attr("style:tab-stop-distance"))
+        }
     };
 }
 
@@ -613,6 +758,11 @@ macro_rules! style_text_autospace {
             self.$acc
                 .set_attr("style:text-autospace", space.to_string());
         }
+
+        /// Parses the style:text-autospace attribute.
+        pub fn text_autospace(&self) -> Result<Option<TextAutoSpace>, OdsError> {
+            TextAutoSpace::parse_attr(self.$acc.attr("style:text-autospace"))
+        }
     };
 }
 
@@ -636,6 +786,11 @@ macro_rules! style_vertical_align_para {
             self.$acc
                 .set_attr("style:vertical-align", align.to_string());
         }
+
+        /// Parses the style:vertical-align attribute.
+        pub fn vertical_align_para(&self) -> Result<Option<ParaAlignVertical>, OdsError> {
+            ParaAlignVertical::parse_attr(self.$acc.attr("style:vertical-align"))
+        }
     };
 }
 
@@ -649,6 +804,11 @@ macro_rules! style_writing_mode {
             self.$acc
                 .set_attr("style:writing-mode", writing_mode.to_string());
         }
+
+        /// Parses the style:writing-mode attribute.
+        pub fn writing_mode(&self) -> Result<Option<WritingMode>, OdsError> {
+            WritingMode::parse_attr(self.$acc.attr("style:writing-mode"))
+        }
     };
 }
 
@@ -672,6 +832,11 @@ macro_rules! style_writing_mode_automatic {
             self.$acc
                 .set_attr("style:writing-mode-automatic", auto.to_string());
         }
+
+        /// Parses the style:writing-mode-automatic attribute.
+        pub fn writing_mode_automatic(&self) -> Result<Option<bool>, OdsError> {
+            bool::parse_attr(self.$acc.attr("style:writing-mode-automatic"))
+        }
     };
 }
 
@@ -686,6 +851,11 @@ macro_rules! style_line_number {
         pub fn set_line_number(&mut self, line: u32) {
             self.$acc.set_attr("text:line-number", line.to_string());
         }
+
+        /// Parses the text:line-number attribute.
+        pub fn line_number(&self) -> Result<Option<u32>, OdsError> {
+            u32::parse_attr(self.$acc.attr("text:line-number"))
+        }
     };
 }
 
@@ -701,6 +871,11 @@ macro_rules! style_number_lines {
         pub fn set_number_lines(&mut self, lines: bool) {
             self.$acc.set_attr("text:number-lines", lines.to_string());
         }
+
+        /// Parses the text:number-lines attribute.
+        pub fn number_lines(&self) -> Result<Option<bool>, OdsError> {
+            bool::parse_attr(self.$acc.attr("text:number-lines"))
+        }
     };
 }
 
@@ -716,6 +891,11 @@ macro_rules! style_font_name {
         pub fn set_font_name<S: Into<String>>(&mut self, name: S) {
             self.$acc.set_attr("style:font-name", name.into());
         }
+
+        /// Returns the style:font-name attribute.
+        pub fn font_name(&self) -> Option<&str> {
+            self.$acc.attr("style:font-name")
+        }
     };
 }
 
@@ -759,6 +939,11 @@ macro_rules! style_font_name_asian {
         pub fn set_font_name_asian<S: Into<String>>(&mut self, name: S) {
             self.$acc.set_attr("style:font-name-asian", name.into());
         }
+
+        /// Returns the style:font-name-asian attribute.
+        pub fn font_name_asian(&self) -> Option<&str> {
+            self.$acc.attr("style:font-name-asian")
+        }
     };
 }
 
@@ -780,6 +965,11 @@ macro_rules! style_font_size_asian {
             self.$acc
                 .set_attr("style:font-size-asian", size.to_string());
         }
+
+        /// Parses the style:font-size-asian attribute.
+        pub fn font_size_asian(&self) -> Result<Option<FontSize>, OdsError> {
+            FontSize::parse_attr(self.$acc.attr("style:font-size-asian"))
+        }
     };
 }
 
@@ -793,6 +983,11 @@ macro_rules! style_font_size_rel_asian {
             self.$acc
                 .set_attr("style:font-size-rel-asian", size.to_string());
         }
+
+        /// Parses the style:font-size-rel-asian attribute.
+        pub fn font_size_rel_asian(&self) -> Result<Option<FontSize>, OdsError> {
+            FontSize::parse_attr(self.$acc.attr("style:font-size-rel-asian"))
+        }
     };
 }
 
@@ -805,6 +1000,11 @@ macro_rules! style_font_style_asian {
                 .set_attr("style:font-style-asian", style.to_string());
         }
 
+        /// Parses the style:font-style-asian attribute.
+        pub fn font_style_asian(&self) -> Result<Option<FontStyle>, OdsError> {
+            FontStyle::parse_attr(self.$acc.attr("style:font-style-asian"))
+        }
+
         /// Set the font-style to italic.
         pub fn set_font_italic_asian(&mut self) {
             self.$acc
@@ -822,6 +1022,11 @@ macro_rules! style_font_weight_asian {
                 .set_attr("style:font-weight-asian", weight.to_string());
         }
 
+        /// Parses the style:font-weight-asian attribute.
+        pub fn font_weight_asian(&self) -> Result<Option<FontWeight>, OdsError> {
+            FontWeight::parse_attr(self.$acc.attr("style:font-weight-asian"))
+        }
+
         /// Sets the font-weight to bold. See set_font_weight.
         pub fn set_font_bold_asian(&mut self) {
             self.$acc
@@ -886,6 +1091,11 @@ macro_rules! style_font_name_complex {
         pub fn set_font_name_complex<S: Into<String>>(&mut self, name: S) {
             self.$acc.set_attr("style:font-name-complex", name.into());
         }
+
+        /// Returns the style:font-name-complex attribute.
+        pub fn font_name_complex(&self) -> Option<&str> {
+            self.$acc.attr("style:font-name-complex")
+        }
     };
 }
 
@@ -907,6 +1117,11 @@ macro_rules! style_font_size_complex {
             self.$acc
                 .set_attr("style:font-size-complex", size.to_string());
         }
+
+        /// Parses the style:font-size-complex attribute.
+        pub fn font_size_complex(&self) -> Result<Option<FontSize>, OdsError> {
+            FontSize::parse_attr(self.$acc.attr("style:font-size-complex"))
+        }
     };
 }
 macro_rules! style_font_size_rel_complex {
@@ -919,6 +1134,11 @@ macro_rules! style_font_size_rel_complex {
             self.$acc
                 .set_attr("style:font-size-rel-complex", size.to_string());
         }
+
+        /// Parses the style:font-size-rel-complex attribute.
+        pub fn font_size_rel_complex(&self) -> Result<Option<FontSize>, OdsError> {
+            FontSize::parse_attr(self.$acc.attr("style:font-size-rel-complex"))
+        }
     };
 }
 macro_rules! style_font_style_complex {
@@ -930,6 +1150,11 @@ macro_rules! style_font_style_complex {
                 .set_attr("style:font-style-complex", style.to_string());
         }
 
+        /// Parses the style:font-style-complex attribute.
+        pub fn font_style_complex(&self) -> Result<Option<FontStyle>, OdsError> {
+            FontStyle::parse_attr(self.$acc.attr("style:font-style-complex"))
+        }
+
         /// Set the font-style to italic.
         /// This attribute is evaluated for any UNICODE character whose script type is complex. 20.358
         pub fn set_font_italic_complex(&mut self) {
@@ -948,6 +1173,11 @@ macro_rules! style_font_weight_complex {
                 .set_attr("style:font-weight-complex", weight.to_string());
         }
 
+        /// Parses the style:font-weight-complex attribute.
+        pub fn font_weight_complex(&self) -> Result<Option<FontWeight>, OdsError> {
+            FontWeight::parse_attr(self.$acc.attr("style:font-weight-complex"))
+        }
+
         /// Sets the font-weight to bold. See set_font_weight.
         pub fn set_font_bold_complex(&mut self) {
             self.$acc
@@ -982,6 +1212,11 @@ macro_rules! style_font_relief {
         pub fn set_font_relief(&mut self, relief: TextRelief) {
             self.$acc.set_attr("style:font-relief", relief.to_string());
         }
+
+        /// Parses the style:font-relief attribute.
+        pub fn font_relief(&self) -> Result<Option<TextRelief>, OdsError> {
+            TextRelief::parse_attr(self.$acc.attr("style:font-relief"))
+        }
     };
 }
 
@@ -1012,6 +1247,11 @@ macro_rules! style_letter_kerning {
             self.$acc
                 .set_attr("style:letter-kerning", kerning.to_string());
         }
+
+        /// Parses the style:letter-kerning attribute.
+        pub fn letter_kerning(&self) -> Result<Option<bool>, OdsError> {
+            bool::parse_attr(self.$acc.attr("style:letter-kerning"))
+        }
     };
 }
 
@@ -1031,6 +1271,11 @@ macro_rules! style_text_combine {
         pub fn set_text_combine(&mut self, pos: TextCombine) {
             self.$acc.set_attr("style:text-combine", pos.to_string());
         }
+
+        /// Parses the style:text-combine attribute.
+        pub fn text_combine(&self) -> Result<Option<TextCombine>, OdsError> {
+            TextCombine::parse_attr(self.$acc.attr("style:text-combine"))
+        }
     };
 }
 
@@ -1094,6 +1339,11 @@ macro_rules! style_text_line_through {
                 .set_attr("style:text-line-through-color", color_string(color));
         }
 
+        /// Parses the style:text-line-through-color attribute.
+        pub fn text_line_through_color(&self) -> Result<Option<Rgb<u8>>, OdsError> {
+            Rgb::parse_attr(self.$acc.attr("style:text-line-through-color"))
+        }
+
         /// The style:text-line-through-mode attribute specifies whether lining through is applied to
         /// words only or to portions of text.
         /// The defined values for the style:text-line-through-mode attribute are:
@@ -1104,6 +1354,11 @@ macro_rules! style_text_line_through {
                 .set_attr("style:text-line-through-mode", lmode.to_string());
         }
 
+        /// Parses the style:text-line-through-mode attribute.
+        pub fn text_line_through_mode(&self) -> Result<Option<LineMode>, OdsError> {
+            LineMode::parse_attr(self.$acc.attr("style:text-line-through-mode"))
+        }
+
         /// The style:text-line-through-style attribute specifies a style for rendering a line-through
         /// text.
         /// The defined values for the style:text-line-through-style attribute are:
@@ -1124,6 +1379,11 @@ macro_rules! style_text_line_through {
                 .set_attr("style:text-line-through-style", lstyle.to_string());
         }
 
+        /// Parses the style:text-line-through-style attribute.
+        pub fn text_line_through_style(&self) -> Result<Option<LineStyle>, OdsError> {
+            LineStyle::parse_attr(self.$acc.attr("style:text-line-through-style"))
+        }
+
         /// The style:text-line-through-text attribute specifies a text that is used for line-through.
         /// The attribute will be evaluated only if the value of style:text-line-through-style 20.373
         /// attribute is different than none.
@@ -1168,6 +1428,11 @@ macro_rules! style_text_line_through {
                 .set_attr("style:text-line-through-type", ltype.to_string());
         }
 
+        /// Parses the style:text-line-through-type attribute.
+        pub fn text_line_through_type(&self) -> Result<Option<LineType>, OdsError> {
+            LineType::parse_attr(self.$acc.attr("style:text-line-through-type"))
+        }
+
         /// The style:text-line-through-width attribute specifies the width of a line-through line. The
         /// value bold specifies a line width that is calculated from the font sizes like an auto width, but is
         /// wider than an auto width.
@@ -1186,6 +1451,11 @@ macro_rules! style_text_line_through {
             self.$acc
                 .set_attr("style:text-line-through-width", lwidth.to_string());
         }
+
+        /// Parses the style:text-line-through-width attribute.
+        pub fn text_line_through_width(&self) -> Result<Option<LineWidth>, OdsError> {
+            LineWidth::parse_attr(self.$acc.attr("style:text-line-through-width"))
+        }
     };
 }
 
@@ -1197,6 +1467,11 @@ macro_rules! style_text_outline {
             self.$acc
                 .set_attr("style:text-outline", outline.to_string());
         }
+
+        /// Parses the style:text-outline attribute.
+        pub fn font_text_outline(&self) -> Result<Option<bool>, OdsError> {
+            bool::parse_attr(self.$acc.attr("style:text-outline"))
+        }
     };
 }
 
@@ -1213,6 +1488,11 @@ macro_rules! style_text_overline {
                 .set_attr("style:text-overline-color", color_string(color));
         }
 
+        /// Parses the style:text-overline-color attribute.
+        pub fn text_overline_color(&self) -> Result<Option<Rgb<u8>>, OdsError> {
+            Rgb::parse_attr(self.$acc.attr("style:text-overline-color"))
+        }
+
         /// The style:text-overline-mode attribute specifies whether overlining is applied to words
         /// only or to portions of text.
         pub fn set_text_overline_mode(&mut self, lmode: LineMode) {
@@ -1220,18 +1500,33 @@ macro_rules! style_text_overline {
                 .set_attr("style:text-overline-mode", lmode.to_string());
         }
 
+        /// Parses the style:text-overline-mode attribute.
+        pub fn text_overline_mode(&self) -> Result<Option<LineMode>, OdsError> {
+            LineMode::parse_attr(self.$acc.attr("style:text-overline-mode"))
+        }
+
         /// The style:text-overline-style attribute specifies a style for rendering a line over text.
         pub fn set_text_overline_style(&mut self, lstyle: LineStyle) {
             self.$acc
                 .set_attr("style:text-overline-style", lstyle.to_string());
         }
 
+        /// Parses the style:text-overline-style attribute.
+        pub fn text_overline_style(&self) -> Result<Option<LineStyle>, OdsError> {
+            LineStyle::parse_attr(self.$acc.attr("style:text-overline-style"))
+        }
+
         /// The style:text-overline-type attribute specifies the type of overlining applied to a text.
         pub fn set_text_overline_type(&mut self, ltype: LineType) {
             self.$acc
                 .set_attr("style:text-overline-type", ltype.to_string());
         }
 
+        /// Parses the style:text-overline-type attribute.
+        pub fn text_overline_type(&self) -> Result<Option<LineType>, OdsError> {
+            LineType::parse_attr(self.$acc.attr("style:text-overline-type"))
+        }
+
         /// The style:text-overline-width attribute specifies the width of an overline. The value bold
         /// specifies a line width that is calculated from the font sizes like an auto width, but is wider than an
         /// auto width.
@@ -1239,6 +1534,11 @@ macro_rules! style_text_overline {
             self.$acc
                 .set_attr("style:text-overline-width", lwidth.to_string());
         }
+
+        /// Parses the style:text-overline-width attribute.
+        pub fn text_overline_width(&self) -> Result<Option<LineWidth>, OdsError> {
+            LineWidth::parse_attr(self.$acc.attr("style:text-overline-width"))
+        }
     };
 }
 
@@ -1253,6 +1553,11 @@ macro_rules! style_text_underline {
                 .set_attr("style:text-underline-color", color_string(color));
         }
 
+        /// Parses the style:text-underline-color attribute.
+        pub fn text_underline_color(&self) -> Result<Option<Rgb<u8>>, OdsError> {
+            Rgb::parse_attr(self.$acc.attr("style:text-underline-color"))
+        }
+
         /// The style:text-underline-mode attribute specifies whether underlining is applied to words
         /// only or to portions of text. If underlining is applied to text portions, the spaces between words and
         /// the words are underlined.
@@ -1261,18 +1566,33 @@ macro_rules! style_text_underline {
                 .set_attr("style:text-underline-mode", lmode.to_string());
         }
 
+        /// Parses the style:text-underline-mode attribute.
+        pub fn text_underline_mode(&self) -> Result<Option<LineMode>, OdsError> {
+            LineMode::parse_attr(self.$acc.attr("style:text-underline-mode"))
+        }
+
         /// The style:text-underline-style attribute specifies a style for underlining text
         pub fn set_text_underline_style(&mut self, lstyle: LineStyle) {
             self.$acc
                 .set_attr("style:text-underline-style", lstyle.to_string());
         }
 
+        /// Parses the style:text-underline-style attribute.
+        pub fn text_underline_style(&self) -> Result<Option<LineStyle>, OdsError> {
+            LineStyle::parse_attr(self.$acc.attr("style:text-underline-style"))
+        }
+
         /// The style:text-underline-type attribute specifies the type of underlining applied to a text
         pub fn set_text_underline_type(&mut self, ltype: LineType) {
             self.$acc
                 .set_attr("style:text-underline-type", ltype.to_string());
         }
 
+        /// Parses the style:text-underline-type attribute.
+        pub fn text_underline_type(&self) -> Result<Option<LineType>, OdsError> {
+            LineType::parse_attr(self.$acc.attr("style:text-underline-type"))
+        }
+
         /// The style:text-underline-width attribute specifies the width of an underline. The value
         /// bold specifies a line width that is calculated from the font sizes like an auto width, but is wider
         /// than an auto width.
@@ -1280,6 +1600,11 @@ macro_rules! style_text_underline {
             self.$acc
                 .set_attr("style:text-underline-width", lwidth.to_string());
         }
+
+        /// Parses the style:text-underline-width attribute.
+        pub fn text_underline_width(&self) -> Result<Option<LineWidth>, OdsError> {
+            LineWidth::parse_attr(self.$acc.attr("style:text-underline-width"))
+        }
     };
 }
 
@@ -1292,6 +1617,11 @@ macro_rules! style_use_window_font_color {
             self.$acc
                 .set_attr("style:use-window-font-color", window_color.to_string());
         }
+
+        /// Parses the style:use-window-font-color attribute.
+        pub fn use_window_font_color(&self) -> Result<Option<bool>, OdsError> {
+            bool::parse_attr(self.$acc.attr("style:use-window-font-color"))
+        }
     };
 }
 
@@ -1302,6 +1632,11 @@ macro_rules! style_dynamic_spacing {
             self.$acc
                 .set_attr("style:dynamic-spacing", dynamic.to_string());
         }
+
+        /// Parses the style:dynamic-spacing attribute.
+        pub fn dynamic_spacing(&self) -> Result<Option<bool>, OdsError> {
+            bool::parse_attr(self.$acc.attr("style:dynamic-spacing"))
+        }
     };
 }
 
@@ -1366,6 +1701,11 @@ macro_rules! style_font_family_generic {
             self.$acc
                 .set_attr("style:font-family-generic", font.to_string());
         }
+
+        /// Parses the style:font-family-generic attribute.
+        pub fn font_family_generic(&self) -> Result<Option<FontFamilyGeneric>, OdsError> {
+            FontFamilyGeneric::parse_attr(self.$acc.attr("style:font-family-generic"))
+        }
     };
 }
 
@@ -1378,6 +1718,11 @@ macro_rules! style_font_pitch {
         pub fn set_font_pitch(&mut self, pitch: FontPitch) {
             self.$acc.set_attr("style:font-pitch", pitch.to_string());
         }
+
+        /// Parses the style:font-pitch attribute.
+        pub fn font_pitch(&self) -> Result<Option<FontPitch>, OdsError> {
+            FontPitch::parse_attr(self.$acc.attr("style:font-pitch"))
+        }
     };
 }
 
@@ -1390,6 +1735,11 @@ macro_rules! style_first_page_number {
             self.style_mut()
                 .set_attr("style:first-page-number", number.to_string());
         }
+
+        /// Parses the style:first-page-number attribute.
+        pub fn first_page_number(&self) -> Result<Option<u32>, OdsError> {
+            u32::parse_attr(self.$acc.attr("style:first-page-number"))
+        }
     };
 }
 
@@ -1404,6 +1754,11 @@ macro_rules! style_footnote_max_height {
             self.style_mut()
                 .set_attr("style:footnote-max-height", height.to_string());
         }
+
+        /// Parses the style:footnote-max-height attribute.
+        pub fn footnote_max_height(&self) -> Result<Option<Length>, OdsError> {
+            Length::parse_attr(self.$acc.attr("style:footnote-max-height"))
+        }
     };
 }
 
@@ -1521,6 +1876,11 @@ macro_rules! style_print_orientation {
             self.$acc
                 .set_attr("style:print-orientation", orientation.to_string());
         }
+
+        /// Parses the style:print-orientation attribute.
+        pub fn print_orientation(&self) -> Result<Option<PrintOrientation>, OdsError> {
+            PrintOrientation::parse_attr(self.$acc.attr("style:print-orientation"))
+        }
     };
 }
 
@@ -1537,6 +1897,11 @@ macro_rules! style_print_page_order {
             self.$acc
                 .set_attr("style:print-page-order", order.to_string());
         }
+
+        /// Parses the style:print-page-order attribute.
+        pub fn print_page_order(&self) -> Result<Option<PrintOrder>, OdsError> {
+            PrintOrder::parse_attr(self.$acc.attr("style:print-page-order"))
+        }
     };
 }
 
@@ -1548,6 +1913,11 @@ macro_rules! style_scale_to {
         pub fn set_scale_to(&mut self, percent: Percent) {
             self.$acc.set_attr("style:scale-to", percent.to_string());
         }
+
+        /// Parses the style:scale-to attribute.
+        pub fn scale_to(&self) -> Result<Option<Percent>, OdsError> {
+            Percent::parse_attr(self.$acc.attr("style:scale-to"))
+        }
     };
 }
 
@@ -1560,6 +1930,53 @@ macro_rules! style_scale_to_pages {
             self.$acc
                 .set_attr("style:scale-to-pages", pages.to_string());
         }
+
+        /// Parses the style:scale-to-pages attribute.
+        pub fn scale_to_pages(&self) -> Result<Option<u32>, OdsError> {
+            u32::parse_attr(self.$acc.attr("style:scale-to-pages"))
+        }
+    };
+}
+
+macro_rules! style_scale_to_x {
+    ($acc:ident) => {
+        /// The style:scale-to-X attribute specifies the number of pages in width on which a
+        /// document should be printed. The document is scaled to fit. A value of 0 means
+        /// that the width is not limited.
+        pub fn set_scale_to_x(&mut self, pages: u32) {
+            self.$acc.set_attr("style:scale-to-X", pages.to_string());
+        }
+
+        /// Parses the style:scale-to-X attribute. LibreOffice writes loext:scale-to-X for
+        /// older file formats, this is recognized too.
+        pub fn scale_to_x(&self) -> Result<Option<u32>, OdsError> {
+            u32::parse_attr(
+                self.$acc
+                    .attr("style:scale-to-X")
+                    .or_else(|| self.$acc.attr("loext:scale-to-X")),
+            )
+        }
+    };
+}
+
+macro_rules! style_scale_to_y {
+    ($acc:ident) => {
+        /// The style:scale-to-Y attribute specifies the number of pages in height on which a
+        /// document should be printed. The document is scaled to fit. A value of 0 means
+        /// that the height is not limited.
+        pub fn set_scale_to_y(&mut self, pages: u32) {
+            self.$acc.set_attr("style:scale-to-Y", pages.to_string());
+        }
+
+        /// Parses the style:scale-to-Y attribute. LibreOffice writes loext:scale-to-Y for
+        /// older file formats, this is recognized too.
+        pub fn scale_to_y(&self) -> Result<Option<u32>, OdsError> {
+            u32::parse_attr(
+                self.$acc
+                    .attr("style:scale-to-Y")
+                    .or_else(|| self.$acc.attr("loext:scale-to-Y")),
+            )
+        }
     };
 }
 
@@ -1580,6 +1997,11 @@ macro_rules! style_table_centering {
             self.$acc
                 .set_attr("style:table-centering", center.to_string());
         }
+
+        /// Parses the style:table-centering attribute.
+        pub fn table_centering(&self) -> Result<Option<PrintCentering>, OdsError> {
+            PrintCentering::parse_attr(self.$acc.attr("style:table-centering"))
+        }
     };
 }
 
@@ -1591,6 +2013,11 @@ macro_rules! style_min_row_height {
             self.$acc
                 .set_attr("style:min-row-height", min_height.to_string());
         }
+
+        /// Parses the style:min-row-height attribute.
+        pub fn min_row_height(&self) -> Result<Option<Length>, OdsError> {
+            Length::parse_attr(self.$acc.attr("style:min-row-height"))
+        }
     };
 }
 
@@ -1694,6 +2121,11 @@ macro_rules! style_width {
         pub fn set_width(&mut self, width: Length) {
             self.$acc.set_attr("style:width", width.to_string());
         }
+
+        /// Parses the style:width attribute.
+        pub fn width(&self) -> Result<Option<Length>, OdsError> {
+            Length::parse_attr(self.$acc.attr("style:width"))
+        }
     };
 }
 
@@ -1715,6 +2147,11 @@ macro_rules! style_leader_color {
             self.attr
                 .set_attr("style:leader-color", color_string(color));
         }
+
+        /// Parses the style:leader-color attribute.
+        pub fn leader_color(&self) -> Result<Option<Rgb<u8>>, OdsError> {
+            Rgb::parse_attr(self.$acc.attr("style:leader-color"))
+        }
     };
 }
 
@@ -1739,6 +2176,11 @@ macro_rules! style_leader_style {
         pub fn set_leader_style(&mut self, style: LineStyle) {
             self.$acc.set_attr("style:leader-style", style.to_string());
         }
+
+        /// Parses the style:leader-style attribute.
+        pub fn leader_style(&self) -> Result<Option<LineStyle>, OdsError> {
+            LineStyle::parse_attr(self.$acc.attr("style:leader-style"))
+        }
     };
 }
 
@@ -1784,6 +2226,11 @@ macro_rules! style_leader_type {
         pub fn set_leader_type(&mut self, t: LineType) {
             self.$acc.set_attr("style:leader-type", t.to_string());
         }
+
+        /// Parses the style:leader-type attribute.
+        pub fn leader_type(&self) -> Result<Option<LineType>, OdsError> {
+            LineType::parse_attr(self.$acc.attr("style:leader-type"))
+        }
     };
 }
 
@@ -1802,6 +2249,11 @@ macro_rules! style_leader_width {
         pub fn set_leader_width(&mut self, w: LineWidth) {
             self.$acc.set_attr("style:leader-width", w.to_string());
         }
+
+        /// Parses the style:leader-width attribute.
+        pub fn leader_width(&self) -> Result<Option<LineWidth>, OdsError> {
+            LineWidth::parse_attr(self.$acc.attr("style:leader-width"))
+        }
     };
 }
 
@@ -1820,6 +2272,11 @@ macro_rules! style_position {
         pub fn set_position(&mut self, pos: Length) {
             self.$acc.set_attr("style:position", pos.to_string());
         }
+
+        /// Parses the style:position attribute.
+        pub fn position(&self) -> Result<Option<Length>, OdsError> {
+            Length::parse_attr(self.$acc.attr("style:position"))
+        }
     };
 }
 
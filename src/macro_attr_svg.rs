@@ -61,7 +61,6 @@ macro_rules! svg_width {
     };
 }
 
-#[allow(unused_macros)]
 macro_rules! svg_rx {
     ($acc:ident) => {
         /// See §9.4 of[SVG].
@@ -79,7 +78,6 @@ macro_rules! svg_rx {
     };
 }
 
-#[allow(unused_macros)]
 macro_rules! svg_ry {
     ($acc:ident) => {
         /// See §9.4 of [SVG].
@@ -113,3 +111,79 @@ macro_rules! svg_y {
         }
     };
 }
+
+macro_rules! svg_stroke_color {
+    ($acc:ident) => {
+        /// The svg:stroke-color attribute specifies the color of a stroke.
+        pub fn set_stroke_color(&mut self, color: Rgb<u8>) {
+            self.$acc.set_attr("svg:stroke-color", color_string(color));
+        }
+
+        /// Parses the svg:stroke-color attribute.
+        pub fn stroke_color(&self) -> Result<Option<Rgb<u8>>, OdsError> {
+            Rgb::parse_attr(self.$acc.attr("svg:stroke-color"))
+        }
+    };
+}
+
+macro_rules! svg_stroke_width {
+    ($acc:ident) => {
+        /// The svg:stroke-width attribute specifies the width of a stroke.
+        pub fn set_stroke_width(&mut self, width: Length) {
+            self.$acc.set_attr("svg:stroke-width", width.to_string());
+        }
+
+        /// Parses the svg:stroke-width attribute.
+        pub fn stroke_width(&self) -> Result<Option<Length>, OdsError> {
+            Length::parse_attr(self.$acc.attr("svg:stroke-width"))
+        }
+    };
+}
+
+macro_rules! svg_x1 {
+    ($acc:ident) => {
+        /// The svg:x1 attribute specifies the x-coordinate of the start point of a line or connector.
+        pub fn set_x1(&mut self, x: Length) {
+            self.$acc.set_attr("svg:x1", x.to_string());
+        }
+    };
+}
+
+macro_rules! svg_y1 {
+    ($acc:ident) => {
+        /// The svg:y1 attribute specifies the y-coordinate of the start point of a line or connector.
+        pub fn set_y1(&mut self, y: Length) {
+            self.$acc.set_attr("svg:y1", y.to_string());
+        }
+    };
+}
+
+macro_rules! svg_x2 {
+    ($acc:ident) => {
+        /// The svg:x2 attribute specifies the x-coordinate of the end point of a line or connector.
+        pub fn set_x2(&mut self, x: Length) {
+            self.$acc.set_attr("svg:x2", x.to_string());
+        }
+    };
+}
+
+macro_rules! svg_y2 {
+    ($acc:ident) => {
+        /// The svg:y2 attribute specifies the y-coordinate of the end point of a line or connector.
+        pub fn set_y2(&mut self, y: Length) {
+            self.$acc.set_attr("svg:y2", y.to_string());
+        }
+    };
+}
+
+macro_rules! svg_view_box {
+    ($acc:ident) => {
+        /// See §7.7 of SVG. The svg:viewBox attribute establishes a user coordinate system inside
+        /// the physical coordinate system of the shape specified by svg:x, svg:y, svg:width and
+        /// svg:height. It is used by draw:points and draw:enhanced-geometry.
+        pub fn set_view_box(&mut self, x: i64, y: i64, width: i64, height: i64) {
+            self.$acc
+                .set_attr("svg:viewBox", format!("{} {} {} {}", x, y, width, height));
+        }
+    };
+}
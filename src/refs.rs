@@ -2,21 +2,29 @@
 //! Defines types for cell references.
 //!
 
+use crate::error::AsStatic;
 use crate::refs::format_refs::{
-    fmt_cell_range, fmt_cell_ref, fmt_col, fmt_col_range, fmt_row, fmt_row_range,
+    fmt_cell_range, fmt_cell_range_a1, fmt_cell_range_r1c1, fmt_cell_ref, fmt_cell_ref_a1,
+    fmt_cell_ref_r1c1, fmt_col, fmt_col_range, fmt_col_range_a1, fmt_col_range_r1c1, fmt_row,
+    fmt_row_range, fmt_row_range_a1, fmt_row_range_r1c1,
 };
 use crate::refs::parser::CRCode::{CRCellRange, CRCellRef, CRColRange, CRRowRange};
-use crate::refs::parser::{CRCode, KTokenizerError};
+use crate::refs::parser::{CRCode, KSpan, KTokenizerResult};
 use crate::OdsError;
 use get_size::GetSize;
 use get_size_derive::GetSize;
+#[cfg(not(debug_assertions))]
+use kparse::prelude::*;
 use kparse::provider::StdTracker;
 use kparse::Track;
 use std::fmt;
 use std::fmt::{Display, Formatter};
 
-mod format;
+pub(crate) mod format;
 mod parser;
+mod rangelist;
+
+pub use rangelist::{CellOrder, CellRangeList, RangeCellIter};
 
 /// Basic cell reference.
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, GetSize)]
@@ -278,6 +286,23 @@ impl CellRef {
         buf
     }
 
+    /// Returns the reference in A1 notation, e.g. "Sheet1!$A$1".
+    /// External references are not part of the A1 notation and are dropped.
+    pub fn to_a1(&self) -> String {
+        let mut buf = String::new();
+        let _ = fmt_cell_ref_a1(&mut buf, self);
+        buf
+    }
+
+    /// Returns the reference in R1C1 notation. Relative rows and columns
+    /// are written as offset to the base cell, e.g. "R1C[2]".
+    /// External references are not part of the R1C1 notation and are dropped.
+    pub fn to_r1c1(&self, base_row: u32, base_col: u32) -> String {
+        let mut buf = String::new();
+        let _ = fmt_cell_ref_r1c1(&mut buf, self, base_row, base_col);
+        buf
+    }
+
     /// Makes this CellReference into an absolute reference.
     pub fn absolute(mut self) -> Self {
         self.col.col_abs = true;
@@ -595,6 +620,25 @@ impl CellRange {
         buf
     }
 
+    /// Returns the range in A1 notation, e.g. "Sheet1!A1:B2".
+    /// A range of a single cell is written as cell reference.
+    /// External references are not part of the A1 notation and are dropped.
+    pub fn to_a1(&self) -> String {
+        let mut buf = String::new();
+        let _ = fmt_cell_range_a1(&mut buf, self);
+        buf
+    }
+
+    /// Returns the range in R1C1 notation. Relative rows and columns
+    /// are written as offset to the base cell, e.g. "R1C1:R[2]C[2]".
+    /// A range of a single cell is written as cell reference.
+    /// External references are not part of the R1C1 notation and are dropped.
+    pub fn to_r1c1(&self, base_row: u32, base_col: u32) -> String {
+        let mut buf = String::new();
+        let _ = fmt_cell_range_r1c1(&mut buf, self, base_row, base_col);
+        buf
+    }
+
     /// Makes this CellReference into an absolute reference.
     pub fn absolute(mut self) -> Self {
         self.from_col.col_abs = true;
@@ -629,6 +673,36 @@ impl CellRange {
             && col <= self.to_col.col
     }
 
+    /// Does the range contain the other range completely.
+    /// Only the rows and columns are compared, not the tables.
+    pub fn contains_range(&self, other: &CellRange) -> bool {
+        self.contains(other.from_row.row, other.from_col.col)
+            && self.contains(other.to_row.row, other.to_col.col)
+    }
+
+    /// Do the ranges have any cell in common.
+    /// Only the rows and columns are compared, not the tables.
+    pub fn intersects(&self, other: &CellRange) -> bool {
+        self.from_row.row <= other.to_row.row
+            && other.from_row.row <= self.to_row.row
+            && self.from_col.col <= other.to_col.col
+            && other.from_col.col <= self.to_col.col
+    }
+
+    /// The cells both ranges have in common.
+    /// Only the rows and columns are compared, the table is taken from self.
+    pub fn intersection(&self, other: &CellRange) -> Option<CellRange> {
+        if !self.intersects(other) {
+            return None;
+        }
+        let mut range = self.clone();
+        range.from_row.row = self.from_row.row.max(other.from_row.row);
+        range.from_col.col = self.from_col.col.max(other.from_col.col);
+        range.to_row.row = self.to_row.row.min(other.to_row.row);
+        range.to_col.col = self.to_col.col.min(other.to_col.col);
+        Some(range)
+    }
+
     /// Is this range any longer relevant, when looping rows first, then columns?
     pub fn out_looped(&self, row: u32, col: u32) -> bool {
         row > self.to_row.row || row == self.to_row.row && col > self.to_col.col
@@ -791,6 +865,23 @@ impl ColRange {
         buf
     }
 
+    /// Returns the range in A1 notation, e.g. "Sheet1!A:C".
+    /// External references are not part of the A1 notation and are dropped.
+    pub fn to_a1(&self) -> String {
+        let mut buf = String::new();
+        let _ = fmt_col_range_a1(&mut buf, self);
+        buf
+    }
+
+    /// Returns the range in R1C1 notation, e.g. "C1:C[2]". Relative
+    /// columns are written as offset to the base column.
+    /// External references are not part of the R1C1 notation and are dropped.
+    pub fn to_r1c1(&self, base_col: u32) -> String {
+        let mut buf = String::new();
+        let _ = fmt_col_range_r1c1(&mut buf, self, base_col);
+        buf
+    }
+
     /// Makes this CellReference into an absolute reference.
     pub fn absolute(mut self) -> Self {
         self.from_col.col_abs = true;
@@ -958,6 +1049,23 @@ impl RowRange {
         buf
     }
 
+    /// Returns the range in A1 notation, e.g. "Sheet1!1:3".
+    /// External references are not part of the A1 notation and are dropped.
+    pub fn to_a1(&self) -> String {
+        let mut buf = String::new();
+        let _ = fmt_row_range_a1(&mut buf, self);
+        buf
+    }
+
+    /// Returns the range in R1C1 notation, e.g. "R1:R[2]". Relative
+    /// rows are written as offset to the base row.
+    /// External references are not part of the R1C1 notation and are dropped.
+    pub fn to_r1c1(&self, base_row: u32) -> String {
+        let mut buf = String::new();
+        let _ = fmt_row_range_r1c1(&mut buf, self, base_row);
+        buf
+    }
+
     /// Makes this CellReference into an absolute reference.
     pub fn absolute(mut self) -> Self {
         self.from_row.row_abs = true;
@@ -986,7 +1094,7 @@ impl Display for RowRange {
     }
 }
 
-mod format_refs {
+pub(crate) mod format_refs {
     use crate::refs::format::{fmt_abs, fmt_col_name, fmt_row_name};
     use crate::refs::{CCol, CRow};
     use crate::{CellRange, CellRef, ColRange, RowRange};
@@ -1057,6 +1165,205 @@ mod format_refs {
         Ok(())
     }
 
+    /// Appends the cell reference in A1 notation.
+    pub(crate) fn fmt_cell_ref_a1(f: &mut impl fmt::Write, cell_ref: &CellRef) -> fmt::Result {
+        if let Some(table) = cell_ref.table().as_ref() {
+            fmt_table_name_a1(f, table)?;
+            write!(f, "!")?;
+        }
+        fmt_col(f, &cell_ref.col)?;
+        fmt_row(f, &cell_ref.row)?;
+        Ok(())
+    }
+
+    /// Appends the range reference in A1 notation. A range of a single
+    /// cell is written as cell reference.
+    pub(crate) fn fmt_cell_range_a1(
+        f: &mut impl fmt::Write,
+        cell_range: &CellRange,
+    ) -> fmt::Result {
+        fmt_tables_a1(f, cell_range.table(), cell_range.to_table())?;
+        fmt_col(f, &cell_range.from_col)?;
+        fmt_row(f, &cell_range.from_row)?;
+        if cell_range.row() != cell_range.to_row() || cell_range.col() != cell_range.to_col() {
+            write!(f, ":")?;
+            fmt_col(f, &cell_range.to_col)?;
+            fmt_row(f, &cell_range.to_row)?;
+        }
+        Ok(())
+    }
+
+    /// Appends the column range in A1 notation.
+    pub(crate) fn fmt_col_range_a1(f: &mut impl fmt::Write, col_range: &ColRange) -> fmt::Result {
+        fmt_tables_a1(f, col_range.table(), col_range.to_table())?;
+        fmt_col(f, &col_range.from_col)?;
+        write!(f, ":")?;
+        fmt_col(f, &col_range.to_col)?;
+        Ok(())
+    }
+
+    /// Appends the row range in A1 notation.
+    pub(crate) fn fmt_row_range_a1(f: &mut impl fmt::Write, row_range: &RowRange) -> fmt::Result {
+        fmt_tables_a1(f, row_range.table(), row_range.to_table())?;
+        fmt_row(f, &row_range.from_row)?;
+        write!(f, ":")?;
+        fmt_row(f, &row_range.to_row)?;
+        Ok(())
+    }
+
+    /// Appends the cell reference in R1C1 notation.
+    pub(crate) fn fmt_cell_ref_r1c1(
+        f: &mut impl fmt::Write,
+        cell_ref: &CellRef,
+        base_row: u32,
+        base_col: u32,
+    ) -> fmt::Result {
+        if let Some(table) = cell_ref.table().as_ref() {
+            fmt_table_name_a1(f, table)?;
+            write!(f, "!")?;
+        }
+        fmt_r1c1(f, 'R', cell_ref.row_abs(), cell_ref.row(), base_row)?;
+        fmt_r1c1(f, 'C', cell_ref.col_abs(), cell_ref.col(), base_col)?;
+        Ok(())
+    }
+
+    /// Appends the range reference in R1C1 notation. A range of a single
+    /// cell is written as cell reference.
+    pub(crate) fn fmt_cell_range_r1c1(
+        f: &mut impl fmt::Write,
+        cell_range: &CellRange,
+        base_row: u32,
+        base_col: u32,
+    ) -> fmt::Result {
+        fmt_tables_a1(f, cell_range.table(), cell_range.to_table())?;
+        fmt_r1c1(f, 'R', cell_range.row_abs(), cell_range.row(), base_row)?;
+        fmt_r1c1(f, 'C', cell_range.col_abs(), cell_range.col(), base_col)?;
+        if cell_range.row() != cell_range.to_row() || cell_range.col() != cell_range.to_col() {
+            write!(f, ":")?;
+            fmt_r1c1(
+                f,
+                'R',
+                cell_range.to_row_abs(),
+                cell_range.to_row(),
+                base_row,
+            )?;
+            fmt_r1c1(
+                f,
+                'C',
+                cell_range.to_col_abs(),
+                cell_range.to_col(),
+                base_col,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Appends the column range in R1C1 notation.
+    pub(crate) fn fmt_col_range_r1c1(
+        f: &mut impl fmt::Write,
+        col_range: &ColRange,
+        base_col: u32,
+    ) -> fmt::Result {
+        fmt_tables_a1(f, col_range.table(), col_range.to_table())?;
+        fmt_r1c1(f, 'C', col_range.col_abs(), col_range.col(), base_col)?;
+        write!(f, ":")?;
+        fmt_r1c1(f, 'C', col_range.to_col_abs(), col_range.to_col(), base_col)?;
+        Ok(())
+    }
+
+    /// Appends the row range in R1C1 notation.
+    pub(crate) fn fmt_row_range_r1c1(
+        f: &mut impl fmt::Write,
+        row_range: &RowRange,
+        base_row: u32,
+    ) -> fmt::Result {
+        fmt_tables_a1(f, row_range.table(), row_range.to_table())?;
+        fmt_r1c1(f, 'R', row_range.row_abs(), row_range.row(), base_row)?;
+        write!(f, ":")?;
+        fmt_r1c1(f, 'R', row_range.to_row_abs(), row_range.to_row(), base_row)?;
+        Ok(())
+    }
+
+    /// Appends a row or column in R1C1 notation. Absolute values are
+    /// 1-based, relative values are written as offset to the base.
+    fn fmt_r1c1(
+        f: &mut impl fmt::Write,
+        letter: char,
+        abs: bool,
+        v: u32,
+        base: u32,
+    ) -> fmt::Result {
+        write!(f, "{}", letter)?;
+        if abs {
+            write!(f, "{}", v as u64 + 1)?;
+        } else if v != base {
+            write!(f, "[{}]", v as i64 - base as i64)?;
+        }
+        Ok(())
+    }
+
+    /// Appends "Sheet1!" or "Sheet1:Sheet3!" if there is a table.
+    fn fmt_tables_a1(
+        f: &mut impl fmt::Write,
+        table: Option<&String>,
+        to_table: Option<&String>,
+    ) -> fmt::Result {
+        if let Some(table) = table {
+            fmt_table_name_a1(f, table)?;
+            if let Some(to_table) = to_table {
+                if to_table != table {
+                    write!(f, ":")?;
+                    fmt_table_name_a1(f, to_table)?;
+                }
+            }
+            write!(f, "!")?;
+        }
+        Ok(())
+    }
+
+    /// Appends the table-name in A1 notation. The name is quoted if it
+    /// contains anything but letters, digits and '_', starts with a digit
+    /// or could be read as a cell reference.
+    pub(crate) fn fmt_table_name_a1(f: &mut impl fmt::Write, table_name: &str) -> fmt::Result {
+        if table_name.is_empty()
+            || table_name.contains(|c: char| !(c.is_alphanumeric() || c == '_'))
+            || table_name.starts_with(|c: char| c.is_ascii_digit())
+            || is_ref_like(table_name)
+        {
+            write!(f, "'")?;
+            write!(f, "{}", &table_name.replace('\'', "''"))?;
+            write!(f, "'")?;
+        } else {
+            write!(f, "{}", table_name)?;
+        }
+        Ok(())
+    }
+
+    /// Looks like "AB12" or like "R1C1", "R", "C2".
+    fn is_ref_like(name: &str) -> bool {
+        let name = name.to_ascii_uppercase();
+
+        let letters = name.trim_end_matches(|c: char| c.is_ascii_digit());
+        if letters.len() < name.len()
+            && !letters.is_empty()
+            && letters.len() <= 3
+            && letters.chars().all(|c| c.is_ascii_uppercase())
+        {
+            return true;
+        }
+
+        let rest = match name.strip_prefix('R') {
+            Some(rest) => rest.trim_start_matches(|c: char| c.is_ascii_digit()),
+            None => name.as_str(),
+        };
+        let rest = match rest.strip_prefix('C') {
+            Some(rest) => rest.trim_start_matches(|c: char| c.is_ascii_digit()),
+            None if rest.len() < name.len() => rest,
+            None => return false,
+        };
+        rest.is_empty()
+    }
+
     /// Appends the cell reference
     pub(crate) fn fmt_col_range(f: &mut impl fmt::Write, col_range: &ColRange) -> fmt::Result {
         fmt_iri(f, col_range.iri())?;
@@ -1139,56 +1446,121 @@ mod format_refs {
 
 /// Parse a cell reference.
 pub fn parse_cellref(buf: &str) -> Result<CellRef, OdsError> {
-    let trk: StdTracker<CRCode, _> = Track::new_tracker();
-    let span = Track::new_span(&trk, buf);
-
-    let (rest, tok) = parser::parse_cell_ref(span)?;
-    if rest.len() > 0 {
-        Err(nom::Err::Error(KTokenizerError::new(CRCellRef, rest)))?
-    } else {
-        Ok(tok)
-    }
+    parse_all(buf, CRCellRef, parser::parse_cell_ref)
 }
 
 /// Parse a cell reference.
 pub fn parse_cellrange(buf: &str) -> Result<CellRange, OdsError> {
-    let trk: StdTracker<CRCode, _> = Track::new_tracker();
-    let span = Track::new_span(&trk, buf);
-
-    let (rest, tok) = parser::parse_cell_range(span)?;
-    if rest.len() > 0 {
-        Err(nom::Err::Error(KTokenizerError::new(CRCellRange, rest)))?
-    } else {
-        Ok(tok)
-    }
+    parse_all(buf, CRCellRange, parser::parse_cell_range)
 }
 
 /// Parse a cell reference.
 pub fn parse_colrange(buf: &str) -> Result<ColRange, OdsError> {
-    let trk: StdTracker<CRCode, _> = Track::new_tracker();
-    let span = Track::new_span(&trk, buf);
-
-    let (rest, tok) = parser::parse_col_range(span)?;
-    if rest.len() > 0 {
-        Err(nom::Err::Error(KTokenizerError::new(CRColRange, rest)))?
-    } else {
-        Ok(tok)
-    }
+    parse_all(buf, CRColRange, parser::parse_col_range)
 }
 
 /// Parse a cell reference.
 pub fn parse_rowrange(buf: &str) -> Result<RowRange, OdsError> {
+    parse_all(buf, CRRowRange, parser::parse_row_range)
+}
+
+/// Parse a cell reference in A1 notation.
+/// Examples: "A1", "$A$1", "Sheet1!A1", "'My Sheet'!$A$1"
+pub fn parse_cellref_a1(buf: &str) -> Result<CellRef, OdsError> {
+    parse_all(buf, CRCellRef, parser::parse_cell_ref_a1)
+}
+
+/// Parse a range reference in A1 notation. A single cell is a range too.
+/// Examples: "A1:B2", "Sheet1!A1:B2", "Sheet1:Sheet3!A1:B2"
+pub fn parse_cellrange_a1(buf: &str) -> Result<CellRange, OdsError> {
+    parse_all(buf, CRCellRange, parser::parse_cell_range_a1)
+}
+
+/// Parse a column range in A1 notation.
+/// Examples: "A:A", "$A:$C", "Sheet1!A:C"
+pub fn parse_colrange_a1(buf: &str) -> Result<ColRange, OdsError> {
+    parse_all(buf, CRColRange, parser::parse_col_range_a1)
+}
+
+/// Parse a row range in A1 notation.
+/// Examples: "1:3", "$1:$3", "Sheet1!1:3"
+pub fn parse_rowrange_a1(buf: &str) -> Result<RowRange, OdsError> {
+    parse_all(buf, CRRowRange, parser::parse_row_range_a1)
+}
+
+/// Parse a cell reference in R1C1 notation.
+///
+/// "R1C1" is absolute and 1-based. "R[-1]C[2]" is an offset to the base
+/// cell, a missing number is the row/column of the base cell.
+/// Examples: "R1C1", "RC[1]", "Sheet1!R[-1]C"
+pub fn parse_cellref_r1c1(buf: &str, base_row: u32, base_col: u32) -> Result<CellRef, OdsError> {
+    parse_all(buf, CRCellRef, |i| {
+        parser::parse_cell_ref_r1c1(i, base_row, base_col)
+    })
+}
+
+/// Parse a range reference in R1C1 notation. A single cell is a range too.
+/// Examples: "R1C1:R2C2", "Sheet1!RC:R[2]C[2]"
+pub fn parse_cellrange_r1c1(
+    buf: &str,
+    base_row: u32,
+    base_col: u32,
+) -> Result<CellRange, OdsError> {
+    parse_all(buf, CRCellRange, |i| {
+        parser::parse_cell_range_r1c1(i, base_row, base_col)
+    })
+}
+
+/// Parse a column range in R1C1 notation.
+/// Examples: "C1:C3", "C:C[2]"
+pub fn parse_colrange_r1c1(buf: &str, base_col: u32) -> Result<ColRange, OdsError> {
+    parse_all(buf, CRColRange, |i| {
+        parser::parse_col_range_r1c1(i, base_col)
+    })
+}
+
+/// Parse a row range in R1C1 notation.
+/// Examples: "R1:R3", "R:R[2]"
+pub fn parse_rowrange_r1c1(buf: &str, base_row: u32) -> Result<RowRange, OdsError> {
+    parse_all(buf, CRRowRange, |i| {
+        parser::parse_row_range_r1c1(i, base_row)
+    })
+}
+
+/// Runs the parser and checks that the complete buffer is used.
+/// The error contains the position where parsing failed.
+fn parse_all<T>(
+    buf: &str,
+    code: CRCode,
+    parse: impl for<'s> FnOnce(KSpan<'s>) -> KTokenizerResult<'s, T>,
+) -> Result<T, OdsError> {
     let trk: StdTracker<CRCode, _> = Track::new_tracker();
     let span = Track::new_span(&trk, buf);
 
-    let (rest, tok) = parser::parse_row_range(span)?;
-    if rest.len() > 0 {
-        Err(nom::Err::Error(KTokenizerError::new(CRRowRange, rest)))?
-    } else {
-        Ok(tok)
+    match parse(span) {
+        Ok((rest, tok)) => {
+            if rest.len() > 0 {
+                Err(parse_error(buf, code, rest))
+            } else {
+                Ok(tok)
+            }
+        }
+        Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => {
+            Err(parse_error(buf, e.code, e.span))
+        }
+        Err(nom::Err::Incomplete(_)) => Err(OdsError::Parse("incomplete", None)),
     }
 }
 
+/// Error with the byte position of the span in the buffer.
+fn parse_error(buf: &str, code: CRCode, span: KSpan<'_>) -> OdsError {
+    let pos = span.fragment().as_ptr() as usize - buf.as_ptr() as usize;
+    OdsError::Parse(
+        code.as_static(),
+        Some(format!("at {}: {:?}", pos, span.fragment())),
+    )
+}
+
 /// Parse a list of range refs
 pub fn parse_cellranges(buf: &str) -> Result<Option<Vec<CellRange>>, OdsError> {
     let trk: StdTracker<CRCode, _> = Track::new_tracker();
@@ -1217,3 +1589,56 @@ pub(crate) fn format_cellranges(v: &[CellRange]) -> impl Display + '_ {
 
     Tmp(v)
 }
+
+/// Replaces the references in a formula. References are enclosed in
+/// brackets, anything in string literals is ignored. The callback gets
+/// the text between the brackets and returns the replacement, or None
+/// to keep it. Returns None if nothing was replaced.
+pub(crate) fn replace_formula_refs(
+    formula: &str,
+    mut replace: impl FnMut(&str) -> Option<String>,
+) -> Option<String> {
+    let mut buf = String::with_capacity(formula.len());
+    let mut changed = false;
+    let mut in_string = false;
+    let mut rest = formula;
+    while let Some(idx) = rest.find(|c| c == '"' || (c == '[' && !in_string)) {
+        buf.push_str(&rest[..idx]);
+        rest = &rest[idx..];
+        if rest.starts_with('"') {
+            in_string = !in_string;
+            buf.push('"');
+            rest = &rest[1..];
+            continue;
+        }
+        let Some(end) = find_unquoted(rest, ']') else {
+            break;
+        };
+        if let Some(inner) = replace(&rest[1..end]) {
+            buf.push('[');
+            buf.push_str(&inner);
+            buf.push(']');
+            changed = true;
+        } else {
+            buf.push_str(&rest[..=end]);
+        }
+        rest = &rest[end + 1..];
+    }
+    buf.push_str(rest);
+
+    changed.then_some(buf)
+}
+
+/// Finds the character outside of single quotes. Quoted sheet names
+/// can contain anything.
+pub(crate) fn find_unquoted(text: &str, c: char) -> Option<usize> {
+    let mut quoted = false;
+    for (idx, v) in text.char_indices() {
+        if v == '\'' {
+            quoted = !quoted;
+        } else if v == c && !quoted {
+            return Some(idx);
+        }
+    }
+    None
+}
@@ -1,13 +1,16 @@
 use crate::error::AsStatic;
-use crate::refs::parser::parser::{parse_col, parse_iri, parse_row, parse_sheet_name};
-use crate::refs::parser::tokens::colon;
+use crate::refs::parser::parser::{
+    parse_col, parse_col_r1c1, parse_iri, parse_row, parse_row_r1c1, parse_sheet_name,
+    parse_sheet_name_a1, parse_sheet_names_a1,
+};
+use crate::refs::parser::tokens::{colon, list_separator};
 use crate::{CellRange, CellRef, ColRange, RowRange};
 use kparse::prelude::*;
 use kparse::{TokenizerError, TokenizerResult};
-use nom::character::complete::multispace1;
-use nom::combinator::all_consuming;
-use nom::multi::separated_list0;
-use nom::sequence::tuple;
+use nom::character::complete::{multispace0, multispace1};
+use nom::combinator::{all_consuming, opt};
+use nom::multi::{separated_list0, separated_list1};
+use nom::sequence::{preceded, tuple};
 use std::fmt::{Display, Formatter};
 use CRCode::*;
 
@@ -26,12 +29,17 @@ pub(crate) enum CRCode {
 
     CRCol,
     CRColInteger,
+    CRColLetter,
     CRColon,
     CRDollar,
     CRDot,
+    CRExclamation,
     CRHash,
+    CRListSeparator,
+    CROffset,
     CRRow,
     CRRowInteger,
+    CRRowLetter,
     CRSingleQuoteEnd,
     CRSingleQuoteStart,
     CRString,
@@ -50,12 +58,17 @@ impl AsStatic<str> for CRCode {
             CRIri => "Iri",
             CRCol => "Col",
             CRColInteger => "ColInteger",
+            CRColLetter => "ColLetter",
             CRColon => "Colon",
             CRDollar => "Dollar",
             CRDot => "Dot",
+            CRExclamation => "Exclamation",
             CRHash => "Hash",
+            CRListSeparator => "ListSeparator",
+            CROffset => "Offset",
             CRRow => "Row",
             CRRowInteger => "RowInteger",
+            CRRowLetter => "RowLetter",
             CRSingleQuoteEnd => "SingleQuoteEnd",
             CRSingleQuoteStart => "SingleQuoteStart",
             CRString => "String",
@@ -76,12 +89,17 @@ impl Display for CRCode {
             CRIri => "iri",
             CRCol => "col",
             CRColInteger => "col int",
+            CRColLetter => "C",
             CRColon => ":",
             CRDollar => "$",
             CRDot => ".",
+            CRExclamation => "!",
             CRHash => "#",
+            CRListSeparator => ", or ;",
+            CROffset => "offset",
             CRRow => "row",
             CRRowInteger => "row int",
+            CRRowLetter => "R",
             CRSingleQuoteEnd => "' start",
             CRSingleQuoteStart => "' end",
             CRString => "str",
@@ -168,6 +186,197 @@ pub(crate) fn parse_cell_range(input: KSpan<'_>) -> KTokenizerResult<'_, CellRan
     )
 }
 
+pub(crate) fn parse_cell_range_list_a1(input: KSpan<'_>) -> KTokenizerResult<'_, Vec<CellRange>> {
+    Track.enter(CRCellRangeList, input);
+
+    let (rest, vec) = separated_list1(
+        tuple((multispace0, list_separator, multispace0)),
+        parse_cell_range_a1,
+    )(input)
+    .track()?;
+
+    Track.ok(rest, input, vec)
+}
+
+/// A1 notation. A single cell is a range too.
+pub(crate) fn parse_cell_range_a1(input: KSpan<'_>) -> KTokenizerResult<'_, CellRange> {
+    Track.enter(CRCellRange, input);
+
+    let (rest, (tables, (abs_col, col), (abs_row, row), to)) = tuple((
+        opt(parse_sheet_names_a1),
+        parse_col,
+        parse_row,
+        opt(preceded(colon, tuple((parse_col, parse_row)))),
+    ))(input)
+    .track()?;
+    let ((abs_to_col, to_col), (abs_to_row, to_row)) =
+        to.unwrap_or(((abs_col, col), (abs_row, row)));
+    let (table, to_table) = split_tables(tables);
+
+    Track.ok(
+        rest,
+        input,
+        CellRange::new_all(
+            None, table, abs_row, row, abs_col, col, to_table, abs_to_row, to_row, abs_to_col,
+            to_col,
+        ),
+    )
+}
+
+/// A1 notation.
+pub(crate) fn parse_cell_ref_a1(input: KSpan<'_>) -> KTokenizerResult<'_, CellRef> {
+    Track.enter(CRCellRef, input);
+
+    let (rest, (table, (abs_col, col), (abs_row, row))) =
+        tuple((opt(parse_sheet_name_a1), parse_col, parse_row))(input).track()?;
+
+    Track.ok(
+        rest,
+        input,
+        CellRef::new_all(None, table, abs_row, row, abs_col, col),
+    )
+}
+
+/// A1 notation, A:C
+pub(crate) fn parse_col_range_a1(input: KSpan<'_>) -> KTokenizerResult<'_, ColRange> {
+    Track.enter(CRColRange, input);
+
+    let (rest, (tables, (abs_col, col), _, (abs_to_col, to_col))) =
+        tuple((opt(parse_sheet_names_a1), parse_col, colon, parse_col))(input).track()?;
+    let (table, to_table) = split_tables(tables);
+
+    Track.ok(
+        rest,
+        input,
+        ColRange::new_all(None, table, abs_col, col, to_table, abs_to_col, to_col),
+    )
+}
+
+/// A1 notation, 1:3
+pub(crate) fn parse_row_range_a1(input: KSpan<'_>) -> KTokenizerResult<'_, RowRange> {
+    Track.enter(CRRowRange, input);
+
+    let (rest, (tables, (abs_row, row), _, (abs_to_row, to_row))) =
+        tuple((opt(parse_sheet_names_a1), parse_row, colon, parse_row))(input).track()?;
+    let (table, to_table) = split_tables(tables);
+
+    Track.ok(
+        rest,
+        input,
+        RowRange::new_all(None, table, abs_row, row, to_table, abs_to_row, to_row),
+    )
+}
+
+/// R1C1 notation. Relative references are resolved with the base cell.
+pub(crate) fn parse_cell_ref_r1c1(
+    input: KSpan<'_>,
+    base_row: u32,
+    base_col: u32,
+) -> KTokenizerResult<'_, CellRef> {
+    Track.enter(CRCellRef, input);
+
+    let (rest, (table, (abs_row, row), (abs_col, col))) = tuple((
+        opt(parse_sheet_name_a1),
+        |i| parse_row_r1c1(i, base_row),
+        |i| parse_col_r1c1(i, base_col),
+    ))(input)
+    .track()?;
+
+    Track.ok(
+        rest,
+        input,
+        CellRef::new_all(None, table, abs_row, row, abs_col, col),
+    )
+}
+
+/// R1C1 notation. A single cell is a range too.
+pub(crate) fn parse_cell_range_r1c1(
+    input: KSpan<'_>,
+    base_row: u32,
+    base_col: u32,
+) -> KTokenizerResult<'_, CellRange> {
+    Track.enter(CRCellRange, input);
+
+    let (rest, (tables, (abs_row, row), (abs_col, col), to)) = tuple((
+        opt(parse_sheet_names_a1),
+        |i| parse_row_r1c1(i, base_row),
+        |i| parse_col_r1c1(i, base_col),
+        opt(preceded(
+            colon,
+            tuple((
+                |i| parse_row_r1c1(i, base_row),
+                |i| parse_col_r1c1(i, base_col),
+            )),
+        )),
+    ))(input)
+    .track()?;
+    let ((abs_to_row, to_row), (abs_to_col, to_col)) =
+        to.unwrap_or(((abs_row, row), (abs_col, col)));
+    let (table, to_table) = split_tables(tables);
+
+    Track.ok(
+        rest,
+        input,
+        CellRange::new_all(
+            None, table, abs_row, row, abs_col, col, to_table, abs_to_row, to_row, abs_to_col,
+            to_col,
+        ),
+    )
+}
+
+/// R1C1 notation, C1:C3
+pub(crate) fn parse_col_range_r1c1(
+    input: KSpan<'_>,
+    base_col: u32,
+) -> KTokenizerResult<'_, ColRange> {
+    Track.enter(CRColRange, input);
+
+    let (rest, (tables, (abs_col, col), _, (abs_to_col, to_col))) = tuple((
+        opt(parse_sheet_names_a1),
+        |i| parse_col_r1c1(i, base_col),
+        colon,
+        |i| parse_col_r1c1(i, base_col),
+    ))(input)
+    .track()?;
+    let (table, to_table) = split_tables(tables);
+
+    Track.ok(
+        rest,
+        input,
+        ColRange::new_all(None, table, abs_col, col, to_table, abs_to_col, to_col),
+    )
+}
+
+/// R1C1 notation, R1:R3
+pub(crate) fn parse_row_range_r1c1(
+    input: KSpan<'_>,
+    base_row: u32,
+) -> KTokenizerResult<'_, RowRange> {
+    Track.enter(CRRowRange, input);
+
+    let (rest, (tables, (abs_row, row), _, (abs_to_row, to_row))) = tuple((
+        opt(parse_sheet_names_a1),
+        |i| parse_row_r1c1(i, base_row),
+        colon,
+        |i| parse_row_r1c1(i, base_row),
+    ))(input)
+    .track()?;
+    let (table, to_table) = split_tables(tables);
+
+    Track.ok(
+        rest,
+        input,
+        RowRange::new_all(None, table, abs_row, row, to_table, abs_to_row, to_row),
+    )
+}
+
+fn split_tables(tables: Option<(String, Option<String>)>) -> (Option<String>, Option<String>) {
+    match tables {
+        Some((table, to_table)) => (Some(table), to_table),
+        None => (None, None),
+    }
+}
+
 pub(crate) fn parse_col_range(input: KSpan<'_>) -> KTokenizerResult<'_, ColRange> {
     Track.enter(CRColRange, input);
 
@@ -209,7 +418,7 @@ pub(crate) fn parse_row_range(input: KSpan<'_>) -> KTokenizerResult<'_, RowRange
 }
 
 mod conv {
-    use crate::refs::parser::KSpan;
+    use crate::refs::parser::{CRCode, KSpan, KTokenizerError};
     #[cfg(not(debug_assertions))]
     use kparse::prelude::*;
     use std::error::Error;
@@ -303,6 +512,29 @@ mod conv {
         }
     }
 
+    /// Row or column of R1C1. The number is absolute and 1-based or an
+    /// offset to the base. Without a number it's the base itself.
+    pub(crate) fn try_u32_from_r1c1<'s>(
+        v: Option<(bool, KSpan<'s>)>,
+        base: u32,
+        code: CRCode,
+    ) -> Result<(bool, u32), nom::Err<KTokenizerError<'s>>> {
+        match v {
+            None => Ok((false, base)),
+            Some((true, num)) => match try_u32_from_rowname(num) {
+                Ok(v) => Ok((true, v)),
+                Err(_) => Err(KTokenizerError::new(code, num).error()),
+            },
+            Some((false, num)) => {
+                let offset = i64::from_str(num.fragment().trim_start_matches('+'));
+                match offset.map(|v| u32::try_from(base as i64 + v)) {
+                    Ok(Ok(v)) => Ok((false, v)),
+                    _ => Err(KTokenizerError::new(code, num).error()),
+                }
+            }
+        }
+    }
+
     /// Error for try_u32_from_colname.
     #[allow(variant_size_differences)]
     #[derive(Debug, Clone, PartialEq, Eq)]
@@ -339,14 +571,16 @@ mod conv {
             }
 
             let mut v = c as u32 - b'A' as u32;
-            if v == 25 {
+            let shifted = if v == 25 {
                 v = 0;
-                col = (col + 1) * 26;
+                col.checked_add(1).and_then(|col| col.checked_mul(26))
             } else {
                 v += 1;
-                col *= 26;
-            }
-            col += v;
+                col.checked_mul(26)
+            };
+            col = shifted
+                .and_then(|col| col.checked_add(v))
+                .ok_or(ParseColnameError::InvalidColname)?;
         }
 
         if col == 0 {
@@ -360,14 +594,15 @@ mod conv {
 #[allow(clippy::module_inception)]
 mod parser {
     use crate::refs::parser::tokens::{
-        col, dollar_nom, dot, hashtag, row, single_quoted_string, unquoted_sheet_name,
+        col, col_letter, colon, dollar_nom, dot, exclamation, hashtag, r1c1_number, row,
+        row_letter, single_quoted_string, unquoted_sheet_name,
     };
     use crate::refs::parser::CRCode::*;
     use crate::refs::parser::{conv, KSpan, KTokenizerError, KTokenizerResult};
     use kparse::combinators::track;
     use kparse::prelude::*;
     use nom::combinator::opt;
-    use nom::sequence::{terminated, tuple};
+    use nom::sequence::{preceded, terminated, tuple};
     use nom::Parser;
 
     pub(crate) fn parse_iri(input: KSpan<'_>) -> KTokenizerResult<'_, Option<String>> {
@@ -397,6 +632,42 @@ mod parser {
         .parse(input)
     }
 
+    /// Sheet name in A1 notation. Sheet1!
+    pub(crate) fn parse_sheet_name_a1(input: KSpan<'_>) -> KTokenizerResult<'_, String> {
+        terminated(single_quoted_string.or(unquoted_sheet_name), exclamation).parse(input)
+    }
+
+    /// Sheet names in A1 notation. Sheet1! or Sheet1:Sheet3!
+    pub(crate) fn parse_sheet_names_a1(
+        input: KSpan<'_>,
+    ) -> KTokenizerResult<'_, (String, Option<String>)> {
+        terminated(
+            tuple((
+                single_quoted_string.or(unquoted_sheet_name),
+                opt(preceded(
+                    colon,
+                    single_quoted_string.or(unquoted_sheet_name),
+                )),
+            )),
+            exclamation,
+        )
+        .parse(input)
+    }
+
+    /// Row in R1C1 notation, relative to the base row.
+    pub(crate) fn parse_row_r1c1(input: KSpan<'_>, base: u32) -> KTokenizerResult<'_, (bool, u32)> {
+        preceded(row_letter, opt(r1c1_number))
+            .map_res(|v| conv::try_u32_from_r1c1(v, base, CRRowInteger))
+            .parse(input)
+    }
+
+    /// Column in R1C1 notation, relative to the base column.
+    pub(crate) fn parse_col_r1c1(input: KSpan<'_>, base: u32) -> KTokenizerResult<'_, (bool, u32)> {
+        preceded(col_letter, opt(r1c1_number))
+            .map_res(|v| conv::try_u32_from_r1c1(v, base, CRColInteger))
+            .parse(input)
+    }
+
     pub(crate) fn parse_row(input: KSpan<'_>) -> KTokenizerResult<'_, (bool, u32)> {
         track(CRRow, row)
             .map_res(|(abs, row)| {
@@ -431,11 +702,11 @@ mod tokens {
     use kparse::combinators::pchar;
     use kparse::prelude::*;
     use nom::branch::alt;
-    use nom::bytes::complete::{tag, take_while1};
+    use nom::bytes::complete::{tag, tag_no_case, take_while1};
     use nom::character::complete::{alpha1, char as nchar, digit1};
     use nom::combinator::{opt, recognize};
     use nom::multi::{count, many0};
-    use nom::sequence::tuple;
+    use nom::sequence::{delimited, tuple};
     use nom::Parser;
 
     const SINGLE_QUOTE: char = '\'';
@@ -507,6 +778,45 @@ mod tokens {
         tag(":").with_code(CRColon).parse(input)
     }
 
+    /// Parse exclamation mark
+    pub(crate) fn exclamation(input: KSpan<'_>) -> KTokenizerResult<'_, KSpan<'_>> {
+        tag("!").with_code(CRExclamation).parse(input)
+    }
+
+    /// Parse a list separator
+    pub(crate) fn list_separator(input: KSpan<'_>) -> KTokenizerResult<'_, KSpan<'_>> {
+        alt((tag(","), tag(";")))
+            .with_code(CRListSeparator)
+            .parse(input)
+    }
+
+    /// R of R1C1
+    pub(crate) fn row_letter(input: KSpan<'_>) -> KTokenizerResult<'_, KSpan<'_>> {
+        tag_no_case("R").with_code(CRRowLetter).parse(input)
+    }
+
+    /// C of R1C1
+    pub(crate) fn col_letter(input: KSpan<'_>) -> KTokenizerResult<'_, KSpan<'_>> {
+        tag_no_case("C").with_code(CRColLetter).parse(input)
+    }
+
+    // R1C1Number ::= [0-9]+ | '[' [+-]? [0-9]+ ']'
+    /// Absolute number or relative offset in brackets. The flag is true
+    /// for the absolute number.
+    pub(crate) fn r1c1_number(input: KSpan<'_>) -> KTokenizerResult<'_, (bool, KSpan<'_>)> {
+        alt((
+            digit1.map(|v| (true, v)),
+            delimited(
+                tag("["),
+                recognize(tuple((opt(alt((tag("-"), tag("+")))), digit1))),
+                tag("]"),
+            )
+            .map(|v| (false, v)),
+        ))
+        .with_code(CROffset)
+        .parse(input)
+    }
+
     // Column ::= '$'? [A-Z]+
     /// Column label
     pub(crate) fn col(i: KSpan<'_>) -> KTokenizerResult<'_, (Option<KSpan<'_>>, KSpan<'_>)> {
@@ -532,7 +842,10 @@ mod tokens {
 mod tests {
     use crate::refs::parser::tokens::{col, row};
     use crate::refs::parser::CRCode::*;
-    use crate::refs::parser::{parse_cell_range, parse_cell_ref, parse_col_range, parse_row_range};
+    use crate::refs::parser::{
+        parse_cell_range, parse_cell_range_a1, parse_cell_ref, parse_cell_ref_r1c1,
+        parse_col_range, parse_col_range_a1, parse_row_range, parse_row_range_r1c1,
+    };
     use crate::{CellRange, CellRef, ColRange, RowRange};
     use kparse::test::{str_parse, CheckTrace};
 
@@ -704,6 +1017,93 @@ mod tests {
         .q(R);
     }
 
+    #[test]
+    pub(crate) fn test_cellrange_a1() {
+        fn table(result: &CellRange, test: &str) -> bool {
+            match result.table() {
+                Some(table) => table == test,
+                None => false,
+            }
+        }
+        fn row_col(result: &CellRange, test: &(u32, u32, u32, u32)) -> bool {
+            (result.row(), result.col(), result.to_row(), result.to_col()) == *test
+        }
+
+        str_parse(&mut None, "", parse_cell_range_a1)
+            .err(CRCol)
+            .q(R);
+        str_parse(&mut None, "A1:C3", parse_cell_range_a1)
+            .ok(row_col, &(0, 0, 2, 2))
+            .q(R);
+        str_parse(&mut None, "B2", parse_cell_range_a1)
+            .ok(row_col, &(1, 1, 1, 1))
+            .q(R);
+        str_parse(&mut None, "Sheet1!$A$1:$C$3", parse_cell_range_a1)
+            .ok(table, "Sheet1")
+            .q(R);
+        str_parse(&mut None, "'my sheet'!A1", parse_cell_range_a1)
+            .ok(table, "my sheet")
+            .q(R);
+        str_parse(&mut None, "A1:C", parse_cell_range_a1)
+            .ok_any()
+            .rest(":C")
+            .q(R);
+        str_parse(&mut None, "!A1", parse_cell_range_a1)
+            .err(CRCol)
+            .q(R);
+    }
+
+    #[test]
+    pub(crate) fn test_colrange_a1() {
+        fn cols(result: &ColRange, test: &(u32, u32)) -> bool {
+            (result.col(), result.to_col()) == *test
+        }
+
+        str_parse(&mut None, "A:C", parse_col_range_a1)
+            .ok(cols, &(0, 2))
+            .q(R);
+        str_parse(&mut None, "Sheet1!$B:$B", parse_col_range_a1)
+            .ok(cols, &(1, 1))
+            .q(R);
+        str_parse(&mut None, "A1:C3", parse_col_range_a1)
+            .err(CRColon)
+            .q(R);
+    }
+
+    #[test]
+    pub(crate) fn test_r1c1() {
+        fn row_col(result: &CellRef, test: &(bool, u32, bool, u32)) -> bool {
+            (
+                result.row_abs(),
+                result.row(),
+                result.col_abs(),
+                result.col(),
+            ) == *test
+        }
+        fn rows(result: &RowRange, test: &(u32, u32)) -> bool {
+            (result.row(), result.to_row()) == *test
+        }
+
+        str_parse(&mut None, "R2C3", |i| parse_cell_ref_r1c1(i, 5, 5))
+            .ok(row_col, &(true, 1, true, 2))
+            .q(R);
+        str_parse(&mut None, "RC[-1]", |i| parse_cell_ref_r1c1(i, 5, 5))
+            .ok(row_col, &(false, 5, false, 4))
+            .q(R);
+        str_parse(&mut None, "R[1]C", |i| parse_cell_ref_r1c1(i, 5, 5))
+            .ok(row_col, &(false, 6, false, 5))
+            .q(R);
+        str_parse(&mut None, "RC[-6]", |i| parse_cell_ref_r1c1(i, 5, 5))
+            .err(CRColInteger)
+            .q(R);
+        str_parse(&mut None, "C1", |i| parse_cell_ref_r1c1(i, 5, 5))
+            .err(CRRowLetter)
+            .q(R);
+        str_parse(&mut None, "R1:R[2]", |i| parse_row_range_r1c1(i, 1))
+            .ok(rows, &(0, 3))
+            .q(R);
+    }
+
     #[test]
     pub(crate) fn colrange() {
         fn iri(result: &ColRange, test: &str) -> bool {
@@ -0,0 +1,563 @@
+//!
+//! A list of cell ranges with set operations.
+//!
+
+use crate::refs::format_refs::fmt_cell_range_a1;
+use crate::refs::parser;
+use crate::refs::parser::CRCode::CRCellRangeList;
+use crate::refs::{format_cellranges, parse_all, CellRange, CellRef};
+use crate::OdsError;
+use get_size::GetSize;
+use get_size_derive::GetSize;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// Order of the cells when iterating a [CellRangeList].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellOrder {
+    /// All cells of a row, then the next row.
+    RowMajor,
+    /// All cells of a column, then the next column.
+    ColMajor,
+}
+
+/// A list of cell ranges, as used for print ranges, selections or the
+/// target ranges of a conditional format.
+///
+/// Ranges of different tables never overlap. Reversed ranges like "B3:A1"
+/// are stored with swapped corners. The result of intersect(),
+/// union() and subtract() is normalized: the ranges don't overlap and are
+/// sorted by row and column.
+///
+/// ```
+/// use spreadsheet_ods::refs::CellRangeList;
+/// use spreadsheet_ods::CellRange;
+///
+/// let a = CellRangeList::parse_a1("A1:C3").unwrap();
+/// let b = CellRangeList::from(CellRange::local(1, 1, 1, 1));
+/// assert_eq!(a.subtract(&b).to_a1(), "A1:C1,A2,C2,A3:C3");
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, GetSize)]
+pub struct CellRangeList {
+    ranges: Vec<CellRange>,
+}
+
+impl CellRangeList {
+    /// Empty list.
+    pub fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    /// Parses the ODF form. The ranges are separated by whitespace.
+    /// Example: "Sheet1.A1:.C3 Sheet1.E1:.E5"
+    pub fn parse(buf: &str) -> Result<Self, OdsError> {
+        let ranges = parse_all(buf, CRCellRangeList, parser::parse_cell_range_list)?;
+        Ok(ranges.unwrap_or_default().into())
+    }
+
+    /// Parses the A1 form. The ranges are separated by ',' or ';', single
+    /// cells are allowed. Example: "Sheet1!A1:C3,E1"
+    pub fn parse_a1(buf: &str) -> Result<Self, OdsError> {
+        let ranges = parse_all(buf, CRCellRangeList, parser::parse_cell_range_list_a1)?;
+        Ok(ranges.into())
+    }
+
+    /// Formats the list in A1 form. External references are not part of
+    /// the A1 form and are dropped.
+    pub fn to_a1(&self) -> String {
+        let mut buf = String::new();
+        for (i, range) in self.ranges.iter().enumerate() {
+            if i > 0 {
+                buf.push(',');
+            }
+            let _ = fmt_cell_range_a1(&mut buf, range);
+        }
+        buf
+    }
+
+    /// Appends a range. The list is not normalized.
+    pub fn push(&mut self, range: CellRange) {
+        self.ranges.push(ordered(range));
+    }
+
+    /// The ranges.
+    pub fn ranges(&self) -> &[CellRange] {
+        &self.ranges
+    }
+
+    /// Number of ranges.
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// No ranges.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Iterates the ranges.
+    pub fn iter(&self) -> std::slice::Iter<'_, CellRange> {
+        self.ranges.iter()
+    }
+
+    /// Does any range contain the cell.
+    /// Only the rows and columns are compared, not the tables.
+    pub fn contains(&self, row: u32, col: u32) -> bool {
+        self.ranges.iter().any(|v| v.contains(row, col))
+    }
+
+    /// Merges overlapping and adjacent ranges into a list of
+    /// non-overlapping ranges.
+    pub fn normalize(&self) -> CellRangeList {
+        let mut ranges = Vec::new();
+        for group in group_by_table(&self.ranges) {
+            normalize_group(&group, &mut ranges);
+        }
+        Self { ranges }
+    }
+
+    /// All cells that are in both lists.
+    pub fn intersect(&self, other: &CellRangeList) -> CellRangeList {
+        let ranges = self
+            .ranges
+            .iter()
+            .flat_map(|a| {
+                other
+                    .ranges
+                    .iter()
+                    .filter(|b| same_table(a, b))
+                    .filter_map(|b| a.intersection(b))
+            })
+            .collect();
+        Self { ranges }.normalize()
+    }
+
+    /// All cells that are in any of the lists.
+    pub fn union(&self, other: &CellRangeList) -> CellRangeList {
+        let ranges = self.ranges.iter().chain(other.ranges.iter()).cloned();
+        Self {
+            ranges: ranges.collect(),
+        }
+        .normalize()
+    }
+
+    /// All cells of this list that are not in the other list.
+    pub fn subtract(&self, other: &CellRangeList) -> CellRangeList {
+        let mut ranges = Vec::new();
+        for a in &self.ranges {
+            let mut pieces = vec![a.clone()];
+            for b in other.ranges.iter().filter(|b| same_table(a, b)) {
+                pieces = pieces.iter().flat_map(|p| subtract_range(p, b)).collect();
+            }
+            ranges.extend(pieces);
+        }
+        Self { ranges }.normalize()
+    }
+
+    /// Grows each range by the given number of rows and columns in all
+    /// directions. The ranges stop at row and column 0.
+    pub fn expand(&self, rows: u32, cols: u32) -> CellRangeList {
+        let ranges = self
+            .ranges
+            .iter()
+            .map(|v| {
+                with_bounds(
+                    v,
+                    v.row().saturating_sub(rows),
+                    v.col().saturating_sub(cols),
+                    v.to_row().saturating_add(rows),
+                    v.to_col().saturating_add(cols),
+                )
+            })
+            .collect();
+        Self { ranges }
+    }
+
+    /// Shrinks each range by the given number of rows and columns in all
+    /// directions. Ranges that vanish are removed.
+    pub fn shrink(&self, rows: u32, cols: u32) -> CellRangeList {
+        let ranges = self
+            .ranges
+            .iter()
+            .filter_map(|v| {
+                let row = v.row().checked_add(rows)?;
+                let col = v.col().checked_add(cols)?;
+                let to_row = v.to_row().checked_sub(rows)?;
+                let to_col = v.to_col().checked_sub(cols)?;
+                (row <= to_row && col <= to_col).then(|| with_bounds(v, row, col, to_row, to_col))
+            })
+            .collect();
+        Self { ranges }
+    }
+
+    /// Moves all ranges. Fails if a range would move outside of the
+    /// sheet.
+    pub fn offset(&self, rows: i32, cols: i32) -> Result<CellRangeList, OdsError> {
+        let mut ranges = Vec::new();
+        for v in &self.ranges {
+            let moved = (|| {
+                Some(with_bounds(
+                    v,
+                    v.row().checked_add_signed(rows)?,
+                    v.col().checked_add_signed(cols)?,
+                    v.to_row().checked_add_signed(rows)?,
+                    v.to_col().checked_add_signed(cols)?,
+                ))
+            })();
+            match moved {
+                Some(moved) => ranges.push(moved),
+                None => {
+                    return Err(OdsError::Ods(format!(
+                        "range {} can't be moved by {},{}",
+                        v, rows, cols
+                    )))
+                }
+            }
+        }
+        Ok(Self { ranges })
+    }
+
+    /// Smallest range that contains all ranges. The table is taken from
+    /// the first range.
+    pub fn bounding_box(&self) -> Option<CellRange> {
+        let first = self.ranges.first()?;
+        let mut bbox = first.clone();
+        for v in &self.ranges[1..] {
+            bbox = with_bounds(
+                &bbox,
+                bbox.row().min(v.row()),
+                bbox.col().min(v.col()),
+                bbox.to_row().max(v.to_row()),
+                bbox.to_col().max(v.to_col()),
+            );
+        }
+        Some(bbox)
+    }
+
+    /// Iterates all cells. Each cell is returned only once, even if it
+    /// is part of more than one range. The cells of each table are
+    /// returned together, in order of the first appearance of the table.
+    pub fn cells(&self, order: CellOrder) -> RangeCellIter {
+        let groups = group_by_table(&self.ranges)
+            .into_iter()
+            .map(|group| {
+                let table = group[0].table().cloned();
+                let mut norm = Vec::new();
+                normalize_group(&group, &mut norm);
+                let rects = norm
+                    .iter()
+                    .map(|v| match order {
+                        CellOrder::RowMajor => (v.row(), v.to_row(), v.col(), v.to_col()),
+                        CellOrder::ColMajor => (v.col(), v.to_col(), v.row(), v.to_row()),
+                    })
+                    .collect();
+                (table, rects)
+            })
+            .collect();
+
+        let mut iter = RangeCellIter {
+            order,
+            groups,
+            group: 0,
+            line: 0,
+            spans: Vec::new(),
+            span: 0,
+            pos: None,
+        };
+        iter.start_group();
+        iter
+    }
+}
+
+/// Rectangle as (line, to_line, pos, to_pos). Lines are rows or columns
+/// depending on the order.
+type Rect = (u32, u32, u32, u32);
+
+/// Iterator over the cells of a [CellRangeList].
+#[derive(Debug)]
+pub struct RangeCellIter {
+    order: CellOrder,
+    // table and rectangles.
+    groups: Vec<(Option<String>, Vec<Rect>)>,
+    group: usize,
+    line: u32,
+    // spans of the current line.
+    spans: Vec<(u32, u32)>,
+    span: usize,
+    pos: Option<u32>,
+}
+
+impl RangeCellIter {
+    fn start_group(&mut self) {
+        while let Some((_, rects)) = self.groups.get(self.group) {
+            if let Some(line) = rects.iter().map(|v| v.0).min() {
+                self.load_line(line);
+                return;
+            }
+            self.group += 1;
+        }
+    }
+
+    fn load_line(&mut self, line: u32) {
+        self.line = line;
+        let rects = &self.groups[self.group].1;
+        self.spans = rects
+            .iter()
+            .filter(|v| v.0 <= line && line <= v.1)
+            .map(|v| (v.2, v.3))
+            .collect();
+        self.spans.sort();
+        self.span = 0;
+        self.pos = self.spans.first().map(|v| v.0);
+    }
+
+    fn next_line(&mut self) {
+        let rects = &self.groups[self.group].1;
+        let line = match self.line.checked_add(1) {
+            Some(next) => rects
+                .iter()
+                .filter(|v| v.1 >= next)
+                .map(|v| v.0.max(next))
+                .min(),
+            None => None,
+        };
+        match line {
+            Some(line) => self.load_line(line),
+            None => {
+                self.group += 1;
+                self.start_group();
+            }
+        }
+    }
+}
+
+impl Iterator for RangeCellIter {
+    type Item = CellRef;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (table, _) = self.groups.get(self.group)?;
+            if let Some(pos) = self.pos {
+                let (row, col) = match self.order {
+                    CellOrder::RowMajor => (self.line, pos),
+                    CellOrder::ColMajor => (pos, self.line),
+                };
+                let mut cell = CellRef::local(row, col);
+                if let Some(table) = table {
+                    cell.set_table(table);
+                }
+
+                let (_, to_pos) = self.spans[self.span];
+                self.pos = if pos < to_pos {
+                    Some(pos + 1)
+                } else {
+                    self.span += 1;
+                    self.spans.get(self.span).map(|v| v.0)
+                };
+                return Some(cell);
+            }
+            self.next_line();
+        }
+    }
+}
+
+impl Display for CellRangeList {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", format_cellranges(&self.ranges))
+    }
+}
+
+impl TryFrom<&str> for CellRangeList {
+    type Error = OdsError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        CellRangeList::parse(s)
+    }
+}
+
+impl From<CellRange> for CellRangeList {
+    fn from(range: CellRange) -> Self {
+        Self {
+            ranges: vec![ordered(range)],
+        }
+    }
+}
+
+impl From<Vec<CellRange>> for CellRangeList {
+    fn from(ranges: Vec<CellRange>) -> Self {
+        ranges.into_iter().collect()
+    }
+}
+
+impl From<CellRangeList> for Vec<CellRange> {
+    fn from(list: CellRangeList) -> Self {
+        list.ranges
+    }
+}
+
+impl FromIterator<CellRange> for CellRangeList {
+    fn from_iter<T: IntoIterator<Item = CellRange>>(iter: T) -> Self {
+        Self {
+            ranges: iter.into_iter().map(ordered).collect(),
+        }
+    }
+}
+
+impl IntoIterator for CellRangeList {
+    type Item = CellRange;
+    type IntoIter = std::vec::IntoIter<CellRange>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a CellRangeList {
+    type Item = &'a CellRange;
+    type IntoIter = std::slice::Iter<'a, CellRange>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.iter()
+    }
+}
+
+/// Copy of the range with other rows and columns.
+fn with_bounds(range: &CellRange, row: u32, col: u32, to_row: u32, to_col: u32) -> CellRange {
+    let mut range = range.clone();
+    range.set_row(row);
+    range.set_col(col);
+    range.set_to_row(to_row);
+    range.set_to_col(to_col);
+    range
+}
+
+/// Swaps the corners of a reversed range, the absolute flags move along.
+fn ordered(mut range: CellRange) -> CellRange {
+    if range.row() > range.to_row() {
+        let (row, row_abs) = (range.row(), range.row_abs());
+        range.set_row(range.to_row());
+        range.set_row_abs(range.to_row_abs());
+        range.set_to_row(row);
+        range.set_to_row_abs(row_abs);
+    }
+    if range.col() > range.to_col() {
+        let (col, col_abs) = (range.col(), range.col_abs());
+        range.set_col(range.to_col());
+        range.set_col_abs(range.to_col_abs());
+        range.set_to_col(col);
+        range.set_to_col_abs(col_abs);
+    }
+    range
+}
+
+/// Ranges can only overlap if they refer to the same tables.
+fn same_table(a: &CellRange, b: &CellRange) -> bool {
+    a.iri() == b.iri() && a.table() == b.table() && a.to_table() == b.to_table()
+}
+
+/// Groups the ranges by table, in order of the first appearance.
+fn group_by_table(ranges: &[CellRange]) -> Vec<Vec<&CellRange>> {
+    let mut groups: Vec<Vec<&CellRange>> = Vec::new();
+    for range in ranges {
+        match groups.iter_mut().find(|v| same_table(v[0], range)) {
+            Some(group) => group.push(range),
+            None => groups.push(vec![range]),
+        }
+    }
+    groups
+}
+
+/// The parts of a that are not covered by b.
+fn subtract_range(a: &CellRange, b: &CellRange) -> Vec<CellRange> {
+    let Some(i) = a.intersection(b) else {
+        return vec![a.clone()];
+    };
+    let mut res = Vec::new();
+    if a.row() < i.row() {
+        res.push(with_bounds(a, a.row(), a.col(), i.row() - 1, a.to_col()));
+    }
+    if i.col() > a.col() {
+        res.push(with_bounds(a, i.row(), a.col(), i.to_row(), i.col() - 1));
+    }
+    if i.to_col() < a.to_col() {
+        res.push(with_bounds(
+            a,
+            i.row(),
+            i.to_col() + 1,
+            i.to_row(),
+            a.to_col(),
+        ));
+    }
+    if i.to_row() < a.to_row() {
+        res.push(with_bounds(
+            a,
+            i.to_row() + 1,
+            a.col(),
+            a.to_row(),
+            a.to_col(),
+        ));
+    }
+    res
+}
+
+/// Normalizes ranges of the same table.
+///
+/// The rows are cut into bands where the same ranges are involved.
+/// For each band the columns are merged, and equal column-spans of
+/// adjacent bands are joined again.
+fn normalize_group(group: &[&CellRange], res: &mut Vec<CellRange>) {
+    let template = group[0];
+
+    let mut bounds = Vec::new();
+    for v in group {
+        bounds.push(v.row() as u64);
+        bounds.push(v.to_row() as u64 + 1);
+    }
+    bounds.sort_unstable();
+    bounds.dedup();
+
+    // rows are the lines here.
+    let mut open: Vec<Rect> = Vec::new();
+    let mut done: Vec<Rect> = Vec::new();
+    for band in bounds.windows(2) {
+        let row = band[0] as u32;
+        let to_row = (band[1] - 1) as u32;
+
+        let mut cols = group
+            .iter()
+            .filter(|v| v.row() <= row && to_row <= v.to_row())
+            .map(|v| (v.col(), v.to_col()))
+            .collect::<Vec<_>>();
+        cols.sort_unstable();
+        let mut merged: Vec<(u32, u32)> = Vec::new();
+        for (col, to_col) in cols {
+            match merged.last_mut() {
+                Some(last) if col as u64 <= last.1 as u64 + 1 => last.1 = last.1.max(to_col),
+                _ => merged.push((col, to_col)),
+            }
+        }
+
+        let mut next_open = Vec::new();
+        for (col, to_col) in merged {
+            match open
+                .iter()
+                .position(|v| v.1 as u64 + 1 == row as u64 && v.2 == col && v.3 == to_col)
+            {
+                Some(idx) => {
+                    let mut rect = open.swap_remove(idx);
+                    rect.1 = to_row;
+                    next_open.push(rect);
+                }
+                None => next_open.push((row, to_row, col, to_col)),
+            }
+        }
+        done.append(&mut open);
+        open = next_open;
+    }
+    done.append(&mut open);
+
+    done.sort_unstable();
+    for (row, to_row, col, to_col) in done {
+        res.push(with_bounds(template, row, col, to_row, to_col));
+    }
+}
@@ -10,13 +10,18 @@ use std::iter::FusedIterator;
 use std::ops::RangeBounds;
 use std::{fmt, mem};
 
-use crate::cell_::{CellContent, CellContentRef, CellData};
-use crate::draw::{Annotation, DrawFrame};
+use crate::cell_::{ArrayFormula, CellContent, CellContentRef, CellData, CellSpan, MergeMode};
+use crate::draw::{
+    Annotation, DrawFrame, DrawImage, DrawShape, ImageInfo, ImageOptions, PageDrawing,
+};
+use crate::find::{self, FindQuery};
+use crate::form::{Form, FormControl};
+use crate::sort::{self, SortKeys};
 use crate::style::{ColStyleRef, RowStyleRef, TableStyleRef};
 use crate::validation::ValidationRef;
 use crate::value_::Value;
 use crate::xmltree::XmlTag;
-use crate::{CellRange, CellStyleRef, Length, OdsError};
+use crate::{CellRange, CellRef, CellStyleRef, Length, OdsError};
 
 #[cfg(test)]
 mod tests;
@@ -59,6 +64,9 @@ pub(crate) struct RowHeader {
     /// on reading.
     pub(crate) span: u32,
     pub(crate) height: Length,
+    /// Manual page break before this row. Synced with fo:break-before
+    /// of the row style.
+    pub(crate) page_break: bool,
 }
 
 impl Default for RowHeader {
@@ -70,6 +78,7 @@ impl Default for RowHeader {
             repeat: 1,
             span: 1,
             height: Default::default(),
+            page_break: false,
         }
     }
 }
@@ -81,6 +90,9 @@ pub(crate) struct ColHeader {
     pub(crate) cellstyle: Option<CellStyleRef>,
     pub(crate) visible: Visibility,
     pub(crate) width: Length,
+    /// Manual page break before this column. Synced with fo:break-before
+    /// of the column style.
+    pub(crate) page_break: bool,
     /// Logical valid range for all the header values. Avoids duplication
     /// on reading.
     pub(crate) span: u32,
@@ -93,6 +105,7 @@ impl Default for ColHeader {
             cellstyle: None,
             visible: Default::default(),
             width: Default::default(),
+            page_break: false,
             span: 1,
         }
     }
@@ -125,6 +138,12 @@ pub struct Sheet {
 
     pub(crate) sheet_config: SheetConfig,
 
+    // table:shapes, anchored to the page.
+    pub(crate) page_drawings: Vec<PageDrawing>,
+
+    // office:forms
+    pub(crate) forms: Vec<Form>,
+
     pub(crate) extra: Vec<XmlTag>,
 }
 
@@ -515,6 +534,12 @@ impl Debug for Sheet {
         for v in &self.group_rows {
             writeln!(f, "group rows {:?}", v)?;
         }
+        for v in &self.page_drawings {
+            writeln!(f, "page drawing {:?}", v)?;
+        }
+        for v in &self.forms {
+            writeln!(f, "form {:?}", v)?;
+        }
         for xtr in &self.extra {
             writeln!(f, "extras {:?}", xtr)?;
         }
@@ -550,6 +575,8 @@ impl Sheet {
             group_rows: Default::default(),
             group_cols: Default::default(),
             sheet_config: Default::default(),
+            page_drawings: Default::default(),
+            forms: Default::default(),
             extra: vec![],
             row_header: Default::default(),
             display: true,
@@ -573,6 +600,8 @@ impl Sheet {
             group_rows: self.group_rows.clone(),
             group_cols: self.group_cols.clone(),
             sheet_config: Default::default(),
+            page_drawings: self.page_drawings.clone(),
+            forms: self.forms.clone(),
             extra: self.extra.clone(),
         }
     }
@@ -587,6 +616,37 @@ impl Sheet {
         self.data.len()
     }
 
+    /// Finds all cells that match the query. The cell references
+    /// contain the sheet name.
+    ///
+    /// Fails if the query is an invalid regex.
+    pub fn find(&self, query: &FindQuery) -> Result<Vec<CellRef>, OdsError> {
+        Ok(find::find_in_sheet(self, &query.matcher()?))
+    }
+
+    /// Replaces the matches of the query in all cells. The formatting
+    /// of rich text is preserved, but matches across differently
+    /// formatted parts are not found. Returns the number of changed cells.
+    ///
+    /// Fails if the query is an invalid regex.
+    pub fn replace_all(&mut self, query: &FindQuery, replacement: &str) -> Result<usize, OdsError> {
+        let matcher = query.matcher()?;
+        Ok(find::replace_in_sheet(self, &matcher, replacement))
+    }
+
+    /// Sorts the cells of the range by the given keys.
+    ///
+    /// The complete cells are moved, including styles, annotations and
+    /// validations. Relative references in formulas that point into the
+    /// same row (or column) are adjusted to the new position. Row heights
+    /// and column widths stay in place. The sort is stable.
+    ///
+    /// Fails if a sort key is outside the range or the range contains
+    /// merged cells, repeated cells or repeated rows.
+    pub fn sort_range(&mut self, range: &CellRange, keys: &SortKeys) -> Result<(), OdsError> {
+        sort::sort_range(self, range, keys)
+    }
+
     /// Iterate the range row-wise.
     ///
     /// If there is no upper bound this uses used_grid_size(), which
@@ -692,11 +752,11 @@ impl Sheet {
                 }
 
                 // back
-                if *base_col + base_span > col {
+                if *base_col + base_span > col + 1 {
                     let mut clone = col_header.clone();
                     clone.span = *base_col + base_span - (col + 1);
                     cloned.push((col + 1, clone));
-                } else if *base_col + base_span == col {
+                } else if *base_col + base_span == col + 1 {
                     // noop
                 } else {
                     unreachable!();
@@ -838,11 +898,11 @@ impl Sheet {
                 }
 
                 // back
-                if *base_row + base_span > row {
+                if *base_row + base_span > row + 1 {
                     let mut clone = row_header.clone();
                     clone.span = *base_row + base_span - (row + 1);
                     cloned.push((row + 1, clone));
-                } else if *base_row + base_span == row {
+                } else if *base_row + base_span == row + 1 {
                     // noop
                 } else {
                     unreachable!();
@@ -1228,6 +1288,213 @@ impl Sheet {
         }
     }
 
+    /// Sets an array formula for the range.
+    ///
+    /// The formula is stored with the first cell of the range and the
+    /// matrix spans are set to the size of the range. All other cells
+    /// of the range hold the results of the formula. Their values and
+    /// formulas are cleared, the application recalculates them when the
+    /// file is opened. Cached results can be set with set_value()
+    /// afterwards.
+    ///
+    /// An existing array formula that lies completely within the range
+    /// is replaced. A range of a single cell gives a normal formula.
+    ///
+    /// Fails if the range refers to another sheet or if it overlaps only
+    /// part of another array formula.
+    pub fn set_array_formula<V: Into<String>>(
+        &mut self,
+        range: &CellRange,
+        formula: V,
+    ) -> Result<(), OdsError> {
+        if let Some(table) = range.table() {
+            if table != &self.name {
+                return Err(OdsError::Ods(format!(
+                    "range {} is not in sheet {}",
+                    range, self.name
+                )));
+            }
+        }
+        if range.row() > range.to_row() || range.col() > range.to_col() {
+            return Err(OdsError::Ods(format!("invalid range {}", range)));
+        }
+        if let Some(array) = self
+            .array_formulas()
+            .find(|v| v.range.intersects(range) && !range.contains_range(&v.range))
+        {
+            return Err(OdsError::Ods(format!(
+                "range {} overlaps the array formula at {}",
+                range, array.range
+            )));
+        }
+
+        self.clear_range_results(range);
+
+        let cell = self.data.entry((range.row(), range.col())).or_default();
+        cell.formula = Some(formula.into());
+        let span = &mut cell.extra_mut().matrix_span;
+        span.set_row_span(range.to_row() - range.row() + 1);
+        span.set_col_span(range.to_col() - range.col() + 1);
+
+        Ok(())
+    }
+
+    /// Returns the array formula that covers the cell.
+    pub fn array_formula_at(&self, row: u32, col: u32) -> Option<ArrayFormula<'_>> {
+        self.array_formulas().find(|v| v.range.contains(row, col))
+    }
+
+    /// Iterates all array formulas of the sheet.
+    pub fn array_formulas(&self) -> impl Iterator<Item = ArrayFormula<'_>> {
+        self.data.iter().filter_map(|((row, col), cell)| {
+            let extra = cell.extra.as_ref()?;
+            if extra.matrix_span.is_empty() {
+                return None;
+            }
+            Some(ArrayFormula {
+                range: CellRange::origin_span(*row, *col, extra.matrix_span.into()),
+                formula: cell.formula.as_deref()?,
+            })
+        })
+    }
+
+    /// Removes the array formula that covers the cell. The formula, the
+    /// matrix spans and the results in all the cells of the array are
+    /// cleared. Returns false if there is no array formula.
+    pub fn clear_array_formula(&mut self, row: u32, col: u32) -> bool {
+        let Some(range) = self.array_formula_at(row, col).map(|v| v.range) else {
+            return false;
+        };
+        self.clear_range_results(&range);
+        true
+    }
+
+    /// Clears values, formulas and matrix spans within the range.
+    fn clear_range_results(&mut self, range: &CellRange) {
+        for row in range.row()..=range.to_row() {
+            for (_, cell) in self
+                .data
+                .range_mut((row, range.col())..=(row, range.to_col()))
+            {
+                cell.value = Value::Empty;
+                cell.formula = None;
+                if let Some(extra) = cell.extra.as_mut() {
+                    extra.matrix_span = CellSpan::default();
+                }
+            }
+        }
+    }
+
+    /// Merges the cells of the range.
+    ///
+    /// The spans are set for the first cell of the range, the other cells
+    /// are covered. The mode decides what happens with the content of the
+    /// covered cells. Merged ranges that lie completely within the range
+    /// are replaced. A range of a single cell removes those merges and
+    /// leaves the cell unmerged.
+    ///
+    /// Fails if the range refers to another sheet or if it overlaps only
+    /// part of another merged range.
+    pub fn merge(&mut self, range: &CellRange, mode: MergeMode) -> Result<(), OdsError> {
+        if let Some(table) = range.table() {
+            if table != &self.name {
+                return Err(OdsError::Ods(format!(
+                    "range {} is not in sheet {}",
+                    range, self.name
+                )));
+            }
+        }
+        if range.row() > range.to_row() || range.col() > range.to_col() {
+            return Err(OdsError::Ods(format!("invalid range {}", range)));
+        }
+        if let Some(merged) = self
+            .merged_ranges()
+            .find(|v| v.intersects(range) && !range.contains_range(v))
+        {
+            return Err(OdsError::Ods(format!(
+                "range {} overlaps the merged range {}",
+                range, merged
+            )));
+        }
+
+        let first = (range.row(), range.col());
+        let mut moved = Vec::new();
+        for row in range.row()..=range.to_row() {
+            for (pos, cell) in self
+                .data
+                .range_mut((row, range.col())..=(row, range.to_col()))
+            {
+                if let Some(extra) = cell.extra.as_mut() {
+                    extra.span = CellSpan::default();
+                }
+                if mode == MergeMode::Concatenate && find::value_text(&cell.value).is_some() {
+                    moved.push(cell.value.clone());
+                }
+                if mode != MergeMode::KeepCovered && *pos != first {
+                    cell.value = Value::Empty;
+                    cell.formula = None;
+                }
+            }
+        }
+
+        if range.row() == range.to_row() && range.col() == range.to_col() {
+            return Ok(());
+        }
+
+        let cell = self.data.entry(first).or_default();
+        if moved.len() > 1 {
+            let text = moved
+                .iter()
+                .filter_map(find::value_text)
+                .collect::<Vec<_>>()
+                .join(" ");
+            cell.value = Value::Text(text);
+            cell.formula = None;
+        } else if let Some(value) = moved.pop() {
+            if cell.value == Value::Empty {
+                cell.value = value;
+            }
+        }
+        let span = &mut cell.extra_mut().span;
+        span.set_row_span(range.to_row() - range.row() + 1);
+        span.set_col_span(range.to_col() - range.col() + 1);
+
+        Ok(())
+    }
+
+    /// Removes the merge that covers the cell. The content of the
+    /// covered cells stays as it is. Returns false if the cell is not
+    /// merged.
+    pub fn unmerge(&mut self, row: u32, col: u32) -> bool {
+        let Some(range) = self.merged_range_at(row, col) else {
+            return false;
+        };
+        if let Some(extra) = self
+            .data
+            .get_mut(&(range.row(), range.col()))
+            .and_then(|v| v.extra.as_mut())
+        {
+            extra.span = CellSpan::default();
+        }
+        true
+    }
+
+    /// Returns the merged range that covers the cell.
+    pub fn merged_range_at(&self, row: u32, col: u32) -> Option<CellRange> {
+        self.merged_ranges().find(|v| v.contains(row, col))
+    }
+
+    /// Iterates all merged ranges of the sheet.
+    pub fn merged_ranges(&self) -> impl Iterator<Item = CellRange> + '_ {
+        self.data.iter().filter_map(|((row, col), cell)| {
+            let extra = cell.extra.as_ref()?;
+            if extra.span.is_empty() {
+                return None;
+            }
+            Some(CellRange::origin_span(*row, *col, extra.span.into()))
+        })
+    }
+
     /// Sets a annotation for this cell.
     pub fn set_annotation(&mut self, row: u32, col: u32, annotation: Annotation) {
         let cell = self.data.entry((row, col)).or_default();
@@ -1265,6 +1532,89 @@ impl Sheet {
         cell.extra_mut().draw_frames.push(draw_frame);
     }
 
+    /// Inserts an image anchored at the given cell.
+    ///
+    /// The format (PNG, JPEG, GIF, SVG, WebP) is detected from the data,
+    /// the size of the frame is calculated from the pixel size and resolution
+    /// of the image. See [ImageOptions] for fitting the image to a cell range
+    /// and other options.
+    ///
+    /// The data is embedded in the image. When the workbook is written as .ods
+    /// it is moved to a separate file in the Pictures/ folder of the package.
+    pub fn insert_image(
+        &mut self,
+        anchor: CellRef,
+        bytes: &[u8],
+        opts: &ImageOptions,
+    ) -> Result<(), OdsError> {
+        let Some(info) = ImageInfo::detect(bytes) else {
+            return Err(OdsError::Ods("Unknown image format".to_string()));
+        };
+
+        let (mut width, mut height) = info.size_pt();
+        if let Some((w, h)) = opts.size {
+            width = w.to_pt().unwrap_or(width);
+            height = h.to_pt().unwrap_or(height);
+        } else if let Some(fit) = &opts.fit {
+            let fit_width: f64 = (fit.col()..=fit.to_col())
+                .take(MAX_IMAGE_SPAN as usize)
+                .map(|c| self.col_width_pt(c))
+                .sum();
+            let fit_height: f64 = (fit.row()..=fit.to_row())
+                .take(MAX_IMAGE_SPAN as usize)
+                .map(|r| self.row_height_pt(r))
+                .sum();
+            if opts.stretch || width <= 0.0 || height <= 0.0 {
+                width = fit_width;
+                height = fit_height;
+            } else {
+                let f = (fit_width / width).min(fit_height / height);
+                width *= f;
+                height *= f;
+            }
+        }
+        if let Some(scale) = opts.scale {
+            if !scale.is_finite() || scale <= 0.0 {
+                return Err(OdsError::Ods(format!("Invalid image scale {}", scale)));
+            }
+            width *= scale;
+            height *= scale;
+        }
+        if !width.is_finite() || !height.is_finite() || width <= 0.0 || height <= 0.0 {
+            return Err(OdsError::Ods(format!(
+                "Invalid image size {}x{}pt",
+                width, height
+            )));
+        }
+
+        let mut image = DrawImage::new();
+        image.set_draw_mime_type(info.mime_type());
+        image.set_binary(bytes);
+
+        let mut frame = opts.create_frame(image, width, height);
+        let (end_col, end_x) = end_position(anchor.col(), width, |c| self.col_width_pt(c));
+        let (end_row, end_y) = end_position(anchor.row(), height, |r| self.row_height_pt(r));
+        frame.set_table_end_cell_address(CellRef::remote(self.name.as_str(), end_row, end_col));
+        frame.set_table_end_x(Length::Pt(end_x));
+        frame.set_table_end_y(Length::Pt(end_y));
+
+        self.add_draw_frame(anchor.row(), anchor.col(), frame);
+
+        Ok(())
+    }
+
+    // Column width in pt, with the default width for unset columns.
+    pub(crate) fn col_width_pt(&self, col: u32) -> f64 {
+        self.col_width(col).to_pt().unwrap_or(DEFAULT_COL_WIDTH_PT)
+    }
+
+    // Row height in pt, with the default height for unset rows.
+    pub(crate) fn row_height_pt(&self, row: u32) -> f64 {
+        self.row_height(row)
+            .to_pt()
+            .unwrap_or(DEFAULT_ROW_HEIGHT_PT)
+    }
+
     /// Removes all drawframes.
     pub fn clear_draw_frames(&mut self, row: u32, col: u32) {
         if let Some(cell) = self.data.get_mut(&(row, col)) {
@@ -1290,6 +1640,144 @@ impl Sheet {
         }
     }
 
+    /// Add a shape to a specific cell. The position of the shape is
+    /// relative to the cell.
+    pub fn add_draw_shape(&mut self, row: u32, col: u32, draw_shape: DrawShape) {
+        let cell = self.data.entry((row, col)).or_default();
+        cell.extra_mut().draw_shapes.push(draw_shape);
+    }
+
+    /// Removes all shapes.
+    pub fn clear_draw_shapes(&mut self, row: u32, col: u32) {
+        if let Some(cell) = self.data.get_mut(&(row, col)) {
+            cell.extra_mut().draw_shapes = Vec::new();
+        }
+    }
+
+    /// Returns the shapes.
+    pub fn draw_shapes(&self, row: u32, col: u32) -> Option<&Vec<DrawShape>> {
+        if let Some(CellData { extra: Some(c), .. }) = self.data.get(&(row, col)) {
+            Some(c.draw_shapes.as_ref())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the shapes.
+    pub fn draw_shapes_mut(&mut self, row: u32, col: u32) -> Option<&mut Vec<DrawShape>> {
+        if let Some(CellData { extra: Some(c), .. }) = self.data.get_mut(&(row, col)) {
+            Some(c.draw_shapes.as_mut())
+        } else {
+            None
+        }
+    }
+
+    /// Add a drawframe anchored to the page. The position is relative
+    /// to the top left corner of the sheet.
+    pub fn add_page_frame(&mut self, draw_frame: DrawFrame) {
+        self.page_drawings.push(PageDrawing::Frame(draw_frame));
+    }
+
+    /// Drawframes anchored to the page.
+    pub fn page_frames(&self) -> impl Iterator<Item = &DrawFrame> {
+        self.page_drawings.iter().filter_map(|v| match v {
+            PageDrawing::Frame(v) => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Drawframes anchored to the page.
+    pub fn page_frames_mut(&mut self) -> impl Iterator<Item = &mut DrawFrame> {
+        self.page_drawings.iter_mut().filter_map(|v| match v {
+            PageDrawing::Frame(v) => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Add a shape anchored to the page. The position is relative
+    /// to the top left corner of the sheet.
+    pub fn add_page_shape(&mut self, draw_shape: DrawShape) {
+        self.page_drawings.push(PageDrawing::Shape(draw_shape));
+    }
+
+    /// Shapes anchored to the page.
+    pub fn page_shapes(&self) -> impl Iterator<Item = &DrawShape> {
+        self.page_drawings.iter().filter_map(|v| match v {
+            PageDrawing::Shape(v) => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Shapes anchored to the page.
+    pub fn page_shapes_mut(&mut self) -> impl Iterator<Item = &mut DrawShape> {
+        self.page_drawings.iter_mut().filter_map(|v| match v {
+            PageDrawing::Shape(v) => Some(v),
+            _ => None,
+        })
+    }
+
+    /// All drawings anchored to the page in document order.
+    pub fn page_drawings(&self) -> &Vec<PageDrawing> {
+        &self.page_drawings
+    }
+
+    /// All drawings anchored to the page in document order.
+    pub fn page_drawings_mut(&mut self) -> &mut Vec<PageDrawing> {
+        &mut self.page_drawings
+    }
+
+    /// Adds a form. The controls of the form are placed on the sheet with
+    /// a draw:control shape. See add_form_control() for a shortcut.
+    pub fn add_form(&mut self, form: Form) {
+        self.forms.push(form);
+    }
+
+    /// Forms.
+    pub fn forms(&self) -> &Vec<Form> {
+        &self.forms
+    }
+
+    /// Forms.
+    pub fn forms_mut(&mut self) -> &mut Vec<Form> {
+        &mut self.forms
+    }
+
+    /// Adds the control to the first form of the sheet and places it at
+    /// the given cell. A form is created if necessary.
+    ///
+    /// Fails if the control has no id.
+    pub fn add_form_control(
+        &mut self,
+        row: u32,
+        col: u32,
+        control: FormControl,
+        width: Length,
+        height: Length,
+    ) -> Result<(), OdsError> {
+        let Some(id) = control.id() else {
+            return Err(OdsError::Ods("Form control without id".to_string()));
+        };
+        let id = id.to_string();
+
+        if self.forms.is_empty() {
+            self.forms.push(Form::new("Form"));
+        }
+        self.forms[0].push_control(control);
+
+        let mut shape = DrawShape::new_control(Length::Pt(0.0), Length::Pt(0.0), width, height, id);
+        let w = width.to_pt().unwrap_or_default();
+        let h = height.to_pt().unwrap_or_default();
+        let (end_col, end_x) = end_position(col, w, |c| self.col_width_pt(c));
+        let (end_row, end_y) = end_position(row, h, |r| self.row_height_pt(r));
+        shape.set_table_end_cell_address(CellRef::remote(self.name.as_str(), end_row, end_col));
+        shape.set_table_end_x(Length::Pt(end_x));
+        shape.set_table_end_y(Length::Pt(end_y));
+
+        self.add_draw_shape(row, col, shape);
+
+        Ok(())
+    }
+
     /// Defines a range of rows as header rows.
     /// These rows are repeated when printing on multiple pages.
     pub fn set_header_rows(&mut self, row_start: u32, row_end: u32) {
@@ -1330,6 +1818,64 @@ impl Sheet {
         self.header_cols.map(Into::into)
     }
 
+    /// Inserts a manual page break before the row.
+    ///
+    /// When writing, a row style with fo:break-before="page" is
+    /// derived from the current row style.
+    /// Breaks outside of the used area of the sheet are not written.
+    pub fn insert_page_break_before_row(&mut self, row: u32) {
+        self.create_split_row_header(row).page_break = true;
+    }
+
+    /// Removes a manual page break before the row.
+    pub fn remove_page_break_before_row(&mut self, row: u32) {
+        if self.valid_row_header(row).is_some() {
+            self.create_split_row_header(row).page_break = false;
+        }
+    }
+
+    /// Inserts a manual page break before the column.
+    ///
+    /// When writing, a column style with fo:break-before="page" is
+    /// derived from the current column style.
+    /// Breaks outside of the used area of the sheet are not written.
+    pub fn insert_page_break_before_col(&mut self, col: u32) {
+        self.create_split_col_header(col).page_break = true;
+    }
+
+    /// Removes a manual page break before the column.
+    pub fn remove_page_break_before_col(&mut self, col: u32) {
+        if self.valid_col_header(col).is_some() {
+            self.create_split_col_header(col).page_break = false;
+        }
+    }
+
+    /// Removes all manual page breaks.
+    pub fn clear_page_breaks(&mut self) {
+        for rh in self.row_header.values_mut() {
+            rh.page_break = false;
+        }
+        for ch in self.col_header.values_mut() {
+            ch.page_break = false;
+        }
+    }
+
+    /// Rows with a manual page break before them.
+    pub fn row_page_breaks(&self) -> impl Iterator<Item = u32> + '_ {
+        self.row_header
+            .iter()
+            .filter(|(_, rh)| rh.page_break)
+            .flat_map(|(row, rh)| *row..*row + rh.span)
+    }
+
+    /// Columns with a manual page break before them.
+    pub fn col_page_breaks(&self) -> impl Iterator<Item = u32> + '_ {
+        self.col_header
+            .iter()
+            .filter(|(_, ch)| ch.page_break)
+            .flat_map(|(col, ch)| *col..*col + ch.span)
+    }
+
     /// Print ranges.
     pub fn add_print_range(&mut self, range: CellRange) {
         self.print_ranges.get_or_insert_with(Vec::new).push(range);
@@ -1694,6 +2240,7 @@ pub(crate) fn dedup_colheader(sheet: &mut Sheet) -> Result<(), OdsError> {
             && ch1.cellstyle == ch2.cellstyle
             && ch1.visible == ch2.visible
             && ch1.width == ch2.width
+            && ch1.page_break == ch2.page_break
     }
 
     let col_header = mem::take(&mut sheet.col_header);
@@ -1723,3 +2270,33 @@ pub(crate) fn dedup_colheader(sheet: &mut Sheet) -> Result<(), OdsError> {
 
     Ok(())
 }
+
+/// Default column width of 2.258cm.
+const DEFAULT_COL_WIDTH_PT: f64 = 64.0;
+/// Default row height of 0.452cm.
+const DEFAULT_ROW_HEIGHT_PT: f64 = 12.8;
+
+/// An image or control covers at most this many columns/rows.
+const MAX_IMAGE_SPAN: u32 = 1 << 20;
+
+/// Walks the columns/rows starting at start until the length is used up.
+/// Returns the last column/row and the remaining offset into it.
+///
+/// Stops after MAX_IMAGE_SPAN columns/rows. A length that is not a
+/// positive number ends in the start cell.
+fn end_position(start: u32, len: f64, size: impl Fn(u32) -> f64) -> (u32, f64) {
+    if !len.is_finite() || len <= 0.0 {
+        return (start, 0.0);
+    }
+    let last = start.saturating_add(MAX_IMAGE_SPAN - 1);
+    let mut idx = start;
+    let mut rest = len;
+    loop {
+        let s = size(idx);
+        if rest <= s || idx == last {
+            return (idx, rest);
+        }
+        rest -= s;
+        idx += 1;
+    }
+}
@@ -0,0 +1,475 @@
+//!
+//! Keeps the references to a sheet intact when the sheet is renamed,
+//! deleted or moved.
+//!
+
+use crate::attrmap2::AttrMap2;
+use crate::condition::Condition;
+use crate::draw::PageDrawing;
+use crate::refs::format_refs::fmt_table_name;
+use crate::refs::{find_unquoted, replace_formula_refs};
+use crate::style::CellStyleRef;
+use crate::validation::ValidationRef;
+use crate::xmltree::{XmlContent, XmlTag};
+use crate::{CellRef, OdsError, WorkBook};
+
+/// Report of the references changed by [WorkBook::rename_sheet],
+/// [WorkBook::delete_sheet] and [WorkBook::move_sheet].
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RefChanges {
+    /// Cells with a changed formula.
+    pub cells: Vec<CellRef>,
+    /// Validations with a changed condition or base-cell.
+    pub validations: Vec<ValidationRef>,
+    /// Cell-styles with changed conditional styles.
+    pub cellstyles: Vec<CellStyleRef>,
+    /// Sheets with changed print-ranges.
+    pub print_ranges: Vec<String>,
+    /// Other changes. Draw frames and shapes, named expressions,
+    /// database ranges, conditional formats.
+    pub other: usize,
+    /// References to a deleted sheet that have been replaced with #REF!.
+    pub invalid: usize,
+}
+
+/// What happens to references to the sheet.
+#[derive(Debug, Clone, Copy)]
+enum Action<'a> {
+    Rename(&'a str),
+    Delete,
+    /// The sheet names in their new order.
+    Move(&'a [String]),
+}
+
+/// Renames the sheet and changes all references.
+pub(crate) fn rename_sheet(
+    book: &mut WorkBook,
+    n: usize,
+    name: &str,
+) -> Result<RefChanges, OdsError> {
+    if name.is_empty()
+        || name.contains(['[', ']', '*', '?', ':', '/', '\\'])
+        || name.starts_with('\'')
+        || name.ends_with('\'')
+    {
+        return Err(OdsError::Ods(format!("invalid sheet name {}", name)));
+    }
+    if let Some(idx) = book.sheet_idx(name) {
+        if idx != n {
+            return Err(OdsError::Ods(format!("sheet {} already exists", name)));
+        }
+    }
+
+    let old = book.sheet(n).name().clone();
+    if old == name {
+        return Ok(RefChanges::default());
+    }
+    book.sheet_mut(n).set_name(name);
+
+    let changes = update_refs(book, &old, Action::Rename(name));
+
+    if book.config().active_table == old {
+        book.config_mut().active_table = name.to_string();
+    }
+    if let Some(tables) = book
+        .config
+        .get_mut(&["ooo:view-settings", "Views", "0", "Tables"])
+    {
+        tables.rename(&old, name);
+    }
+    if let Some(scripts) = book
+        .config
+        .get_mut(&["ooo:configuration-settings", "ScriptConfiguration"])
+    {
+        scripts.rename(&old, name);
+    }
+
+    Ok(changes)
+}
+
+/// Removes the sheet and invalidates all references.
+pub(crate) fn delete_sheet(book: &mut WorkBook, n: usize) -> RefChanges {
+    let sheet = book.remove_sheet(n);
+    let old = sheet.name();
+
+    let changes = update_refs(book, old, Action::Delete);
+
+    if &book.config().active_table == old {
+        book.config_mut().active_table = if book.num_sheets() > 0 {
+            book.sheet(n.min(book.num_sheets() - 1)).name().clone()
+        } else {
+            String::new()
+        };
+    }
+    if let Some(tables) = book
+        .config
+        .get_mut(&["ooo:view-settings", "Views", "0", "Tables"])
+    {
+        tables.remove(old);
+    }
+    if let Some(scripts) = book
+        .config
+        .get_mut(&["ooo:configuration-settings", "ScriptConfiguration"])
+    {
+        scripts.remove(old);
+    }
+
+    changes
+}
+
+/// Moves the sheet. Ranges across several sheets with the moved sheet
+/// as one end are swapped if the order of their ends changes.
+pub(crate) fn move_sheet(book: &mut WorkBook, from: usize, to: usize) -> RefChanges {
+    assert!(to < book.sheets.len());
+    let sheet = book.sheets.remove(from);
+    book.sheets.insert(to, sheet);
+    if from == to {
+        return RefChanges::default();
+    }
+
+    let table = book.sheet(to).name().clone();
+    let order = (0..book.num_sheets())
+        .map(|n| book.sheet(n).name().clone())
+        .collect::<Vec<_>>();
+    update_refs(book, &table, Action::Move(&order))
+}
+
+/// Changes all references to the table.
+fn update_refs(book: &mut WorkBook, table: &str, action: Action<'_>) -> RefChanges {
+    let mut changes = RefChanges::default();
+
+    for sheet in book.sheets.iter_mut() {
+        let sheet = sheet.as_mut();
+
+        for ((row, col), cell) in sheet.data.iter_mut() {
+            if let Some(formula) = &cell.formula {
+                if let Some(formula) = update_formula(formula, table, action, &mut changes) {
+                    cell.formula = Some(formula);
+                    changes
+                        .cells
+                        .push(CellRef::remote(sheet.name.as_str(), *row, *col));
+                }
+            }
+            if let Some(extra) = cell.extra.as_mut() {
+                for frame in extra.draw_frames.iter_mut() {
+                    if update_attrs(frame.attrmap_mut(), table, action, &mut changes) {
+                        changes.other += 1;
+                    }
+                }
+                for shape in extra.draw_shapes.iter_mut() {
+                    if update_attrs(shape.attrmap_mut(), table, action, &mut changes) {
+                        changes.other += 1;
+                    }
+                }
+            }
+        }
+        for drawing in sheet.page_drawings.iter_mut() {
+            let changed = match drawing {
+                PageDrawing::Frame(frame) => {
+                    update_attrs(frame.attrmap_mut(), table, action, &mut changes)
+                }
+                PageDrawing::Shape(shape) => {
+                    update_attrs(shape.attrmap_mut(), table, action, &mut changes)
+                }
+                PageDrawing::Xml(tag) => update_xml(tag, table, action, &mut changes),
+            };
+            if changed {
+                changes.other += 1;
+            }
+        }
+
+        if let Some(print_ranges) = sheet.print_ranges.as_mut() {
+            let mut changed = false;
+            match action {
+                Action::Rename(name) => {
+                    for range in print_ranges.iter_mut() {
+                        if range.table().map(|v| v == table).unwrap_or(false) {
+                            range.set_table(name);
+                            changed = true;
+                        }
+                        if range.to_table().map(|v| v == table).unwrap_or(false) {
+                            range.set_to_table(name);
+                            changed = true;
+                        }
+                    }
+                }
+                Action::Move(order) => {
+                    for range in print_ranges.iter_mut() {
+                        if let (Some(from), Some(to)) = (range.table(), range.to_table()) {
+                            if (from == table || to == table) && is_reversed(order, from, to) {
+                                let (from, to) = (from.clone(), to.clone());
+                                range.set_table(to);
+                                range.set_to_table(from);
+                                changed = true;
+                            }
+                        }
+                    }
+                }
+                Action::Delete => {
+                    let len = print_ranges.len();
+                    print_ranges.retain(|v| {
+                        v.table().map(|v| v != table).unwrap_or(true)
+                            && v.to_table().map(|v| v != table).unwrap_or(true)
+                    });
+                    changed = len != print_ranges.len();
+                }
+            }
+            if changed {
+                changes.print_ranges.push(sheet.name.clone());
+            }
+        }
+
+        for tag in sheet.extra.iter_mut() {
+            if update_xml(tag, table, action, &mut changes) {
+                changes.other += 1;
+            }
+        }
+    }
+
+    for validation in book.validations.values_mut() {
+        let mut changed = false;
+        let condition = validation.condition().to_string();
+        if let Some(condition) = update_formula(&condition, table, action, &mut changes) {
+            validation.set_condition(Condition::new(condition));
+            changed = true;
+        }
+        if let Action::Rename(name) = action {
+            if validation.base_cell().table().map(|v| v == table) == Some(true) {
+                let mut base_cell = validation.base_cell().clone();
+                base_cell.set_table(name);
+                validation.set_base_cell(base_cell);
+                changed = true;
+            }
+        }
+        if changed {
+            changes.validations.push(validation.validation_ref());
+        }
+    }
+
+    for style in book.cellstyles.values_mut() {
+        if style.stylemaps().is_none() {
+            continue;
+        }
+        let mut changed = false;
+        for stylemap in style.stylemaps_mut() {
+            let condition = stylemap.condition().to_string();
+            if let Some(condition) = update_formula(&condition, table, action, &mut changes) {
+                stylemap.set_condition(Condition::new(condition));
+                changed = true;
+            }
+            if let Action::Rename(name) = action {
+                if let Some(base_cell) = stylemap.base_cell() {
+                    if base_cell.table().map(|v| v == table) == Some(true) {
+                        let mut base_cell = base_cell.clone();
+                        base_cell.set_table(name);
+                        stylemap.set_base_cell(Some(base_cell));
+                        changed = true;
+                    }
+                }
+            }
+        }
+        if changed {
+            changes.cellstyles.push(style.style_ref());
+        }
+    }
+
+    for tag in book.extra.iter_mut() {
+        if update_xml(tag, table, action, &mut changes) {
+            changes.other += 1;
+        }
+    }
+
+    changes
+}
+
+/// Changes the references in a tag and all its children.
+fn update_xml(tag: &mut XmlTag, table: &str, action: Action<'_>, changes: &mut RefChanges) -> bool {
+    let mut changed = update_attrs(tag.attrmap_mut(), table, action, changes);
+    for content in tag.content_mut() {
+        if let XmlContent::Tag(tag) = content {
+            changed |= update_xml(tag, table, action, changes);
+        }
+    }
+    changed
+}
+
+/// Changes the references in address attributes and formulas.
+fn update_attrs(
+    attr: &mut AttrMap2,
+    table: &str,
+    action: Action<'_>,
+    changes: &mut RefChanges,
+) -> bool {
+    let mut update = Vec::new();
+    for (name, value) in attr.iter() {
+        let value = if name.ends_with("-address") || name.ends_with("-addresses") {
+            update_address_list(value, table, action, changes)
+        } else if value.contains('[') {
+            update_formula(value, table, action, changes)
+        } else {
+            None
+        };
+        if let Some(value) = value {
+            update.push((name.to_string(), value));
+        }
+    }
+
+    let changed = !update.is_empty();
+    for (name, value) in update {
+        attr.set_attr(&name, value);
+    }
+    changed
+}
+
+/// Changes the references in a formula.
+fn update_formula(
+    formula: &str,
+    table: &str,
+    action: Action<'_>,
+    changes: &mut RefChanges,
+) -> Option<String> {
+    replace_formula_refs(formula, |inner| {
+        update_address(inner, table, action, changes)
+    })
+}
+
+/// Changes a whitespace separated list of cell addresses.
+fn update_address_list(
+    list: &str,
+    table: &str,
+    action: Action<'_>,
+    changes: &mut RefChanges,
+) -> Option<String> {
+    let mut changed = false;
+    let list = split_unquoted(list, ' ')
+        .into_iter()
+        .map(|v| match update_address(v, table, action, changes) {
+            Some(v) => {
+                changed = true;
+                v
+            }
+            None => v.to_string(),
+        })
+        .collect::<Vec<_>>();
+    changed.then(|| list.join(" "))
+}
+
+/// Changes one cell address or cell range.
+fn update_address(
+    address: &str,
+    table: &str,
+    action: Action<'_>,
+    changes: &mut RefChanges,
+) -> Option<String> {
+    if let Action::Move(order) = action {
+        return move_address(address, table, order);
+    }
+
+    let mut changed = false;
+    let parts = split_unquoted(address, ':')
+        .into_iter()
+        .map(|v| match update_part(v, table, action, changes) {
+            Some(v) => {
+                changed = true;
+                v
+            }
+            None => v.to_string(),
+        })
+        .collect::<Vec<_>>();
+    changed.then(|| parts.join(":"))
+}
+
+/// Swaps the table-names of a range across several sheets if the
+/// moved table is one end and the ends are in reverse order now.
+fn move_address(address: &str, table: &str, order: &[String]) -> Option<String> {
+    let [from, to] = split_unquoted(address, ':')[..] else {
+        return None;
+    };
+    let (from_abs, from_name, from_cell) = split_part(from)?;
+    let (to_abs, to_name, to_cell) = split_part(to)?;
+    if (from_name != table && to_name != table) || !is_reversed(order, &from_name, &to_name) {
+        return None;
+    }
+
+    let mut buf = String::new();
+    fmt_table_name(&mut buf, &to_name, from_abs).expect("fmt");
+    buf.push('.');
+    buf.push_str(from_cell);
+    buf.push(':');
+    fmt_table_name(&mut buf, &from_name, to_abs).expect("fmt");
+    buf.push('.');
+    buf.push_str(to_cell);
+    Some(buf)
+}
+
+/// The second table comes before the first one.
+fn is_reversed(order: &[String], from: &str, to: &str) -> bool {
+    let pos = |name: &str| order.iter().position(|v| v == name);
+    match (pos(from), pos(to)) {
+        (Some(from), Some(to)) => from > to,
+        _ => false,
+    }
+}
+
+/// Changes the table-name of one side of a cell address.
+/// References to other files are never changed.
+fn update_part(
+    part: &str,
+    table: &str,
+    action: Action<'_>,
+    changes: &mut RefChanges,
+) -> Option<String> {
+    let (abs, name, cell) = split_part(part)?;
+    if name != table {
+        return None;
+    }
+
+    let mut buf = String::new();
+    match action {
+        Action::Rename(new_name) => {
+            fmt_table_name(&mut buf, new_name, abs).expect("fmt");
+        }
+        Action::Delete => {
+            if abs {
+                buf.push('$');
+            }
+            buf.push_str("#REF!");
+            changes.invalid += 1;
+        }
+        Action::Move(_) => return None,
+    }
+    buf.push('.');
+    buf.push_str(cell);
+    Some(buf)
+}
+
+/// Splits one side of a cell address into the absolute flag, the
+/// unquoted table-name and the cell. None if there is no table-name.
+fn split_part(part: &str) -> Option<(bool, String, &str)> {
+    let (abs, rest) = match part.strip_prefix('$') {
+        Some(rest) => (true, rest),
+        None => (false, part),
+    };
+
+    if let Some(quoted) = rest.strip_prefix('\'') {
+        let end = find_unquoted(rest, '.')?;
+        // the closing quote must come directly before the dot.
+        let name = quoted[..end - 1].strip_suffix('\'')?;
+        Some((abs, name.replace("''", "'"), &rest[end + 1..]))
+    } else {
+        let (name, cell) = rest.split_once('.')?;
+        Some((abs, name.to_string(), cell))
+    }
+}
+
+/// Splits at the character outside of single quotes.
+fn split_unquoted(text: &str, c: char) -> Vec<&str> {
+    let mut res = Vec::new();
+    let mut rest = text;
+    while let Some(idx) = find_unquoted(rest, c) {
+        res.push(&rest[..idx]);
+        rest = &rest[idx + c.len_utf8()..];
+    }
+    res.push(rest);
+    res
+}
@@ -0,0 +1,532 @@
+//!
+//! Sorting of cell ranges.
+//!
+//! ```
+//! use spreadsheet_ods::sort::{SortKey, SortKeys};
+//! use spreadsheet_ods::{CellRange, Sheet};
+//!
+//! let mut sheet = Sheet::new("one");
+//! sheet.set_value(0, 0, "Name");
+//! sheet.set_value(0, 1, "Amount");
+//! sheet.set_value(1, 0, "item10");
+//! sheet.set_value(1, 1, 3);
+//! sheet.set_value(2, 0, "Item9");
+//! sheet.set_value(2, 1, 3);
+//! sheet.set_value(3, 0, "item1");
+//! sheet.set_value(3, 1, 7);
+//!
+//! let keys = SortKeys::new()
+//!     .header(true)
+//!     .key(SortKey::new(1).ascending(false))
+//!     .key(SortKey::new(0).natural(true));
+//! sheet
+//!     .sort_range(&CellRange::local(0, 0, 3, 1), &keys)
+//!     .unwrap();
+//!
+//! assert_eq!(sheet.value(1, 0).as_str_or(""), "item1");
+//! assert_eq!(sheet.value(2, 0).as_str_or(""), "Item9");
+//! assert_eq!(sheet.value(3, 0).as_str_or(""), "item10");
+//! ```
+//!
+//! Values are ordered like LibreOffice does it. Numbers, dates and
+//! booleans come before text, empty cells are always sorted last.
+//!
+
+use crate::cell_::CellData;
+use crate::condition::value_as_number;
+use crate::refs::{parse_cellrange, parse_cellref, replace_formula_refs};
+use crate::{CellRange, OdsError, Sheet, Value};
+use icu_locid::Locale;
+use std::cmp::Ordering;
+
+/// Direction of the sort.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortOrientation {
+    /// Sort the rows. The keys are columns.
+    #[default]
+    TopToBottom,
+    /// Sort the columns. The keys are rows.
+    LeftToRight,
+}
+
+/// One sort key.
+#[derive(Debug, Clone)]
+pub struct SortKey {
+    index: u32,
+    ascending: bool,
+    match_case: bool,
+    natural: bool,
+}
+
+impl SortKey {
+    /// Sort by this column, or row when sorting left to right.
+    /// The index is counted from the start of the sheet, not from the
+    /// start of the range.
+    pub fn new(index: u32) -> Self {
+        Self {
+            index,
+            ascending: true,
+            match_case: false,
+            natural: false,
+        }
+    }
+
+    /// Sort ascending. This is the default.
+    pub fn ascending(mut self, ascending: bool) -> Self {
+        self.ascending = ascending;
+        self
+    }
+
+    /// Upper and lower case are distinguished. Lowercase sorts before
+    /// uppercase.
+    pub fn match_case(mut self, match_case: bool) -> Self {
+        self.match_case = match_case;
+        self
+    }
+
+    /// Natural sort. Numbers within the text are compared by
+    /// their value, so "item9" sorts before "item10".
+    pub fn natural(mut self, natural: bool) -> Self {
+        self.natural = natural;
+        self
+    }
+}
+
+/// Sort keys and options for [Sheet::sort_range].
+#[derive(Debug, Clone, Default)]
+pub struct SortKeys {
+    keys: Vec<SortKey>,
+    orientation: SortOrientation,
+    header: bool,
+    locale: Option<Locale>,
+}
+
+impl SortKeys {
+    /// Empty.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds a sort key. Later keys are used if all the earlier
+    /// keys compare equal.
+    pub fn key(mut self, key: SortKey) -> Self {
+        self.keys.push(key);
+        self
+    }
+
+    /// Sort rows or columns.
+    pub fn orientation(mut self, orientation: SortOrientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// The first row (or column) of the range contains headers and
+    /// is not sorted.
+    pub fn header(mut self, header: bool) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Use the collation rules for this locale. Without a locale
+    /// accented letters sort with their base letter.
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.locale = Some(locale);
+        self
+    }
+}
+
+/// Sorts the range. See [Sheet::sort_range].
+pub(crate) fn sort_range(
+    sheet: &mut Sheet,
+    range: &CellRange,
+    keys: &SortKeys,
+) -> Result<(), OdsError> {
+    if let Some(table) = range.table() {
+        if table != sheet.name() {
+            return Err(OdsError::Ods(format!(
+                "range {} is not in sheet {}",
+                range,
+                sheet.name()
+            )));
+        }
+    }
+
+    let by_rows = keys.orientation == SortOrientation::TopToBottom;
+    let (key_min, key_max) = if by_rows {
+        (range.col(), range.to_col())
+    } else {
+        (range.row(), range.to_row())
+    };
+    if let Some(key) = keys
+        .keys
+        .iter()
+        .find(|v| v.index < key_min || v.index > key_max)
+    {
+        return Err(OdsError::Ods(format!(
+            "sort key {} is outside of range {}",
+            key.index, range
+        )));
+    }
+
+    // a repeated row or cell would be sorted as a single line.
+    let repeated_row = sheet
+        .row_header
+        .range(..=range.to_row())
+        .any(|(row, header)| header.repeat > 1 && row.saturating_add(header.repeat) > range.row());
+    if repeated_row {
+        return Err(OdsError::Ods(format!(
+            "range {} contains repeated rows",
+            range
+        )));
+    }
+
+    let mut cells = Vec::new();
+    for row in range.row()..=range.to_row() {
+        for (pos, cell) in sheet.data.range((row, 0)..=(row, range.to_col())) {
+            if cell.repeat > 1 && pos.1.saturating_add(cell.repeat) > range.col() {
+                return Err(OdsError::Ods(format!(
+                    "range {} contains repeated cells",
+                    range
+                )));
+            }
+            if pos.1 < range.col() {
+                continue;
+            }
+            if let Some(extra) = &cell.extra {
+                if !extra.span.is_empty() || !extra.matrix_span.is_empty() {
+                    return Err(OdsError::Ods(format!(
+                        "range {} contains merged cells",
+                        range
+                    )));
+                }
+            }
+            cells.push(*pos);
+        }
+    }
+
+    let (first, last) = if by_rows {
+        (range.row(), range.to_row())
+    } else {
+        (range.col(), range.to_col())
+    };
+    let first = if keys.header { first + 1 } else { first };
+    if first >= last {
+        return Ok(());
+    }
+
+    let collator = Collator::new(keys.locale.as_ref());
+    let mut lines = (first..=last).collect::<Vec<_>>();
+    lines.sort_by(|a, b| {
+        for key in &keys.keys {
+            let (va, vb) = if by_rows {
+                (sheet.value(*a, key.index), sheet.value(*b, key.index))
+            } else {
+                (sheet.value(key.index, *a), sheet.value(key.index, *b))
+            };
+            match compare_value(va, vb, key, &collator) {
+                Ordering::Equal => {}
+                ord => return ord,
+            }
+        }
+        Ordering::Equal
+    });
+
+    // old line -> new line
+    let mut moved = vec![0; lines.len()];
+    for (idx, old) in lines.iter().enumerate() {
+        moved[(*old - first) as usize] = first + idx as u32;
+    }
+
+    let mut taken = Vec::new();
+    for pos in cells {
+        let line = if by_rows { pos.0 } else { pos.1 };
+        if line < first {
+            continue;
+        }
+        if let Some(cell) = sheet.data.remove(&pos) {
+            taken.push((pos, cell));
+        }
+    }
+    for ((row, col), mut cell) in taken {
+        let (row, col) = if by_rows {
+            let new_row = moved[(row - first) as usize];
+            move_formula(&mut cell, sheet.name(), by_rows, row, new_row);
+            (new_row, col)
+        } else {
+            let new_col = moved[(col - first) as usize];
+            move_formula(&mut cell, sheet.name(), by_rows, col, new_col);
+            (row, new_col)
+        };
+        sheet.data.insert((row, col), cell);
+    }
+
+    Ok(())
+}
+
+/// Adjusts the relative references within the moved row (or column).
+fn move_formula(cell: &mut CellData, sheet_name: &str, by_rows: bool, old: u32, new: u32) {
+    if old == new {
+        return;
+    }
+    if let Some(formula) = &cell.formula {
+        if let Some(formula) = move_refs(formula, sheet_name, by_rows, old, new) {
+            cell.formula = Some(formula);
+        }
+    }
+}
+
+/// Rewrites all references to the old row (or column) that are
+/// relative in this direction. Returns None if nothing changed.
+fn move_refs(formula: &str, sheet_name: &str, by_rows: bool, old: u32, new: u32) -> Option<String> {
+    let local = |table: Option<&String>, iri: Option<&String>| {
+        iri.is_none() && table.map(|v| v == sheet_name).unwrap_or(true)
+    };
+
+    replace_formula_refs(formula, |inner| {
+        let mut new_ref = if let Ok(mut r) = parse_cellrange(inner) {
+            if by_rows
+                && local(r.table(), r.iri())
+                && !r.row_abs()
+                && !r.to_row_abs()
+                && r.row() == old
+                && r.to_row() == old
+            {
+                r.set_row(new);
+                r.set_to_row(new);
+                r.to_string()
+            } else if !by_rows
+                && local(r.table(), r.iri())
+                && !r.col_abs()
+                && !r.to_col_abs()
+                && r.col() == old
+                && r.to_col() == old
+            {
+                r.set_col(new);
+                r.set_to_col(new);
+                r.to_string()
+            } else {
+                return None;
+            }
+        } else if let Ok(mut r) = parse_cellref(inner) {
+            if by_rows && local(r.table(), r.iri()) && !r.row_abs() && r.row() == old {
+                r.set_row(new);
+                r.to_string()
+            } else if !by_rows && local(r.table(), r.iri()) && !r.col_abs() && r.col() == old {
+                r.set_col(new);
+                r.to_string()
+            } else {
+                return None;
+            }
+        } else {
+            return None;
+        };
+        // an absolute table name is not kept by the parser
+        if inner.starts_with('$') && !new_ref.starts_with('$') {
+            new_ref.insert(0, '$');
+        }
+        Some(new_ref)
+    })
+}
+
+/// Compares two values for sorting.
+fn compare_value(a: &Value, b: &Value, key: &SortKey, collator: &Collator) -> Ordering {
+    let ord = match (a, b) {
+        (Value::Empty, Value::Empty) => return Ordering::Equal,
+        (Value::Empty, _) => return Ordering::Greater,
+        (_, Value::Empty) => return Ordering::Less,
+        _ => match (value_as_number(a), value_as_number(b)) {
+            (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => {
+                let a = a.as_cow_str_or("");
+                let b = b.as_cow_str_or("");
+                if key.natural {
+                    collator.compare_natural(&a, &b, key.match_case)
+                } else {
+                    collator.compare(&a, &b, key.match_case)
+                }
+            }
+        },
+    };
+    if key.ascending {
+        ord
+    } else {
+        ord.reverse()
+    }
+}
+
+/// Simple collation for latin scripts.
+///
+/// Compares the base letters first, then the accents and
+/// then the case.
+struct Collator {
+    /// Letters that sort after 'z' in this locale.
+    after_z: &'static [char],
+    /// Letters that sort as separate letters after another letter.
+    separate: &'static [(char, char)],
+}
+
+impl Collator {
+    fn new(locale: Option<&Locale>) -> Self {
+        let language = locale.map(|v| v.id.language.as_str()).unwrap_or("");
+        match language {
+            "sv" | "fi" => Self {
+                after_z: &['å', 'ä', 'æ', 'ö', 'ø'],
+                separate: &[],
+            },
+            "da" | "nb" | "nn" | "no" => Self {
+                after_z: &['æ', 'ä', 'ø', 'ö', 'å'],
+                separate: &[],
+            },
+            "es" => Self {
+                after_z: &[],
+                separate: &[('ñ', 'n')],
+            },
+            "pl" => Self {
+                after_z: &[],
+                separate: &[
+                    ('ą', 'a'),
+                    ('ć', 'c'),
+                    ('ę', 'e'),
+                    ('ł', 'l'),
+                    ('ń', 'n'),
+                    ('ó', 'o'),
+                    ('ś', 's'),
+                    ('ź', 'z'),
+                    ('ż', 'z'),
+                ],
+            },
+            _ => Self {
+                after_z: &[],
+                separate: &[],
+            },
+        }
+    }
+
+    /// Primary weights of a char.
+    fn primary(&self, c: char, buf: &mut Vec<u32>) {
+        let lower = c.to_lowercase().next().unwrap_or(c);
+        if let Some(idx) = self.after_z.iter().position(|v| *v == lower) {
+            buf.push(('z' as u32) * 16 + 1 + idx as u32);
+        } else if let Some(idx) = self.separate.iter().position(|(v, _)| *v == lower) {
+            let base = self.separate[idx].1;
+            let n = self.separate[..idx]
+                .iter()
+                .filter(|(_, v)| *v == base)
+                .count();
+            buf.push((base as u32) * 16 + 1 + n as u32);
+        } else {
+            match base_letters(lower) {
+                Some(base) => {
+                    for base in base.chars() {
+                        buf.push((base as u32) * 16);
+                    }
+                }
+                None => buf.push((lower as u32) * 16),
+            }
+        }
+    }
+
+    fn compare(&self, a: &str, b: &str, match_case: bool) -> Ordering {
+        let mut wa = Vec::new();
+        let mut wb = Vec::new();
+        for c in a.chars() {
+            self.primary(c, &mut wa);
+        }
+        for c in b.chars() {
+            self.primary(c, &mut wb);
+        }
+        wa.cmp(&wb)
+            .then_with(|| {
+                a.chars()
+                    .flat_map(char::to_lowercase)
+                    .cmp(b.chars().flat_map(char::to_lowercase))
+            })
+            .then_with(|| {
+                if match_case {
+                    // lowercase first
+                    a.chars()
+                        .map(char::is_uppercase)
+                        .cmp(b.chars().map(char::is_uppercase))
+                } else {
+                    Ordering::Equal
+                }
+            })
+    }
+
+    fn compare_natural(&self, a: &str, b: &str, match_case: bool) -> Ordering {
+        let mut ca = chunks(a);
+        let mut cb = chunks(b);
+        loop {
+            match (ca.next(), cb.next()) {
+                (None, None) => return Ordering::Equal,
+                (None, Some(_)) => return Ordering::Less,
+                (Some(_), None) => return Ordering::Greater,
+                (Some(x), Some(y)) => {
+                    let xd = x.starts_with(|c: char| c.is_ascii_digit());
+                    let yd = y.starts_with(|c: char| c.is_ascii_digit());
+                    let ord = match (xd, yd) {
+                        (true, true) => {
+                            let xt = x.trim_start_matches('0');
+                            let yt = y.trim_start_matches('0');
+                            xt.len().cmp(&yt.len()).then_with(|| xt.cmp(yt))
+                        }
+                        (true, false) => Ordering::Less,
+                        (false, true) => Ordering::Greater,
+                        (false, false) => self.compare(x, y, match_case),
+                    };
+                    if ord != Ordering::Equal {
+                        return ord;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Splits into runs of digits and non-digits.
+fn chunks(s: &str) -> impl Iterator<Item = &str> {
+    let mut rest = s;
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let digit = rest.starts_with(|c: char| c.is_ascii_digit());
+        let end = rest
+            .find(|c: char| c.is_ascii_digit() != digit)
+            .unwrap_or(rest.len());
+        let (chunk, tail) = rest.split_at(end);
+        rest = tail;
+        Some(chunk)
+    })
+}
+
+/// Base letters without accents for a lowercase letter.
+fn base_letters(c: char) -> Option<&'static str> {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => Some("a"),
+        'æ' => Some("ae"),
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => Some("c"),
+        'ď' | 'đ' => Some("d"),
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => Some("e"),
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => Some("g"),
+        'ĥ' | 'ħ' => Some("h"),
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => Some("i"),
+        'ĵ' => Some("j"),
+        'ķ' => Some("k"),
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => Some("l"),
+        'ñ' | 'ń' | 'ņ' | 'ň' => Some("n"),
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => Some("o"),
+        'œ' => Some("oe"),
+        'ŕ' | 'ŗ' | 'ř' => Some("r"),
+        'ß' => Some("ss"),
+        'ś' | 'ŝ' | 'ş' | 'š' => Some("s"),
+        'ţ' | 'ť' | 'ŧ' => Some("t"),
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => Some("u"),
+        'ŵ' => Some("w"),
+        'ý' | 'ÿ' | 'ŷ' => Some("y"),
+        'ź' | 'ż' | 'ž' => Some("z"),
+        _ => None,
+    }
+}
@@ -2,7 +2,9 @@ use crate::attrmap2::AttrMap2;
 use crate::style::units::{
     FontFamilyGeneric, FontPitch, FontStretch, FontStyle, FontVariant, FontWeight,
 };
+use crate::style::ParseStyleAttr;
 use crate::style::StyleOrigin;
+use crate::OdsError;
 use get_size::GetSize;
 use get_size_derive::GetSize;
 
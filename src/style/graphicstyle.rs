@@ -1,9 +1,10 @@
 use crate::attrmap2::AttrMap2;
 use crate::style::units::{
-    FontSize, FontStyle, FontVariant, FontWeight, Hyphenation, HyphenationLadderCount, Indent,
-    LetterSpacing, LineBreak, LineHeight, LineMode, LineStyle, LineType, LineWidth, Margin,
-    PageBreak, PageNumber, ParaAlignVertical, Percent, PunctuationWrap, RotationScale, TextAlign,
-    TextAlignLast, TextAutoSpace, TextCombine, TextCondition, TextDisplay, TextEmphasize,
+    DrawFill, DrawStroke, FontSize, FontStyle, FontVariant, FontWeight, Hyphenation,
+    HyphenationLadderCount, Indent, LetterSpacing, LineBreak, LineHeight, LineMode, LineStyle,
+    LineType, LineWidth, Margin, PageBreak, PageNumber, ParaAlignVertical, Percent,
+    PunctuationWrap, RotationScale, TextAlign, TextAlignLast, TextAreaHorizontalAlign,
+    TextAreaVerticalAlign, TextAutoSpace, TextCombine, TextCondition, TextDisplay, TextEmphasize,
     TextEmphasizePosition, TextKeep, TextPosition, TextRelief, TextTransform, WritingMode,
 };
 use crate::style::AnyStyleRef;
@@ -11,7 +12,9 @@ use crate::style::{
     border_line_width_string, border_string, color_string, shadow_string, text_position, Border,
     Length, Rgb,
 };
+use crate::style::{parse_border, parse_border_line_width, ParseStyleAttr};
 use crate::style::{StyleOrigin, StyleUse, TextStyleRef};
+use crate::OdsError;
 use get_size::GetSize;
 use get_size_derive::GetSize;
 use icu_locid::Locale;
@@ -109,6 +112,14 @@ impl GraphicStyle {
         &mut self.textstyle
     }
 
+    draw_fill!(graphicstyle);
+    draw_fill_color!(graphicstyle);
+    draw_stroke!(graphicstyle);
+    draw_textarea_horizontal_align!(graphicstyle);
+    draw_textarea_vertical_align!(graphicstyle);
+    svg_stroke_color!(graphicstyle);
+    svg_stroke_width!(graphicstyle);
+
     fo_background_color!(paragraphstyle);
     fo_border!(paragraphstyle);
     fo_break!(paragraphstyle);
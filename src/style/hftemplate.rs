@@ -0,0 +1,384 @@
+//!
+//! Compact template syntax for headers and footers.
+//!
+//! See HeaderFooter::set_template().
+//!
+
+use crate::text::{
+    MetaDate, MetaFileName, MetaPageCount, MetaPageNumber, MetaSheetName, MetaTime, MetaTitle,
+    TextP, TextS, TextSpan, TextTab, TextTag,
+};
+use crate::xmltree::XmlContent;
+use crate::OdsError;
+
+/// Limit for text:c of text:s.
+const MAX_SPACES: usize = 1024;
+
+/// Field names and their text-tags.
+const FIELDS: [(&str, &str); 8] = [
+    ("Page", "text:page-number"),
+    ("Pages", "text:page-count"),
+    ("Sheet", "text:sheet-name"),
+    ("File", "text:file-name"),
+    ("Path", "text:file-name"),
+    ("Date", "text:date"),
+    ("Time", "text:time"),
+    ("Title", "text:title"),
+];
+
+fn field_tag(name: &str) -> Option<TextTag> {
+    let tag = match name {
+        "Page" => MetaPageNumber::new().into_xmltag(),
+        "Pages" => MetaPageCount::new().into_xmltag(),
+        "Sheet" => MetaSheetName::new().into_xmltag(),
+        "File" => {
+            let mut tag = MetaFileName::new().into_xmltag();
+            tag.set_attr("text:display", "name-and-extension");
+            tag
+        }
+        "Path" => {
+            let mut tag = MetaFileName::new().into_xmltag();
+            tag.set_attr("text:display", "full");
+            tag
+        }
+        "Date" => MetaDate::new().into_xmltag(),
+        "Time" => MetaTime::new().into_xmltag(),
+        "Title" => MetaTitle::new().into_xmltag(),
+        _ => return None,
+    };
+    Some(tag)
+}
+
+fn field_name(tag: &TextTag) -> Option<&'static str> {
+    if tag.name() == "text:file-name" {
+        if tag.get_attr("text:display") == Some("full") {
+            Some("Path")
+        } else {
+            Some("File")
+        }
+    } else {
+        FIELDS
+            .iter()
+            .find(|(_, v)| *v == tag.name())
+            .map(|(k, _)| *k)
+    }
+}
+
+/// Collects the paragraphs of one region.
+struct ParagraphBuilder {
+    paragraphs: Vec<TextTag>,
+    para: TextTag,
+    span: Option<TextTag>,
+    buf: String,
+    spaces: u32,
+    at_start: bool,
+    // skip the spaces at the start of the region.
+    at_region_start: bool,
+    // plain spaces at the end, they are skipped at the end of the region.
+    trailing: u32,
+}
+
+impl ParagraphBuilder {
+    fn new() -> Self {
+        Self {
+            paragraphs: Vec::new(),
+            para: TextP::new().into_xmltag(),
+            span: None,
+            buf: String::new(),
+            spaces: 0,
+            at_start: true,
+            at_region_start: true,
+            trailing: 0,
+        }
+    }
+
+    fn target(&mut self) -> &mut TextTag {
+        self.span.as_mut().unwrap_or(&mut self.para)
+    }
+
+    fn flush_text(&mut self) {
+        if !self.buf.is_empty() {
+            let buf = std::mem::take(&mut self.buf);
+            self.target().add_text(buf);
+        }
+        // Runs of spaces and leading spaces need text:s.
+        if self.spaces > 0 {
+            let spaces = self.spaces;
+            self.spaces = 0;
+            let s = if spaces > 1 {
+                TextS::new().count(spaces)
+            } else {
+                TextS::new()
+            };
+            self.target().add_tag(s);
+        }
+    }
+
+    fn push_char(&mut self, c: char) {
+        if c == ' ' && self.at_region_start {
+            return;
+        }
+        if c == ' ' {
+            self.trailing += 1;
+        } else {
+            self.trailing = 0;
+        }
+        self.push_char_kept(c);
+    }
+
+    // No skipping at the start or end of the region.
+    fn push_char_kept(&mut self, c: char) {
+        self.at_region_start = false;
+        if c == ' ' {
+            if self.at_start || self.spaces > 0 || self.buf.ends_with(' ') {
+                self.spaces += 1;
+            } else {
+                self.buf.push(' ');
+            }
+        } else {
+            if self.spaces > 0 {
+                self.flush_text();
+            }
+            self.buf.push(c);
+        }
+        self.at_start = false;
+    }
+
+    fn push_tag(&mut self, tag: TextTag) {
+        self.at_region_start = false;
+        self.trailing = 0;
+        self.flush_text();
+        self.target().add_tag(tag);
+        self.at_start = false;
+    }
+
+    fn open_span(&mut self, style: &str) {
+        self.close_span();
+        let mut span = TextSpan::new().into_xmltag();
+        span.set_attr("text:style-name", style);
+        self.span = Some(span);
+    }
+
+    fn close_span(&mut self) {
+        self.flush_text();
+        if let Some(span) = self.span.take() {
+            if !span.is_empty() {
+                self.para.add_tag(span);
+            }
+        }
+    }
+
+    fn new_paragraph(&mut self) {
+        let style = self
+            .span
+            .as_ref()
+            .and_then(|v| v.get_attr("text:style-name"))
+            .map(|v| v.to_string());
+        self.close_span();
+        self.trailing = 0;
+        let para = std::mem::replace(&mut self.para, TextP::new().into_xmltag());
+        self.paragraphs.push(para);
+        if let Some(style) = style {
+            self.open_span(&style);
+        }
+        self.at_start = true;
+    }
+
+    fn finish(mut self) -> Vec<TextTag> {
+        // skip the spaces at the end of the region.
+        let n = self.trailing.min(self.spaces);
+        self.spaces -= n;
+        for _ in n..self.trailing {
+            if self.buf.ends_with(' ') {
+                self.buf.pop();
+            }
+        }
+        self.close_span();
+        if !self.para.is_empty() || !self.paragraphs.is_empty() {
+            self.paragraphs.push(self.para);
+        }
+        self.paragraphs
+    }
+}
+
+/// Parses a template into 1 to 3 regions of text:p.
+pub(crate) fn parse_template(template: &str) -> Result<Vec<Vec<TextTag>>, OdsError> {
+    let mut regions = Vec::new();
+    let mut builder = ParagraphBuilder::new();
+
+    let mut it = template.chars();
+    while let Some(c) = it.next() {
+        match c {
+            '|' => {
+                regions.push(std::mem::replace(&mut builder, ParagraphBuilder::new()).finish());
+            }
+            '\n' => {
+                builder.new_paragraph();
+            }
+            '\t' => {
+                builder.push_tag(TextTab::new().into_xmltag());
+            }
+            '&' => match it.next() {
+                Some('&') => builder.push_char('&'),
+                Some('|') => builder.push_char('|'),
+                Some(' ') => builder.push_char_kept(' '),
+                Some('[') => {
+                    let mut name = String::new();
+                    loop {
+                        match it.next() {
+                            Some(']') => break,
+                            Some(c) => name.push(c),
+                            None => {
+                                return Err(OdsError::Parse(
+                                    "unterminated field in template",
+                                    Some(name),
+                                ))
+                            }
+                        }
+                    }
+                    let Some(tag) = field_tag(name.trim()) else {
+                        return Err(OdsError::Parse("unknown field in template", Some(name)));
+                    };
+                    builder.push_tag(tag);
+                }
+                Some('{') => {
+                    let mut name = String::new();
+                    loop {
+                        match it.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => {
+                                return Err(OdsError::Parse(
+                                    "unterminated style in template",
+                                    Some(name),
+                                ))
+                            }
+                        }
+                    }
+                    if name.is_empty() {
+                        builder.close_span();
+                    } else {
+                        builder.open_span(&name);
+                    }
+                }
+                c => {
+                    return Err(OdsError::Parse(
+                        "invalid escape in template",
+                        c.map(|v| v.to_string()),
+                    ))
+                }
+            },
+            c => builder.push_char(c),
+        }
+    }
+    regions.push(builder.finish());
+
+    if regions.len() > 3 {
+        return Err(OdsError::Parse(
+            "more than 3 regions in template",
+            Some(template.to_string()),
+        ));
+    }
+
+    Ok(regions)
+}
+
+/// Template syntax of one region. Spaces are kept apart, at the start and
+/// end of the region they must be escaped.
+enum Part {
+    Space,
+    // doesn't stop the skipping of spaces at the start of the region.
+    Newline,
+    // &{name} or &{}
+    Span(String),
+    Text(String),
+}
+
+/// Converts the paragraphs of one region back to template syntax.
+pub(crate) fn format_template(paragraphs: &[TextTag]) -> String {
+    let mut parts = Vec::new();
+    for (i, p) in paragraphs.iter().enumerate() {
+        if i > 0 {
+            parts.push(Part::Newline);
+        }
+        format_content(p, &mut parts);
+    }
+
+    let first = parts
+        .iter()
+        .position(|v| matches!(v, Part::Text(_)))
+        .unwrap_or(parts.len());
+    let last = parts
+        .iter()
+        .rposition(|v| matches!(v, Part::Text(_) | Part::Newline))
+        .unwrap_or(0);
+
+    let mut buf = String::new();
+    for (i, part) in parts.iter().enumerate() {
+        match part {
+            Part::Space if i < first || i > last => buf.push_str("& "),
+            Part::Space => buf.push(' '),
+            Part::Newline => buf.push('\n'),
+            Part::Span(v) | Part::Text(v) => buf.push_str(v),
+        }
+    }
+    buf
+}
+
+fn push_text(parts: &mut Vec<Part>, text: &str) {
+    if let Some(Part::Text(v)) = parts.last_mut() {
+        v.push_str(text);
+    } else {
+        parts.push(Part::Text(text.to_string()));
+    }
+}
+
+fn format_content(tag: &TextTag, parts: &mut Vec<Part>) {
+    for c in tag.content() {
+        match c {
+            XmlContent::Text(t) => {
+                for c in t.chars() {
+                    match c {
+                        ' ' => parts.push(Part::Space),
+                        '&' => push_text(parts, "&&"),
+                        '|' => push_text(parts, "&|"),
+                        c => push_text(parts, c.encode_utf8(&mut [0; 4])),
+                    }
+                }
+            }
+            XmlContent::Tag(t) => format_tag(t, parts),
+        }
+    }
+}
+
+fn format_tag(tag: &TextTag, parts: &mut Vec<Part>) {
+    if let Some(field) = field_name(tag) {
+        push_text(parts, &format!("&[{}]", field));
+    } else {
+        match tag.name() {
+            "text:s" => {
+                let count = tag
+                    .get_attr("text:c")
+                    .and_then(|v| v.parse::<usize>().ok())
+                    .unwrap_or(1)
+                    .min(MAX_SPACES);
+                for _ in 0..count {
+                    parts.push(Part::Space);
+                }
+            }
+            "text:tab" => push_text(parts, "\t"),
+            "text:line-break" => parts.push(Part::Newline),
+            "text:span" => {
+                if let Some(style) = tag.get_attr("text:style-name") {
+                    parts.push(Part::Span(format!("&{{{}}}", style)));
+                    format_content(tag, parts);
+                    parts.push(Part::Span("&{}".to_string()));
+                } else {
+                    format_content(tag, parts);
+                }
+            }
+            // anything else only contributes its text.
+            _ => format_content(tag, parts),
+        }
+    }
+}
@@ -1,6 +1,8 @@
+use crate::style::hftemplate::{format_template, parse_template};
 use crate::style::pagestyle::PageStyleRef;
 use crate::style::AnyStyleRef;
 use crate::text::TextTag;
+use crate::OdsError;
 use get_size::GetSize;
 use get_size_derive::GetSize;
 use std::borrow::Borrow;
@@ -231,6 +233,36 @@ impl MasterPage {
 /// Can be seen as three regions left/center/right or as one region.
 /// In the first case region* contains the data, in the second it's content.
 /// Each is a TextTag of parsed XML-tags.
+///
+/// The content can be set with a compact template too. The template
+///
+/// ```text
+/// &[Sheet] - Page &[Page] of &[Pages] | &{Bold}Confidential&{} | &[Date]
+/// ```
+///
+/// sets the left, center and right region. The syntax is
+///
+/// * `|` separates the regions. A single part is used as content
+///   without regions, two parts are used for the left and right region,
+///   three parts for left, center and right. Spaces at the start and
+///   end of a region are ignored.
+/// * `&[Field]` inserts a field. Fields are Page, Pages, Sheet, File,
+///   Path, Date, Time and Title.
+/// * `&{name}` starts a span with the text style name, `&{}` ends it.
+///   The style must be defined with StyleOrigin::Styles, validate()
+///   reports styles that are not.
+/// * A newline starts a new paragraph, a tab inserts a text:tab.
+/// * `&&` and `&|` are a literal `&` and `|`. `& ` is a space that is
+///   kept at the start and end of a region.
+///
+/// ```
+/// use spreadsheet_ods::style::HeaderFooter;
+///
+/// let mut footer = HeaderFooter::new();
+/// footer.set_template("&[Sheet] | Page &[Page] of &[Pages]").unwrap();
+/// assert_eq!(footer.left().len(), 1);
+/// assert_eq!(footer.template(), "&[Sheet]||Page &[Page] of &[Pages]");
+/// ```
 #[derive(Clone, Debug, Default, GetSize)]
 pub struct HeaderFooter {
     display: bool,
@@ -264,6 +296,53 @@ impl HeaderFooter {
         self.display
     }
 
+    /// Sets the content from a template. See [HeaderFooter] for the syntax.
+    /// Any existing content is replaced.
+    pub fn set_template(&mut self, template: &str) -> Result<(), OdsError> {
+        let mut regions = parse_template(template)?;
+
+        self.region_left.clear();
+        self.region_center.clear();
+        self.region_right.clear();
+        self.content.clear();
+
+        match regions.len() {
+            1 => {
+                self.content = regions.remove(0);
+            }
+            2 => {
+                self.region_right = regions.remove(1);
+                self.region_left = regions.remove(0);
+            }
+            _ => {
+                self.region_right = regions.remove(2);
+                self.region_center = regions.remove(1);
+                self.region_left = regions.remove(0);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Converts the content to the template syntax. See [HeaderFooter].
+    ///
+    /// Elements that have no template equivalent only contribute their text.
+    pub fn template(&self) -> String {
+        if self.region_left.is_empty()
+            && self.region_center.is_empty()
+            && self.region_right.is_empty()
+        {
+            format_template(&self.content)
+        } else {
+            format!(
+                "{}|{}|{}",
+                format_template(&self.region_left),
+                format_template(&self.region_center),
+                format_template(&self.region_right)
+            )
+        }
+    }
+
     /// true if all regions of the header/footer are empty.
     pub fn is_empty(&self) -> bool {
         self.region_left.is_empty()
@@ -117,6 +117,7 @@ mod cellstyle;
 mod colstyle;
 mod fontface;
 mod graphicstyle;
+mod hftemplate;
 mod masterpage;
 mod pagestyle;
 mod paragraphstyle;
@@ -202,6 +203,149 @@ impl ParseStyleAttr<bool> for bool {
     }
 }
 
+impl ParseStyleAttr<u32> for u32 {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<u32>, OdsError> {
+        if let Some(s) = attr {
+            Ok(Some(u32::from_str(s)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl ParseStyleAttr<Rgb<u8>> for Rgb<u8> {
+    /// Accepts "#rrggbb", "#rgb", "rgb(r, g, b)" and the named colors
+    /// of CSS2.
+    fn parse_attr(attr: Option<&str>) -> Result<Option<Rgb<u8>>, OdsError> {
+        if let Some(s) = attr {
+            match parse_color(s.trim()) {
+                Some(v) => Ok(Some(v)),
+                None => Err(OdsError::Parse("invalid color", Some(s.to_string()))),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+fn parse_color(s: &str) -> Option<Rgb<u8>> {
+    if s.starts_with('#') && s.is_ascii() {
+        let hex = |v: &str| u8::from_str_radix(v, 16).ok();
+        match s.len() - 1 {
+            6 => Some(Rgb::new(hex(&s[1..3])?, hex(&s[3..5])?, hex(&s[5..7])?)),
+            3 => Some(Rgb::new(
+                hex(&s[1..2])? * 17,
+                hex(&s[2..3])? * 17,
+                hex(&s[3..4])? * 17,
+            )),
+            _ => None,
+        }
+    } else if let Some(rgb) = s
+        .get(..4)
+        .filter(|v| v.eq_ignore_ascii_case("rgb("))
+        .and_then(|_| s[4..].strip_suffix(')'))
+    {
+        let mut it = rgb.split(',').map(|v| u8::from_str(v.trim()).ok());
+        match (it.next(), it.next(), it.next(), it.next()) {
+            (Some(r), Some(g), Some(b), None) => Some(Rgb::new(r?, g?, b?)),
+            _ => None,
+        }
+    } else {
+        let (r, g, b) = match s.to_ascii_lowercase().as_str() {
+            "black" => (0, 0, 0),
+            "silver" => (192, 192, 192),
+            "gray" => (128, 128, 128),
+            "white" => (255, 255, 255),
+            "maroon" => (128, 0, 0),
+            "red" => (255, 0, 0),
+            "purple" => (128, 0, 128),
+            "fuchsia" => (255, 0, 255),
+            "green" => (0, 128, 0),
+            "lime" => (0, 255, 0),
+            "olive" => (128, 128, 0),
+            "yellow" => (255, 255, 0),
+            "navy" => (0, 0, 128),
+            "blue" => (0, 0, 255),
+            "teal" => (0, 128, 128),
+            "aqua" => (0, 255, 255),
+            _ => return None,
+        };
+        Some(Rgb::new(r, g, b))
+    }
+}
+
+/// Parses a border attribute as written by border_string().
+/// The parts may occur in any order, missing parts default to
+/// Length::Default, Border::None and black.
+///
+/// The width keywords "thin", "medium" and "thick" are mapped to
+/// 1px, 3px and 5px as in CSS2.
+pub(crate) fn parse_border(
+    attr: Option<&str>,
+) -> Result<Option<(Length, Border, Rgb<u8>)>, OdsError> {
+    if let Some(s) = attr {
+        let mut width = Length::Default;
+        let mut border = Border::None;
+        let mut color = Rgb::new(0, 0, 0);
+
+        let mut parts = s.split_whitespace();
+        while let Some(v) = parts.next() {
+            // rgb(r, g, b) may contain whitespace.
+            let mut v = v.to_string();
+            if v.to_ascii_lowercase().starts_with("rgb(") {
+                while !v.ends_with(')') {
+                    match parts.next() {
+                        Some(p) => v.push_str(p),
+                        None => break,
+                    }
+                }
+            }
+
+            if let Ok(Some(b)) = Border::parse_attr(Some(&v)) {
+                border = b;
+            } else if let Some(w) = match v.as_str() {
+                "thin" => Some(Length::Pt(0.75)),
+                "medium" => Some(Length::Pt(2.25)),
+                "thick" => Some(Length::Pt(3.75)),
+                _ => None,
+            } {
+                width = w;
+            } else if let Ok(Some(w)) = Length::parse_attr(Some(&v)) {
+                width = w;
+            } else if let Some(c) = parse_color(&v) {
+                color = c;
+            } else {
+                return Err(OdsError::Parse("invalid border", Some(s.to_string())));
+            }
+        }
+        Ok(Some((width, border, color)))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Parses the three lengths of a border-line-width attribute.
+pub(crate) fn parse_border_line_width(
+    attr: Option<&str>,
+) -> Result<Option<(Length, Length, Length)>, OdsError> {
+    if let Some(s) = attr {
+        let mut it = s.split_whitespace();
+        match (it.next(), it.next(), it.next(), it.next()) {
+            (Some(inner), Some(space), Some(outer), None) => Ok(Some((
+                Length::parse_attr_def(Some(inner), Length::Default)?,
+                Length::parse_attr_def(Some(space), Length::Default)?,
+                Length::parse_attr_def(Some(outer), Length::Default)?,
+            ))),
+            _ => Err(OdsError::Parse(
+                "invalid border line width",
+                Some(s.to_string()),
+            )),
+        }
+    } else {
+        Ok(None)
+    }
+}
+
 pub(crate) fn color_string(color: Rgb<u8>) -> String {
     format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
 }
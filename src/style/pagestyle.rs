@@ -8,6 +8,8 @@ use crate::style::AnyStyleRef;
 use crate::style::{
     border_line_width_string, border_string, color_string, shadow_string, ParseStyleAttr,
 };
+use crate::style::{parse_border, parse_border_line_width};
+use crate::OdsError;
 use crate::{Length, OdsResult};
 use get_size::GetSize;
 use get_size_derive::GetSize;
@@ -121,6 +123,47 @@ impl PageStyle {
         &self.style
     }
 
+    /// Sets the scaling for printing. This replaces any other scaling
+    /// that was set before.
+    pub fn set_print_scale(&mut self, scale: PrintScale) {
+        self.clear_print_scale();
+        match scale {
+            PrintScale::Percent(v) => self.set_scale_to(Percent::Percent(v)),
+            PrintScale::Pages(v) => self.set_scale_to_pages(v),
+            PrintScale::FitTo { width, height } => {
+                self.set_scale_to_x(width);
+                self.set_scale_to_y(height);
+            }
+        }
+    }
+
+    /// Scaling for printing.
+    pub fn print_scale(&self) -> Result<Option<PrintScale>, OdsError> {
+        if let Some(Percent::Percent(v)) = self.scale_to()? {
+            Ok(Some(PrintScale::Percent(v)))
+        } else if let Some(v) = self.scale_to_pages()? {
+            Ok(Some(PrintScale::Pages(v)))
+        } else {
+            match (self.scale_to_x()?, self.scale_to_y()?) {
+                (None, None) => Ok(None),
+                (width, height) => Ok(Some(PrintScale::FitTo {
+                    width: width.unwrap_or_default(),
+                    height: height.unwrap_or_default(),
+                })),
+            }
+        }
+    }
+
+    /// Removes all scaling.
+    pub fn clear_print_scale(&mut self) {
+        self.style.clear_attr("style:scale-to");
+        self.style.clear_attr("style:scale-to-pages");
+        self.style.clear_attr("style:scale-to-X");
+        self.style.clear_attr("style:scale-to-Y");
+        self.style.clear_attr("loext:scale-to-X");
+        self.style.clear_attr("loext:scale-to-Y");
+    }
+
     /// Access to all style attributes.
     pub fn style_mut(&mut self) -> &mut AttrMap2 {
         &mut self.style
@@ -140,6 +183,8 @@ impl PageStyle {
     style_print_page_order!(style);
     style_scale_to!(style);
     style_scale_to_pages!(style);
+    style_scale_to_x!(style);
+    style_scale_to_y!(style);
     style_table_centering!(style);
     style_writing_mode!(style);
     fo_background_color!(style);
@@ -151,6 +196,23 @@ impl PageStyle {
     style_shadow!(style);
 }
 
+/// Scaling of the printout. See PageStyle::set_print_scale().
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PrintScale {
+    /// Scale to a percentage. 100% means no scaling.
+    Percent(f64),
+    /// Fit the printout to this number of pages.
+    Pages(u32),
+    /// Fit the printout to this number of pages in width and height.
+    /// A value of 0 means no limit in this direction.
+    FitTo {
+        /// Pages in width.
+        width: u32,
+        /// Pages in height.
+        height: u32,
+    },
+}
+
 /// Style attributes for header/footer.
 #[derive(Clone, Debug, Default, GetSize)]
 pub struct HeaderFooterStyle {
@@ -15,6 +15,8 @@ use crate::style::{
     border_line_width_string, border_string, color_string, shadow_string, text_position,
     StyleOrigin, StyleUse, TextStyleRef,
 };
+use crate::style::{parse_border, parse_border_line_width, ParseStyleAttr};
+use crate::OdsError;
 use get_size::GetSize;
 use get_size_derive::GetSize;
 use icu_locid::Locale;
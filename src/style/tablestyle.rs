@@ -5,7 +5,9 @@ use crate::style::units::{
     WritingMode,
 };
 use crate::style::AnyStyleRef;
+use crate::style::ParseStyleAttr;
 use crate::style::{color_string, shadow_string, MasterPageRef, StyleOrigin, StyleUse};
+use crate::OdsError;
 use core::borrow::Borrow;
 use get_size::GetSize;
 use get_size_derive::GetSize;
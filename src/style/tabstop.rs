@@ -6,7 +6,9 @@ use crate::attrmap2::AttrMap2;
 use crate::color::Rgb;
 use crate::style::color_string;
 use crate::style::units::{Length, LineStyle, LineType, LineWidth, TabStopType};
+use crate::style::ParseStyleAttr;
 use crate::style::TextStyleRef;
+use crate::OdsError;
 use get_size::GetSize;
 use get_size_derive::GetSize;
 
@@ -6,7 +6,9 @@ use crate::style::units::{
     TextDisplay, TextEmphasize, TextEmphasizePosition, TextPosition, TextRelief, TextTransform,
 };
 use crate::style::AnyStyleRef;
+use crate::style::ParseStyleAttr;
 use crate::style::{color_string, shadow_string, text_position, StyleOrigin, StyleUse};
+use crate::OdsError;
 use core::borrow::Borrow;
 use get_size::GetSize;
 use get_size_derive::GetSize;
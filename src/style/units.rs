@@ -33,6 +33,26 @@ impl Display for Angle {
     }
 }
 
+impl ParseStyleAttr<Angle> for Angle {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<Angle>, OdsError> {
+        if let Some(s) = attr {
+            if let Some(v) = s.strip_suffix("deg") {
+                Ok(Some(Angle::Deg(v.parse()?)))
+            } else if let Some(v) = s.strip_suffix("grad") {
+                Ok(Some(Angle::Grad(v.parse()?)))
+            } else if let Some(v) = s.strip_suffix("rad") {
+                Ok(Some(Angle::Rad(v.parse()?)))
+            } else if let Ok(v) = s.parse() {
+                Ok(Some(Angle::Deg(v)))
+            } else {
+                Err(OdsError::Parse("invalid angle", Some(s.to_string())))
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// A (positive or negative) length, consisting of magnitude and unit, in conformance with the Units of
 /// Measure defined in §5.9.13 of XSL.
 #[derive(Debug, Clone, Copy, PartialEq, Default, GetSize)]
@@ -67,6 +87,19 @@ impl Length {
             Length::Em(v) => *v,
         }
     }
+
+    /// Converts to pt. Em is calculated with a 12pt font.
+    pub(crate) fn to_pt(self) -> Option<f64> {
+        match self {
+            Length::Default => None,
+            Length::Cm(v) => Some(v * 72.0 / 2.54),
+            Length::Mm(v) => Some(v * 72.0 / 25.4),
+            Length::In(v) => Some(v * 72.0),
+            Length::Pt(v) => Some(v),
+            Length::Pc(v) => Some(v * 12.0),
+            Length::Em(v) => Some(v * 12.0),
+        }
+    }
 }
 
 impl Display for Length {
@@ -122,6 +155,20 @@ impl Display for Percent {
     }
 }
 
+impl ParseStyleAttr<Percent> for Percent {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<Percent>, OdsError> {
+        if let Some(s) = attr {
+            if let Some(v) = s.strip_suffix('%') {
+                Ok(Some(Percent::Percent(v.parse()?)))
+            } else {
+                Err(OdsError::Parse("invalid percent", Some(s.to_string())))
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// Length or percentage.
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[allow(missing_docs)]
@@ -151,6 +198,25 @@ impl Display for LengthPercent {
     }
 }
 
+impl ParseStyleAttr<LengthPercent> for LengthPercent {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<LengthPercent>, OdsError> {
+        if let Some(s) = attr {
+            if s.ends_with('%') {
+                Ok(Percent::parse_attr(attr)?.map(LengthPercent::Percent))
+            } else if let Some(v) = Length::parse_attr(attr).ok().flatten() {
+                Ok(Some(LengthPercent::Length(v)))
+            } else {
+                Err(OdsError::Parse(
+                    "invalid length or percent",
+                    Some(s.to_string()),
+                ))
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 19.348 number:format-source
 ///
 /// The number:format-source attribute specifies the source of definitions of the short and
@@ -278,6 +344,27 @@ impl Display for FontFamilyGeneric {
     }
 }
 
+impl ParseStyleAttr<FontFamilyGeneric> for FontFamilyGeneric {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<FontFamilyGeneric>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "decorative" => Ok(Some(FontFamilyGeneric::Decorative)),
+                "modern" => Ok(Some(FontFamilyGeneric::Modern)),
+                "roman" => Ok(Some(FontFamilyGeneric::Roman)),
+                "script" => Ok(Some(FontFamilyGeneric::Script)),
+                "swiss" => Ok(Some(FontFamilyGeneric::Swiss)),
+                "system" => Ok(Some(FontFamilyGeneric::System)),
+                _ => Err(OdsError::Parse(
+                    "invalid font family generic",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 19.485 style:font-pitch
 /// The style:font-pitch attribute specifies whether a font has a fixed or variable width.
 /// The defined values for the style:font-pitch attribute are:
@@ -300,6 +387,23 @@ impl Display for FontPitch {
     }
 }
 
+impl ParseStyleAttr<FontPitch> for FontPitch {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<FontPitch>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "variable" => Ok(Some(FontPitch::Variable)),
+                "fixed" => Ok(Some(FontPitch::Fixed)),
+                _ => Err(OdsError::Parse(
+                    "invalid font pitch",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 19.509 style:page-usage
 ///
 /// The style:page-usage attribute specifies the type of pages that a master page should
@@ -429,6 +533,30 @@ impl Display for FontStretch {
     }
 }
 
+impl ParseStyleAttr<FontStretch> for FontStretch {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<FontStretch>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "normal" => Ok(Some(FontStretch::Normal)),
+                "ultra-condensed" => Ok(Some(FontStretch::UltraCondensed)),
+                "extra-condensed" => Ok(Some(FontStretch::ExtraCondensed)),
+                "condensed" => Ok(Some(FontStretch::Condensed)),
+                "semi-condensed" => Ok(Some(FontStretch::SemiCondensed)),
+                "semi-expanded" => Ok(Some(FontStretch::SemiExpanded)),
+                "expanded" => Ok(Some(FontStretch::Expanded)),
+                "extra-expanded" => Ok(Some(FontStretch::ExtraExpanded)),
+                "ultra-expanded" => Ok(Some(FontStretch::UltraExpanded)),
+                _ => Err(OdsError::Parse(
+                    "invalid font stretch",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.183 fo-border Properties.
 /// See §7.29.3ff of XSL
 #[allow(missing_docs)]
@@ -463,6 +591,28 @@ impl Display for Border {
     }
 }
 
+impl ParseStyleAttr<Border> for Border {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<Border>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "none" => Ok(Some(Border::None)),
+                "hidden" => Ok(Some(Border::Hidden)),
+                "dotted" => Ok(Some(Border::Dotted)),
+                "dashed" => Ok(Some(Border::Dashed)),
+                "solid" => Ok(Some(Border::Solid)),
+                "double" => Ok(Some(Border::Double)),
+                "groove" => Ok(Some(Border::Groove)),
+                "ridge" => Ok(Some(Border::Ridge)),
+                "inset" => Ok(Some(Border::Inset)),
+                "outset" => Ok(Some(Border::Outset)),
+                _ => Err(OdsError::Parse("invalid border", Some(attr.to_string()))),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.184 fo:break-after, fo:break-before
 /// See §7.19.1 of XSL. The values odd-page and even-page are not supported.
 ///
@@ -491,6 +641,24 @@ impl Display for PageBreak {
     }
 }
 
+impl ParseStyleAttr<PageBreak> for PageBreak {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<PageBreak>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "auto" => Ok(Some(PageBreak::Auto)),
+                "column" => Ok(Some(PageBreak::Column)),
+                "page" => Ok(Some(PageBreak::Page)),
+                _ => Err(OdsError::Parse(
+                    "invalid page break",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.190 fo:font-size
 ///
 /// See §7.8.4 of XSL.
@@ -543,6 +711,22 @@ impl Display for FontSize {
     }
 }
 
+impl ParseStyleAttr<FontSize> for FontSize {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<FontSize>, OdsError> {
+        if let Some(s) = attr {
+            if s.ends_with('%') {
+                Ok(Percent::parse_attr(attr)?.map(FontSize::Percent))
+            } else if let Some(v) = Length::parse_attr(attr).ok().flatten() {
+                Ok(Some(FontSize::Length(v)))
+            } else {
+                Err(OdsError::Parse("invalid font size", Some(s.to_string())))
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.191 fo:font-style
 /// See §7.8.7 of XSL.
 ///
@@ -570,6 +754,24 @@ impl Display for FontStyle {
     }
 }
 
+impl ParseStyleAttr<FontStyle> for FontStyle {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<FontStyle>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "normal" => Ok(Some(FontStyle::Normal)),
+                "italic" => Ok(Some(FontStyle::Italic)),
+                "oblique" => Ok(Some(FontStyle::Oblique)),
+                _ => Err(OdsError::Parse(
+                    "invalid font style",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.192 fo:font-variant
 ///
 /// See §7.8.8 of XSL.
@@ -594,6 +796,23 @@ impl Display for FontVariant {
     }
 }
 
+impl ParseStyleAttr<FontVariant> for FontVariant {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<FontVariant>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "normal" => Ok(Some(FontVariant::Normal)),
+                "small-caps" => Ok(Some(FontVariant::SmallCaps)),
+                _ => Err(OdsError::Parse(
+                    "invalid font variant",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.193 fo:font-weight
 ///
 /// See §7.8.9 of XSL.
@@ -638,6 +857,32 @@ impl Display for FontWeight {
     }
 }
 
+impl ParseStyleAttr<FontWeight> for FontWeight {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<FontWeight>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "normal" => Ok(Some(FontWeight::Normal)),
+                "bold" => Ok(Some(FontWeight::Bold)),
+                "100" => Ok(Some(FontWeight::W100)),
+                "200" => Ok(Some(FontWeight::W200)),
+                "300" => Ok(Some(FontWeight::W300)),
+                "400" => Ok(Some(FontWeight::W400)),
+                "500" => Ok(Some(FontWeight::W500)),
+                "600" => Ok(Some(FontWeight::W600)),
+                "700" => Ok(Some(FontWeight::W700)),
+                "800" => Ok(Some(FontWeight::W800)),
+                "900" => Ok(Some(FontWeight::W900)),
+                _ => Err(OdsError::Parse(
+                    "invalid font weight",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.196 fo:hyphenation-keep
 ///
 /// See §7.15.1 of XSL.
@@ -659,6 +904,23 @@ impl Display for Hyphenation {
     }
 }
 
+impl ParseStyleAttr<Hyphenation> for Hyphenation {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<Hyphenation>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "auto" => Ok(Some(Hyphenation::Auto)),
+                "page" => Ok(Some(Hyphenation::Page)),
+                _ => Err(OdsError::Parse(
+                    "invalid hyphenation",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.197 fo:hyphenation-ladder-count
 ///
 /// See §7.15.2 of XSL.
@@ -682,6 +944,19 @@ impl Display for HyphenationLadderCount {
     }
 }
 
+impl ParseStyleAttr<HyphenationLadderCount> for HyphenationLadderCount {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<HyphenationLadderCount>, OdsError> {
+        if let Some(s) = attr {
+            match s {
+                "no-limit" | "no_limit" => Ok(Some(HyphenationLadderCount::NoLimit)),
+                _ => Ok(Some(HyphenationLadderCount::Count(s.parse()?))),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.200 fo:keep-together and fo:keep-with-next
 /// See §7.19.3 of XSL.
 /// In the OpenDocument XSL-compatible namespace, the fo:keep-together attribute does not
@@ -704,6 +979,20 @@ impl Display for TextKeep {
     }
 }
 
+impl ParseStyleAttr<TextKeep> for TextKeep {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<TextKeep>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "auto" => Ok(Some(TextKeep::Auto)),
+                "always" => Ok(Some(TextKeep::Always)),
+                _ => Err(OdsError::Parse("invalid text keep", Some(attr.to_string()))),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.203 fo:letter-spacing
 ///
 /// See §7.16.2 of XSL.
@@ -737,6 +1026,25 @@ impl Display for LetterSpacing {
     }
 }
 
+impl ParseStyleAttr<LetterSpacing> for LetterSpacing {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<LetterSpacing>, OdsError> {
+        if let Some(s) = attr {
+            if s == "normal" {
+                Ok(Some(LetterSpacing::Normal))
+            } else if let Some(v) = Length::parse_attr(attr).ok().flatten() {
+                Ok(Some(LetterSpacing::Length(v)))
+            } else {
+                Err(OdsError::Parse(
+                    "invalid letter spacing",
+                    Some(s.to_string()),
+                ))
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.204 fo:line-height
 ///
 /// See §7.15.4 of XSL.
@@ -793,6 +1101,24 @@ impl Display for LineHeight {
     }
 }
 
+impl ParseStyleAttr<LineHeight> for LineHeight {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<LineHeight>, OdsError> {
+        if let Some(s) = attr {
+            if s == "normal" {
+                Ok(Some(LineHeight::Normal))
+            } else if s.ends_with('%') {
+                Ok(Percent::parse_attr(attr)?.map(LineHeight::Percent))
+            } else if let Some(v) = Length::parse_attr(attr).ok().flatten() {
+                Ok(Some(LineHeight::Length(v)))
+            } else {
+                Err(OdsError::Parse("invalid line height", Some(s.to_string())))
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.205 fo:margin
 ///
 /// See §7.29.14 of XSL.
@@ -840,6 +1166,22 @@ impl Display for Margin {
     }
 }
 
+impl ParseStyleAttr<Margin> for Margin {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<Margin>, OdsError> {
+        if let Some(s) = attr {
+            if s.ends_with('%') {
+                Ok(Percent::parse_attr(attr)?.map(Margin::Percent))
+            } else if let Some(v) = Length::parse_attr(attr).ok().flatten() {
+                Ok(Some(Margin::Length(v)))
+            } else {
+                Err(OdsError::Parse("invalid margin", Some(s.to_string())))
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.223 fo:text-align
 ///
 /// See §7.15.9 of XSL.
@@ -880,6 +1222,29 @@ impl Display for TextAlign {
     }
 }
 
+impl ParseStyleAttr<TextAlign> for TextAlign {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<TextAlign>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "start" => Ok(Some(TextAlign::Start)),
+                "center" => Ok(Some(TextAlign::Center)),
+                "end" => Ok(Some(TextAlign::End)),
+                "justify" => Ok(Some(TextAlign::Justify)),
+                "inside" => Ok(Some(TextAlign::Inside)),
+                "outside" => Ok(Some(TextAlign::Outside)),
+                "left" => Ok(Some(TextAlign::Left)),
+                "right" => Ok(Some(TextAlign::Right)),
+                _ => Err(OdsError::Parse(
+                    "invalid text align",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.224 fo:text-align-last
 ///
 /// See §7.15.10 of XSL.
@@ -906,6 +1271,24 @@ impl Display for TextAlignLast {
     }
 }
 
+impl ParseStyleAttr<TextAlignLast> for TextAlignLast {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<TextAlignLast>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "start" => Ok(Some(TextAlignLast::Start)),
+                "center" => Ok(Some(TextAlignLast::Center)),
+                "justify" => Ok(Some(TextAlignLast::Justify)),
+                _ => Err(OdsError::Parse(
+                    "invalid text align last",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.225 fo:text-indent
 ///
 /// The fo:text-indent attribute specifies a positive or negative indent for the first line of a
@@ -944,6 +1327,22 @@ impl Display for Indent {
     }
 }
 
+impl ParseStyleAttr<Indent> for Indent {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<Indent>, OdsError> {
+        if let Some(s) = attr {
+            if s.ends_with('%') {
+                Ok(Percent::parse_attr(attr)?.map(Indent::Percent))
+            } else if let Some(v) = Length::parse_attr(attr).ok().flatten() {
+                Ok(Some(Indent::Length(v)))
+            } else {
+                Err(OdsError::Parse("invalid indent", Some(s.to_string())))
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.227 fo:text-transform
 ///
 /// See §7.16.6 of XSL.
@@ -976,6 +1375,25 @@ impl Display for TextTransform {
     }
 }
 
+impl ParseStyleAttr<TextTransform> for TextTransform {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<TextTransform>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "none" => Ok(Some(TextTransform::None)),
+                "lowercase" => Ok(Some(TextTransform::Lowercase)),
+                "uppercase" => Ok(Some(TextTransform::Uppercase)),
+                "capitalize" => Ok(Some(TextTransform::Capitalize)),
+                _ => Err(OdsError::Parse(
+                    "invalid text transform",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.230 fo:wrap-option
 /// See §7.15.13 of XSL.
 ///
@@ -999,6 +1417,23 @@ impl Display for WrapOption {
     }
 }
 
+impl ParseStyleAttr<WrapOption> for WrapOption {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<WrapOption>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "no-wrap" => Ok(Some(WrapOption::NoWrap)),
+                "wrap" => Ok(Some(WrapOption::Wrap)),
+                _ => Err(OdsError::Parse(
+                    "invalid wrap option",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.253 style:cell-protect
 ///
 /// The style:cell-protect attribute specifies how a cell is protected.
@@ -1047,16 +1482,36 @@ impl Display for CellProtect {
     }
 }
 
-/// 20.263 style:direction
-///
-/// The style:direction attribute specifies the direction of characters.
-///
-/// The style:direction attribute modifies the direction of text rendering as specified by a
-/// style:writing-mode attribute. 20.404
-///
-/// The defined values for the style:direction attribute are:
-/// * ltr – left to right, text is rendered in the direction specified by the style:writing-mode
-/// attribute
+impl ParseStyleAttr<CellProtect> for CellProtect {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<CellProtect>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "formula-hidden" => Ok(Some(CellProtect::FormulaHidden)),
+                "hidden-and-protected" => Ok(Some(CellProtect::HiddenAndProtected)),
+                "none" => Ok(Some(CellProtect::None)),
+                "protected" => Ok(Some(CellProtect::Protected)),
+                "protected formula-hidden" => Ok(Some(CellProtect::ProtectedFormulaHidden)),
+                _ => Err(OdsError::Parse(
+                    "invalid cell protect",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// 20.263 style:direction
+///
+/// The style:direction attribute specifies the direction of characters.
+///
+/// The style:direction attribute modifies the direction of text rendering as specified by a
+/// style:writing-mode attribute. 20.404
+///
+/// The defined values for the style:direction attribute are:
+/// * ltr – left to right, text is rendered in the direction specified by the style:writing-mode
+/// attribute
 /// * ttb – top to bottom, characters are vertically stacked but not rotated
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 #[allow(missing_docs)]
@@ -1074,6 +1529,23 @@ impl Display for WritingDirection {
     }
 }
 
+impl ParseStyleAttr<WritingDirection> for WritingDirection {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<WritingDirection>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "ltr" => Ok(Some(WritingDirection::Ltr)),
+                "ttb" => Ok(Some(WritingDirection::Ttb)),
+                _ => Err(OdsError::Parse(
+                    "invalid writing direction",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.283 style:font-relief
 ///
 /// The style:font-relief attribute specifies whether a font should be embossed, engraved, or
@@ -1101,6 +1573,24 @@ impl Display for TextRelief {
     }
 }
 
+impl ParseStyleAttr<TextRelief> for TextRelief {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<TextRelief>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "none" => Ok(Some(TextRelief::None)),
+                "embossed" => Ok(Some(TextRelief::Embossed)),
+                "engraved" => Ok(Some(TextRelief::Engraved)),
+                _ => Err(OdsError::Parse(
+                    "invalid text relief",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.297 style:glyph-orientation-vertical
 ///
 /// The style:glyph-orientation-vertical attribute specifies a vertical glyph orientation.
@@ -1131,6 +1621,17 @@ impl Display for GlyphOrientation {
     }
 }
 
+impl ParseStyleAttr<GlyphOrientation> for GlyphOrientation {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<GlyphOrientation>, OdsError> {
+        match attr {
+            Some("auto") => Ok(Some(GlyphOrientation::Auto)),
+            Some("0") => Ok(Some(GlyphOrientation::Zero)),
+            Some(_) => Ok(Angle::parse_attr(attr)?.map(GlyphOrientation::Angle)),
+            None => Ok(None),
+        }
+    }
+}
+
 /// 20.315 style:line-break
 /// The style:line-break attribute specifies line breaking rules.
 /// The defined values for the style:line-break attribute are:
@@ -1153,6 +1654,23 @@ impl Display for LineBreak {
     }
 }
 
+impl ParseStyleAttr<LineBreak> for LineBreak {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<LineBreak>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "normal" => Ok(Some(LineBreak::Normal)),
+                "strict" => Ok(Some(LineBreak::Strict)),
+                _ => Err(OdsError::Parse(
+                    "invalid line break",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.322 style:num-format
 ///
 /// The style:num-format attribute specifies a numbering sequence.
@@ -1217,6 +1735,19 @@ impl Display for PageNumber {
     }
 }
 
+impl ParseStyleAttr<PageNumber> for PageNumber {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<PageNumber>, OdsError> {
+        if let Some(s) = attr {
+            match s {
+                "auto" => Ok(Some(PageNumber::Auto)),
+                _ => Ok(Some(PageNumber::Number(s.parse()?))),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.330 style:print
 ///
 /// The style:print attribute specifies the components in a spreadsheet document to print.
@@ -1285,6 +1816,23 @@ impl Display for PrintOrder {
     }
 }
 
+impl ParseStyleAttr<PrintOrder> for PrintOrder {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<PrintOrder>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "ltr" => Ok(Some(PrintOrder::Ltr)),
+                "ttb" => Ok(Some(PrintOrder::Ttb)),
+                _ => Err(OdsError::Parse(
+                    "invalid print order",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.333 style:print-orientation
 ///
 /// The style:print-orientation attribute specifies the orientation of the printed page. The
@@ -1309,6 +1857,23 @@ impl Display for PrintOrientation {
     }
 }
 
+impl ParseStyleAttr<PrintOrientation> for PrintOrientation {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<PrintOrientation>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "landscape" => Ok(Some(PrintOrientation::Landscape)),
+                "portrait" => Ok(Some(PrintOrientation::Portrait)),
+                _ => Err(OdsError::Parse(
+                    "invalid print orientation",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.335 style:punctuation-wrap
 ///
 /// The style:punctuation-wrap attribute specifies whether a punctuation mark, if one is
@@ -1335,6 +1900,23 @@ impl Display for PunctuationWrap {
     }
 }
 
+impl ParseStyleAttr<PunctuationWrap> for PunctuationWrap {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<PunctuationWrap>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "hanging" => Ok(Some(PunctuationWrap::Hanging)),
+                "simple" => Ok(Some(PunctuationWrap::Simple)),
+                _ => Err(OdsError::Parse(
+                    "invalid punctuation wrap",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.340 style:rel-width
 ///
 /// The style:rel-width attribute specifies the width of a table relative to the width of the area
@@ -1384,6 +1966,25 @@ impl Display for RotationAlign {
     }
 }
 
+impl ParseStyleAttr<RotationAlign> for RotationAlign {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<RotationAlign>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "none" => Ok(Some(RotationAlign::None)),
+                "bottom" => Ok(Some(RotationAlign::Bottom)),
+                "top" => Ok(Some(RotationAlign::Top)),
+                "center" => Ok(Some(RotationAlign::Center)),
+                _ => Err(OdsError::Parse(
+                    "invalid rotation align",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.363 style:table-centering
 ///
 /// The style:table-centering attribute specifies whether tables are centered horizontally and/
@@ -1419,6 +2020,25 @@ impl Display for PrintCentering {
     }
 }
 
+impl ParseStyleAttr<PrintCentering> for PrintCentering {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<PrintCentering>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "none" => Ok(Some(PrintCentering::None)),
+                "horizontal" => Ok(Some(PrintCentering::Horizontal)),
+                "vertical" => Ok(Some(PrintCentering::Vertical)),
+                "both" => Ok(Some(PrintCentering::Both)),
+                _ => Err(OdsError::Parse(
+                    "invalid print centering",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.364 style:text-align-source
 ///
 /// The style:text-align-source attribute specifies the source of a text-align attribute.
@@ -1446,6 +2066,23 @@ impl Display for TextAlignSource {
     }
 }
 
+impl ParseStyleAttr<TextAlignSource> for TextAlignSource {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<TextAlignSource>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "fix" => Ok(Some(TextAlignSource::Fix)),
+                "value-type" => Ok(Some(TextAlignSource::ValueType)),
+                _ => Err(OdsError::Parse(
+                    "invalid text align source",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.365 style:text-autospace
 ///
 /// The style:text-autospace attribute specifies whether to add space between portions of
@@ -1471,6 +2108,23 @@ impl Display for TextAutoSpace {
     }
 }
 
+impl ParseStyleAttr<TextAutoSpace> for TextAutoSpace {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<TextAutoSpace>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "ideograph-alpha" => Ok(Some(TextAutoSpace::IdeographAlpha)),
+                "none" => Ok(Some(TextAutoSpace::None)),
+                _ => Err(OdsError::Parse(
+                    "invalid text auto space",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.367 style:text-combine
 ///
 /// The style:text-combine attribute specifies whether to combine characters so that they are
@@ -1502,6 +2156,24 @@ impl Display for TextCombine {
     }
 }
 
+impl ParseStyleAttr<TextCombine> for TextCombine {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<TextCombine>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "none" => Ok(Some(TextCombine::None)),
+                "letters" => Ok(Some(TextCombine::Letters)),
+                "lines" => Ok(Some(TextCombine::Lines)),
+                _ => Err(OdsError::Parse(
+                    "invalid text combine",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.370 style:text-emphasize
 ///
 /// The style:text-emphasize attribute specifies emphasis in a text composed of UNICODE
@@ -1602,6 +2274,20 @@ impl Display for LineMode {
     }
 }
 
+impl ParseStyleAttr<LineMode> for LineMode {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<LineMode>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "continuous" => Ok(Some(LineMode::Continuous)),
+                "skip-white-space" => Ok(Some(LineMode::SkipWhiteSpace)),
+                _ => Err(OdsError::Parse("invalid line mode", Some(attr.to_string()))),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// Line style for underline, overline, line-through.
 ///
 /// 20.373 style:text-line-through-style
@@ -1655,6 +2341,29 @@ impl Display for LineStyle {
     }
 }
 
+impl ParseStyleAttr<LineStyle> for LineStyle {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<LineStyle>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "dash" => Ok(Some(LineStyle::Dash)),
+                "dot-dash" => Ok(Some(LineStyle::DotDash)),
+                "dot-dot-dash" => Ok(Some(LineStyle::DotDotDash)),
+                "dotted" => Ok(Some(LineStyle::Dotted)),
+                "long-dash" => Ok(Some(LineStyle::LongDash)),
+                "none" => Ok(Some(LineStyle::None)),
+                "solid" => Ok(Some(LineStyle::Solid)),
+                "wave" => Ok(Some(LineStyle::Wave)),
+                _ => Err(OdsError::Parse(
+                    "invalid line style",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.376 style:text-line-through-type
 /// 20.382 style:text-overline-type
 /// 20.391 style:text-underline-type
@@ -1691,6 +2400,21 @@ impl Display for LineType {
     }
 }
 
+impl ParseStyleAttr<LineType> for LineType {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<LineType>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "none" => Ok(Some(LineType::None)),
+                "single" => Ok(Some(LineType::Single)),
+                "double" => Ok(Some(LineType::Double)),
+                _ => Err(OdsError::Parse("invalid line type", Some(attr.to_string()))),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// Line width for underline, overline, line-through.
 ///
 /// 20.377 style:text-line-through-width
@@ -1761,6 +2485,35 @@ impl Display for LineWidth {
     }
 }
 
+impl ParseStyleAttr<LineWidth> for LineWidth {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<LineWidth>, OdsError> {
+        if let Some(s) = attr {
+            match s {
+                "auto" => Ok(Some(LineWidth::Auto)),
+                "bold" => Ok(Some(LineWidth::Bold)),
+                "normal" => Ok(Some(LineWidth::Normal)),
+                "dash" => Ok(Some(LineWidth::Dash)),
+                "thin" => Ok(Some(LineWidth::Thin)),
+                "medium" => Ok(Some(LineWidth::Medium)),
+                "thick" => Ok(Some(LineWidth::Thick)),
+                _ => {
+                    if s.ends_with('%') {
+                        Ok(Percent::parse_attr(attr)?.map(LineWidth::Percent))
+                    } else if let Ok(v) = s.parse() {
+                        Ok(Some(LineWidth::Int(v)))
+                    } else if let Some(v) = Length::parse_attr(attr).ok().flatten() {
+                        Ok(Some(LineWidth::Length(v)))
+                    } else {
+                        Err(OdsError::Parse("invalid line width", Some(s.to_string())))
+                    }
+                }
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.384 style:text-position
 ///
 /// The style:text-position attribute specifies whether text is positioned above or below the
@@ -1824,6 +2577,23 @@ impl Display for RotationScale {
     }
 }
 
+impl ParseStyleAttr<RotationScale> for RotationScale {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<RotationScale>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "fixed" => Ok(Some(RotationScale::Fixed)),
+                "line-height" => Ok(Some(RotationScale::LineHeight)),
+                _ => Err(OdsError::Parse(
+                    "invalid rotation scale",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.396 style:vertical-align
 ///
 /// The style:vertical-align attribute specifies the vertical position of a character. By default
@@ -1862,6 +2632,26 @@ impl Display for ParaAlignVertical {
     }
 }
 
+impl ParseStyleAttr<ParaAlignVertical> for ParaAlignVertical {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<ParaAlignVertical>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "top" => Ok(Some(ParaAlignVertical::Top)),
+                "middle" => Ok(Some(ParaAlignVertical::Middle)),
+                "bottom" => Ok(Some(ParaAlignVertical::Bottom)),
+                "auto" => Ok(Some(ParaAlignVertical::Auto)),
+                "baseline" => Ok(Some(ParaAlignVertical::Baseline)),
+                _ => Err(OdsError::Parse(
+                    "invalid para align vertical",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.396 style:vertical-align
 ///
 /// The style:vertical-align attribute specifies the vertical alignment of text in a table cell. The
@@ -1895,6 +2685,25 @@ impl Display for CellAlignVertical {
     }
 }
 
+impl ParseStyleAttr<CellAlignVertical> for CellAlignVertical {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<CellAlignVertical>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "top" => Ok(Some(CellAlignVertical::Top)),
+                "middle" => Ok(Some(CellAlignVertical::Middle)),
+                "bottom" => Ok(Some(CellAlignVertical::Bottom)),
+                "automatic" => Ok(Some(CellAlignVertical::Automatic)),
+                _ => Err(OdsError::Parse(
+                    "invalid cell align vertical",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.404 style:writing-mode
 ///
 /// See §7.27.7 of XSL with the additional value of page.
@@ -1931,6 +2740,29 @@ impl Display for WritingMode {
     }
 }
 
+impl ParseStyleAttr<WritingMode> for WritingMode {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<WritingMode>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "lr-tb" => Ok(Some(WritingMode::LrTb)),
+                "rl-tb" => Ok(Some(WritingMode::RlTb)),
+                "tb-rl" => Ok(Some(WritingMode::TbRl)),
+                "tb-lr" => Ok(Some(WritingMode::TbLr)),
+                "lr" => Ok(Some(WritingMode::Lr)),
+                "rl" => Ok(Some(WritingMode::Rl)),
+                "tb" => Ok(Some(WritingMode::Tb)),
+                "page" => Ok(Some(WritingMode::Page)),
+                _ => Err(OdsError::Parse(
+                    "invalid writing mode",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.414 table:align
 ///
 /// The table:align attribute specifies the horizontal alignment of a table.
@@ -1962,6 +2794,25 @@ impl Display for TableAlign {
     }
 }
 
+impl ParseStyleAttr<TableAlign> for TableAlign {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<TableAlign>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "center" => Ok(Some(TableAlign::Center)),
+                "left" => Ok(Some(TableAlign::Left)),
+                "right" => Ok(Some(TableAlign::Right)),
+                "margins" => Ok(Some(TableAlign::Margins)),
+                _ => Err(OdsError::Parse(
+                    "invalid table align",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.415 table:border-model
 ///
 /// The table:border-model attribute specifies what border model to use when creating a table
@@ -1992,6 +2843,23 @@ impl Display for TableBorderModel {
     }
 }
 
+impl ParseStyleAttr<TableBorderModel> for TableBorderModel {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<TableBorderModel>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "collapsing" => Ok(Some(TableBorderModel::Collapsing)),
+                "separating" => Ok(Some(TableBorderModel::Separating)),
+                _ => Err(OdsError::Parse(
+                    "invalid table border model",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 /// 20.426 text:condition
 ///
 /// The text:condition attribute specifies the display of text.
@@ -2036,3 +2904,189 @@ impl Display for TextDisplay {
         }
     }
 }
+
+/// draw:fill
+///
+/// The draw:fill attribute specifies the fill style for a graphic object.
+///
+/// The defined values for the draw:fill attribute are:
+/// • bitmap: the drawing object is filled with the bitmap specified by the draw:fill-image-name
+/// attribute.
+/// • gradient: the drawing object is filled with the gradient specified by the draw:fill-gradient-name
+/// attribute.
+/// • hatch: the drawing object is filled with the hatch specified by the draw:fill-hatch-name
+/// attribute.
+/// • none: the drawing object is not filled.
+/// • solid: the drawing object is filled with the color specified by the draw:fill-color attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub enum DrawFill {
+    None,
+    Solid,
+    Bitmap,
+    Gradient,
+    Hatch,
+}
+
+impl Display for DrawFill {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DrawFill::None => write!(f, "none"),
+            DrawFill::Solid => write!(f, "solid"),
+            DrawFill::Bitmap => write!(f, "bitmap"),
+            DrawFill::Gradient => write!(f, "gradient"),
+            DrawFill::Hatch => write!(f, "hatch"),
+        }
+    }
+}
+
+impl ParseStyleAttr<DrawFill> for DrawFill {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<DrawFill>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "none" => Ok(Some(DrawFill::None)),
+                "solid" => Ok(Some(DrawFill::Solid)),
+                "bitmap" => Ok(Some(DrawFill::Bitmap)),
+                "gradient" => Ok(Some(DrawFill::Gradient)),
+                "hatch" => Ok(Some(DrawFill::Hatch)),
+                _ => Err(OdsError::Parse("invalid draw:fill", Some(attr.to_string()))),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// draw:stroke
+///
+/// The draw:stroke attribute specifies the style of the stroke on the current object.
+///
+/// The defined values for the draw:stroke attribute are:
+/// • dash: a dashed line is used.
+/// • none: no stroke is drawn.
+/// • solid: a solid line is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub enum DrawStroke {
+    None,
+    Dash,
+    Solid,
+}
+
+impl Display for DrawStroke {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DrawStroke::None => write!(f, "none"),
+            DrawStroke::Dash => write!(f, "dash"),
+            DrawStroke::Solid => write!(f, "solid"),
+        }
+    }
+}
+
+impl ParseStyleAttr<DrawStroke> for DrawStroke {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<DrawStroke>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "none" => Ok(Some(DrawStroke::None)),
+                "dash" => Ok(Some(DrawStroke::Dash)),
+                "solid" => Ok(Some(DrawStroke::Solid)),
+                _ => Err(OdsError::Parse(
+                    "invalid draw:stroke",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// draw:textarea-horizontal-align
+///
+/// The draw:textarea-horizontal-align attribute specifies the horizontal text anchor
+/// of the text of a drawing shape.
+///
+/// The defined values are left, center, right and justify.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub enum TextAreaHorizontalAlign {
+    Left,
+    Center,
+    Right,
+    Justify,
+}
+
+impl Display for TextAreaHorizontalAlign {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TextAreaHorizontalAlign::Left => write!(f, "left"),
+            TextAreaHorizontalAlign::Center => write!(f, "center"),
+            TextAreaHorizontalAlign::Right => write!(f, "right"),
+            TextAreaHorizontalAlign::Justify => write!(f, "justify"),
+        }
+    }
+}
+
+impl ParseStyleAttr<TextAreaHorizontalAlign> for TextAreaHorizontalAlign {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<TextAreaHorizontalAlign>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "left" => Ok(Some(TextAreaHorizontalAlign::Left)),
+                "center" => Ok(Some(TextAreaHorizontalAlign::Center)),
+                "right" => Ok(Some(TextAreaHorizontalAlign::Right)),
+                "justify" => Ok(Some(TextAreaHorizontalAlign::Justify)),
+                _ => Err(OdsError::Parse(
+                    "invalid draw:textarea-horizontal-align",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// draw:textarea-vertical-align
+///
+/// The draw:textarea-vertical-align attribute specifies the vertical text anchor
+/// of the text of a drawing shape.
+///
+/// The defined values are top, middle, bottom and justify.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub enum TextAreaVerticalAlign {
+    Top,
+    Middle,
+    Bottom,
+    Justify,
+}
+
+impl Display for TextAreaVerticalAlign {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TextAreaVerticalAlign::Top => write!(f, "top"),
+            TextAreaVerticalAlign::Middle => write!(f, "middle"),
+            TextAreaVerticalAlign::Bottom => write!(f, "bottom"),
+            TextAreaVerticalAlign::Justify => write!(f, "justify"),
+        }
+    }
+}
+
+impl ParseStyleAttr<TextAreaVerticalAlign> for TextAreaVerticalAlign {
+    fn parse_attr(attr: Option<&str>) -> Result<Option<TextAreaVerticalAlign>, OdsError> {
+        if let Some(attr) = attr {
+            match attr {
+                "top" => Ok(Some(TextAreaVerticalAlign::Top)),
+                "middle" => Ok(Some(TextAreaVerticalAlign::Middle)),
+                "bottom" => Ok(Some(TextAreaVerticalAlign::Bottom)),
+                "justify" => Ok(Some(TextAreaVerticalAlign::Justify)),
+                _ => Err(OdsError::Parse(
+                    "invalid draw:textarea-vertical-align",
+                    Some(attr.to_string()),
+                )),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
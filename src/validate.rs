@@ -0,0 +1,677 @@
+//!
+//! Structural checks for a WorkBook.
+//!
+//! validate() looks for problems that would produce a broken or
+//! misleading document when written: references to styles that don't
+//! exist, attribute values that can't be parsed, overlapping cell spans,
+//! references to missing sheets and duplicate sheet names.
+//!
+//! Reading with OdsOptions::collect_issues() additionally reports the
+//! elements and attributes the reader doesn't understand.
+//!
+//! ```
+//! use spreadsheet_ods::{Sheet, WorkBook};
+//! use spreadsheet_ods::validate::{validate, IssueKind};
+//!
+//! let mut wb = WorkBook::new_empty();
+//! let mut sh = Sheet::new("one");
+//! sh.set_formula(0, 0, "of:=[$two.A1]");
+//! wb.push_sheet(sh);
+//!
+//! let issues = validate(&wb);
+//! assert_eq!(issues[0].kind(), IssueKind::MissingSheet);
+//! ```
+//!
+
+use crate::attrmap2::AttrMap2;
+use crate::color::Rgb;
+use crate::format::ValueFormatTrait;
+use crate::refs::{parse_cellrange, parse_cellref, replace_formula_refs};
+use crate::style::units::{
+    Angle, CellAlignVertical, CellProtect, FontStyle, FontWeight, Length, LineStyle,
+    ParaAlignVertical, Percent, TextAlign, WrapOption,
+};
+use crate::style::{parse_border, ParseStyleAttr, StyleOrigin};
+use crate::xmltree::{XmlContent, XmlTag};
+use crate::{CellRef, HashMap, Sheet, Value, WorkBook};
+use get_size::GetSize;
+use get_size_derive::GetSize;
+use std::fmt::{Display, Formatter};
+
+/// Kind of problem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GetSize)]
+pub enum IssueKind {
+    /// A style, value-format, validation or page-layout is referenced but
+    /// doesn't exist.
+    DanglingStyleRef,
+    /// An attribute value can't be parsed.
+    InvalidAttrValue,
+    /// Two cell spans or matrix spans overlap.
+    OverlappingSpan,
+    /// A formula or cell-range references a sheet that doesn't exist.
+    MissingSheet,
+    /// Two sheets have the same name.
+    DuplicateSheetName,
+    /// The reader found an element it doesn't understand.
+    UnknownElement,
+    /// The reader found an attribute it doesn't understand.
+    UnknownAttribute,
+}
+
+impl Display for IssueKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            IssueKind::DanglingStyleRef => "dangling reference",
+            IssueKind::InvalidAttrValue => "invalid attribute value",
+            IssueKind::OverlappingSpan => "overlapping span",
+            IssueKind::MissingSheet => "missing sheet",
+            IssueKind::DuplicateSheetName => "duplicate sheet name",
+            IssueKind::UnknownElement => "unknown element",
+            IssueKind::UnknownAttribute => "unknown attribute",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// A problem found by validate() or while reading.
+#[derive(Debug, Clone, PartialEq, GetSize)]
+pub struct Issue {
+    kind: IssueKind,
+    location: String,
+    message: String,
+}
+
+impl Issue {
+    /// New issue.
+    pub fn new<S: Into<String>, T: Into<String>>(kind: IssueKind, location: S, message: T) -> Self {
+        Self {
+            kind,
+            location: location.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Kind of problem.
+    pub fn kind(&self) -> IssueKind {
+        self.kind
+    }
+
+    /// Where the problem was found. This is a cell-reference like
+    /// "Sheet1.B3", the name of a style like "style ce1", or a position
+    /// in a file like "content.xml:1024" when reading.
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+
+    /// Description.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}: {}", self.location, self.kind, self.message)
+    }
+}
+
+/// Checks the workbook for structural problems.
+///
+/// The result is empty if nothing was found.
+pub fn validate(book: &WorkBook) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    check_sheet_names(book, &mut issues);
+    for sheet in book.iter_sheets() {
+        check_sheet(book, sheet, &mut issues);
+        check_spans(sheet, &mut issues);
+    }
+    check_styles(book, &mut issues);
+
+    issues
+}
+
+fn check_sheet_names(book: &WorkBook, issues: &mut Vec<Issue>) {
+    let mut names: HashMap<&str, usize> = HashMap::new();
+    for (idx, sheet) in book.iter_sheets().enumerate() {
+        if let Some(first) = names.get(sheet.name().as_str()) {
+            issues.push(Issue::new(
+                IssueKind::DuplicateSheetName,
+                format!("sheet {}", idx),
+                format!("'{}' is already used by sheet {}", sheet.name(), first),
+            ));
+        } else {
+            names.insert(sheet.name().as_str(), idx);
+        }
+    }
+}
+
+fn check_sheet(book: &WorkBook, sheet: &Sheet, issues: &mut Vec<Issue>) {
+    if let Some(style) = &sheet.style {
+        if book.tablestyle(style).is_none() {
+            dangling(issues, sheet.name(), "table-style", style.as_str());
+        }
+    }
+    for (col, header) in &sheet.col_header {
+        let location = || CellRef::remote(sheet.name(), 0, *col).to_string();
+        if let Some(style) = &header.style {
+            if book.colstyle(style).is_none() {
+                dangling(issues, location(), "column-style", style.as_str());
+            }
+        }
+        if let Some(style) = &header.cellstyle {
+            if book.cellstyle(style).is_none() {
+                dangling(issues, location(), "cell-style", style.as_str());
+            }
+        }
+    }
+    for (row, header) in &sheet.row_header {
+        let location = || CellRef::remote(sheet.name(), *row, 0).to_string();
+        if let Some(style) = &header.style {
+            if book.rowstyle(style).is_none() {
+                dangling(issues, location(), "row-style", style.as_str());
+            }
+        }
+        if let Some(style) = &header.cellstyle {
+            if book.cellstyle(style).is_none() {
+                dangling(issues, location(), "cell-style", style.as_str());
+            }
+        }
+    }
+
+    for ((row, col), cell) in &sheet.data {
+        let location = || CellRef::remote(sheet.name(), *row, *col).to_string();
+        if let Some(style) = &cell.style {
+            if book.cellstyle(style).is_none() {
+                dangling(issues, location(), "cell-style", style.as_str());
+            }
+        }
+        if let Some(extra) = &cell.extra {
+            if let Some(valid) = &extra.validation_name {
+                if book.validation(valid).is_none() {
+                    dangling(issues, location(), "validation", valid.as_str());
+                }
+            }
+            for frame in &extra.draw_frames {
+                check_graphic_style(book, frame.attrmap(), &location(), issues);
+            }
+            for shape in &extra.draw_shapes {
+                check_graphic_style(book, shape.attrmap(), &location(), issues);
+                check_control(sheet, shape.attrmap(), &location(), issues);
+            }
+        }
+        if let Some(formula) = &cell.formula {
+            for table in formula_tables(formula) {
+                if book.sheet_idx(&table).is_none() {
+                    missing_sheet(issues, location(), &table);
+                }
+            }
+        }
+        if let Value::TextXml(text) = &cell.value {
+            for tag in text {
+                check_text_styles(book, tag, &location(), issues);
+            }
+        }
+    }
+
+    for frame in sheet.page_frames() {
+        check_graphic_style(book, frame.attrmap(), sheet.name(), issues);
+    }
+    for shape in sheet.page_shapes() {
+        check_graphic_style(book, shape.attrmap(), sheet.name(), issues);
+        check_control(sheet, shape.attrmap(), sheet.name(), issues);
+    }
+
+    for form in &sheet.forms {
+        for control in form.controls() {
+            let location = format!(
+                "{} form-control {}",
+                sheet.name(),
+                control.id().unwrap_or("")
+            );
+            if let Ok(Some(cell)) = control.linked_cell() {
+                if let Some(table) = cell.table() {
+                    if book.sheet_idx(table).is_none() {
+                        missing_sheet(issues, location.as_str(), table);
+                    }
+                }
+            }
+            if let Ok(Some(range)) = control.source_cell_range() {
+                for table in [range.table(), range.to_table()].into_iter().flatten() {
+                    if book.sheet_idx(table).is_none() {
+                        missing_sheet(issues, location.as_str(), table);
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(print_ranges) = &sheet.print_ranges {
+        for range in print_ranges {
+            for table in [range.table(), range.to_table()].into_iter().flatten() {
+                if range.iri().is_none() && book.sheet_idx(table).is_none() {
+                    missing_sheet(issues, format!("{} print-range", sheet.name()), table);
+                }
+            }
+        }
+    }
+}
+
+// Graphic style of a frame or shape.
+fn check_graphic_style(book: &WorkBook, attr: &AttrMap2, location: &str, issues: &mut Vec<Issue>) {
+    if let Some(style) = attr.attr("draw:style-name") {
+        if book.graphicstyle(style).is_none() {
+            dangling(issues, location, "graphic-style", style);
+        }
+    }
+}
+
+// Form control placed by a draw:control. Controls that are only kept as xml
+// count too.
+fn check_control(sheet: &Sheet, attr: &AttrMap2, location: &str, issues: &mut Vec<Issue>) {
+    if let Some(id) = attr.attr("draw:control") {
+        let found = sheet.forms.iter().any(|form| {
+            form.control(id).is_some()
+                || form
+                    .extra()
+                    .iter()
+                    .any(|v| v.get_attr("form:id") == Some(id) || v.get_attr("xml:id") == Some(id))
+        });
+        if !found {
+            dangling(issues, location, "form-control", id);
+        }
+    }
+}
+
+// Text styles used in text:span and text:p.
+fn check_text_styles(book: &WorkBook, tag: &XmlTag, location: &str, issues: &mut Vec<Issue>) {
+    if let Some(style) = tag.get_attr("text:style-name") {
+        let found = if tag.name() == "text:p" || tag.name() == "text:h" {
+            book.paragraphstyle(style).is_some()
+        } else {
+            book.textstyle(style).is_some()
+        };
+        if !found {
+            dangling(issues, location, "text-style", style);
+        }
+    }
+    for content in tag.content() {
+        if let XmlContent::Tag(tag) = content {
+            check_text_styles(book, tag, location, issues);
+        }
+    }
+}
+
+// Text styles in headers and footers. styles.xml can't reference the
+// styles in content.xml, so they must be defined with StyleOrigin::Styles.
+fn check_header_styles(book: &WorkBook, tag: &XmlTag, location: &str, issues: &mut Vec<Issue>) {
+    if let Some(style) = tag.get_attr("text:style-name") {
+        let origin = if tag.name() == "text:p" || tag.name() == "text:h" {
+            book.paragraphstyle(style).map(|v| v.origin())
+        } else {
+            book.textstyle(style).map(|v| v.origin())
+        };
+        match origin {
+            None => dangling(issues, location, "text-style", style),
+            Some(StyleOrigin::Content) => issues.push(Issue::new(
+                IssueKind::DanglingStyleRef,
+                location,
+                format!("text-style '{}' is not defined in styles.xml", style),
+            )),
+            Some(StyleOrigin::Styles) => {}
+        }
+    }
+    for content in tag.content() {
+        if let XmlContent::Tag(tag) = content {
+            check_header_styles(book, tag, location, issues);
+        }
+    }
+}
+
+// Pairwise check of all spans. Spans are rare enough for this.
+fn check_spans(sheet: &Sheet, issues: &mut Vec<Issue>) {
+    let mut spans = Vec::new();
+    let mut matrix_spans = Vec::new();
+    for ((row, col), cell) in &sheet.data {
+        if let Some(extra) = &cell.extra {
+            if extra.span.row_span > 1 || extra.span.col_span > 1 {
+                spans.push((*row, *col, extra.span.row_span, extra.span.col_span));
+            }
+            if extra.matrix_span.row_span > 1 || extra.matrix_span.col_span > 1 {
+                matrix_spans.push((
+                    *row,
+                    *col,
+                    extra.matrix_span.row_span,
+                    extra.matrix_span.col_span,
+                ));
+            }
+        }
+    }
+
+    for (what, spans) in [("span", spans), ("matrix span", matrix_spans)] {
+        for (i, a) in spans.iter().enumerate() {
+            for b in &spans[i + 1..] {
+                if overlap(*a, *b) {
+                    issues.push(Issue::new(
+                        IssueKind::OverlappingSpan,
+                        CellRef::remote(sheet.name(), b.0, b.1).to_string(),
+                        format!(
+                            "{} overlaps the {} at {}",
+                            what,
+                            what,
+                            CellRef::local(a.0, a.1)
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+fn overlap(a: (u32, u32, u32, u32), b: (u32, u32, u32, u32)) -> bool {
+    let (ar, ac, ars, acs) = a;
+    let (br, bc, brs, bcs) = b;
+    ar < br + brs.max(1) && br < ar + ars.max(1) && ac < bc + bcs.max(1) && bc < ac + acs.max(1)
+}
+
+fn check_styles(book: &WorkBook, issues: &mut Vec<Issue>) {
+    for style in book.iter_cellstyles() {
+        let location = format!("style {}", style.name());
+        if let Some(parent) = style.attrmap().attr("style:parent-style-name") {
+            if book.cellstyle(parent).is_none() {
+                dangling(issues, &location, "parent style", parent);
+            }
+        }
+        if let Some(format) = style.value_format() {
+            if !has_value_format(book, format) {
+                dangling(issues, &location, "value-format", format);
+            }
+        }
+        if let Some(stylemaps) = style.stylemaps() {
+            for sm in stylemaps {
+                let applied = sm.applied_style().as_str();
+                if book.cellstyle(applied).is_none() {
+                    dangling(issues, &location, "applied style", applied);
+                }
+                if let Some(table) = sm.base_cell().and_then(|v| v.table()) {
+                    if book.sheet_idx(table).is_none() {
+                        missing_sheet(issues, &location, table);
+                    }
+                }
+            }
+        }
+        check_attrmap(style.attrmap(), &location, issues);
+        check_attrmap(style.cellstyle(), &location, issues);
+        check_attrmap(style.paragraphstyle(), &location, issues);
+        check_attrmap(style.textstyle(), &location, issues);
+    }
+    for style in book.iter_table_styles() {
+        let location = format!("style {}", style.name());
+        if let Some(master) = style.attrmap().attr("style:master-page-name") {
+            if book.masterpage(master).is_none() {
+                dangling(issues, &location, "master-page", master);
+            }
+        }
+        check_attrmap(style.tablestyle(), &location, issues);
+    }
+    for style in book.iter_colstyles() {
+        check_attrmap(style.colstyle(), &format!("style {}", style.name()), issues);
+    }
+    for style in book.iter_rowstyles() {
+        check_attrmap(style.rowstyle(), &format!("style {}", style.name()), issues);
+    }
+    for style in book.iter_paragraphstyles() {
+        let location = format!("style {}", style.name());
+        check_attrmap(style.paragraphstyle(), &location, issues);
+        check_attrmap(style.textstyle(), &location, issues);
+    }
+    for style in book.iter_textstyles() {
+        check_attrmap(
+            style.textstyle(),
+            &format!("style {}", style.name()),
+            issues,
+        );
+    }
+    for style in book.iter_graphicstyles() {
+        let location = format!("style {}", style.name());
+        check_attrmap(style.graphicstyle(), &location, issues);
+        check_attrmap(style.paragraphstyle(), &location, issues);
+        check_attrmap(style.textstyle(), &location, issues);
+    }
+    for style in book.iter_pagestyles() {
+        let location = format!("page-layout {}", style.name());
+        check_attrmap(style.style(), &location, issues);
+        check_attrmap(style.headerstyle().style(), &location, issues);
+        check_attrmap(style.footerstyle().style(), &location, issues);
+    }
+    for master in book.iter_masterpages() {
+        if let Some(page) = master.pagestyle() {
+            if book.pagestyle(page).is_none() {
+                dangling(
+                    issues,
+                    format!("master-page {}", master.name()),
+                    "page-layout",
+                    page.as_str(),
+                );
+            }
+        }
+        let location = format!("master-page {}", master.name());
+        for hf in [
+            master.header(),
+            master.header_first(),
+            master.header_left(),
+            master.footer(),
+            master.footer_first(),
+            master.footer_left(),
+        ] {
+            for tag in hf
+                .left()
+                .iter()
+                .chain(hf.center())
+                .chain(hf.right())
+                .chain(hf.content())
+            {
+                check_header_styles(book, tag, &location, issues);
+            }
+        }
+    }
+    for style in book.def_styles.values() {
+        if book.cellstyle(style).is_none() {
+            dangling(issues, "default styles", "cell-style", style.as_str());
+        }
+    }
+    for valid in book.iter_validations() {
+        let location = format!("validation {}", valid.name());
+        let mut tables = formula_tables(&valid.condition().to_string());
+        tables.extend(valid.base_cell().table().cloned());
+        for table in tables {
+            if book.sheet_idx(&table).is_none() {
+                missing_sheet(issues, &location, &table);
+            }
+        }
+    }
+
+    check_value_formats(book, book.iter_boolean_formats(), issues);
+    check_value_formats(book, book.iter_number_formats(), issues);
+    check_value_formats(book, book.iter_percentage_formats(), issues);
+    check_value_formats(book, book.iter_currency_formats(), issues);
+    check_value_formats(book, book.iter_text_formats(), issues);
+    check_value_formats(book, book.iter_datetime_formats(), issues);
+    check_value_formats(book, book.iter_timeduration_formats(), issues);
+}
+
+fn check_value_formats<'a, T: ValueFormatTrait + 'a>(
+    book: &WorkBook,
+    formats: impl Iterator<Item = &'a T>,
+    issues: &mut Vec<Issue>,
+) {
+    for format in formats {
+        let location = format!("value-format {}", format.name());
+        if let Some(stylemaps) = format.stylemaps() {
+            for sm in stylemaps {
+                if !has_value_format(book, sm.applied_style()) {
+                    dangling(issues, &location, "applied style", sm.applied_style());
+                }
+            }
+        }
+        check_attrmap(format.textstyle(), &location, issues);
+    }
+}
+
+fn has_value_format(book: &WorkBook, name: &str) -> bool {
+    book.formats_boolean.contains_key(name)
+        || book.formats_number.contains_key(name)
+        || book.formats_percentage.contains_key(name)
+        || book.formats_currency.contains_key(name)
+        || book.formats_text.contains_key(name)
+        || book.formats_datetime.contains_key(name)
+        || book.formats_timeduration.contains_key(name)
+}
+
+fn dangling<S: Into<String>>(issues: &mut Vec<Issue>, location: S, what: &str, name: &str) {
+    issues.push(Issue::new(
+        IssueKind::DanglingStyleRef,
+        location,
+        format!("{} '{}' doesn't exist", what, name),
+    ));
+}
+
+fn missing_sheet<S: Into<String>>(issues: &mut Vec<Issue>, location: S, table: &str) {
+    issues.push(Issue::new(
+        IssueKind::MissingSheet,
+        location,
+        format!("sheet '{}' doesn't exist", table),
+    ));
+}
+
+/// Extracts the sheet names from the references in a formula.
+/// References to external files are ignored.
+fn formula_tables(formula: &str) -> Vec<String> {
+    let mut tables = Vec::new();
+
+    replace_formula_refs(formula, |inner| {
+        if let Ok(range) = parse_cellrange(inner) {
+            if range.iri().is_none() {
+                tables.extend(range.table().cloned());
+                tables.extend(range.to_table().cloned());
+            }
+        } else if let Ok(cellref) = parse_cellref(inner) {
+            if cellref.iri().is_none() {
+                tables.extend(cellref.table().cloned());
+            }
+        }
+        None
+    });
+
+    tables.sort();
+    tables.dedup();
+    tables
+}
+
+fn check_attrmap(attrmap: &AttrMap2, location: &str, issues: &mut Vec<Issue>) {
+    for (name, value) in attrmap.iter() {
+        if let Some(expected) = check_attr(name, value) {
+            issues.push(Issue::new(
+                IssueKind::InvalidAttrValue,
+                location,
+                format!("{}=\"{}\" is not {}", name, value, expected),
+            ));
+        }
+    }
+}
+
+/// Returns a description of the expected value if the value is invalid.
+/// Attributes that are not known are always valid.
+///
+/// Uses the same parsers as the typed style getters. Lengths follow the
+/// schema and allow px too.
+fn check_attr(name: &str, value: &str) -> Option<&'static str> {
+    let v = Some(value);
+    let (valid, expected) = match name {
+        "fo:color" | "fo:border-color" => (Rgb::<u8>::parse_attr(v).is_ok(), "a color"),
+        "fo:background-color" => (
+            value == "transparent" || Rgb::<u8>::parse_attr(v).is_ok(),
+            "a color",
+        ),
+        "style:text-underline-color"
+        | "style:text-overline-color"
+        | "style:text-line-through-color" => (
+            value == "font-color" || Rgb::<u8>::parse_attr(v).is_ok(),
+            "a color",
+        ),
+
+        "fo:font-weight" | "style:font-weight-asian" | "style:font-weight-complex" => {
+            (FontWeight::parse_attr(v).is_ok(), "an allowed value")
+        }
+        "fo:font-style" | "style:font-style-asian" | "style:font-style-complex" => {
+            (FontStyle::parse_attr(v).is_ok(), "an allowed value")
+        }
+        "fo:font-size" | "style:font-size-asian" | "style:font-size-complex" => {
+            (is_length_percent(value), "a length or percentage")
+        }
+        "fo:text-align" => (TextAlign::parse_attr(v).is_ok(), "an allowed value"),
+        // used for cells and paragraphs.
+        "style:vertical-align" => (
+            CellAlignVertical::parse_attr(v).is_ok() || ParaAlignVertical::parse_attr(v).is_ok(),
+            "an allowed value",
+        ),
+        "fo:wrap-option" => (WrapOption::parse_attr(v).is_ok(), "an allowed value"),
+        "style:text-underline-style"
+        | "style:text-overline-style"
+        | "style:text-line-through-style" => (LineStyle::parse_attr(v).is_ok(), "an allowed value"),
+        "style:cell-protect" => (CellProtect::parse_attr(v).is_ok(), "an allowed value"),
+        "style:shrink-to-fit" | "style:print-content" | "fo:hyphenate" => {
+            (bool::parse_attr(v).is_ok(), "a boolean")
+        }
+        "style:rotation-angle" => (Angle::parse_attr(v).is_ok(), "an angle"),
+
+        "fo:border"
+        | "fo:border-top"
+        | "fo:border-bottom"
+        | "fo:border-left"
+        | "fo:border-right"
+        | "style:diagonal-bl-tr"
+        | "style:diagonal-tl-br" => (parse_border(v).is_ok(), "a border definition"),
+
+        "style:column-width"
+        | "style:row-height"
+        | "style:min-row-height"
+        | "fo:padding"
+        | "fo:padding-left"
+        | "fo:padding-right"
+        | "fo:padding-top"
+        | "fo:padding-bottom"
+        | "fo:page-width"
+        | "fo:page-height"
+        | "svg:width"
+        | "svg:height"
+        | "style:width" => (is_length(value), "a length"),
+        "fo:margin" | "fo:margin-left" | "fo:margin-right" | "fo:margin-top"
+        | "fo:margin-bottom" | "fo:min-height" | "fo:text-indent" => {
+            (is_length_percent(value), "a length or percentage")
+        }
+        "fo:line-height" => (
+            value == "normal" || is_length_percent(value),
+            "normal, a length or percentage",
+        ),
+        _ => return None,
+    };
+
+    if valid {
+        None
+    } else {
+        Some(expected)
+    }
+}
+
+/// Length as defined by the schema. Length doesn't know px.
+fn is_length(value: &str) -> bool {
+    Length::parse_attr(Some(value)).is_ok()
+        || value
+            .strip_suffix("px")
+            .is_some_and(|v| v.parse::<f64>().is_ok())
+}
+
+fn is_length_percent(value: &str) -> bool {
+    is_length(value) || Percent::parse_attr(Some(value)).is_ok()
+}
@@ -9,7 +9,8 @@ use std::fmt::{Display, Formatter};
 use crate::condition::Condition;
 use crate::style::AnyStyleRef;
 use crate::text::TextTag;
-use crate::{CellRef, OdsError};
+use crate::value_::Value;
+use crate::{CellRange, CellRef, OdsError, Sheet, WorkBook};
 use get_size_derive::GetSize;
 use std::borrow::Borrow;
 use std::str::from_utf8;
@@ -120,7 +121,7 @@ impl ValidationHelp {
 /// Determines the severity of a validation error.
 /// When this is error the entered value is discarded, otherwise
 /// the error is just shown as a warning or a hint.
-#[derive(Copy, Clone, Debug, GetSize)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, GetSize)]
 pub enum MessageType {
     /// Hard error.
     Error,
@@ -324,3 +325,191 @@ impl Validation {
         self.help.as_ref()
     }
 }
+
+/// Kind of a validation violation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViolationKind {
+    /// The value doesn't satisfy the condition.
+    Invalid,
+    /// The cell is empty, but the validation doesn't allow empty cells.
+    Empty,
+    /// The condition can't be evaluated, e.g. an is-true-formula().
+    Unsupported,
+}
+
+/// A cell that doesn't satisfy its validation. Found by
+/// [WorkBook::check_validations].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    cell: CellRef,
+    validation: ValidationRef,
+    kind: ViolationKind,
+    msg_type: MessageType,
+    title: Option<String>,
+    message: Option<String>,
+}
+
+impl Violation {
+    /// The cell.
+    pub fn cell(&self) -> &CellRef {
+        &self.cell
+    }
+
+    /// The validation that failed.
+    pub fn validation(&self) -> &ValidationRef {
+        &self.validation
+    }
+
+    /// Kind of violation.
+    pub fn kind(&self) -> ViolationKind {
+        self.kind
+    }
+
+    /// Severity as defined by the validation.
+    pub fn msg_type(&self) -> MessageType {
+        self.msg_type
+    }
+
+    /// Title of the error message of the validation.
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Text of the error message of the validation.
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: ", self.cell)?;
+        match self.kind {
+            ViolationKind::Invalid => write!(f, "invalid value")?,
+            ViolationKind::Empty => write!(f, "empty value")?,
+            ViolationKind::Unsupported => write!(f, "unsupported condition")?,
+        }
+        write!(f, " for validation {}", self.validation.as_str())?;
+        if let Some(message) = &self.message {
+            write!(f, ": {}", message)?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks all cells with a validation.
+pub(crate) fn check_validations(book: &WorkBook) -> Vec<Violation> {
+    let mut violations = Vec::new();
+
+    for sheet in book.iter_sheets() {
+        for ((row, col), cell) in sheet.data.iter() {
+            let Some(valid_ref) = cell.extra.as_ref().and_then(|v| v.validation_name.as_ref())
+            else {
+                continue;
+            };
+            // dangling references are reported by validate().
+            let Some(valid) = book.validation(valid_ref) else {
+                continue;
+            };
+
+            // repeated rows and cells each count as a cell of their own.
+            let rows = *row..*row + sheet.row_repeat(*row).max(1);
+            let cols = *col..*col + cell.repeat.max(1);
+            for (row, col) in rows.flat_map(|row| cols.clone().map(move |col| (row, col))) {
+                let kind = if is_empty(&cell.value) {
+                    if valid.allow_empty() {
+                        continue;
+                    }
+                    ViolationKind::Empty
+                } else {
+                    let lookup = |range: &CellRange| {
+                        lookup_range(book, sheet, valid.base_cell(), row, col, range)
+                    };
+                    match valid.condition().eval_validation(&cell.value, &lookup) {
+                        Some(true) => continue,
+                        Some(false) => ViolationKind::Invalid,
+                        None => ViolationKind::Unsupported,
+                    }
+                };
+
+                violations.push(Violation {
+                    cell: CellRef::remote(sheet.name().as_str(), row, col),
+                    validation: valid_ref.clone(),
+                    kind,
+                    msg_type: valid
+                        .err()
+                        .map(|v| *v.msg_type())
+                        .unwrap_or(MessageType::Error),
+                    title: valid.err().and_then(|v| v.title()).map(|v| v.to_string()),
+                    message: valid.err().and_then(|v| v.text()).map(|v| {
+                        let mut buf = String::new();
+                        v.extract_text(&mut buf);
+                        buf
+                    }),
+                });
+            }
+        }
+    }
+
+    violations
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Empty => true,
+        Value::Text(v) => v.is_empty(),
+        _ => false,
+    }
+}
+
+/// Values of the cell-range of a cell-content-is-in-list(). Relative
+/// references are moved by the distance of the cell to the base-cell.
+/// A repeated cell is listed once.
+fn lookup_range(
+    book: &WorkBook,
+    sheet: &Sheet,
+    base_cell: &CellRef,
+    row: u32,
+    col: u32,
+    range: &CellRange,
+) -> Option<Vec<Value>> {
+    if range.iri().is_some() {
+        return None;
+    }
+    let table = range
+        .table()
+        .or(base_cell.table())
+        .unwrap_or_else(|| sheet.name());
+    let source = book.sheet(book.sheet_idx(table)?);
+
+    let row_delta = row as i64 - base_cell.row() as i64;
+    let col_delta = col as i64 - base_cell.col() as i64;
+    let shift = |v: u32, abs: bool, delta: i64| {
+        if abs {
+            Some(v)
+        } else {
+            u32::try_from(v as i64 + delta).ok()
+        }
+    };
+    let from_row = shift(range.row(), range.row_abs(), row_delta)?;
+    let from_col = shift(range.col(), range.col_abs(), col_delta)?;
+    let to_row = shift(range.to_row(), range.to_row_abs(), row_delta)?;
+    let to_col = shift(range.to_col(), range.to_col_abs(), col_delta)?;
+    // mixed absolute/relative references can end up reversed.
+    let (from_row, to_row) = (from_row.min(to_row), from_row.max(to_row));
+    let (from_col, to_col) = (from_col.min(to_col), from_col.max(to_col));
+
+    // repeated rows and cells can start before the range.
+    Some(
+        source
+            .data
+            .range(..=(to_row, u32::MAX))
+            .filter(|((r, c), cell)| {
+                let to_r = r.saturating_add(source.row_repeat(*r).max(1) - 1);
+                let to_c = c.saturating_add(cell.repeat.max(1) - 1);
+                to_r >= from_row && *c <= to_col && to_c >= from_col
+            })
+            .map(|(_, cell)| cell.value.clone())
+            .collect(),
+    )
+}
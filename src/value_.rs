@@ -6,6 +6,7 @@ use rust_decimal::prelude::FromPrimitive;
 use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
 use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
 
 /// Datatypes for the values. Only the discriminants of the Value enum.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, GetSize)]
@@ -20,6 +21,74 @@ pub enum ValueType {
     TextXml,
     DateTime,
     TimeDuration,
+    Error,
+}
+
+/// Error results of a formula as defined by OpenFormula.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FormulaError {
+    /// #NULL! The intersection of two ranges is empty.
+    Null,
+    /// #DIV/0! Division by zero.
+    Div0,
+    /// #VALUE! Wrong type of argument.
+    Value,
+    /// #REF! Invalid reference.
+    Ref,
+    /// #NAME? Unknown function or name.
+    Name,
+    /// #NUM! Invalid numeric value.
+    Num,
+    /// #N/A No value available.
+    NA,
+    /// Any other error text, e.g. "Err:502" from LibreOffice.
+    Other(String),
+}
+
+impl GetSize for FormulaError {
+    fn get_heap_size(&self) -> usize {
+        match self {
+            FormulaError::Other(v) => v.get_heap_size(),
+            _ => 0,
+        }
+    }
+}
+
+impl FormulaError {
+    /// Error text as shown in the cell.
+    pub fn as_str(&self) -> &str {
+        match self {
+            FormulaError::Null => "#NULL!",
+            FormulaError::Div0 => "#DIV/0!",
+            FormulaError::Value => "#VALUE!",
+            FormulaError::Ref => "#REF!",
+            FormulaError::Name => "#NAME?",
+            FormulaError::Num => "#NUM!",
+            FormulaError::NA => "#N/A",
+            FormulaError::Other(v) => v.as_str(),
+        }
+    }
+}
+
+impl From<&str> for FormulaError {
+    fn from(s: &str) -> Self {
+        match s.trim() {
+            "#NULL!" => FormulaError::Null,
+            "#DIV/0!" => FormulaError::Div0,
+            "#VALUE!" => FormulaError::Value,
+            "#REF!" => FormulaError::Ref,
+            "#NAME?" => FormulaError::Name,
+            "#NUM!" => FormulaError::Num,
+            "#N/A" => FormulaError::NA,
+            s => FormulaError::Other(s.to_string()),
+        }
+    }
+}
+
+impl Display for FormulaError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
 }
 
 /// Content-Values
@@ -32,10 +101,18 @@ pub enum Value {
     Number(f64),
     Percentage(f64),
     Currency(f64, Box<str>),
+    /// Number stored as decimal. Read with OdsOptions::use_decimal().
+    NumberDecimal(Decimal),
+    /// Percentage stored as decimal. Read with OdsOptions::use_decimal().
+    PercentageDecimal(Decimal),
+    /// Currency stored as decimal. Read with OdsOptions::use_decimal().
+    CurrencyDecimal(Decimal, Box<str>),
     Text(String),
     TextXml(Vec<TextTag>),
     DateTime(NaiveDateTime),
     TimeDuration(Duration),
+    /// Error result of a formula.
+    Error(FormulaError),
 }
 
 impl GetSize for Value {
@@ -46,10 +123,14 @@ impl GetSize for Value {
             Value::Number(_) => 0,
             Value::Percentage(_) => 0,
             Value::Currency(_, v) => v.get_heap_size(),
+            Value::NumberDecimal(_) => 0,
+            Value::PercentageDecimal(_) => 0,
+            Value::CurrencyDecimal(_, v) => v.get_heap_size(),
             Value::Text(v) => v.get_heap_size(),
             Value::TextXml(v) => v.get_heap_size(),
             Value::DateTime(_) => 0,
             Value::TimeDuration(_) => 0,
+            Value::Error(v) => v.get_heap_size(),
         }
     }
 }
@@ -63,10 +144,14 @@ impl Value {
             Value::Number(_) => ValueType::Number,
             Value::Percentage(_) => ValueType::Percentage,
             Value::Currency(_, _) => ValueType::Currency,
+            Value::NumberDecimal(_) => ValueType::Number,
+            Value::PercentageDecimal(_) => ValueType::Percentage,
+            Value::CurrencyDecimal(_, _) => ValueType::Currency,
             Value::Text(_) => ValueType::Text,
             Value::TextXml(_) => ValueType::TextXml,
             Value::TimeDuration(_) => ValueType::TimeDuration,
             Value::DateTime(_) => ValueType::DateTime,
+            Value::Error(_) => ValueType::Error,
         }
     }
 
@@ -85,6 +170,9 @@ impl Value {
             Value::Number(n) => *n as i64,
             Value::Percentage(p) => *p as i64,
             Value::Currency(v, _) => *v as i64,
+            Value::NumberDecimal(v)
+            | Value::PercentageDecimal(v)
+            | Value::CurrencyDecimal(v, _) => v.to_i64().unwrap_or(d),
             _ => d,
         }
     }
@@ -96,6 +184,9 @@ impl Value {
             Value::Number(n) => Some(*n as i64),
             Value::Percentage(p) => Some(*p as i64),
             Value::Currency(v, _) => Some(*v as i64),
+            Value::NumberDecimal(v)
+            | Value::PercentageDecimal(v)
+            | Value::CurrencyDecimal(v, _) => v.to_i64(),
             _ => None,
         }
     }
@@ -107,6 +198,9 @@ impl Value {
             Value::Number(n) => *n as u64,
             Value::Percentage(p) => *p as u64,
             Value::Currency(v, _) => *v as u64,
+            Value::NumberDecimal(v)
+            | Value::PercentageDecimal(v)
+            | Value::CurrencyDecimal(v, _) => v.to_u64().unwrap_or(d),
             _ => d,
         }
     }
@@ -118,6 +212,9 @@ impl Value {
             Value::Number(n) => Some(*n as u64),
             Value::Percentage(p) => Some(*p as u64),
             Value::Currency(v, _) => Some(*v as u64),
+            Value::NumberDecimal(v)
+            | Value::PercentageDecimal(v)
+            | Value::CurrencyDecimal(v, _) => v.to_u64(),
             _ => None,
         }
     }
@@ -129,6 +226,9 @@ impl Value {
             Value::Number(n) => *n as i32,
             Value::Percentage(p) => *p as i32,
             Value::Currency(v, _) => *v as i32,
+            Value::NumberDecimal(v)
+            | Value::PercentageDecimal(v)
+            | Value::CurrencyDecimal(v, _) => v.to_i32().unwrap_or(d),
             _ => d,
         }
     }
@@ -140,6 +240,9 @@ impl Value {
             Value::Number(n) => Some(*n as i32),
             Value::Percentage(p) => Some(*p as i32),
             Value::Currency(v, _) => Some(*v as i32),
+            Value::NumberDecimal(v)
+            | Value::PercentageDecimal(v)
+            | Value::CurrencyDecimal(v, _) => v.to_i32(),
             _ => None,
         }
     }
@@ -151,6 +254,9 @@ impl Value {
             Value::Number(n) => *n as u32,
             Value::Percentage(p) => *p as u32,
             Value::Currency(v, _) => *v as u32,
+            Value::NumberDecimal(v)
+            | Value::PercentageDecimal(v)
+            | Value::CurrencyDecimal(v, _) => v.to_u32().unwrap_or(d),
             _ => d,
         }
     }
@@ -162,6 +268,9 @@ impl Value {
             Value::Number(n) => Some(*n as u32),
             Value::Percentage(p) => Some(*p as u32),
             Value::Currency(v, _) => Some(*v as u32),
+            Value::NumberDecimal(v)
+            | Value::PercentageDecimal(v)
+            | Value::CurrencyDecimal(v, _) => v.to_u32(),
             _ => None,
         }
     }
@@ -173,6 +282,9 @@ impl Value {
             Value::Number(n) => *n as i16,
             Value::Percentage(p) => *p as i16,
             Value::Currency(v, _) => *v as i16,
+            Value::NumberDecimal(v)
+            | Value::PercentageDecimal(v)
+            | Value::CurrencyDecimal(v, _) => v.to_i16().unwrap_or(d),
             _ => d,
         }
     }
@@ -184,6 +296,9 @@ impl Value {
             Value::Number(n) => Some(*n as i16),
             Value::Percentage(p) => Some(*p as i16),
             Value::Currency(v, _) => Some(*v as i16),
+            Value::NumberDecimal(v)
+            | Value::PercentageDecimal(v)
+            | Value::CurrencyDecimal(v, _) => v.to_i16(),
             _ => None,
         }
     }
@@ -195,6 +310,9 @@ impl Value {
             Value::Number(n) => *n as u16,
             Value::Percentage(p) => *p as u16,
             Value::Currency(v, _) => *v as u16,
+            Value::NumberDecimal(v)
+            | Value::PercentageDecimal(v)
+            | Value::CurrencyDecimal(v, _) => v.to_u16().unwrap_or(d),
             _ => d,
         }
     }
@@ -206,6 +324,9 @@ impl Value {
             Value::Number(n) => Some(*n as u16),
             Value::Percentage(p) => Some(*p as u16),
             Value::Currency(v, _) => Some(*v as u16),
+            Value::NumberDecimal(v)
+            | Value::PercentageDecimal(v)
+            | Value::CurrencyDecimal(v, _) => v.to_u16(),
             _ => None,
         }
     }
@@ -217,6 +338,9 @@ impl Value {
             Value::Number(n) => *n as i8,
             Value::Percentage(p) => *p as i8,
             Value::Currency(v, _) => *v as i8,
+            Value::NumberDecimal(v)
+            | Value::PercentageDecimal(v)
+            | Value::CurrencyDecimal(v, _) => v.to_i8().unwrap_or(d),
             _ => d,
         }
     }
@@ -228,6 +352,9 @@ impl Value {
             Value::Number(n) => Some(*n as i8),
             Value::Percentage(p) => Some(*p as i8),
             Value::Currency(v, _) => Some(*v as i8),
+            Value::NumberDecimal(v)
+            | Value::PercentageDecimal(v)
+            | Value::CurrencyDecimal(v, _) => v.to_i8(),
             _ => None,
         }
     }
@@ -239,6 +366,9 @@ impl Value {
             Value::Number(n) => *n as u8,
             Value::Percentage(p) => *p as u8,
             Value::Currency(v, _) => *v as u8,
+            Value::NumberDecimal(v)
+            | Value::PercentageDecimal(v)
+            | Value::CurrencyDecimal(v, _) => v.to_u8().unwrap_or(d),
             _ => d,
         }
     }
@@ -250,6 +380,9 @@ impl Value {
             Value::Number(n) => Some(*n as u8),
             Value::Percentage(p) => Some(*p as u8),
             Value::Currency(v, _) => Some(*v as u8),
+            Value::NumberDecimal(v)
+            | Value::PercentageDecimal(v)
+            | Value::CurrencyDecimal(v, _) => v.to_u8(),
             _ => None,
         }
     }
@@ -262,6 +395,9 @@ impl Value {
             Value::Number(n) => Decimal::from_f64(*n).unwrap_or(d),
             Value::Currency(v, _) => Decimal::from_f64(*v).unwrap_or(d),
             Value::Percentage(p) => Decimal::from_f64(*p).unwrap_or(d),
+            Value::NumberDecimal(v)
+            | Value::PercentageDecimal(v)
+            | Value::CurrencyDecimal(v, _) => *v,
             _ => d,
         }
     }
@@ -274,6 +410,9 @@ impl Value {
             Value::Number(n) => Decimal::from_f64(*n),
             Value::Currency(v, _) => Decimal::from_f64(*v),
             Value::Percentage(p) => Decimal::from_f64(*p),
+            Value::NumberDecimal(v)
+            | Value::PercentageDecimal(v)
+            | Value::CurrencyDecimal(v, _) => Some(*v),
             _ => None,
         }
     }
@@ -285,6 +424,9 @@ impl Value {
             Value::Number(n) => *n,
             Value::Currency(v, _) => *v,
             Value::Percentage(p) => *p,
+            Value::NumberDecimal(v)
+            | Value::PercentageDecimal(v)
+            | Value::CurrencyDecimal(v, _) => v.to_f64().unwrap_or(d),
             _ => d,
         }
     }
@@ -296,6 +438,9 @@ impl Value {
             Value::Number(n) => Some(*n),
             Value::Currency(v, _) => Some(*v),
             Value::Percentage(p) => Some(*p),
+            Value::NumberDecimal(v)
+            | Value::PercentageDecimal(v)
+            | Value::CurrencyDecimal(v, _) => v.to_f64(),
             _ => None,
         }
     }
@@ -336,6 +481,14 @@ impl Value {
         }
     }
 
+    /// Return the formula error if the value is an Error.
+    pub fn as_error_opt(&self) -> Option<&FormulaError> {
+        match self {
+            Value::Error(e) => Some(e),
+            _ => None,
+        }
+    }
+
     /// Return the content as Duration if the value is a TimeDuration.
     /// Default otherwise.
     pub fn as_timeduration_or(&self, d: Duration) -> Duration {
@@ -394,6 +547,7 @@ impl Value {
     pub fn currency(&self) -> &str {
         match self {
             Value::Currency(_, c) => c,
+            Value::CurrencyDecimal(_, c) => c,
             _ => "",
         }
     }
@@ -408,6 +562,16 @@ impl Value {
     pub fn new_percentage(value: f64) -> Self {
         Value::Percentage(value)
     }
+
+    /// Create a currency value stored as decimal.
+    pub fn new_currency_decimal<S: AsRef<str>>(cur: S, value: Decimal) -> Self {
+        Value::CurrencyDecimal(value, cur.as_ref().into())
+    }
+
+    /// Create a percentage value stored as decimal.
+    pub fn new_percentage_decimal(value: Decimal) -> Self {
+        Value::PercentageDecimal(value)
+    }
 }
 
 /// currency value
@@ -495,7 +659,7 @@ impl From<Option<String>> for Value {
 #[cfg(feature = "use_decimal")]
 impl From<Decimal> for Value {
     fn from(f: Decimal) -> Self {
-        Value::Number(f.to_f64().expect("decimal->f64 should not fail"))
+        Value::NumberDecimal(f)
     }
 }
 
@@ -503,7 +667,7 @@ impl From<Decimal> for Value {
 impl From<Option<Decimal>> for Value {
     fn from(f: Option<Decimal>) -> Self {
         if let Some(f) = f {
-            Value::Number(f.to_f64().expect("decimal->f64 should not fail"))
+            Value::NumberDecimal(f)
         } else {
             Value::Empty
         }
@@ -561,6 +725,12 @@ from_number!(u32);
 from_number!(u16);
 from_number!(u8);
 
+impl From<FormulaError> for Value {
+    fn from(e: FormulaError) -> Self {
+        Value::Error(e)
+    }
+}
+
 impl From<bool> for Value {
     fn from(b: bool) -> Self {
         Value::Boolean(b)
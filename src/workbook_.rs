@@ -11,28 +11,33 @@ use std::hash::Hash;
 
 use icu_locid::{locale, Locale};
 
+use crate::attrmap2::AttrMap2;
 use crate::config::Config;
 use crate::defaultstyles::{DefaultFormat, DefaultStyle};
+use crate::draw::{DrawFrameContent, SheetImage};
 use crate::ds::detach::{Detach, Detached};
+use crate::find::{self, FindQuery};
 use crate::format::ValueFormatTrait;
 use crate::io::read::default_settings;
 use crate::io::NamespaceMap;
 use crate::manifest::Manifest;
 use crate::metadata::Metadata;
 use crate::sheet_::Sheet;
+use crate::sheetrefs::{self, RefChanges};
 use crate::style::{
     ColStyle, ColStyleRef, FontFaceDecl, GraphicStyle, GraphicStyleRef, MasterPage, MasterPageRef,
     PageStyle, PageStyleRef, ParagraphStyle, ParagraphStyleRef, RowStyle, RowStyleRef, RubyStyle,
-    RubyStyleRef, TableStyle, TableStyleRef, TextStyle, TextStyleRef,
+    RubyStyleRef, StyleUse, TableStyle, TableStyleRef, TextStyle, TextStyleRef,
 };
-use crate::validation::{Validation, ValidationRef};
+use crate::validate::Issue;
+use crate::validation::{self, Validation, ValidationRef, Violation};
 use crate::value_::ValueType;
 use crate::xlink::{XLinkActuate, XLinkType};
 use crate::xmltree::{XmlContent, XmlTag};
 use crate::{
-    locale, CellStyle, CellStyleRef, HashMap, ValueFormatBoolean, ValueFormatCurrency,
-    ValueFormatDateTime, ValueFormatNumber, ValueFormatPercentage, ValueFormatRef, ValueFormatText,
-    ValueFormatTimeDuration,
+    locale, CellRef, CellStyle, CellStyleRef, HashMap, OdsError, ValueFormatBoolean,
+    ValueFormatCurrency, ValueFormatDateTime, ValueFormatNumber, ValueFormatPercentage,
+    ValueFormatRef, ValueFormatText, ValueFormatTimeDuration,
 };
 
 /// Book is the main structure for the Spreadsheet.
@@ -99,6 +104,9 @@ pub struct WorkBook {
     /// Metadata
     pub(crate) metadata: Metadata,
 
+    /// Problems found while reading.
+    pub(crate) issues: Vec<Issue>,
+
     /// other stuff ...
     pub(crate) extra: Vec<XmlTag>,
 }
@@ -236,6 +244,15 @@ fn auto_style_name<T>(
     style_name
 }
 
+/// Copies all attributes that are not already set.
+fn merge_attr(target: &mut AttrMap2, source: &AttrMap2) {
+    for (k, v) in source.iter() {
+        if target.attr(k.as_ref()).is_none() {
+            target.set_attr(k.as_ref(), v);
+        }
+    }
+}
+
 impl Default for WorkBook {
     fn default() -> Self {
         WorkBook::new(locale!("en"))
@@ -275,6 +292,7 @@ impl WorkBook {
             validations: Default::default(),
             config: default_settings(),
             workbook_config: Default::default(),
+            issues: Default::default(),
             extra: vec![],
             manifest: Default::default(),
             metadata: Default::default(),
@@ -288,7 +306,8 @@ impl WorkBook {
     /// If the locale is not supported no ValueFormat's are set and all
     /// depends on the application opening the spreadsheet.
     ///
-    /// The available locales can be activated via feature-flags.
+    /// The available locales can be activated via feature-flags, others
+    /// can be added with [register_locale](crate::locale::register_locale).
     pub fn new(locale: Locale) -> Self {
         let mut wb = WorkBook::new_empty();
         wb.locale_settings(locale);
@@ -300,7 +319,8 @@ impl WorkBook {
     /// If the locale is not supported no ValueFormat's are set and all
     /// depends on the application opening the spreadsheet.
     ///
-    /// The available locales can be activated via feature-flags.
+    /// The available locales can be activated via feature-flags, others
+    /// can be added with [register_locale](crate::locale::register_locale).
     pub fn locale_settings(&mut self, locale: Locale) {
         if let Some(lf) = locale::localized_format(locale) {
             self.add_boolean_format(lf.boolean_format());
@@ -432,6 +452,63 @@ impl WorkBook {
         self.sheets.iter().map(|sheet| &**sheet)
     }
 
+    /// Finds all cells in all sheets that match the query.
+    ///
+    /// Fails if the query is an invalid regex.
+    pub fn find(&self, query: &FindQuery) -> Result<Vec<CellRef>, OdsError> {
+        let matcher = query.matcher()?;
+        Ok(self
+            .iter_sheets()
+            .flat_map(|sheet| find::find_in_sheet(sheet, &matcher))
+            .collect())
+    }
+
+    /// Replaces the matches of the query in all sheets. See
+    /// [Sheet::replace_all]. Returns the number of changed cells.
+    ///
+    /// Fails if the query is an invalid regex.
+    pub fn replace_all(&mut self, query: &FindQuery, replacement: &str) -> Result<usize, OdsError> {
+        let matcher = query.matcher()?;
+        Ok(self
+            .sheets
+            .iter_mut()
+            .map(|sheet| find::replace_in_sheet(sheet.as_mut(), &matcher, replacement))
+            .sum())
+    }
+
+    /// Returns an iterator over all images in all sheets.
+    ///
+    /// The image data is available via [SheetImage::bytes], regardless of
+    /// whether it is embedded or stored as separate file in the package.
+    pub fn images(&self) -> impl Iterator<Item = SheetImage<'_>> {
+        self.sheets
+            .iter()
+            .enumerate()
+            .flat_map(move |(idx, sheet)| {
+                sheet
+                    .data
+                    .iter()
+                    .filter_map(|(pos, cell)| cell.extra.as_ref().map(|extra| (pos, extra)))
+                    .flat_map(move |((row, col), extra)| {
+                        extra.draw_frames.iter().flat_map(move |frame| {
+                            frame
+                                .content_ref()
+                                .iter()
+                                .filter_map(move |content| match content {
+                                    DrawFrameContent::Image(image) => Some(SheetImage::new(
+                                        self,
+                                        idx,
+                                        CellRef::remote(sheet.name(), *row, *col),
+                                        frame,
+                                        image,
+                                    )),
+                                    _ => None,
+                                })
+                        })
+                    })
+            })
+    }
+
     /// Inserts the sheet at the given position.
     pub fn insert_sheet(&mut self, i: usize, sheet: Sheet) {
         self.sheets.insert(i, sheet.into());
@@ -451,6 +528,52 @@ impl WorkBook {
         self.sheets.remove(n).take()
     }
 
+    /// Renames a sheet and updates all references to it.
+    ///
+    /// This changes formulas, the conditions and base-cells of validations
+    /// and conditional styles, print-ranges, the end-cell of draw frames
+    /// and shapes, named expressions, database ranges and the view settings.
+    ///
+    /// Fails if the name is empty, contains one of `[]*?:/\`, starts or
+    /// ends with an apostrophe or is already used by another sheet.
+    ///
+    /// Panics
+    ///
+    /// Panics if n is out of bounds or if any sheet is detached.
+    pub fn rename_sheet<S: AsRef<str>>(
+        &mut self,
+        n: usize,
+        name: S,
+    ) -> Result<RefChanges, OdsError> {
+        sheetrefs::rename_sheet(self, n, name.as_ref())
+    }
+
+    /// Deletes a sheet. All references to it are replaced with #REF!
+    /// the same way LibreOffice does. Print-ranges into the sheet are
+    /// removed, the view settings are dropped.
+    ///
+    /// Panics
+    ///
+    /// Panics if n is out of bounds or if any sheet is detached.
+    pub fn delete_sheet(&mut self, n: usize) -> RefChanges {
+        sheetrefs::delete_sheet(self, n)
+    }
+
+    /// Moves a sheet to a new position.
+    ///
+    /// References use the sheet-name, so most of them stay as they are.
+    /// A range spanning several sheets like `[$Sheet1.A1:$Sheet3.B2]`
+    /// covers the sheets between its ends. If the moved sheet is one
+    /// end and comes after the other end now, the ends are swapped the
+    /// same way LibreOffice does.
+    ///
+    /// Panics
+    ///
+    /// Panics if from or to are out of bounds or if any sheet is detached.
+    pub fn move_sheet(&mut self, from: usize, to: usize) -> RefChanges {
+        sheetrefs::move_sheet(self, from, to)
+    }
+
     /// Scripts.
     pub fn add_script(&mut self, v: Script) {
         self.scripts.push(v);
@@ -656,6 +779,121 @@ impl WorkBook {
         self.cellstyles.get_mut(name.as_ref())
     }
 
+    /// Returns the effective cell-style for the given cell.
+    ///
+    /// This resolves the style following the rules of the ODF specification.
+    /// The search starts with the cell-style, or the default style for the
+    /// value-type if the cell has no style. Only if there is neither, the
+    /// default cell-style of the row or else of the column is used. It
+    /// continues with the parent styles and ends with the default-style for
+    /// the table-cell family.
+    ///
+    /// If the cell-style has style-maps, the first condition that matches
+    /// the cell-value selects the applied style, which takes precedence over
+    /// everything else. Conditions that depend on something other than the
+    /// cell-content are not evaluated.
+    ///
+    /// The result contains all resolved attributes, has the name of the
+    /// cell-style and neither a parent-style nor style-maps.
+    ///
+    /// Panics
+    ///
+    /// Panics if the sheet index is out of bounds.
+    pub fn effective_cell_style(&self, sheet: usize, row: u32, col: u32) -> CellStyle {
+        let sheet = self.sheet(sheet);
+        let value = sheet.value(row, col);
+
+        let cell_style = sheet
+            .cellstyle(row, col)
+            .or_else(|| {
+                if !sheet.is_empty(row, col) {
+                    self.def_style(value.value_type())
+                } else {
+                    None
+                }
+            })
+            .or_else(|| sheet.row_cellstyle(row))
+            .or_else(|| sheet.col_cellstyle(col));
+
+        let mut applied = None;
+        if let Some(style) = cell_style.and_then(|v| self.cellstyle(v)) {
+            if let Some(stylemaps) = style.stylemaps() {
+                for sm in stylemaps {
+                    if sm.condition().eval_cell_content(value) == Some(true) {
+                        applied = Some(sm.applied_style().as_str());
+                        break;
+                    }
+                }
+            }
+        }
+
+        self.merge_cellstyles(applied, cell_style)
+    }
+
+    /// Resolves the cell-style with its parents and the default-style
+    /// for the table-cell family. Style-maps are not evaluated.
+    pub(crate) fn resolved_cellstyle(&self, style: &CellStyleRef) -> CellStyle {
+        self.merge_cellstyles(None, Some(style))
+    }
+
+    // Merges the applied style of a style-map, the cell-style and the
+    // default-style, each with its parents.
+    fn merge_cellstyles(
+        &self,
+        applied: Option<&str>,
+        cell_style: Option<&CellStyleRef>,
+    ) -> CellStyle {
+        let mut chain = Vec::new();
+        if let Some(applied) = applied {
+            self.cellstyle_chain(applied, &mut chain);
+        }
+        if let Some(style) = cell_style {
+            self.cellstyle_chain(style.as_str(), &mut chain);
+        }
+        if let Some(style) = self
+            .cellstyles
+            .values()
+            .find(|v| v.styleuse() == StyleUse::Default)
+        {
+            if !chain.iter().any(|v| v.name() == style.name()) {
+                chain.push(style);
+            }
+        }
+
+        let mut effective = CellStyle::new_empty();
+        if let Some(style) = cell_style {
+            effective.set_name(style.as_str());
+        }
+        for style in chain {
+            merge_attr(effective.attrmap_mut(), style.attrmap());
+            merge_attr(effective.cellstyle_mut(), style.cellstyle());
+            merge_attr(effective.paragraphstyle_mut(), style.paragraphstyle());
+            merge_attr(effective.textstyle_mut(), style.textstyle());
+        }
+        effective
+            .attrmap_mut()
+            .clear_attr("style:parent-style-name");
+        effective.attrmap_mut().clear_attr("style:display-name");
+
+        effective
+    }
+
+    // Appends the style and all its parents. Stops at styles that
+    // are already part of the chain.
+    fn cellstyle_chain<'a>(&'a self, name: &str, chain: &mut Vec<&'a CellStyle>) {
+        let mut next = self.cellstyle(name);
+        while let Some(style) = next {
+            if chain.iter().any(|v| v.name() == style.name()) {
+                break;
+            }
+            chain.push(style);
+            next = style
+                .attrmap()
+                .attr("style:parent-style-name")
+                .and_then(|v| self.cellstyle(v));
+        }
+    }
+
     /// Adds a style.
     /// Unnamed styles will be assigned an automatic name.
     pub fn add_paragraphstyle(&mut self, mut style: ParagraphStyle) -> ParagraphStyleRef {
@@ -1143,6 +1381,18 @@ impl WorkBook {
         self.validations.get_mut(name.as_ref())
     }
 
+    /// Checks the values of all cells with a validation against the
+    /// condition of the validation.
+    ///
+    /// Comparisons, text-length, list and range-list conditions and the
+    /// date, time, decimal and whole number checks are evaluated.
+    /// Empty cells are checked against allow_empty(). Conditions that
+    /// need a formula engine like is-true-formula() are reported as
+    /// ViolationKind::Unsupported.
+    pub fn check_validations(&self) -> Vec<Violation> {
+        validation::check_validations(self)
+    }
+
     /// Adds a manifest entry, replaces an existing one with the same name.
     pub fn add_manifest(&mut self, manifest: Manifest) {
         self.manifest.insert(manifest.full_path.clone(), manifest);
@@ -1177,6 +1427,14 @@ impl WorkBook {
     pub fn metadata_mut(&mut self) -> &mut Metadata {
         &mut self.metadata
     }
+
+    /// Problems found while reading.
+    ///
+    /// This is only filled if the workbook was read with
+    /// OdsOptions::collect_issues().
+    pub fn issues(&self) -> &Vec<Issue> {
+        &self.issues
+    }
 }
 
 /// Subset of the Workbook wide configurations.
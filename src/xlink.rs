@@ -16,8 +16,8 @@ pub enum XLinkActuate {
 impl Display for XLinkActuate {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            XLinkActuate::OnLoad => write!(f, "OnLoad"),
-            XLinkActuate::OnRequest => write!(f, "OnRequest"),
+            XLinkActuate::OnLoad => write!(f, "onLoad"),
+            XLinkActuate::OnRequest => write!(f, "onRequest"),
         }
     }
 }
@@ -29,6 +29,8 @@ pub enum XLinkShow {
     New,
     ///
     Replace,
+    /// Embedded content, used for images.
+    Embed,
 }
 
 impl Display for XLinkShow {
@@ -36,6 +38,7 @@ impl Display for XLinkShow {
         match self {
             XLinkShow::New => write!(f, "new"),
             XLinkShow::Replace => write!(f, "replace"),
+            XLinkShow::Embed => write!(f, "embed"),
         }
     }
 }
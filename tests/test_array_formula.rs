@@ -0,0 +1,103 @@
+use spreadsheet_ods::{
+    read_fods_buf, read_ods_buf, write_ods_buf, CellRange, OdsError, Sheet, Value, WorkBook,
+};
+
+#[test]
+fn test_array_formula() -> Result<(), OdsError> {
+    let mut sheet = Sheet::new("one");
+    for r in 0..2 {
+        for c in 0..3 {
+            sheet.set_value(r, c, r * 3 + c);
+        }
+    }
+    sheet.set_value(5, 1, "stale");
+    sheet.set_formula(6, 0, "of:=1+1");
+
+    let range = CellRange::local(4, 0, 6, 1);
+    sheet.set_array_formula(&range, "of:=TRANSPOSE([.A1:.C2])")?;
+    assert_eq!(sheet.formula(4, 0).unwrap(), "of:=TRANSPOSE([.A1:.C2])");
+    assert_eq!(sheet.matrix_row_span(4, 0), 3);
+    assert_eq!(sheet.matrix_col_span(4, 0), 2);
+    assert_eq!(sheet.value(5, 1), &Value::Empty);
+    assert_eq!(sheet.formula(6, 0), None);
+
+    let array = sheet.array_formula_at(5, 1).unwrap();
+    assert_eq!(array.range, range);
+    assert_eq!(array.formula, "of:=TRANSPOSE([.A1:.C2])");
+    assert!(sheet.array_formula_at(7, 0).is_none());
+    assert!(sheet.array_formula_at(0, 0).is_none());
+
+    // cached results
+    sheet.set_value(4, 0, 0);
+    sheet.set_value(4, 1, 3);
+
+    // partial overlap
+    assert!(sheet
+        .set_array_formula(&CellRange::local(5, 1, 7, 1), "of:=[.A1:.A3]")
+        .is_err());
+    // other sheet
+    assert!(sheet
+        .set_array_formula(&CellRange::remote("two", 0, 0, 1, 1), "of:=[.A1:.B2]")
+        .is_err());
+    assert_eq!(sheet.array_formulas().count(), 1);
+
+    let mut wb = WorkBook::new_empty();
+    wb.push_sheet(sheet);
+    let buf = write_ods_buf(&mut wb, Vec::new())?;
+    let mut wb = read_ods_buf(&buf)?;
+    let sheet = wb.sheet_mut(0);
+
+    let array = sheet.array_formula_at(6, 1).unwrap();
+    assert_eq!(array.range, range);
+    assert_eq!(array.formula, "of:=TRANSPOSE([.A1:.C2])");
+    assert_eq!(sheet.value(4, 1), &Value::Number(3.0));
+
+    // replace with a larger one
+    let range2 = CellRange::local(4, 0, 7, 2);
+    sheet.set_array_formula(&range2, "of:=MMULT([.A1:.B4];[.A1:.C2])")?;
+    assert_eq!(sheet.array_formulas().count(), 1);
+    assert_eq!(sheet.array_formula_at(4, 0).unwrap().range, range2);
+    assert_eq!(sheet.value(4, 1), &Value::Empty);
+
+    assert!(sheet.clear_array_formula(7, 2));
+    assert!(!sheet.clear_array_formula(7, 2));
+    assert_eq!(sheet.formula(4, 0), None);
+    assert_eq!(sheet.matrix_row_span(4, 0), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_read_array_formula() -> Result<(), OdsError> {
+    // as written by LibreOffice
+    let fods = r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0"
+    xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0"
+    xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0"
+    office:version="1.3" office:mimetype="application/vnd.oasis.opendocument.spreadsheet">
+<office:body>
+<office:spreadsheet>
+<table:table table:name="Sheet1">
+<table:table-row>
+<table:table-cell office:value-type="float" office:value="1"><text:p>1</text:p></table:table-cell>
+<table:table-cell office:value-type="float" office:value="2"><text:p>2</text:p></table:table-cell>
+<table:table-cell table:number-matrix-columns-spanned="1" table:number-matrix-rows-spanned="2" table:formula="of:=TRANSPOSE([.A1:.B1])" office:value-type="float" office:value="1"><text:p>1</text:p></table:table-cell>
+</table:table-row>
+<table:table-row>
+<table:table-cell table:number-columns-repeated="2"/>
+<table:table-cell office:value-type="float" office:value="2"><text:p>2</text:p></table:table-cell>
+</table:table-row>
+</table:table>
+</office:spreadsheet>
+</office:body>
+</office:document>
+"#;
+    let wb = read_fods_buf(fods.as_bytes())?;
+    let sheet = wb.sheet(0);
+    let array = sheet.array_formula_at(1, 2).unwrap();
+    assert_eq!(array.range, CellRange::local(0, 2, 1, 2));
+    assert_eq!(array.formula, "of:=TRANSPOSE([.A1:.B1])");
+    assert_eq!(sheet.value(1, 2), &Value::Number(2.0));
+
+    Ok(())
+}
@@ -0,0 +1,280 @@
+use spreadsheet_ods::condition::Condition;
+use spreadsheet_ods::text::TextP;
+use spreadsheet_ods::validation::{MessageType, Validation, ValidationError, ViolationKind};
+use spreadsheet_ods::{read_fods_buf, CellRange, CellRef, OdsError, Sheet, WorkBook};
+
+fn validation(
+    wb: &mut WorkBook,
+    name: &str,
+    cond: Condition,
+) -> spreadsheet_ods::validation::ValidationRef {
+    let mut valid = Validation::new();
+    valid.set_name(name);
+    valid.set_condition(cond);
+    wb.add_validation(valid)
+}
+
+#[test]
+fn test_check_validations() -> Result<(), OdsError> {
+    let mut wb = WorkBook::new_empty();
+
+    let mut lists = Sheet::new("lists");
+    lists.set_value(0, 0, "red");
+    lists.set_value(1, 0, "green");
+    lists.set_value(2, 0, 42);
+    wb.push_sheet(lists);
+
+    let whole = validation(
+        &mut wb,
+        "whole",
+        Condition::content_is_whole_number_and(Condition::content_le(10)),
+    );
+    let decimal = validation(
+        &mut wb,
+        "decimal",
+        Condition::content_is_decimal_number_and(Condition::content_gt(0)),
+    );
+    let list = validation(
+        &mut wb,
+        "list",
+        Condition::content_is_in_list(&["a", "b", "1"]),
+    );
+    let range = validation(
+        &mut wb,
+        "range",
+        Condition::content_is_in_cellrange(CellRange::remote("lists", 0, 0, 9, 0).absolute()),
+    );
+    let length = validation(
+        &mut wb,
+        "length",
+        Condition::content_text_length_is_between(2, 4),
+    );
+    let formula = validation(&mut wb, "formula", Condition::is_true_formula("[.A1]>0"));
+
+    let mut valid = Validation::new();
+    valid.set_name("required");
+    valid.set_condition(Condition::content_text_length_gt(0));
+    valid.set_allow_empty(false);
+    let mut err = ValidationError::new();
+    err.set_msg_type(MessageType::Warning);
+    err.set_title(Some("Required".to_string()));
+    err.set_text(Some(TextP::new().text("Please fill in").into_xmltag()));
+    valid.set_err(Some(err));
+    let required = wb.add_validation(valid);
+
+    let mut sheet = Sheet::new("input");
+    let mut set = |row: u32,
+                   col: u32,
+                   value: spreadsheet_ods::Value,
+                   v: &spreadsheet_ods::validation::ValidationRef| {
+        sheet.set_value(row, col, value);
+        sheet.set_validation(row, col, v);
+    };
+    set(0, 0, 5.into(), &whole);
+    set(0, 1, 5.5.into(), &whole);
+    set(0, 2, "5".into(), &whole);
+    set(0, 3, 11.into(), &whole);
+    set(0, 4, ().into(), &whole);
+
+    set(1, 0, 0.1.into(), &decimal);
+    set(1, 1, (-1).into(), &decimal);
+
+    set(2, 0, "A".into(), &list);
+    set(2, 1, 1.into(), &list);
+    set(2, 2, "c".into(), &list);
+
+    set(3, 0, "Green".into(), &range);
+    set(3, 1, 42.into(), &range);
+    set(3, 2, "blue".into(), &range);
+
+    set(4, 0, "abc".into(), &length);
+    set(4, 1, "abcde".into(), &length);
+
+    set(5, 0, 1.into(), &formula);
+
+    set(6, 0, "x".into(), &required);
+    set(6, 1, ().into(), &required);
+    wb.push_sheet(sheet);
+
+    let violations = wb.check_validations();
+    let found: Vec<_> = violations
+        .iter()
+        .map(|v| (v.cell().row(), v.cell().col(), v.kind()))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (0, 1, ViolationKind::Invalid),
+            (0, 2, ViolationKind::Invalid),
+            (0, 3, ViolationKind::Invalid),
+            (1, 1, ViolationKind::Invalid),
+            (2, 2, ViolationKind::Invalid),
+            (3, 2, ViolationKind::Invalid),
+            (4, 1, ViolationKind::Invalid),
+            (5, 0, ViolationKind::Unsupported),
+            (6, 1, ViolationKind::Empty),
+        ]
+    );
+
+    let last = violations.last().unwrap();
+    assert_eq!(last.cell(), &CellRef::remote("input", 6, 1));
+    assert_eq!(last.validation().as_str(), "required");
+    assert_eq!(last.msg_type(), MessageType::Warning);
+    assert_eq!(last.title(), Some("Required"));
+    assert_eq!(last.message(), Some("Please fill in"));
+
+    Ok(())
+}
+
+#[test]
+fn test_check_validations_relative() -> Result<(), OdsError> {
+    // as written by LibreOffice, the list moves with the cell.
+    let fods = r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0"
+    xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0"
+    xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0"
+    office:version="1.3" office:mimetype="application/vnd.oasis.opendocument.spreadsheet">
+<office:body>
+<office:spreadsheet>
+<table:content-validations>
+<table:content-validation table:name="val1" table:condition="of:cell-content-is-in-list([.A1:.B1])" table:allow-empty-cell="true" table:base-cell-address="Sheet1.C1">
+<table:error-message table:title="Oops" table:message-type="warning" table:display="true"><text:p>Not in list</text:p></table:error-message>
+</table:content-validation>
+<table:content-validation table:name="val2" table:condition="of:cell-content-text-length()&lt;3" table:allow-empty-cell="false" table:base-cell-address="Sheet1.D1"/>
+</table:content-validations>
+<table:table table:name="Sheet1">
+<table:table-row>
+<table:table-cell office:value-type="string"><text:p>a</text:p></table:table-cell>
+<table:table-cell office:value-type="string"><text:p>b</text:p></table:table-cell>
+<table:table-cell table:content-validation-name="val1" office:value-type="string"><text:p>b</text:p></table:table-cell>
+</table:table-row>
+<table:table-row>
+<table:table-cell office:value-type="string"><text:p>c</text:p></table:table-cell>
+<table:table-cell office:value-type="string"><text:p>d</text:p></table:table-cell>
+<table:table-cell table:content-validation-name="val1" office:value-type="string"><text:p>b</text:p></table:table-cell>
+</table:table-row>
+</table:table>
+</office:spreadsheet>
+</office:body>
+</office:document>
+"#;
+    let wb = read_fods_buf(fods.as_bytes())?;
+    let violations = wb.check_validations();
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].cell(), &CellRef::remote("Sheet1", 1, 2));
+    assert_eq!(violations[0].kind(), ViolationKind::Invalid);
+    assert_eq!(violations[0].msg_type(), MessageType::Warning);
+    assert_eq!(violations[0].title(), Some("Oops"));
+    assert_eq!(violations[0].message(), Some("Not in list"));
+    assert!(wb.validation("val2").is_some());
+
+    Ok(())
+}
+
+#[test]
+fn test_check_validations_repeat() -> Result<(), OdsError> {
+    let mut wb = WorkBook::new_empty();
+    let whole = validation(
+        &mut wb,
+        "whole",
+        Condition::content_is_whole_number_and(Condition::content_le(10)),
+    );
+
+    let mut sheet = Sheet::new("input");
+    sheet.set_value(1, 1, 11);
+    sheet.set_validation(1, 1, &whole);
+    sheet.set_cell_repeat(1, 1, 2);
+    sheet.set_row_repeat(1, 2);
+    wb.push_sheet(sheet);
+
+    let found: Vec<_> = wb
+        .check_validations()
+        .iter()
+        .map(|v| (v.cell().row(), v.cell().col()))
+        .collect();
+    assert_eq!(found, vec![(1, 1), (1, 2), (2, 1), (2, 2)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_check_validations_reversed() -> Result<(), OdsError> {
+    let mut wb = WorkBook::new_empty();
+
+    let mut lists = Sheet::new("lists");
+    lists.set_value(0, 0, "red");
+    lists.set_value(1, 0, "green");
+    wb.push_sheet(lists);
+
+    // relative start, absolute end: from row 3 on the range is reversed
+    // and covers only lists.A2 downwards.
+    let mut range = CellRange::remote("lists", 0, 0, 1, 0);
+    range.set_to_row_abs(true);
+    range.set_to_col_abs(true);
+    let mut valid = Validation::new();
+    valid.set_name("list");
+    valid.set_condition(Condition::content_is_in_cellrange(range));
+    valid.set_base_cell(CellRef::remote("input", 0, 0));
+    let list = wb.add_validation(valid);
+
+    let mut sheet = Sheet::new("input");
+    sheet.set_value(3, 0, "green");
+    sheet.set_validation(3, 0, &list);
+    sheet.set_value(4, 0, "red");
+    sheet.set_validation(4, 0, &list);
+    wb.push_sheet(sheet);
+
+    let violations = wb.check_validations();
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].cell(), &CellRef::remote("input", 4, 0));
+    assert_eq!(violations[0].kind(), ViolationKind::Invalid);
+
+    Ok(())
+}
+
+#[test]
+fn test_check_validations_repeated_list() -> Result<(), OdsError> {
+    let mut wb = WorkBook::new_empty();
+
+    // as read with use_repeat_for_cells(): A1:C1 is "red", A2:A4 is "green".
+    let mut lists = Sheet::new("lists");
+    lists.set_value(0, 0, "red");
+    lists.set_cell_repeat(0, 0, 3);
+    lists.set_value(1, 0, "green");
+    lists.set_row_repeat(1, 3);
+    wb.push_sheet(lists);
+
+    let cols = validation(
+        &mut wb,
+        "cols",
+        Condition::content_is_in_cellrange(CellRange::remote("lists", 0, 1, 0, 2).absolute()),
+    );
+    let rows = validation(
+        &mut wb,
+        "rows",
+        Condition::content_is_in_cellrange(CellRange::remote("lists", 2, 0, 3, 0).absolute()),
+    );
+
+    let mut sheet = Sheet::new("input");
+    sheet.set_value(0, 0, "red");
+    sheet.set_validation(0, 0, &cols);
+    sheet.set_value(1, 0, "green");
+    sheet.set_validation(1, 0, &cols);
+    sheet.set_value(2, 0, "green");
+    sheet.set_validation(2, 0, &rows);
+    sheet.set_value(3, 0, "red");
+    sheet.set_validation(3, 0, &rows);
+    wb.push_sheet(sheet);
+
+    let found: Vec<_> = wb
+        .check_validations()
+        .iter()
+        .map(|v| (v.cell().row(), v.kind()))
+        .collect();
+    assert_eq!(
+        found,
+        vec![(1, ViolationKind::Invalid), (3, ViolationKind::Invalid)]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,185 @@
+use spreadsheet_ods::condition::{
+    CompareOp, Condition, ConditionExpr, ContentType, Operand, ValueCondition,
+};
+use spreadsheet_ods::style::stylemap::StyleMap;
+use spreadsheet_ods::style::CellStyle;
+use spreadsheet_ods::{read_fods_buf, read_ods_buf, write_ods_buf, CellRange, CellRef, OdsError};
+use spreadsheet_ods::{Sheet, WorkBook};
+
+#[test]
+fn test_parse_print() -> Result<(), OdsError> {
+    for (text, expr) in [
+        (
+            "cell-content()>=5",
+            ConditionExpr::Content(CompareOp::Ge, Operand::Number(5.0)),
+        ),
+        (
+            r#"cell-content()="a""b""#,
+            ConditionExpr::Content(CompareOp::Eq, Operand::Text(r#"a"b"#.into())),
+        ),
+        (
+            "cell-content-is-between(1, 10)",
+            ConditionExpr::ContentBetween(Operand::Number(1.0), Operand::Number(10.0)),
+        ),
+        (
+            "cell-content-is-not-between([.$B$1], SUM([.A1:.A5];1))",
+            ConditionExpr::ContentNotBetween(
+                Operand::Formula("[.$B$1]".into()),
+                Operand::Formula("SUM([.A1:.A5];1)".into()),
+            ),
+        ),
+        (
+            "cell-content-text-length()<3",
+            ConditionExpr::TextLength(CompareOp::Lt, Operand::Number(3.0)),
+        ),
+        (
+            "cell-content-text-length-is-between(2, 4)",
+            ConditionExpr::TextLengthBetween(Operand::Number(2.0), Operand::Number(4.0)),
+        ),
+        (
+            r#"cell-content-is-in-list("a";"b";1;true)"#,
+            ConditionExpr::InList(vec![
+                Operand::Text("a".into()),
+                Operand::Text("b".into()),
+                Operand::Number(1.0),
+                Operand::Bool(true),
+            ]),
+        ),
+        (
+            "cell-content-is-in-list([lists.A1:.A3])",
+            ConditionExpr::InRange(CellRange::remote("lists", 0, 0, 2, 0)),
+        ),
+        (
+            "cell-content-is-time() and cell-content()>0.5",
+            ConditionExpr::And(
+                ContentType::Time,
+                Box::new(ConditionExpr::Content(CompareOp::Gt, Operand::Number(0.5))),
+            ),
+        ),
+        (
+            "is-true-formula(MOD([.A1];2)=0)",
+            ConditionExpr::IsTrueFormula("MOD([.A1];2)=0".into()),
+        ),
+    ] {
+        assert_eq!(ConditionExpr::parse(text)?, expr);
+        assert_eq!(expr.to_string(), text);
+        assert_eq!(Condition::from(&expr).expr()?, expr);
+    }
+
+    // as written by LibreOffice.
+    assert_eq!(
+        ConditionExpr::parse(
+            "of:cell-content-is-whole-number() and cell-content-is-between(1;10)"
+        )?,
+        ConditionExpr::And(
+            ContentType::WholeNumber,
+            Box::new(ConditionExpr::ContentBetween(
+                Operand::Number(1.0),
+                Operand::Number(10.0)
+            ))
+        )
+    );
+    assert_eq!(
+        Condition::content_is_date_and(Condition::content_ne("x")).expr()?,
+        ConditionExpr::And(
+            ContentType::Date,
+            Box::new(ConditionExpr::Content(
+                CompareOp::Ne,
+                Operand::Text("x".into())
+            ))
+        )
+    );
+
+    assert!(ConditionExpr::parse("cell-content()").is_err());
+    assert!(ConditionExpr::parse("cell-content-is-between(1)").is_err());
+    assert!(ConditionExpr::parse("cell-content-is-date() or cell-content()=1").is_err());
+    assert!(ConditionExpr::parse("something()").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_value_condition() -> Result<(), OdsError> {
+    let c = ValueCondition::value_lt(0);
+    assert_eq!(c.compare()?, (CompareOp::Lt, Operand::Number(0.0)));
+    let c = ValueCondition::value_cmp(CompareOp::Ge, Operand::Number(1000.0));
+    assert_eq!(c.to_string(), "value()>=1000");
+    Ok(())
+}
+
+#[test]
+fn test_modify_validation() -> Result<(), OdsError> {
+    let fods = r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0"
+    xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0"
+    xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0"
+    office:version="1.3" office:mimetype="application/vnd.oasis.opendocument.spreadsheet">
+<office:body>
+<office:spreadsheet>
+<table:content-validations>
+<table:content-validation table:name="val1" table:condition="of:cell-content-is-decimal-number() and cell-content()&lt;=100" table:allow-empty-cell="true" table:base-cell-address="Sheet1.A1"/>
+</table:content-validations>
+<table:table table:name="Sheet1">
+<table:table-row>
+<table:table-cell table:content-validation-name="val1" office:value-type="float" office:value="150"><text:p>150</text:p></table:table-cell>
+</table:table-row>
+</table:table>
+</office:spreadsheet>
+</office:body>
+</office:document>
+"#;
+    let mut wb = read_fods_buf(fods.as_bytes())?;
+    assert_eq!(wb.check_validations().len(), 1);
+
+    let valid = wb.validation_mut("val1").expect("validation");
+    let mut expr = valid.condition().expr()?;
+    if let ConditionExpr::And(_, cmp) = &mut expr {
+        if let ConditionExpr::Content(_, limit) = cmp.as_mut() {
+            *limit = Operand::Number(200.0);
+        }
+    }
+    let mut cond = valid.condition().clone();
+    cond.set_expr(&expr);
+    valid.set_condition(cond);
+    assert_eq!(
+        valid.condition().to_string(),
+        "cell-content-is-decimal-number() and cell-content()<=200"
+    );
+    assert!(wb.check_validations().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_stylemap_expr() -> Result<(), OdsError> {
+    let mut wb = WorkBook::new_empty();
+
+    let mut neg = CellStyle::new_empty();
+    neg.set_name("neg");
+    let neg = wb.add_cellstyle(neg);
+
+    let mut cs = CellStyle::new_empty();
+    cs.set_name("cond");
+    cs.push_stylemap(StyleMap::new(
+        ConditionExpr::Content(CompareOp::Lt, Operand::Number(0.0)).into(),
+        neg.into(),
+        Some(CellRef::remote("one", 0, 0)),
+    ));
+    let cs = wb.add_cellstyle(cs);
+
+    let mut sheet = Sheet::new("one");
+    sheet.set_styled_value(0, 0, -5, &cs);
+    wb.push_sheet(sheet);
+
+    let buf = write_ods_buf(&mut wb, Vec::new())?;
+    let wb = read_ods_buf(&buf)?;
+
+    let cs = wb.cellstyle("cond").expect("style");
+    let stylemaps = cs.stylemaps().expect("stylemaps");
+    assert_eq!(
+        stylemaps[0].condition().expr()?,
+        ConditionExpr::Content(CompareOp::Lt, Operand::Number(0.0))
+    );
+
+    Ok(())
+}
@@ -0,0 +1,111 @@
+#![cfg(feature = "use_decimal")]
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use spreadsheet_ods::{
+    read_fods_buf, write_fods_buf, write_ods_buf, OdsError, OdsOptions, Sheet, Value, ValueType,
+    WorkBook,
+};
+
+const FODS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0"
+    xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0"
+    xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0"
+    office:version="1.3" office:mimetype="application/vnd.oasis.opendocument.spreadsheet">
+<office:body>
+<office:spreadsheet>
+<table:table table:name="Sheet1">
+<table:table-row>
+<table:table-cell office:value-type="float" office:value="0.1"><text:p>0.1</text:p></table:table-cell>
+<table:table-cell office:value-type="currency" office:currency="EUR" office:value="0.2"><text:p>0.20 €</text:p></table:table-cell>
+<table:table-cell office:value-type="percentage" office:value="0.075"><text:p>7.5%</text:p></table:table-cell>
+<table:table-cell office:value-type="float" office:value="1E-20"><text:p>1E-20</text:p></table:table-cell>
+</table:table-row>
+</table:table>
+</office:spreadsheet>
+</office:body>
+</office:document>
+"#;
+
+#[test]
+fn test_read_decimal() -> Result<(), OdsError> {
+    let wb = OdsOptions::default()
+        .use_decimal()
+        .read_fods(FODS.as_bytes())?;
+    let sheet = wb.sheet(0);
+    assert_eq!(sheet.value(0, 0), &Value::NumberDecimal(dec!(0.1)));
+    assert_eq!(
+        sheet.value(0, 1),
+        &Value::CurrencyDecimal(dec!(0.2), "EUR".into())
+    );
+    assert_eq!(sheet.value(0, 1).value_type(), ValueType::Currency);
+    assert_eq!(sheet.value(0, 1).currency(), "EUR");
+    assert_eq!(sheet.value(0, 2), &Value::PercentageDecimal(dec!(0.075)));
+    // no exact decimal, falls back to f64.
+    assert_eq!(sheet.value(0, 3), &Value::Number(1E-20));
+
+    let sum: Decimal = (0..3)
+        .filter_map(|c| sheet.value(0, c).as_decimal_opt())
+        .sum();
+    assert_eq!(sum, dec!(0.375));
+    assert_eq!(sheet.value(0, 0).as_f64_opt(), Some(0.1));
+
+    // default is unchanged.
+    let wb = read_fods_buf(FODS.as_bytes())?;
+    assert_eq!(wb.sheet(0).value(0, 0), &Value::Number(0.1));
+
+    Ok(())
+}
+
+#[test]
+fn test_write_decimal() -> Result<(), OdsError> {
+    let mut wb = WorkBook::new_empty();
+    let mut sheet = Sheet::new("one");
+    let mut total = Decimal::ZERO;
+    for r in 0..100 {
+        sheet.set_value(r, 0, Value::new_currency_decimal("EUR", dec!(0.10)));
+        total += dec!(0.10);
+    }
+    sheet.set_value(100, 0, Value::NumberDecimal(dec!(12345678901234.56)));
+    sheet.set_value(101, 0, dec!(1.25));
+    sheet.set_value(102, 0, Value::new_currency_decimal("", dec!(3.50)));
+    wb.push_sheet(sheet);
+    assert_eq!(wb.sheet(0).value(101, 0), &Value::NumberDecimal(dec!(1.25)));
+
+    let buf = write_fods_buf(&mut wb, Vec::new())?;
+    let xml = String::from_utf8(buf.clone()).expect("utf8");
+    assert!(xml.contains(r#"office:currency="EUR" office:value="0.10""#));
+    assert!(xml.contains(r#"office:value="12345678901234.56""#));
+    assert!(!xml.contains(r#"office:currency="""#));
+    assert!(xml.contains(r#"office:value="3.50"><text:p>3.50</text:p>"#));
+
+    let wb = OdsOptions::default()
+        .use_decimal()
+        .read_fods(buf.as_slice())?;
+    let sheet = wb.sheet(0);
+    let sum: Decimal = (0..100)
+        .filter_map(|r| sheet.value(r, 0).as_decimal_opt())
+        .sum();
+    assert_eq!(sum, total);
+    assert_eq!(sum, dec!(10.00));
+    assert_eq!(
+        sheet.value(100, 0),
+        &Value::NumberDecimal(dec!(12345678901234.56))
+    );
+    assert_eq!(
+        sheet.value(102, 0),
+        &Value::CurrencyDecimal(dec!(3.50), "".into())
+    );
+
+    let mut wb = wb;
+    let buf = write_ods_buf(&mut wb, Vec::new())?;
+    let wb = OdsOptions::default()
+        .use_decimal()
+        .read_ods(std::io::Cursor::new(buf))?;
+    assert_eq!(
+        wb.sheet(0).value(0, 0),
+        &Value::CurrencyDecimal(dec!(0.10), "EUR".into())
+    );
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use spreadsheet_ods::text::{TextP, TextS, TextSpan, TextTag};
+use spreadsheet_ods::{read_ods, OdsError, OdsWriteOptions, Sheet, Value, WorkBook};
+use std::io::{Cursor, Read};
+use zip::CompressionMethod;
+
+fn write(book: &mut WorkBook, pretty: bool) -> Result<Vec<u8>, OdsError> {
+    let mut buf = Cursor::new(Vec::new());
+    OdsWriteOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .deterministic(true)
+        .pretty_print(pretty)
+        .write_ods(book, &mut buf)?;
+    Ok(buf.into_inner())
+}
+
+#[test]
+fn test_deterministic() -> Result<(), OdsError> {
+    let mut wb0 = read_ods("tests/test_write_read_1.ods")?;
+    let mut wb1 = read_ods("tests/test_write_read_1.ods")?;
+
+    let buf0 = write(&mut wb0, false)?;
+    let buf1 = write(&mut wb1, false)?;
+    assert!(buf0 == buf1);
+
+    // writing again gives the same result.
+    let buf2 = write(&mut wb0, false)?;
+    assert!(buf0 == buf2);
+
+    let mut zip = zip::ZipArchive::new(Cursor::new(buf0))?;
+    for i in 0..zip.len() {
+        let file = zip.by_index(i)?;
+        let time = file.last_modified().expect("time");
+        assert_eq!(time.year(), 1980);
+        assert_eq!(time.month(), 1);
+        assert_eq!(time.day(), 1);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_pretty_print() -> Result<(), OdsError> {
+    let mut wb = read_ods("tests/test_write_read_1.ods")?;
+
+    let buf = write(&mut wb, false)?;
+    let mut zip = zip::ZipArchive::new(Cursor::new(buf))?;
+    let mut content = String::new();
+    zip.by_name("content.xml")?.read_to_string(&mut content)?;
+    let plain_lines = content.lines().count();
+
+    let buf = write(&mut wb, true)?;
+    let mut zip = zip::ZipArchive::new(Cursor::new(buf))?;
+    let mut content = String::new();
+    zip.by_name("content.xml")?.read_to_string(&mut content)?;
+    let pretty_lines = content.lines().count();
+
+    assert!(pretty_lines > plain_lines + 10);
+
+    let wb2 = spreadsheet_ods::read_ods_buf(&write(&mut wb, true)?)?;
+    assert_eq!(wb2.num_sheets(), wb.num_sheets());
+
+    Ok(())
+}
+
+#[test]
+fn test_pretty_print_mixed() -> Result<(), OdsError> {
+    let mut wb = WorkBook::new_empty();
+    let mut sh = Sheet::new("one");
+    let rich: Vec<TextTag> = vec![TextP::new()
+        .text("Buy the ")
+        .tag(TextSpan::new().text("frobnicator"))
+        .tag(TextS::new())
+        .text("now")
+        .into_xmltag()];
+    sh.set_value(0, 0, Value::TextXml(rich.clone()));
+    wb.push_sheet(sh);
+
+    let buf = write(&mut wb, true)?;
+    let mut zip = zip::ZipArchive::new(Cursor::new(buf.clone()))?;
+    let mut content = String::new();
+    zip.by_name("content.xml")?.read_to_string(&mut content)?;
+    assert!(content
+        .contains("<text:p>Buy the <text:span>frobnicator</text:span><text:s/>now</text:p>\n"));
+
+    let wb2 = spreadsheet_ods::read_ods_buf(&buf)?;
+    assert_eq!(wb2.sheet(0).value(0, 0), &Value::TextXml(rich));
+
+    Ok(())
+}
@@ -1,7 +1,17 @@
 mod lib_test;
 
 use lib_test::*;
-use spreadsheet_ods::{read_ods, OdsError};
+use spreadsheet_ods::color::Rgb;
+use spreadsheet_ods::draw::{
+    DrawFrame, DrawShape, DrawShapeKind, ImageInfo, ImageOptions, PageDrawing,
+};
+use spreadsheet_ods::style::units::{DrawFill, DrawStroke, TextAreaVerticalAlign};
+use spreadsheet_ods::style::GraphicStyle;
+use spreadsheet_ods::text::{TextP, TextSpan};
+use spreadsheet_ods::validate::validate;
+use spreadsheet_ods::{
+    read_ods, read_ods_buf, write_ods_buf, CellRange, CellRef, Length, OdsError, Sheet, WorkBook,
+};
 
 #[test]
 fn test_draw_image() -> Result<(), OdsError> {
@@ -32,3 +42,248 @@ fn test_images() -> Result<(), OdsError> {
 
     Ok(())
 }
+
+fn png(width: u32, height: u32, ppm: u32) -> Vec<u8> {
+    let mut buf = b"\x89PNG\r\n\x1a\n".to_vec();
+    buf.extend(13u32.to_be_bytes());
+    buf.extend(b"IHDR");
+    buf.extend(width.to_be_bytes());
+    buf.extend(height.to_be_bytes());
+    buf.extend([8, 6, 0, 0, 0]);
+    buf.extend([0; 4]);
+    buf.extend(9u32.to_be_bytes());
+    buf.extend(b"pHYs");
+    buf.extend(ppm.to_be_bytes());
+    buf.extend(ppm.to_be_bytes());
+    buf.push(1);
+    buf.extend([0; 4]);
+    buf
+}
+
+#[test]
+fn test_image_info() {
+    let info = ImageInfo::detect(&png(200, 100, 11811)).expect("png");
+    assert_eq!(info.mime_type(), "image/png");
+    assert_eq!((info.width(), info.height()), (200, 100));
+    assert!((info.dpi().0 - 300.0).abs() < 0.1);
+
+    let mut jpeg = b"\xFF\xD8\xFF\xE0\x00\x10JFIF\x00\x01\x01\x01\x00\x48\x00\x48\x00\x00".to_vec();
+    jpeg.extend(b"\xFF\xC0\x00\x11\x08\x00\x32\x00\x64\x03");
+    jpeg.extend([0; 9]);
+    let info = ImageInfo::detect(&jpeg).expect("jpeg");
+    assert_eq!(info.mime_type(), "image/jpeg");
+    assert_eq!((info.width(), info.height()), (100, 50));
+    assert_eq!(info.dpi(), (72.0, 72.0));
+    assert_eq!(info.size_pt(), (100.0, 50.0));
+
+    let gif = b"GIF89a\x10\x00\x20\x00\x00\x00\x00";
+    let info = ImageInfo::detect(gif).expect("gif");
+    assert_eq!((info.width(), info.height()), (16, 32));
+
+    let svg = br#"<?xml version="1.0"?><svg xmlns="http://www.w3.org/2000/svg" width="2in" viewBox="0 0 100 50"></svg>"#;
+    let info = ImageInfo::detect(svg).expect("svg");
+    assert_eq!(info.mime_type(), "image/svg+xml");
+    assert_eq!((info.width(), info.height()), (192, 96));
+
+    let mut webp = b"RIFF\0\0\0\0WEBPVP8X\x0a\0\0\0\0\0\0\0".to_vec();
+    webp.extend([0x3F, 0x01, 0x00, 0xC7, 0x00, 0x00]);
+    let info = ImageInfo::detect(&webp).expect("webp");
+    assert_eq!((info.width(), info.height()), (320, 200));
+
+    // no panic if the 4096 byte limit splits a char.
+    let mut svg = b"<svg width=\"10\" height=\"10\">".to_vec();
+    svg.resize(4095, b' ');
+    svg.extend("äöü".as_bytes());
+    assert!(ImageInfo::detect(&svg).is_some());
+    let svg = br#"<svg width="1e400" height="10"></svg>"#;
+    assert!(ImageInfo::detect(svg).is_none());
+
+    assert!(ImageInfo::detect(b"no image").is_none());
+}
+
+#[test]
+fn test_insert_image() -> Result<(), OdsError> {
+    let mut wb = WorkBook::new_empty();
+    let mut sh = Sheet::new("one");
+    sh.set_col_width(1, Length::Pt(100.0));
+    sh.set_col_width(2, Length::Pt(100.0));
+    sh.set_row_height(1, Length::Pt(20.0));
+    sh.set_row_height(2, Length::Pt(20.0));
+
+    let logo = png(200, 100, 3780);
+    sh.insert_image(
+        CellRef::local(1, 1),
+        &logo,
+        &ImageOptions::new()
+            .fit(CellRange::local(1, 1, 2, 2))
+            .name("logo"),
+    )?;
+    wb.push_sheet(sh);
+
+    let frame = &wb.sheet(0).draw_frames(1, 1).expect("frame")[0];
+    assert_eq!(frame.attrmap().attr("svg:width"), Some("80pt"));
+    assert_eq!(frame.attrmap().attr("svg:height"), Some("40pt"));
+    assert_eq!(
+        frame.attrmap().attr("table:end-cell-address"),
+        Some("one.B3")
+    );
+
+    let buf = write_ods_buf(&mut wb, Vec::new())?;
+    let wb = read_ods_buf(&buf)?;
+
+    let images: Vec<_> = wb.images().collect();
+    assert_eq!(images.len(), 1);
+    let image = &images[0];
+    assert_eq!(image.sheet(), 0);
+    assert_eq!(image.anchor(), &CellRef::remote("one", 1, 1));
+    assert_eq!(image.mime_type(), Some("image/png"));
+    assert!(image.href().expect("href").starts_with("Pictures/"));
+    assert_eq!(image.bytes()?.as_ref(), logo.as_slice());
+    assert!(wb.manifest(image.href().expect("href")).is_some());
+
+    Ok(())
+}
+
+#[test]
+fn test_insert_image_invalid() {
+    let mut sh = Sheet::new("one");
+    let logo = png(200, 100, 3780);
+    for opts in [
+        ImageOptions::new().scale(f64::NAN),
+        ImageOptions::new().scale(-1.0),
+        ImageOptions::new().scale(f64::INFINITY),
+        ImageOptions::new().size(Length::Pt(f64::MAX), Length::Pt(-1.0)),
+    ] {
+        assert!(sh.insert_image(CellRef::local(0, 0), &logo, &opts).is_err());
+    }
+
+    // the end cell is bounded.
+    sh.insert_image(
+        CellRef::local(0, 0),
+        &logo,
+        &ImageOptions::new().size(Length::Pt(1e300), Length::Pt(10.0)),
+    )
+    .expect("image");
+}
+
+#[test]
+fn test_shapes() -> Result<(), OdsError> {
+    let mut wb = WorkBook::new_empty();
+
+    let mut gs = GraphicStyle::new("callout");
+    gs.set_fill(DrawFill::Solid);
+    gs.set_fill_color(Rgb::new(255, 255, 192));
+    gs.set_stroke(DrawStroke::Solid);
+    gs.set_stroke_color(Rgb::new(255, 0, 0));
+    gs.set_stroke_width(Length::Pt(1.5));
+    gs.set_textarea_vertical_align(TextAreaVerticalAlign::Middle);
+    let gs = wb.add_graphicstyle(gs);
+
+    let mut sh = Sheet::new("one");
+    sh.set_value(1, 1, 4711);
+
+    let mut arrow = DrawShape::new_custom_shape(
+        Length::Pt(0.0),
+        Length::Pt(0.0),
+        Length::Pt(40.0),
+        Length::Pt(20.0),
+        "right-arrow",
+    );
+    arrow.set_draw_style_name(gs.clone());
+    arrow.set_draw_id("arrow");
+    sh.add_draw_shape(1, 0, arrow);
+
+    let mut line = DrawShape::new_line(
+        Length::Pt(0.0),
+        Length::Pt(0.0),
+        Length::Pt(10.0),
+        Length::Pt(10.0),
+    );
+    line.set_title("line");
+    sh.add_draw_shape(1, 0, line);
+
+    let mut callout = DrawFrame::new_text_box(
+        Length::Pt(0.0),
+        Length::Pt(0.0),
+        Length::Pt(100.0),
+        Length::Pt(40.0),
+    );
+    callout.set_draw_style_name(gs.clone());
+    callout.text_box_mut().expect("text_box").push_text(
+        TextP::new()
+            .text("key ")
+            .tag(TextSpan::new().text("figure"))
+            .into_xmltag(),
+    );
+    sh.add_draw_frame(1, 2, callout);
+
+    sh.add_page_shape(DrawShape::new_ellipse(
+        Length::Cm(1.0),
+        Length::Cm(1.0),
+        Length::Cm(2.0),
+        Length::Cm(1.0),
+    ));
+    // the frame stays between the shapes.
+    sh.add_page_frame(DrawFrame::new_text_box(
+        Length::Cm(1.0),
+        Length::Cm(3.0),
+        Length::Cm(2.0),
+        Length::Cm(1.0),
+    ));
+    sh.add_page_shape(DrawShape::new_polygon(
+        Length::Cm(4.0),
+        Length::Cm(1.0),
+        Length::Cm(2.0),
+        Length::Cm(2.0),
+        &[(10, 20), (100, 50), (10, 100)],
+    ));
+    wb.push_sheet(sh);
+
+    assert!(validate(&wb).is_empty());
+
+    let buf = write_ods_buf(&mut wb, Vec::new())?;
+    let wb = read_ods_buf(&buf)?;
+
+    let gs = wb.graphicstyle("callout").expect("style");
+    assert_eq!(gs.fill()?, Some(DrawFill::Solid));
+    assert_eq!(gs.stroke_color()?, Some(Rgb::new(255, 0, 0)));
+    assert_eq!(gs.stroke_width()?, Some(Length::Pt(1.5)));
+
+    let sh = wb.sheet(0);
+    let shapes = sh.draw_shapes(1, 0).expect("shapes");
+    assert_eq!(shapes.len(), 2);
+    assert_eq!(shapes[0].kind(), DrawShapeKind::CustomShape);
+    assert_eq!(shapes[0].attrmap().attr("draw:style-name"), Some("callout"));
+    assert_eq!(
+        shapes[0]
+            .enhanced_geometry()
+            .and_then(|v| v.get_attr("draw:type")),
+        Some("right-arrow")
+    );
+    assert_eq!(shapes[1].kind(), DrawShapeKind::Line);
+    assert_eq!(shapes[1].title().map(|v| v.as_str()), Some("line"));
+    assert_eq!(shapes[1].attrmap().attr("svg:x2"), Some("10pt"));
+
+    let frame = &sh.draw_frames(1, 2).expect("frame")[0];
+    let text_box = frame.text_box().expect("text_box");
+    let mut text = String::new();
+    text_box.text()[0].extract_text(&mut text);
+    assert_eq!(text, "key figure");
+
+    let drawings = sh.page_drawings();
+    assert_eq!(drawings.len(), 3);
+    assert!(matches!(&drawings[0], PageDrawing::Shape(v) if v.kind() == DrawShapeKind::Ellipse));
+    assert!(matches!(&drawings[1], PageDrawing::Frame(_)));
+    let PageDrawing::Shape(polygon) = &drawings[2] else {
+        panic!("polygon");
+    };
+    assert_eq!(polygon.kind(), DrawShapeKind::Polygon);
+    assert_eq!(
+        polygon.attrmap().attr("draw:points"),
+        Some("10,20 100,50 10,100")
+    );
+    assert_eq!(polygon.attrmap().attr("svg:viewBox"), Some("10 20 90 80"));
+    assert_eq!(sh.page_shapes().count(), 2);
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use color::Rgb;
+use icu_locid::locale;
+use spreadsheet_ods::condition::Condition;
+use spreadsheet_ods::style::stylemap::StyleMap;
+use spreadsheet_ods::style::units::{Border, TextAlign};
+use spreadsheet_ods::style::{CellStyle, StyleUse};
+use spreadsheet_ods::{pt, CellRef, Length, Sheet, ValueType, WorkBook};
+
+#[test]
+fn test_effective_parent() {
+    let mut wb = WorkBook::new(locale!("en_US"));
+
+    let mut base = CellStyle::new("base", &"".into());
+    base.set_font_bold();
+    base.set_color(Rgb::new(255, 0, 0));
+    let base = wb.add_cellstyle(base);
+
+    let mut child = CellStyle::new_empty();
+    child.set_name("child");
+    child.set_parent_style(&base);
+    child.set_color(Rgb::new(0, 0, 255));
+    let child = wb.add_cellstyle(child);
+
+    let mut sheet = Sheet::new("one");
+    sheet.set_styled_value(0, 0, "text", &child);
+    wb.push_sheet(sheet);
+
+    let eff = wb.effective_cell_style(0, 0, 0);
+    assert_eq!(eff.name(), "child");
+    assert_eq!(eff.textstyle().attr("fo:font-weight"), Some("bold"));
+    assert_eq!(eff.textstyle().attr("fo:color"), Some("#0000ff"));
+    assert_eq!(eff.attrmap().attr("style:parent-style-name"), None);
+}
+
+#[test]
+fn test_effective_row_col_default() {
+    let mut wb = WorkBook::new_empty();
+
+    let mut def = CellStyle::new_empty();
+    def.set_styleuse(StyleUse::Default);
+    def.set_font_size(pt!(10));
+    def.set_text_align(TextAlign::Start);
+    wb.add_cellstyle(def);
+
+    let mut col = CellStyle::new_empty();
+    col.set_name("col");
+    col.set_border(pt!(1), Border::Solid, Rgb::new(0, 0, 0));
+    col.set_text_align(TextAlign::End);
+    let col = wb.add_cellstyle(col);
+
+    let mut row = CellStyle::new_empty();
+    row.set_name("row");
+    row.set_font_size(pt!(14));
+    let row = wb.add_cellstyle(row);
+
+    let mut own = CellStyle::new_empty();
+    own.set_name("own");
+    own.set_text_align(TextAlign::Center);
+    let own = wb.add_cellstyle(own);
+
+    let mut sheet = Sheet::new("one");
+    sheet.set_col_cellstyle(1, &col);
+    sheet.set_col_cellstyle(4, &col);
+    sheet.set_row_cellstyle(2, &row);
+    sheet.set_row_cellstyle(4, &row);
+    sheet.set_value(2, 1, 42);
+    sheet.set_styled_value(4, 4, 42, &own);
+    wb.push_sheet(sheet);
+
+    // the row default wins over the column default.
+    let eff = wb.effective_cell_style(0, 2, 1);
+    assert_eq!(eff.name(), "row");
+    assert_eq!(eff.textstyle().attr("fo:font-size"), Some("14pt"));
+    assert_eq!(eff.paragraphstyle().attr("fo:text-align"), Some("start"));
+    assert_eq!(eff.cellstyle().attr("fo:border"), None);
+
+    let eff = wb.effective_cell_style(0, 3, 1);
+    assert_eq!(eff.name(), "col");
+    assert_eq!(eff.textstyle().attr("fo:font-size"), Some("10pt"));
+    assert_eq!(eff.paragraphstyle().attr("fo:text-align"), Some("end"));
+    assert_eq!(eff.cellstyle().attr("fo:border"), Some("1pt solid #000000"));
+
+    // a cell with its own style doesn't use the defaults.
+    let eff = wb.effective_cell_style(0, 4, 4);
+    assert_eq!(eff.name(), "own");
+    assert_eq!(eff.textstyle().attr("fo:font-size"), Some("10pt"));
+    assert_eq!(eff.cellstyle().attr("fo:border"), None);
+
+    let eff = wb.effective_cell_style(0, 5, 5);
+    assert_eq!(eff.name(), "");
+    assert_eq!(eff.textstyle().attr("fo:font-size"), Some("10pt"));
+    assert_eq!(eff.paragraphstyle().attr("fo:text-align"), Some("start"));
+}
+
+#[test]
+fn test_effective_stylemap() {
+    let mut wb = WorkBook::new(locale!("en_US"));
+
+    let mut neg = CellStyle::new_empty();
+    neg.set_name("neg");
+    neg.set_color(Rgb::new(255, 0, 0));
+    let neg = wb.add_cellstyle(neg);
+
+    let mut cs = CellStyle::new_empty();
+    cs.set_name("cond");
+    cs.set_font_bold();
+    cs.set_color(Rgb::new(0, 0, 0));
+    cs.push_stylemap(StyleMap::new(
+        Condition::content_lt(0),
+        neg.into(),
+        Some(CellRef::remote("one", 0, 0)),
+    ));
+    let cs = wb.add_cellstyle(cs);
+
+    let mut sheet = Sheet::new("one");
+    sheet.set_styled_value(0, 0, -5, &cs);
+    sheet.set_styled_value(1, 0, 5, &cs);
+    wb.push_sheet(sheet);
+
+    let eff = wb.effective_cell_style(0, 0, 0);
+    assert_eq!(eff.textstyle().attr("fo:color"), Some("#ff0000"));
+    assert_eq!(eff.textstyle().attr("fo:font-weight"), Some("bold"));
+    assert!(eff.stylemaps().is_none());
+
+    let eff = wb.effective_cell_style(0, 1, 0);
+    assert_eq!(eff.textstyle().attr("fo:color"), Some("#000000"));
+}
+
+#[test]
+fn test_effective_value_default() {
+    let mut wb = WorkBook::new(locale!("en_US"));
+
+    let mut sheet = Sheet::new("one");
+    sheet.set_value(0, 0, 1.5);
+    wb.push_sheet(sheet);
+
+    let number = wb.def_style(ValueType::Number).cloned();
+    let eff = wb.effective_cell_style(0, 0, 0);
+    assert_eq!(Some(eff.name()), number.as_ref().map(|v| v.as_str()));
+    assert!(eff.value_format().is_some());
+}
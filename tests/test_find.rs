@@ -0,0 +1,138 @@
+use spreadsheet_ods::draw::Annotation;
+use spreadsheet_ods::find::FindQuery;
+use spreadsheet_ods::text::{TextP, TextSpan, TextTag};
+use spreadsheet_ods::xmltree::XmlContent;
+use spreadsheet_ods::{CellRange, CellRef, OdsError, Sheet, Value, WorkBook};
+
+fn sample() -> WorkBook {
+    let mut wb = WorkBook::new_empty();
+
+    let mut sh = Sheet::new("one");
+    sh.set_value(0, 0, "Frobnicator 3000");
+    sh.set_value(0, 1, "frobnicator");
+    sh.set_value(1, 0, 3000);
+    sh.set_value(1, 1, "other");
+    sh.set_formula(1, 1, "of:=CONCAT(\"frobnicator\";[.A2])");
+    sh.set_value(2, 0, "nothing");
+    let mut annotation = Annotation::new("The Frobnicator is great");
+    annotation.set_display(false);
+    sh.set_annotation(2, 0, annotation);
+    wb.push_sheet(sh);
+
+    let mut sh = Sheet::new("two");
+    let rich: Vec<TextTag> = vec![TextP::new()
+        .text("Buy the ")
+        .tag(TextSpan::new().text("frobnicator"))
+        .text(" now")
+        .into_xmltag()];
+    sh.set_value(5, 5, Value::TextXml(rich));
+    wb.push_sheet(sh);
+
+    wb
+}
+
+#[test]
+fn test_find() -> Result<(), OdsError> {
+    let wb = sample();
+
+    let q = FindQuery::new("FROBNICATOR");
+    assert_eq!(
+        wb.find(&q)?,
+        vec![
+            CellRef::remote("one", 0, 0),
+            CellRef::remote("one", 0, 1),
+            CellRef::remote("two", 5, 5),
+        ]
+    );
+    let q = FindQuery::new("FROBNICATOR").match_case(true);
+    assert!(wb.find(&q)?.is_empty());
+    let q = FindQuery::new("frobnicator").whole_cell(true);
+    assert_eq!(wb.find(&q)?, vec![CellRef::remote("one", 0, 1)]);
+    let q = FindQuery::new("3000").whole_cell(true);
+    assert_eq!(wb.find(&q)?, vec![CellRef::remote("one", 1, 0)]);
+
+    let q = FindQuery::new("frobnicator")
+        .in_values(false)
+        .in_formulas(true)
+        .in_annotations(true);
+    assert_eq!(
+        wb.find(&q)?,
+        vec![CellRef::remote("one", 1, 1), CellRef::remote("one", 2, 0)]
+    );
+
+    let q = FindQuery::new("frobnicator").in_range(CellRange::local(0, 1, 10, 10));
+    assert_eq!(
+        wb.find(&q)?,
+        vec![CellRef::remote("one", 0, 1), CellRef::remote("two", 5, 5)]
+    );
+    let q = FindQuery::new("frobnicator").in_range(CellRange::remote("two", 0, 0, 10, 10));
+    assert_eq!(wb.find(&q)?, vec![CellRef::remote("two", 5, 5)]);
+    assert_eq!(wb.sheet(0).find(&q)?, Vec::<CellRef>::new());
+
+    let q = FindQuery::new_regex(r"^Frob\w+ \d+$")?;
+    assert_eq!(wb.find(&q)?, vec![CellRef::remote("one", 0, 0)]);
+    assert!(FindQuery::new_regex("(unclosed").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_replace() -> Result<(), OdsError> {
+    let mut wb = sample();
+
+    let q = FindQuery::new("frobnicator")
+        .in_formulas(true)
+        .in_annotations(true);
+    assert_eq!(wb.replace_all(&q, "Gizmo")?, 5);
+
+    let sh = wb.sheet(0);
+    assert_eq!(sh.value(0, 0), &Value::Text("Gizmo 3000".into()));
+    assert_eq!(sh.value(0, 1), &Value::Text("Gizmo".into()));
+    assert_eq!(
+        sh.formula(1, 1).map(|v| v.as_str()),
+        Some("of:=CONCAT(\"Gizmo\";[.A2])")
+    );
+    let mut text = String::new();
+    sh.annotation(2, 0).expect("annotation").text()[0].extract_text(&mut text);
+    assert_eq!(text, "The Gizmo is great");
+
+    // the span is still there
+    match wb.sheet(1).value(5, 5) {
+        Value::TextXml(tags) => match &tags[0].content()[1] {
+            XmlContent::Tag(span) => {
+                assert_eq!(span.name(), "text:span");
+                assert_eq!(span.content()[0], XmlContent::Text("Gizmo".into()));
+            }
+            _ => panic!("span expected"),
+        },
+        _ => panic!("rich text expected"),
+    }
+
+    // regex with capture groups
+    let q = FindQuery::new_regex(r"(\w+) (\d+)")?;
+    assert_eq!(wb.sheet_mut(0).replace_all(&q, "$2 $1")?, 1);
+    assert_eq!(wb.sheet(0).value(0, 0), &Value::Text("3000 Gizmo".into()));
+    // literal replacement
+    let q = FindQuery::new("Gizmo").match_case(true);
+    assert_eq!(wb.sheet_mut(0).replace_all(&q, "$1")?, 2);
+    assert_eq!(wb.sheet(0).value(0, 1), &Value::Text("$1".into()));
+
+    // numbers are not replaced
+    let q = FindQuery::new("3000");
+    assert_eq!(wb.sheet_mut(0).replace_all(&q, "x")?, 1);
+    assert_eq!(wb.sheet(0).value(1, 0), &Value::Number(3000.0));
+
+    // whole cell in rich text
+    let q = FindQuery::new("buy the gizmo now").whole_cell(true);
+    assert_eq!(wb.replace_all(&q, "Sold out")?, 1);
+    match wb.sheet(1).value(5, 5) {
+        Value::TextXml(tags) => {
+            let mut text = String::new();
+            tags[0].extract_text(&mut text);
+            assert_eq!(text, "Sold out");
+        }
+        _ => panic!("rich text expected"),
+    }
+
+    Ok(())
+}
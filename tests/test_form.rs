@@ -0,0 +1,173 @@
+use spreadsheet_ods::draw::DrawShapeKind;
+use spreadsheet_ods::form::{Form, FormControl, FormControlKind, FormItem, ListLinkageType};
+use spreadsheet_ods::validate::validate;
+use spreadsheet_ods::{
+    read_ods_buf, write_ods_buf, CellRange, CellRef, Length, OdsError, Sheet, WorkBook,
+};
+
+#[test]
+fn test_form_controls() -> Result<(), OdsError> {
+    let mut wb = WorkBook::new_empty();
+
+    let mut sh = Sheet::new("questions");
+    sh.set_value(10, 0, "red");
+    sh.set_value(11, 0, "green");
+    sh.set_value(12, 0, "blue");
+
+    let mut check = FormControl::new_checkbox("agree", "I agree");
+    check.set_linked_cell(CellRef::remote("questions", 0, 1));
+    check.set_checked(true);
+    sh.add_form_control(0, 0, check, Length::Cm(3.0), Length::Cm(0.6))?;
+
+    let mut list = FormControl::new_listbox("color");
+    list.set_linked_cell(CellRef::remote("questions", 1, 1));
+    list.set_source_cell_range(CellRange::remote("questions", 10, 0, 12, 0));
+    list.set_list_linkage_type(ListLinkageType::SelectionIndices);
+    list.set_dropdown(true);
+    sh.add_form_control(1, 0, list, Length::Cm(3.0), Length::Cm(0.6))?;
+
+    let mut combo = FormControl::new_combobox("size");
+    combo.push_item("S");
+    combo.push_item("M");
+    combo.push_item("L");
+
+    // the selected entry stays in place.
+    let mut fruit = FormControl::new_listbox("fruit");
+    fruit.push_item("apple");
+    let mut pear = FormItem::new("pear");
+    pear.set_selected(true);
+    fruit.push_form_item(pear);
+    fruit.push_item("plum");
+    sh.add_form_control(7, 0, fruit, Length::Cm(3.0), Length::Cm(0.6))?;
+    combo.set_linked_cell(CellRef::remote("questions", 2, 1));
+    sh.add_form_control(2, 0, combo, Length::Cm(3.0), Length::Cm(0.6))?;
+
+    let mut spin = FormControl::new_spin_button("count", 0, 10);
+    spin.set_step_size(2);
+    spin.set_linked_cell(CellRef::remote("questions", 3, 1));
+    sh.add_form_control(3, 0, spin, Length::Cm(1.0), Length::Cm(0.6))?;
+
+    sh.add_form_control(
+        4,
+        0,
+        FormControl::new_radio("yes", "answer", "Yes"),
+        Length::Cm(2.0),
+        Length::Cm(0.6),
+    )?;
+    sh.add_form_control(
+        5,
+        0,
+        FormControl::new_label("hint", "Please answer all questions."),
+        Length::Cm(6.0),
+        Length::Cm(0.6),
+    )?;
+    sh.add_form_control(
+        6,
+        0,
+        FormControl::new_button("send", "Send"),
+        Length::Cm(2.0),
+        Length::Cm(0.8),
+    )?;
+    wb.push_sheet(sh);
+
+    assert!(validate(&wb).is_empty());
+
+    let buf = write_ods_buf(&mut wb, Vec::new())?;
+    let wb = read_ods_buf(&buf)?;
+    let sh = wb.sheet(0);
+
+    assert_eq!(sh.forms().len(), 1);
+    let form = &sh.forms()[0];
+    assert_eq!(form.controls().len(), 8);
+
+    let check = form.control("agree").expect("checkbox");
+    assert_eq!(check.kind(), FormControlKind::Checkbox);
+    assert_eq!(check.label(), Some("I agree"));
+    assert!(check.checked());
+    assert_eq!(
+        check.linked_cell()?,
+        Some(CellRef::remote("questions", 0, 1))
+    );
+
+    let list = form.control("color").expect("listbox");
+    assert_eq!(list.kind(), FormControlKind::ListBox);
+    assert_eq!(
+        list.source_cell_range()?,
+        Some(CellRange::remote("questions", 10, 0, 12, 0))
+    );
+    assert_eq!(
+        list.list_linkage_type()?,
+        Some(ListLinkageType::SelectionIndices)
+    );
+    assert!(list.dropdown());
+
+    let combo = form.control("size").expect("combobox");
+    assert_eq!(combo.kind(), FormControlKind::ComboBox);
+    let labels: Vec<_> = combo.items().iter().filter_map(|v| v.label()).collect();
+    assert_eq!(labels, vec!["S", "M", "L"]);
+
+    let fruit = form.control("fruit").expect("listbox");
+    let labels: Vec<_> = fruit.items().iter().filter_map(|v| v.label()).collect();
+    assert_eq!(labels, vec!["apple", "pear", "plum"]);
+    assert!(fruit.items()[1].selected());
+    assert!(!fruit.items()[0].selected());
+
+    let spin = form.control("count").expect("spin button");
+    assert_eq!(spin.kind(), FormControlKind::SpinButton);
+    assert_eq!(spin.min_value()?, Some(0));
+    assert_eq!(spin.max_value()?, Some(10));
+    assert_eq!(spin.step_size()?, Some(2));
+
+    assert_eq!(
+        form.control("yes").map(|v| v.kind()),
+        Some(FormControlKind::Radio)
+    );
+    assert_eq!(form.control("yes").and_then(|v| v.name()), Some("answer"));
+    assert_eq!(
+        form.control("hint").map(|v| v.kind()),
+        Some(FormControlKind::Label)
+    );
+    assert_eq!(
+        form.control("send").map(|v| v.kind()),
+        Some(FormControlKind::Button)
+    );
+
+    let shapes = sh.draw_shapes(1, 0).expect("shapes");
+    assert_eq!(shapes.len(), 1);
+    assert_eq!(shapes[0].kind(), DrawShapeKind::Control);
+    assert_eq!(shapes[0].draw_control(), Some("color"));
+
+    Ok(())
+}
+
+#[test]
+fn test_form_dangling_control() {
+    let mut wb = WorkBook::new_empty();
+    let mut sh = Sheet::new("one");
+    sh.add_form(Form::new("Form"));
+    sh.add_form_control(
+        0,
+        0,
+        FormControl::new_button("ok", "Ok"),
+        Length::Cm(2.0),
+        Length::Cm(0.8),
+    )
+    .expect("control");
+    sh.forms_mut()[0].controls_mut().clear();
+    wb.push_sheet(sh);
+
+    let issues = validate(&wb);
+    assert_eq!(issues.len(), 1);
+    assert!(issues[0].message().contains("form-control"));
+}
+
+#[test]
+fn test_form_control_without_id() {
+    let mut sh = Sheet::new("one");
+    let mut button = FormControl::new_button("ok", "Ok");
+    button.attrmap_mut().clear_attr("form:id");
+    button.attrmap_mut().clear_attr("xml:id");
+    assert!(sh
+        .add_form_control(0, 0, button, Length::Cm(2.0), Length::Cm(0.8))
+        .is_err());
+}
@@ -0,0 +1,87 @@
+use spreadsheet_ods::{
+    read_fods_buf, read_ods_buf, write_fods_buf, write_ods_buf, FormulaError, OdsError, Sheet,
+    Value, ValueType, WorkBook,
+};
+
+#[test]
+fn test_formula_error() {
+    assert_eq!(FormulaError::from("#DIV/0!"), FormulaError::Div0);
+    assert_eq!(FormulaError::from("#N/A"), FormulaError::NA);
+    assert_eq!(FormulaError::from("#NAME?"), FormulaError::Name);
+    assert_eq!(
+        FormulaError::from("Err:502"),
+        FormulaError::Other("Err:502".to_string())
+    );
+    assert_eq!(FormulaError::Ref.to_string(), "#REF!");
+    assert_eq!(FormulaError::Other("Err:502".into()).as_str(), "Err:502");
+
+    let v = Value::from(FormulaError::Value);
+    assert_eq!(v.value_type(), ValueType::Error);
+    assert_eq!(v.as_error_opt(), Some(&FormulaError::Value));
+    assert_eq!(Value::Number(1.0).as_error_opt(), None);
+}
+
+#[test]
+fn test_read_error() -> Result<(), OdsError> {
+    // as written by LibreOffice
+    let fods = r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0"
+    xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0"
+    xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0"
+    xmlns:calcext="urn:org:documentfoundation:names:experimental:calc:xmlns:calcext:1.0"
+    office:version="1.3" office:mimetype="application/vnd.oasis.opendocument.spreadsheet">
+<office:body>
+<office:spreadsheet>
+<table:table table:name="Sheet1">
+<table:table-row>
+<table:table-cell table:formula="of:=1/0" office:value-type="string" office:string-value="" calcext:value-type="error"><text:p>#DIV/0!</text:p></table:table-cell>
+<table:table-cell table:formula="of:=NA()" office:value-type="string" office:string-value="" calcext:value-type="error"><text:p>#N/A</text:p></table:table-cell>
+<table:table-cell office:value-type="string" calcext:value-type="string"><text:p>#N/A</text:p></table:table-cell>
+</table:table-row>
+</table:table>
+</office:spreadsheet>
+</office:body>
+</office:document>
+"#;
+    let wb = read_fods_buf(fods.as_bytes())?;
+    let sheet = wb.sheet(0);
+    assert_eq!(sheet.value(0, 0), &Value::Error(FormulaError::Div0));
+    assert_eq!(sheet.formula(0, 0).unwrap(), "of:=1/0");
+    assert_eq!(sheet.value(0, 1), &Value::Error(FormulaError::NA));
+    assert_eq!(sheet.value(0, 2), &Value::Text("#N/A".to_string()));
+
+    Ok(())
+}
+
+#[test]
+fn test_write_error() -> Result<(), OdsError> {
+    let mut wb = WorkBook::new_empty();
+    let mut sheet = Sheet::new("one");
+    sheet.set_formula(0, 0, "of:=1/0");
+    sheet.set_value(0, 0, FormulaError::Div0);
+    sheet.set_value(0, 1, FormulaError::Other("Err:502".into()));
+    sheet.set_value(0, 2, "#REF!");
+    wb.push_sheet(sheet);
+
+    let buf = write_fods_buf(&mut wb, Vec::new())?;
+    let xml = String::from_utf8(buf.clone()).expect("utf8");
+    assert!(xml.contains(
+        r##"office:value-type="string" office:string-value="#DIV/0!" calcext:value-type="error"><text:p>#DIV/0!</text:p>"##
+    ));
+
+    let wb = read_fods_buf(&buf)?;
+    let sheet = wb.sheet(0);
+    assert_eq!(sheet.value(0, 0), &Value::Error(FormulaError::Div0));
+    assert_eq!(
+        sheet.value(0, 1),
+        &Value::Error(FormulaError::Other("Err:502".into()))
+    );
+    assert_eq!(sheet.value(0, 2), &Value::Text("#REF!".to_string()));
+
+    let mut wb = wb;
+    let buf = write_ods_buf(&mut wb, Vec::new())?;
+    let wb = read_ods_buf(&buf)?;
+    assert_eq!(wb.sheet(0).value(0, 0), &Value::Error(FormulaError::Div0));
+
+    Ok(())
+}
@@ -0,0 +1,213 @@
+#![cfg(feature = "all_locales")]
+
+use chrono::{Datelike, Duration, NaiveDate};
+use icu_locid::locale;
+use spreadsheet_ods::defaultstyles::DefaultFormat;
+use spreadsheet_ods::locale::{parse_value, ValueParser};
+use spreadsheet_ods::{Value, ValueFormatDateTime, ValueFormatNumber};
+
+fn date(y: i32, m: u32, d: u32) -> Value {
+    Value::DateTime(
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap(),
+    )
+}
+
+#[test]
+fn test_parse_numbers() {
+    let de = ValueParser::new(locale!("de_DE"));
+    assert_eq!(de.parse("1.234,56").value, Value::Number(1234.56));
+    assert_eq!(de.parse("-0,5").value, Value::Number(-0.5));
+    assert_eq!(de.parse(",5").value, Value::Number(0.5));
+    assert_eq!(de.parse("(12)").value, Value::Number(-12.0));
+    assert_eq!(de.parse("1,5E3").value, Value::Number(1500.0));
+    assert_eq!(de.parse("12,5 %").value, Value::Percentage(0.125));
+    assert_eq!(de.parse("12,5 %").format, Some(DefaultFormat::percent()));
+    assert_eq!(de.parse("1.23,4").value, Value::Text("1.23,4".into()));
+    assert_eq!(de.parse("1.234").format, None);
+
+    let en = ValueParser::new(locale!("en_US"));
+    assert_eq!(en.parse("1,234.56").value, Value::Number(1234.56));
+    assert_eq!(en.parse("+7").value, Value::Number(7.0));
+    assert_eq!(en.parse("1,5").value, Value::Text("1,5".into()));
+    assert_eq!(en.parse("%50").value, Value::Percentage(0.5));
+
+    let fr = ValueParser::new(locale!("fr_FR"));
+    assert_eq!(fr.parse("1 234,5").value, Value::Number(1234.5));
+    assert_eq!(fr.parse("1\u{A0}234,5").value, Value::Number(1234.5));
+
+    let ch = ValueParser::new(locale!("de_CH"));
+    assert_eq!(ch.parse("1'234.5").value, Value::Number(1234.5));
+}
+
+#[test]
+fn test_parse_currency() {
+    let de = ValueParser::new(locale!("de_DE"));
+    assert_eq!(
+        de.parse("1.234,56 €").value,
+        Value::new_currency("EUR", 1234.56)
+    );
+    assert_eq!(de.parse("-5 €").value, Value::new_currency("EUR", -5.0));
+    assert_eq!(de.parse("$ 3").value, Value::new_currency("USD", 3.0));
+    assert_eq!(de.parse("10 CHF").value, Value::new_currency("CHF", 10.0));
+    assert_eq!(de.parse("GBP 10").value, Value::new_currency("GBP", 10.0));
+    assert_eq!(de.parse("1 €").format, Some(DefaultFormat::currency()));
+
+    let br = ValueParser::new(locale!("pt_BR"));
+    assert_eq!(
+        br.parse("R$ 1.000,00").value,
+        Value::new_currency("BRL", 1000.0)
+    );
+
+    let cn = ValueParser::new(locale!("zh_CN"));
+    assert_eq!(cn.parse("¥12.5").value, Value::new_currency("CNY", 12.5));
+    let jp = ValueParser::new(locale!("ja_JP"));
+    assert_eq!(
+        jp.parse("￥1,200").value,
+        Value::new_currency("JPY", 1200.0)
+    );
+
+    let en = ValueParser::new(locale!("en_GB"));
+    assert_eq!(en.parse("-£5.50").value, Value::new_currency("GBP", -5.5));
+}
+
+#[test]
+fn test_parse_dates() {
+    let de = ValueParser::new(locale!("de_DE"));
+    assert_eq!(de.parse("31.12.2024").value, date(2024, 12, 31));
+    assert_eq!(de.parse("31.12.2024").format, Some(DefaultFormat::date()));
+    assert_eq!(de.parse("1.2.24").value, date(2024, 2, 1));
+    assert_eq!(de.parse("1.2.45").value, date(1945, 2, 1));
+    assert_eq!(de.parse("2024-12-31").value, date(2024, 12, 31));
+    let year = chrono::Local::now().year();
+    assert_eq!(de.parse("24.12.").value, date(year, 12, 24));
+    assert_eq!(de.parse("31.2.2024").value, Value::Text("31.2.2024".into()));
+
+    let us = ValueParser::new(locale!("en_US"));
+    assert_eq!(us.parse("12/31/2024").value, date(2024, 12, 31));
+    let sv = ValueParser::new(locale!("sv_SE"));
+    assert_eq!(sv.parse("2024-12-31").value, date(2024, 12, 31));
+    let nl = ValueParser::new(locale!("nl_NL"));
+    assert_eq!(nl.parse("31-12-2024").value, date(2024, 12, 31));
+
+    assert_eq!(
+        de.parse("31.12.2024 13:45").value,
+        Value::DateTime(
+            NaiveDate::from_ymd_opt(2024, 12, 31)
+                .unwrap()
+                .and_hms_opt(13, 45, 0)
+                .unwrap()
+        )
+    );
+    let v = us.parse("2024-12-31T01:02:03");
+    assert_eq!(
+        v.value,
+        Value::DateTime(
+            NaiveDate::from_ymd_opt(2024, 12, 31)
+                .unwrap()
+                .and_hms_opt(1, 2, 3)
+                .unwrap()
+        )
+    );
+    assert_eq!(v.format, Some(DefaultFormat::datetime()));
+}
+
+#[test]
+fn test_parse_times() {
+    let us = ValueParser::new(locale!("en_US"));
+    let v = us.parse("3:45 PM");
+    assert_eq!(
+        v.value,
+        Value::TimeDuration(Duration::hours(15) + Duration::minutes(45))
+    );
+    assert_eq!(v.format, Some(DefaultFormat::time_of_day()));
+    assert_eq!(
+        us.parse("12:10 am").value,
+        Value::TimeDuration(Duration::minutes(10))
+    );
+    assert_eq!(
+        us.parse("13:45:10.5").value,
+        Value::TimeDuration(
+            Duration::hours(13)
+                + Duration::minutes(45)
+                + Duration::seconds(10)
+                + Duration::milliseconds(500)
+        )
+    );
+    let v = us.parse("36:15");
+    assert_eq!(
+        v.value,
+        Value::TimeDuration(Duration::hours(36) + Duration::minutes(15))
+    );
+    assert_eq!(v.format, Some(DefaultFormat::time_interval()));
+    assert_eq!(
+        us.parse("-1:30").value,
+        Value::TimeDuration(-Duration::minutes(90))
+    );
+    assert_eq!(us.parse("13:45 PM").value, Value::Text("13:45 PM".into()));
+    assert_eq!(us.parse("1:75").value, Value::Text("1:75".into()));
+    assert_eq!(
+        us.parse("9999999999999999:00").value,
+        Value::Text("9999999999999999:00".into())
+    );
+}
+
+#[test]
+fn test_parse_other() {
+    let de = ValueParser::new(locale!("de_DE"));
+    assert_eq!(de.parse("wahr").value, Value::Boolean(true));
+    assert_eq!(de.parse("FALSE").value, Value::Boolean(false));
+    assert_eq!(de.parse("FALSE").format, Some(DefaultFormat::bool()));
+    assert_eq!(de.parse("  ").value, Value::Empty);
+    assert_eq!(de.parse("'123").value, Value::Text("123".into()));
+    assert_eq!(
+        de.parse("hallo welt").value,
+        Value::Text("hallo welt".into())
+    );
+
+    assert_eq!(
+        parse_value("Faux", locale!("fr_FR")).value,
+        Value::Boolean(false)
+    );
+    assert_eq!(
+        parse_value("1.5", locale!("ko_KR")).value,
+        Value::Number(1.5)
+    );
+    assert_eq!(
+        parse_value("1,234.5", locale!("ko_KR")).value,
+        Value::Number(1234.5)
+    );
+    assert_eq!(
+        parse_value("2024-12-31", locale!("ko_KR")).value,
+        date(2024, 12, 31)
+    );
+    assert_eq!(de.parse("1e400").value, Value::Text("1e400".into()));
+}
+
+#[test]
+fn test_parse_target_format() {
+    let mut f = ValueFormatDateTime::new_localized("ymd", locale!("en_US"));
+    f.part_year().long_style().build();
+    f.part_text("/").build();
+    f.part_month().long_style().build();
+    f.part_text("/").build();
+    f.part_day().long_style().build();
+
+    let mut p = ValueParser::new(locale!("de_DE"));
+    p.set_format(&f);
+    let v = p.parse("24/12/31");
+    assert_eq!(v.value, date(2024, 12, 31));
+    assert_eq!(v.format.as_ref().map(|v| v.as_str()), Some("ymd"));
+    // en_US separators from the format
+    assert_eq!(p.parse("1,234.5").value, Value::Number(1234.5));
+    assert_eq!(p.parse("1,234.5").format, None);
+
+    let f = ValueFormatNumber::new_localized("num", locale!("de_DE"));
+    let mut p = ValueParser::new(locale!("en_US"));
+    p.set_format(&f);
+    let v = p.parse("1,5");
+    assert_eq!(v.value, Value::Number(1.5));
+    assert_eq!(v.format.as_ref().map(|v| v.as_str()), Some("num"));
+}
@@ -3,8 +3,14 @@ mod lib_test;
 use chrono::{Duration, NaiveDate, NaiveDateTime};
 use icu_locid::{locale, Locale};
 use lib_test::*;
-use spreadsheet_ods::defaultstyles::DefaultStyle;
-use spreadsheet_ods::{read_ods, CellStyle, OdsError, Sheet, Value, ValueFormatCurrency, WorkBook};
+use spreadsheet_ods::defaultstyles::{DefaultFormat, DefaultStyle};
+use spreadsheet_ods::format::ValueFormatTrait;
+use spreadsheet_ods::locale::{localized_format, register_locale, LocalizedValueFormat};
+use spreadsheet_ods::{
+    read_ods, CellStyle, OdsError, Sheet, Value, ValueFormatBoolean, ValueFormatCurrency,
+    ValueFormatDateTime, ValueFormatNumber, ValueFormatPercentage, ValueFormatTimeDuration,
+    WorkBook,
+};
 
 #[test]
 pub fn test_locale1() -> Result<(), OdsError> {
@@ -154,3 +160,155 @@ pub fn test_locale4() -> Result<(), OdsError> {
 
     Ok(())
 }
+
+#[test]
+#[cfg(feature = "all_locales")]
+pub fn test_locale_builtin() -> Result<(), OdsError> {
+    use spreadsheet_ods::format::FormatPartType;
+
+    for (loc, decimal, grouping) in [
+        (locale!("de_DE"), ',', '.'),
+        (locale!("de_CH"), '.', '\''),
+        (locale!("en_GB"), '.', ','),
+        (locale!("es_ES"), ',', '.'),
+        (locale!("fr_FR"), ',', '\u{A0}'),
+        (locale!("it_IT"), ',', '.'),
+        (locale!("ja_JP"), '.', ','),
+        (locale!("nl_NL"), ',', '.'),
+        (locale!("pl_PL"), ',', '\u{A0}'),
+        (locale!("pt_BR"), ',', '.'),
+        (locale!("sv_SE"), ',', '\u{A0}'),
+        (locale!("zh_CN"), '.', ','),
+    ] {
+        let lf = localized_format(loc.clone()).expect("locale");
+        assert_eq!(lf.locale(), loc);
+        assert_eq!(lf.decimal_separator(), decimal);
+        assert_eq!(lf.grouping_separator(), grouping);
+
+        let wb = WorkBook::new(loc.clone());
+        let v = wb
+            .currency_format(DefaultFormat::currency().as_str())
+            .expect("currency");
+        assert_eq!(v.locale(), Some(loc));
+    }
+
+    // language fallback
+    let lf = localized_format(locale!("fr_CA")).expect("locale");
+    assert_eq!(lf.locale(), locale!("fr_FR"));
+    assert!(localized_format(locale!("ko_KR")).is_none());
+
+    // currency after the number
+    let wb = WorkBook::new(locale!("de_DE"));
+    let v = wb
+        .currency_format(DefaultFormat::currency().as_str())
+        .expect("currency");
+    assert_eq!(
+        v.parts().last().expect("part").part_type(),
+        FormatPartType::CurrencySymbol
+    );
+    // date order
+    let wb = WorkBook::new(locale!("sv_SE"));
+    let v = wb
+        .datetime_format(DefaultFormat::date().as_str())
+        .expect("date");
+    assert_eq!(v.parts()[0].part_type(), FormatPartType::Year);
+
+    Ok(())
+}
+
+struct LocaleRuRu;
+
+impl LocalizedValueFormat for LocaleRuRu {
+    fn locale(&self) -> Locale {
+        locale!("ru_RU")
+    }
+
+    fn decimal_separator(&self) -> char {
+        ','
+    }
+
+    fn grouping_separator(&self) -> char {
+        '\u{A0}'
+    }
+
+    fn boolean_format(&self) -> ValueFormatBoolean {
+        let mut v = ValueFormatBoolean::new_localized(DefaultFormat::bool(), self.locale());
+        v.part_boolean().build();
+        v
+    }
+
+    fn number_format(&self) -> ValueFormatNumber {
+        let mut v = ValueFormatNumber::new_localized(DefaultFormat::number(), self.locale());
+        v.part_number().decimal_places(2).build();
+        v
+    }
+
+    fn percentage_format(&self) -> ValueFormatPercentage {
+        let mut v = ValueFormatPercentage::new_localized(DefaultFormat::percent(), self.locale());
+        v.part_number().decimal_places(2).build();
+        v.part_text("%").build();
+        v
+    }
+
+    fn currency_format(&self) -> ValueFormatCurrency {
+        let mut v = ValueFormatCurrency::new_localized(DefaultFormat::currency(), self.locale());
+        v.part_number().decimal_places(2).grouping().build();
+        v.part_text(" ").build();
+        v.part_currency().locale(self.locale()).symbol("₽").build();
+        v
+    }
+
+    fn date_format(&self) -> ValueFormatDateTime {
+        let mut v = ValueFormatDateTime::new_localized(DefaultFormat::date(), self.locale());
+        v.part_day().long_style().build();
+        v.part_text(".").build();
+        v.part_month().long_style().build();
+        v.part_text(".").build();
+        v.part_year().long_style().build();
+        v
+    }
+
+    fn datetime_format(&self) -> ValueFormatDateTime {
+        let mut v = self.date_format();
+        v.set_name(DefaultFormat::datetime().as_str());
+        v
+    }
+
+    fn time_of_day_format(&self) -> ValueFormatDateTime {
+        let mut v = ValueFormatDateTime::new_localized(DefaultFormat::time_of_day(), self.locale());
+        v.part_hours().long_style().build();
+        v.part_text(":").build();
+        v.part_minutes().long_style().build();
+        v
+    }
+
+    fn time_interval_format(&self) -> ValueFormatTimeDuration {
+        let mut v =
+            ValueFormatTimeDuration::new_localized(DefaultFormat::time_interval(), self.locale());
+        v.part_hours().build();
+        v.part_text(":").build();
+        v.part_minutes().long_style().build();
+        v
+    }
+}
+
+static LOCALE_RU_RU: LocaleRuRu = LocaleRuRu;
+
+#[test]
+pub fn test_register_locale() -> Result<(), OdsError> {
+    register_locale(&LOCALE_RU_RU);
+
+    let wb = WorkBook::new(locale!("ru_RU"));
+    let v = wb
+        .currency_format(DefaultFormat::currency().as_str())
+        .expect("currency");
+    assert_eq!(v.locale(), Some(locale!("ru_RU")));
+    assert_eq!(
+        localized_format(locale!("ru"))
+            .expect("fallback")
+            .decimal_separator(),
+        ','
+    );
+
+    Ok(())
+}
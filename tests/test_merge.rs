@@ -0,0 +1,112 @@
+use spreadsheet_ods::cell::MergeMode;
+use spreadsheet_ods::{read_ods_buf, write_ods_buf, CellRange, OdsError, Sheet, Value, WorkBook};
+
+#[test]
+fn test_merge_keep_first() -> Result<(), OdsError> {
+    let mut sheet = Sheet::new("one");
+    sheet.set_value(0, 0, "Title");
+    sheet.set_value(0, 1, "lost");
+    sheet.set_value(1, 2, 42);
+
+    sheet.merge(&CellRange::local(0, 0, 1, 2), MergeMode::KeepFirst)?;
+    assert_eq!(sheet.row_span(0, 0), 2);
+    assert_eq!(sheet.col_span(0, 0), 3);
+    assert_eq!(sheet.value(0, 0), &Value::from("Title"));
+    assert_eq!(sheet.value(0, 1), &Value::Empty);
+    assert_eq!(sheet.value(1, 2), &Value::Empty);
+
+    assert_eq!(
+        sheet.merged_range_at(1, 1),
+        Some(CellRange::local(0, 0, 1, 2))
+    );
+    assert_eq!(sheet.merged_range_at(2, 0), None);
+
+    Ok(())
+}
+
+#[test]
+fn test_merge_concatenate() -> Result<(), OdsError> {
+    let mut sheet = Sheet::new("one");
+    sheet.set_value(0, 0, "Group");
+    sheet.set_value(0, 1, 1);
+    sheet.set_value(0, 2, true);
+    sheet.merge(&CellRange::local(0, 0, 0, 2), MergeMode::Concatenate)?;
+    assert_eq!(sheet.value(0, 0), &Value::from("Group 1 TRUE"));
+    assert_eq!(sheet.value(0, 1), &Value::Empty);
+    assert_eq!(sheet.value(0, 2), &Value::Empty);
+
+    // a single value is moved as it is.
+    sheet.set_value(1, 1, 7);
+    sheet.merge(&CellRange::local(1, 0, 1, 1), MergeMode::Concatenate)?;
+    assert_eq!(sheet.value(1, 0), &Value::Number(7.0));
+    assert_eq!(sheet.value(1, 1), &Value::Empty);
+
+    Ok(())
+}
+
+#[test]
+fn test_merge_keep_covered() -> Result<(), OdsError> {
+    let mut wb = WorkBook::new_empty();
+    let mut sheet = Sheet::new("one");
+    sheet.set_value(0, 0, "a");
+    sheet.set_value(0, 1, "b");
+    sheet.set_value(1, 0, "c");
+    sheet.merge(&CellRange::local(0, 0, 1, 1), MergeMode::KeepCovered)?;
+    sheet.merge(&CellRange::local(3, 0, 3, 3), MergeMode::KeepFirst)?;
+    wb.push_sheet(sheet);
+
+    let buf = write_ods_buf(&mut wb, Vec::new())?;
+    let wb = read_ods_buf(&buf)?;
+    let sheet = wb.sheet(0);
+
+    assert_eq!(sheet.value(0, 1), &Value::from("b"));
+    assert_eq!(sheet.value(1, 0), &Value::from("c"));
+    assert_eq!(
+        sheet.merged_ranges().collect::<Vec<_>>(),
+        vec![CellRange::local(0, 0, 1, 1), CellRange::local(3, 0, 3, 3)]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_merge_overlap() -> Result<(), OdsError> {
+    let mut sheet = Sheet::new("one");
+    sheet.merge(&CellRange::local(1, 1, 2, 2), MergeMode::KeepFirst)?;
+
+    assert!(sheet
+        .merge(&CellRange::local(2, 2, 3, 3), MergeMode::KeepFirst)
+        .is_err());
+    assert!(sheet
+        .merge(&CellRange::local(0, 0, 1, 1), MergeMode::KeepFirst)
+        .is_err());
+    assert!(sheet
+        .merge(&CellRange::remote("two", 0, 0, 1, 1), MergeMode::KeepFirst)
+        .is_err());
+    assert_eq!(sheet.merged_ranges().count(), 1);
+
+    // a merge within the new range is replaced.
+    sheet.merge(&CellRange::local(0, 0, 3, 3), MergeMode::KeepFirst)?;
+    assert_eq!(
+        sheet.merged_ranges().collect::<Vec<_>>(),
+        vec![CellRange::local(0, 0, 3, 3)]
+    );
+    assert_eq!(sheet.col_span(1, 1), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_unmerge() -> Result<(), OdsError> {
+    let mut sheet = Sheet::new("one");
+    sheet.set_value(0, 1, "covered");
+    sheet.merge(&CellRange::local(0, 0, 0, 2), MergeMode::KeepCovered)?;
+
+    assert!(sheet.unmerge(0, 2));
+    assert!(!sheet.unmerge(0, 2));
+    assert_eq!(sheet.col_span(0, 0), 1);
+    assert_eq!(sheet.merged_ranges().count(), 0);
+    assert_eq!(sheet.value(0, 1), &Value::from("covered"));
+
+    Ok(())
+}
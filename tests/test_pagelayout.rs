@@ -2,10 +2,16 @@ mod lib_test;
 
 use color::Rgb;
 use lib_test::*;
-use spreadsheet_ods::style::units::Length;
-use spreadsheet_ods::style::{MasterPage, PageStyle, TableStyle};
-use spreadsheet_ods::xmltree::XmlVec;
-use spreadsheet_ods::{cm, read_ods, OdsError, Sheet, WorkBook};
+use spreadsheet_ods::style::units::{Length, PageBreak, PrintCentering, PrintOrder};
+use spreadsheet_ods::style::{
+    HeaderFooter, MasterPage, PageStyle, PrintScale, RowStyle, StyleOrigin, TableStyle, TextStyle,
+};
+use spreadsheet_ods::text::{TextP, TextS};
+use spreadsheet_ods::xmltree::{XmlContent, XmlVec};
+use spreadsheet_ods::{
+    cm, read_ods, read_ods_buf, read_xlsx_buf, write_ods_buf, write_xlsx_buf, OdsError, Sheet,
+    WorkBook,
+};
 
 #[test]
 fn test_pagelayout() -> Result<(), OdsError> {
@@ -50,3 +56,200 @@ fn test_crpagelayout() -> Result<(), OdsError> {
 
     Ok(())
 }
+
+#[test]
+fn test_page_breaks() -> Result<(), OdsError> {
+    let mut wb = WorkBook::new_empty();
+
+    let rs = wb.add_rowstyle(RowStyle::new_empty());
+
+    let mut sh = Sheet::new("invoices");
+    for r in 0..30 {
+        sh.set_value(r, 0, r);
+        sh.set_value(r, 3, r);
+    }
+    sh.set_rowstyle(10, &rs);
+    sh.set_rowstyle(11, &rs);
+    sh.set_row_height(10, cm!(1));
+    sh.insert_page_break_before_row(10);
+    sh.insert_page_break_before_row(20);
+    sh.insert_page_break_before_col(2);
+    wb.push_sheet(sh);
+
+    assert_eq!(
+        wb.sheet(0).row_page_breaks().collect::<Vec<_>>(),
+        vec![10, 20]
+    );
+    assert_eq!(wb.sheet(0).col_page_breaks().collect::<Vec<_>>(), vec![2]);
+
+    let buf = write_ods_buf(&mut wb, Vec::new())?;
+    let mut wb2 = read_ods_buf(&buf)?;
+    let sh = wb2.sheet(0);
+    assert_eq!(sh.row_page_breaks().collect::<Vec<_>>(), vec![10, 20]);
+    assert_eq!(sh.col_page_breaks().collect::<Vec<_>>(), vec![2]);
+
+    // the original row style is kept without break, the variant keeps the height.
+    assert_eq!(wb2.rowstyle(&rs).expect("rs").break_before()?, None);
+    let rs10 = sh.rowstyle(10).expect("style");
+    assert_ne!(rs10, &rs);
+    assert_eq!(
+        wb2.rowstyle(rs10).expect("rs10").break_before()?,
+        Some(PageBreak::Page)
+    );
+    assert_eq!(sh.rowstyle(11), Some(&rs));
+    assert_eq!(sh.row_height(10), cm!(1));
+
+    let sh = wb2.sheet_mut(0);
+    sh.remove_page_break_before_row(20);
+    sh.remove_page_break_before_col(2);
+    assert_eq!(sh.row_page_breaks().collect::<Vec<_>>(), vec![10]);
+    sh.clear_page_breaks();
+    assert_eq!(sh.row_page_breaks().count(), 0);
+
+    let buf = write_ods_buf(&mut wb2, Vec::new())?;
+    let wb3 = read_ods_buf(&buf)?;
+    assert_eq!(wb3.sheet(0).row_page_breaks().count(), 0);
+    assert_eq!(wb3.sheet(0).col_page_breaks().count(), 0);
+
+    // xlsx
+    let buf = write_xlsx_buf(&wb, Vec::new())?;
+    let wb4 = read_xlsx_buf(&buf)?;
+    assert_eq!(
+        wb4.sheet(0).row_page_breaks().collect::<Vec<_>>(),
+        vec![10, 20]
+    );
+    assert_eq!(wb4.sheet(0).col_page_breaks().collect::<Vec<_>>(), vec![2]);
+
+    Ok(())
+}
+
+#[test]
+fn test_print_scale() -> Result<(), OdsError> {
+    let mut ps = PageStyle::new("ps1");
+    assert_eq!(ps.print_scale()?, None);
+
+    ps.set_print_scale(PrintScale::Percent(80.0));
+    assert_eq!(ps.print_scale()?, Some(PrintScale::Percent(80.0)));
+
+    ps.set_print_scale(PrintScale::Pages(2));
+    assert_eq!(ps.print_scale()?, Some(PrintScale::Pages(2)));
+    assert_eq!(ps.scale_to()?, None);
+
+    ps.set_print_scale(PrintScale::FitTo {
+        width: 1,
+        height: 0,
+    });
+    ps.set_print_page_order(PrintOrder::Ttb);
+    ps.set_table_centering(PrintCentering::Horizontal);
+
+    let mut wb = WorkBook::new_empty();
+    let ps = wb.add_pagestyle(ps);
+    wb.push_sheet(Sheet::new("1"));
+
+    let buf = write_ods_buf(&mut wb, Vec::new())?;
+    let wb = read_ods_buf(&buf)?;
+    let ps = wb.pagestyle(&ps).expect("pagestyle");
+    assert_eq!(
+        ps.print_scale()?,
+        Some(PrintScale::FitTo {
+            width: 1,
+            height: 0
+        })
+    );
+    assert_eq!(ps.scale_to_pages()?, None);
+    assert_eq!(ps.print_page_order()?, Some(PrintOrder::Ttb));
+    assert_eq!(ps.table_centering()?, Some(PrintCentering::Horizontal));
+
+    Ok(())
+}
+
+#[test]
+fn test_headerfooter_template() -> Result<(), OdsError> {
+    let mut wb = WorkBook::new_empty();
+
+    let mut bold = TextStyle::new("bold");
+    bold.set_origin(StyleOrigin::Styles);
+    bold.set_font_bold();
+    let bold = wb.add_textstyle(bold);
+
+    let ps = wb.add_pagestyle(PageStyle::new("ps1"));
+    let mut mp = MasterPage::new("mp1");
+    mp.set_pagestyle(&ps);
+    mp.header_mut()
+        .set_template("&[Sheet] - Page &[Page] of &[Pages] | &{bold}Confidential&{} | &[Date]")?;
+    mp.footer_mut()
+        .set_template("&[File]  &&  &[Path]\n&|&[Time]&[Title]")?;
+    let mp = wb.add_masterpage(mp);
+
+    let header = wb.masterpage(&mp).expect("mp").header();
+    assert_eq!(header.left().len(), 1);
+    assert_eq!(header.center().len(), 1);
+    assert_eq!(header.right().len(), 1);
+    assert!(header.content().is_empty());
+    match &header.center()[0].content()[0] {
+        XmlContent::Tag(span) => {
+            assert_eq!(span.name(), "text:span");
+            assert_eq!(span.get_attr("text:style-name"), Some(bold.as_str()));
+        }
+        _ => panic!("span expected"),
+    }
+    let footer = wb.masterpage(&mp).expect("mp").footer();
+    assert_eq!(footer.content().len(), 2);
+
+    let mut ts = TableStyle::new("ts1");
+    ts.set_master_page(&mp);
+    let ts = wb.add_tablestyle(ts);
+    let mut sh = Sheet::new("1");
+    sh.set_style(&ts);
+    wb.push_sheet(sh);
+
+    let buf = write_ods_buf(&mut wb, Vec::new())?;
+    let wb = read_ods_buf(&buf)?;
+    let mp = wb.masterpage(&mp).expect("mp");
+    assert_eq!(
+        mp.header().template(),
+        "&[Sheet] - Page &[Page] of &[Pages]|&{bold}Confidential&{}|&[Date]"
+    );
+    assert_eq!(
+        mp.footer().template(),
+        "&[File]  &&  &[Path]\n&|&[Time]&[Title]"
+    );
+
+    // existing headers
+    let wb = read_ods("tests/test_pagelayout.ods")?;
+    let mp = wb.masterpage("Default").expect("masterpage");
+    assert_eq!(mp.header().template(), "&[Sheet]");
+    assert_eq!(
+        mp.footer().template(),
+        "left\nleft|Seite &[Page]\nbla\ngong|right\nright"
+    );
+
+    let mut hf = HeaderFooter::new();
+    hf.set_template("left | right")?;
+    assert_eq!(hf.template(), "left||right");
+    hf.set_template("")?;
+    assert!(hf.is_empty());
+    assert!(hf.set_template("&[Unknown]").is_err());
+    assert!(hf.set_template("&[Page").is_err());
+    assert!(hf.set_template("a|b|c|d").is_err());
+    assert!(hf.set_template("&x").is_err());
+
+    // escaped spaces at the start and end of a region are kept.
+    hf.set_template("& & indented& |&{bold}& right&{}")?;
+    assert_eq!(hf.template(), "& & indented& ||&{bold}& right&{}");
+    let template = hf.template();
+    hf.set_template(&template)?;
+    assert_eq!(hf.template(), template);
+
+    let mut hf = HeaderFooter::new();
+    hf.add_content(
+        TextP::new()
+            .text("a")
+            .tag(TextS::new().count(u32::MAX))
+            .text("b")
+            .into_xmltag(),
+    );
+    assert_eq!(hf.template().len(), 1026);
+
+    Ok(())
+}
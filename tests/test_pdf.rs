@@ -0,0 +1,174 @@
+use flate2::read::ZlibDecoder;
+use spreadsheet_ods::style::units::{Border, Length, PrintOrientation};
+use spreadsheet_ods::style::{CellStyle, MasterPage, PageStyle, PrintScale, TableStyle};
+use spreadsheet_ods::{cm, pt, write_pdf_buf, CellRange, OdsError, Sheet, WorkBook};
+use std::io::Read;
+
+/// Number of pages and the uncompressed content streams.
+fn pdf_pages(buf: &[u8]) -> (usize, Vec<String>) {
+    assert!(buf.starts_with(b"%PDF-1.4"));
+    assert!(buf.ends_with(b"%%EOF\n"));
+
+    let text = String::from_utf8_lossy(buf);
+    let pages = text.matches("/Type /Page ").count();
+
+    let mut contents = Vec::new();
+    let mut pos = 0;
+    while let Some(start) = find(&buf[pos..], b"stream\n") {
+        let start = pos + start + 7;
+        let end = start + find(&buf[start..], b"\nendstream").expect("endstream");
+        let mut content = String::new();
+        ZlibDecoder::new(&buf[start..end])
+            .read_to_string(&mut content)
+            .expect("inflate");
+        contents.push(content);
+        pos = end + 10;
+    }
+    (pages, contents)
+}
+
+fn find(buf: &[u8], pat: &[u8]) -> Option<usize> {
+    buf.windows(pat.len()).position(|v| v == pat)
+}
+
+#[test]
+fn test_pdf_simple() -> Result<(), OdsError> {
+    let mut wb = WorkBook::new_empty();
+
+    let mut cs = CellStyle::new_empty();
+    cs.set_font_bold();
+    cs.set_border(pt!(1), Border::Solid, color::Rgb::new(0, 0, 0));
+    cs.set_background_color(color::Rgb::new(255, 255, 0));
+    let cs = wb.add_cellstyle(cs);
+
+    let mut sh = Sheet::new("one");
+    sh.set_styled_value(0, 0, "Hello (World)", &cs);
+    sh.set_value(1, 0, 42);
+    sh.set_value(2, 0, "Grüße");
+    wb.push_sheet(sh);
+
+    let mut sh = Sheet::new("empty");
+    sh.set_print(true);
+    wb.push_sheet(sh);
+
+    let buf = write_pdf_buf(&wb, Vec::new())?;
+    let (pages, contents) = pdf_pages(&buf);
+    assert_eq!(pages, 1);
+    assert_eq!(contents.len(), 1);
+    assert!(contents[0].contains("(Hello \\(World\\)) Tj"));
+    assert!(contents[0].contains("(42) Tj"));
+    assert!(contents[0].contains("(Gr\\374\\337e) Tj"));
+    assert!(contents[0].contains("1 1 0 rg"));
+    assert!(String::from_utf8_lossy(&buf).contains("/BaseFont /Helvetica-Bold"));
+
+    // no pages at all.
+    let wb = WorkBook::new_empty();
+    let buf = write_pdf_buf(&wb, Vec::new())?;
+    let (pages, _) = pdf_pages(&buf);
+    assert_eq!(pages, 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_pdf_pagination() -> Result<(), OdsError> {
+    let mut wb = WorkBook::new_empty();
+
+    let mut sh = Sheet::new("rows");
+    sh.set_value(0, 0, "Title");
+    for r in 1..200 {
+        sh.set_value(r, 0, format!("row{}", r));
+    }
+    sh.set_header_rows(0, 0);
+    wb.push_sheet(sh);
+
+    let mut sh = Sheet::new("breaks");
+    for r in 0..10 {
+        sh.set_value(r, 0, r);
+    }
+    sh.insert_page_break_before_row(5);
+    wb.push_sheet(sh);
+
+    let buf = write_pdf_buf(&wb, Vec::new())?;
+    let (pages, contents) = pdf_pages(&buf);
+    // 200 rows of 12.8pt on A4 with 2cm margins need 5 pages.
+    assert_eq!(pages, 7);
+    for content in &contents[0..5] {
+        assert!(content.contains("(Title) Tj"));
+    }
+    assert!(contents[0].contains("(row1) Tj"));
+    assert!(contents[4].contains("(row199) Tj"));
+    assert!(contents[5].contains("(4) Tj"));
+    assert!(!contents[5].contains("(5) Tj"));
+    assert!(contents[6].contains("(5) Tj"));
+
+    Ok(())
+}
+
+#[test]
+fn test_pdf_pagestyle() -> Result<(), OdsError> {
+    let mut wb = WorkBook::new_empty();
+
+    let mut ps = PageStyle::new("ps1");
+    ps.set_print_orientation(PrintOrientation::Landscape);
+    ps.set_page_width(cm!(29.7));
+    ps.set_page_height(cm!(21.0));
+    ps.set_print_scale(PrintScale::Pages(1));
+    let ps = wb.add_pagestyle(ps);
+
+    let mut mp = MasterPage::new("mp1");
+    mp.set_pagestyle(&ps);
+    mp.header_mut()
+        .set_template("&[Sheet]||Page &[Page] of &[Pages]")?;
+    let mp = wb.add_masterpage(mp);
+
+    let mut ts = TableStyle::new("ts1");
+    ts.set_master_page(&mp);
+    let ts = wb.add_tablestyle(ts);
+
+    let mut sh = Sheet::new("Scaled");
+    sh.set_style(&ts);
+    for r in 0..100 {
+        sh.set_value(r, 0, r);
+    }
+    wb.push_sheet(sh);
+
+    let buf = write_pdf_buf(&wb, Vec::new())?;
+    let (pages, contents) = pdf_pages(&buf);
+    assert_eq!(pages, 1);
+    assert!(String::from_utf8_lossy(&buf).contains("/MediaBox [0 0 841.89 595.28]"));
+    assert!(contents[0].contains("(Scaled) Tj"));
+    assert!(contents[0].contains("(Page 1 of 1) Tj"));
+    assert!(contents[0].contains("(99) Tj"));
+
+    Ok(())
+}
+
+#[test]
+fn test_pdf_print_range() -> Result<(), OdsError> {
+    let mut wb = WorkBook::new_empty();
+
+    let mut sh = Sheet::new("ranges");
+    for r in 0..10 {
+        for c in 0..10 {
+            sh.set_value(r, c, format!("{}/{}", r, c));
+        }
+    }
+    sh.add_print_range(CellRange::local(2, 2, 3, 3));
+    sh.set_value(5, 5, "merged");
+    sh.set_col_span(5, 5, 2);
+    sh.add_print_range(CellRange::local(5, 5, 5, 6));
+    wb.push_sheet(sh);
+
+    let buf = write_pdf_buf(&wb, Vec::new())?;
+    let (pages, contents) = pdf_pages(&buf);
+    assert_eq!(pages, 2);
+    assert!(contents[0].contains("(2/2) Tj"));
+    assert!(contents[0].contains("(3/3) Tj"));
+    assert!(!contents[0].contains("(1/1) Tj"));
+    assert!(!contents[0].contains("(4/4) Tj"));
+    assert!(contents[1].contains("(merged) Tj"));
+    assert!(!contents[1].contains("(5/6) Tj"));
+
+    Ok(())
+}
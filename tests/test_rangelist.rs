@@ -0,0 +1,145 @@
+use spreadsheet_ods::refs::CellOrder;
+use spreadsheet_ods::{CellRange, CellRangeList, CellRef, OdsError};
+
+fn a1(s: &str) -> CellRangeList {
+    CellRangeList::parse_a1(s).expect("a1")
+}
+
+#[test]
+fn test_parse_format() -> Result<(), OdsError> {
+    let l = CellRangeList::parse("Sheet1.A1:.C3 'my sheet'.$E$1:.$E$5")?;
+    assert_eq!(l.len(), 2);
+    assert_eq!(l.ranges()[0], CellRange::remote("Sheet1", 0, 0, 2, 2));
+    assert_eq!(l.to_string(), "Sheet1.A1:.C3 $'my sheet'.$E$1:.$E$5");
+    assert_eq!(l.to_a1(), "Sheet1!A1:C3,'my sheet'!$E$1:$E$5");
+
+    let l = CellRangeList::parse_a1("A1:B2, Sheet2!C3;'it''s'!$D$4")?;
+    assert_eq!(
+        l.ranges(),
+        &[
+            CellRange::local(0, 0, 1, 1),
+            CellRange::remote("Sheet2", 2, 2, 2, 2),
+            CellRange::remote("it's", 3, 3, 3, 3).absolute(),
+        ]
+    );
+    assert_eq!(l.to_a1(), "A1:B2,Sheet2!C3,'it''s'!$D$4");
+
+    assert!(CellRangeList::parse("").expect("empty").is_empty());
+    assert!(CellRangeList::parse(".A1:.B2 x").is_err());
+    assert!(CellRangeList::parse_a1("A1:B2,").is_err());
+    assert!(CellRangeList::parse_a1("A0").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_union() {
+    // overlapping and adjacent ranges are merged.
+    let l = a1("A1:B2,B2:C3,D1:D3").union(&a1("A3"));
+    assert_eq!(l.to_a1(), "A1:B1,D1,A2:D3");
+    assert_eq!(l.normalize(), l);
+
+    let l = a1("A1:A5").union(&a1("B1:B5"));
+    assert_eq!(l.to_a1(), "A1:B5");
+
+    // other tables are kept apart.
+    let l = a1("A1:B2").union(&a1("Sheet2!A1:B2"));
+    assert_eq!(l.to_a1(), "A1:B2,Sheet2!A1:B2");
+}
+
+#[test]
+fn test_intersect_subtract() {
+    let l = a1("A1:C3,E1:E5").intersect(&a1("B2:E2"));
+    assert_eq!(l.to_a1(), "B2:C2,E2");
+
+    let l = a1("A1:C3").intersect(&a1("Sheet2!A1:C3"));
+    assert!(l.is_empty());
+
+    let l = a1("A1:C3").subtract(&a1("B2"));
+    assert_eq!(l.to_a1(), "A1:C1,A2,C2,A3:C3");
+
+    let l = a1("A1:C3").subtract(&a1("A1:A3,C1:C3"));
+    assert_eq!(l.to_a1(), "B1:B3");
+
+    let l = a1("A1:C3").subtract(&a1("A1:D4"));
+    assert!(l.is_empty());
+}
+
+#[test]
+fn test_expand_shrink_offset() -> Result<(), OdsError> {
+    let l = a1("B2:C3");
+    assert_eq!(l.expand(1, 2).to_a1(), "A1:E4");
+    assert_eq!(l.expand(1, 1).shrink(1, 1), l);
+    assert!(l.shrink(1, 0).is_empty());
+    assert_eq!(l.offset(2, -1)?.to_a1(), "A4:B5");
+    assert!(l.offset(-2, 0).is_err());
+
+    assert_eq!(
+        a1("B2:C3,Sheet2!A10").bounding_box(),
+        Some(CellRange::local(1, 0, 9, 2))
+    );
+    assert_eq!(CellRangeList::new().bounding_box(), None);
+
+    Ok(())
+}
+
+#[test]
+fn test_cells() {
+    let l = a1("A1:B2,B2:C2");
+    let cells = l.cells(CellOrder::RowMajor).collect::<Vec<_>>();
+    assert_eq!(
+        cells,
+        vec![
+            CellRef::local(0, 0),
+            CellRef::local(0, 1),
+            CellRef::local(1, 0),
+            CellRef::local(1, 1),
+            CellRef::local(1, 2),
+        ]
+    );
+
+    let cells = l.cells(CellOrder::ColMajor).collect::<Vec<_>>();
+    assert_eq!(
+        cells,
+        vec![
+            CellRef::local(0, 0),
+            CellRef::local(1, 0),
+            CellRef::local(0, 1),
+            CellRef::local(1, 1),
+            CellRef::local(1, 2),
+        ]
+    );
+
+    let l = a1("A1,A5,Sheet2!B2");
+    let cells = l.cells(CellOrder::RowMajor).collect::<Vec<_>>();
+    assert_eq!(
+        cells,
+        vec![
+            CellRef::local(0, 0),
+            CellRef::local(4, 0),
+            CellRef::remote("Sheet2", 1, 1),
+        ]
+    );
+
+    assert_eq!(CellRangeList::new().cells(CellOrder::RowMajor).count(), 0);
+}
+
+#[test]
+fn test_reversed() {
+    let l = a1("B3:A1");
+    assert_eq!(l.to_a1(), "A1:B3");
+    assert_eq!(l.normalize().to_a1(), "A1:B3");
+    assert_eq!(l.union(&a1("C1")).to_a1(), "A1:C1,A2:B3");
+    assert_eq!(l.intersect(&a1("B2:C3")).to_a1(), "B2:B3");
+    assert_eq!(l.subtract(&a1("A1:B2")).to_a1(), "A3:B3");
+    assert_eq!(l.cells(CellOrder::RowMajor).count(), 6);
+
+    let l = a1("$B1:A$3");
+    assert_eq!(l.to_a1(), "A1:$B$3");
+
+    let mut r = CellRange::local(0, 0, 0, 0);
+    r.set_row(2);
+    r.set_col(1);
+    let l = CellRangeList::from(r);
+    assert_eq!(l.to_a1(), "A1:B3");
+}
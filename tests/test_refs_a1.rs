@@ -0,0 +1,133 @@
+use spreadsheet_ods::refs::{
+    parse_cellrange, parse_cellrange_a1, parse_cellrange_r1c1, parse_cellref, parse_cellref_a1,
+    parse_cellref_r1c1, parse_colrange_a1, parse_colrange_r1c1, parse_rowrange_a1,
+    parse_rowrange_r1c1,
+};
+use spreadsheet_ods::{CellRange, CellRef, ColRange, OdsError, RowRange};
+
+#[test]
+fn test_a1() -> Result<(), OdsError> {
+    assert_eq!(parse_cellref_a1("A1")?, CellRef::local(0, 0));
+    assert_eq!(parse_cellref_a1("$B$3")?, CellRef::local(2, 1).absolute());
+    assert_eq!(
+        parse_cellref_a1("Sheet1!C2")?,
+        CellRef::remote("Sheet1", 1, 2)
+    );
+    let r = parse_cellref_a1("'My Sheet'!$A$1")?;
+    assert_eq!(r, CellRef::remote("My Sheet", 0, 0).absolute());
+    assert_eq!(r.to_a1(), "'My Sheet'!$A$1");
+
+    let r = parse_cellrange_a1("Sheet1!A1:B2")?;
+    assert_eq!(r, CellRange::remote("Sheet1", 0, 0, 1, 1));
+    assert_eq!(r.to_a1(), "Sheet1!A1:B2");
+    assert_eq!(parse_cellrange_a1("A1")?, CellRange::local(0, 0, 0, 0));
+
+    let r = parse_cellrange_a1("Sheet1:Sheet3!A1:B2")?;
+    assert_eq!(r.table().map(|v| v.as_str()), Some("Sheet1"));
+    assert_eq!(r.to_table().map(|v| v.as_str()), Some("Sheet3"));
+    assert_eq!(r.to_a1(), "Sheet1:Sheet3!A1:B2");
+
+    let r = parse_colrange_a1("A:A")?;
+    assert_eq!(r, ColRange::new(0, 0));
+    assert_eq!(r.to_a1(), "A:A");
+    let r = parse_colrange_a1("'it''s'!$B:$D")?;
+    assert_eq!(r.table().map(|v| v.as_str()), Some("it's"));
+    assert_eq!(r.to_a1(), "'it''s'!$B:$D");
+
+    let r = parse_rowrange_a1("1:3")?;
+    assert_eq!(r, RowRange::new(0, 2));
+    assert_eq!(r.to_a1(), "1:3");
+    assert_eq!(parse_rowrange_a1("Data!$2:$5")?.to_a1(), "Data!$2:$5");
+
+    Ok(())
+}
+
+#[test]
+fn test_a1_quoting() {
+    for (table, a1) in [
+        ("Sheet1", "Sheet1!A1"),
+        ("my_data", "my_data!A1"),
+        ("My Sheet", "'My Sheet'!A1"),
+        ("it's", "'it''s'!A1"),
+        ("2024", "'2024'!A1"),
+        ("AB12", "'AB12'!A1"),
+        ("R1C1", "'R1C1'!A1"),
+        ("rc", "'rc'!A1"),
+        ("C", "'C'!A1"),
+        ("a-b", "'a-b'!A1"),
+        ("", "''!A1"),
+    ] {
+        let r = CellRef::remote(table, 0, 0);
+        assert_eq!(r.to_a1(), a1);
+        assert_eq!(parse_cellref_a1(a1).expect(a1), r);
+    }
+}
+
+#[test]
+fn test_r1c1() -> Result<(), OdsError> {
+    assert_eq!(
+        parse_cellref_r1c1("R1C1", 5, 5)?,
+        CellRef::local(0, 0).absolute()
+    );
+    assert_eq!(parse_cellref_r1c1("RC", 5, 6)?, CellRef::local(5, 6));
+    assert_eq!(parse_cellref_r1c1("r[-1]c[2]", 5, 6)?, CellRef::local(4, 8));
+    assert_eq!(parse_cellref_r1c1("R[+1]C3", 5, 6)?, {
+        let mut r = CellRef::local(6, 2);
+        r.set_col_abs(true);
+        r
+    });
+    let r = parse_cellref_r1c1("'My Sheet'!R2C[-1]", 0, 1)?;
+    assert_eq!(r.table().map(|v| v.as_str()), Some("My Sheet"));
+    assert_eq!(r.to_r1c1(0, 1), "'My Sheet'!R2C[-1]");
+
+    let r = parse_cellrange_r1c1("R1C1:R[2]C[2]", 1, 1)?;
+    assert_eq!(r.row(), 0);
+    assert_eq!(r.to_row(), 3);
+    assert_eq!(r.to_col(), 3);
+    assert_eq!(r.to_r1c1(1, 1), "R1C1:R[2]C[2]");
+    assert_eq!(r.to_a1(), "$A$1:D4");
+
+    let r = parse_colrange_r1c1("C:C[2]", 3)?;
+    assert_eq!(r, ColRange::new(3, 5));
+    assert_eq!(r.to_r1c1(3), "C:C[2]");
+    assert_eq!(r.to_r1c1(0), "C[3]:C[5]");
+
+    let r = parse_rowrange_r1c1("R1:R3", 0)?;
+    assert_eq!(r, RowRange::new(0, 2).absolute());
+    assert_eq!(r.to_r1c1(0), "R1:R3");
+
+    // A1 and R1C1 describe the same cell.
+    let r = CellRef::local(9, 27);
+    assert_eq!(r.to_a1(), "AB10");
+    assert_eq!(r.to_r1c1(0, 0), "R[9]C[27]");
+    assert_eq!(parse_cellref_r1c1(&r.to_r1c1(3, 4), 3, 4)?, r);
+
+    Ok(())
+}
+
+#[test]
+fn test_errors() {
+    fn err(r: Result<impl std::fmt::Debug, OdsError>) -> String {
+        match r {
+            Err(OdsError::Parse(_, Some(v))) => v,
+            v => panic!("{:?}", v),
+        }
+    }
+
+    assert_eq!(err(parse_cellref_a1("A1x")), r#"at 2: "x""#);
+    assert_eq!(err(parse_cellref_a1("Sheet1!A0")), r#"at 8: "0""#);
+    assert!(parse_cellref_a1("ZZZZZZZZ1").is_err());
+    assert_eq!(err(parse_cellrange_a1("A1:B")), r#"at 2: ":B""#);
+    assert_eq!(err(parse_colrange_a1("A:")), r#"at 2: """#);
+    assert_eq!(err(parse_cellref_r1c1("R0C1", 0, 0)), r#"at 1: "0""#);
+    assert_eq!(err(parse_cellref_r1c1("R[-2]C", 1, 0)), r#"at 2: "-2""#);
+    assert_eq!(err(parse_rowrange_r1c1("R1:X", 0)), r#"at 3: "X""#);
+    assert_eq!(err(parse_cellref(".A1 x")), r#"at 3: " x""#);
+
+    // ODF notation stays as it is.
+    assert_eq!(
+        parse_cellref("$Sheet1.$A$1").unwrap().to_a1(),
+        "Sheet1!$A$1"
+    );
+    assert_eq!(parse_cellrange(".A1:.B2").unwrap().to_a1(), "A1:B2");
+}
@@ -0,0 +1,198 @@
+use spreadsheet_ods::condition::Condition;
+use spreadsheet_ods::style::stylemap::StyleMap;
+use spreadsheet_ods::style::AnyStyleRef;
+use spreadsheet_ods::validation::Validation;
+use spreadsheet_ods::workbook::RefChanges;
+use spreadsheet_ods::{
+    read_ods_buf, write_ods_buf, CellRange, CellRef, CellStyle, OdsError, Sheet, WorkBook,
+};
+
+fn setup() -> WorkBook {
+    let mut wb = WorkBook::new_empty();
+
+    let mut sheet = Sheet::new("Old");
+    sheet.set_value(0, 0, 1);
+    sheet.set_formula(0, 1, "of:=[.A1]*2");
+    wb.push_sheet(sheet);
+
+    let mut sheet = Sheet::new("Other");
+    sheet.set_formula(0, 0, "of:=[$Old.A1]+SUM([Old.A1:Old.B1])");
+    sheet.set_formula(1, 0, r#"of:=CONCATENATE("[Old.A1]";[$Old.$B$1])"#);
+    sheet.set_formula(2, 0, "of:=[$Other.A1]");
+    sheet.set_formula(3, 0, "of:=['file:///tmp/x.ods'#$Old.A1]");
+    sheet.add_print_range(CellRange::remote("Old", 0, 0, 5, 5));
+    sheet.add_print_range(CellRange::remote("Other", 0, 0, 5, 5));
+    wb.push_sheet(sheet);
+
+    let mut valid = Validation::new();
+    valid.set_condition(Condition::content_is_in_cellrange(CellRange::remote(
+        "Old", 0, 0, 10, 0,
+    )));
+    valid.set_base_cell(CellRef::remote("Other", 0, 0));
+    wb.add_validation(valid);
+
+    let mut style = CellStyle::new("highlight", &"".into());
+    style.push_stylemap(StyleMap::new(
+        Condition::is_true_formula("[$Old.A1]>0"),
+        AnyStyleRef::from("Default"),
+        Some(CellRef::remote("Old", 0, 0)),
+    ));
+    wb.add_cellstyle(style);
+
+    wb.config_mut().active_table = "Old".to_string();
+
+    wb
+}
+
+#[test]
+fn test_rename_sheet() -> Result<(), OdsError> {
+    let mut wb = setup();
+
+    let changes = wb.rename_sheet(0, "New Name")?;
+    assert_eq!(wb.sheet(0).name(), "New Name");
+    assert_eq!(
+        changes.cells,
+        vec![
+            CellRef::remote("Other", 0, 0),
+            CellRef::remote("Other", 1, 0)
+        ]
+    );
+    assert_eq!(changes.validations.len(), 1);
+    assert_eq!(changes.cellstyles.len(), 1);
+    assert_eq!(changes.print_ranges, vec!["Other".to_string()]);
+    assert_eq!(changes.invalid, 0);
+
+    let other = wb.sheet(1);
+    assert_eq!(
+        other.formula(0, 0).unwrap(),
+        "of:=[$'New Name'.A1]+SUM(['New Name'.A1:'New Name'.B1])"
+    );
+    assert_eq!(
+        other.formula(1, 0).unwrap(),
+        r#"of:=CONCATENATE("[Old.A1]";[$'New Name'.$B$1])"#
+    );
+    assert_eq!(other.formula(2, 0).unwrap(), "of:=[$Other.A1]");
+    assert_eq!(
+        other.formula(3, 0).unwrap(),
+        "of:=['file:///tmp/x.ods'#$Old.A1]"
+    );
+    assert_eq!(wb.sheet(0).formula(0, 1).unwrap(), "of:=[.A1]*2");
+    assert_eq!(
+        other.print_ranges().unwrap()[0],
+        CellRange::remote("New Name", 0, 0, 5, 5)
+    );
+    assert_eq!(wb.config().active_table, "New Name");
+
+    let style = wb.cellstyle("highlight").unwrap();
+    let stylemap = &style.stylemaps().unwrap()[0];
+    assert_eq!(
+        stylemap.condition().to_string(),
+        "is-true-formula([$'New Name'.A1]>0)"
+    );
+    assert_eq!(
+        stylemap.base_cell(),
+        Some(&CellRef::remote("New Name", 0, 0))
+    );
+
+    let valid = wb.iter_validations().next().unwrap();
+    assert!(valid.condition().to_string().contains("['New Name'.A1"));
+
+    // back again, quotes are removed.
+    wb.rename_sheet(0, "Old")?;
+    assert_eq!(
+        wb.sheet(1).formula(0, 0).unwrap(),
+        "of:=[$Old.A1]+SUM([Old.A1:Old.B1])"
+    );
+
+    // round trip
+    let buf = write_ods_buf(&mut wb, Vec::new())?;
+    let wb = read_ods_buf(&buf)?;
+    assert_eq!(wb.sheet(0).name(), "Old");
+    assert_eq!(wb.config().active_table, "Old");
+
+    Ok(())
+}
+
+#[test]
+fn test_rename_sheet_fails() {
+    let mut wb = setup();
+    assert!(wb.rename_sheet(0, "Other").is_err());
+    assert!(wb.rename_sheet(0, "").is_err());
+    assert!(wb.rename_sheet(0, "a:b").is_err());
+    assert!(wb.rename_sheet(0, "'quoted").is_err());
+    assert!(wb.rename_sheet(0, "quoted'").is_err());
+    assert!(wb.rename_sheet(0, "Old").unwrap() == Default::default());
+    assert!(wb.rename_sheet(0, "it's").is_ok());
+}
+
+#[test]
+fn test_delete_sheet() -> Result<(), OdsError> {
+    let mut wb = setup();
+
+    let changes = wb.delete_sheet(0);
+    assert_eq!(wb.num_sheets(), 1);
+    assert_eq!(changes.cells.len(), 2);
+    assert_eq!(changes.invalid, 6);
+    assert_eq!(changes.validations.len(), 1);
+
+    let other = wb.sheet(0);
+    assert_eq!(
+        other.formula(0, 0).unwrap(),
+        "of:=[$#REF!.A1]+SUM([#REF!.A1:#REF!.B1])"
+    );
+    assert_eq!(
+        other.print_ranges().unwrap(),
+        &vec![CellRange::remote("Other", 0, 0, 5, 5)]
+    );
+    assert_eq!(wb.config().active_table, "Other");
+
+    let mut wb = wb;
+    let buf = write_ods_buf(&mut wb, Vec::new())?;
+    let wb = read_ods_buf(&buf)?;
+    assert_eq!(wb.num_sheets(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_move_sheet() {
+    let mut wb = setup();
+    wb.push_sheet(Sheet::new("Third"));
+    let changes = wb.move_sheet(0, 2);
+    assert_eq!(changes, RefChanges::default());
+    assert_eq!(wb.sheet(0).name(), "Other");
+    assert_eq!(wb.sheet(1).name(), "Third");
+    assert_eq!(wb.sheet(2).name(), "Old");
+    wb.move_sheet(2, 0);
+    assert_eq!(wb.sheet(0).name(), "Old");
+    assert_eq!(wb.sheet(2).name(), "Third");
+}
+
+#[test]
+fn test_move_sheet_3d() {
+    let mut wb = WorkBook::new_empty();
+    for name in ["One", "Two", "Three", "Four"] {
+        wb.push_sheet(Sheet::new(name));
+    }
+    let sheet = wb.sheet_mut(3);
+    sheet.set_formula(0, 0, "of:=SUM([$One.A1:$Three.B2])");
+    sheet.set_formula(1, 0, "of:=SUM([$Two.A1:$Four.B2])");
+    let mut range = CellRange::remote("One", 0, 0, 5, 5);
+    range.set_to_table("Three");
+    sheet.add_print_range(range);
+
+    // One is the end of the range now.
+    let changes = wb.move_sheet(0, 3);
+    assert_eq!(changes.cells, vec![CellRef::remote("Four", 0, 0)]);
+    assert_eq!(changes.print_ranges, vec!["Four".to_string()]);
+    let sheet = wb.sheet(wb.sheet_idx("Four").unwrap());
+    assert_eq!(sheet.formula(0, 0).unwrap(), "of:=SUM([$Three.A1:$One.B2])");
+    assert_eq!(sheet.formula(1, 0).unwrap(), "of:=SUM([$Two.A1:$Four.B2])");
+    let range = &sheet.print_ranges().unwrap()[0];
+    assert_eq!(range.table().unwrap(), "Three");
+    assert_eq!(range.to_table().unwrap(), "One");
+
+    // the order doesn't change if the sheet moves within the range.
+    let changes = wb.move_sheet(1, 2);
+    assert!(changes.cells.is_empty());
+}
@@ -243,3 +243,38 @@ fn test_cell_style() {
     let ss0 = wb.cellstyle(&s0).expect("style");
     assert_eq!(ss0.name(), "a21");
 }
+
+#[test]
+fn test_split_header_at_span_end() -> Result<(), OdsError> {
+    let fods = r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0"
+    xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0"
+    office:version="1.3" office:mimetype="application/vnd.oasis.opendocument.spreadsheet">
+<office:body>
+<office:spreadsheet>
+<table:table table:name="T">
+<table:table-column table:style-name="co1" table:number-columns-repeated="3"/>
+<table:table-column table:style-name="co2"/>
+<table:table-row table:style-name="ro1" table:number-rows-repeated="3"><table:table-cell/></table:table-row>
+<table:table-row table:style-name="ro2"><table:table-cell/></table:table-row>
+</table:table>
+</office:spreadsheet>
+</office:body>
+</office:document>
+"#;
+    let mut wb = OdsOptions::default().read_fods(fods.as_bytes())?;
+    let sh = wb.sheet_mut(0);
+
+    // split at the last column/row of the span keeps the next header.
+    sh.set_colstyle(2, &"co3".into());
+    sh.set_rowstyle(2, &"ro3".into());
+
+    assert_eq!(sh.colstyle(1).map(|v| v.as_str()), Some("co1"));
+    assert_eq!(sh.colstyle(2).map(|v| v.as_str()), Some("co3"));
+    assert_eq!(sh.colstyle(3).map(|v| v.as_str()), Some("co2"));
+    assert_eq!(sh.rowstyle(1).map(|v| v.as_str()), Some("ro1"));
+    assert_eq!(sh.rowstyle(2).map(|v| v.as_str()), Some("ro3"));
+    assert_eq!(sh.rowstyle(3).map(|v| v.as_str()), Some("ro2"));
+
+    Ok(())
+}
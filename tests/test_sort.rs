@@ -0,0 +1,257 @@
+use chrono::NaiveDate;
+use icu_locid::locale;
+use spreadsheet_ods::draw::Annotation;
+use spreadsheet_ods::formula::fcellref;
+use spreadsheet_ods::sort::{SortKey, SortKeys, SortOrientation};
+use spreadsheet_ods::{CellRange, CellStyleRef, Sheet, Value};
+
+fn texts(sheet: &Sheet, col: u32, rows: std::ops::RangeInclusive<u32>) -> Vec<String> {
+    rows.map(|r| sheet.value(r, col).as_cow_str_or("").to_string())
+        .collect()
+}
+
+#[test]
+fn test_sort_types() {
+    let mut sheet = Sheet::new("one");
+    sheet.set_value(0, 0, "b");
+    sheet.set_value(1, 0, 10);
+    sheet.set_value(3, 0, "a");
+    sheet.set_value(4, 0, true);
+    sheet.set_value(
+        5,
+        0,
+        NaiveDate::from_ymd_opt(1900, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap(),
+    );
+
+    let range = CellRange::local(0, 0, 5, 0);
+    sheet
+        .sort_range(&range, &SortKeys::new().key(SortKey::new(0)))
+        .unwrap();
+    assert_eq!(sheet.value(0, 0), &Value::Boolean(true));
+    assert!(matches!(sheet.value(1, 0), Value::DateTime(_)));
+    assert_eq!(sheet.value(2, 0), &Value::Number(10.0));
+    assert_eq!(sheet.value(3, 0).as_str_or(""), "a");
+    assert_eq!(sheet.value(4, 0).as_str_or(""), "b");
+    assert_eq!(sheet.value(5, 0), &Value::Empty);
+
+    // descending, empty stays last
+    sheet
+        .sort_range(
+            &range,
+            &SortKeys::new().key(SortKey::new(0).ascending(false)),
+        )
+        .unwrap();
+    assert_eq!(sheet.value(0, 0).as_str_or(""), "b");
+    assert_eq!(sheet.value(1, 0).as_str_or(""), "a");
+    assert_eq!(sheet.value(2, 0), &Value::Number(10.0));
+    assert_eq!(sheet.value(5, 0), &Value::Empty);
+}
+
+#[test]
+fn test_sort_keys() {
+    let mut sheet = Sheet::new("one");
+    let data = [("x", 2), ("y", 1), ("z", 2), ("w", 1)];
+    sheet.set_value(0, 1, "Name");
+    sheet.set_value(0, 2, "Group");
+    for (idx, (name, group)) in data.iter().enumerate() {
+        sheet.set_value(idx as u32 + 1, 1, *name);
+        sheet.set_value(idx as u32 + 1, 2, *group);
+    }
+
+    let keys = SortKeys::new()
+        .header(true)
+        .key(SortKey::new(2).ascending(false))
+        .key(SortKey::new(1));
+    sheet
+        .sort_range(&CellRange::local(0, 1, 4, 2), &keys)
+        .unwrap();
+    assert_eq!(texts(&sheet, 1, 0..=4), ["Name", "x", "z", "w", "y"]);
+
+    // key outside of the range
+    let keys = SortKeys::new().key(SortKey::new(0));
+    assert!(sheet
+        .sort_range(&CellRange::local(0, 1, 4, 2), &keys)
+        .is_err());
+    // other sheet
+    let keys = SortKeys::new().key(SortKey::new(1));
+    assert!(sheet
+        .sort_range(&CellRange::remote("two", 0, 1, 4, 2), &keys)
+        .is_err());
+}
+
+#[test]
+fn test_sort_collation() {
+    let mut sheet = Sheet::new("one");
+    for (idx, v) in ["b", "Ä", "a", "A", "z", "äb"].iter().enumerate() {
+        sheet.set_value(idx as u32, 0, *v);
+    }
+    let range = CellRange::local(0, 0, 5, 0);
+
+    // case-insensitive is stable
+    sheet
+        .sort_range(&range, &SortKeys::new().key(SortKey::new(0)))
+        .unwrap();
+    assert_eq!(texts(&sheet, 0, 0..=5), ["a", "A", "Ä", "äb", "b", "z"]);
+
+    sheet
+        .sort_range(
+            &range,
+            &SortKeys::new().key(SortKey::new(0).match_case(true)),
+        )
+        .unwrap();
+    assert_eq!(texts(&sheet, 0, 0..=5), ["a", "A", "Ä", "äb", "b", "z"]);
+
+    sheet.set_value(0, 0, "A");
+    sheet.set_value(1, 0, "a");
+    sheet
+        .sort_range(
+            &range,
+            &SortKeys::new().key(SortKey::new(0).match_case(true)),
+        )
+        .unwrap();
+    assert_eq!(texts(&sheet, 0, 0..=5), ["a", "A", "Ä", "äb", "b", "z"]);
+
+    sheet
+        .sort_range(
+            &range,
+            &SortKeys::new()
+                .locale(locale!("sv_SE"))
+                .key(SortKey::new(0)),
+        )
+        .unwrap();
+    assert_eq!(texts(&sheet, 0, 0..=5), ["a", "A", "b", "z", "Ä", "äb"]);
+}
+
+#[test]
+fn test_sort_natural() {
+    let mut sheet = Sheet::new("one");
+    for (idx, v) in ["item10", "item9", "item1", "item09b", "10"]
+        .iter()
+        .enumerate()
+    {
+        sheet.set_value(idx as u32, 0, *v);
+    }
+    let range = CellRange::local(0, 0, 4, 0);
+
+    sheet
+        .sort_range(&range, &SortKeys::new().key(SortKey::new(0)))
+        .unwrap();
+    assert_eq!(
+        texts(&sheet, 0, 0..=4),
+        ["10", "item09b", "item1", "item10", "item9"]
+    );
+
+    sheet
+        .sort_range(&range, &SortKeys::new().key(SortKey::new(0).natural(true)))
+        .unwrap();
+    assert_eq!(
+        texts(&sheet, 0, 0..=4),
+        ["10", "item1", "item9", "item09b", "item10"]
+    );
+}
+
+#[test]
+fn test_sort_cells() {
+    let mut sheet = Sheet::new("one");
+    sheet.set_value(0, 0, 3);
+    sheet.set_value(0, 1, 30);
+    sheet.set_formula(0, 2, format!("of:={}*2", fcellref(0, 1)));
+    sheet.set_value(1, 0, 1);
+    sheet.set_value(1, 1, 10);
+    sheet.set_formula(1, 2, "of:=SUM([.A2:.B2])+[$one.B2]+[.$B$2]+[.A1]&\"[.A2]\"");
+    sheet.set_annotation(1, 1, Annotation::new("note"));
+    sheet.set_cellstyle(1, 0, &CellStyleRef::from("bold"));
+    sheet.set_value(2, 0, 2);
+    sheet.set_value(2, 5, "outside");
+
+    sheet
+        .sort_range(
+            &CellRange::local(0, 0, 2, 2),
+            &SortKeys::new().key(SortKey::new(0)),
+        )
+        .unwrap();
+
+    assert_eq!(sheet.value(0, 0), &Value::Number(1.0));
+    assert_eq!(sheet.value(0, 1), &Value::Number(10.0));
+    assert!(sheet.annotation(0, 1).is_some());
+    assert_eq!(sheet.cellstyle(0, 0), Some(&CellStyleRef::from("bold")));
+    assert_eq!(
+        sheet.formula(0, 2).unwrap(),
+        "of:=SUM([.A1:.B1])+[$one.B1]+[.$B$2]+[.A1]&\"[.A2]\""
+    );
+    assert_eq!(sheet.value(1, 0), &Value::Number(2.0));
+    assert!(sheet.annotation(1, 1).is_none());
+    assert_eq!(sheet.value(2, 0), &Value::Number(3.0));
+    assert_eq!(sheet.formula(2, 2).unwrap(), "of:=[.B3]*2");
+    assert_eq!(sheet.value(2, 5).as_str_or(""), "outside");
+
+    // merged cells
+    sheet.set_col_span(0, 0, 2);
+    assert!(sheet
+        .sort_range(
+            &CellRange::local(0, 0, 2, 2),
+            &SortKeys::new().key(SortKey::new(0)),
+        )
+        .is_err());
+}
+
+#[test]
+fn test_sort_repeat() {
+    let keys = SortKeys::new().key(SortKey::new(1));
+
+    let mut sheet = Sheet::new("one");
+    sheet.set_value(0, 0, 3);
+    sheet.set_value(1, 0, 1);
+    sheet.set_value(1, 2, "x");
+    sheet.set_cell_repeat(1, 0, 2);
+    // starts before the range, repeats into it.
+    assert!(sheet
+        .sort_range(&CellRange::local(0, 1, 2, 1), &keys)
+        .is_err());
+    // ends before the range.
+    assert!(sheet
+        .sort_range(
+            &CellRange::local(0, 2, 2, 2),
+            &SortKeys::new().key(SortKey::new(2))
+        )
+        .is_ok());
+
+    let mut sheet = Sheet::new("one");
+    sheet.set_value(0, 1, 3);
+    sheet.set_value(1, 1, 1);
+    sheet.set_row_repeat(0, 2);
+    assert!(sheet
+        .sort_range(&CellRange::local(1, 0, 2, 1), &keys)
+        .is_err());
+    assert!(sheet
+        .sort_range(&CellRange::local(2, 0, 3, 1), &keys)
+        .is_ok());
+}
+
+#[test]
+fn test_sort_columns() {
+    let mut sheet = Sheet::new("one");
+    sheet.set_value(0, 0, "Key");
+    sheet.set_value(0, 1, "c");
+    sheet.set_value(0, 2, "a");
+    sheet.set_value(0, 3, "b");
+    sheet.set_formula(1, 3, format!("of:={}", fcellref(0, 3)));
+
+    sheet
+        .sort_range(
+            &CellRange::local(0, 0, 1, 3),
+            &SortKeys::new()
+                .orientation(SortOrientation::LeftToRight)
+                .header(true)
+                .key(SortKey::new(0)),
+        )
+        .unwrap();
+    assert_eq!(sheet.value(0, 0).as_str_or(""), "Key");
+    assert_eq!(sheet.value(0, 1).as_str_or(""), "a");
+    assert_eq!(sheet.value(0, 2).as_str_or(""), "b");
+    assert_eq!(sheet.value(0, 3).as_str_or(""), "c");
+    assert_eq!(sheet.formula(1, 2).unwrap(), "of:=[.C1]");
+}
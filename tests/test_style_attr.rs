@@ -5,9 +5,9 @@ use lib_test::*;
 use spreadsheet_ods::condition::Condition;
 use spreadsheet_ods::style::stylemap::StyleMap;
 use spreadsheet_ods::style::units::{
-    Angle, Border, CellAlignVertical, FontFamilyGeneric, FontPitch, FontWeight, Length, PageBreak,
-    ParaAlignVertical, RotationAlign, TextAlignSource, TextKeep, TextPosition, TextRelief,
-    TextTransform, WrapOption, WritingMode,
+    Angle, Border, CellAlignVertical, FontFamilyGeneric, FontPitch, FontSize, FontWeight, Length,
+    LineHeight, Margin, PageBreak, ParaAlignVertical, Percent, RotationAlign, TextAlign,
+    TextAlignSource, TextKeep, TextPosition, TextRelief, TextTransform, WrapOption, WritingMode,
 };
 use spreadsheet_ods::style::{
     CellStyle, ColStyle, FontFaceDecl, PageStyle, RowStyle, StyleOrigin, StyleUse, TableStyle,
@@ -270,3 +270,118 @@ fn test_stylemap() -> Result<(), OdsError> {
 
     Ok(())
 }
+
+#[test]
+fn test_typed_getters() -> Result<(), OdsError> {
+    let mut st = CellStyle::new_empty();
+    assert_eq!(st.font_weight()?, None);
+    assert_eq!(st.border()?, None);
+
+    st.set_font_bold();
+    st.set_font_size(pt!(11));
+    st.set_color(Rgb::new(192, 128, 0));
+    st.set_background_color(Rgb::new(12, 33, 46));
+    st.set_border(pt!(1), Border::Groove, Rgb::new(99, 0, 0));
+    st.set_border_line_width_top(pt!(1), pt!(2), pt!(3));
+    st.set_text_align(TextAlign::Center);
+    st.set_vertical_align(CellAlignVertical::Middle);
+    st.set_wrap_option(WrapOption::Wrap);
+    st.set_rotation_angle(deg!(45));
+    st.set_shrink_to_fit(true);
+    st.set_margin_left(Percent::Percent(10.0).into());
+    st.set_line_height(LineHeight::Normal);
+    st.set_font_name("Liberation Sans");
+
+    assert_eq!(st.font_weight()?, Some(FontWeight::Bold));
+    assert_eq!(st.font_size()?, Some(FontSize::Length(Length::Pt(11.0))));
+    assert_eq!(st.color()?, Some(Rgb::new(192, 128, 0)));
+    assert_eq!(st.background_color()?, Some(Rgb::new(12, 33, 46)));
+    assert_eq!(
+        st.border()?,
+        Some((Length::Pt(1.0), Border::Groove, Rgb::new(99, 0, 0)))
+    );
+    assert_eq!(
+        st.border_line_width_top()?,
+        Some((Length::Pt(1.0), Length::Pt(2.0), Length::Pt(3.0)))
+    );
+    assert_eq!(st.text_align()?, Some(TextAlign::Center));
+    assert_eq!(st.vertical_align()?, Some(CellAlignVertical::Middle));
+    assert_eq!(st.wrap_option()?, Some(WrapOption::Wrap));
+    assert_eq!(st.rotation_angle()?, Some(Angle::Deg(45.0)));
+    assert_eq!(st.shrink_to_fit()?, Some(true));
+    assert_eq!(
+        st.margin_left()?,
+        Some(Margin::Percent(Percent::Percent(10.0)))
+    );
+    assert_eq!(st.line_height()?, Some(LineHeight::Normal));
+    assert_eq!(st.font_name(), Some("Liberation Sans"));
+
+    st.cellstyle_mut()
+        .set_attr("fo:background-color", "transparent".to_string());
+    assert_eq!(st.background_color()?, None);
+
+    Ok(())
+}
+
+#[test]
+fn test_typed_getters_keywords() -> Result<(), OdsError> {
+    let mut st = CellStyle::new_empty();
+    for (color, rgb) in [
+        ("#C08000", Rgb::new(192, 128, 0)),
+        ("#f80", Rgb::new(255, 136, 0)),
+        ("rgb(12, 33, 46)", Rgb::new(12, 33, 46)),
+        ("Navy", Rgb::new(0, 0, 128)),
+    ] {
+        st.textstyle_mut().set_attr("fo:color", color.to_string());
+        assert_eq!(st.color()?, Some(rgb));
+    }
+
+    st.cellstyle_mut()
+        .set_attr("fo:border", "thin solid #000000".to_string());
+    assert_eq!(
+        st.border()?,
+        Some((Length::Pt(0.75), Border::Solid, Rgb::new(0, 0, 0)))
+    );
+    st.cellstyle_mut()
+        .set_attr("fo:border", "thick double rgb(255, 0, 0)".to_string());
+    assert_eq!(
+        st.border()?,
+        Some((Length::Pt(3.75), Border::Double, Rgb::new(255, 0, 0)))
+    );
+    st.cellstyle_mut()
+        .set_attr("fo:border", "medium dashed blue".to_string());
+    assert_eq!(
+        st.border()?,
+        Some((Length::Pt(2.25), Border::Dashed, Rgb::new(0, 0, 255)))
+    );
+
+    st.textstyle_mut()
+        .set_attr("fo:color", "#12345".to_string());
+    assert!(st.color().is_err());
+    st.textstyle_mut()
+        .set_attr("fo:color", "rgb(1, 2, 300)".to_string());
+    assert!(st.color().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_typed_getters_invalid() {
+    let mut st = CellStyle::new_empty();
+    st.textstyle_mut()
+        .set_attr("fo:font-weight", "heavy".to_string());
+    st.textstyle_mut()
+        .set_attr("fo:color", "reddish".to_string());
+    st.textstyle_mut()
+        .set_attr("fo:font-size", "12".to_string());
+    st.cellstyle_mut()
+        .set_attr("fo:border", "1pt wavy #000000".to_string());
+    st.cellstyle_mut()
+        .set_attr("style:shrink-to-fit", "yes".to_string());
+
+    assert!(st.font_weight().is_err());
+    assert!(st.color().is_err());
+    assert!(st.font_size().is_err());
+    assert!(st.border().is_err());
+    assert!(st.shrink_to_fit().is_err());
+}
@@ -0,0 +1,147 @@
+use spreadsheet_ods::style::{CellStyle, MasterPage, StyleOrigin, TextStyle};
+use spreadsheet_ods::validate::{validate, IssueKind};
+use spreadsheet_ods::{
+    write_ods_buf, CellRange, CellStyleRef, OdsError, OdsOptions, Sheet, WorkBook,
+};
+use std::io::Cursor;
+
+#[test]
+fn test_validate() {
+    let mut wb = WorkBook::new_empty();
+
+    let mut style = CellStyle::new_empty();
+    style
+        .attrmap_mut()
+        .set_attr("style:parent-style-name", "nope");
+    style
+        .cellstyle_mut()
+        .set_attr("fo:background-color", "reddish");
+    // valid for the typed getters too.
+    style
+        .cellstyle_mut()
+        .set_attr("fo:border", "thin solid red");
+    style
+        .paragraphstyle_mut()
+        .set_attr("fo:text-align", "inside");
+    let style = wb.add_cellstyle(style);
+
+    let mut sh = Sheet::new("one");
+    sh.set_styled_value(0, 0, 1, &style);
+    sh.set_cellstyle(0, 1, &CellStyleRef::from("missing"));
+    sh.set_formula(1, 0, "of:=[$two.A1]+[$'three'.A1:.B2]+[.A1]");
+    sh.set_formula(1, 1, "of:=\"[$none.A1]\"");
+    sh.set_col_span(2, 0, 3);
+    sh.set_row_span(2, 1, 2);
+    sh.add_print_range(CellRange::remote("four", 0, 0, 1, 1));
+    wb.push_sheet(sh);
+    wb.push_sheet(Sheet::new("two"));
+    wb.push_sheet(Sheet::new("two"));
+
+    let issues = validate(&wb);
+    let count = |kind: IssueKind| issues.iter().filter(|v| v.kind() == kind).count();
+
+    assert_eq!(count(IssueKind::DuplicateSheetName), 1);
+    assert_eq!(count(IssueKind::DanglingStyleRef), 2);
+    assert_eq!(count(IssueKind::InvalidAttrValue), 1);
+    assert_eq!(count(IssueKind::OverlappingSpan), 1);
+    assert_eq!(count(IssueKind::MissingSheet), 2);
+
+    let missing = issues
+        .iter()
+        .find(|v| v.kind() == IssueKind::DanglingStyleRef && v.location() == "one.B1")
+        .expect("issue");
+    assert_eq!(missing.message(), "cell-style 'missing' doesn't exist");
+}
+
+#[test]
+fn test_validate_lengths() {
+    let mut wb = WorkBook::new_empty();
+
+    let mut style = CellStyle::new_empty();
+    style.paragraphstyle_mut().set_attr("fo:margin-left", "5%");
+    style
+        .paragraphstyle_mut()
+        .set_attr("fo:margin-right", "-2mm");
+    style
+        .paragraphstyle_mut()
+        .set_attr("fo:line-height", "normal");
+    style
+        .paragraphstyle_mut()
+        .set_attr("fo:text-indent", "10px");
+    style.cellstyle_mut().set_attr("fo:padding", "2px");
+    style.cellstyle_mut().set_attr("fo:margin-top", "wide");
+    wb.add_cellstyle(style);
+
+    let issues = validate(&wb);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].kind(), IssueKind::InvalidAttrValue);
+    assert!(issues[0]
+        .message()
+        .ends_with("fo:margin-top=\"wide\" is not a length or percentage"));
+}
+
+#[test]
+fn test_read_issues() -> Result<(), OdsError> {
+    let fods = r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0"
+    xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0"
+    office:version="1.3" office:mimetype="application/vnd.oasis.opendocument.spreadsheet">
+<office:body>
+<office:spreadsheet>
+<table:table table:name="T" table:unknown="x">
+<table:table-row><table:table-cell table:style-name="missing"/></table:table-row>
+</table:table>
+</office:spreadsheet>
+</office:body>
+<office:unknown/>
+</office:document>
+"#;
+
+    let wb = OdsOptions::default().read_fods(Cursor::new(fods.as_bytes()))?;
+    assert!(wb.issues().is_empty());
+
+    let wb = OdsOptions::default()
+        .collect_issues()
+        .read_fods(Cursor::new(fods.as_bytes()))?;
+    let kinds = wb.issues().iter().map(|v| v.kind()).collect::<Vec<_>>();
+    assert!(kinds.contains(&IssueKind::UnknownAttribute));
+    assert!(kinds.contains(&IssueKind::UnknownElement));
+    assert!(kinds.contains(&IssueKind::DanglingStyleRef));
+    assert!(wb.issues()[0].location().starts_with("fods:"));
+
+    // a clean file
+    let mut wb = WorkBook::new_empty();
+    let mut sh = Sheet::new("one");
+    sh.set_value(0, 0, 1);
+    wb.push_sheet(sh);
+    let buf = write_ods_buf(&mut wb, Vec::new())?;
+    let wb = OdsOptions::default()
+        .collect_issues()
+        .read_ods(Cursor::new(buf))?;
+    assert_eq!(wb.issues(), &Vec::new());
+
+    Ok(())
+}
+
+#[test]
+fn test_validate_header_styles() -> Result<(), OdsError> {
+    let mut wb = WorkBook::new_empty();
+    let bold = wb.add_textstyle(TextStyle::new("bold"));
+    let mut mp = MasterPage::new("mp1");
+    mp.header_mut().set_template("&{bold}Confidential&{}")?;
+    mp.footer_mut().set_template("&{none}x&{}")?;
+    wb.add_masterpage(mp);
+
+    let issues = validate(&wb);
+    assert_eq!(issues.len(), 2);
+    assert!(issues[0].message().contains(bold.as_str()));
+    assert!(issues[0].message().contains("styles.xml"));
+    assert_eq!(issues[1].message(), "text-style 'none' doesn't exist");
+
+    wb.textstyle_mut(&bold)
+        .expect("style")
+        .set_origin(StyleOrigin::Styles);
+    assert_eq!(validate(&wb).len(), 1);
+
+    Ok(())
+}
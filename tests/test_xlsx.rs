@@ -0,0 +1,215 @@
+use chrono::NaiveDate;
+use color::Rgb;
+use spreadsheet_ods::condition::Condition;
+use spreadsheet_ods::draw::Annotation;
+use spreadsheet_ods::style::CellStyle;
+use spreadsheet_ods::validation::Validation;
+use spreadsheet_ods::{
+    read_xlsx_buf, write_xlsx_buf, Length, OdsError, Sheet, Value, ValueFormatNumber, WorkBook,
+};
+use std::io::{Cursor, Read};
+use zip::ZipArchive;
+
+fn read_part(zip: &mut ZipArchive<Cursor<Vec<u8>>>, name: &str) -> String {
+    let mut buf = String::new();
+    zip.by_name(name)
+        .expect(name)
+        .read_to_string(&mut buf)
+        .expect(name);
+    buf
+}
+
+#[test]
+fn test_write_xlsx() -> Result<(), OdsError> {
+    let mut wb = WorkBook::new_empty();
+
+    let mut f1 = ValueFormatNumber::new_named("f1");
+    f1.part_number().fixed_decimal_places(2).build();
+    let f1 = wb.add_number_format(f1);
+
+    let mut s1 = CellStyle::new("s1", &f1);
+    s1.set_font_bold();
+    s1.set_background_color(Rgb::new(255, 255, 0));
+    let s1 = wb.add_cellstyle(s1);
+
+    let mut valid = Validation::new();
+    valid.set_condition(Condition::content_text_length_lt(5));
+    let valid = wb.add_validation(valid);
+
+    let mut sh = Sheet::new("One");
+    sh.set_value(0, 0, 1);
+    sh.set_value(0, 1, 2);
+    sh.set_value(1, 0, "text");
+    sh.set_value(1, 1, true);
+    sh.set_styled_value(2, 0, 1.5, &s1);
+    sh.set_formula(2, 1, "of:=SUM([.A1:.B2])");
+    sh.set_value(3, 0, "merged");
+    sh.set_col_span(3, 0, 2);
+    sh.set_col_width(0, Length::Pt(70.0));
+    sh.set_validation(4, 0, &valid);
+    let mut ann = Annotation::new_empty();
+    ann.push_text_str("note");
+    sh.set_annotation(0, 0, ann);
+    wb.push_sheet(sh);
+
+    let mut sh = Sheet::new("Two Words");
+    sh.set_value(0, 0, "text");
+    wb.push_sheet(sh);
+
+    let buf = write_xlsx_buf(&wb, Vec::new())?;
+    let mut zip = ZipArchive::new(Cursor::new(buf))?;
+
+    let workbook = read_part(&mut zip, "xl/workbook.xml");
+    assert!(workbook.contains(r#"<sheet name="One" sheetId="1" r:id="rId1"/>"#));
+    assert!(workbook.contains(r#"<sheet name="Two Words" sheetId="2" r:id="rId2"/>"#));
+
+    let sheet = read_part(&mut zip, "xl/worksheets/sheet1.xml");
+    assert!(sheet.contains(r#"<c r="A1"><v>1</v></c>"#));
+    assert!(sheet.contains(r#"<c r="A2" t="s"><v>0</v></c>"#));
+    assert!(sheet.contains(r#"<c r="B2" t="b"><v>1</v></c>"#));
+    assert!(sheet.contains(r#"<c r="A3" s="1"><v>1.5</v></c>"#));
+    assert!(sheet.contains("<f>SUM(A1:B2)</f>"));
+    assert!(sheet.contains(r#"<mergeCell ref="A4:B4"/>"#));
+    assert!(sheet.contains(r#"<col min="1" max="1" width="13.33203125" customWidth="1"/>"#));
+    assert!(sheet.contains(r#"type="textLength" operator="lessThan""#));
+    assert!(sheet.contains(r#"sqref="A5""#));
+    assert!(sheet.contains("<legacyDrawing r:id="));
+
+    let strings = read_part(&mut zip, "xl/sharedStrings.xml");
+    assert!(strings.contains(r#"<sst xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" count="3" uniqueCount="2">"#));
+
+    let styles = read_part(&mut zip, "xl/styles.xml");
+    assert!(styles.contains(r#"<numFmt numFmtId="164" formatCode="0.00"/>"#));
+    assert!(styles.contains("<b/>"));
+    assert!(styles.contains(r#"<fgColor rgb="FFFFFF00"/>"#));
+
+    let comments = read_part(&mut zip, "xl/comments1.xml");
+    assert!(comments.contains(r#"<comment ref="A1" authorId="0">"#));
+    assert!(comments.contains("note"));
+
+    let content_types = read_part(&mut zip, "[Content_Types].xml");
+    assert!(content_types.contains(r#"PartName="/xl/worksheets/sheet2.xml""#));
+    assert!(content_types.contains(r#"PartName="/xl/comments1.xml""#));
+
+    Ok(())
+}
+
+#[test]
+fn test_write_xlsx_blank_repeat() -> Result<(), OdsError> {
+    let mut wb = WorkBook::new_empty();
+
+    let mut s1 = CellStyle::new_empty();
+    s1.set_name("s1");
+    s1.set_font_bold();
+    let s1 = wb.add_cellstyle(s1);
+    let mut s2 = CellStyle::new_empty();
+    s2.set_name("s2");
+    s2.set_font_italic();
+    let s2 = wb.add_cellstyle(s2);
+
+    let mut sh = Sheet::new("One");
+    sh.set_value(0, 0, 1);
+    // a styled block that fills the rest of the sheet.
+    sh.set_cellstyle(1, 0, &s1);
+    sh.set_cell_repeat(1, 0, 1000);
+    sh.set_row_repeat(1, 100_000);
+    // the same style as the column.
+    sh.set_col_cellstyle(2, &s2);
+    sh.set_cellstyle(0, 2, &s2);
+    sh.set_cell_repeat(0, 2, 1);
+    wb.push_sheet(sh);
+
+    let buf = write_xlsx_buf(&wb, Vec::new())?;
+    let mut zip = ZipArchive::new(Cursor::new(buf))?;
+    let sheet = read_part(&mut zip, "xl/worksheets/sheet1.xml");
+
+    assert!(sheet.contains(r#"<col min="3" max="3" width="9.140625" style="1"/>"#));
+    assert!(sheet.contains(r#"<c r="A1"><v>1</v></c>"#));
+    assert!(!sheet.contains(r#"<c r="C1""#));
+    assert!(sheet.contains(r#"<c r="ALL2" s="2"/>"#));
+    assert!(sheet.contains(r#"<c r="C2" s="2"/>"#));
+    assert!(!sheet.contains(r#"<row r="3""#));
+
+    Ok(())
+}
+
+#[test]
+fn test_read_xlsx() -> Result<(), OdsError> {
+    let mut wb = WorkBook::new_empty();
+
+    let mut f1 = ValueFormatNumber::new_named("f1");
+    f1.part_number().fixed_decimal_places(2).build();
+    let f1 = wb.add_number_format(f1);
+
+    let mut s1 = CellStyle::new("s1", &f1);
+    s1.set_font_bold();
+    s1.set_background_color(Rgb::new(255, 255, 0));
+    let s1 = wb.add_cellstyle(s1);
+
+    let mut sh = Sheet::new("One");
+    sh.set_value(0, 0, 1);
+    sh.set_value(0, 1, 2);
+    sh.set_value(1, 0, "text");
+    sh.set_value(1, 1, true);
+    sh.set_styled_value(2, 0, 1.5, &s1);
+    sh.set_formula(2, 1, "of:=SUM([.A1:.B2])+[$'Two Words'.A1]");
+    sh.set_formula(2, 2, "of:=SUM({1;2|3;4})");
+    sh.set_value(3, 0, "merged");
+    sh.set_col_span(3, 0, 2);
+    sh.set_value(
+        4,
+        0,
+        NaiveDate::from_ymd_opt(2024, 2, 29)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap(),
+    );
+    wb.push_sheet(sh);
+
+    let mut sh = Sheet::new("Two Words");
+    sh.set_value(0, 0, "text");
+    wb.push_sheet(sh);
+
+    let buf = write_xlsx_buf(&wb, Vec::new())?;
+    let wb = read_xlsx_buf(&buf)?;
+
+    assert_eq!(wb.num_sheets(), 2);
+    let sh = wb.sheet(0);
+    assert_eq!(sh.name(), "One");
+    assert_eq!(sh.value(0, 0), &Value::Number(1.0));
+    assert_eq!(sh.value(1, 0), &Value::Text("text".to_string()));
+    assert_eq!(sh.value(1, 1), &Value::Boolean(true));
+    assert_eq!(sh.value(2, 0), &Value::Number(1.5));
+    assert_eq!(
+        sh.formula(2, 1).map(|v| v.as_str()),
+        Some("of:=SUM([.A1:.B2])+[$'Two Words'.A1]")
+    );
+    assert_eq!(
+        sh.formula(2, 2).map(|v| v.as_str()),
+        Some("of:=SUM({1;2|3;4})")
+    );
+    assert_eq!(sh.col_span(3, 0), 2);
+    assert_eq!(
+        sh.value(4, 0),
+        &Value::DateTime(
+            NaiveDate::from_ymd_opt(2024, 2, 29)
+                .unwrap()
+                .and_hms_opt(12, 0, 0)
+                .unwrap()
+        )
+    );
+
+    let style = wb
+        .cellstyle(sh.cellstyle(2, 0).expect("style").as_str())
+        .expect("style");
+    assert_eq!(style.textstyle().attr("fo:font-weight"), Some("bold"));
+    assert_eq!(
+        style.cellstyle().attr("fo:background-color"),
+        Some("#ffff00")
+    );
+
+    assert_eq!(wb.sheet(1).name(), "Two Words");
+    assert_eq!(wb.sheet(1).value(0, 0), &Value::Text("text".to_string()));
+
+    Ok(())
+}